@@ -19,11 +19,12 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, InverseGamma, Normal, StudentsT};
 use std::fmt;
 
 /// Result of Bayesian linear regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BayesianLinearResult {
     /// Posterior mean of coefficients
     pub beta: Array1<f64>,
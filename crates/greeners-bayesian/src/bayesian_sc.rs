@@ -21,11 +21,12 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
 use std::fmt;
 
 /// Result of Bayesian Synthetic Control estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BayesianScResult {
     /// Posterior mean of treatment effect (tau)
     pub tau: f64,
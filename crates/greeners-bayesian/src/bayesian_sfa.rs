@@ -14,11 +14,13 @@
 
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::GreenersRng;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Bayesian stochastic frontier estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BayesianSfaResult {
     /// Posterior mean of coefficients (intercept + beta)
     pub beta: Array1<f64>,
@@ -98,25 +100,31 @@ impl BayesianSFA {
     /// Estimate Bayesian stochastic production frontier via MCMC.
     ///
     /// y = α + β'x + v - u,  v ~ N(0, σ_v²),  u ~ half-normal(σ_u²)
+    ///
+    /// `seed: None` uses the default seed.
     pub fn fit_production(
         y: &Array1<f64>,
         x: &Array2<f64>,
         variable_names: Option<Vec<String>>,
         n_burn: usize,
         n_draws: usize,
+        seed: Option<u64>,
     ) -> Result<BayesianSfaResult, GreenersError> {
-        Self::fit(y, x, variable_names, "production", n_burn, n_draws)
+        Self::fit(y, x, variable_names, "production", n_burn, n_draws, seed)
     }
 
     /// Estimate Bayesian stochastic cost frontier via MCMC.
+    ///
+    /// `seed: None` uses the default seed.
     pub fn fit_cost(
         y: &Array1<f64>,
         x: &Array2<f64>,
         variable_names: Option<Vec<String>>,
         n_burn: usize,
         n_draws: usize,
+        seed: Option<u64>,
     ) -> Result<BayesianSfaResult, GreenersError> {
-        Self::fit(y, x, variable_names, "cost", n_burn, n_draws)
+        Self::fit(y, x, variable_names, "cost", n_burn, n_draws, seed)
     }
 
     fn fit(
@@ -126,6 +134,7 @@ impl BayesianSFA {
         model_type: &str,
         n_burn: usize,
         n_draws: usize,
+        seed: Option<u64>,
     ) -> Result<BayesianSfaResult, GreenersError> {
         let n = y.len();
         if x.nrows() != n {
@@ -157,8 +166,7 @@ impl BayesianSFA {
         let mut sigma_u2_draws: Vec<f64> = Vec::with_capacity(n_draws);
         let mut efficiency_draws: Vec<f64> = Vec::with_capacity(n_draws);
 
-        // Simple LCG for reproducibility (no external rand dependency needed)
-        let mut rng = SimpleRng::new(42);
+        let mut rng = SimpleRng::new(seed);
 
         let total_iters = n_burn + n_draws;
         for iter in 0..total_iters {
@@ -275,37 +283,24 @@ impl BayesianSFA {
     }
 }
 
-/// Simple deterministic RNG for reproducible MCMC (xorshift).
+/// Samplers used by the Gibbs steps, drawing from a seeded [`GreenersRng`].
 struct SimpleRng {
-    state: u64,
+    rng: GreenersRng,
 }
 
 impl SimpleRng {
-    fn new(seed: u64) -> Self {
+    fn new(seed: Option<u64>) -> Self {
         Self {
-            state: if seed == 0 { 1 } else { seed },
+            rng: GreenersRng::new(seed),
         }
     }
 
-    fn next_u64(&mut self) -> u64 {
-        let mut x = self.state;
-        x ^= x << 13;
-        x ^= x >> 7;
-        x ^= x << 17;
-        self.state = x;
-        x
-    }
-
     fn uniform(&mut self) -> f64 {
-        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+        self.rng.uniform()
     }
 
     fn normal(&mut self, mean: f64, sd: f64) -> f64 {
-        // Box-Muller
-        let u1 = self.uniform().max(1e-10);
-        let u2 = self.uniform();
-        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
-        mean + sd * z
+        mean + sd * self.rng.normal()
     }
 
     fn truncated_normal(&mut self, mean: f64, sd: f64, lower: f64) -> f64 {
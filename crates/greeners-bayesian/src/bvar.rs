@@ -16,13 +16,15 @@
 //! posterior is Normal. Closed-form posterior mean and covariance.
 
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
+use greeners_core::var_dynamics::VarDynamics;
 use greeners_core::GreenersError;
-use ndarray::{Array1, Array2};
+use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of BVAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BvarResult {
     /// Posterior mean coefficients (k x (k*p)), each row = equation
     pub coeffs: Array2<f64>,
@@ -46,6 +48,54 @@ pub struct BvarResult {
     pub lags: usize,
     /// Variable names
     pub var_names: Vec<String>,
+    /// Estimation sample (T x k)
+    #[serde(default)]
+    pub data: Array2<f64>,
+    /// Residuals at the posterior mean for observations `lags..T`
+    #[serde(default)]
+    pub residuals: Array2<f64>,
+}
+
+/// Forecasts and bootstrap bands at the posterior mean. The bootstrap
+/// re-estimates the posterior with the same hyperparameters.
+impl VarDynamics for BvarResult {
+    fn var_names(&self) -> Vec<String> {
+        self.var_names.clone()
+    }
+
+    fn intercept(&self) -> Array1<f64> {
+        Array1::zeros(self.n_vars)
+    }
+
+    fn lag_matrices(&self) -> Vec<Array2<f64>> {
+        let k = self.n_vars;
+        (0..self.lags)
+            .map(|l| self.coeffs.slice(s![.., l * k..(l + 1) * k]).to_owned())
+            .collect()
+    }
+
+    fn innovation_cov(&self) -> Array2<f64> {
+        self.resid_cov.clone()
+    }
+
+    fn sample(&self) -> &Array2<f64> {
+        &self.data
+    }
+
+    fn innovations(&self) -> &Array2<f64> {
+        &self.residuals
+    }
+
+    fn refit(&self, data: &Array2<f64>) -> Result<Self, GreenersError> {
+        BVAR::fit(
+            data,
+            self.lags,
+            Some(self.hyperparams[0]),
+            Some(self.hyperparams[1]),
+            Some(self.hyperparams[2]),
+            Some(self.var_names.clone()),
+        )
+    }
 }
 
 impl fmt::Display for BvarResult {
@@ -284,6 +334,8 @@ impl BVAR {
             n_vars: k,
             lags,
             var_names: names,
+            data: y.to_owned(),
+            residuals: post_resid,
         })
     }
 }
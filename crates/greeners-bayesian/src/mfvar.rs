@@ -15,11 +15,12 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of MF-VAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MfVarResult {
     /// VAR coefficients (k x (k*p)), each row = equation
     pub coeffs: Array2<f64>,
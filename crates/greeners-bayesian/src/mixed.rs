@@ -2,11 +2,12 @@ use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::ContinuousCDF;
 use std::fmt;
 
 /// Result of Mixed Linear Model estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MixedResult {
     pub fixed_effects: Array1<f64>,
     pub fixed_se: Array1<f64>,
@@ -318,7 +319,7 @@ impl MixedLM {
 }
 
 /// Result of Bayesian Mixed GLM estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BayesMixedGLMResult {
     pub posterior_mean: Array1<f64>,
     pub posterior_sd: Array1<f64>,
@@ -29,7 +29,7 @@ fn make_sfa_data(n: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
 #[test]
 fn test_bayesian_sfa_shape_and_finite() {
     let (y, x) = make_sfa_data(40, 9001);
-    let r = BayesianSFA::fit_production(&y, &x, None, 20, 50).unwrap();
+    let r = BayesianSFA::fit_production(&y, &x, None, 20, 50, None).unwrap();
     assert_eq!(r.n_obs, y.len());
     assert_eq!(r.beta.len(), x.ncols());
     assert!(r.beta.iter().all(|v| v.is_finite()));
@@ -44,7 +44,7 @@ fn test_bayesian_sfa_shape_and_finite() {
 #[test]
 fn test_bayesian_sfa_cost_type() {
     let (y, x) = make_sfa_data(40, 9002);
-    let r = BayesianSFA::fit_cost(&y, &x, None, 20, 50).unwrap();
+    let r = BayesianSFA::fit_cost(&y, &x, None, 20, 50, None).unwrap();
     assert_eq!(r.model_type, "cost");
     assert!(r.beta.len() == x.ncols());
     assert!(r.n_draws > 0);
@@ -55,5 +55,5 @@ fn test_bayesian_sfa_cost_type() {
 fn test_bayesian_sfa_input_validation() {
     let (y, _x) = make_sfa_data(20, 9003);
     let x_bad = Array2::from_shape_vec((25, 2), vec![0.0; 50]).unwrap();
-    assert!(BayesianSFA::fit_production(&y, &x_bad, None, 10, 30).is_err());
+    assert!(BayesianSFA::fit_production(&y, &x_bad, None, 10, 30, None).is_err());
 }
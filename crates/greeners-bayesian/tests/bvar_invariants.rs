@@ -1,5 +1,6 @@
 use greeners_bayesian::bvar::BVAR;
 use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::var_dynamics::{IrfKind, VarBootstrapConfig, VarDynamics};
 use ndarray::{Array1, Array2};
 use rand::distributions::Distribution;
 use rand::{rngs::StdRng, SeedableRng};
@@ -108,3 +109,47 @@ fn test_bvar_input_validation() {
     assert!(BVAR::fit(&data, 0, None, None, None, None).is_err());
     assert!(BVAR::fit(&data, 2, None, None, None, None).is_err());
 }
+
+/// Forecasts, bootstrap IRF bands and the historical decomposition work from
+/// the posterior-mean VAR (no intercept).
+#[test]
+fn test_bvar_forecast_and_decomposition() {
+    let a = Array2::from_shape_vec((2, 2), vec![0.5, 0.1, 0.2, 0.3]).unwrap();
+    let y0 = Array1::from(vec![1.0, -1.0]);
+    let data = generate_var1_with_noise(&a, &y0, 150, 1.0, 9);
+    let result = BVAR::fit(&data, 2, None, None, None, None).unwrap();
+
+    let fc = result.forecast(2, 0.05).unwrap();
+    let a1 = result.coeffs.slice(ndarray::s![.., 0..2]).to_owned();
+    let a2 = result.coeffs.slice(ndarray::s![.., 2..4]).to_owned();
+    let step1 = a1.dot(&data.row(149)) + a2.dot(&data.row(148));
+    let step2 = a1.dot(&step1) + a2.dot(&data.row(149));
+    for i in 0..2 {
+        approx_zero(fc.mean[[0, i]] - step1[i], 1e-10);
+        approx_zero(fc.mean[[1, i]] - step2[i], 1e-10);
+        approx_zero(
+            fc.std_errors[[0, i]] - result.resid_cov[[i, i]].sqrt(),
+            1e-10,
+        );
+    }
+
+    let total = result.historical_decomposition().unwrap().total();
+    for t in 0..148 {
+        for i in 0..2 {
+            approx_zero(total[[t, i]] - data[[t + 2, i]], 1e-8);
+        }
+    }
+
+    let config = VarBootstrapConfig {
+        n_bootstrap: 49,
+        seed: Some(1),
+        ..VarBootstrapConfig::default()
+    };
+    let bands = result.irf_bands(5, IrfKind::Generalized, &config).unwrap();
+    assert_eq!(bands.n_bootstrap, 49);
+    assert!(bands
+        .lower
+        .iter()
+        .zip(bands.upper.iter())
+        .all(|(l, u)| l <= u));
+}
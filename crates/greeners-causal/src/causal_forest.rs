@@ -14,12 +14,13 @@
 //! Requires: treatment indicator T, outcome Y, features X.
 //! Assumes: unconfoundedness, overlap, SUTVA.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single causal tree node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct CausalNode {
     feature: usize,
@@ -38,7 +39,7 @@ struct CausalNode {
 }
 
 /// Result of Causal Forest estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CausalForestResult {
     /// Predicted treatment effect for each observation (n)
     pub treatment_effects: Array1<f64>,
@@ -62,6 +63,36 @@ pub struct CausalForestResult {
     pub n_features: usize,
     /// Variable names
     pub variable_names: Vec<String>,
+    /// Fitted causal trees
+    forest: Vec<CausalNode>,
+}
+
+impl CausalForestResult {
+    /// Predicted treatment effects (CATE) for new observations, averaged
+    /// over the trees of the forest.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "CausalForest: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut effects = Array1::zeros(x.nrows());
+        if self.forest.is_empty() {
+            return Ok(effects);
+        }
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            let total: f64 = self
+                .forest
+                .iter()
+                .map(|tree| CausalForest::predict_te(tree, &row))
+                .sum();
+            effects[i] = total / self.forest.len() as f64;
+        }
+        Ok(effects)
+    }
 }
 
 impl fmt::Display for CausalForestResult {
@@ -138,6 +169,7 @@ impl CausalForest {
     /// * `n_trees` - Number of trees (default 100)
     /// * `max_depth` - Max tree depth (default 5)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         t: &[bool],
@@ -145,6 +177,7 @@ impl CausalForest {
         n_trees: Option<usize>,
         max_depth: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<CausalForestResult, GreenersError> {
         let n = y.len();
         let k = x.ncols();
@@ -173,23 +206,18 @@ impl CausalForest {
         let mtry = (k as f64).sqrt().ceil() as usize;
         let mtry = mtry.max(1).min(k);
 
+        let mut rng = GreenersRng::new(seed);
         let mut forest: Vec<CausalNode> = Vec::with_capacity(trees);
         let mut feature_importance = Array1::zeros(k);
         let mut te_preds: Vec<Vec<f64>> = vec![Vec::new(); n]; // per-obs predictions
 
         for _ in 0..trees {
             // Bootstrap sample
-            let mut boot_indices = Vec::with_capacity(n);
-            for _ in 0..n {
-                boot_indices.push(Self::rand_int(n));
-            }
+            let boot_indices = rng.bootstrap_indices(n);
 
             // Honest splitting: split boot sample into two halves
             let mut split_idx = boot_indices.clone();
-            for i in 0..split_idx.len() {
-                let j = i + Self::rand_int(split_idx.len() - i);
-                split_idx.swap(i, j);
-            }
+            rng.shuffle(&mut split_idx);
             let mid = split_idx.len() / 2;
             let split_half = &split_idx[..mid];
             let est_half = &split_idx[mid..];
@@ -206,6 +234,7 @@ impl CausalForest {
                 k,
                 0,
                 &mut feature_importance,
+                &mut rng,
             );
 
             // Predict for all observations
@@ -253,6 +282,7 @@ impl CausalForest {
             n_obs: n,
             n_features: k,
             variable_names: names,
+            forest,
         })
     }
 
@@ -269,6 +299,7 @@ impl CausalForest {
         k: usize,
         depth: usize,
         importance: &mut Array1<f64>,
+        rng: &mut GreenersRng,
     ) -> CausalNode {
         // Compute leaf effect from estimation half
         let (effect, var, n_t, n_c) = Self::compute_leaf_effect(y, t, est_idx);
@@ -288,7 +319,7 @@ impl CausalForest {
         }
 
         let (best_feature, best_threshold, best_gain) =
-            Self::find_best_causal_split(y, t, x, split_idx, mtry, k);
+            Self::find_best_causal_split(y, t, x, split_idx, mtry, k, rng);
 
         if best_gain < 1e-10 || best_feature >= k {
             return CausalNode {
@@ -359,6 +390,7 @@ impl CausalForest {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             right: Some(Box::new(Self::build_tree(
                 y,
@@ -371,6 +403,7 @@ impl CausalForest {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             leaf_effect: effect,
             leaf_n_treated: n_t,
@@ -434,14 +467,12 @@ impl CausalForest {
         indices: &[usize],
         mtry: usize,
         k: usize,
+        rng: &mut GreenersRng,
     ) -> (usize, f64, f64) {
         let (parent_effect, parent_var, _, _) = Self::compute_leaf_effect(y, t, indices);
 
         let mut features: Vec<usize> = (0..k).collect();
-        for i in 0..features.len() {
-            let j = i + Self::rand_int(features.len() - i);
-            features.swap(i, j);
-        }
+        rng.shuffle(&mut features);
         let features = &features[..mtry.min(features.len())];
 
         let mut best_feature = k;
@@ -512,26 +543,4 @@ impl CausalForest {
         }
         tree.leaf_effect
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1618033988) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
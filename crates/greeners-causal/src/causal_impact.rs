@@ -22,11 +22,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of Causal Impact estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CausalImpactResult {
     /// Observed y (n)
     pub y: Array1<f64>,
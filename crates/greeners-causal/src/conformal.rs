@@ -19,12 +19,13 @@
 //! conformal wrapper is model-agnostic.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Conformal Prediction.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConformalResult {
     /// Point predictions (n_test)
     pub predictions: Array1<f64>,
@@ -134,6 +135,7 @@ impl ConformalPrediction {
     /// * `alpha` - Miscoverage level (default 0.1 for 90% coverage)
     /// * `calib_fraction` - Fraction of data for calibration (default 0.3)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -141,7 +143,9 @@ impl ConformalPrediction {
         alpha: Option<f64>,
         calib_fraction: Option<f64>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<ConformalResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         let n_test = x_test.nrows();
@@ -168,10 +172,7 @@ impl ConformalPrediction {
 
         // Split into training and calibration
         let mut indices: Vec<usize> = (0..n).collect();
-        for i in 0..n {
-            let j = i + Self::rand_int(n - i);
-            indices.swap(i, j);
-        }
+        rng.shuffle(&mut indices);
         let n_calib = (n as f64 * calib_frac).round() as usize;
         let n_calib = n_calib.max(5).min(n - 5);
         let n_train = n - n_calib;
@@ -268,26 +269,4 @@ impl ConformalPrediction {
         }
         pred
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1732050807) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -17,11 +17,12 @@
 
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of CUPED estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CupedResult {
     /// CUPED-adjusted treatment effect
     pub treatment_effect: f64,
@@ -1,10 +1,11 @@
 use greeners_core::{CovarianceType, DataFrame, Formula, GreenersError};
 use greeners_ols::ols::OLS;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of the Difference-in-Differences estimator (Canonical 2x2)
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DidResult {
     pub att: f64,       // The treatment effect (Interaction coefficient)
     pub std_error: f64, // ATT standard error
@@ -20,13 +20,14 @@
 //! This avoids the "own observation" bias of standard DML.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of DML cross-fitting estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DmlResult {
     /// Causal effect estimate (theta)
     pub theta: f64,
@@ -84,12 +85,15 @@ impl DML {
     /// * `d` - Treatment variable (n)
     /// * `x` - Confounders (n x k)
     /// * `n_folds` - Number of cross-fitting folds (default 5)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         d: &Array1<f64>,
         x: &Array2<f64>,
         n_folds: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<DmlResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if d.len() != n || x.nrows() != n {
@@ -107,10 +111,7 @@ impl DML {
 
         // Create fold assignments (shuffle then split)
         let mut indices: Vec<usize> = (0..n).collect();
-        for i in 0..n {
-            let j = i + Self::rand_int(n - i);
-            indices.swap(i, j);
-        }
+        rng.shuffle(&mut indices);
         let fold_size = n / folds;
         let fold_assignment: Vec<usize> = (0..n)
             .map(|i| (i / fold_size.max(1)).min(folds - 1))
@@ -231,26 +232,4 @@ impl DML {
         }
         pred
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(2718281828) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
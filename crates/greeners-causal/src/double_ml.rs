@@ -19,11 +19,12 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of double/debiased ML estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DoubleMLResult {
     /// Estimated treatment effect (theta)
     pub theta: f64,
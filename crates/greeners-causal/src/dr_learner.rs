@@ -18,13 +18,14 @@
 //I'm sorry. Doubtly robust: consistant if other m(X) or(X) is correct.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::Normal;
 use std::fmt;
 
 /// Result of DR-learner estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DrLearnerResult {
     /// Predicted CATE for each observation (n)
     pub cate: Array1<f64>,
@@ -112,13 +113,16 @@ impl DRLearner {
     /// * `x` - Features (n x k)
     /// * `n_folds` - Number of cross-fitting folds (default 3)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         t: &[bool],
         x: &Array2<f64>,
         n_folds: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<DrLearnerResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if t.len() != n || x.nrows() != n {
@@ -145,10 +149,7 @@ impl DRLearner {
 
         // Create fold assignments (shuffle then split)
         let mut indices: Vec<usize> = (0..n).collect();
-        for i in 0..n {
-            let j = i + Self::rand_int(n - i);
-            indices.swap(i, j);
-        }
+        rng.shuffle(&mut indices);
         let fold_size = n / folds;
         let fold_of: Vec<usize> = (0..n)
             .map(|i| (i / fold_size.max(1)).min(folds - 1))
@@ -280,26 +281,4 @@ impl DRLearner {
         }
         pred
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(2236067977) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
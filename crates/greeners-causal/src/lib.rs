@@ -17,6 +17,10 @@ pub use rd::{RdKernel, RdResult, RD};
 pub use synth::{SynthResult, SyntheticControl};
 pub use synth_did::{SyntheticDiD, SyntheticDidResult};
 pub use tmle::{TmleResult, TMLE};
+pub use twfe_diagnostics::{
+    BaconComparison, BaconComparisonType, BaconDecomposition, BaconResult, TwfeCellWeight,
+    TwfeWeights, TwfeWeightsResult,
+};
 
 pub mod causal_forest;
 pub mod causal_impact;
@@ -32,3 +36,4 @@ pub mod rd;
 pub mod synth;
 pub mod synth_did;
 pub mod tmle;
+pub mod twfe_diagnostics;
@@ -15,12 +15,12 @@
 
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::{LinalgInverse, LinalgPinv};
-use greeners_core::{Column, CovarianceType, DataFrame};
+use greeners_core::{Column, CovarianceType, DataFrame, GreenersRng};
 use greeners_ols::ols::OLS;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2, Axis};
-use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::collections::HashMap;
 use std::fmt;
@@ -30,7 +30,7 @@ use std::fmt;
 // -----------------------------------------------------------------------------
 
 /// Result of an LP-DiD estimation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LpDidResult {
     /// Horizon values (relative to treatment)
     pub horizons: Vec<i64>,
@@ -206,7 +206,7 @@ pub struct LpDid {
     switch_in: String,
     inference: String,
     n_bootstrap: usize,
-    seed: u64,
+    seed: Option<u64>,
 }
 
 impl Default for LpDid {
@@ -229,7 +229,7 @@ impl Default for LpDid {
             switch_in: "sustained".to_string(),
             inference: "cluster".to_string(),
             n_bootstrap: 999,
-            seed: 0,
+            seed: None,
         }
     }
 }
@@ -340,9 +340,9 @@ impl LpDid {
         self
     }
 
-    /// Random seed for bootstrap resampling.
+    /// Random seed for bootstrap resampling (the default seed is used if unset).
     pub fn with_seed(mut self, seed: u64) -> Self {
-        self.seed = seed;
+        self.seed = Some(seed);
         self
     }
 
@@ -721,7 +721,7 @@ impl LpDid {
             false,
         )?;
 
-        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut rng = GreenersRng::new(self.seed);
         let mut event_draws: HashMap<i64, Vec<f64>> = HashMap::new();
         let mut scalar_draws: HashMap<String, Vec<f64>> = HashMap::new();
 
@@ -2378,7 +2378,7 @@ fn se_from_influence(psi: &[f64]) -> f64 {
 fn resample_clusters(
     df: &DataFrame,
     unit: &str,
-    rng: &mut StdRng,
+    rng: &mut GreenersRng,
 ) -> Result<DataFrame, GreenersError> {
     let unit_col = df.get_column(unit)?;
     let unit_f64 = column_to_f64(unit_col, unit)?;
@@ -2406,7 +2406,7 @@ fn resample_clusters(
     let mut selected: Vec<usize> = Vec::with_capacity(df.n_rows());
     let mut new_unit: Vec<f64> = Vec::with_capacity(df.n_rows());
     for b in 0..g {
-        let sampled = rng.gen_range(0..g);
+        let sampled = rng.index(g);
         for &idx in &cluster_indices[sampled] {
             selected.push(idx);
             new_unit.push(b as f64);
@@ -1,12 +1,14 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::GreenersRng;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 //── Result Structs ──────────────────────────────────────────────────────
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BalanceRow {
     pub covariate: String,
     pub mean_treated: f64,
@@ -18,7 +20,7 @@ pub struct BalanceRow {
     pub smd_after: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PsmResult {
     /// Mean treatment effect in treatment (ATT).
     pub att: f64,
@@ -159,6 +161,7 @@ impl PSM {
     /// * `with_replacement`— reposição no matching (padrão false)
     /// * `n_boot`          — replicações bootstrap para SE (padrão 200)
     /// * `variable_names`  — (outcome, treatment, covariates)
+    /// * `seed`            — semente do bootstrap (`None` usa a semente padrão)
     #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
@@ -169,6 +172,7 @@ impl PSM {
         with_replacement: bool,
         n_boot: usize,
         variable_names: Option<(String, String, Vec<String>)>,
+        seed: Option<u64>,
     ) -> Result<PsmResult, GreenersError> {
         let n = y.len();
         if d.len() != n || x.nrows() != n {
@@ -210,7 +214,7 @@ impl PSM {
         }
 
         // ── 5. Bootstrap SE ───────────────────────────────────────────────────
-        let se = bootstrap_se(y, d, &x_aug, k, caliper, with_replacement, n_boot);
+        let se = bootstrap_se(y, d, &x_aug, k, caliper, with_replacement, n_boot, seed);
 
         //── 6. Inference ─────────────────────────────────────────────────────
         let z = att / se;
@@ -386,6 +390,7 @@ fn compute_att(y: &Array1<f64>, pairs: &[(usize, Vec<usize>)]) -> f64 {
 
 // ── Bootstrap SE ─────────────────────────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn bootstrap_se(
     y: &Array1<f64>,
     d: &Array1<f64>,
@@ -394,13 +399,14 @@ fn bootstrap_se(
     caliper: Option<f64>,
     with_replacement: bool,
     n_boot: usize,
+    seed: Option<u64>,
 ) -> f64 {
     let n = y.len();
     let mut att_boot: Vec<f64> = Vec::with_capacity(n_boot);
-    let mut state = 0x123456789abcdef0u64;
+    let mut rng = GreenersRng::new(seed);
 
     for _ in 0..n_boot {
-        let idx: Vec<usize> = (0..n).map(|_| lcg_next(&mut state) % n).collect();
+        let idx = rng.bootstrap_indices(n);
 
         let y_b: Array1<f64> = idx.iter().map(|&i| y[i]).collect::<Vec<_>>().into();
         let d_b: Array1<f64> = idx.iter().map(|&i| d[i]).collect::<Vec<_>>().into();
@@ -443,13 +449,6 @@ fn bootstrap_se(
     var.sqrt()
 }
 
-fn lcg_next(s: &mut u64) -> usize {
-    *s = s
-        .wrapping_mul(6_364_136_223_846_793_005)
-        .wrapping_add(1_442_695_040_888_963_407);
-    (*s >> 33) as usize
-}
-
 //── Balance sheet ─────────────────────────────────────────────────────────
 
 fn compute_balance(
@@ -1,12 +1,13 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 // ── Kernel ───────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum RdKernel {
     #[default]
     Triangular,
@@ -42,7 +43,7 @@ impl fmt::Display for RdKernel {
 
 // ── RdResult ─────────────────────────────────────────────────────────────────
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RdResult {
     pub tau: f64,
     pub se: f64,
@@ -2,11 +2,12 @@ use greeners_core::error::GreenersError;
 use greeners_core::DataFrame;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 // ── SynthResult ───────────────────────────────────────────────────────────────
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SynthResult {
     /// Pesos dos doadores: (id_string, peso). Inclui pesos ≈ 0.
     pub weights: Vec<(String, f64)>,
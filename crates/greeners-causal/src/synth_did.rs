@@ -20,11 +20,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of Synthetic DiD estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SyntheticDidResult {
     /// Synthetic DiD estimate (ATT)
     pub att: f64,
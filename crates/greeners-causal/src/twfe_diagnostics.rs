@@ -0,0 +1,665 @@
+//! Diagnostics for two-way fixed effects (TWFE) difference-in-differences.
+//!
+//! With staggered or heterogeneous treatment, the TWFE coefficient on a
+//! treatment dummy is a weighted average of simpler comparisons, some of
+//! which may carry perverse weights:
+//!
+//! - [`BaconDecomposition`] (Goodman-Bacon 2021) splits the coefficient of a
+//!   balanced panel with absorbing treatment into all 2×2 DiD comparisons
+//!   between timing groups, never-treated and always-treated units.
+//! - [`TwfeWeights`] (de Chaisemartin & D'Haultfœuille 2020) writes it as a
+//!   weighted sum of the treatment effects of treated (group, period) cells,
+//!   reporting the negative weights and how much effect heterogeneity would
+//!   be needed for the coefficient to mislead.
+
+use greeners_core::{DataFrame, GreenersError};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Kind of 2×2 comparison in a Bacon decomposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BaconComparisonType {
+    /// Timing group against never-treated units
+    TreatedVsNever,
+    /// Timing group against units treated in every period
+    TreatedVsAlways,
+    /// Earlier group treated, later group not yet treated as control
+    EarlierVsLater,
+    /// Later group treated, already-treated earlier group as control
+    LaterVsEarlier,
+}
+
+impl fmt::Display for BaconComparisonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BaconComparisonType::TreatedVsNever => "Treated vs never treated",
+            BaconComparisonType::TreatedVsAlways => "Treated vs always treated",
+            BaconComparisonType::EarlierVsLater => "Earlier vs later treated",
+            BaconComparisonType::LaterVsEarlier => "Later vs earlier treated",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One 2×2 comparison of a Bacon decomposition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaconComparison {
+    pub kind: BaconComparisonType,
+    /// First treated period of the group acting as treated
+    pub treated: i64,
+    /// First treated period of the control group, `None` if untimed
+    pub control: Option<i64>,
+    /// 2×2 DiD estimate
+    pub estimate: f64,
+    /// Weight in the TWFE coefficient
+    pub weight: f64,
+}
+
+/// Result of [`BaconDecomposition`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaconResult {
+    /// TWFE coefficient on the treatment dummy
+    pub twfe_coef: f64,
+    pub comparisons: Vec<BaconComparison>,
+    pub n_obs: usize,
+    pub n_units: usize,
+    pub n_periods: usize,
+    pub n_timing_groups: usize,
+    pub n_never_treated: usize,
+    pub n_always_treated: usize,
+}
+
+impl BaconResult {
+    /// Total weight and weighted average estimate of each kind of
+    /// comparison present.
+    pub fn by_type(&self) -> Vec<(BaconComparisonType, f64, f64)> {
+        let kinds = [
+            BaconComparisonType::TreatedVsNever,
+            BaconComparisonType::TreatedVsAlways,
+            BaconComparisonType::EarlierVsLater,
+            BaconComparisonType::LaterVsEarlier,
+        ];
+        kinds
+            .iter()
+            .filter_map(|&kind| {
+                let parts: Vec<&BaconComparison> =
+                    self.comparisons.iter().filter(|c| c.kind == kind).collect();
+                let weight: f64 = parts.iter().map(|c| c.weight).sum();
+                if parts.is_empty() || weight == 0.0 {
+                    return None;
+                }
+                let avg = parts.iter().map(|c| c.weight * c.estimate).sum::<f64>() / weight;
+                Some((kind, weight, avg))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BaconResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " Goodman-Bacon Decomposition ")?;
+        writeln!(
+            f,
+            "{:<20} {:>15.4} || {:<20} {:>15}",
+            "TWFE coefficient:", self.twfe_coef, "Observations:", self.n_obs
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Timing groups:", self.n_timing_groups, "Periods:", self.n_periods
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Never treated:", self.n_never_treated, "Always treated:", self.n_always_treated
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<30} {:>15} {:>15}",
+            "Comparison", "Weight", "Avg. DiD"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (kind, weight, avg) in self.by_type() {
+            writeln!(f, "{:<30} {:>15.4} {:>15.4}", kind.to_string(), weight, avg)?;
+        }
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<10} {:>10} {:>15} {:>15}",
+            "Treated", "Control", "Weight", "DiD"
+        )?;
+        for c in &self.comparisons {
+            let control = match (c.control, c.kind) {
+                (Some(k), _) => k.to_string(),
+                (None, BaconComparisonType::TreatedVsAlways) => "always".to_string(),
+                (None, _) => "never".to_string(),
+            };
+            writeln!(
+                f,
+                "{:<10} {:>10} {:>15.4} {:>15.4}",
+                c.treated, control, c.weight, c.estimate
+            )?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Goodman-Bacon (2021) decomposition of the TWFE DiD coefficient.
+pub struct BaconDecomposition;
+
+impl BaconDecomposition {
+    /// Decomposes the TWFE coefficient of `y` on the 0/1 column
+    /// `treatment` with `unit` and `time` fixed effects.
+    pub fn from_dataframe(
+        data: &DataFrame,
+        y: &str,
+        unit: &str,
+        time: &str,
+        treatment: &str,
+    ) -> Result<BaconResult, GreenersError> {
+        let (y, unit, time, d) = panel_columns(data, y, unit, time, treatment)?;
+        Self::fit(&y, &unit, &time, &d)
+    }
+
+    /// Decomposes the TWFE coefficient on a balanced panel.
+    ///
+    /// # Arguments
+    /// * `y` - Outcome for each observation
+    /// * `unit` - Unit identifier of each observation (arbitrary codes)
+    /// * `time` - Integer calendar period of each observation
+    /// * `treatment` - 0/1 treatment, absorbing within each unit
+    pub fn fit(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        treatment: &Array1<f64>,
+    ) -> Result<BaconResult, GreenersError> {
+        let panel = CellPanel::new(y, unit, time, treatment)?;
+        let (n_units, n_periods) = (panel.n_groups, panel.periods.len());
+        if panel.cells.len() != n_units * n_periods || panel.y.len() != panel.cells.len() {
+            return Err(GreenersError::InvalidOperation(
+                "The Bacon decomposition requires a balanced panel with one observation per unit and period".into(),
+            ));
+        }
+        let mut y_mat = vec![vec![0.0; n_periods]; n_units];
+        let mut d_mat = vec![vec![0.0; n_periods]; n_units];
+        for i in 0..panel.y.len() {
+            y_mat[panel.group[i]][panel.period[i]] = panel.y[i];
+            d_mat[panel.group[i]][panel.period[i]] = panel.d[i];
+        }
+
+        // Timing: index of the first treated period; 0 means always treated
+        let mut timing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let (mut never, mut always) = (vec![], vec![]);
+        for (u, d) in d_mat.iter().enumerate() {
+            if d.windows(2).any(|w| w[1] < w[0]) {
+                return Err(GreenersError::InvalidOperation(
+                    "Treatment must be absorbing: once treated, units stay treated".into(),
+                ));
+            }
+            match d.iter().position(|&v| v == 1.0) {
+                None => never.push(u),
+                Some(0) => always.push(u),
+                Some(k) => timing.entry(k).or_default().push(u),
+            }
+        }
+        if timing.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "No unit changes treatment status within the sample".into(),
+            ));
+        }
+
+        // Two-way demeaned treatment of a balanced panel
+        let (n, t) = (n_units as f64, n_periods as f64);
+        let unit_mean: Vec<f64> = d_mat.iter().map(|d| d.iter().sum::<f64>() / t).collect();
+        let period_mean: Vec<f64> = (0..n_periods)
+            .map(|p| d_mat.iter().map(|d| d[p]).sum::<f64>() / n)
+            .collect();
+        let grand = unit_mean.iter().sum::<f64>() / n;
+        let (mut sdd, mut syd) = (0.0, 0.0);
+        for u in 0..n_units {
+            for p in 0..n_periods {
+                let dt = d_mat[u][p] - unit_mean[u] - period_mean[p] + grand;
+                sdd += dt * dt;
+                syd += dt * y_mat[u][p];
+            }
+        }
+        let var_d = sdd / (n * t);
+        let twfe_coef = syd / sdd;
+
+        let trajectory = |units: &[usize]| -> Vec<f64> {
+            (0..n_periods)
+                .map(|p| units.iter().map(|&u| y_mat[u][p]).sum::<f64>() / units.len() as f64)
+                .collect()
+        };
+        let avg = |traj: &[f64], from: usize, to: usize| {
+            traj[from..to].iter().sum::<f64>() / (to - from) as f64
+        };
+        let share = |units: &[usize]| units.len() as f64 / n;
+        let treated_share = |k: usize| (n_periods - k) as f64 / t;
+        let label = |k: usize| panel.periods[k];
+
+        let mut comparisons = Vec::new();
+        // Timing groups against the pooled untimed units, split by kind
+        let untimed: Vec<usize> = never.iter().chain(&always).copied().collect();
+        for (&k, units) in &timing {
+            let yk = trajectory(units);
+            let (nk, dk) = (share(units), treated_share(k));
+            for (control, kind) in [
+                (&never, BaconComparisonType::TreatedVsNever),
+                (&always, BaconComparisonType::TreatedVsAlways),
+            ] {
+                if control.is_empty() {
+                    continue;
+                }
+                let yc = trajectory(control);
+                let nu = share(&untimed);
+                let nku = nk / (nk + nu);
+                let pooled = (nk + nu).powi(2) * nku * (1.0 - nku) * dk * (1.0 - dk) / var_d;
+                comparisons.push(BaconComparison {
+                    kind,
+                    treated: label(k),
+                    control: None,
+                    estimate: (avg(&yk, k, n_periods) - avg(&yk, 0, k))
+                        - (avg(&yc, k, n_periods) - avg(&yc, 0, k)),
+                    weight: pooled * control.len() as f64 / untimed.len() as f64,
+                });
+            }
+        }
+        // Pairs of timing groups, k treated before l
+        for (&k, units_k) in &timing {
+            for (&l, units_l) in timing.range(k + 1..) {
+                let (yk, yl) = (trajectory(units_k), trajectory(units_l));
+                let (nk, nl) = (share(units_k), share(units_l));
+                let (dk, dl) = (treated_share(k), treated_share(l));
+                let nkl = nk / (nk + nl);
+                comparisons.push(BaconComparison {
+                    kind: BaconComparisonType::EarlierVsLater,
+                    treated: label(k),
+                    control: Some(label(l)),
+                    estimate: (avg(&yk, k, l) - avg(&yk, 0, k)) - (avg(&yl, k, l) - avg(&yl, 0, k)),
+                    weight: ((nk + nl) * (1.0 - dl)).powi(2)
+                        * nkl
+                        * (1.0 - nkl)
+                        * ((dk - dl) / (1.0 - dl))
+                        * ((1.0 - dk) / (1.0 - dl))
+                        / var_d,
+                });
+                comparisons.push(BaconComparison {
+                    kind: BaconComparisonType::LaterVsEarlier,
+                    treated: label(l),
+                    control: Some(label(k)),
+                    estimate: (avg(&yl, l, n_periods) - avg(&yl, k, l))
+                        - (avg(&yk, l, n_periods) - avg(&yk, k, l)),
+                    weight: ((nk + nl) * dk).powi(2)
+                        * nkl
+                        * (1.0 - nkl)
+                        * (dl / dk)
+                        * ((dk - dl) / dk)
+                        / var_d,
+                });
+            }
+        }
+
+        Ok(BaconResult {
+            twfe_coef,
+            comparisons,
+            n_obs: panel.y.len(),
+            n_units,
+            n_periods,
+            n_timing_groups: timing.len(),
+            n_never_treated: never.len(),
+            n_always_treated: always.len(),
+        })
+    }
+}
+
+/// TWFE weight of one treated (group, period) cell.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwfeCellWeight {
+    /// Group identifier as passed in
+    pub group: usize,
+    pub period: i64,
+    /// Weight of the cell's average treatment effect; the weights sum to one
+    pub weight: f64,
+    pub n_obs: usize,
+}
+
+/// Result of [`TwfeWeights`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwfeWeightsResult {
+    /// TWFE coefficient on the treatment
+    pub beta_fe: f64,
+    pub weights: Vec<TwfeCellWeight>,
+    pub n_positive: usize,
+    pub n_negative: usize,
+    pub sum_positive: f64,
+    pub sum_negative: f64,
+    /// Smallest standard deviation of the cell effects under which
+    /// `beta_fe` is compatible with an average effect of zero; `None` when
+    /// all cells share one weight, since `beta_fe` is then the ATT itself
+    /// and no heterogeneity can offset it
+    pub sigma_fe: Option<f64>,
+    /// Smallest standard deviation of the cell effects under which
+    /// `beta_fe` is compatible with every cell effect having the opposite
+    /// sign; `None` without negative weights
+    pub sigma_fe_sign: Option<f64>,
+    pub n_obs: usize,
+    pub n_treated_obs: usize,
+}
+
+impl fmt::Display for TwfeWeightsResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "\n{:=^78}",
+            " TWFE Weights (de Chaisemartin-D'Haultfoeuille) "
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15.4} || {:<20} {:>15}",
+            "TWFE coefficient:", self.beta_fe, "Observations:", self.n_obs
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Treated cells:",
+            self.weights.len(),
+            "Treated obs.:",
+            self.n_treated_obs
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(f, "{:<20} {:>15} {:>15}", "", "Count", "Sum")?;
+        writeln!(
+            f,
+            "{:<20} {:>15} {:>15.4}",
+            "Positive weights", self.n_positive, self.sum_positive
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} {:>15.4}",
+            "Negative weights", self.n_negative, self.sum_negative
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        if let Some(s) = self.sigma_fe {
+            writeln!(
+                f,
+                "Min. sd of effects compatible with a zero ATT:        {:>10.4}",
+                s
+            )?;
+        }
+        if let Some(s) = self.sigma_fe_sign {
+            writeln!(
+                f,
+                "Min. sd of effects compatible with opposite signs:    {:>10.4}",
+                s
+            )?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// de Chaisemartin & D'Haultfœuille (2020) weights of the TWFE estimator.
+pub struct TwfeWeights;
+
+impl TwfeWeights {
+    /// TWFE weights of `y` on the 0/1 column `treatment` with `group` and
+    /// `time` fixed effects.
+    pub fn from_dataframe(
+        data: &DataFrame,
+        y: &str,
+        group: &str,
+        time: &str,
+        treatment: &str,
+    ) -> Result<TwfeWeightsResult, GreenersError> {
+        let (y, group, time, d) = panel_columns(data, y, group, time, treatment)?;
+        Self::fit(&y, &group, &time, &d)
+    }
+
+    /// Computes the weights of the regression of `y` on a 0/1 treatment with
+    /// group and period fixed effects. Groups may contain several
+    /// observations per period (e.g. individuals within states), provided
+    /// treatment is constant within each (group, period) cell. The panel
+    /// need not be balanced nor treatment absorbing.
+    pub fn fit(
+        y: &Array1<f64>,
+        group: &[usize],
+        time: &[i64],
+        treatment: &Array1<f64>,
+    ) -> Result<TwfeWeightsResult, GreenersError> {
+        let panel = CellPanel::new(y, group, time, treatment)?;
+        let eps = panel.two_way_residuals(&panel.d, 1e-13, 10_000)?;
+        let n_treated_obs = panel.d.iter().filter(|&&v| v == 1.0).count();
+        let sum_de: f64 = panel.d.iter().zip(&eps).map(|(d, e)| d * e).sum();
+        if n_treated_obs == 0 || sum_de.abs() < 1e-12 {
+            return Err(GreenersError::InvalidOperation(
+                "Treatment is collinear with the group and period effects".into(),
+            ));
+        }
+        let beta_fe = panel.y.iter().zip(&eps).map(|(y, e)| y * e).sum::<f64>() / sum_de;
+
+        // Residuals are constant within cells; weights are p * w with
+        // p = N_gt / N_1 and w = eps_gt / E[eps | D = 1]
+        let n1 = n_treated_obs as f64;
+        let mean_eps = sum_de / n1;
+        let mut weights = Vec::new();
+        let (mut p, mut w) = (vec![], vec![]);
+        for (&(g, t), obs) in &panel.cells {
+            if panel.d[obs[0]] != 1.0 {
+                continue;
+            }
+            let cell_w = obs.iter().map(|&i| eps[i]).sum::<f64>() / obs.len() as f64 / mean_eps;
+            let cell_p = obs.len() as f64 / n1;
+            weights.push(TwfeCellWeight {
+                group: panel.group_ids[g],
+                period: panel.periods[t],
+                weight: cell_p * cell_w,
+                n_obs: obs.len(),
+            });
+            p.push(cell_p);
+            w.push(cell_w);
+        }
+
+        let sd_w = p
+            .iter()
+            .zip(&w)
+            .map(|(p, w)| p * (w - 1.0).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let positive = weights.iter().filter(|c| c.weight > 0.0);
+        let negative = weights.iter().filter(|c| c.weight < 0.0);
+        let n_negative = negative.clone().count();
+
+        Ok(TwfeWeightsResult {
+            beta_fe,
+            n_positive: positive.clone().count(),
+            n_negative,
+            sum_positive: positive.map(|c| c.weight).sum(),
+            sum_negative: negative.map(|c| c.weight).sum(),
+            sigma_fe: (sd_w > 1e-12).then(|| beta_fe.abs() / sd_w),
+            sigma_fe_sign: if n_negative > 0 {
+                sign_reversal_sd(beta_fe, &p, &w)
+            } else {
+                None
+            },
+            weights,
+            n_obs: panel.y.len(),
+            n_treated_obs,
+        })
+    }
+}
+
+/// Corollary 1 of de Chaisemartin & D'Haultfœuille (2020): with the cells
+/// sorted by decreasing weight and `P_k`, `S_k`, `T_k` the tail sums of `p`,
+/// `p w` and `p w²`, the bound is `|β| / sqrt(T_s + S_s² / (1 - P_s))` for
+/// the first `s > 1` with `w_s < -S_s / (1 - P_s)`.
+fn sign_reversal_sd(beta: f64, p: &[f64], w: &[f64]) -> Option<f64> {
+    let mut order: Vec<usize> = (0..w.len()).collect();
+    order.sort_by(|&a, &b| w[b].total_cmp(&w[a]));
+    let m = order.len();
+    let (mut pk, mut sk, mut tk) = (vec![0.0; m + 1], vec![0.0; m + 1], vec![0.0; m + 1]);
+    for r in (0..m).rev() {
+        let i = order[r];
+        pk[r] = pk[r + 1] + p[i];
+        sk[r] = sk[r + 1] + p[i] * w[i];
+        tk[r] = tk[r + 1] + p[i] * w[i] * w[i];
+    }
+    (1..m)
+        .find(|&r| w[order[r]] < -sk[r] / (1.0 - pk[r]))
+        .map(|s| beta.abs() / (tk[s] + sk[s] * sk[s] / (1.0 - pk[s])).sqrt())
+}
+
+/// Observations with dense group and period codes, grouped into cells.
+struct CellPanel {
+    y: Vec<f64>,
+    d: Vec<f64>,
+    group: Vec<usize>,
+    period: Vec<usize>,
+    group_ids: Vec<usize>,
+    n_groups: usize,
+    periods: Vec<i64>,
+    /// Observations by (group, period)
+    cells: BTreeMap<(usize, usize), Vec<usize>>,
+}
+
+impl CellPanel {
+    fn new(
+        y: &Array1<f64>,
+        group: &[usize],
+        time: &[i64],
+        treatment: &Array1<f64>,
+    ) -> Result<Self, GreenersError> {
+        let n = y.len();
+        if group.len() != n || time.len() != n || treatment.len() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "y, group, time and treatment must have the same length".into(),
+            ));
+        }
+        if n == 0 || y.iter().any(|v| !v.is_finite()) {
+            return Err(GreenersError::InvalidOperation(
+                "y must be non-empty and finite".into(),
+            ));
+        }
+        if treatment.iter().any(|&v| v != 0.0 && v != 1.0) {
+            return Err(GreenersError::InvalidOperation(
+                "Treatment must be a 0/1 indicator".into(),
+            ));
+        }
+        let mut periods = time.to_vec();
+        periods.sort_unstable();
+        periods.dedup();
+        let mut codes: HashMap<usize, usize> = HashMap::new();
+        let mut group_ids = Vec::new();
+        let mut cells: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+        let (mut g_out, mut p_out) = (Vec::with_capacity(n), Vec::with_capacity(n));
+        for i in 0..n {
+            let next = codes.len();
+            let g = *codes.entry(group[i]).or_insert_with(|| {
+                group_ids.push(group[i]);
+                next
+            });
+            let p = periods.binary_search(&time[i]).unwrap();
+            let cell = cells.entry((g, p)).or_default();
+            if cell.first().is_some_and(|&j| treatment[j] != treatment[i]) {
+                return Err(GreenersError::InvalidOperation(format!(
+                    "Treatment varies within group {} in period {}",
+                    group[i], time[i]
+                )));
+            }
+            cell.push(i);
+            g_out.push(g);
+            p_out.push(p);
+        }
+        Ok(CellPanel {
+            y: y.to_vec(),
+            d: treatment.to_vec(),
+            group: g_out,
+            period: p_out,
+            n_groups: group_ids.len(),
+            group_ids,
+            periods,
+            cells,
+        })
+    }
+
+    /// Residuals of `v` on group and period dummies by alternating
+    /// projections.
+    fn two_way_residuals(
+        &self,
+        v: &[f64],
+        tol: f64,
+        max_iter: usize,
+    ) -> Result<Vec<f64>, GreenersError> {
+        let mut r = v.to_vec();
+        let sweep = |r: &mut [f64], codes: &[usize], n_levels: usize| {
+            let (mut sum, mut count) = (vec![0.0; n_levels], vec![0.0; n_levels]);
+            for (x, &c) in r.iter().zip(codes) {
+                sum[c] += x;
+                count[c] += 1.0;
+            }
+            let mut change: f64 = 0.0;
+            for (x, &c) in r.iter_mut().zip(codes) {
+                let m = sum[c] / count[c];
+                change = change.max(m.abs());
+                *x -= m;
+            }
+            change
+        };
+        for _ in 0..max_iter {
+            let change = sweep(&mut r, &self.group, self.n_groups).max(sweep(
+                &mut r,
+                &self.period,
+                self.periods.len(),
+            ));
+            if change < tol {
+                return Ok(r);
+            }
+        }
+        Err(GreenersError::OptimizationFailed)
+    }
+}
+
+/// Outcome, unit codes, integer periods and treatment of the rows of `data`
+/// without missing values.
+type PanelColumns = (Array1<f64>, Vec<usize>, Vec<i64>, Array1<f64>);
+
+fn panel_columns(
+    data: &DataFrame,
+    y: &str,
+    unit: &str,
+    time: &str,
+    treatment: &str,
+) -> Result<PanelColumns, GreenersError> {
+    let y_col = data.get_column(y)?.to_float();
+    let t_col = data.get_column(time)?.to_float();
+    let d_col = data.get_column(treatment)?.to_float();
+    let mut codes = vec![usize::MAX; data.n_rows()];
+    for (g, rows) in data.group_by(&[unit])?.groups().iter().enumerate() {
+        for &i in rows {
+            codes[i] = g;
+        }
+    }
+    let keep: Vec<usize> = (0..data.n_rows())
+        .filter(|&i| {
+            codes[i] != usize::MAX
+                && y_col[i].is_finite()
+                && t_col[i].is_finite()
+                && d_col[i].is_finite()
+        })
+        .collect();
+    if keep.iter().any(|&i| t_col[i].fract() != 0.0) {
+        return Err(GreenersError::InvalidOperation(format!(
+            "'{}' must hold integer periods",
+            time
+        )));
+    }
+    Ok((
+        keep.iter().map(|&i| y_col[i]).collect(),
+        keep.iter().map(|&i| codes[i]).collect(),
+        keep.iter().map(|&i| t_col[i] as i64).collect(),
+        keep.iter().map(|&i| d_col[i]).collect(),
+    ))
+}
@@ -48,7 +48,7 @@ fn assert_cf_result_finite(result: &CausalForestResult, n: usize, k: usize) {
 fn test_causal_forest_fit_finite_and_reasonable() {
     let n = 60;
     let (y, t, x) = make_cf_data(n, 9422);
-    let result = CausalForest::fit(&y, &t, &x, Some(50), Some(5), None).unwrap();
+    let result = CausalForest::fit(&y, &t, &x, Some(50), Some(5), None, None).unwrap();
     assert_cf_result_finite(&result, n, 2);
     assert!((result.ate - 2.0).abs() < 1.0, "ate = {}", result.ate);
 }
@@ -58,14 +58,36 @@ fn test_causal_forest_fit_finite_and_reasonable() {
 fn test_causal_forest_input_validation() {
     let n = 15;
     let (y, t, x) = make_cf_data(n, 9423);
-    assert!(CausalForest::fit(&y, &t, &x, None, None, None).is_err());
+    assert!(CausalForest::fit(&y, &t, &x, None, None, None, None).is_err());
 
     let n2 = 60;
     let (y2, _t2, x2) = make_cf_data(n2, 9424);
     let t2_all_false = vec![false; n2];
-    assert!(CausalForest::fit(&y2, &t2_all_false, &x2, None, None, None).is_err());
+    assert!(CausalForest::fit(&y2, &t2_all_false, &x2, None, None, None, None).is_err());
 
     let mut t_short = vec![false; n2];
     t_short.pop();
-    assert!(CausalForest::fit(&y2, &t_short, &x2, None, None, None).is_err());
+    assert!(CausalForest::fit(&y2, &t_short, &x2, None, None, None, None).is_err());
+}
+
+/// The fitted forest scores new observations and survives a save/load
+/// round trip.
+#[test]
+fn test_causal_forest_predict_and_persist() {
+    use greeners_core::Persist;
+
+    let n = 60;
+    let (y, t, x) = make_cf_data(n, 9423);
+    let result = CausalForest::fit(&y, &t, &x, Some(20), Some(4), None, None).unwrap();
+    let pred = result.predict(&x).unwrap();
+    for (a, b) in pred.iter().zip(result.treatment_effects.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+    assert!(result.predict(&Array2::zeros((2, 3))).is_err());
+
+    let restored = CausalForestResult::from_bytes(&result.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.predict(&x).unwrap(), pred);
+    assert_eq!(restored.ate, result.ate);
+    let restored = CausalForestResult::from_json(&result.to_json().unwrap()).unwrap();
+    assert_eq!(restored.n_trees, result.n_trees);
 }
@@ -27,7 +27,7 @@ fn make_conformal_data(n: usize, seed: u64) -> (Array1<f64>, Array2<f64>, Array2
 #[test]
 fn test_conformal_shape_and_finite() {
     let (y, x, x_test) = make_conformal_data(60, 20001);
-    let r = ConformalPrediction::fit(&y, &x, &x_test, Some(0.1), Some(0.3), None).unwrap();
+    let r = ConformalPrediction::fit(&y, &x, &x_test, Some(0.1), Some(0.3), None, None).unwrap();
     assert_eq!(r.n_test, x_test.nrows());
     assert_eq!(r.n_train + r.n_calib, y.len());
     assert_eq!(r.predictions.len(), x_test.nrows());
@@ -55,7 +55,7 @@ fn test_conformal_shape_and_finite() {
 #[test]
 fn test_conformal_coverage_identity() {
     let (y, x, x_test) = make_conformal_data(60, 20002);
-    let r = ConformalPrediction::fit(&y, &x, &x_test, Some(0.1), None, None).unwrap();
+    let r = ConformalPrediction::fit(&y, &x, &x_test, Some(0.1), None, None, None).unwrap();
     assert!((r.coverage - (1.0 - r.alpha)).abs() < 1e-12);
 }
 
@@ -64,7 +64,7 @@ fn test_conformal_coverage_identity() {
 fn test_conformal_input_validation() {
     let (y, x, x_test) = make_conformal_data(60, 20003);
     let x_bad = Array2::from_shape_vec((10, 3), vec![0.0; 30]).unwrap();
-    assert!(ConformalPrediction::fit(&y, &x, &x_bad, None, None, None).is_err());
+    assert!(ConformalPrediction::fit(&y, &x, &x_bad, None, None, None, None).is_err());
     let y_short = Array1::from_vec(vec![0.0; 5]);
-    assert!(ConformalPrediction::fit(&y_short, &x, &x_test, None, None, None).is_err());
+    assert!(ConformalPrediction::fit(&y_short, &x, &x_test, None, None, None, None).is_err());
 }
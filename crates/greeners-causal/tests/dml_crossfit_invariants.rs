@@ -29,7 +29,7 @@ fn make_dml_data(n: usize, seed: u64) -> (Array1<f64>, Array1<f64>, Array2<f64>)
 #[test]
 fn test_dml_crossfit_shape_and_finite() {
     let (y, d, x) = make_dml_data(50, 17001);
-    let r = DMLCrossfit::fit(&y, &d, &x, Some(3)).unwrap();
+    let r = DMLCrossfit::fit(&y, &d, &x, Some(3), None).unwrap();
     assert_eq!(r.n_obs, y.len());
     assert_eq!(r.n_confounders, x.ncols());
     assert!(r.theta.is_finite());
@@ -43,7 +43,7 @@ fn test_dml_crossfit_shape_and_finite() {
 #[test]
 fn test_dml_crossfit_ci_contains_theta() {
     let (y, d, x) = make_dml_data(80, 17002);
-    let r = DMLCrossfit::fit(&y, &d, &x, Some(4)).unwrap();
+    let r = DMLCrossfit::fit(&y, &d, &x, Some(4), None).unwrap();
     assert!(r.ci[0] <= r.theta && r.theta <= r.ci[1]);
 }
 
@@ -52,7 +52,7 @@ fn test_dml_crossfit_ci_contains_theta() {
 fn test_dml_crossfit_input_validation() {
     let (y, d, x) = make_dml_data(50, 17003);
     let d_bad = Array1::from_vec(vec![0.0; 10]);
-    assert!(DMLCrossfit::fit(&y, &d_bad, &x, None).is_err());
+    assert!(DMLCrossfit::fit(&y, &d_bad, &x, None, None).is_err());
     let y_short = Array1::from_vec(vec![0.0; 10]);
-    assert!(DMLCrossfit::fit(&y_short, &d, &x, None).is_err());
+    assert!(DMLCrossfit::fit(&y_short, &d, &x, None, None).is_err());
 }
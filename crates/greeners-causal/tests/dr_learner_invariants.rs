@@ -29,7 +29,7 @@ fn make_dr_data(n: usize, seed: u64) -> (Array1<f64>, Vec<bool>, Array2<f64>) {
 #[test]
 fn test_dr_learner_shape_and_finite() {
     let (y, t, x) = make_dr_data(50, 18001);
-    let r = DRLearner::fit(&y, &t, &x, None, None).unwrap();
+    let r = DRLearner::fit(&y, &t, &x, None, None, None).unwrap();
     assert_eq!(r.n_obs, y.len());
     assert_eq!(r.n_features, x.ncols());
     assert_eq!(r.cate.len(), y.len());
@@ -43,7 +43,7 @@ fn test_dr_learner_shape_and_finite() {
 #[test]
 fn test_dr_learner_coefs() {
     let (y, t, x) = make_dr_data(60, 18002);
-    let r = DRLearner::fit(&y, &t, &x, Some(2), None).unwrap();
+    let r = DRLearner::fit(&y, &t, &x, Some(2), None, None).unwrap();
     assert_eq!(r.cate_coefficients.len(), x.ncols() + 1);
     assert!(r.cate_coefficients.iter().all(|v| v.is_finite()));
 }
@@ -53,7 +53,7 @@ fn test_dr_learner_coefs() {
 fn test_dr_learner_input_validation() {
     let (y, t, x) = make_dr_data(50, 18003);
     let t_all = vec![true; 50];
-    assert!(DRLearner::fit(&y, &t_all, &x, None, None).is_err());
+    assert!(DRLearner::fit(&y, &t_all, &x, None, None, None).is_err());
     let y_short = Array1::from_vec(vec![0.0; 5]);
-    assert!(DRLearner::fit(&y_short, &t, &x, None, None).is_err());
+    assert!(DRLearner::fit(&y_short, &t, &x, None, None, None).is_err());
 }
@@ -32,7 +32,7 @@ fn psm_runs_and_recovers_att() {
     let d = Array1::from_vec(d);
     let x = Array2::from_shape_vec((n, 2), x).unwrap();
 
-    let result = PSM::fit(&y, &d, &x, 2, None, false, 30, None).unwrap();
+    let result = PSM::fit(&y, &d, &x, 2, None, false, 30, None, None).unwrap();
 
     assert!(result.att.is_finite());
     assert!(result.att > 0.0, "ATT should be positive: {}", result.att);
@@ -53,9 +53,9 @@ fn psm_input_validation() {
     let x = Array2::zeros((n, 1));
 
     // Length mismatch
-    assert!(PSM::fit(&y, &d, &x, 1, None, false, 10, None).is_err());
+    assert!(PSM::fit(&y, &d, &x, 1, None, false, 10, None, None).is_err());
 
     // k == 0
     let d2 = Array1::from_vec(vec![0.0; n]);
-    assert!(PSM::fit(&y, &d2, &x, 0, None, false, 10, None).is_err());
+    assert!(PSM::fit(&y, &d2, &x, 0, None, false, 10, None, None).is_err());
 }
@@ -35,7 +35,7 @@ fn test_psm_basic() {
     let d = Array1::from(d_vec);
     let x = Array2::from_shape_vec((n, 2), x_vec).unwrap();
 
-    let res = PSM::fit(&y, &d, &x, 1, None, true, 50, None).unwrap();
+    let res = PSM::fit(&y, &d, &x, 1, None, true, 50, None, None).unwrap();
 
     assert!(res.att > 0.0);
     assert!(res.se > 0.0);
@@ -0,0 +1,268 @@
+use greeners_causal::twfe_diagnostics::{BaconComparisonType, BaconDecomposition, TwfeWeights};
+use greeners_core::{CovarianceType, DataFrame};
+use greeners_ols::ols::OLS;
+use ndarray::{Array1, Array2};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::Normal;
+
+struct Panel {
+    y: Array1<f64>,
+    unit: Vec<usize>,
+    time: Vec<i64>,
+    d: Array1<f64>,
+}
+
+/// Balanced panel over periods 1..=8. `timing[u]` is the first treated
+/// period of unit u (1 = always treated, 0 = never); effects grow by
+/// `slope` per period since treatment.
+fn make_panel(seed: u64, timing: &[i64], slope: f64, noise: f64) -> Panel {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let (mut y, mut unit, mut time, mut d) = (vec![], vec![], vec![], vec![]);
+    for (u, &g) in timing.iter().enumerate() {
+        let alpha = normal.sample(&mut rng);
+        for t in 1..=8 {
+            let treated = g > 0 && t >= g;
+            let effect = if treated {
+                1.0 + slope * (t - g) as f64
+            } else {
+                0.0
+            };
+            y.push(alpha + 0.2 * t as f64 + effect + noise * normal.sample(&mut rng));
+            unit.push(10 * u + 3);
+            time.push(t);
+            d.push(if treated { 1.0 } else { 0.0 });
+        }
+    }
+    Panel {
+        y: Array1::from(y),
+        unit,
+        time,
+        d: Array1::from(d),
+    }
+}
+
+/// TWFE coefficient from OLS with unit and period dummies.
+fn twfe_ols(p: &Panel, n_units: usize) -> f64 {
+    let n = p.y.len();
+    let mut x = Array2::<f64>::zeros((n, 1 + n_units + 7));
+    for i in 0..n {
+        x[[i, 0]] = p.d[i];
+        x[[i, 1 + i / 8]] = 1.0;
+        if p.time[i] > 1 {
+            x[[i, n_units + p.time[i] as usize - 1]] = 1.0;
+        }
+    }
+    OLS::fit(&p.y, &x, CovarianceType::NonRobust)
+        .unwrap()
+        .params[0]
+}
+
+#[test]
+fn test_bacon_weights_sum_to_one_and_reproduce_twfe() {
+    let timing: Vec<i64> = (0..30).map(|u| [0, 1, 3, 5, 7][u % 5]).collect();
+    let p = make_panel(1, &timing, 0.4, 1.0);
+    let fit = BaconDecomposition::fit(&p.y, &p.unit, &p.time, &p.d).unwrap();
+
+    assert_eq!(fit.n_timing_groups, 3);
+    assert_eq!(fit.n_never_treated, 6);
+    assert_eq!(fit.n_always_treated, 6);
+    // 3 groups x 2 untimed controls + 3 pairs x 2 directions
+    assert_eq!(fit.comparisons.len(), 12);
+    let total: f64 = fit.comparisons.iter().map(|c| c.weight).sum();
+    assert!((total - 1.0).abs() < 1e-10);
+    assert!(fit.comparisons.iter().all(|c| c.weight > 0.0));
+    let recomposed: f64 = fit.comparisons.iter().map(|c| c.weight * c.estimate).sum();
+    assert!((recomposed - fit.twfe_coef).abs() < 1e-10);
+    assert!((fit.twfe_coef - twfe_ols(&p, 30)).abs() < 1e-8);
+
+    let summary = fit.by_type();
+    assert_eq!(summary.len(), 4);
+    assert!((summary.iter().map(|s| s.1).sum::<f64>() - 1.0).abs() < 1e-10);
+    assert!(format!("{}", fit).contains("Later vs earlier treated"));
+}
+
+#[test]
+fn test_bacon_flags_forbidden_comparisons() {
+    // Without noise the 2x2s are exact: growing effects bias the
+    // comparisons that use already-treated units as controls downwards
+    let timing: Vec<i64> = (0..20).map(|u| [0, 3, 6][u % 3]).collect();
+    let p = make_panel(2, &timing, 0.5, 0.0);
+    let fit = BaconDecomposition::fit(&p.y, &p.unit, &p.time, &p.d).unwrap();
+    let later = fit
+        .comparisons
+        .iter()
+        .find(|c| c.kind == BaconComparisonType::LaterVsEarlier)
+        .unwrap();
+    assert_eq!((later.treated, later.control), (6, Some(3)));
+    // Cohort 6 gains 1.5 on average, but so does the cohort-3 control
+    // between its middle and late windows, cancelling the effect
+    assert!(later.estimate.abs() < 1e-10);
+
+    // A single 2x2 design has one comparison with all the weight
+    let two: Vec<i64> = (0..10).map(|u| [0, 5][u % 2]).collect();
+    let p = make_panel(3, &two, 0.0, 1.0);
+    let fit = BaconDecomposition::fit(&p.y, &p.unit, &p.time, &p.d).unwrap();
+    assert_eq!(fit.comparisons.len(), 1);
+    assert!((fit.comparisons[0].weight - 1.0).abs() < 1e-12);
+    assert!((fit.comparisons[0].estimate - fit.twfe_coef).abs() < 1e-10);
+}
+
+#[test]
+fn test_bacon_rejects_unbalanced_and_reversible_designs() {
+    let timing: Vec<i64> = (0..10).map(|u| [0, 4][u % 2]).collect();
+    let p = make_panel(4, &timing, 0.0, 1.0);
+    let n = p.y.len() - 1;
+    let short = BaconDecomposition::fit(
+        &p.y.slice(ndarray::s![..n]).to_owned(),
+        &p.unit[..n],
+        &p.time[..n],
+        &p.d.slice(ndarray::s![..n]).to_owned(),
+    );
+    assert!(short.is_err());
+
+    let mut d = p.d.clone();
+    d[8 + 7] = 0.0; // unit 1 switches back in the last period
+    assert!(BaconDecomposition::fit(&p.y, &p.unit, &p.time, &d).is_err());
+}
+
+#[test]
+fn test_twfe_weights_reproduce_coefficient_and_bounds() {
+    // Heterogeneous cell effects, no noise: beta_fe = sum of weight x effect
+    let timing: Vec<i64> = (0..24).map(|u| [0, 2, 4, 6][u % 4]).collect();
+    let p = make_panel(5, &timing, 0.6, 0.0);
+    let fit = TwfeWeights::fit(&p.y, &p.unit, &p.time, &p.d).unwrap();
+
+    assert!((fit.beta_fe - twfe_ols(&p, 24)).abs() < 1e-8);
+    let total: f64 = fit.weights.iter().map(|c| c.weight).sum();
+    assert!((total - 1.0).abs() < 1e-10);
+    let effect = |c: &greeners_causal::TwfeCellWeight| {
+        let g = timing[(c.group - 3) / 10];
+        1.0 + 0.6 * (c.period - g) as f64
+    };
+    let implied: f64 = fit.weights.iter().map(|c| c.weight * effect(c)).sum();
+    assert!((implied - fit.beta_fe).abs() < 1e-8);
+
+    // Early cohort's late periods get negative weights
+    assert!(fit.n_negative > 0);
+    assert!(fit.sum_negative < 0.0);
+    assert!((fit.sum_positive + fit.sum_negative - 1.0).abs() < 1e-10);
+    assert_eq!(fit.n_positive + fit.n_negative, fit.weights.len());
+    let worst = fit
+        .weights
+        .iter()
+        .min_by(|a, b| a.weight.total_cmp(&b.weight))
+        .unwrap();
+    assert_eq!(timing[(worst.group - 3) / 10], 2);
+    assert!(worst.period >= 6);
+
+    // sigma_fe: effects with mean zero and sd sigma_fe proportional to w - 1
+    let p_cell: Vec<f64> = fit
+        .weights
+        .iter()
+        .map(|c| c.n_obs as f64 / fit.n_treated_obs as f64)
+        .collect();
+    let w: Vec<f64> = fit
+        .weights
+        .iter()
+        .zip(&p_cell)
+        .map(|(c, p)| c.weight / p)
+        .collect();
+    let var_w: f64 = p_cell
+        .iter()
+        .zip(&w)
+        .map(|(p, w)| p * (w - 1.0).powi(2))
+        .sum();
+    let sigma_fe = fit.sigma_fe.unwrap();
+    assert!((sigma_fe - fit.beta_fe.abs() / var_w.sqrt()).abs() < 1e-12);
+
+    // The sign-reversal bound is attained by effects min(0, m + c w)
+    let bound = fit.sigma_fe_sign.unwrap();
+    assert!(bound >= sigma_fe);
+    let best = (0..2000)
+        .map(|k| -3.0 + 6.0 * k as f64 / 2000.0)
+        .filter_map(|cut: f64| {
+            // Effects proportional to (cut - w) on cells with w < cut
+            let delta: Vec<f64> = w.iter().map(|&wi| (wi - cut).min(0.0)).collect();
+            let scale: f64 = p_cell
+                .iter()
+                .zip(&w)
+                .zip(&delta)
+                .map(|((p, w), d)| p * w * d)
+                .sum();
+            if scale <= 0.0 {
+                return None;
+            }
+            let delta: Vec<f64> = delta.iter().map(|d| d * fit.beta_fe / scale).collect();
+            let mean: f64 = p_cell.iter().zip(&delta).map(|(p, d)| p * d).sum();
+            let sd: f64 = p_cell
+                .iter()
+                .zip(&delta)
+                .map(|(p, d)| p * (d - mean).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            Some(sd)
+        })
+        .fold(f64::INFINITY, f64::min);
+    assert!(best >= bound - 1e-9, "{} < {}", best, bound);
+    assert!(best < bound * 1.01);
+
+    // A single treated cell has weight one: beta_fe is that cell's effect
+    let timing: Vec<i64> = (0..6).map(|u| if u == 0 { 8 } else { 0 }).collect();
+    let p = make_panel(3, &timing, 0.0, 0.1);
+    let fit = TwfeWeights::fit(&p.y, &p.unit, &p.time, &p.d).unwrap();
+    assert_eq!(fit.weights.len(), 1);
+    assert!(fit.beta_fe != 0.0);
+    assert!(fit.sigma_fe.is_none());
+    assert!(fit.sigma_fe_sign.is_none());
+}
+
+#[test]
+fn test_twfe_weights_with_grouped_unbalanced_data() {
+    // Individuals within states: several observations per cell
+    let mut rng = StdRng::seed_from_u64(6);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let adopt = [0i64, 3, 5, 0, 4];
+    let (mut y, mut state, mut year, mut d) = (vec![], vec![], vec![], vec![]);
+    for (s, &g) in adopt.iter().enumerate() {
+        for t in 1..=6 {
+            for _ in 0..(2 + (s + t as usize) % 3) {
+                let treated = g > 0 && t >= g;
+                y.push(
+                    s as f64
+                        + 0.1 * t as f64
+                        + if treated { 2.0 } else { 0.0 }
+                        + normal.sample(&mut rng),
+                );
+                state.push(s as i64);
+                year.push(t);
+                d.push(treated as i64);
+            }
+        }
+    }
+    let df = DataFrame::builder()
+        .add_column("y", y)
+        .add_int("state", state)
+        .add_int("year", year)
+        .add_int("d", d)
+        .build()
+        .unwrap();
+    let fit = TwfeWeights::from_dataframe(&df, "y", "state", "year", "d").unwrap();
+    assert_eq!(fit.weights.len(), 4 + 2 + 3);
+    assert!((fit.weights.iter().map(|c| c.weight).sum::<f64>() - 1.0).abs() < 1e-10);
+    assert!((fit.beta_fe - 2.0).abs() < 1.0);
+    assert!(format!("{}", fit).contains("Negative weights"));
+
+    // Treatment must be constant within a state-year
+    let mut mixed: Vec<i64> = df.get_int("d").unwrap().to_vec();
+    mixed[0] = 1;
+    let df = DataFrame::builder()
+        .add_column("y", df.get("y").unwrap().to_vec())
+        .add_int("state", df.get_int("state").unwrap().to_vec())
+        .add_int("year", df.get_int("year").unwrap().to_vec())
+        .add_int("d", mixed)
+        .build()
+        .unwrap();
+    assert!(TwfeWeights::from_dataframe(&df, "y", "state", "year", "d").is_err());
+}
@@ -15,10 +15,11 @@
 use crate::multivariate::PCA;
 use crate::GreenersError;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Biplot type.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum BiplotType {
     /// Row principal (GH biplot): preserves distances between observations
     Form,
@@ -39,7 +40,7 @@ impl BiplotType {
 }
 
 /// Result of PCA biplot.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BiplotResult {
     /// Observation scores (n x 2)
     pub scores: Array2<f64>,
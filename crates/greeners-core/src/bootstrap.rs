@@ -1,8 +1,11 @@
 use crate::error::GreenersError;
 use crate::linalg::LinalgInverse as _;
+use crate::model::{FittedModel as _, Model};
+use crate::rng::GreenersRng;
 use ndarray::{Array1, Array2};
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Bootstrap methods for statistical inference
 pub struct Bootstrap;
@@ -16,10 +19,14 @@ impl Bootstrap {
     /// * `y` - Dependent variable (n × 1)
     /// * `x` - Design matrix (n × k)
     /// * `n_bootstrap` - Number of bootstrap replications (recommended: 1000-10000)
+    /// * `seed` - Random seed (`None` uses the default seed)
     ///
     /// # Returns
     /// Array of bootstrap coefficient estimates (n_bootstrap × k)
     ///
+    /// Replications run in parallel; each draws from its own stream of the
+    /// seeded generator, so the result does not depend on the thread count.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -36,7 +43,7 @@ impl Bootstrap {
     ///     1.0, 5.0
     /// ])?;
     ///
-    /// let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 100)?;
+    /// let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 100, Some(42))?;
     /// // ...
     /// # Ok(())
     /// # }
@@ -45,6 +52,7 @@ impl Bootstrap {
         y: &Array1<f64>,
         x: &Array2<f64>,
         n_bootstrap: usize,
+        seed: Option<u64>,
     ) -> Result<Array2<f64>, GreenersError> {
         let n = y.len();
         let k = x.ncols();
@@ -54,55 +62,112 @@ impl Bootstrap {
                 "X and y must have same number of rows".to_string(),
             ));
         }
+        if n == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "Empty index pool for bootstrap".to_string(),
+            ));
+        }
 
-        let mut rng = thread_rng();
-        let indices: Vec<usize> = (0..n).collect();
+        let rng = GreenersRng::new(seed);
+        let replications: Vec<Option<Array1<f64>>> = (0..n_bootstrap)
+            .into_par_iter()
+            .map(|b| {
+                let boot_indices = rng.stream(b as u64).bootstrap_indices(n);
+                Self::ols_replication(y, x, &boot_indices)
+            })
+            .collect();
 
         // Store bootstrap coefficients
         let mut boot_coefs = Array2::<f64>::zeros((n_bootstrap, k));
-
-        for b in 0..n_bootstrap {
-            // Resample indices with replacement
-            let mut boot_indices = vec![0; n];
-            // for i in 0..n {
-            for indice in boot_indices.iter_mut().take(n) {
-                *indice = *indices.choose(&mut rng).ok_or_else(|| {
-                    GreenersError::InvalidOperation("Empty index pool for bootstrap".to_string())
-                })?;
+        for (b, beta) in replications.iter().enumerate() {
+            if let Some(beta) = beta {
+                boot_coefs.row_mut(b).assign(beta);
             }
+        }
+
+        Ok(boot_coefs)
+    }
+
+    /// OLS coefficients on the rows `boot_indices`, falling back to the
+    /// full-sample estimate when the resampled design is singular.
+    fn ols_replication(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        boot_indices: &[usize],
+    ) -> Option<Array1<f64>> {
+        let n = boot_indices.len();
+        let k = x.ncols();
+
+        // Create bootstrap sample
+        let mut y_boot = Array1::<f64>::zeros(n);
+        let mut x_boot = Array2::<f64>::zeros((n, k));
 
-            // Create bootstrap sample
-            let mut y_boot = Array1::<f64>::zeros(n);
-            let mut x_boot = Array2::<f64>::zeros((n, k));
+        for (i, &idx) in boot_indices.iter().enumerate() {
+            y_boot[i] = y[idx];
+            x_boot.row_mut(i).assign(&x.row(idx));
+        }
 
-            for (i, &idx) in boot_indices.iter().enumerate() {
-                y_boot[i] = y[idx];
-                x_boot.row_mut(i).assign(&x.row(idx));
+        // Fit OLS on bootstrap sample
+        let xt_x = x_boot.t().dot(&x_boot);
+        let xt_y = x_boot.t().dot(&y_boot);
+
+        match xt_x.inv() {
+            Ok(xt_x_inv) => Some(xt_x_inv.dot(&xt_y)),
+            Err(_) => {
+                // Singular matrix in this bootstrap sample - use original estimate
+                // This is rare but can happen with small samples
+                let xt_x_orig = x.t().dot(x);
+                let xt_y_orig = x.t().dot(y);
+                xt_x_orig.inv().ok().map(|inv| inv.dot(&xt_y_orig))
             }
+        }
+    }
 
-            // Fit OLS on bootstrap sample
-            let xt_x = x_boot.t().dot(&x_boot);
-            let xt_y = x_boot.t().dot(&y_boot);
+    /// Pairs bootstrap for any [`Model`].
+    ///
+    /// Refits `model` on each resample of (y, X) rows and returns the
+    /// coefficient estimates (n_bootstrap × k). Replications in which the fit
+    /// fails or returns a different number of coefficients are skipped, so the
+    /// result may have fewer than `n_bootstrap` rows. `seed: None` uses the
+    /// default seed.
+    pub fn pairs_bootstrap_model<M: Model>(
+        model: &M,
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_bootstrap: usize,
+        seed: Option<u64>,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let n = y.len();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "X and y must have same number of rows".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "Empty index pool for bootstrap".to_string(),
+            ));
+        }
 
-            match xt_x.inv() {
-                Ok(xt_x_inv) => {
-                    let beta_boot = xt_x_inv.dot(&xt_y);
-                    boot_coefs.row_mut(b).assign(&beta_boot);
-                }
-                Err(_) => {
-                    // Singular matrix in this bootstrap sample - use original estimate
-                    // This is rare but can happen with small samples
-                    let xt_x_orig = x.t().dot(x);
-                    let xt_y_orig = x.t().dot(y);
-                    if let Ok(inv) = xt_x_orig.inv() {
-                        let beta_orig = inv.dot(&xt_y_orig);
-                        boot_coefs.row_mut(b).assign(&beta_orig);
-                    }
+        let k = model.fit(y, x)?.params().len();
+        let rng = GreenersRng::new(seed);
+        let mut rows: Vec<f64> = Vec::with_capacity(n_bootstrap * k);
+        let mut n_ok = 0;
+        for b in 0..n_bootstrap {
+            let idx = rng.stream(b as u64).bootstrap_indices(n);
+            let y_boot = y.select(ndarray::Axis(0), &idx);
+            let x_boot = x.select(ndarray::Axis(0), &idx);
+            if let Ok(fitted) = model.fit(&y_boot, &x_boot) {
+                let params = fitted.params();
+                if params.len() == k {
+                    rows.extend(params.iter());
+                    n_ok += 1;
                 }
             }
         }
 
-        Ok(boot_coefs)
+        Array2::from_shape_vec((n_ok, k), rows)
+            .map_err(|e| GreenersError::ShapeMismatch(e.to_string()))
     }
 
     /// Calculate bootstrap standard errors from bootstrap coefficient matrix
@@ -146,6 +211,471 @@ impl Bootstrap {
     }
 }
 
+/// Auxiliary weight distribution for the wild bootstrap.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum WildWeights {
+    /// ±1 with equal probability
+    #[default]
+    Rademacher,
+    /// Webb's six-point distribution ±√(3/2), ±1, ±√(1/2); preferred with
+    /// very few clusters
+    Webb,
+    /// Mammen's two-point distribution matching the third moment
+    Mammen,
+}
+
+impl fmt::Display for WildWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildWeights::Rademacher => write!(f, "Rademacher"),
+            WildWeights::Webb => write!(f, "Webb"),
+            WildWeights::Mammen => write!(f, "Mammen"),
+        }
+    }
+}
+
+impl WildWeights {
+    pub(crate) fn draw(&self, rng: &mut GreenersRng) -> f64 {
+        let u = rng.uniform();
+        match self {
+            WildWeights::Rademacher => {
+                if u < 0.5 {
+                    -1.0
+                } else {
+                    1.0
+                }
+            }
+            WildWeights::Webb => {
+                let point = [
+                    -(1.5f64).sqrt(),
+                    -1.0,
+                    -(0.5f64).sqrt(),
+                    (0.5f64).sqrt(),
+                    1.0,
+                    (1.5f64).sqrt(),
+                ];
+                point[((u * 6.0) as usize).min(5)]
+            }
+            WildWeights::Mammen => {
+                let sqrt5 = 5.0f64.sqrt();
+                if u < (sqrt5 + 1.0) / (2.0 * sqrt5) {
+                    -(sqrt5 - 1.0) / 2.0
+                } else {
+                    (sqrt5 + 1.0) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Settings for [`WildBootstrap`].
+#[derive(Debug, Clone)]
+pub struct WildBootstrapConfig {
+    pub weights: WildWeights,
+    /// Number of replications. With Rademacher weights and 2^G ≤
+    /// `n_bootstrap` all 2^G sign patterns are enumerated instead.
+    pub n_bootstrap: usize,
+    /// Random seed (`None` uses the default seed)
+    pub seed: Option<u64>,
+    /// Impose the null when generating bootstrap samples (WCR/WR); `false`
+    /// gives the unrestricted WCU/WU bootstrap
+    pub impose_null: bool,
+    /// Significance level of the confidence interval
+    pub alpha: f64,
+    /// Invert the test to obtain a confidence interval
+    pub confidence_interval: bool,
+}
+
+impl Default for WildBootstrapConfig {
+    fn default() -> Self {
+        Self {
+            weights: WildWeights::Rademacher,
+            n_bootstrap: 999,
+            seed: None,
+            impose_null: true,
+            alpha: 0.05,
+            confidence_interval: true,
+        }
+    }
+}
+
+/// Wild (cluster) bootstrap test of H0: β_j = r.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WildBootstrapResult {
+    pub coef_index: usize,
+    pub null_value: f64,
+    pub estimate: f64,
+    /// Robust (HC1) or cluster-robust (CR1) t statistic of the original sample
+    pub t_stat: f64,
+    /// Symmetric bootstrap p-value, share of |t*| ≥ |t|
+    pub p_value: f64,
+    /// Test-inversion confidence interval at level 1 − alpha
+    pub conf_interval: Option<(f64, f64)>,
+    pub alpha: f64,
+    /// Replications actually used (2^G under full enumeration)
+    pub n_bootstrap: usize,
+    pub n_clusters: Option<usize>,
+    pub weights: WildWeights,
+    pub impose_null: bool,
+}
+
+impl fmt::Display for WildBootstrapResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = match (self.n_clusters.is_some(), self.impose_null) {
+            (true, true) => "Wild Cluster Restricted (WCR)",
+            (true, false) => "Wild Cluster Unrestricted (WCU)",
+            (false, true) => "Wild Restricted (WR)",
+            (false, false) => "Wild Unrestricted (WU)",
+        };
+        writeln!(f, "\n{:=^60}", " Wild Bootstrap Test ")?;
+        writeln!(f, "H0: beta[{}] = {}", self.coef_index, self.null_value)?;
+        writeln!(f, "{:-^60}", "")?;
+        writeln!(f, "{:<30} {:>29}", "Method:", method)?;
+        writeln!(f, "{:<30} {:>29}", "Weights:", self.weights.to_string())?;
+        writeln!(f, "{:<30} {:>29}", "Replications:", self.n_bootstrap)?;
+        if let Some(g) = self.n_clusters {
+            writeln!(f, "{:<30} {:>29}", "Clusters:", g)?;
+        }
+        writeln!(f, "{:<30} {:>29.4}", "Estimate:", self.estimate)?;
+        writeln!(f, "{:<30} {:>29.4}", "t statistic:", self.t_stat)?;
+        writeln!(f, "{:<30} {:>29.4}", "Bootstrap p-value:", self.p_value)?;
+        if let Some((lo, hi)) = self.conf_interval {
+            writeln!(
+                f,
+                "{:<30} {:>29}",
+                format!("{:.0}% CI:", (1.0 - self.alpha) * 100.0),
+                format!("[{:.4}, {:.4}]", lo, hi)
+            )?;
+        }
+        write!(f, "{:=^60}", "")
+    }
+}
+
+/// Wild bootstrap-t and wild cluster bootstrap for linear regressions
+/// (Cameron, Gelbach and Miller, 2008; Roodman et al., 2019).
+///
+/// Bootstrap samples are y* = Xβ̃ + ũ·v, where (β̃, ũ) come from the model
+/// with the null imposed (or the unrestricted fit) and v is drawn once per
+/// cluster (or per observation without clusters). Each replication
+/// computes the same HC1/CR1 t statistic as the original sample.
+pub struct WildBootstrap;
+
+impl WildBootstrap {
+    /// Tests H0: β_`coef` = `null` for the regression of `y` on `x`.
+    ///
+    /// # Arguments
+    /// * `y` - Dependent variable (n × 1)
+    /// * `x` - Design matrix (n × k)
+    /// * `clusters` - Cluster ids (wild cluster bootstrap) or `None`
+    ///   (heteroskedasticity-robust wild bootstrap)
+    /// * `coef` - Index of the tested coefficient
+    /// * `null` - Hypothesised value r
+    /// * `config` - Weights, replications and confidence-interval settings
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::{WildBootstrap, WildBootstrapConfig};
+    /// use ndarray::{Array1, Array2};
+    ///
+    /// let x = Array2::from_shape_fn((40, 2), |(i, j)| if j == 0 { 1.0 } else { (i % 7) as f64 });
+    /// let y = Array1::from_shape_fn(40, |i| 1.0 + 0.5 * (i % 7) as f64 + ((i * 13) % 5) as f64 - 2.0);
+    /// let clusters: Vec<usize> = (0..40).map(|i| i / 4).collect();
+    ///
+    /// let res = WildBootstrap::test(&y, &x, Some(&clusters), 1, 0.0, &WildBootstrapConfig::default())
+    ///     .unwrap();
+    /// assert!(res.p_value < 0.05);
+    /// ```
+    pub fn test(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        clusters: Option<&[usize]>,
+        coef: usize,
+        null: f64,
+        config: &WildBootstrapConfig,
+    ) -> Result<WildBootstrapResult, GreenersError> {
+        Self::test_absorbed(y, x, clusters, coef, null, 0, config)
+    }
+
+    /// As [`test`](Self::test) for a regression on demeaned data, where
+    /// `df_absorbed` fixed-effect parameters were partialled out. They are
+    /// added to K in the small-sample factor, so the t statistic matches the
+    /// estimator's own standard errors.
+    pub fn test_absorbed(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        clusters: Option<&[usize]>,
+        coef: usize,
+        null: f64,
+        df_absorbed: usize,
+        config: &WildBootstrapConfig,
+    ) -> Result<WildBootstrapResult, GreenersError> {
+        let problem = WildProblem::new(y, x, clusters, coef, df_absorbed, config)?;
+        let (estimate, t_stat) = problem.original_t(null);
+        let p_value = problem.p_value(null);
+
+        let conf_interval = if config.confidence_interval {
+            Some(problem.confidence_interval()?)
+        } else {
+            None
+        };
+
+        Ok(WildBootstrapResult {
+            coef_index: coef,
+            null_value: null,
+            estimate,
+            t_stat,
+            p_value,
+            conf_interval,
+            alpha: config.alpha,
+            n_bootstrap: problem.draws.nrows(),
+            n_clusters: problem.clusters.as_ref().map(|_| problem.n_groups),
+            weights: config.weights,
+            impose_null: config.impose_null,
+        })
+    }
+}
+
+struct WildProblem<'a> {
+    y: &'a Array1<f64>,
+    x: &'a Array2<f64>,
+    coef: usize,
+    impose_null: bool,
+    alpha: f64,
+    /// Dense cluster index per observation (`None`: one group per row)
+    clusters: Option<Vec<usize>>,
+    n_groups: usize,
+    /// Auxiliary weights, one row per replication and one column per group
+    draws: Array2<f64>,
+    /// (X'X)⁻¹
+    xtx_inv: Array2<f64>,
+    /// X (X'X)⁻¹ e_j: observation weights of the j-th coefficient
+    w_j: Array1<f64>,
+    beta_hat: Array1<f64>,
+    /// Small-sample factor of the variance estimator
+    scale: f64,
+}
+
+impl<'a> WildProblem<'a> {
+    fn new(
+        y: &'a Array1<f64>,
+        x: &'a Array2<f64>,
+        clusters: Option<&[usize]>,
+        coef: usize,
+        df_absorbed: usize,
+        config: &WildBootstrapConfig,
+    ) -> Result<Self, GreenersError> {
+        let (n, k) = x.dim();
+        if y.len() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "X and y must have same number of rows".to_string(),
+            ));
+        }
+        if coef >= k {
+            return Err(GreenersError::InvalidOperation(format!(
+                "Coefficient index {} out of range for {} regressors",
+                coef, k
+            )));
+        }
+        if n <= k + df_absorbed {
+            return Err(GreenersError::ShapeMismatch(
+                "Not enough observations for the number of regressors".to_string(),
+            ));
+        }
+        if !(config.alpha > 0.0 && config.alpha < 1.0) || config.n_bootstrap == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "alpha must lie in (0, 1) and n_bootstrap must be positive".to_string(),
+            ));
+        }
+
+        let (dense, n_groups) = match clusters {
+            Some(ids) => {
+                if ids.len() != n {
+                    return Err(GreenersError::ShapeMismatch(format!(
+                        "Cluster IDs length ({}) must match number of observations ({})",
+                        ids.len(),
+                        n
+                    )));
+                }
+                let mut map = std::collections::HashMap::new();
+                let dense: Vec<usize> = ids
+                    .iter()
+                    .map(|id| {
+                        let next = map.len();
+                        *map.entry(*id).or_insert(next)
+                    })
+                    .collect();
+                if map.len() < 2 {
+                    return Err(GreenersError::InvalidOperation(
+                        "Wild cluster bootstrap needs at least two clusters".to_string(),
+                    ));
+                }
+                (Some(dense), map.len())
+            }
+            None => (None, n),
+        };
+
+        let nf = n as f64;
+        let kf = (k + df_absorbed) as f64;
+        let scale = match &dense {
+            Some(_) => {
+                let g = n_groups as f64;
+                g / (g - 1.0) * (nf - 1.0) / (nf - kf)
+            }
+            None => nf / (nf - kf),
+        };
+
+        // Full enumeration of Rademacher sign patterns when affordable
+        let draws = if config.weights == WildWeights::Rademacher
+            && n_groups < 30
+            && (1usize << n_groups) <= config.n_bootstrap
+        {
+            let total = 1usize << n_groups;
+            Array2::from_shape_fn((total, n_groups), |(b, g)| {
+                if (b >> g) & 1 == 1 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            })
+        } else {
+            let rng = GreenersRng::new(config.seed);
+            let rows: Vec<Vec<f64>> = (0..config.n_bootstrap)
+                .into_par_iter()
+                .map(|b| {
+                    let mut stream = rng.stream(b as u64);
+                    (0..n_groups)
+                        .map(|_| config.weights.draw(&mut stream))
+                        .collect()
+                })
+                .collect();
+            Array2::from_shape_vec(
+                (config.n_bootstrap, n_groups),
+                rows.into_iter().flatten().collect(),
+            )
+            .map_err(|e| GreenersError::ShapeMismatch(e.to_string()))?
+        };
+
+        let xtx_inv = x.t().dot(x).inv()?;
+        let w_j = x.dot(&xtx_inv.column(coef));
+        let beta_hat = xtx_inv.dot(&x.t().dot(y));
+
+        Ok(Self {
+            y,
+            x,
+            coef,
+            impose_null: config.impose_null,
+            alpha: config.alpha,
+            clusters: dense,
+            n_groups,
+            draws,
+            xtx_inv,
+            w_j,
+            beta_hat,
+            scale,
+        })
+    }
+
+    /// Standard error of coefficient j given residuals `u`.
+    fn std_error(&self, u: &Array1<f64>) -> f64 {
+        let scores = &self.w_j * u;
+        let var = match &self.clusters {
+            Some(ids) => {
+                let mut sums = vec![0.0; self.n_groups];
+                for (s, &g) in scores.iter().zip(ids.iter()) {
+                    sums[g] += s;
+                }
+                sums.iter().map(|s| s * s).sum::<f64>()
+            }
+            None => scores.iter().map(|s| s * s).sum::<f64>(),
+        };
+        (self.scale * var).sqrt()
+    }
+
+    fn original_t(&self, null: f64) -> (f64, f64) {
+        let resid = self.y - &self.x.dot(&self.beta_hat);
+        let estimate = self.beta_hat[self.coef];
+        (estimate, (estimate - null) / self.std_error(&resid))
+    }
+
+    /// Residuals ũ of the bootstrap DGP: restricted least squares under
+    /// β_j = r, or the unrestricted fit.
+    fn dgp_residuals(&self, null: f64) -> Array1<f64> {
+        if !self.impose_null {
+            return self.y - &self.x.dot(&self.beta_hat);
+        }
+        // β̃ = β̂ − (X'X)⁻¹e_j (β̂_j − r) / [(X'X)⁻¹]_jj
+        let a = self.xtx_inv.column(self.coef);
+        let shift = (self.beta_hat[self.coef] - null) / a[self.coef];
+        let beta = &self.beta_hat - &(&a * shift);
+        self.y - &self.x.dot(&beta)
+    }
+
+    fn p_value(&self, null: f64) -> f64 {
+        let (_, t_orig) = self.original_t(null);
+        let resid_dgp = self.dgp_residuals(null);
+        let n = self.y.len();
+        let exceed: usize = (0..self.draws.nrows())
+            .into_par_iter()
+            .filter(|&b| {
+                let v = self.draws.row(b);
+                let e = Array1::from_shape_fn(n, |i| {
+                    let g = match &self.clusters {
+                        Some(ids) => ids[i],
+                        None => i,
+                    };
+                    resid_dgp[i] * v[g]
+                });
+                // β* − β̃ = (X'X)⁻¹X'e and u* = e − X(β* − β̃)
+                let delta = self.xtx_inv.dot(&self.x.t().dot(&e));
+                let resid = &e - &self.x.dot(&delta);
+                // t* = (β*_j − β̃_j) / se*, centred on the DGP value
+                let t_star = delta[self.coef] / self.std_error(&resid);
+                t_star.abs() >= t_orig.abs()
+            })
+            .count();
+        exceed as f64 / self.draws.nrows() as f64
+    }
+
+    /// Inverts the bootstrap test: the bounds are where the p-value crosses
+    /// alpha on either side of the estimate.
+    fn confidence_interval(&self) -> Result<(f64, f64), GreenersError> {
+        let estimate = self.beta_hat[self.coef];
+        let resid = self.y - &self.x.dot(&self.beta_hat);
+        let se = self.std_error(&resid).max(1e-12 * (1.0 + estimate.abs()));
+        let accepts = |r: f64| self.p_value(r) > self.alpha;
+
+        let bound = |direction: f64| -> Result<f64, GreenersError> {
+            let mut inside = estimate;
+            let mut step = 2.0 * se;
+            let mut outside = estimate + direction * step;
+            let mut tries = 0;
+            while accepts(outside) {
+                inside = outside;
+                step *= 2.0;
+                outside = estimate + direction * step;
+                tries += 1;
+                if tries > 40 {
+                    return Ok(direction * f64::INFINITY);
+                }
+            }
+            for _ in 0..60 {
+                let mid = 0.5 * (inside + outside);
+                if accepts(mid) {
+                    inside = mid;
+                } else {
+                    outside = mid;
+                }
+                if (outside - inside).abs() <= 1e-8 * se {
+                    break;
+                }
+            }
+            Ok(0.5 * (inside + outside))
+        };
+
+        Ok((bound(-1.0)?, bound(1.0)?))
+    }
+}
+
 /// Hypothesis testing methods
 pub struct HypothesisTest;
 
@@ -14,7 +14,14 @@ impl BSplineBasis {
         df: usize,
         degree: usize,
     ) -> Result<Array2<f64>, GreenersError> {
-        let n = x.len();
+        let knots = Self::knots(x, df, degree)?;
+        Self::generate_with_knots(x, df, degree, &knots)
+    }
+
+    /// Full knot sequence used by `generate`: boundary knots slightly outside
+    /// the range of `x`, repeated (degree + 1) times, with equally spaced
+    /// interior knots.
+    pub fn knots(x: &Array1<f64>, df: usize, degree: usize) -> Result<Vec<f64>, GreenersError> {
         if df < degree + 1 {
             return Err(GreenersError::InvalidOperation(
                 "df must be >= degree + 1".into(),
@@ -22,8 +29,21 @@ impl BSplineBasis {
         }
 
         let n_knots = df - degree + 1;
-        let x_min = x.iter().cloned().fold(f64::INFINITY, f64::min);
-        let x_max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let x_min = x
+            .iter()
+            .cloned()
+            .filter(|v| v.is_finite())
+            .fold(f64::INFINITY, f64::min);
+        let x_max = x
+            .iter()
+            .cloned()
+            .filter(|v| v.is_finite())
+            .fold(f64::NEG_INFINITY, f64::max);
+        if !x_min.is_finite() || !x_max.is_finite() {
+            return Err(GreenersError::InvalidOperation(
+                "B-spline knots require at least one finite value".into(),
+            ));
+        }
         let range = (x_max - x_min).max(1e-10);
 
         // Interior knots (equally spaced)
@@ -41,12 +61,39 @@ impl BSplineBasis {
             knots.push(x_max + 0.01 * range);
         }
 
+        Ok(knots)
+    }
+
+    /// Evaluate the B-spline basis on a fixed knot sequence (e.g. knots
+    /// computed on a training sample). Values outside the boundary knots get
+    /// an all-zero row.
+    pub fn generate_with_knots(
+        x: &Array1<f64>,
+        df: usize,
+        degree: usize,
+        knots: &[f64],
+    ) -> Result<Array2<f64>, GreenersError> {
+        if knots.len() != df + degree + 1 {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "B-spline with df={} and degree={} needs {} knots, got {}",
+                df,
+                degree,
+                df + degree + 1,
+                knots.len()
+            )));
+        }
+
         //Evaluate B-spline bass using Boor's algorithm
+        let n = x.len();
         let mut basis = Array2::<f64>::zeros((n, df));
 
         for (idx, &xi) in x.iter().enumerate() {
+            if xi.is_nan() {
+                basis.row_mut(idx).fill(f64::NAN);
+                continue;
+            }
             for j in 0..df {
-                basis[[idx, j]] = bspline_basis(j, degree, xi, &knots);
+                basis[[idx, j]] = bspline_basis(j, degree, xi, knots);
             }
         }
 
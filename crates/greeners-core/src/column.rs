@@ -25,6 +25,10 @@ pub struct CategoricalColumn {
 }
 
 impl CategoricalColumn {
+    /// Code of a missing value. It has no level, so design matrices treat
+    /// the row as missing.
+    pub const MISSING_CODE: u32 = u32::MAX;
+
     /// Create a new categorical column from string values
     pub fn from_strings(values: Vec<String>) -> Self {
         let mut levels = Vec::new();
@@ -167,6 +171,10 @@ pub enum Column {
 }
 
 impl Column {
+    /// Placeholder for a missing datetime. Design matrices treat the row as
+    /// missing and `to_float` maps it to NaN.
+    pub const MISSING_DATETIME: NaiveDateTime = NaiveDateTime::MIN;
+
     /// Get the data type of this column
     pub fn dtype(&self) -> DataType {
         match self {
@@ -268,7 +276,7 @@ impl Column {
         }
     }
 
-    /// Convert to float array (categorical -> codes as f64, bool -> 1.0/0.0, int -> f64, datetime -> timestamp, string -> NaN)
+    /// Convert to float array (categorical -> codes as f64, bool -> 1.0/0.0, int -> f64, datetime -> timestamp or NaN if missing, string -> NaN)
     pub fn to_float(&self) -> Array1<f64> {
         match self {
             Column::Float(arr) => arr.clone(),
@@ -281,7 +289,13 @@ impl Column {
             Column::Int(arr) => Array1::from(arr.iter().map(|&i| i as f64).collect::<Vec<_>>()),
             Column::DateTime(arr) => Array1::from(
                 arr.iter()
-                    .map(|dt| dt.and_utc().timestamp() as f64)
+                    .map(|dt| {
+                        if *dt == Self::MISSING_DATETIME {
+                            f64::NAN
+                        } else {
+                            dt.and_utc().timestamp() as f64
+                        }
+                    })
                     .collect::<Vec<_>>(),
             ),
             Column::String(arr) => Array1::from(vec![f64::NAN; arr.len()]),
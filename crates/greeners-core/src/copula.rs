@@ -21,11 +21,12 @@ use crate::linalg::LinalgDeterminant as _;
 use crate::linalg::LinalgInverse as _;
 use crate::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Copula types supported.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CopulaType {
     Gaussian,
     Clayton,
@@ -45,7 +46,7 @@ impl fmt::Display for CopulaType {
 }
 
 /// Result of copula fitting.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CopulaResult {
     /// Copula type
     pub copula_type: CopulaType,
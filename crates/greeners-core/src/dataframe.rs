@@ -1,6 +1,9 @@
 use crate::{
-    bspline::BSplineBasis, column::CategoricalColumn, column::Column, formula::Formula,
-    GreenersError,
+    column::CategoricalColumn,
+    column::Column,
+    design::{DesignInfo, DesignMatrices, MissingPolicy, RowMap},
+    formula::Formula,
+    GreenersError, GreenersRng,
 };
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
@@ -117,6 +120,41 @@ impl Default for TypeInferenceConfig {
     }
 }
 
+/// Key uniqueness check for [`DataFrame::merge_with_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeValidate {
+    /// No check (default)
+    #[default]
+    ManyToMany,
+    /// Keys must be unique in both DataFrames
+    OneToOne,
+    /// Keys must be unique in the left DataFrame
+    OneToMany,
+    /// Keys must be unique in the right DataFrame
+    ManyToOne,
+}
+
+/// Options for [`DataFrame::merge_with_config`].
+#[derive(Debug, Clone)]
+pub struct MergeConfig {
+    /// Suffixes appended to non-key columns present in both DataFrames
+    /// (left, right).
+    /// Default: ("_x", "_y")
+    pub suffixes: (String, String),
+    /// Uniqueness check applied to the join keys before merging.
+    /// Default: `MergeValidate::ManyToMany` (no check)
+    pub validate: MergeValidate,
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        MergeConfig {
+            suffixes: ("_x".into(), "_y".into()),
+            validate: MergeValidate::ManyToMany,
+        }
+    }
+}
+
 impl DataFrame {
     /// Create a new DataFrame from a IndexMap of column names to data arrays.
     ///
@@ -576,75 +614,19 @@ impl DataFrame {
         self.columns.keys().cloned().collect()
     }
 
-    /// Count the actual number of columns in the design matrix
-    /// (accounting for categorical variable expansion)
-    fn count_design_matrix_cols(&self, formula: &Formula) -> Result<usize, GreenersError> {
-        let mut count = 0;
-
-        // Intercept
-        if formula.intercept {
-            count += 1;
-        }
-
-        // Independent variables
-        for var_name in &formula.independents {
-            if var_name.starts_with("C(") && var_name.ends_with(')') {
-                // Categorical: count unique values - 1 (drop first)
-                let var = var_name[2..var_name.len() - 1].trim();
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-
-                use std::collections::BTreeSet;
-                let unique_vals: BTreeSet<i32> =
-                    col_data.iter().map(|&v| v.round() as i32).collect();
-
-                let n_categories = unique_vals.len();
-                if n_categories < 2 {
-                    return Err(GreenersError::FormulaError(format!(
-                        "Categorical variable '{}' must have at least 2 categories",
-                        var
-                    )));
-                }
-
-                count += n_categories - 1; // Drop first category
-            } else if var_name.starts_with("log(")
-                || var_name.starts_with("exp(")
-                || var_name.starts_with("sqrt(")
-            {
-                count += 1;
-            } else if var_name.starts_with("poly(") && var_name.ends_with(')') {
-                // poly(var, degree) => degree columns
-                let inner = &var_name[5..var_name.len() - 1];
-                let parts: Vec<&str> = inner.split(',').collect();
-                if parts.len() == 2 {
-                    if let Ok(deg) = parts[1].trim().parse::<usize>() {
-                        count += deg;
-                    } else {
-                        count += 1;
-                    }
-                } else {
-                    count += 1;
-                }
-            } else if var_name.starts_with("bs(") && var_name.ends_with(')') {
-                // bs(var, df) => df columns
-                let inner = &var_name[3..var_name.len() - 1];
-                let parts: Vec<&str> = inner.split(',').collect();
-                if parts.len() >= 2 {
-                    if let Ok(df) = parts[1].trim().parse::<usize>() {
-                        count += df;
-                    } else {
-                        count += 1;
-                    }
-                } else {
-                    count += 1;
-                }
-            } else {
-                // Regular, interaction, or polynomial: 1 column each
-                count += 1;
-            }
-        }
+    /// Build variable names list from a Formula, expanding C(var) into per-category names.
+    /// - Categorical column (string labels): produces `var=label` for each non-reference level.
+    /// - Numeric column: produces `C(var)_N` for each non-reference numeric code.
+    /// - `poly()` / `bs()`: one indexed name per basis column, e.g. `poly(x, 2)[1]`.
+    pub fn formula_var_names(&self, formula: &Formula) -> Result<Vec<String>, GreenersError> {
+        Ok(DesignInfo::from_formula(formula, self)?.column_names())
+    }
 
-        Ok(count)
+    /// Learn the design (categorical levels, spline knots, ...) of `formula`
+    /// on this DataFrame. See `DesignInfo` for rebuilding the same columns on
+    /// new data.
+    pub fn design_info(&self, formula: &Formula) -> Result<DesignInfo, GreenersError> {
+        DesignInfo::from_formula(formula, self)
     }
 
     /// Build design matrix (X) and response vector (y) from a Formula.
@@ -668,279 +650,92 @@ impl DataFrame {
     /// assert_eq!(y.len(), 3);
     /// assert_eq!(x.shape(), &[3, 3]); // 3 rows, 3 cols (intercept + x1 + x2)
     /// ```
-    /// Build variable names list from a Formula, expanding C(var) into per-category names.
-    /// - Categorical column (string labels): produces `var=label` for each non-reference level.
-    /// - Numeric column: produces `C(var)_N` for each non-reference numeric code.
-    pub fn formula_var_names(&self, formula: &Formula) -> Result<Vec<String>, GreenersError> {
-        let mut names = Vec::new();
-        if formula.intercept {
-            names.push("const".to_string());
-        }
-        for var in &formula.independents {
-            if var.starts_with("C(") && var.ends_with(')') {
-                let col_name = var[2..var.len() - 1].trim();
-                let col = self.get_column(col_name)?;
-
-                match col {
-                    Column::Categorical(cat) => {
-                        // Use string level names: skip levels[0] (reference)
-                        for level in cat.levels.iter().skip(1) {
-                            names.push(format!("{}={}", col_name, level));
-                        }
-                    }
-                    _ => {
-                        // Numeric column: use sorted integer codes
-                        let col_data = col.to_float();
-                        use std::collections::BTreeSet;
-                        let mut categories: Vec<i32> = col_data
-                            .iter()
-                            .map(|&v| v.round() as i32)
-                            .collect::<BTreeSet<_>>()
-                            .into_iter()
-                            .collect();
-                        categories.sort();
-                        for &cat in categories.iter().skip(1) {
-                            names.push(format!("{}_{}", var, cat));
-                        }
-                    }
-                }
-            } else {
-                names.push(var.clone());
-            }
-        }
-        Ok(names)
-    }
-
     pub fn to_design_matrix(
         &self,
         formula: &Formula,
     ) -> Result<(Array1<f64>, Array2<f64>), GreenersError> {
-        // Extract y (dependent variable) - convert any type to float
-        let y_column = self.get_column(&formula.dependent)?;
-        let y = y_column.to_float();
-
-        // Build X matrix
-        let n_rows = self.n_rows;
-
-        // Count actual number of columns (accounting for categorical expansion)
-        let actual_n_cols = self.count_design_matrix_cols(formula)?;
-        let mut x_mat = Array2::<f64>::zeros((n_rows, actual_n_cols));
+        DesignInfo::from_formula(formula, self)?.build(self)
+    }
 
-        let mut col_idx = 0;
+    /// Build the design of `formula` after applying a missing-value policy.
+    ///
+    /// With `MissingPolicy::Drop`, rows with a missing value in any variable
+    /// the formula references are removed and the design (levels, knots,
+    /// centering) is learned on the remaining rows. The returned `RowMap`
+    /// records which rows were dropped. `to_design_matrix` behaves like
+    /// `MissingPolicy::Keep`.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::{DataFrame, Formula, MissingPolicy};
+    ///
+    /// let df = DataFrame::builder()
+    ///     .add_column("y", vec![1.0, 2.0, f64::NAN, 4.0])
+    ///     .add_column("x", vec![1.0, f64::NAN, 3.0, 4.0])
+    ///     .build()
+    ///     .unwrap();
+    /// let formula = Formula::parse("y ~ x").unwrap();
+    ///
+    /// let dm = df.design_matrices(&formula, MissingPolicy::Drop).unwrap();
+    /// assert_eq!(dm.x.nrows(), 2);
+    /// assert_eq!(dm.rows.dropped, vec![1, 2]);
+    ///
+    /// assert!(df.design_matrices(&formula, MissingPolicy::Raise).is_err());
+    /// ```
+    pub fn design_matrices(
+        &self,
+        formula: &Formula,
+        missing: MissingPolicy,
+    ) -> Result<DesignMatrices, GreenersError> {
+        self.design_matrices_with_mask(formula, missing, &[])
+    }
 
-        // Add intercept if requested
-        if formula.intercept {
-            for i in 0..n_rows {
-                x_mat[[i, col_idx]] = 1.0;
+    /// Like [`design_matrices`](Self::design_matrices), but rows listed in
+    /// `extra_missing` are treated as missing as well, e.g. rows whose weight
+    /// or id is missing. They are ignored under `MissingPolicy::Keep`.
+    pub fn design_matrices_with_mask(
+        &self,
+        formula: &Formula,
+        missing: MissingPolicy,
+        extra_missing: &[usize],
+    ) -> Result<DesignMatrices, GreenersError> {
+        let info = DesignInfo::from_formula(formula, self)?;
+        let dropped = match missing {
+            MissingPolicy::Keep => Vec::new(),
+            _ => {
+                let mut rows = info.missing_rows(self)?;
+                rows.extend(extra_missing.iter().copied().filter(|&i| i < self.n_rows));
+                rows.sort_unstable();
+                rows.dedup();
+                rows
             }
-            col_idx += 1;
+        };
+        if dropped.is_empty() {
+            let (y, x) = info.build(self)?;
+            return Ok(DesignMatrices {
+                y,
+                x,
+                info,
+                rows: RowMap::new(self.n_rows, dropped),
+            });
         }
-
-        // Add independent variables (including interactions, categorical, polynomial)
-        for var_name in &formula.independents {
-            // Check for categorical variable: C(var)
-            if var_name.starts_with("C(") && var_name.ends_with(')') {
-                // Extract variable name from C(var)
-                let var = var_name[2..var_name.len() - 1].trim();
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-
-                // Get unique values (categories)
-                use std::collections::BTreeSet;
-                let unique_vals: BTreeSet<i32> =
-                    col_data.iter().map(|&v| v.round() as i32).collect();
-
-                let mut categories: Vec<i32> = unique_vals.into_iter().collect();
-                categories.sort();
-
-                // Create dummies (drop first category for identification)
-                // e.g., if categories are [0, 1, 2], create dummies for 1 and 2
-                if categories.len() < 2 {
-                    return Err(GreenersError::FormulaError(format!(
-                        "Categorical variable '{}' must have at least 2 categories",
-                        var
-                    )));
-                }
-
-                // Skip first category (reference level)
-                for &cat_val in categories.iter().skip(1) {
-                    for i in 0..n_rows {
-                        x_mat[[i, col_idx]] = if (col_data[i].round() as i32) == cat_val {
-                            1.0
-                        } else {
-                            0.0
-                        };
-                    }
-                    col_idx += 1;
-                }
-
-                continue; // Skip col_idx increment at end since we handled it
-            }
-
-            // Check for polynomial term: I(expr)
-            if var_name.starts_with("I(") && var_name.ends_with(')') {
-                // Extract expression from I(...)
-                let expr = var_name[2..var_name.len() - 1].trim();
-
-                // Parse simple expressions: var^power or var**power
-                if expr.find('^').or_else(|| expr.find("**")).is_some() {
-                    let var_part;
-                    let power_part;
-
-                    if expr.contains("**") {
-                        let parts: Vec<&str> = expr.split("**").collect();
-                        if parts.len() != 2 {
-                            return Err(GreenersError::FormulaError(format!(
-                                "Invalid polynomial expression '{}'",
-                                expr
-                            )));
-                        }
-                        var_part = parts[0].trim();
-                        power_part = parts[1].trim();
-                    } else {
-                        let parts: Vec<&str> = expr.split('^').collect();
-                        if parts.len() != 2 {
-                            return Err(GreenersError::FormulaError(format!(
-                                "Invalid polynomial expression '{}'",
-                                expr
-                            )));
-                        }
-                        var_part = parts[0].trim();
-                        power_part = parts[1].trim();
-                    }
-
-                    let column = self.get_column(var_part)?;
-                    let col_data = column.to_float();
-                    let power: i32 = power_part.parse().map_err(|_| {
-                        GreenersError::FormulaError(format!(
-                            "Invalid power in expression '{}'",
-                            expr
-                        ))
-                    })?;
-
-                    // Compute x^power
-                    for i in 0..n_rows {
-                        x_mat[[i, col_idx]] = col_data[i].powi(power);
-                    }
-                } else {
-                    return Err(GreenersError::FormulaError(format!(
-                        "I() expression must contain ^ or **: '{}'",
-                        expr
-                    )));
-                }
-            }
-            // Function transforms: log(var), exp(var), sqrt(var)
-            else if var_name.starts_with("log(") && var_name.ends_with(')') {
-                let var = &var_name[4..var_name.len() - 1].trim();
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-                for i in 0..n_rows {
-                    x_mat[[i, col_idx]] = col_data[i].ln();
-                }
-            } else if var_name.starts_with("exp(") && var_name.ends_with(')') {
-                let var = &var_name[4..var_name.len() - 1].trim();
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-                for i in 0..n_rows {
-                    x_mat[[i, col_idx]] = col_data[i].exp();
-                }
-            } else if var_name.starts_with("sqrt(") && var_name.ends_with(')') {
-                let var = &var_name[5..var_name.len() - 1].trim();
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-                for i in 0..n_rows {
-                    x_mat[[i, col_idx]] = col_data[i].sqrt();
-                }
-            } else if var_name.starts_with("poly(") && var_name.ends_with(')') {
-                let inner = &var_name[5..var_name.len() - 1];
-                let parts: Vec<&str> = inner.split(',').collect();
-                if parts.len() != 2 {
-                    return Err(GreenersError::FormulaError(format!(
-                        "poly() requires 2 arguments: poly(var, degree), got '{}'",
-                        var_name
-                    )));
-                }
-                let var = parts[0].trim();
-                let degree: usize = parts[1].trim().parse().map_err(|_| {
-                    GreenersError::FormulaError(format!(
-                        "Invalid degree in poly(): '{}'",
-                        parts[1].trim()
-                    ))
-                })?;
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-                for d in 1..=degree {
-                    for i in 0..n_rows {
-                        x_mat[[i, col_idx]] = col_data[i].powi(d as i32);
-                    }
-                    col_idx += 1;
-                }
-                continue; // already incremented col_idx
-            } else if var_name.starts_with("bs(") && var_name.ends_with(')') {
-                let inner = &var_name[3..var_name.len() - 1];
-                let parts: Vec<&str> = inner.split(',').collect();
-                if parts.len() < 2 {
-                    return Err(GreenersError::FormulaError(format!(
-                        "bs() requires at least 2 arguments: bs(var, df), got '{}'",
-                        var_name
-                    )));
-                }
-                let var = parts[0].trim();
-                let df: usize = parts[1].trim().parse().map_err(|_| {
-                    GreenersError::FormulaError(format!(
-                        "Invalid df in bs(): '{}'",
-                        parts[1].trim()
-                    ))
-                })?;
-                let degree: usize = if parts.len() >= 3 {
-                    parts[2].trim().parse().unwrap_or(3)
-                } else {
-                    3
-                };
-                let column = self.get_column(var)?;
-                let col_data = column.to_float();
-                // Generate B-spline basis using equally spaced knots
-                let basis = BSplineBasis::generate(&col_data, df, degree)?;
-                for j in 0..df {
-                    for i in 0..n_rows {
-                        x_mat[[i, col_idx]] = basis[[i, j]];
-                    }
-                    col_idx += 1;
-                }
-                continue; // already incremented col_idx
-            }
-            // Check if this is an interaction term (contains ':')
-            else if var_name.contains(':') {
-                // Parse interaction: "x1:x2"
-                let parts: Vec<&str> = var_name.split(':').collect();
-                if parts.len() != 2 {
-                    return Err(GreenersError::FormulaError(format!(
-                        "Invalid interaction term '{}'",
-                        var_name
-                    )));
-                }
-
-                let column1 = self.get_column(parts[0].trim())?;
-                let var1 = column1.to_float();
-                let column2 = self.get_column(parts[1].trim())?;
-                let var2 = column2.to_float();
-
-                // Compute interaction: elementwise multiplication
-                for i in 0..n_rows {
-                    x_mat[[i, col_idx]] = var1[i] * var2[i];
-                }
-            } else {
-                // Regular variable - convert any type to float
-                let column = self.get_column(var_name)?;
-                let col_data = column.to_float();
-                for i in 0..n_rows {
-                    x_mat[[i, col_idx]] = col_data[i];
-                }
-            }
-            col_idx += 1;
+        if missing == MissingPolicy::Raise {
+            return Err(GreenersError::InvalidOperation(format!(
+                "{} row(s) have missing values in variables of '{}' (first at row {})",
+                dropped.len(),
+                formula.dependent,
+                dropped[0]
+            )));
         }
-
-        Ok((y, x_mat))
+        let subset = self.drop_rows(&dropped)?;
+        let info = DesignInfo::from_formula(formula, &subset)?;
+        let (y, x) = info.build(&subset)?;
+        Ok(DesignMatrices {
+            y,
+            x,
+            info,
+            rows: RowMap::new(self.n_rows, dropped),
+        })
     }
 
     /// Insert or update a Float column.
@@ -1269,6 +1064,10 @@ impl DataFrame {
     /// * `path` - Path to the CSV file
     /// * `config` - Type inference configuration (see `TypeInferenceConfig`)
     /// * `columns` - Optional column projection (only load these columns)
+    /// * `delimiter` - Field delimiter (default: `b','`)
+    /// * `predicate` - Optional row filter applied while reading, before type
+    ///   inference (see `RowPredicate`). It may reference columns outside the
+    ///   projection and is type-checked against `config.column_types`.
     ///
     /// # Examples
     /// ```no_run
@@ -1280,6 +1079,12 @@ impl DataFrame {
     /// config.column_types.insert("price".into(), ColumnType::Float);
     ///
     /// let df = DataFrame::from_csv_with_config("data.csv", config, None, None, None).unwrap();
+    ///
+    /// // Filter while loading
+    /// use greeners_core::RowPredicate;
+    /// let pred = RowPredicate::parse("price > 10 and ticker in ['AAPL', 'MSFT']").unwrap();
+    /// let config = TypeInferenceConfig::default();
+    /// let df = DataFrame::from_csv_with_config("data.csv", config, None, None, Some(&pred)).unwrap();
     /// ```
     pub fn from_csv_with_config<P: AsRef<Path>>(
         path: P,
@@ -1330,6 +1135,29 @@ impl DataFrame {
             raw_columns.insert(name.clone(), Vec::new());
         }
 
+        // Resolve and type-check the predicate once. Only the referenced
+        // fields are copied out of each record, into reused buffers.
+        let predicate = match predicate {
+            Some(pred) => {
+                let pred_cols = pred.referenced_columns();
+                let header_layout: Vec<(usize, String)> =
+                    all_names.iter().cloned().enumerate().collect();
+                pred.check_layout(&header_layout, &config.column_types)?;
+                let pred_src: Vec<usize> = pred_cols
+                    .iter()
+                    .map(|c| all_names.iter().position(|n| n == c).unwrap())
+                    .collect();
+                let pred_layout: Vec<(usize, String)> = pred_cols.into_iter().enumerate().collect();
+                let bound = pred.clone().with_null_values(config.null_values.clone());
+                Some((bound, pred_src, pred_layout))
+            }
+            None => None,
+        };
+        let mut pred_fields: Vec<String> = predicate
+            .as_ref()
+            .map(|(_, src, _)| vec![String::new(); src.len()])
+            .unwrap_or_default();
+
         // Read all records as strings first
         for result in reader.records() {
             let record = result.map_err(|e| {
@@ -1337,23 +1165,14 @@ impl DataFrame {
             })?;
 
             // Apply row predicate if provided
-            if let Some(pred) = predicate {
-                // Build row for predicate evaluation
-                let row_data: Vec<String> = record.iter().map(|f| f.to_string()).collect();
-                let pred_cols = pred.referenced_columns();
-                let pred_idx: Vec<usize> = pred_cols
-                    .iter()
-                    .map(|c| all_names.iter().position(|n| n == c).unwrap())
-                    .collect();
-                let pred_layout: Vec<(usize, String)> = pred_idx
-                    .iter()
-                    .copied()
-                    .zip(pred_cols.iter().cloned())
-                    .collect();
-
+            if let Some((pred, pred_src, pred_layout)) = &predicate {
+                for (buf, &src_i) in pred_fields.iter_mut().zip(pred_src) {
+                    buf.clear();
+                    buf.push_str(record.get(src_i).unwrap_or(""));
+                }
                 let row = crate::predicate::DsvRow {
-                    fields: &row_data,
-                    layout: &pred_layout,
+                    fields: &pred_fields,
+                    layout: pred_layout,
                 };
                 if !pred.evaluate(&row) {
                     continue;
@@ -2912,7 +2731,8 @@ impl DataFrame {
         Ok(cov_matrix)
     }
 
-    /// Sample n random rows from the DataFrame.
+    /// Sample n random rows from the DataFrame without replacement.
+    /// `seed: None` uses the default seed.
     ///
     /// # Examples
     /// ```
@@ -2924,13 +2744,10 @@ impl DataFrame {
     ///     .build()
     ///     .unwrap();
     ///
-    /// let sample = df.sample(3).unwrap();
+    /// let sample = df.sample(3, Some(7)).unwrap();
     /// assert_eq!(sample.n_rows(), 3);
     /// ```
-    pub fn sample(&self, n: usize) -> Result<Self, GreenersError> {
-        use rand::seq::SliceRandom;
-        use rand::thread_rng;
-
+    pub fn sample(&self, n: usize, seed: Option<u64>) -> Result<Self, GreenersError> {
         if n > self.n_rows {
             return Err(GreenersError::ShapeMismatch(format!(
                 "Cannot sample {} rows from DataFrame with {} rows",
@@ -2938,9 +2755,9 @@ impl DataFrame {
             )));
         }
 
-        let mut rng = thread_rng();
+        let mut rng = GreenersRng::new(seed);
         let mut indices: Vec<usize> = (0..self.n_rows).collect();
-        indices.shuffle(&mut rng);
+        rng.shuffle(&mut indices);
         let sample_indices = &indices[..n];
 
         // Use iloc to select the sampled rows
@@ -3605,6 +3422,8 @@ impl DataFrame {
 
     /// Merge (join) two DataFrames on a common column.
     ///
+    /// Shorthand for [`merge_on`](Self::merge_on) with a single key column.
+    ///
     /// # Arguments
     /// * `other` - The DataFrame to merge with
     /// * `on` - The column name to join on
@@ -3631,268 +3450,332 @@ impl DataFrame {
     /// assert_eq!(merged.n_rows(), 2); // Only rows with id 2 and 3
     /// ```
     pub fn merge(&self, other: &DataFrame, on: &str, how: &str) -> Result<Self, GreenersError> {
-        // Validate join column exists in both DataFrames
-        if !self.has_column(on) {
-            return Err(GreenersError::VariableNotFound(format!(
-                "Join column '{}' not found in left DataFrame",
-                on
-            )));
+        self.merge_on(other, &[on], how)
+    }
+
+    /// Merge (join) two DataFrames on one or more key columns.
+    ///
+    /// Keys are compared in their native type: integers exactly, strings and
+    /// categoricals by label (the two may be mixed), datetimes to the second
+    /// and below. Integer and float keys may be mixed and are then compared as
+    /// floats. NaN keys never match. Overlapping non-key columns get the
+    /// default suffixes `_x` / `_y`; see [`merge_with_config`](Self::merge_with_config).
+    ///
+    /// Rows without a match are filled with NaN for float columns,
+    /// [`CategoricalColumn::MISSING_CODE`] for categorical columns (which keep
+    /// their levels and level order), "NA" for string columns and
+    /// [`Column::MISSING_DATETIME`] for datetime columns, so the formula
+    /// interfaces see the gaps as missing. Int and bool columns that need a
+    /// fill are promoted to float so the gap can be NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::DataFrame;
+    ///
+    /// let panel = DataFrame::builder()
+    ///     .add_categorical("state", vec!["SP".into(), "SP".into(), "RJ".into()])
+    ///     .add_int("year", vec![2020, 2021, 2020])
+    ///     .add_column("gdp", vec![1.0, 1.1, 0.8])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let pop = DataFrame::builder()
+    ///     .add_categorical("state", vec!["SP".into(), "RJ".into()])
+    ///     .add_int("year", vec![2021, 2020])
+    ///     .add_column("pop", vec![46.0, 17.0])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let merged = panel.merge_on(&pop, &["state", "year"], "left").unwrap();
+    /// assert_eq!(merged.n_rows(), 3);
+    /// assert!(merged.get("pop").unwrap()[0].is_nan());
+    /// assert_eq!(merged.get("pop").unwrap()[1], 46.0);
+    /// ```
+    pub fn merge_on(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+        how: &str,
+    ) -> Result<Self, GreenersError> {
+        self.merge_with_config(other, on, how, &MergeConfig::default())
+    }
+
+    /// Merge (join) two DataFrames with explicit suffixes and key validation.
+    ///
+    /// # Arguments
+    /// * `other` - The DataFrame to merge with
+    /// * `on` - Key columns present in both DataFrames
+    /// * `how` - Join type: "inner", "left", "right", or "outer"
+    /// * `config` - Suffixes for overlapping columns and the uniqueness check
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::{DataFrame, MergeConfig, MergeValidate};
+    ///
+    /// let firms = DataFrame::builder()
+    ///     .add_int("firm", vec![1, 2, 2])
+    ///     .add_column("value", vec![1.0, 2.0, 3.0])
+    ///     .build()
+    ///     .unwrap();
+    /// let sectors = DataFrame::builder()
+    ///     .add_int("firm", vec![1, 2])
+    ///     .add_column("value", vec![10.0, 20.0])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let config = MergeConfig {
+    ///     suffixes: ("_firm".into(), "_sector".into()),
+    ///     validate: MergeValidate::ManyToOne,
+    /// };
+    /// let merged = firms.merge_with_config(&sectors, &["firm"], "inner", &config).unwrap();
+    /// assert_eq!(merged.get("value_sector").unwrap()[2], 20.0);
+    ///
+    /// // The reverse direction is one-to-many and fails validation
+    /// assert!(sectors.merge_with_config(&firms, &["firm"], "inner", &config).is_err());
+    /// ```
+    pub fn merge_with_config(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+        how: &str,
+        config: &MergeConfig,
+    ) -> Result<Self, GreenersError> {
+        if on.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "merge requires at least one join column".to_string(),
+            ));
         }
-        if !other.has_column(on) {
-            return Err(GreenersError::VariableNotFound(format!(
-                "Join column '{}' not found in right DataFrame",
-                on
+        if !matches!(how, "inner" | "left" | "right" | "outer") {
+            return Err(GreenersError::FormulaError(format!(
+                "Unknown join type '{}'. Use 'inner', 'left', 'right', or 'outer'",
+                how
             )));
         }
 
-        // Convert keys to float for comparison
-        let left_key = self.get_column(on)?.to_float();
-        let right_key = other.get_column(on)?.to_float();
+        let left_keys = self.join_key_rows(other, on)?;
+        let right_keys = other.join_key_rows(self, on)?;
 
-        // Convert all columns to float arrays for indexing
-        let left_arrays: IndexMap<String, Array1<f64>> = self
-            .columns
-            .iter()
-            .map(|(name, col)| (name.clone(), col.to_float()))
-            .collect();
-        let right_arrays: IndexMap<String, Array1<f64>> = other
-            .columns
-            .iter()
-            .map(|(name, col)| (name.clone(), col.to_float()))
-            .collect();
+        match config.validate {
+            MergeValidate::ManyToMany => {}
+            MergeValidate::OneToOne => {
+                check_unique_keys(&left_keys, "left", "one_to_one")?;
+                check_unique_keys(&right_keys, "right", "one_to_one")?;
+            }
+            MergeValidate::OneToMany => check_unique_keys(&left_keys, "left", "one_to_many")?,
+            MergeValidate::ManyToOne => check_unique_keys(&right_keys, "right", "many_to_one")?,
+        }
 
-        // Build result columns
-        let mut result_data: IndexMap<String, Vec<f64>> = IndexMap::new();
+        let rows = match how {
+            "right" => join_rows(&right_keys, &left_keys, true, false)
+                .into_iter()
+                .map(|(j, i)| (i, j))
+                .collect(),
+            _ => join_rows(&left_keys, &right_keys, how != "inner", how == "outer"),
+        };
 
-        // Initialize all columns
-        for col_name in self.columns.keys() {
-            result_data.insert(col_name.clone(), Vec::new());
+        let left_rows: Vec<Option<usize>> = rows.iter().map(|&(i, _)| i).collect();
+        let right_rows: Vec<Option<usize>> = rows.iter().map(|&(_, j)| j).collect();
+
+        let mut result = IndexMap::new();
+        for (name, col) in &self.columns {
+            if on.contains(&name.as_str()) {
+                let merged = coalesce_rows(col, &other.columns[name], &rows);
+                result.insert(name.clone(), merged);
+            } else {
+                let out_name = if other.columns.contains_key(name) {
+                    format!("{}{}", name, config.suffixes.0)
+                } else {
+                    name.clone()
+                };
+                insert_merged_column(&mut result, out_name, take_rows(col, &left_rows))?;
+            }
         }
-        for col_name in other.columns.keys() {
-            if col_name != on {
-                result_data.insert(col_name.clone(), Vec::new());
+        for (name, col) in &other.columns {
+            if on.contains(&name.as_str()) {
+                continue;
             }
+            let out_name = if self.columns.contains_key(name) {
+                format!("{}{}", name, config.suffixes.1)
+            } else {
+                name.clone()
+            };
+            insert_merged_column(&mut result, out_name, take_rows(col, &right_rows))?;
         }
 
-        match how {
-            "inner" => {
-                // Inner join: only rows with matching keys
-                for i in 0..self.n_rows {
-                    let left_val = left_key[i];
-                    for j in 0..other.n_rows {
-                        if (left_val - right_key[j]).abs() < 1e-10 {
-                            // Match found
-                            for (col_name, col_data) in &left_arrays {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[i]);
-                            }
-                            for (col_name, col_data) in &right_arrays {
-                                if col_name != on {
-                                    result_data
-                                        .entry(col_name.to_string())
-                                        .or_default()
-                                        .push(col_data[j]);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            "left" => {
-                // Left join: all rows from left, matched from right
-                for i in 0..self.n_rows {
-                    let left_val = left_key[i];
-                    let mut found = false;
-
-                    for j in 0..other.n_rows {
-                        if (left_val - right_key[j]).abs() < 1e-10 {
-                            found = true;
-                            for (col_name, col_data) in &left_arrays {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[i]);
-                            }
-                            for (col_name, col_data) in &right_arrays {
-                                if col_name != on {
-                                    result_data
-                                        .entry(col_name.to_string())
-                                        .or_default()
-                                        .push(col_data[j]);
-                                }
-                            }
-                        }
-                    }
+        DataFrame::from_columns(result)
+    }
 
-                    if !found {
-                        // No match: include left row with NaN for right columns
-                        for (col_name, col_data) in &left_arrays {
-                            result_data
-                                .entry(col_name.to_string())
-                                .or_default()
-                                .push(col_data[i]);
-                        }
-                        for col_name in other.columns.keys() {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(f64::NAN);
-                            }
-                        }
-                    }
-                }
-            }
-            "right" => {
-                // Right join: all rows from right, matched from left
-                for j in 0..other.n_rows {
-                    let right_val = right_key[j];
-                    let mut found = false;
-
-                    for i in 0..self.n_rows {
-                        if (left_key[i] - right_val).abs() < 1e-10 {
-                            found = true;
-                            for (col_name, col_data) in &left_arrays {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[i]);
-                            }
-                            for (col_name, col_data) in &right_arrays {
-                                if col_name != on {
-                                    result_data
-                                        .entry(col_name.to_string())
-                                        .or_default()
-                                        .push(col_data[j]);
-                                }
-                            }
-                        }
-                    }
+    /// As-of merge: attach to each left row the nearest right row by time.
+    ///
+    /// Both DataFrames must hold `on` as a DateTime column; neither needs to
+    /// be sorted. Rows are first matched exactly on the `by` columns (e.g. a
+    /// panel id), then on time according to `direction`:
+    /// * "backward" - last right row with time <= left time
+    /// * "forward" - first right row with time >= left time
+    /// * "nearest" - closest in absolute distance (ties go backward)
+    ///
+    /// A match further away than `tolerance` is dropped. Every left row is
+    /// kept in its original order, with unmatched rows filled as in
+    /// [`merge_on`](Self::merge_on). The right `on` and `by` columns are not
+    /// repeated in the result; other overlapping names get `_x` / `_y`.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::DataFrame;
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let day = |d: u32| NaiveDate::from_ymd_opt(2024, 1, d).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///
+    /// let trades = DataFrame::builder()
+    ///     .add_datetime("time", vec![day(3), day(10)])
+    ///     .add_column("price", vec![100.0, 101.0])
+    ///     .build()
+    ///     .unwrap();
+    /// let quotes = DataFrame::builder()
+    ///     .add_datetime("time", vec![day(1), day(2), day(9)])
+    ///     .add_column("bid", vec![99.0, 99.5, 100.5])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let merged = trades.merge_asof(&quotes, "time", &[], "backward", None).unwrap();
+    /// assert_eq!(merged.get("bid").unwrap().to_vec(), vec![99.5, 100.5]);
+    ///
+    /// let tight = trades
+    ///     .merge_asof(&quotes, "time", &[], "backward", Some(Duration::hours(12)))
+    ///     .unwrap();
+    /// assert!(tight.get("bid").unwrap()[0].is_nan());
+    /// ```
+    pub fn merge_asof(
+        &self,
+        other: &DataFrame,
+        on: &str,
+        by: &[&str],
+        direction: &str,
+        tolerance: Option<chrono::Duration>,
+    ) -> Result<Self, GreenersError> {
+        if !matches!(direction, "backward" | "forward" | "nearest") {
+            return Err(GreenersError::InvalidOperation(format!(
+                "Unknown as-of direction '{}'. Use 'backward', 'forward', or 'nearest'",
+                direction
+            )));
+        }
+        if by.contains(&on) {
+            return Err(GreenersError::InvalidOperation(format!(
+                "As-of column '{}' cannot also be a 'by' column",
+                on
+            )));
+        }
+
+        let left_time = self.get_datetime(on)?;
+        let right_time = other.get_datetime(on)?;
+        let left_keys = self.join_key_rows(other, by)?;
+        let right_keys = other.join_key_rows(self, by)?;
 
-                    if !found {
-                        // No match: include right row with NaN for left columns
-                        for col_name in self.columns.keys() {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(f64::NAN);
+        // Right rows per `by` group, sorted by time (stable for equal times)
+        let mut groups: HashMap<&Vec<JoinKey>, Vec<usize>> = HashMap::new();
+        for (j, key) in right_keys.iter().enumerate() {
+            if let Some(k) = key {
+                groups.entry(k).or_default().push(j);
+            }
+        }
+        for members in groups.values_mut() {
+            members.sort_by_key(|&j| right_time[j]);
+        }
+
+        let right_rows: Vec<Option<usize>> = (0..self.n_rows)
+            .map(|i| {
+                let members = groups.get(left_keys[i].as_ref()?)?;
+                let t = left_time[i];
+                let after = members.partition_point(|&j| right_time[j] <= t);
+                let before = after.checked_sub(1).map(|p| members[p]);
+                let first_ge = members.partition_point(|&j| right_time[j] < t);
+                let forward = members.get(first_ge).copied();
+                let candidate = match direction {
+                    "backward" => before,
+                    "forward" => forward,
+                    _ => match (before, forward) {
+                        (Some(b), Some(f)) => {
+                            if right_time[f] - t < t - right_time[b] {
+                                Some(f)
                             } else {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(right_val);
-                            }
-                        }
-                        for (col_name, col_data) in &right_arrays {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[j]);
+                                Some(b)
                             }
                         }
-                    }
+                        (b, f) => b.or(f),
+                    },
+                }?;
+                let gap = (right_time[candidate] - t).abs();
+                match tolerance {
+                    Some(tol) if gap > tol => None,
+                    _ => Some(candidate),
                 }
+            })
+            .collect();
+
+        let mut result = IndexMap::new();
+        for (name, col) in &self.columns {
+            let dropped_on_right = name == on || by.contains(&name.as_str());
+            let out_name = if !dropped_on_right && other.columns.contains_key(name) {
+                format!("{}_x", name)
+            } else {
+                name.clone()
+            };
+            insert_merged_column(&mut result, out_name, col.as_ref().clone())?;
+        }
+        for (name, col) in &other.columns {
+            if name == on || by.contains(&name.as_str()) {
+                continue;
             }
-            "outer" => {
-                // Outer join: all rows from both, with NaN where no match
-                use std::collections::HashSet;
-                let mut processed_right: HashSet<usize> = HashSet::new();
-
-                // Process left rows
-                for i in 0..self.n_rows {
-                    let left_val = left_key[i];
-                    let mut found = false;
-
-                    for j in 0..other.n_rows {
-                        if (left_val - right_key[j]).abs() < 1e-10 {
-                            found = true;
-                            processed_right.insert(j);
-
-                            for (col_name, col_data) in &left_arrays {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[i]);
-                            }
-                            for (col_name, col_data) in &right_arrays {
-                                if col_name != on {
-                                    result_data
-                                        .entry(col_name.to_string())
-                                        .or_default()
-                                        .push(col_data[j]);
-                                }
-                            }
-                        }
-                    }
+            let out_name = if self.columns.contains_key(name) {
+                format!("{}_y", name)
+            } else {
+                name.clone()
+            };
+            insert_merged_column(&mut result, out_name, take_rows(col, &right_rows))?;
+        }
 
-                    if !found {
-                        for (col_name, col_data) in &left_arrays {
-                            result_data
-                                .entry(col_name.to_string())
-                                .or_default()
-                                .push(col_data[i]);
-                        }
-                        for col_name in other.columns.keys() {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(f64::NAN);
-                            }
-                        }
-                    }
-                }
+        DataFrame::from_columns(result)
+    }
 
-                // Add unmatched right rows
-                for j in 0..other.n_rows {
-                    if !processed_right.contains(&j) {
-                        for col_name in self.columns.keys() {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(f64::NAN);
-                            } else {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(right_key[j]);
-                            }
-                        }
-                        for (col_name, col_data) in &right_arrays {
-                            if col_name != on {
-                                result_data
-                                    .entry(col_name.to_string())
-                                    .or_default()
-                                    .push(col_data[j]);
-                            }
-                        }
-                    }
-                }
-            }
-            _ => {
-                return Err(GreenersError::FormulaError(format!(
-                    "Unknown join type '{}'. Use 'inner', 'left', 'right', or 'outer'",
-                    how
+    /// Per-row join keys over `on`, checked for presence and compatible
+    /// types against `other`. `None` marks a row with a NaN key component.
+    fn join_key_rows(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+    ) -> Result<Vec<Option<Vec<JoinKey>>>, GreenersError> {
+        for (k, name) in on.iter().enumerate() {
+            if on[..k].contains(name) {
+                return Err(GreenersError::InvalidOperation(format!(
+                    "Join column '{}' listed more than once",
+                    name
                 )));
             }
         }
 
-        // Convert to Column
-        let mut final_columns = IndexMap::new();
-        for (col_name, values) in result_data {
-            final_columns.insert(col_name, Column::Float(Array1::from(values)));
+        let mut per_column = Vec::with_capacity(on.len());
+        for name in on {
+            let col = self.columns.get(*name).ok_or_else(|| {
+                GreenersError::VariableNotFound(format!("Join column '{}' not found", name))
+            })?;
+            let other_col = other.columns.get(*name).ok_or_else(|| {
+                GreenersError::VariableNotFound(format!("Join column '{}' not found", name))
+            })?;
+            let kind = join_key_kind(col, other_col, name)?;
+            per_column.push(join_key_cells(col, kind));
         }
 
-        DataFrame::from_columns(final_columns)
+        Ok((0..self.n_rows)
+            .map(|i| per_column.iter().map(|cells| cells[i].clone()).collect())
+            .collect())
     }
 
     /// Group by one or more columns and apply aggregation functions.
     ///
+    /// Keys are rounded to integers and returned as floats. For native-typed
+    /// keys, several aggregations at once and group-wise transforms, use
+    /// [`group_by`](Self::group_by).
+    ///
     /// # Examples
     /// ```
     /// use greeners_core::DataFrame;
@@ -4998,6 +4881,309 @@ impl DataFrame {
     }
 }
 
+/// Native-typed key cell used by the merge and group-by routines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum JoinKey {
+    Int(i64),
+    /// Float key stored by bit pattern (with -0.0 folded into 0.0)
+    Float(u64),
+    Bool(bool),
+    Text(String),
+    Time(chrono::NaiveDateTime),
+}
+
+impl Ord for JoinKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (JoinKey::Int(a), JoinKey::Int(b)) => a.cmp(b),
+            (JoinKey::Float(a), JoinKey::Float(b)) => {
+                f64::from_bits(*a).total_cmp(&f64::from_bits(*b))
+            }
+            (JoinKey::Bool(a), JoinKey::Bool(b)) => a.cmp(b),
+            (JoinKey::Text(a), JoinKey::Text(b)) => a.cmp(b),
+            (JoinKey::Time(a), JoinKey::Time(b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl PartialOrd for JoinKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl JoinKey {
+    fn rank(&self) -> u8 {
+        match self {
+            JoinKey::Int(_) => 0,
+            JoinKey::Float(_) => 1,
+            JoinKey::Bool(_) => 2,
+            JoinKey::Text(_) => 3,
+            JoinKey::Time(_) => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum JoinKeyKind {
+    Int,
+    Float,
+    Bool,
+    Text,
+    Time,
+}
+
+pub(crate) fn join_key_kind(
+    left: &Column,
+    right: &Column,
+    name: &str,
+) -> Result<JoinKeyKind, GreenersError> {
+    use crate::column::DataType;
+    match (left.dtype(), right.dtype()) {
+        (DataType::Int, DataType::Int) => Ok(JoinKeyKind::Int),
+        (DataType::Float | DataType::Int, DataType::Float | DataType::Int) => {
+            Ok(JoinKeyKind::Float)
+        }
+        (DataType::Bool, DataType::Bool) => Ok(JoinKeyKind::Bool),
+        (DataType::Categorical | DataType::String, DataType::Categorical | DataType::String) => {
+            Ok(JoinKeyKind::Text)
+        }
+        (DataType::DateTime, DataType::DateTime) => Ok(JoinKeyKind::Time),
+        (l, r) => Err(GreenersError::ShapeMismatch(format!(
+            "Join column '{}' has incompatible types ({:?} vs {:?})",
+            name, l, r
+        ))),
+    }
+}
+
+pub(crate) fn join_key_cells(col: &Column, kind: JoinKeyKind) -> Vec<Option<JoinKey>> {
+    match (kind, col) {
+        (JoinKeyKind::Int, Column::Int(arr)) => {
+            arr.iter().map(|&v| Some(JoinKey::Int(v))).collect()
+        }
+        (JoinKeyKind::Bool, Column::Bool(arr)) => {
+            arr.iter().map(|&v| Some(JoinKey::Bool(v))).collect()
+        }
+        (JoinKeyKind::Time, Column::DateTime(arr)) => {
+            arr.iter().map(|&v| Some(JoinKey::Time(v))).collect()
+        }
+        (JoinKeyKind::Text, Column::Categorical(cat)) => (0..cat.len())
+            .map(|i| cat.get_string(i).map(|s| JoinKey::Text(s.to_string())))
+            .collect(),
+        (JoinKeyKind::Text, Column::String(arr)) => {
+            arr.iter().map(|v| Some(JoinKey::Text(v.clone()))).collect()
+        }
+        _ => col
+            .to_float()
+            .iter()
+            .map(|&v| (!v.is_nan()).then(|| JoinKey::Float((v + 0.0).to_bits())))
+            .collect(),
+    }
+}
+
+fn check_unique_keys(
+    keys: &[Option<Vec<JoinKey>>],
+    side: &str,
+    mode: &str,
+) -> Result<(), GreenersError> {
+    let mut seen = std::collections::HashSet::new();
+    for key in keys.iter().flatten() {
+        if !seen.insert(key) {
+            return Err(GreenersError::InvalidOperation(format!(
+                "Merge keys are not unique in the {} DataFrame (validate = {})",
+                side, mode
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Row pairs (outer side, inner side) of a hash join. Matches for each outer
+/// row follow inner order; unmatched inner rows are appended last when
+/// `keep_inner` is set.
+fn join_rows(
+    outer: &[Option<Vec<JoinKey>>],
+    inner: &[Option<Vec<JoinKey>>],
+    keep_outer: bool,
+    keep_inner: bool,
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut index: HashMap<&Vec<JoinKey>, Vec<usize>> = HashMap::new();
+    for (j, key) in inner.iter().enumerate() {
+        if let Some(k) = key {
+            index.entry(k).or_default().push(j);
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut inner_matched = vec![false; inner.len()];
+    for (i, key) in outer.iter().enumerate() {
+        match key.as_ref().and_then(|k| index.get(k)) {
+            Some(matches) => {
+                for &j in matches {
+                    rows.push((Some(i), Some(j)));
+                    inner_matched[j] = true;
+                }
+            }
+            None if keep_outer => rows.push((Some(i), None)),
+            None => {}
+        }
+    }
+    if keep_inner {
+        for (j, matched) in inner_matched.iter().enumerate() {
+            if !matched {
+                rows.push((None, Some(j)));
+            }
+        }
+    }
+    rows
+}
+
+/// Gather `rows` from `col`, filling `None` with the column's missing marker.
+fn take_rows(col: &Column, rows: &[Option<usize>]) -> Column {
+    if let Some(indices) = rows.iter().copied().collect::<Option<Vec<usize>>>() {
+        return col.filter_indices(&indices);
+    }
+    match col {
+        Column::Categorical(cat) => Column::Categorical(CategoricalColumn::from_codes(
+            cat.levels.clone(),
+            rows.iter()
+                .map(|r| r.map_or(CategoricalColumn::MISSING_CODE, |i| cat.codes[i]))
+                .collect(),
+        )),
+        Column::String(arr) => Column::String(
+            rows.iter()
+                .map(|r| r.map_or_else(|| "NA".to_string(), |i| arr[i].clone()))
+                .collect(),
+        ),
+        Column::DateTime(arr) => Column::DateTime(
+            rows.iter()
+                .map(|r| r.map_or(Column::MISSING_DATETIME, |i| arr[i]))
+                .collect(),
+        ),
+        _ => {
+            let values = col.to_float();
+            Column::Float(
+                rows.iter()
+                    .map(|r| r.map_or(f64::NAN, |i| values[i]))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Key column of a merge result: the left value where the row has a left
+/// match, otherwise the right value.
+fn coalesce_rows(left: &Column, right: &Column, rows: &[(Option<usize>, Option<usize>)]) -> Column {
+    if rows.iter().all(|(i, _)| i.is_some()) {
+        return take_rows(left, &rows.iter().map(|&(i, _)| i).collect::<Vec<_>>());
+    }
+    let pick = |&(i, j): &(Option<usize>, Option<usize>)| match i {
+        Some(i) => (true, i),
+        None => (false, j.unwrap_or(0)),
+    };
+    match (left, right) {
+        (Column::Int(l), Column::Int(r)) => Column::Int(
+            rows.iter()
+                .map(|row| match pick(row) {
+                    (true, i) => l[i],
+                    (false, j) => r[j],
+                })
+                .collect(),
+        ),
+        (Column::Bool(l), Column::Bool(r)) => Column::Bool(
+            rows.iter()
+                .map(|row| match pick(row) {
+                    (true, i) => l[i],
+                    (false, j) => r[j],
+                })
+                .collect(),
+        ),
+        (Column::DateTime(l), Column::DateTime(r)) => Column::DateTime(
+            rows.iter()
+                .map(|row| match pick(row) {
+                    (true, i) => l[i],
+                    (false, j) => r[j],
+                })
+                .collect(),
+        ),
+        (
+            Column::Categorical(_) | Column::String(_),
+            Column::Categorical(_) | Column::String(_),
+        ) => {
+            let text = |col: &Column, i: usize| match col {
+                Column::Categorical(cat) => cat.get_string(i).map(str::to_string),
+                Column::String(arr) => Some(arr[i].clone()),
+                _ => None,
+            };
+            let values: Vec<Option<String>> = rows
+                .iter()
+                .map(|row| match pick(row) {
+                    (true, i) => text(left, i),
+                    (false, j) => text(right, j),
+                })
+                .collect();
+            match left {
+                Column::Categorical(cat) => {
+                    // Keep the left levels in order; right-only labels follow
+                    let mut levels: IndexMap<String, u32> = cat
+                        .levels
+                        .iter()
+                        .enumerate()
+                        .map(|(c, level)| (level.clone(), c as u32))
+                        .collect();
+                    let codes = values
+                        .into_iter()
+                        .map(|v| match v {
+                            Some(label) => {
+                                let next = levels.len() as u32;
+                                *levels.entry(label).or_insert(next)
+                            }
+                            None => CategoricalColumn::MISSING_CODE,
+                        })
+                        .collect();
+                    Column::Categorical(CategoricalColumn::from_codes(
+                        levels.into_keys().collect(),
+                        codes,
+                    ))
+                }
+                _ => Column::String(
+                    values
+                        .into_iter()
+                        .map(|v| v.unwrap_or_else(|| "NA".to_string()))
+                        .collect(),
+                ),
+            }
+        }
+        _ => {
+            let (l, r) = (left.to_float(), right.to_float());
+            Column::Float(
+                rows.iter()
+                    .map(|row| match pick(row) {
+                        (true, i) => l[i],
+                        (false, j) => r[j],
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn insert_merged_column(
+    result: &mut IndexMap<String, Column>,
+    name: String,
+    column: Column,
+) -> Result<(), GreenersError> {
+    if result.contains_key(&name) {
+        return Err(GreenersError::InvalidOperation(format!(
+            "Column '{}' would appear twice in the merge result; use distinct suffixes",
+            name
+        )));
+    }
+    result.insert(name, column);
+    Ok(())
+}
+
 impl std::fmt::Display for DataFrame {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.n_rows == 0 {
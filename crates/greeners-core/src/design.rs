@@ -0,0 +1,783 @@
+//! Stateful design-matrix construction for formula-based models.
+//!
+//! [`DesignInfo`] records everything that `DataFrame::to_design_matrix` learns
+//! from the data — categorical levels and the reference level, polynomial
+//! centering and B-spline knots — so that the same columns can be rebuilt on
+//! a new `DataFrame` (e.g. a holdout sample) at prediction time.
+//!
+//! [`MissingPolicy`] and [`RowMap`] handle rows with missing values in any
+//! variable the formula references, and map per-observation outputs back to
+//! the rows of the original `DataFrame`.
+
+use crate::{bspline::BSplineBasis, column::Column, dataframe::DataFrame, formula::Formula};
+use crate::{CovarianceType, GreenersError};
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Elementwise function applied by `log()`, `exp()` and `sqrt()` terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TermFunction {
+    Log,
+    Exp,
+    Sqrt,
+}
+
+impl TermFunction {
+    fn apply(self, v: f64) -> f64 {
+        match self {
+            TermFunction::Log => v.ln(),
+            TermFunction::Exp => v.exp(),
+            TermFunction::Sqrt => v.sqrt(),
+        }
+    }
+}
+
+/// One formula term: its label as written in the formula and the state
+/// learned at fit time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesignTerm {
+    pub label: String,
+    pub kind: TermKind,
+}
+
+/// Kind of design term.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TermKind {
+    /// Column of ones.
+    Intercept,
+    /// Plain variable, converted to f64.
+    Numeric { var: String },
+    /// Product of two variables: `x1:x2`.
+    Interaction { left: String, right: String },
+    /// Power term: `I(x^2)`.
+    Power { var: String, power: i32 },
+    /// `log(x)`, `exp(x)` or `sqrt(x)`.
+    Function { var: String, func: TermFunction },
+    /// Treatment-coded dummies: `C(x)` or `C(x, ref=level)`.
+    ///
+    /// `levels` holds every level observed at fit time, in code order for
+    /// categorical columns and sorted for numeric ones (rounded to integers).
+    /// One dummy is generated for each level other than `reference`.
+    Categorical {
+        var: String,
+        levels: Vec<String>,
+        reference: String,
+        labeled: bool,
+    },
+    /// Raw polynomial `poly(x, degree)`; `poly(x, degree, center)` subtracts
+    /// the training mean before taking powers.
+    Poly {
+        var: String,
+        degree: usize,
+        center: f64,
+    },
+    /// B-spline basis `bs(x, df[, degree])` on knots fixed at fit time.
+    BSpline {
+        var: String,
+        df: usize,
+        degree: usize,
+        knots: Vec<f64>,
+    },
+}
+
+/// Design specification learned from a formula and a training `DataFrame`.
+///
+/// # Examples
+/// ```
+/// use greeners_core::{DataFrame, DesignInfo, Formula};
+///
+/// let train = DataFrame::builder()
+///     .add_column("y", vec![1.0, 2.0, 3.0, 4.0])
+///     .add_categorical("g", vec!["a".into(), "b".into(), "c".into(), "a".into()])
+///     .build()
+///     .unwrap();
+/// let formula = Formula::parse("y ~ C(g)").unwrap();
+/// let info = DesignInfo::from_formula(&formula, &train).unwrap();
+/// assert_eq!(info.column_names(), vec!["const", "g=b", "g=c"]);
+///
+/// // A holdout sample containing a subset of the levels gets the same columns.
+/// let test = DataFrame::builder()
+///     .add_categorical("g", vec!["c".into(), "c".into()])
+///     .build()
+///     .unwrap();
+/// let x = info.build_x(&test).unwrap();
+/// assert_eq!(x.shape(), &[2, 3]);
+/// assert_eq!(x[[0, 2]], 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesignInfo {
+    /// Name of the dependent variable.
+    pub dependent: String,
+    /// Terms in column order (the intercept, if any, comes first).
+    pub terms: Vec<DesignTerm>,
+}
+
+impl DesignInfo {
+    /// Learn the design from `formula` evaluated on `data`.
+    pub fn from_formula(formula: &Formula, data: &DataFrame) -> Result<Self, GreenersError> {
+        let mut terms = Vec::with_capacity(formula.independents.len() + 1);
+        if formula.intercept {
+            terms.push(DesignTerm {
+                label: "const".to_string(),
+                kind: TermKind::Intercept,
+            });
+        }
+        for var_name in &formula.independents {
+            terms.push(DesignTerm {
+                label: var_name.clone(),
+                kind: Self::learn_term(var_name, data)?,
+            });
+        }
+        Ok(DesignInfo {
+            dependent: formula.dependent.clone(),
+            terms,
+        })
+    }
+
+    fn learn_term(var_name: &str, data: &DataFrame) -> Result<TermKind, GreenersError> {
+        // Categorical variable: C(var) or C(var, ref=level)
+        if let Some(inner) = call_args(var_name, "C") {
+            let args = split_args(inner);
+            let var = args[0].to_string();
+            let column = data.get_column(&var)?;
+            let (levels, labeled) = observed_levels(column);
+            if levels.len() < 2 {
+                return Err(GreenersError::FormulaError(format!(
+                    "Categorical variable '{}' must have at least 2 categories",
+                    var
+                )));
+            }
+            let reference = match args.get(1) {
+                Some(arg) => {
+                    let level = arg
+                        .strip_prefix("ref")
+                        .map(|s| s.trim_start())
+                        .and_then(|s| s.strip_prefix('='))
+                        .map(|s| s.trim().trim_matches(|c| c == '\'' || c == '"'))
+                        .ok_or_else(|| {
+                            GreenersError::FormulaError(format!(
+                                "Invalid argument '{}' in '{}'. Expected C(var, ref=level)",
+                                arg, var_name
+                            ))
+                        })?;
+                    if !levels.iter().any(|l| l == level) {
+                        return Err(GreenersError::FormulaError(format!(
+                            "Reference level '{}' not found in '{}'",
+                            level, var
+                        )));
+                    }
+                    level.to_string()
+                }
+                None => levels[0].clone(),
+            };
+            return Ok(TermKind::Categorical {
+                var,
+                levels,
+                reference,
+                labeled,
+            });
+        }
+
+        // Polynomial term: I(var^power) or I(var**power)
+        if let Some(expr) = call_args(var_name, "I") {
+            let parts: Vec<&str> = if expr.contains("**") {
+                expr.split("**").collect()
+            } else if expr.contains('^') {
+                expr.split('^').collect()
+            } else {
+                return Err(GreenersError::FormulaError(format!(
+                    "I() expression must contain ^ or **: '{}'",
+                    expr
+                )));
+            };
+            if parts.len() != 2 {
+                return Err(GreenersError::FormulaError(format!(
+                    "Invalid polynomial expression '{}'",
+                    expr
+                )));
+            }
+            let var = parts[0].trim().to_string();
+            data.get_column(&var)?;
+            let power: i32 = parts[1].trim().parse().map_err(|_| {
+                GreenersError::FormulaError(format!("Invalid power in expression '{}'", expr))
+            })?;
+            return Ok(TermKind::Power { var, power });
+        }
+
+        for (name, func) in [
+            ("log", TermFunction::Log),
+            ("exp", TermFunction::Exp),
+            ("sqrt", TermFunction::Sqrt),
+        ] {
+            if let Some(inner) = call_args(var_name, name) {
+                let var = inner.trim().to_string();
+                data.get_column(&var)?;
+                return Ok(TermKind::Function { var, func });
+            }
+        }
+
+        if let Some(inner) = call_args(var_name, "poly") {
+            let parts = split_args(inner);
+            if parts.len() != 2 && parts.len() != 3 {
+                return Err(GreenersError::FormulaError(format!(
+                    "poly() requires 2 arguments: poly(var, degree), got '{}'",
+                    var_name
+                )));
+            }
+            let var = parts[0].to_string();
+            let degree: usize = parts[1].parse().map_err(|_| {
+                GreenersError::FormulaError(format!("Invalid degree in poly(): '{}'", parts[1]))
+            })?;
+            let col_data = data.get_column(&var)?.to_float();
+            let center = match parts.get(2).copied() {
+                None => 0.0,
+                Some("center") | Some("center=true") | Some("center=True") => {
+                    let finite: Vec<f64> =
+                        col_data.iter().copied().filter(|v| v.is_finite()).collect();
+                    if finite.is_empty() {
+                        0.0
+                    } else {
+                        finite.iter().sum::<f64>() / finite.len() as f64
+                    }
+                }
+                Some(other) => {
+                    return Err(GreenersError::FormulaError(format!(
+                        "Invalid argument '{}' in poly(). Expected 'center'",
+                        other
+                    )))
+                }
+            };
+            return Ok(TermKind::Poly {
+                var,
+                degree,
+                center,
+            });
+        }
+
+        if let Some(inner) = call_args(var_name, "bs") {
+            let parts = split_args(inner);
+            if parts.len() < 2 {
+                return Err(GreenersError::FormulaError(format!(
+                    "bs() requires at least 2 arguments: bs(var, df), got '{}'",
+                    var_name
+                )));
+            }
+            let var = parts[0].to_string();
+            let df: usize = parts[1].parse().map_err(|_| {
+                GreenersError::FormulaError(format!("Invalid df in bs(): '{}'", parts[1]))
+            })?;
+            let degree: usize = parts.get(2).and_then(|p| p.parse().ok()).unwrap_or(3);
+            let col_data = data.get_column(&var)?.to_float();
+            let knots = BSplineBasis::knots(&col_data, df, degree)?;
+            return Ok(TermKind::BSpline {
+                var,
+                df,
+                degree,
+                knots,
+            });
+        }
+
+        // Interaction term: "x1:x2"
+        if var_name.contains(':') {
+            let parts: Vec<&str> = var_name.split(':').collect();
+            if parts.len() != 2 {
+                return Err(GreenersError::FormulaError(format!(
+                    "Invalid interaction term '{}'",
+                    var_name
+                )));
+            }
+            let (left, right) = (parts[0].trim().to_string(), parts[1].trim().to_string());
+            data.get_column(&left)?;
+            data.get_column(&right)?;
+            return Ok(TermKind::Interaction { left, right });
+        }
+
+        data.get_column(var_name)?;
+        Ok(TermKind::Numeric {
+            var: var_name.to_string(),
+        })
+    }
+
+    /// Number of columns in the design matrix.
+    pub fn n_columns(&self) -> usize {
+        self.terms.iter().map(|t| term_width(&t.kind)).sum()
+    }
+
+    /// Names of the design-matrix columns.
+    ///
+    /// - Intercept: `const`
+    /// - `C(var)` on a categorical column: `var=label` for each non-reference level
+    /// - `C(var)` on a numeric column: `C(var)_N` for each non-reference code
+    /// - `poly(x, d)`: `poly(x, d)[1]` .. `poly(x, d)[d]`
+    /// - `bs(x, df)`: `bs(x, df)[0]` .. `bs(x, df)[df-1]`
+    /// - other terms: the term as written in the formula
+    pub fn column_names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.n_columns());
+        for term in &self.terms {
+            match &term.kind {
+                TermKind::Categorical {
+                    var,
+                    levels,
+                    reference,
+                    labeled,
+                } => {
+                    for level in levels.iter().filter(|l| *l != reference) {
+                        if *labeled {
+                            names.push(format!("{}={}", var, level));
+                        } else {
+                            names.push(format!("{}_{}", term.label, level));
+                        }
+                    }
+                }
+                TermKind::Poly { degree, .. } => {
+                    for d in 1..=*degree {
+                        names.push(format!("{}[{}]", term.label, d));
+                    }
+                }
+                TermKind::BSpline { df, .. } => {
+                    for j in 0..*df {
+                        names.push(format!("{}[{}]", term.label, j));
+                    }
+                }
+                _ => names.push(term.label.clone()),
+            }
+        }
+        names
+    }
+
+    /// Names of the data columns referenced by the design, starting with the
+    /// dependent variable (no duplicates).
+    pub fn variables(&self) -> Vec<&str> {
+        let mut vars: Vec<&str> = vec![self.dependent.as_str()];
+        for term in &self.terms {
+            let used: Vec<&str> = match &term.kind {
+                TermKind::Intercept => vec![],
+                TermKind::Interaction { left, right } => vec![left, right],
+                TermKind::Numeric { var }
+                | TermKind::Power { var, .. }
+                | TermKind::Function { var, .. }
+                | TermKind::Categorical { var, .. }
+                | TermKind::Poly { var, .. }
+                | TermKind::BSpline { var, .. } => vec![var],
+            };
+            for v in used {
+                if !vars.contains(&v) {
+                    vars.push(v);
+                }
+            }
+        }
+        vars
+    }
+
+    /// Indices of the rows of `data` with a missing value in any variable
+    /// referenced by the design (NaN for numeric columns, an unknown code for
+    /// categorical ones).
+    pub fn missing_rows(&self, data: &DataFrame) -> Result<Vec<usize>, GreenersError> {
+        let mut missing = vec![false; data.n_rows()];
+        for var in self.variables() {
+            match data.get_column(var)? {
+                Column::Float(arr) => {
+                    for (m, v) in missing.iter_mut().zip(arr.iter()) {
+                        *m |= v.is_nan();
+                    }
+                }
+                Column::Categorical(cat) => {
+                    for (m, &c) in missing.iter_mut().zip(cat.codes.iter()) {
+                        *m |= cat.get_level(c).is_none();
+                    }
+                }
+                Column::DateTime(arr) => {
+                    for (m, v) in missing.iter_mut().zip(arr.iter()) {
+                        *m |= *v == Column::MISSING_DATETIME;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(missing
+            .iter()
+            .enumerate()
+            .filter(|(_, &m)| m)
+            .map(|(i, _)| i)
+            .collect())
+    }
+
+    /// Build the response vector and design matrix on `data`.
+    pub fn build(&self, data: &DataFrame) -> Result<(Array1<f64>, Array2<f64>), GreenersError> {
+        let y = data.get_column(&self.dependent)?.to_float();
+        let x = self.build_x(data)?;
+        Ok((y, x))
+    }
+
+    /// Build only the design matrix on `data` (the response may be absent).
+    ///
+    /// Categorical levels that were not observed at fit time are rejected.
+    pub fn build_x(&self, data: &DataFrame) -> Result<Array2<f64>, GreenersError> {
+        let n_rows = data.n_rows();
+        let mut x_mat = Array2::<f64>::zeros((n_rows, self.n_columns()));
+        let mut col_idx = 0;
+
+        for term in &self.terms {
+            match &term.kind {
+                TermKind::Intercept => {
+                    x_mat.column_mut(col_idx).fill(1.0);
+                }
+                TermKind::Numeric { var } => {
+                    let col_data = data.get_column(var)?.to_float();
+                    x_mat.column_mut(col_idx).assign(&col_data);
+                }
+                TermKind::Interaction { left, right } => {
+                    let var1 = data.get_column(left)?.to_float();
+                    let var2 = data.get_column(right)?.to_float();
+                    x_mat.column_mut(col_idx).assign(&(&var1 * &var2));
+                }
+                TermKind::Power { var, power } => {
+                    let col_data = data.get_column(var)?.to_float();
+                    x_mat
+                        .column_mut(col_idx)
+                        .assign(&col_data.mapv(|v| v.powi(*power)));
+                }
+                TermKind::Function { var, func } => {
+                    let col_data = data.get_column(var)?.to_float();
+                    x_mat
+                        .column_mut(col_idx)
+                        .assign(&col_data.mapv(|v| func.apply(v)));
+                }
+                TermKind::Categorical {
+                    var,
+                    levels,
+                    reference,
+                    ..
+                } => {
+                    let keys = level_keys(data.get_column(var)?);
+                    let dummies: Vec<&String> = levels.iter().filter(|l| *l != reference).collect();
+                    for (i, key) in keys.iter().enumerate() {
+                        let Some(key) = key else {
+                            for j in 0..dummies.len() {
+                                x_mat[[i, col_idx + j]] = f64::NAN;
+                            }
+                            continue;
+                        };
+                        if !levels.contains(key) {
+                            return Err(GreenersError::FormulaError(format!(
+                                "Level '{}' of '{}' was not seen when the design was built",
+                                key, var
+                            )));
+                        }
+                        if let Some(j) = dummies.iter().position(|l| *l == key) {
+                            x_mat[[i, col_idx + j]] = 1.0;
+                        }
+                    }
+                }
+                TermKind::Poly {
+                    var,
+                    degree,
+                    center,
+                } => {
+                    let col_data = data.get_column(var)?.to_float();
+                    for d in 1..=*degree {
+                        x_mat
+                            .column_mut(col_idx + d - 1)
+                            .assign(&col_data.mapv(|v| (v - center).powi(d as i32)));
+                    }
+                }
+                TermKind::BSpline {
+                    var,
+                    df,
+                    degree,
+                    knots,
+                } => {
+                    let col_data = data.get_column(var)?.to_float();
+                    let basis = BSplineBasis::generate_with_knots(&col_data, *df, *degree, knots)?;
+                    x_mat
+                        .slice_mut(ndarray::s![.., col_idx..col_idx + df])
+                        .assign(&basis);
+                }
+            }
+            col_idx += term_width(&term.kind);
+        }
+
+        Ok(x_mat)
+    }
+
+    /// Build the design matrix on `data` and drop the columns at the given
+    /// positions, e.g. the `omitted_vars` removed for collinearity at fit time.
+    pub fn build_x_omitting(
+        &self,
+        data: &DataFrame,
+        omitted: &[(usize, String)],
+    ) -> Result<Array2<f64>, GreenersError> {
+        let x = self.build_x(data)?;
+        if omitted.is_empty() {
+            return Ok(x);
+        }
+        let keep: Vec<usize> = (0..x.ncols())
+            .filter(|j| !omitted.iter().any(|(p, _)| p == j))
+            .collect();
+        Ok(x.select(Axis(1), &keep))
+    }
+}
+
+/// How formula-based estimators treat rows with missing values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MissingPolicy {
+    /// Listwise deletion: drop every row with a missing value in any variable
+    /// the formula references.
+    #[default]
+    Drop,
+    /// Return an error if any referenced variable has a missing value.
+    Raise,
+    /// Pass missing values through to `y` and `X` unchanged.
+    Keep,
+}
+
+impl FromStr for MissingPolicy {
+    type Err = GreenersError;
+
+    /// Parse `"drop"`, `"raise"` or `"keep"` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "drop" => Ok(MissingPolicy::Drop),
+            "raise" => Ok(MissingPolicy::Raise),
+            "keep" => Ok(MissingPolicy::Keep),
+            other => Err(GreenersError::InvalidOperation(format!(
+                "Unknown missing policy '{}'. Expected 'drop', 'raise' or 'keep'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Mapping between the rows used in estimation and the rows of the original
+/// `DataFrame`.
+///
+/// # Examples
+/// ```
+/// use greeners_core::RowMap;
+/// use ndarray::array;
+///
+/// let rows = RowMap::new(4, vec![1]);
+/// assert_eq!(rows.kept(), vec![0, 2, 3]);
+///
+/// // Residuals of the 3 estimation rows, aligned back to the 4 data rows.
+/// let full = rows.expand(&array![0.5, -0.5, 1.0]);
+/// assert_eq!(full[0], 0.5);
+/// assert!(full[1].is_nan());
+/// assert_eq!(full[3], 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowMap {
+    /// Number of rows in the original `DataFrame`.
+    pub n_rows: usize,
+    /// Indices of the rows excluded from estimation, sorted ascending.
+    pub dropped: Vec<usize>,
+}
+
+impl RowMap {
+    pub fn new(n_rows: usize, mut dropped: Vec<usize>) -> Self {
+        dropped.sort_unstable();
+        dropped.dedup();
+        RowMap { n_rows, dropped }
+    }
+
+    /// Number of rows used in estimation.
+    pub fn n_kept(&self) -> usize {
+        self.n_rows - self.dropped.len()
+    }
+
+    /// Indices of the original rows used in estimation, in order.
+    pub fn kept(&self) -> Vec<usize> {
+        let mut dropped = self.dropped.iter().peekable();
+        (0..self.n_rows)
+            .filter(|i| {
+                if dropped.peek() == Some(&i) {
+                    dropped.next();
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Spread a per-observation vector (fitted values, residuals, ...) back
+    /// over the original rows, filling dropped rows with NaN.
+    pub fn expand(&self, values: &Array1<f64>) -> Array1<f64> {
+        let mut full = Array1::from_elem(self.n_rows, f64::NAN);
+        for (&i, &v) in self.kept().iter().zip(values.iter()) {
+            full[i] = v;
+        }
+        full
+    }
+
+    /// Select the estimation rows of a vector aligned with the original rows.
+    pub fn select(&self, values: &Array1<f64>) -> Array1<f64> {
+        values.select(Axis(0), &self.kept())
+    }
+
+    /// Select the estimation rows of a matrix aligned with the original rows.
+    pub fn select_rows(&self, values: &Array2<f64>) -> Array2<f64> {
+        values.select(Axis(0), &self.kept())
+    }
+
+    /// Select the estimation rows of a slice (e.g. entity or cluster ids).
+    /// `values` must be aligned with the original rows.
+    pub fn select_slice<T: Clone>(&self, values: &[T]) -> Result<Vec<T>, GreenersError> {
+        if values.len() != self.n_rows {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Row-aligned input has {} values but the data has {} rows",
+                values.len(),
+                self.n_rows
+            )));
+        }
+        Ok(self.pick(values))
+    }
+
+    fn pick<T: Clone>(&self, values: &[T]) -> Vec<T> {
+        self.kept().into_iter().map(|i| values[i].clone()).collect()
+    }
+
+    /// Subset cluster ids, time ids and coordinates that are aligned with the
+    /// original rows; other covariance types are returned unchanged.
+    pub fn select_cov_type(&self, cov_type: CovarianceType) -> CovarianceType {
+        if self.dropped.is_empty() {
+            return cov_type;
+        }
+        match cov_type {
+            CovarianceType::Clustered(ids) if ids.len() == self.n_rows => {
+                CovarianceType::Clustered(self.pick(&ids))
+            }
+            CovarianceType::ClusteredTwoWay(a, b)
+                if a.len() == self.n_rows && b.len() == self.n_rows =>
+            {
+                CovarianceType::ClusteredTwoWay(self.pick(&a), self.pick(&b))
+            }
+            CovarianceType::CR2(ids) if ids.len() == self.n_rows => {
+                CovarianceType::CR2(self.pick(&ids))
+            }
+            CovarianceType::CR3(ids) if ids.len() == self.n_rows => {
+                CovarianceType::CR3(self.pick(&ids))
+            }
+            CovarianceType::DriscollKraay(ids, lags) if ids.len() == self.n_rows => {
+                CovarianceType::DriscollKraay(self.pick(&ids), lags)
+            }
+            CovarianceType::Conley(coords, cutoff) if coords.len() == self.n_rows => {
+                CovarianceType::Conley(self.pick(&coords), cutoff)
+            }
+            other => other,
+        }
+    }
+}
+
+/// Response, design matrix and row mapping produced by
+/// `DataFrame::design_matrices`.
+#[derive(Debug, Clone)]
+pub struct DesignMatrices {
+    pub y: Array1<f64>,
+    pub x: Array2<f64>,
+    /// Design learned on the estimation rows.
+    pub info: DesignInfo,
+    pub rows: RowMap,
+}
+
+fn term_width(term: &TermKind) -> usize {
+    match term {
+        TermKind::Categorical { levels, .. } => levels.len() - 1,
+        TermKind::Poly { degree, .. } => *degree,
+        TermKind::BSpline { df, .. } => *df,
+        _ => 1,
+    }
+}
+
+/// Inner text of `name(...)`, if `term` is such a call.
+fn call_args<'a>(term: &'a str, name: &str) -> Option<&'a str> {
+    term.strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Split call arguments on top-level commas, trimming whitespace.
+fn split_args(inner: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(inner[start..].trim());
+    parts
+}
+
+/// Level label of each row, or `None` where the value is missing.
+fn level_keys(column: &Column) -> Vec<Option<String>> {
+    match column {
+        Column::Categorical(cat) => cat
+            .codes
+            .iter()
+            .map(|&c| cat.get_level(c).map(|s| s.to_string()))
+            .collect(),
+        Column::String(arr) => arr.iter().map(|s| Some(s.clone())).collect(),
+        _ => column
+            .to_float()
+            .iter()
+            .map(|&v| {
+                if v.is_nan() {
+                    None
+                } else {
+                    Some((v.round() as i32).to_string())
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Levels present in `column`, and whether they are string labels.
+fn observed_levels(column: &Column) -> (Vec<String>, bool) {
+    match column {
+        Column::Categorical(cat) => {
+            let mut present = vec![false; cat.levels.len()];
+            // Codes without a level (missing or corrupt) carry no level
+            for &c in &cat.codes {
+                if cat.get_level(c).is_some() {
+                    present[c as usize] = true;
+                }
+            }
+            let levels = cat
+                .levels
+                .iter()
+                .zip(present)
+                .filter(|(_, p)| *p)
+                .map(|(l, _)| l.clone())
+                .collect();
+            (levels, true)
+        }
+        Column::String(arr) => {
+            let mut levels: Vec<String> = Vec::new();
+            for s in arr.iter() {
+                if !levels.contains(s) {
+                    levels.push(s.clone());
+                }
+            }
+            (levels, true)
+        }
+        _ => {
+            use std::collections::BTreeSet;
+            let codes: BTreeSet<i32> = column
+                .to_float()
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|&v| v.round() as i32)
+                .collect();
+            (codes.into_iter().map(|c| c.to_string()).collect(), false)
+        }
+    }
+}
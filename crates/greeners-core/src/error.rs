@@ -27,4 +27,8 @@ pub enum GreenersError {
     /// Error thrown when an invalid operation is requested
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
+
+    /// Error thrown when saving or loading a result fails
+    #[error("Serialization error: {0}")]
+    Serialization(String),
 }
@@ -15,17 +15,18 @@
 use crate::linalg::LinalgInverse as _;
 use crate::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Kernel type for local regression.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum KernelType {
     Gaussian,
     Epanechnikov,
 }
 
 /// Result of functional coefficient model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FunctionalCoefResult {
     /// Varying coefficients at each evaluation point, shape (n_points, k+1)
     /// Column 0 = intercept, columns 1..k = slopes
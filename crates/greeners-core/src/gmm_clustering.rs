@@ -14,12 +14,14 @@
 //! BIC, and AIC.
 
 use crate::linalg::{LinalgDeterminant as _, LinalgInverse as _};
+use crate::rng::GreenersRng;
 use crate::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of GMM clustering.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GmmResult {
     /// Cluster assignments (n), values 0..k-1
     pub labels: Vec<usize>,
@@ -109,12 +111,15 @@ impl GmmClustering {
     /// * `n_clusters` - Number of mixture components k
     /// * `max_iter` - Max EM iterations (default 100)
     /// * `tol` - Convergence tolerance (default 1e-6)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         x: &Array2<f64>,
         n_clusters: usize,
         max_iter: Option<usize>,
         tol: Option<f64>,
+        seed: Option<u64>,
     ) -> Result<GmmResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = x.nrows();
         let d = x.ncols();
         if n < n_clusters * 2 {
@@ -134,7 +139,7 @@ impl GmmClustering {
 
         // Initialize means via k-means++ style initialization
         let mut means = Array2::<f64>::zeros((k, d));
-        let first = Self::rand_int(n);
+        let first = rng.index(n);
         for j in 0..d {
             means[(0, j)] = x[(first, j)];
         }
@@ -150,13 +155,13 @@ impl GmmClustering {
             }
             let total: f64 = dists.iter().sum();
             if total < 1e-15 {
-                let idx = Self::rand_int(n);
+                let idx = rng.index(n);
                 for j in 0..d {
                     means[(c, j)] = x[(idx, j)];
                 }
                 continue;
             }
-            let r = Self::rand_uniform() * total;
+            let r = rng.uniform() * total;
             let mut cumsum = 0.0;
             let mut chosen = 0;
             for (i, &di) in dists.iter().enumerate().take(n) {
@@ -328,26 +333,4 @@ impl GmmClustering {
         let norm = (2.0 * std::f64::consts::PI).powi(d as i32) * det;
         (norm).sqrt().recip() * (-0.5 * quad).exp()
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(9988776655) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -0,0 +1,457 @@
+//! Group-wise aggregation and transformation for [`DataFrame`].
+//!
+//! [`DataFrame::group_by`] splits the rows by one or more key columns and
+//! returns a [`GroupBy`] that can
+//! - aggregate several columns with several functions at once ([`GroupBy::agg`]),
+//! - aggregate with a custom closure ([`GroupBy::agg_with`]),
+//! - broadcast a group statistic back to every row ([`GroupBy::transform`]),
+//! - compute lags, leads, differences, rolling windows and cumulative sums
+//!   that restart at every group boundary, so panel regressors never borrow
+//!   values from a neighbouring entity.
+//!
+//! Keys are compared in their native type (see [`DataFrame::merge_on`]).
+//! Groups are reported in ascending key order; rows whose key contains a NaN
+//! belong to no group and receive NaN from every row-wise operation. Within a
+//! group, rows keep the order they have in the frame, so sort by time first
+//! when computing lags on panel data.
+
+use crate::column::Column;
+use crate::dataframe::{join_key_cells, join_key_kind, DataFrame, JoinKey};
+use crate::GreenersError;
+use indexmap::IndexMap;
+use ndarray::Array1;
+use std::collections::BTreeMap;
+
+/// Rows of a [`DataFrame`] split by key columns.
+///
+/// # Examples
+/// ```
+/// use greeners_core::DataFrame;
+///
+/// let df = DataFrame::builder()
+///     .add_categorical("firm", vec!["A".into(), "A".into(), "B".into(), "B".into()])
+///     .add_column("sales", vec![1.0, 3.0, 10.0, 30.0])
+///     .build()
+///     .unwrap();
+///
+/// let grouped = df.group_by(&["firm"]).unwrap();
+/// let summary = grouped.agg(&[("sales", "mean"), ("sales", "max")]).unwrap();
+/// assert_eq!(summary.get("sales_mean").unwrap().to_vec(), vec![2.0, 20.0]);
+///
+/// let lagged = grouped.lag("sales", 1).unwrap();
+/// let lag = lagged.get("sales_lag_1").unwrap();
+/// assert!(lag[2].is_nan()); // firm B does not see firm A's last value
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupBy<'a> {
+    df: &'a DataFrame,
+    by: Vec<String>,
+    /// Row indices of each group, groups in ascending key order
+    groups: Vec<Vec<usize>>,
+}
+
+impl DataFrame {
+    /// Group rows by one or more key columns.
+    ///
+    /// Unlike [`groupby`](Self::groupby), keys keep their native type and the
+    /// returned [`GroupBy`] supports several aggregations and group-wise
+    /// transforms.
+    pub fn group_by(&self, by: &[&str]) -> Result<GroupBy<'_>, GreenersError> {
+        if by.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "group_by requires at least one key column".to_string(),
+            ));
+        }
+
+        let mut per_column = Vec::with_capacity(by.len());
+        for name in by {
+            let col = self.get_column(name).map_err(|_| {
+                GreenersError::VariableNotFound(format!("Grouping column '{}' not found", name))
+            })?;
+            let kind = join_key_kind(col, col, name)?;
+            per_column.push(join_key_cells(col, kind));
+        }
+
+        let mut groups: BTreeMap<Vec<JoinKey>, Vec<usize>> = BTreeMap::new();
+        for i in 0..self.n_rows() {
+            let key: Option<Vec<JoinKey>> =
+                per_column.iter().map(|cells| cells[i].clone()).collect();
+            if let Some(key) = key {
+                groups.entry(key).or_default().push(i);
+            }
+        }
+
+        Ok(GroupBy {
+            df: self,
+            by: by.iter().map(|s| s.to_string()).collect(),
+            groups: groups.into_values().collect(),
+        })
+    }
+}
+
+impl<'a> GroupBy<'a> {
+    /// Number of groups.
+    pub fn n_groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Row indices of each group, in the same order as the rows of
+    /// [`keys`](Self::keys).
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    /// One row per group holding the key values (native types) and a
+    /// `size` column with the number of rows in the group.
+    pub fn keys(&self) -> Result<DataFrame, GreenersError> {
+        let mut columns = self.key_columns()?;
+        let sizes = self.groups.iter().map(|g| g.len() as i64).collect();
+        columns.insert("size".to_string(), Column::Int(sizes));
+        DataFrame::from_columns(columns)
+    }
+
+    /// Aggregate columns with one or more named functions.
+    ///
+    /// Each `(column, agg)` pair produces a column named `{column}_{agg}`.
+    /// Supported functions: "sum", "mean", "count", "size", "min", "max",
+    /// "median", "std", "var" (sample, n - 1), "first" and "last". NaN
+    /// values are skipped except by "size" (rows in the group), "first" and
+    /// "last"; "count" is the number of non-missing values.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::DataFrame;
+    ///
+    /// let df = DataFrame::builder()
+    ///     .add_int("region", vec![1, 1, 2])
+    ///     .add_int("year", vec![2020, 2020, 2021])
+    ///     .add_column("y", vec![1.0, 2.0, 4.0])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let out = df
+    ///     .group_by(&["region", "year"])
+    ///     .unwrap()
+    ///     .agg(&[("y", "sum"), ("y", "count")])
+    ///     .unwrap();
+    /// assert_eq!(out.column_names(), vec!["region", "year", "y_sum", "y_count"]);
+    /// assert_eq!(out.get_int("year").unwrap().to_vec(), vec![2020, 2021]);
+    /// ```
+    pub fn agg(&self, specs: &[(&str, &str)]) -> Result<DataFrame, GreenersError> {
+        let mut columns = self.key_columns()?;
+        for &(column, func) in specs {
+            let values = self.values(column)?;
+            let mut out = Vec::with_capacity(self.groups.len());
+            for group in &self.groups {
+                let group_values: Vec<f64> = group.iter().map(|&i| values[i]).collect();
+                out.push(aggregate(&group_values, func)?);
+            }
+            insert_unique(&mut columns, format!("{}_{}", column, func), out)?;
+        }
+        DataFrame::from_columns(columns)
+    }
+
+    /// Aggregate one column with a custom function of the group's values.
+    ///
+    /// The result holds the key columns and a column called `name`.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::DataFrame;
+    ///
+    /// let df = DataFrame::builder()
+    ///     .add_int("g", vec![1, 1, 2, 2])
+    ///     .add_column("x", vec![1.0, 5.0, 2.0, 3.0])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let range = df
+    ///     .group_by(&["g"])
+    ///     .unwrap()
+    ///     .agg_with("x", "x_range", |v| {
+    ///         v.iter().cloned().fold(f64::MIN, f64::max) - v.iter().cloned().fold(f64::MAX, f64::min)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(range.get("x_range").unwrap().to_vec(), vec![4.0, 1.0]);
+    /// ```
+    pub fn agg_with<F>(&self, column: &str, name: &str, func: F) -> Result<DataFrame, GreenersError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let values = self.values(column)?;
+        let out = self
+            .groups
+            .iter()
+            .map(|group| func(&group.iter().map(|&i| values[i]).collect::<Vec<_>>()))
+            .collect();
+        let mut columns = self.key_columns()?;
+        insert_unique(&mut columns, name.to_string(), out)?;
+        DataFrame::from_columns(columns)
+    }
+
+    /// Broadcast a group aggregate back to every row.
+    ///
+    /// Returns a copy of the frame with a new column `{column}_{func}` where
+    /// each row carries its group's value (e.g. group means for within
+    /// transformations). Accepts the same functions as [`agg`](Self::agg).
+    pub fn transform(&self, column: &str, func: &str) -> Result<DataFrame, GreenersError> {
+        let values = self.values(column)?;
+        let mut out = vec![f64::NAN; self.df.n_rows()];
+        for group in &self.groups {
+            let group_values: Vec<f64> = group.iter().map(|&i| values[i]).collect();
+            let stat = aggregate(&group_values, func)?;
+            for &i in group {
+                out[i] = stat;
+            }
+        }
+        self.with_column(format!("{}_{}", column, func), out)
+    }
+
+    /// Broadcast a custom group statistic back to every row as column `name`.
+    pub fn transform_with<F>(
+        &self,
+        column: &str,
+        name: &str,
+        func: F,
+    ) -> Result<DataFrame, GreenersError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let values = self.values(column)?;
+        let mut out = vec![f64::NAN; self.df.n_rows()];
+        for group in &self.groups {
+            let stat = func(&group.iter().map(|&i| values[i]).collect::<Vec<_>>());
+            for &i in group {
+                out[i] = stat;
+            }
+        }
+        self.with_column(name.to_string(), out)
+    }
+
+    /// Lag within each group; adds `{column}_lag_{periods}`.
+    ///
+    /// The first `periods` rows of every group are NaN.
+    pub fn lag(&self, column: &str, periods: usize) -> Result<DataFrame, GreenersError> {
+        if periods == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "Lag periods must be at least 1".to_string(),
+            ));
+        }
+        let out = self.map_groups(column, |v| {
+            (0..v.len())
+                .map(|t| {
+                    if t >= periods {
+                        v[t - periods]
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect()
+        })?;
+        self.with_column(format!("{}_lag_{}", column, periods), out)
+    }
+
+    /// Lead within each group; adds `{column}_lead_{periods}`.
+    pub fn lead(&self, column: &str, periods: usize) -> Result<DataFrame, GreenersError> {
+        if periods == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "Lead periods must be at least 1".to_string(),
+            ));
+        }
+        let out = self.map_groups(column, |v| {
+            (0..v.len())
+                .map(|t| v.get(t + periods).copied().unwrap_or(f64::NAN))
+                .collect()
+        })?;
+        self.with_column(format!("{}_lead_{}", column, periods), out)
+    }
+
+    /// Difference within each group; adds `{column}_diff_{periods}`.
+    pub fn diff(&self, column: &str, periods: usize) -> Result<DataFrame, GreenersError> {
+        if periods == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "Diff periods must be at least 1".to_string(),
+            ));
+        }
+        let out = self.map_groups(column, |v| {
+            (0..v.len())
+                .map(|t| {
+                    if t >= periods {
+                        v[t] - v[t - periods]
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect()
+        })?;
+        self.with_column(format!("{}_diff_{}", column, periods), out)
+    }
+
+    /// Percentage change within each group; adds `{column}_pct_{periods}`.
+    pub fn pct_change(&self, column: &str, periods: usize) -> Result<DataFrame, GreenersError> {
+        if periods == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "pct_change periods must be at least 1".to_string(),
+            ));
+        }
+        let out = self.map_groups(column, |v| {
+            (0..v.len())
+                .map(|t| {
+                    if t >= periods && v[t - periods] != 0.0 {
+                        (v[t] - v[t - periods]) / v[t - periods]
+                    } else {
+                        f64::NAN
+                    }
+                })
+                .collect()
+        })?;
+        self.with_column(format!("{}_pct_{}", column, periods), out)
+    }
+
+    /// Rolling window statistic within each group; adds
+    /// `{column}_rolling_{func}`.
+    ///
+    /// Same functions and conventions as [`DataFrame::rolling`]: "mean",
+    /// "sum", "min", "max" or "std", NaN until a group has `window` rows.
+    pub fn rolling(
+        &self,
+        column: &str,
+        window: usize,
+        func: &str,
+    ) -> Result<DataFrame, GreenersError> {
+        if window == 0 {
+            return Err(GreenersError::FormulaError(
+                "Window size must be > 0".to_string(),
+            ));
+        }
+        if !matches!(func, "mean" | "sum" | "min" | "max" | "std") {
+            return Err(GreenersError::FormulaError(format!(
+                "Unknown function '{}'. Use 'mean', 'sum', 'min', 'max', or 'std'",
+                func
+            )));
+        }
+        let out = self.map_groups(column, |v| {
+            (0..v.len())
+                .map(|t| {
+                    if t + 1 < window {
+                        return f64::NAN;
+                    }
+                    let w = &v[t + 1 - window..=t];
+                    let mean = w.iter().sum::<f64>() / window as f64;
+                    match func {
+                        "mean" => mean,
+                        "sum" => w.iter().sum(),
+                        "min" => w.iter().fold(f64::INFINITY, |a, &b| a.min(b)),
+                        "max" => w.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)),
+                        _ => (w.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / window as f64)
+                            .sqrt(),
+                    }
+                })
+                .collect()
+        })?;
+        self.with_column(format!("{}_rolling_{}", column, func), out)
+    }
+
+    /// Cumulative sum within each group; adds `{column}_cumsum`.
+    pub fn cumsum(&self, column: &str) -> Result<DataFrame, GreenersError> {
+        let out = self.map_groups(column, |v| {
+            v.iter()
+                .scan(0.0, |acc, &x| {
+                    *acc += x;
+                    Some(*acc)
+                })
+                .collect()
+        })?;
+        self.with_column(format!("{}_cumsum", column), out)
+    }
+
+    /// Apply a row-wise function to each group's values and scatter the
+    /// results back to the original rows (NaN for rows outside every group).
+    fn map_groups<F>(&self, column: &str, func: F) -> Result<Vec<f64>, GreenersError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+    {
+        let values = self.values(column)?;
+        let mut out = vec![f64::NAN; self.df.n_rows()];
+        for group in &self.groups {
+            let group_values: Vec<f64> = group.iter().map(|&i| values[i]).collect();
+            for (&i, v) in group.iter().zip(func(&group_values)) {
+                out[i] = v;
+            }
+        }
+        Ok(out)
+    }
+
+    fn values(&self, column: &str) -> Result<Array1<f64>, GreenersError> {
+        Ok(self.df.get_column(column)?.to_float())
+    }
+
+    fn key_columns(&self) -> Result<IndexMap<String, Column>, GreenersError> {
+        let first_rows: Vec<usize> = self.groups.iter().map(|g| g[0]).collect();
+        let mut columns = IndexMap::new();
+        for name in &self.by {
+            let col = self.df.get_column(name)?;
+            columns.insert(name.clone(), col.filter_indices(&first_rows));
+        }
+        Ok(columns)
+    }
+
+    fn with_column(&self, name: String, values: Vec<f64>) -> Result<DataFrame, GreenersError> {
+        let mut df = self.df.clone();
+        df.insert(name, Array1::from(values))?;
+        Ok(df)
+    }
+}
+
+fn insert_unique(
+    columns: &mut IndexMap<String, Column>,
+    name: String,
+    values: Vec<f64>,
+) -> Result<(), GreenersError> {
+    if columns.contains_key(&name) {
+        return Err(GreenersError::InvalidOperation(format!(
+            "Column '{}' would appear twice in the aggregation result",
+            name
+        )));
+    }
+    columns.insert(name, Column::Float(Array1::from(values)));
+    Ok(())
+}
+
+fn aggregate(values: &[f64], func: &str) -> Result<f64, GreenersError> {
+    let valid: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    let n = valid.len() as f64;
+    let mean = valid.iter().sum::<f64>() / n;
+    let var = valid.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Ok(match func {
+        "sum" => valid.iter().sum(),
+        "mean" => mean,
+        "count" => n,
+        "size" => values.len() as f64,
+        "min" => valid.iter().fold(f64::NAN, |a, &b| a.min(b)),
+        "max" => valid.iter().fold(f64::NAN, |a, &b| a.max(b)),
+        "median" => {
+            let mut sorted = valid.clone();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            let mid = sorted.len() / 2;
+            if sorted.is_empty() {
+                f64::NAN
+            } else if sorted.len() % 2 == 0 {
+                (sorted[mid - 1] + sorted[mid]) / 2.0
+            } else {
+                sorted[mid]
+            }
+        }
+        "var" if valid.len() > 1 => var,
+        "std" if valid.len() > 1 => var.sqrt(),
+        "var" | "std" => f64::NAN,
+        "first" => values.first().copied().unwrap_or(f64::NAN),
+        "last" => values.last().copied().unwrap_or(f64::NAN),
+        _ => {
+            return Err(GreenersError::FormulaError(format!(
+                "Unknown aggregation '{}'. Use 'sum', 'mean', 'count', 'size', 'min', 'max', 'median', 'std', 'var', 'first', or 'last'",
+                func
+            )));
+        }
+    })
+}
@@ -14,10 +14,11 @@
 
 use crate::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of isotonic regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IsotonicResult {
     /// Fitted (monotone) values (n)
     pub fitted: Array1<f64>,
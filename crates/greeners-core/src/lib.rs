@@ -4,24 +4,33 @@
 // Items with names duplicated across modules remain namespaced.
 
 pub use biplot::{Biplot, BiplotResult, BiplotType};
-pub use bootstrap::{Bootstrap, HypothesisTest};
+pub use bootstrap::{
+    Bootstrap, HypothesisTest, WildBootstrap, WildBootstrapConfig, WildBootstrapResult, WildWeights,
+};
 pub use bspline::BSplineBasis;
 pub use column::{CategoricalColumn, Column, DataType};
 pub use copula::{Copula, CopulaResult, CopulaType};
-pub use dataframe::{ColumnType, DataFrame, DataFrameBuilder, TypeInferenceConfig};
+pub use dataframe::{
+    ColumnType, DataFrame, DataFrameBuilder, MergeConfig, MergeValidate, TypeInferenceConfig,
+};
 pub use datasets::Datasets;
 pub use descrstatsw::DescrStatsW;
+pub use design::{
+    DesignInfo, DesignMatrices, DesignTerm, MissingPolicy, RowMap, TermFunction, TermKind,
+};
 pub use distributions::{chi2_pvalue, f_pvalue, logistic, norm_pdf, t_pvalue_two, t_quantile};
 pub use error::GreenersError;
 pub use formula::Formula;
 pub use functional_coef::{FunctionalCoef, FunctionalCoefResult, KernelType};
 pub use gmm_clustering::{GmmClustering, GmmResult};
+pub use groupby::GroupBy;
 pub use isotonic::{IsotonicRegression, IsotonicResult};
 pub use linalg::{
     drop_collinear, CollinearityResult, LinalgCholesky, LinalgDeterminant, LinalgEig, LinalgEigh,
     LinalgInverse, LinalgPinv, LinalgQR, LinalgSVD, UPLO,
 };
 pub use margins::{MarginalEffectsResult, Margins};
+pub use model::{CrossValResult, CrossValidation, FittedModel, Model};
 pub use moment_helpers::MomentHelpers;
 pub use multipletests::{MultiTestMethod, MultipleTests};
 pub use multivariate::{
@@ -32,12 +41,19 @@ pub use nonparametric::{
     KDEMultivariate, KDEMultivariateResult, KDEResult, KDEUnivariate, Kernel, KernelReg,
     KernelRegResult, Lowess, LowessResult,
 };
+pub use persist::{Persist, FORMAT_VERSION};
 pub use predicate::{DsvRow, RowPredicate};
 pub use proportion::ProportionTests;
+pub use rng::{GreenersRng, DEFAULT_SEED};
+pub use sandwich::Sandwich;
 pub use stats::{AnovaRegressionResult, AnovaResult, CompareMeansResult, Stats, TTestResult};
 pub use summary_col::{ModelSummary, SummaryCol, SummaryColResult};
 pub use transforms::Transforms;
-pub use types::{array1_slice, CovarianceType, InferenceType};
+pub use types::{array1_slice, CovarianceType, HacBandwidth, HacKernel, InferenceType};
+pub use var_dynamics::{
+    HistoricalDecomposition, IrfBands, IrfKind, VarBootstrapConfig, VarBootstrapScheme,
+    VarDynamics, VarForecast,
+};
 
 pub mod biplot;
 pub mod bootstrap;
@@ -47,21 +63,28 @@ pub mod copula;
 pub mod dataframe;
 pub mod datasets;
 pub mod descrstatsw;
+pub mod design;
 pub mod distributions;
 pub mod error;
 pub mod formula;
 pub mod functional_coef;
 pub mod gmm_clustering;
+pub mod groupby;
 pub mod isotonic;
 pub mod linalg;
 pub mod margins;
+pub mod model;
 pub mod moment_helpers;
 pub mod multipletests;
 pub mod multivariate;
 pub mod nonparametric;
+pub mod persist;
 pub mod predicate;
 pub mod proportion;
+pub mod rng;
+pub mod sandwich;
 pub mod stats;
 pub mod summary_col;
 pub mod transforms;
 pub mod types;
+pub mod var_dynamics;
@@ -1,8 +1,9 @@
-/// Pure-Rust linear algebra layer backed by `faer`, replacing `ndarray-linalg`/OpenBLAS.
-///
-/// Exposes the same method names (`.inv()`, `.qr()`, `.svd()`, `.eigh()`, `.eig()`,
-/// `.cholesky()`, `.det()`) that the old `ndarray_linalg` traits provided, so the
-/// only change needed in each caller is to swap the `use` line.
+//! Pure-Rust linear algebra layer backed by `faer`, replacing `ndarray-linalg`/OpenBLAS.
+//!
+//! Exposes the same method names (`.inv()`, `.qr()`, `.svd()`, `.eigh()`, `.eig()`,
+//! `.cholesky()`, `.det()`) that the old `ndarray_linalg` traits provided, so the
+//! only change needed in each caller is to swap the `use` line.
+
 use crate::error::GreenersError;
 use faer::linalg::solvers::{
     ColPivQr, DenseSolveCore, Llt, PartialPivLu, Qr, SelfAdjointEigen, Svd,
@@ -11,6 +12,7 @@ use faer::prelude::*;
 use faer::Side;
 use ndarray::{Array1, Array2};
 use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
 
 // ─── UPLO ─────────────────────────────────────────────────────────────────────
 
@@ -260,6 +262,7 @@ impl LinalgDeterminant for Array2<f64> {
 
 // ─── Collinearity detection ─────────────────────────────────────────────────
 
+#[derive(Serialize, Deserialize)]
 pub struct CollinearityResult {
     pub x_clean: Array2<f64>,
     pub keep_indices: Vec<usize>,
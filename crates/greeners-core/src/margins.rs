@@ -1,7 +1,8 @@
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 
 /// Average Marginal Effects result with standard errors.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarginalEffectsResult {
     pub variable_names: Vec<String>,
     pub effects: Vec<f64>,
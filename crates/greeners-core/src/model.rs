@@ -0,0 +1,248 @@
+//! Common interface for estimators and their fitted results.
+//!
+//! [`FittedModel`] exposes what every estimation result has in common
+//! (coefficients, standard errors, fit statistics, predictions), so generic
+//! tooling such as `SummaryCol::compare_fitted`, `Bootstrap::pairs_bootstrap_model`
+//! and [`CrossValidation`] can work with any model. [`Model`] is implemented by
+//! estimators that fit on a response vector and a design matrix, and by any
+//! closure `Fn(&Array1<f64>, &Array2<f64>) -> Result<R, GreenersError>`, which
+//! is the way to pass an estimator together with its options.
+
+use crate::GreenersError;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Estimation result with a common set of accessors.
+///
+/// Nonparametric models (e.g. forests) return empty `params` and `bse`.
+pub trait FittedModel {
+    /// Short model name, used as a column header in comparison tables.
+    fn model_name(&self) -> String;
+
+    /// Estimated coefficients.
+    fn params(&self) -> Array1<f64>;
+
+    /// Standard errors of the coefficients.
+    fn bse(&self) -> Array1<f64>;
+
+    /// Number of observations used in estimation.
+    fn nobs(&self) -> usize;
+
+    /// Predictions on the scale of the response for a new design matrix
+    /// with the same columns as the one used in estimation.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError>;
+
+    /// Variance-covariance matrix of the coefficients, when available.
+    fn vcov(&self) -> Option<Array2<f64>> {
+        None
+    }
+
+    /// Two-sided p-values of the coefficients, when available.
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        None
+    }
+
+    /// Coefficient names (`x0`, `x1`, ... when the model was fitted without names).
+    fn param_names(&self) -> Vec<String> {
+        (0..self.params().len())
+            .map(|i| format!("x{}", i))
+            .collect()
+    }
+
+    /// Number of estimated parameters counted by the information criteria.
+    fn n_params(&self) -> usize {
+        self.params().len()
+    }
+
+    /// Maximized log-likelihood, when the model is likelihood-based.
+    fn loglike(&self) -> Option<f64> {
+        None
+    }
+
+    /// Akaike information criterion: `-2 ln L + 2k`.
+    fn aic(&self) -> Option<f64> {
+        self.loglike()
+            .map(|ll| -2.0 * ll + 2.0 * self.n_params() as f64)
+    }
+
+    /// Bayesian information criterion: `-2 ln L + k ln n`.
+    fn bic(&self) -> Option<f64> {
+        self.loglike()
+            .map(|ll| -2.0 * ll + self.n_params() as f64 * (self.nobs() as f64).ln())
+    }
+
+    /// R-squared (or the model's pseudo R-squared), when defined.
+    fn r_squared(&self) -> Option<f64> {
+        None
+    }
+
+    /// Response residuals `y - predict(x)`.
+    fn residuals(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let y_hat = self.predict(x)?;
+        if y_hat.len() != y.len() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "y has {} rows but X has {}",
+                y.len(),
+                y_hat.len()
+            )));
+        }
+        Ok(y - &y_hat)
+    }
+}
+
+/// Estimator that can be fitted on a response vector and a design matrix.
+///
+/// # Examples
+/// ```
+/// use greeners_core::{FittedModel, GreenersError, Model};
+/// use ndarray::{array, Array1, Array2};
+///
+/// struct Mean(f64, usize);
+///
+/// impl FittedModel for Mean {
+///     fn model_name(&self) -> String { "Mean".into() }
+///     fn params(&self) -> Array1<f64> { array![self.0] }
+///     fn bse(&self) -> Array1<f64> { array![f64::NAN] }
+///     fn nobs(&self) -> usize { self.1 }
+///     fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+///         Ok(Array1::from_elem(x.nrows(), self.0))
+///     }
+/// }
+///
+/// // Any closure with the right signature is a `Model`.
+/// let mean = |y: &Array1<f64>, _x: &Array2<f64>| -> Result<Mean, GreenersError> {
+///     Ok(Mean(y.mean().unwrap_or(0.0), y.len()))
+/// };
+/// let fitted = mean.fit(&array![1.0, 2.0, 3.0], &Array2::zeros((3, 1))).unwrap();
+/// assert_eq!(fitted.params()[0], 2.0);
+/// ```
+pub trait Model {
+    type Fitted: FittedModel;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<Self::Fitted, GreenersError>;
+}
+
+impl<F, R> Model for F
+where
+    F: Fn(&Array1<f64>, &Array2<f64>) -> Result<R, GreenersError>,
+    R: FittedModel,
+{
+    type Fitted = R;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<R, GreenersError> {
+        self(y, x)
+    }
+}
+
+/// Design matrix for `FittedModel::predict`: `x` may have one column per
+/// coefficient, or also include the columns that were dropped for
+/// collinearity at fit time (`omitted`, as stored in `omitted_vars`).
+pub fn align_design(
+    x: &Array2<f64>,
+    n_params: usize,
+    omitted: &[(usize, String)],
+) -> Result<Array2<f64>, GreenersError> {
+    if x.ncols() == n_params {
+        return Ok(x.clone());
+    }
+    if !omitted.is_empty() && x.ncols() == n_params + omitted.len() {
+        let keep: Vec<usize> = (0..x.ncols())
+            .filter(|j| !omitted.iter().any(|(p, _)| p == j))
+            .collect();
+        return Ok(x.select(Axis(1), &keep));
+    }
+    Err(GreenersError::ShapeMismatch(format!(
+        "X has {} columns but the model has {} coefficients",
+        x.ncols(),
+        n_params
+    )))
+}
+
+/// Out-of-sample evaluation of a [`Model`].
+pub struct CrossValidation;
+
+impl CrossValidation {
+    /// K-fold cross-validation with contiguous folds (no shuffling, so
+    /// results are deterministic; shuffle the rows beforehand if the data are
+    /// ordered).
+    ///
+    /// Each fold is predicted by the model fitted on the remaining folds.
+    pub fn k_fold<M: Model>(
+        model: &M,
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_folds: usize,
+    ) -> Result<CrossValResult, GreenersError> {
+        let n = y.len();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "X and y must have same number of rows".to_string(),
+            ));
+        }
+        if n_folds < 2 || n_folds > n {
+            return Err(GreenersError::InvalidOperation(format!(
+                "n_folds must be between 2 and the number of observations ({}), got {}",
+                n, n_folds
+            )));
+        }
+
+        let mut predictions = Array1::<f64>::zeros(n);
+        let mut fold_mse = Vec::with_capacity(n_folds);
+        for fold in 0..n_folds {
+            let start = fold * n / n_folds;
+            let end = (fold + 1) * n / n_folds;
+            let test: Vec<usize> = (start..end).collect();
+            let train: Vec<usize> = (0..start).chain(end..n).collect();
+
+            let fitted = model.fit(&y.select(Axis(0), &train), &x.select(Axis(0), &train))?;
+            let pred = fitted.predict(&x.select(Axis(0), &test))?;
+            let mut sse = 0.0;
+            for (j, &i) in test.iter().enumerate() {
+                predictions[i] = pred[j];
+                sse += (y[i] - pred[j]).powi(2);
+            }
+            fold_mse.push(sse / test.len() as f64);
+        }
+
+        let mse = y
+            .iter()
+            .zip(predictions.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Ok(CrossValResult {
+            n_folds,
+            fold_mse,
+            mse,
+            predictions,
+        })
+    }
+}
+
+/// Result of [`CrossValidation::k_fold`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossValResult {
+    pub n_folds: usize,
+    /// Mean squared prediction error in each fold.
+    pub fold_mse: Vec<f64>,
+    /// Mean squared prediction error over all observations.
+    pub mse: f64,
+    /// Out-of-fold prediction for every observation.
+    pub predictions: Array1<f64>,
+}
+
+impl fmt::Display for CrossValResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^60}", " K-Fold Cross-Validation ")?;
+        writeln!(f, "{:<24} {:>12}", "Folds:", self.n_folds)?;
+        writeln!(f, "{:<24} {:>12.6}", "MSE:", self.mse)?;
+        writeln!(f, "{:<24} {:>12.6}", "RMSE:", self.mse.sqrt())?;
+        writeln!(f, "{:-^60}", "")?;
+        for (i, m) in self.fold_mse.iter().enumerate() {
+            writeln!(f, "  Fold {:<17} {:>12.6}", i + 1, m)?;
+        }
+        write!(f, "{:=^60}", "")
+    }
+}
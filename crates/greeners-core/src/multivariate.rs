@@ -1,13 +1,14 @@
 use crate::error::GreenersError;
 use crate::linalg::{LinalgEigh as _, LinalgInverse as _, UPLO};
 use ndarray::{s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, FisherSnedecor};
 use std::fmt;
 
 // ─── PCA ───────────────────────────────────────────────────────────────────────
 
 /// Result of Principal Component Analysis.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PCAResult {
     /// Principal components (eigenvectors as columns, k x n_components)
     pub components: Array2<f64>,
@@ -162,7 +163,7 @@ pub enum Rotation {
 }
 
 /// Result of Factor Analysis.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FactorResult {
     pub loadings: Array2<f64>,
     pub communalities: Array1<f64>,
@@ -311,7 +312,7 @@ fn varimax_rotation(loadings: &Array2<f64>, max_iter: usize) -> Array2<f64> {
 // ─── MANOVA ────────────────────────────────────────────────────────────────────
 
 /// Result of MANOVA test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ManovaResult {
     /// Wilks' Lambda
     pub wilks_lambda: f64,
@@ -522,7 +523,7 @@ impl MANOVA {
 // ─── Canonical Correlation Analysis ──────────────────────────────────────────
 
 /// Result of Canonical Correlation Analysis.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CanCorrResult {
     /// Canonical correlations (descending)
     pub cancorr: Array1<f64>,
@@ -1,9 +1,10 @@
 use crate::error::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Kernel function for density estimation and kernel regression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Kernel {
     Gaussian,
     Epanechnikov,
@@ -43,7 +44,7 @@ impl Kernel {
 // ─── KDE ───────────────────────────────────────────────────────────────────────
 
 /// Result of Kernel Density Estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KDEResult {
     pub bandwidth: f64,
     pub support: Array1<f64>,
@@ -153,7 +154,7 @@ impl KDEUnivariate {
 // ─── LOWESS ────────────────────────────────────────────────────────────────────
 
 /// Result of LOWESS smoothing.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LowessResult {
     pub smoothed: Array1<f64>,
     pub residuals: Array1<f64>,
@@ -307,7 +308,7 @@ fn loess_smooth(x: &[f64], y: &[f64], w: &[f64], span: usize) -> Vec<f64> {
 // ─── KernelReg ─────────────────────────────────────────────────────────────────
 
 /// Result of Nadaraya-Watson kernel regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KernelRegResult {
     pub fitted: Array1<f64>,
     pub residuals: Array1<f64>,
@@ -385,7 +386,7 @@ impl KernelReg {
 // ─── KDEMultivariate ────────────────────────────────────────────────────────────
 
 /// Result of multivariate kernel density estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KDEMultivariateResult {
     pub bandwidths: Array1<f64>,
     pub n_obs: usize,
@@ -0,0 +1,463 @@
+//! Versioned persistence of fitted results and trained models.
+//!
+//! Result types implement `Serialize`/`Deserialize`, and [`Persist`] (implemented
+//! for every such type) writes them as JSON or as a compact binary form
+//! (bincode). The payload is wrapped in an envelope recording the format
+//! version and the result type, which are checked on load:
+//!
+//! ```json
+//! {"format":"greeners","version":1,"kind":"OlsResult","payload":{...}}
+//! ```
+//!
+//! JSON has no representation for NaN or infinities; non-finite floats are
+//! written as `null` and read back as NaN.
+
+use crate::GreenersError;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Version of the persisted format. Files written by a newer version are
+/// rejected on load.
+pub const FORMAT_VERSION: u32 = 1;
+
+const FORMAT_TAG: &str = "greeners";
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    format: &'a str,
+    version: u32,
+    kind: &'a str,
+    payload: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+    kind: String,
+}
+
+/// JSON envelope once the header has been checked; the header keys are
+/// skipped as unknown fields.
+#[derive(Deserialize)]
+struct Envelope<T> {
+    payload: T,
+}
+
+/// Save and load results in a versioned JSON or binary envelope.
+///
+/// # Examples
+/// ```
+/// use greeners_core::{GreenersError, Persist};
+/// use ndarray::array;
+///
+/// # fn main() -> Result<(), GreenersError> {
+/// let coefs = array![1.0, f64::NAN];
+/// let restored = ndarray::Array1::<f64>::from_json(&coefs.to_json()?)?;
+/// assert_eq!(restored[0], 1.0);
+/// assert!(restored[1].is_nan());
+///
+/// let bytes = coefs.to_bytes()?;
+/// assert_eq!(ndarray::Array1::<f64>::from_bytes(&bytes)?[0], 1.0);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Persist: Serialize + DeserializeOwned {
+    /// Serialize to a JSON string.
+    fn to_json(&self) -> Result<String, GreenersError> {
+        serde_json::to_string(&envelope(self)).map_err(ser_error)
+    }
+
+    /// Deserialize from a JSON string written by [`Persist::to_json`].
+    fn from_json(json: &str) -> Result<Self, GreenersError> {
+        let header: Header = serde_json::from_str(json).map_err(ser_error)?;
+        check_header::<Self>(&header)?;
+        let mut de = serde_json::Deserializer::from_str(json);
+        let envelope = Envelope::<Self>::deserialize(NullAsNan(&mut de)).map_err(ser_error)?;
+        de.end().map_err(ser_error)?;
+        Ok(envelope.payload)
+    }
+
+    /// Serialize to the compact binary form.
+    fn to_bytes(&self) -> Result<Vec<u8>, GreenersError> {
+        bincode::serialize(&envelope(self)).map_err(ser_error)
+    }
+
+    /// Deserialize from bytes written by [`Persist::to_bytes`].
+    fn from_bytes(bytes: &[u8]) -> Result<Self, GreenersError> {
+        let header: Header = bincode::deserialize(bytes).map_err(ser_error)?;
+        check_header::<Self>(&header)?;
+        // Binary fields are positional: the header comes first
+        let (_, payload): (Header, Self) = bincode::deserialize(bytes).map_err(ser_error)?;
+        Ok(payload)
+    }
+
+    /// Write to a file: JSON if the extension is `.json`, binary otherwise.
+    fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), GreenersError> {
+        let path = path.as_ref();
+        let bytes = if is_json(path) {
+            self.to_json()?.into_bytes()
+        } else {
+            self.to_bytes()?
+        };
+        std::fs::write(path, bytes)
+            .map_err(|e| GreenersError::Serialization(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Read a file written by [`Persist::save`].
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, GreenersError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| GreenersError::Serialization(format!("{}: {}", path.display(), e)))?;
+        if is_json(path) {
+            let json = std::str::from_utf8(&bytes).map_err(ser_error)?;
+            Self::from_json(json)
+        } else {
+            Self::from_bytes(&bytes)
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Persist for T {}
+
+fn envelope<T>(payload: &T) -> EnvelopeRef<'_, T> {
+    EnvelopeRef {
+        format: FORMAT_TAG,
+        version: FORMAT_VERSION,
+        kind: kind_of::<T>(),
+        payload,
+    }
+}
+
+/// Unqualified type name, so that moving a type between modules does not
+/// invalidate saved files.
+fn kind_of<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+fn check_header<T>(header: &Header) -> Result<(), GreenersError> {
+    if header.format != FORMAT_TAG {
+        return Err(GreenersError::Serialization(format!(
+            "not a greeners file (format tag '{}')",
+            header.format
+        )));
+    }
+    if header.version > FORMAT_VERSION {
+        return Err(GreenersError::Serialization(format!(
+            "format version {} is newer than the supported version {}",
+            header.version, FORMAT_VERSION
+        )));
+    }
+    let expected = kind_of::<T>();
+    if header.kind != expected {
+        return Err(GreenersError::Serialization(format!(
+            "file contains a {}, expected {}",
+            header.kind, expected
+        )));
+    }
+    Ok(())
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn ser_error<E: fmt::Display>(e: E) -> GreenersError {
+    GreenersError::Serialization(e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// JSON reading with `null` accepted as NaN wherever a float is expected.
+//
+// `NullAsNan` wraps a deserializer and re-wraps every nested deserializer
+// (sequence elements, map values, enum variants, options), so the rule
+// applies at any depth, including the elements of ndarray arrays.
+// ---------------------------------------------------------------------------
+
+struct NullAsNan<D>(D);
+
+/// Wrapper for visitors, seeds and accessors handed out while deserializing.
+struct Wrap<T>(T);
+
+/// Visitor for floats: passes numbers through and maps `null` to NaN.
+struct Float<V>(V);
+
+macro_rules! forward_deserialize {
+    ($($method:ident),*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+            self.0.$method(Wrap(visitor))
+        }
+    )*};
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for NullAsNan<D> {
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_seq,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any
+    );
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_any(Float(visitor))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_any(Float(visitor))
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_unit_struct(name, Wrap(visitor))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_newtype_struct(name, Wrap(visitor))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_tuple(len, Wrap(visitor))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_tuple_struct(name, len, Wrap(visitor))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_struct(name, fields, Wrap(visitor))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.0.deserialize_enum(name, variants, Wrap(visitor))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty),*) => {$(
+        fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+            self.0.$method(v)
+        }
+    )*};
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Wrap<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    forward_visit!(
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_str: &str,
+        visit_borrowed_str: &'de str,
+        visit_string: String,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.0.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.0.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<V::Value, D::Error> {
+        self.0.visit_some(NullAsNan(d))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<V::Value, D::Error> {
+        self.0.visit_newtype_struct(NullAsNan(d))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.0.visit_seq(Wrap(seq))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.0.visit_map(Wrap(map))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.0.visit_enum(Wrap(data))
+    }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Float<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<V::Value, E> {
+        self.0.visit_f64(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<V::Value, E> {
+        self.0.visit_i64(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<V::Value, E> {
+        self.0.visit_u64(v)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.0.visit_f64(f64::NAN)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Wrap<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<S::Value, D::Error> {
+        self.0.deserialize(NullAsNan(d))
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Wrap<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        self.0.next_element_seed(Wrap(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Wrap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        self.0.next_key_seed(Wrap(seed))
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error> {
+        self.0.next_value_seed(Wrap(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for Wrap<A> {
+    type Error = A::Error;
+    type Variant = Wrap<A::Variant>;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<(T::Value, Self::Variant), A::Error> {
+        self.0
+            .variant_seed(Wrap(seed))
+            .map(|(value, variant)| (value, Wrap(variant)))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Wrap<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.0.newtype_variant_seed(Wrap(seed))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.0.tuple_variant(len, Wrap(visitor))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.0.struct_variant(fields, Wrap(visitor))
+    }
+}
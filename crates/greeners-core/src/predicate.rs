@@ -1,46 +1,968 @@
 //! Row predicate evaluation for CSV/TSV filtering.
+//!
+//! A [`RowPredicate`] is a small boolean expression evaluated against each raw
+//! record while a delimited file is being read, so that rows can be discarded
+//! before they are materialised into typed columns.
+//!
+//! # Syntax
+//! - Comparisons: `==` (or `=`), `!=` (or `<>`), `<`, `<=`, `>`, `>=`
+//! - Logic: `and` / `&&`, `or` / `||`, `not` / `!`
+//! - Membership: `region in ["north", "south"]`, `year not in [2020, 2021]`
+//! - Null checks: `income is null`, `income is not null`
+//! - Arithmetic on columns: `+`, `-`, `*`, `/`, `%` and unary minus
+//! - Literals: numbers, `'single'` or `"double"` quoted strings, `true`,
+//!   `false`, `null`
+//! - Column names containing spaces or symbols can be quoted with backticks:
+//!   `` `unit price` * qty > 100 ``
+//!
+//! Keywords are case-insensitive. Comparisons involving a missing value
+//! evaluate to null, logic follows SQL three-valued semantics and a row is kept
+//! only when the whole predicate evaluates to `true`.
+
+use crate::dataframe::ColumnType;
+use crate::GreenersError;
+use std::collections::HashMap;
 
 /// Row predicate for filtering data during loading.
-/// This is a simplified version of Hayashi's RowPredicate for use in Greeners.
+///
+/// # Examples
+/// ```
+/// use greeners_core::{DsvRow, RowPredicate};
+///
+/// let pred = RowPredicate::parse("age >= 18 and region in ['north', 'south']").unwrap();
+/// assert_eq!(pred.referenced_columns(), vec!["age", "region"]);
+///
+/// let fields = vec!["21".to_string(), "north".to_string()];
+/// let layout = vec![(0, "age".to_string()), (1, "region".to_string())];
+/// let row = DsvRow { fields: &fields, layout: &layout };
+/// assert!(pred.evaluate(&row));
+/// ```
 #[derive(Debug, Clone)]
 pub struct RowPredicate {
-    // The predicate expression as a string (for now, we'll use a simple approach)
+    /// The original predicate source.
     expr: String,
+    /// Parsed expression tree.
+    ast: Expr,
+    /// Raw field values treated as missing.
+    null_values: Vec<String>,
+}
+
+/// Binary operators of the predicate language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+            BinOp::Lt => "<",
+            BinOp::Le => "<=",
+            BinOp::Gt => ">",
+            BinOp::Ge => ">=",
+            BinOp::And => "and",
+            BinOp::Or => "or",
+        }
+    }
+
+    fn is_arithmetic(self) -> bool {
+        matches!(
+            self,
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem
+        )
+    }
+
+    fn is_ordering(self) -> bool {
+        matches!(self, BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge)
+    }
+}
+
+/// Literal values allowed in the predicate source.
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+}
+
+/// Predicate expression tree.
+#[derive(Debug, Clone)]
+enum Expr {
+    Column(String),
+    Lit(Literal),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    In {
+        expr: Box<Expr>,
+        list: Vec<Literal>,
+        negated: bool,
+    },
+    IsNull {
+        expr: Box<Expr>,
+        negated: bool,
+    },
+}
+
+/// Static type of a sub-expression. `Any` is used for columns whose type is
+/// not known before loading and for the `null` literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ty {
+    Num,
+    Str,
+    Bool,
+    Any,
+}
+
+impl Ty {
+    fn name(self) -> &'static str {
+        match self {
+            Ty::Num => "numeric",
+            Ty::Str => "string",
+            Ty::Bool => "boolean",
+            Ty::Any => "untyped",
+        }
+    }
+
+    fn compatible(self, other: Ty) -> bool {
+        self == Ty::Any || other == Ty::Any || self == other
+    }
+
+    fn from_column_type(ct: &ColumnType) -> Ty {
+        match ct {
+            ColumnType::Int | ColumnType::Float => Ty::Num,
+            ColumnType::Bool => Ty::Bool,
+            ColumnType::String | ColumnType::Categorical | ColumnType::DateTime => Ty::Str,
+        }
+    }
+}
+
+impl Literal {
+    fn ty(&self) -> Ty {
+        match self {
+            Literal::Num(_) => Ty::Num,
+            Literal::Str(_) => Ty::Str,
+            Literal::Bool(_) => Ty::Bool,
+            Literal::Null => Ty::Any,
+        }
+    }
 }
 
 impl RowPredicate {
     /// Parse a predicate string.
-    pub fn parse(expr: &str) -> Result<Self, crate::GreenersError> {
-        Ok(Self {
+    ///
+    /// Syntax errors and type errors that can be detected without knowing the
+    /// column types (e.g. `x + 'a'` or `1 and y`) are reported here.
+    pub fn parse(expr: &str) -> Result<Self, GreenersError> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let ast = parser.parse_or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(GreenersError::FormulaError(format!(
+                "Invalid predicate '{}': unexpected {}",
+                expr,
+                parser.tokens[parser.pos].describe()
+            )));
+        }
+
+        let pred = Self {
             expr: expr.to_string(),
-        })
+            ast,
+            null_values: default_null_values(),
+        };
+        pred.check_types(&|_| Ty::Any)?;
+        Ok(pred)
+    }
+
+    /// Replace the raw values treated as missing (default: the same list as
+    /// `TypeInferenceConfig::null_values`).
+    pub fn with_null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
     }
 
-    /// Get columns referenced by this predicate.
+    /// The predicate source string.
+    pub fn as_str(&self) -> &str {
+        &self.expr
+    }
+
+    /// Get columns referenced by this predicate, in order of first appearance.
     pub fn referenced_columns(&self) -> Vec<String> {
-        // Simple extraction: find identifiers that look like column names
-        // This is a simplified version - in practice, Hayashi does this properly
         let mut cols = Vec::new();
-        for word in self.expr.split(|c: char| !c.is_alphanumeric() && c != '_') {
-            if !word.is_empty() && word.chars().next().unwrap().is_alphabetic() {
-                cols.push(word.to_string());
+        collect_columns(&self.ast, &mut cols);
+        cols
+    }
+
+    /// Type-check the predicate against a row layout.
+    ///
+    /// Every referenced column must appear in `layout`. Columns with an entry
+    /// in `column_types` are checked against their declared type (`Int` and
+    /// `Float` are numeric, `Bool` is boolean and the remaining types are
+    /// strings); other columns are accepted in any context and coerced when
+    /// the row is evaluated.
+    pub fn check_layout(
+        &self,
+        layout: &[(usize, String)],
+        column_types: &HashMap<String, ColumnType>,
+    ) -> Result<(), GreenersError> {
+        for col in self.referenced_columns() {
+            if !layout.iter().any(|(_, n)| *n == col) {
+                return Err(GreenersError::VariableNotFound(format!(
+                    "'{}' referenced in predicate '{}'",
+                    col, self.expr
+                )));
             }
         }
-        cols
+        self.check_types(&|name| {
+            column_types
+                .get(name)
+                .map(Ty::from_column_type)
+                .unwrap_or(Ty::Any)
+        })
     }
 
     /// Evaluate predicate against a row.
-    pub fn evaluate(&self, _row: &DsvRow) -> bool {
-        // For now, delegate to Hayashi's evaluation via a callback
-        // This is a placeholder - in practice, Hayashi should evaluate predicates
-        // before calling Greeners, or we need a more complete expression evaluator.
-        // For the minimal implementation, we'll always return true.
-        // TODO: Implement proper predicate evaluation or use Hayashi's evaluator.
-        true
+    ///
+    /// Returns `true` only when the predicate is definitely true; rows where
+    /// it evaluates to false or null (e.g. comparing a missing value) are
+    /// rejected.
+    pub fn evaluate(&self, row: &DsvRow) -> bool {
+        matches!(self.eval(&self.ast, row), Value::Bool(true))
+    }
+
+    fn check_types(&self, column_ty: &dyn Fn(&str) -> Ty) -> Result<(), GreenersError> {
+        let ty = infer(&self.ast, column_ty).map_err(|msg| {
+            GreenersError::FormulaError(format!("Invalid predicate '{}': {}", self.expr, msg))
+        })?;
+        if ty.compatible(Ty::Bool) {
+            Ok(())
+        } else {
+            Err(GreenersError::FormulaError(format!(
+                "Invalid predicate '{}': expression is {}, expected boolean",
+                self.expr,
+                ty.name()
+            )))
+        }
+    }
+
+    fn is_null(&self, raw: &str) -> bool {
+        let t = raw.trim();
+        self.null_values.iter().any(|nv| nv == t)
+    }
+
+    /// Coerce a raw field or value to a number.
+    fn as_num(&self, v: Value) -> Option<f64> {
+        match v {
+            Value::Num(x) => Some(x),
+            Value::Field(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+
+    /// Coerce a raw field or value to a boolean.
+    fn as_bool(&self, v: Value) -> Option<bool> {
+        match v {
+            Value::Bool(b) => Some(b),
+            Value::Field(s) => parse_bool(s.trim()),
+            _ => None,
+        }
+    }
+
+    fn eval<'a>(&'a self, expr: &'a Expr, row: &'a DsvRow<'_>) -> Value<'a> {
+        match expr {
+            Expr::Column(name) => match row.get_str(name) {
+                Some(s) if !self.is_null(s) => Value::Field(s),
+                _ => Value::Null,
+            },
+            Expr::Lit(lit) => Value::from_literal(lit),
+            Expr::Neg(e) => match self.as_num(self.eval(e, row)) {
+                Some(x) => Value::Num(-x),
+                None => Value::Null,
+            },
+            Expr::Not(e) => match self.as_bool(self.eval(e, row)) {
+                Some(b) => Value::Bool(!b),
+                None => Value::Null,
+            },
+            Expr::Binary(BinOp::And, l, r) => {
+                let lv = self.as_bool(self.eval(l, row));
+                if lv == Some(false) {
+                    return Value::Bool(false);
+                }
+                match (lv, self.as_bool(self.eval(r, row))) {
+                    (_, Some(false)) => Value::Bool(false),
+                    (Some(true), Some(true)) => Value::Bool(true),
+                    _ => Value::Null,
+                }
+            }
+            Expr::Binary(BinOp::Or, l, r) => {
+                let lv = self.as_bool(self.eval(l, row));
+                if lv == Some(true) {
+                    return Value::Bool(true);
+                }
+                match (lv, self.as_bool(self.eval(r, row))) {
+                    (_, Some(true)) => Value::Bool(true),
+                    (Some(false), Some(false)) => Value::Bool(false),
+                    _ => Value::Null,
+                }
+            }
+            Expr::Binary(op, l, r) if op.is_arithmetic() => {
+                match (
+                    self.as_num(self.eval(l, row)),
+                    self.as_num(self.eval(r, row)),
+                ) {
+                    (Some(a), Some(b)) => Value::Num(match op {
+                        BinOp::Add => a + b,
+                        BinOp::Sub => a - b,
+                        BinOp::Mul => a * b,
+                        BinOp::Div => a / b,
+                        _ => a % b,
+                    }),
+                    _ => Value::Null,
+                }
+            }
+            Expr::Binary(op, l, r) => {
+                let (lv, rv) = (self.eval(l, row), self.eval(r, row));
+                match self.compare(lv, rv) {
+                    Some(ord) => Value::Bool(match op {
+                        BinOp::Eq => ord.is_eq(),
+                        BinOp::Ne => ord.is_ne(),
+                        BinOp::Lt => ord.is_lt(),
+                        BinOp::Le => ord.is_le(),
+                        BinOp::Gt => ord.is_gt(),
+                        _ => ord.is_ge(),
+                    }),
+                    None => Value::Null,
+                }
+            }
+            Expr::In {
+                expr,
+                list,
+                negated,
+            } => {
+                let v = self.eval(expr, row);
+                if matches!(v, Value::Null) {
+                    return Value::Null;
+                }
+                let mut saw_null = false;
+                for lit in list {
+                    match self.compare(v, Value::from_literal(lit)) {
+                        Some(std::cmp::Ordering::Equal) => return Value::Bool(!negated),
+                        Some(_) => {}
+                        None => saw_null = true,
+                    }
+                }
+                if saw_null {
+                    Value::Null
+                } else {
+                    Value::Bool(*negated)
+                }
+            }
+            Expr::IsNull { expr, negated } => {
+                let null = matches!(self.eval(expr, row), Value::Null);
+                Value::Bool(null != *negated)
+            }
+        }
+    }
+
+    /// Compare two values, coercing raw fields to the type of the other side.
+    /// Two raw fields compare numerically when both parse as numbers and as
+    /// strings otherwise. Returns `None` when either side is null or the
+    /// values cannot be compared.
+    fn compare(&self, a: Value, b: Value) -> Option<std::cmp::Ordering> {
+        match (a, b) {
+            (Value::Null, _) | (_, Value::Null) => None,
+            (Value::Num(_), _) | (_, Value::Num(_)) => {
+                self.as_num(a)?.partial_cmp(&self.as_num(b)?)
+            }
+            (Value::Bool(_), _) | (_, Value::Bool(_)) => {
+                Some(self.as_bool(a)?.cmp(&self.as_bool(b)?))
+            }
+            (Value::Field(x), Value::Field(y)) => match (self.as_num(a), self.as_num(b)) {
+                (Some(p), Some(q)) => p.partial_cmp(&q),
+                _ => Some(x.trim().cmp(y.trim())),
+            },
+            (Value::Str(x), y) | (y, Value::Str(x)) => {
+                let y = match y {
+                    Value::Str(s) | Value::Field(s) => s.trim(),
+                    _ => return None,
+                };
+                let ord = x.cmp(y);
+                // Keep the original orientation when the literal was on the right.
+                Some(if matches!(a, Value::Str(_)) {
+                    ord
+                } else {
+                    ord.reverse()
+                })
+            }
+        }
+    }
+}
+
+/// Runtime value produced while evaluating a predicate. `Field` holds raw,
+/// untyped text from the row and is coerced on use.
+#[derive(Debug, Clone, Copy)]
+enum Value<'a> {
+    Null,
+    Num(f64),
+    Bool(bool),
+    Str(&'a str),
+    Field(&'a str),
+}
+
+impl<'a> Value<'a> {
+    fn from_literal(lit: &'a Literal) -> Self {
+        match lit {
+            Literal::Num(x) => Value::Num(*x),
+            Literal::Str(s) => Value::Str(s),
+            Literal::Bool(b) => Value::Bool(*b),
+            Literal::Null => Value::Null,
+        }
+    }
+}
+
+fn default_null_values() -> Vec<String> {
+    ["", "NA", ".", "NaN", "NULL", "null"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Some(true),
+        "false" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn collect_columns(expr: &Expr, out: &mut Vec<String>) {
+    match expr {
+        Expr::Column(name) => {
+            if !out.contains(name) {
+                out.push(name.clone());
+            }
+        }
+        Expr::Lit(_) => {}
+        Expr::Neg(e) | Expr::Not(e) => collect_columns(e, out),
+        Expr::Binary(_, l, r) => {
+            collect_columns(l, out);
+            collect_columns(r, out);
+        }
+        Expr::In { expr, .. } | Expr::IsNull { expr, .. } => collect_columns(expr, out),
+    }
+}
+
+/// Infer the static type of an expression, reporting operand mismatches.
+fn infer(expr: &Expr, column_ty: &dyn Fn(&str) -> Ty) -> Result<Ty, String> {
+    match expr {
+        Expr::Column(name) => Ok(column_ty(name)),
+        Expr::Lit(lit) => Ok(lit.ty()),
+        Expr::Neg(e) => {
+            let t = infer(e, column_ty)?;
+            if t.compatible(Ty::Num) {
+                Ok(Ty::Num)
+            } else {
+                Err(format!("cannot negate a {} operand", t.name()))
+            }
+        }
+        Expr::Not(e) => {
+            let t = infer(e, column_ty)?;
+            if t.compatible(Ty::Bool) {
+                Ok(Ty::Bool)
+            } else {
+                Err(format!("'not' expects a boolean operand, got {}", t.name()))
+            }
+        }
+        Expr::Binary(op, l, r) => {
+            let (lt, rt) = (infer(l, column_ty)?, infer(r, column_ty)?);
+            if op.is_arithmetic() {
+                if lt.compatible(Ty::Num) && rt.compatible(Ty::Num) {
+                    Ok(Ty::Num)
+                } else {
+                    Err(format!(
+                        "'{}' expects numeric operands, got {} and {}",
+                        op.symbol(),
+                        lt.name(),
+                        rt.name()
+                    ))
+                }
+            } else if matches!(op, BinOp::And | BinOp::Or) {
+                if lt.compatible(Ty::Bool) && rt.compatible(Ty::Bool) {
+                    Ok(Ty::Bool)
+                } else {
+                    Err(format!(
+                        "'{}' expects boolean operands, got {} and {}",
+                        op.symbol(),
+                        lt.name(),
+                        rt.name()
+                    ))
+                }
+            } else if !lt.compatible(rt) {
+                Err(format!(
+                    "cannot compare {} with {} using '{}'",
+                    lt.name(),
+                    rt.name(),
+                    op.symbol()
+                ))
+            } else if op.is_ordering() && (lt == Ty::Bool || rt == Ty::Bool) {
+                Err(format!("'{}' is not defined for booleans", op.symbol()))
+            } else {
+                Ok(Ty::Bool)
+            }
+        }
+        Expr::In { expr, list, .. } => {
+            let mut t = infer(expr, column_ty)?;
+            for lit in list {
+                if !t.compatible(lit.ty()) {
+                    return Err(format!(
+                        "'in' list contains a {} value for a {} operand",
+                        lit.ty().name(),
+                        t.name()
+                    ));
+                }
+                if t == Ty::Any {
+                    t = lit.ty();
+                }
+            }
+            Ok(Ty::Bool)
+        }
+        Expr::IsNull { expr, .. } => {
+            infer(expr, column_ty)?;
+            Ok(Ty::Bool)
+        }
+    }
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    QuotedIdent(String),
+    Num(f64),
+    Str(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Op(&'static str),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(s) => format!("'{}'", s),
+            Token::QuotedIdent(s) => format!("`{}`", s),
+            Token::Num(x) => format!("number {}", x),
+            Token::Str(s) => format!("string '{}'", s),
+            Token::LParen => "'('".into(),
+            Token::RParen => "')'".into(),
+            Token::LBracket => "'['".into(),
+            Token::RBracket => "']'".into(),
+            Token::Comma => "','".into(),
+            Token::Op(op) => format!("'{}'", op),
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self, Token::Ident(s) if s.eq_ignore_ascii_case(kw))
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, GreenersError> {
+    let err =
+        |msg: String| GreenersError::FormulaError(format!("Invalid predicate '{}': {}", src, msg));
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()))
+        {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let x = text
+                .parse::<f64>()
+                .map_err(|_| err(format!("invalid number '{}'", text)))?;
+            tokens.push(Token::Num(x));
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            continue;
+        }
+
+        if c == '\'' || c == '"' || c == '`' {
+            let quote = c;
+            let mut s = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(err(format!("unterminated {} quote", quote))),
+                    Some('\\') if quote != '`' && i + 1 < chars.len() => {
+                        s.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some(&ch) if ch == quote => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        s.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push(if quote == '`' {
+                Token::QuotedIdent(s)
+            } else {
+                Token::Str(s)
+            });
+            continue;
+        }
+
+        let next = chars.get(i + 1).copied();
+        let (tok, width) = match (c, next) {
+            ('=', Some('=')) => (Token::Op("=="), 2),
+            ('!', Some('=')) => (Token::Op("!="), 2),
+            ('<', Some('>')) => (Token::Op("!="), 2),
+            ('<', Some('=')) => (Token::Op("<="), 2),
+            ('>', Some('=')) => (Token::Op(">="), 2),
+            ('&', Some('&')) => (Token::Op("&&"), 2),
+            ('|', Some('|')) => (Token::Op("||"), 2),
+            ('=', _) => (Token::Op("=="), 1),
+            ('<', _) => (Token::Op("<"), 1),
+            ('>', _) => (Token::Op(">"), 1),
+            ('!', _) => (Token::Op("!"), 1),
+            ('+', _) => (Token::Op("+"), 1),
+            ('-', _) => (Token::Op("-"), 1),
+            ('*', _) => (Token::Op("*"), 1),
+            ('/', _) => (Token::Op("/"), 1),
+            ('%', _) => (Token::Op("%"), 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('[', _) => (Token::LBracket, 1),
+            (']', _) => (Token::RBracket, 1),
+            (',', _) => (Token::Comma, 1),
+            _ => return Err(err(format!("unexpected character '{}'", c))),
+        };
+        tokens.push(tok);
+        i += width;
+    }
+
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser (recursive descent)
+// ============================================================================
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_op(&self, ops: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(op) => Some(op),
+            _ => None,
+        }
+    }
+
+    fn eat_keyword(&mut self, kw: &str) -> bool {
+        if self.peek().is_some_and(|t| t.is_keyword(kw)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume either spelling of a logical operator (`and` / `&&`).
+    fn eat_keyword_or_op(&mut self, kw: &str, op: &str) -> bool {
+        if self.eat_keyword(kw) {
+            return true;
+        }
+        if self.peek_op(&[op]).is_some() {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, tok: Token) -> Result<(), GreenersError> {
+        match self.peek() {
+            Some(t) if *t == tok => {
+                self.pos += 1;
+                Ok(())
+            }
+            other => Err(self.error(format!(
+                "expected {}, found {}",
+                tok.describe(),
+                other
+                    .map(Token::describe)
+                    .unwrap_or_else(|| "end of input".into())
+            ))),
+        }
+    }
+
+    fn error(&self, msg: String) -> GreenersError {
+        GreenersError::FormulaError(format!("Invalid predicate: {}", msg))
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, GreenersError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_keyword_or_op("or", "||") {
+            let rhs = self.parse_and()?;
+            lhs = Expr::Binary(BinOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, GreenersError> {
+        let mut lhs = self.parse_not()?;
+        while self.eat_keyword_or_op("and", "&&") {
+            let rhs = self.parse_not()?;
+            lhs = Expr::Binary(BinOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, GreenersError> {
+        if self.eat_keyword_or_op("not", "!") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, GreenersError> {
+        let lhs = self.parse_additive()?;
+
+        if let Some(op) = self.peek_op(&["==", "!=", "<", "<=", ">", ">="]) {
+            self.pos += 1;
+            let rhs = self.parse_additive()?;
+            let op = match op {
+                "==" => BinOp::Eq,
+                "!=" => BinOp::Ne,
+                "<" => BinOp::Lt,
+                "<=" => BinOp::Le,
+                ">" => BinOp::Gt,
+                _ => BinOp::Ge,
+            };
+            return Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)));
+        }
+
+        if self.eat_keyword("is") {
+            let negated = self.eat_keyword("not");
+            if !self.eat_keyword("null") {
+                return Err(self.error("expected 'null' after 'is'".into()));
+            }
+            return Ok(Expr::IsNull {
+                expr: Box::new(lhs),
+                negated,
+            });
+        }
+
+        let negated = if self.peek().is_some_and(|t| t.is_keyword("not"))
+            && self
+                .tokens
+                .get(self.pos + 1)
+                .is_some_and(|t| t.is_keyword("in"))
+        {
+            self.pos += 1;
+            true
+        } else {
+            false
+        };
+        if self.eat_keyword("in") {
+            let list = self.parse_list()?;
+            return Ok(Expr::In {
+                expr: Box::new(lhs),
+                list,
+                negated,
+            });
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<Literal>, GreenersError> {
+        self.expect(Token::LBracket)?;
+        let mut items = Vec::new();
+        if matches!(self.peek(), Some(Token::RBracket)) {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            let negative = self.peek_op(&["-"]).is_some();
+            if negative {
+                self.pos += 1;
+            }
+            let lit = match self.peek() {
+                Some(Token::Num(x)) => Literal::Num(if negative { -x } else { *x }),
+                Some(Token::Str(s)) if !negative => Literal::Str(s.clone()),
+                Some(t) if !negative && t.is_keyword("true") => Literal::Bool(true),
+                Some(t) if !negative && t.is_keyword("false") => Literal::Bool(false),
+                Some(t) if !negative && t.is_keyword("null") => Literal::Null,
+                other => {
+                    return Err(self.error(format!(
+                        "'in' lists may only contain literals, found {}",
+                        other
+                            .map(Token::describe)
+                            .unwrap_or_else(|| "end of input".into())
+                    )))
+                }
+            };
+            self.pos += 1;
+            items.push(lit);
+            match self.peek() {
+                Some(Token::Comma) => self.pos += 1,
+                Some(Token::RBracket) => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                other => {
+                    return Err(self.error(format!(
+                        "expected ',' or ']' in list, found {}",
+                        other
+                            .map(Token::describe)
+                            .unwrap_or_else(|| "end of input".into())
+                    )))
+                }
+            }
+        }
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, GreenersError> {
+        let mut lhs = self.parse_multiplicative()?;
+        while let Some(op) = self.peek_op(&["+", "-"]) {
+            self.pos += 1;
+            let rhs = self.parse_multiplicative()?;
+            let op = if op == "+" { BinOp::Add } else { BinOp::Sub };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, GreenersError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(op) = self.peek_op(&["*", "/", "%"]) {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            let op = match op {
+                "*" => BinOp::Mul,
+                "/" => BinOp::Div,
+                _ => BinOp::Rem,
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, GreenersError> {
+        if self.peek_op(&["-"]).is_some() {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.parse_unary()?)));
+        }
+        if self.peek_op(&["+"]).is_some() {
+            self.pos += 1;
+            return self.parse_unary();
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, GreenersError> {
+        let tok = match self.peek() {
+            Some(t) => t.clone(),
+            None => return Err(self.error("unexpected end of input".into())),
+        };
+        self.pos += 1;
+        match tok {
+            Token::Num(x) => Ok(Expr::Lit(Literal::Num(x))),
+            Token::Str(s) => Ok(Expr::Lit(Literal::Str(s))),
+            Token::QuotedIdent(name) => Ok(Expr::Column(name)),
+            Token::LParen => {
+                let e = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(e)
+            }
+            Token::Ident(name) => {
+                let lower = name.to_ascii_lowercase();
+                match lower.as_str() {
+                    "true" => Ok(Expr::Lit(Literal::Bool(true))),
+                    "false" => Ok(Expr::Lit(Literal::Bool(false))),
+                    "null" => Ok(Expr::Lit(Literal::Null)),
+                    "and" | "or" | "not" | "in" | "is" => Err(self.error(format!(
+                        "unexpected keyword '{}' (quote column names with backticks)",
+                        name
+                    ))),
+                    _ => Ok(Expr::Column(name)),
+                }
+            }
+            other => Err(self.error(format!("unexpected {}", other.describe()))),
+        }
     }
 }
 
 /// Row access for predicate evaluation.
+///
+/// `layout` maps each column name to its index in `fields`.
 #[derive(Debug)]
 pub struct DsvRow<'a> {
     pub fields: &'a [String],
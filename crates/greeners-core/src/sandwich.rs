@@ -0,0 +1,469 @@
+//! Building blocks for sandwich covariance estimators.
+//!
+//! Estimators compute their own bread; the functions here return the
+//! "meat" built from per-observation scores (e.g. `x_i * u_i` for OLS) so
+//! that every model accepting a `CovarianceType` can share the cluster,
+//! kernel, spatial and bias-reduced cluster variants. Only the cluster
+//! meats carry a small-sample factor (G/(G-1)); degrees-of-freedom
+//! corrections are left to the estimator.
+
+use crate::error::GreenersError;
+use crate::linalg::{LinalgEigh as _, UPLO};
+use crate::types::{CovarianceType, HacBandwidth, HacKernel};
+use indexmap::IndexMap;
+use ndarray::{s, Array1, Array2};
+
+/// Mean Earth radius in kilometres used for great-circle distances.
+const EARTH_RADIUS_KM: f64 = 6371.01;
+
+/// Sandwich covariance helpers
+pub struct Sandwich;
+
+impl Sandwich {
+    /// Kernel weight k(x) for a lag scaled by the bandwidth (x = j / S).
+    pub fn kernel_weight(kernel: HacKernel, x: f64) -> f64 {
+        let x = x.abs();
+        match kernel {
+            HacKernel::Bartlett => (1.0 - x).max(0.0),
+            HacKernel::Parzen => {
+                if x <= 0.5 {
+                    1.0 - 6.0 * x * x + 6.0 * x.powi(3)
+                } else if x <= 1.0 {
+                    2.0 * (1.0 - x).powi(3)
+                } else {
+                    0.0
+                }
+            }
+            HacKernel::QuadraticSpectral => {
+                let z = 6.0 * std::f64::consts::PI * x / 5.0;
+                if z < 1e-3 {
+                    // Series expansion avoids cancellation near zero
+                    return 1.0 - z * z / 10.0;
+                }
+                25.0 / (12.0 * std::f64::consts::PI.powi(2) * x * x) * (z.sin() / z - z.cos())
+            }
+        }
+    }
+
+    /// HAC bandwidth S for the given scores (n × k, in time order).
+    ///
+    /// `Andrews` fits an AR(1) to every score column and uses the plug-in
+    /// rule of Andrews (1991) with equal column weights. `NeweyWest` applies
+    /// the nonparametric rule of Newey & West (1994) to the row sums of the
+    /// scores. A `Fixed` bandwidth is returned unchanged.
+    pub fn bandwidth(scores: &Array2<f64>, kernel: HacKernel, rule: HacBandwidth) -> f64 {
+        let n = scores.nrows();
+        let n_f = n as f64;
+        match rule {
+            HacBandwidth::Fixed(bw) => bw,
+            HacBandwidth::Andrews => {
+                let (mut num, mut den) = (0.0, 0.0);
+                for v in scores.columns() {
+                    if n < 3 {
+                        break;
+                    }
+                    let lagged = v.slice(s![..n - 1]);
+                    let current = v.slice(s![1..]);
+                    let ss = lagged.dot(&lagged);
+                    if ss < 1e-300 {
+                        continue;
+                    }
+                    let rho = (current.dot(&lagged) / ss).clamp(-0.97, 0.97);
+                    let sigma2 = (&current - &(&lagged * rho)).mapv(|e| e * e).sum() / (n_f - 1.0);
+                    let s4 = sigma2 * sigma2;
+                    num += match kernel {
+                        HacKernel::Bartlett => {
+                            4.0 * rho * rho * s4 / ((1.0 - rho).powi(6) * (1.0 + rho).powi(2))
+                        }
+                        _ => 4.0 * rho * rho * s4 / (1.0 - rho).powi(8),
+                    };
+                    den += s4 / (1.0 - rho).powi(4);
+                }
+                if den <= 0.0 {
+                    return 1.0;
+                }
+                let alpha = num / den;
+                match kernel {
+                    HacKernel::Bartlett => 1.1447 * (alpha * n_f).powf(1.0 / 3.0),
+                    HacKernel::Parzen => 2.6614 * (alpha * n_f).powf(0.2),
+                    HacKernel::QuadraticSpectral => 1.3221 * (alpha * n_f).powf(0.2),
+                }
+                .max(1.0)
+            }
+            HacBandwidth::NeweyWest => {
+                let (pilot_exp, q, c) = match kernel {
+                    HacKernel::Bartlett => (2.0 / 9.0, 1, 1.1447),
+                    HacKernel::Parzen => (4.0 / 25.0, 2, 2.6614),
+                    HacKernel::QuadraticSpectral => (2.0 / 25.0, 2, 1.3221),
+                };
+                let h = scores.sum_axis(ndarray::Axis(1));
+                let pilot =
+                    ((4.0 * (n_f / 100.0).powf(pilot_exp)) as usize).min(n.saturating_sub(1));
+                let sigma = |j: usize| h.slice(s![j..]).dot(&h.slice(s![..n - j])) / n_f;
+                let mut s0 = sigma(0);
+                let mut sq = 0.0;
+                for j in 1..=pilot {
+                    let sj = sigma(j);
+                    s0 += 2.0 * sj;
+                    sq += 2.0 * (j as f64).powi(q) * sj;
+                }
+                if s0.abs() < 1e-300 {
+                    return 1.0;
+                }
+                let exponent = 1.0 / (2 * q + 1) as f64;
+                (c * ((sq / s0).powi(2)).powf(exponent) * n_f.powf(exponent)).max(1.0)
+            }
+        }
+    }
+
+    /// Kernel HAC meat Γ₀ + Σⱼ k(j/S)(Γⱼ + Γⱼ') with Γⱼ = Σₜ sₜ sₜ₋ⱼ'.
+    ///
+    /// Rows of `scores` must be in time order.
+    pub fn hac_meat(scores: &Array2<f64>, kernel: HacKernel, rule: HacBandwidth) -> Array2<f64> {
+        let n = scores.nrows();
+        let bw = Self::bandwidth(scores, kernel, rule);
+        let max_lag = match kernel {
+            HacKernel::QuadraticSpectral => n.saturating_sub(1),
+            _ => (bw.ceil() as usize)
+                .saturating_sub(1)
+                .min(n.saturating_sub(1)),
+        };
+        let mut meat = scores.t().dot(scores);
+        for j in 1..=max_lag {
+            let w = Self::kernel_weight(kernel, j as f64 / bw);
+            if w == 0.0 {
+                continue;
+            }
+            let gamma = scores
+                .slice(s![j.., ..])
+                .t()
+                .dot(&scores.slice(s![..n - j, ..]));
+            meat = meat + (&gamma + &gamma.t()) * w;
+        }
+        meat
+    }
+
+    /// Driscoll-Kraay meat: Bartlett HAC with `lags` lags applied to the
+    /// cross-sectional sums of the scores in each period.
+    pub fn driscoll_kraay_meat(
+        scores: &Array2<f64>,
+        time_ids: &[usize],
+        lags: usize,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let (n, k) = scores.dim();
+        if time_ids.len() != n {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Time IDs length ({}) must match number of observations ({})",
+                time_ids.len(),
+                n
+            )));
+        }
+        let mut periods: Vec<usize> = time_ids.to_vec();
+        periods.sort_unstable();
+        periods.dedup();
+        let mut sums = Array2::<f64>::zeros((periods.len(), k));
+        for (i, t) in time_ids.iter().enumerate() {
+            let row = periods.binary_search(t).unwrap_or(0);
+            let mut target = sums.row_mut(row);
+            target += &scores.row(i);
+        }
+        Ok(Self::hac_meat(
+            &sums,
+            HacKernel::Bartlett,
+            HacBandwidth::Fixed((lags + 1) as f64),
+        ))
+    }
+
+    /// Conley spatial meat Σᵢ Σⱼ 1{dᵢⱼ ≤ cutoff} sᵢ sⱼ' with great-circle
+    /// distances between (latitude, longitude) pairs in degrees.
+    pub fn conley_meat(
+        scores: &Array2<f64>,
+        coords: &[(f64, f64)],
+        cutoff_km: f64,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let (n, k) = scores.dim();
+        if coords.len() != n {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Coordinates length ({}) must match number of observations ({})",
+                coords.len(),
+                n
+            )));
+        }
+        if cutoff_km.is_nan()
+            || cutoff_km <= 0.0
+            || coords.iter().any(|c| !c.0.is_finite() || !c.1.is_finite())
+        {
+            return Err(GreenersError::InvalidOperation(
+                "Conley SE need a positive cutoff and finite coordinates".into(),
+            ));
+        }
+        let rad: Vec<(f64, f64)> = coords
+            .iter()
+            .map(|&(lat, lon)| (lat.to_radians(), lon.to_radians()))
+            .collect();
+        // Latitude gap alone already exceeds the cutoff beyond this angle
+        let max_dlat = cutoff_km / EARTH_RADIUS_KM;
+        let mut meat = Array2::<f64>::zeros((k, k));
+        for i in 0..n {
+            let (lat_i, lon_i) = rad[i];
+            let mut acc = Array1::<f64>::zeros(k);
+            for (j, &(lat_j, lon_j)) in rad.iter().enumerate() {
+                if (lat_i - lat_j).abs() > max_dlat {
+                    continue;
+                }
+                let a = ((lat_j - lat_i) / 2.0).sin().powi(2)
+                    + lat_i.cos() * lat_j.cos() * ((lon_j - lon_i) / 2.0).sin().powi(2);
+                let d = 2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin();
+                if d <= cutoff_km {
+                    acc += &scores.row(j);
+                }
+            }
+            let s_i = scores.row(i);
+            for p in 0..k {
+                for q in 0..k {
+                    meat[[p, q]] += s_i[p] * acc[q];
+                }
+            }
+        }
+        Ok((&meat + &meat.t()) * 0.5)
+    }
+
+    /// Residual adjustment A_g of one cluster for CR2 ((I - H_gg)^(-1/2))
+    /// or CR3 ((I - H_gg)^(-1), when `jackknife` is set), where
+    /// H_gg = X_g `bread` X_g'. Eigenvalues of I - H_gg below 1e-10 are
+    /// dropped, giving the generalised inverse.
+    pub fn cluster_adjustment(
+        x_g: &Array2<f64>,
+        bread: &Array2<f64>,
+        jackknife: bool,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let power = if jackknife { -1.0 } else { -0.5 };
+        let m_g = Array2::<f64>::eye(x_g.nrows()) - x_g.dot(bread).dot(&x_g.t());
+        let (eigs, vecs) = m_g.eigh(UPLO::Lower)?;
+        let d = eigs.mapv(|e| if e > 1e-10 { e.powf(power) } else { 0.0 });
+        Ok((&vecs * &d).dot(&vecs.t()))
+    }
+
+    /// Bias-reduced cluster meat (CR2, or CR3 when `jackknife` is set) and
+    /// the Bell-McCaffrey Satterthwaite degrees of freedom of every
+    /// coefficient.
+    ///
+    /// `bread` must be (X'X)⁻¹ for the `x` passed in. Residuals of cluster
+    /// g are scaled by (I - H_gg)^(-1/2) (CR2) or (I - H_gg)^(-1) (CR3,
+    /// which is also multiplied by (G-1)/G); clusters whose residual-maker
+    /// block is singular, e.g. under cluster fixed effects, use the
+    /// generalised inverse. The degrees of freedom assume a homoscedastic
+    /// working model.
+    pub fn bias_reduced_cluster(
+        x: &Array2<f64>,
+        bread: &Array2<f64>,
+        residuals: &Array1<f64>,
+        cluster_ids: &[usize],
+        jackknife: bool,
+    ) -> Result<(Array2<f64>, Array1<f64>), GreenersError> {
+        let (n, k) = x.dim();
+        if cluster_ids.len() != n {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Cluster IDs length ({}) must match number of observations ({})",
+                cluster_ids.len(),
+                n
+            )));
+        }
+        let mut clusters: IndexMap<usize, Vec<usize>> = IndexMap::new();
+        for (i, &g) in cluster_ids.iter().enumerate() {
+            clusters.entry(g).or_default().push(i);
+        }
+        let n_clusters = clusters.len();
+        if n_clusters < 2 {
+            return Err(GreenersError::InvalidOperation(
+                "Cluster-robust SE need at least two clusters".into(),
+            ));
+        }
+
+        let mut meat = Array2::<f64>::zeros((k, k));
+        // Per cluster: squared column norms of P_g = A_g X_g (X'X)⁻¹ and W_g = X_g' P_g
+        let mut p_norms = Array2::<f64>::zeros((n_clusters, k));
+        let mut w_cols: Vec<Array2<f64>> = Vec::with_capacity(n_clusters);
+        for (g, rows) in clusters.values().enumerate() {
+            let x_g = x.select(ndarray::Axis(0), rows);
+            let u_g: Array1<f64> = rows.iter().map(|&i| residuals[i]).collect();
+            let a_g = Self::cluster_adjustment(&x_g, bread, jackknife)?;
+
+            let v = x_g.t().dot(&a_g.dot(&u_g));
+            for p in 0..k {
+                for q in 0..k {
+                    meat[[p, q]] += v[p] * v[q];
+                }
+            }
+
+            let p_g = a_g.dot(&x_g).dot(bread);
+            p_norms
+                .row_mut(g)
+                .assign(&p_g.mapv(|e| e * e).sum_axis(ndarray::Axis(0)));
+            w_cols.push(x_g.t().dot(&p_g));
+        }
+        if jackknife {
+            meat *= (n_clusters - 1) as f64 / n_clusters as f64;
+        }
+
+        // Satterthwaite df = tr(M)² / tr(M²) with M = G'G, where
+        // M[g, h] = δ_gh ‖p_g‖² - w_g' (X'X)⁻¹ w_h for each coefficient
+        let mut df = Array1::<f64>::zeros(k);
+        for j in 0..k {
+            let w: Vec<Array1<f64>> = w_cols.iter().map(|w_g| w_g.column(j).to_owned()).collect();
+            let bw: Vec<Array1<f64>> = w.iter().map(|w_g| bread.dot(w_g)).collect();
+            let (mut trace, mut frob) = (0.0, 0.0);
+            for (g, w_g) in w.iter().enumerate() {
+                for (h, bw_h) in bw.iter().enumerate() {
+                    let mut m = -w_g.dot(bw_h);
+                    if g == h {
+                        m += p_norms[[g, j]];
+                        trace += m;
+                    }
+                    frob += m * m;
+                }
+            }
+            df[j] = if frob > 0.0 {
+                trace * trace / frob
+            } else {
+                f64::NAN
+            };
+        }
+        Ok((meat, df))
+    }
+
+    /// Σ_g s_g s_g' over the clusters in `cluster_ids`, and the number of
+    /// clusters.
+    fn cluster_sum(
+        scores: &Array2<f64>,
+        cluster_ids: &[usize],
+    ) -> Result<(Array2<f64>, usize), GreenersError> {
+        let (n, k) = scores.dim();
+        if cluster_ids.len() != n {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Cluster IDs length ({}) must match number of observations ({})",
+                cluster_ids.len(),
+                n
+            )));
+        }
+        let mut sums: IndexMap<usize, Array1<f64>> = IndexMap::new();
+        for (row, &g) in scores.outer_iter().zip(cluster_ids) {
+            *sums.entry(g).or_insert_with(|| Array1::zeros(k)) += &row;
+        }
+        if sums.len() < 2 {
+            return Err(GreenersError::InvalidOperation(
+                "Cluster-robust SE need at least two clusters".into(),
+            ));
+        }
+        let mut meat = Array2::<f64>::zeros((k, k));
+        for s in sums.values() {
+            let col = s.view().insert_axis(ndarray::Axis(1));
+            meat += &col.dot(&col.t());
+        }
+        Ok((meat, sums.len()))
+    }
+
+    /// One-way cluster meat Σ_g s_g s_g' × G/(G-1). Estimators with
+    /// t-based inference add their own (N-1)/(N-K) on top.
+    pub fn cluster_meat(
+        scores: &Array2<f64>,
+        cluster_ids: &[usize],
+    ) -> Result<Array2<f64>, GreenersError> {
+        let (meat, g) = Self::cluster_sum(scores, cluster_ids)?;
+        Ok(meat * (g as f64 / (g - 1) as f64))
+    }
+
+    /// Two-way cluster meat (Cameron, Gelbach & Miller 2011): the one-way
+    /// sums on each dimension minus the sum on their intersection, scaled
+    /// by G/(G-1) with G the smaller of the two cluster counts.
+    pub fn two_way_cluster_meat(
+        scores: &Array2<f64>,
+        cluster_ids_1: &[usize],
+        cluster_ids_2: &[usize],
+    ) -> Result<Array2<f64>, GreenersError> {
+        if cluster_ids_1.len() != scores.nrows() || cluster_ids_2.len() != scores.nrows() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Both cluster ID vectors must match number of observations ({})",
+                scores.nrows()
+            )));
+        }
+        let mut pairs: IndexMap<(usize, usize), usize> = IndexMap::new();
+        let intersection: Vec<usize> = cluster_ids_1
+            .iter()
+            .zip(cluster_ids_2)
+            .map(|(&a, &b)| {
+                let next = pairs.len();
+                *pairs.entry((a, b)).or_insert(next)
+            })
+            .collect();
+        let (meat_1, g_1) = Self::cluster_sum(scores, cluster_ids_1)?;
+        let (meat_2, g_2) = Self::cluster_sum(scores, cluster_ids_2)?;
+        let (meat_12, _) = Self::cluster_sum(scores, &intersection)?;
+        let g = g_1.min(g_2) as f64;
+        Ok((meat_1 + meat_2 - meat_12) * (g / (g - 1.0)))
+    }
+
+    /// Meat for the score-based covariance types: `Clustered` and
+    /// `ClusteredTwoWay` (with the G/(G-1) correction), `Hac`,
+    /// `DriscollKraay` and `Conley`; `None` for every other type.
+    pub fn score_meat(
+        scores: &Array2<f64>,
+        cov_type: &CovarianceType,
+    ) -> Result<Option<Array2<f64>>, GreenersError> {
+        Ok(match cov_type {
+            CovarianceType::Clustered(ids) => Some(Self::cluster_meat(scores, ids)?),
+            CovarianceType::ClusteredTwoWay(ids1, ids2) => {
+                Some(Self::two_way_cluster_meat(scores, ids1, ids2)?)
+            }
+            CovarianceType::Hac(kernel, rule) => Some(Self::hac_meat(scores, *kernel, *rule)),
+            CovarianceType::DriscollKraay(time_ids, lags) => {
+                Some(Self::driscoll_kraay_meat(scores, time_ids, *lags)?)
+            }
+            CovarianceType::Conley(coords, cutoff) => {
+                Some(Self::conley_meat(scores, coords, *cutoff)?)
+            }
+            _ => None,
+        })
+    }
+
+    /// Human-readable description used in model summaries.
+    pub fn describe(cov_type: &CovarianceType) -> String {
+        let n_groups = |ids: &[usize]| ids.iter().collect::<std::collections::HashSet<_>>().len();
+        match cov_type {
+            CovarianceType::NonRobust => "Non-Robust".to_string(),
+            CovarianceType::HC0 => "Robust (HC0)".to_string(),
+            CovarianceType::HC1 => "Robust (HC1)".to_string(),
+            CovarianceType::HC2 => "Robust (HC2)".to_string(),
+            CovarianceType::HC3 => "Robust (HC3)".to_string(),
+            CovarianceType::HC4 => "Robust (HC4)".to_string(),
+            CovarianceType::HC5 => "Robust (HC5)".to_string(),
+            CovarianceType::NeweyWest(lags) => format!("HAC (Newey-West, L={})", lags),
+            CovarianceType::Hac(kernel, rule) => {
+                let rule = match rule {
+                    HacBandwidth::Fixed(bw) => format!("S={}", bw),
+                    HacBandwidth::Andrews => "Andrews".to_string(),
+                    HacBandwidth::NeweyWest => "NW94".to_string(),
+                };
+                format!("HAC ({:?}, {})", kernel, rule)
+            }
+            CovarianceType::DriscollKraay(time_ids, lags) => {
+                format!(
+                    "Driscoll-Kraay ({} periods, L={})",
+                    n_groups(time_ids),
+                    lags
+                )
+            }
+            CovarianceType::Conley(_, cutoff) => format!("Conley ({} km)", cutoff),
+            CovarianceType::Clustered(clusters) => {
+                format!("Clustered ({} clusters)", n_groups(clusters))
+            }
+            CovarianceType::CR2(clusters) => format!("CR2 ({} clusters)", n_groups(clusters)),
+            CovarianceType::CR3(clusters) => format!("CR3 ({} clusters)", n_groups(clusters)),
+            CovarianceType::ClusteredTwoWay(clusters1, clusters2) => format!(
+                "Two-Way Clustered ({}×{})",
+                n_groups(clusters1),
+                n_groups(clusters2)
+            ),
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use crate::error::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, FisherSnedecor, Normal, StudentsT};
 use std::fmt;
 
 // ─── ANOVA ─────────────────────────────────────────────────────────────────────
 
 /// One-way ANOVA result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AnovaResult {
     pub ss_between: f64,
     pub ss_within: f64,
@@ -57,7 +58,7 @@ impl fmt::Display for AnovaResult {
 }
 
 /// ANOVA table for regression models.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AnovaRegressionResult {
     pub ss_model: f64,
     pub ss_resid: f64,
@@ -101,7 +102,7 @@ impl fmt::Display for AnovaRegressionResult {
 }
 
 /// Result of comparing two sample means.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompareMeansResult {
     pub mean1: f64,
     pub mean2: f64,
@@ -122,7 +123,7 @@ pub struct CompareMeansResult {
 }
 
 /// Result of a single sample or paired t-test.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TTestResult {
     pub mean: f64,
     pub std_dev: f64,
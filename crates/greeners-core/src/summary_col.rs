@@ -1,8 +1,10 @@
+use crate::model::FittedModel;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A model summary for side-by-side comparison.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelSummary {
     pub name: String,
     pub params: Vec<f64>,
@@ -69,6 +71,24 @@ impl ModelSummary {
         self
     }
 
+    /// Build a summary from any fitted model.
+    pub fn from_fitted<M: FittedModel + ?Sized>(name: &str, model: &M) -> Self {
+        let params = model.params();
+        let p_values = model
+            .pvalues()
+            .unwrap_or_else(|| Array1::from_elem(params.len(), f64::NAN));
+        ModelSummary::new(name)
+            .with_coefficients(&params, &model.bse(), &p_values, &model.param_names())
+            .with_fit_stats(
+                model.nobs(),
+                model.r_squared(),
+                None,
+                model.aic(),
+                model.bic(),
+                model.loglike(),
+            )
+    }
+
     fn significance_stars(p: f64) -> &'static str {
         if p < 0.001 {
             "***"
@@ -105,10 +125,19 @@ impl SummaryCol {
             all_vars,
         }
     }
+
+    /// Side-by-side comparison of fitted models of any type.
+    pub fn compare_fitted(models: &[(&str, &dyn FittedModel)]) -> SummaryColResult {
+        let summaries: Vec<ModelSummary> = models
+            .iter()
+            .map(|(name, m)| ModelSummary::from_fitted(name, *m))
+            .collect();
+        Self::compare(&summaries)
+    }
 }
 
 /// Result of summary_col comparison, can be displayed or exported.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SummaryColResult {
     pub models: Vec<ModelSummary>,
     pub all_vars: Vec<String>,
@@ -1,5 +1,6 @@
 use crate::distributions::{chi2_pvalue, f_pvalue, norm_pdf, t_pvalue_two, t_quantile};
 use crate::error::GreenersError;
+use crate::rng::GreenersRng;
 
 /// Pure element-wise transforms on numeric slices.
 ///
@@ -221,31 +222,23 @@ impl Transforms {
     // ── Random generators ───────────────────────────────────────────────────
 
     /// Generate `n` uniform random values in [0, 1).
-    pub fn uniform(n: usize) -> Vec<f64> {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        (0..n).map(|_| rng.gen::<f64>()).collect()
+    /// `seed: None` uses the default seed.
+    pub fn uniform(n: usize, seed: Option<u64>) -> Vec<f64> {
+        let mut rng = GreenersRng::new(seed);
+        (0..n).map(|_| rng.uniform()).collect()
     }
 
     /// Generate `n` standard normal random values.
-    pub fn rnormal(n: usize) -> Vec<f64> {
-        use rand::distributions::Distribution;
-        let mut rng = rand::thread_rng();
-        let normal = rand::distributions::Standard;
-        (0..n)
-            .map(|_| {
-                let v: f64 = normal.sample(&mut rng);
-                v
-            })
-            .collect()
+    pub fn rnormal(n: usize, seed: Option<u64>) -> Vec<f64> {
+        let mut rng = GreenersRng::new(seed);
+        (0..n).map(|_| rng.normal()).collect()
     }
 
     /// Generate `n` Bernoulli random values with probability `p`.
-    pub fn rbernoulli(n: usize, p: f64) -> Vec<f64> {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+    pub fn rbernoulli(n: usize, p: f64, seed: Option<u64>) -> Vec<f64> {
+        let mut rng = GreenersRng::new(seed);
         (0..n)
-            .map(|_| if rng.gen::<f64>() < p { 1.0 } else { 0.0 })
+            .map(|_| if rng.uniform() < p { 1.0 } else { 0.0 })
             .collect()
     }
 
@@ -621,20 +614,22 @@ mod tests {
 
     #[test]
     fn test_uniform_length() {
-        let v = Transforms::uniform(100);
+        let v = Transforms::uniform(100, Some(3));
         assert_eq!(v.len(), 100);
         assert!(v.iter().all(|&x| (0.0..1.0).contains(&x)));
+        assert_eq!(v, Transforms::uniform(100, Some(3)));
     }
 
     #[test]
     fn test_rnormal_length() {
-        let v = Transforms::rnormal(100);
+        let v = Transforms::rnormal(100, None);
         assert_eq!(v.len(), 100);
+        assert!(v.iter().any(|&x| x < 0.0));
     }
 
     #[test]
     fn test_rbernoulli() {
-        let v = Transforms::rbernoulli(1000, 0.5);
+        let v = Transforms::rbernoulli(1000, 0.5, None);
         assert_eq!(v.len(), 1000);
         assert!(v.iter().all(|&x| x == 0.0 || x == 1.0));
     }
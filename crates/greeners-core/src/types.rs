@@ -1,12 +1,16 @@
+use serde::{Deserialize, Serialize};
 #[inline]
 pub fn array1_slice(arr: &ndarray::Array1<f64>) -> &[f64] {
     arr.as_slice().unwrap_or(&[])
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CovarianceType {
     /// Standard OLS (Homoscedastic)
     NonRobust,
+    /// HC0 - White (1980) heteroscedasticity-robust SE without any
+    /// small-sample correction
+    HC0,
     /// White's Robust Errors (HC1) - Only Heteroscedasticity
     /// Uses small-sample correction: n/(n-k)
     HC1,
@@ -24,14 +28,40 @@ pub enum CovarianceType {
     /// Best small-sample performance, especially with influential observations
     /// More refined than HC3 for datasets with high-leverage points
     HC4,
+    /// HC5 - Cribari-Neto, Souza & Vasconcellos (2007)
+    /// Uses σ²_i / (1 - h_i)^(δᵢ/2) where δᵢ = min(n·h_i/k, max(4, 0.7·n·h_max/k))
+    /// Guards against a single extremely high-leverage point
+    HC5,
     /// Newey-West (HAC) - Heteroscedasticity + Autocorrelation
     /// The 'usize' parameter is the number of lags (L).
     /// Common rule of thumb: L = n^0.25
     NeweyWest(usize),
+    /// Kernel HAC with a chosen kernel and bandwidth rule
+    /// `HacBandwidth::Andrews` and `HacBandwidth::NeweyWest` select the
+    /// bandwidth from the data (Andrews, 1991; Newey-West, 1994)
+    /// Observations must be in time order
+    Hac(HacKernel, HacBandwidth),
+    /// Driscoll-Kraay (1998) SE for panels with cross-sectional dependence
+    /// First field: time period of each observation
+    /// Second field: number of lags of the Bartlett kernel applied to the
+    /// period sums of the scores
+    DriscollKraay(Vec<usize>, usize),
+    /// Conley (1999) spatial HAC SE
+    /// First field: (latitude, longitude) of each observation in degrees
+    /// Second field: distance cutoff in kilometres (great-circle distance);
+    /// pairs closer than the cutoff receive weight one (uniform kernel)
+    Conley(Vec<(f64, f64)>, f64),
     /// Clustered Standard Errors (One-Way)
     /// Critical for panel data, experiments, and grouped observations
     /// The `Vec<usize>` contains cluster IDs for each observation
     Clustered(Vec<usize>),
+    /// CR2 - Bell-McCaffrey (2002) bias-reduced cluster-robust SE
+    /// Residuals of cluster g are scaled by (I - H_gg)^(-1/2); OLS reports
+    /// Satterthwaite degrees of freedom for each coefficient
+    CR2(Vec<usize>),
+    /// CR3 - Jackknife cluster-robust SE
+    /// Residuals of cluster g are scaled by (I - H_gg)^(-1), times (G-1)/G
+    CR3(Vec<usize>),
     /// Two-Way Clustered Standard Errors (Cameron-Gelbach-Miller, 2011)
     /// For panel data with clustering along two dimensions (e.g., firm + time)
     /// First Vec: cluster IDs for dimension 1 (e.g., firm IDs)
@@ -41,7 +71,31 @@ pub enum CovarianceType {
     ClusteredTwoWay(Vec<usize>, Vec<usize>),
 }
 
-#[derive(Debug, Clone, PartialEq, Default)]
+/// Kernel weighting autocovariances in `CovarianceType::Hac`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HacKernel {
+    /// Bartlett (triangular) kernel, as in Newey-West (1987)
+    #[default]
+    Bartlett,
+    /// Parzen kernel
+    Parzen,
+    /// Quadratic spectral kernel (Andrews, 1991); uses every lag
+    QuadraticSpectral,
+}
+
+/// Bandwidth rule for `CovarianceType::Hac`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HacBandwidth {
+    /// Fixed bandwidth S; lag j receives weight k(j / S)
+    /// (Bartlett with S = L + 1 reproduces `NeweyWest(L)`)
+    Fixed(f64),
+    /// Andrews (1991) plug-in based on AR(1) fits to each score column
+    Andrews,
+    /// Newey-West (1994) nonparametric plug-in
+    NeweyWest,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum InferenceType {
     /// Student's t-distribution (default for finite samples)
     ///
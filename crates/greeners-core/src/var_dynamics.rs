@@ -0,0 +1,823 @@
+//! Forecasts, impulse responses and historical decompositions for VAR-type
+//! models (reduced-form, structural, error-correction and Bayesian VARs).
+//!
+//! A model implements [`VarDynamics`] by exposing its VAR in levels
+//!
+//! ```text
+//! y_t = c + A_1 y_{t-1} + ... + A_p y_{t-p} + u_t,    Var(u_t) = Σ_u,
+//! ```
+//!
+//! its estimation sample and residuals, the impact matrix `P` of its
+//! structural shocks (`u_t = P ε_t`, the lower Cholesky factor of `Σ_u`
+//! unless overridden) and a way to re-estimate itself on new data. The
+//! provided methods then give:
+//!
+//! - h-step forecasts with MSE-based intervals (Lütkepohl 2005, §2.2.2) or
+//!   bootstrap intervals that also reflect estimation uncertainty;
+//! - conditional forecasts in which some variables follow given future
+//!   paths, built from the minimum-norm structural shocks that deliver them
+//!   (Doan, Litterman & Sims 1984; Waggoner & Zha 1999);
+//! - orthogonalized and generalized (Pesaran & Shin 1998) impulse responses
+//!   with residual- or wild-bootstrap confidence bands;
+//! - historical decompositions of the sample into the contributions of each
+//!   structural shock.
+//!
+//! Bootstrap samples are generated recursively from the first `p`
+//! observations with resampled (or wild-weighted) residuals, and the model is
+//! re-estimated on each of them with [`VarDynamics::refit`].
+
+use crate::bootstrap::{Bootstrap, WildWeights};
+use crate::linalg::{LinalgCholesky as _, LinalgInverse as _, UPLO};
+use crate::rng::GreenersRng;
+use crate::GreenersError;
+use ndarray::{s, Array1, Array2, Array3, Axis};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::fmt;
+
+/// How bootstrap samples draw their innovations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VarBootstrapScheme {
+    /// Centered residuals resampled with replacement
+    #[default]
+    Residual,
+    /// Each residual multiplied by an auxiliary weight, which keeps
+    /// conditional heteroskedasticity (Gonçalves & Kilian 2004)
+    Wild,
+}
+
+impl fmt::Display for VarBootstrapScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarBootstrapScheme::Residual => write!(f, "Residual bootstrap"),
+            VarBootstrapScheme::Wild => write!(f, "Wild bootstrap"),
+        }
+    }
+}
+
+/// Settings for the bootstrap methods of [`VarDynamics`].
+#[derive(Debug, Clone)]
+pub struct VarBootstrapConfig {
+    pub scheme: VarBootstrapScheme,
+    /// Auxiliary weights of the wild bootstrap
+    pub weights: WildWeights,
+    /// Number of replications
+    pub n_bootstrap: usize,
+    /// Intervals cover `1 - alpha`
+    pub alpha: f64,
+    /// Random seed (`None` uses the default seed)
+    pub seed: Option<u64>,
+}
+
+impl Default for VarBootstrapConfig {
+    fn default() -> Self {
+        VarBootstrapConfig {
+            scheme: VarBootstrapScheme::Residual,
+            weights: WildWeights::Rademacher,
+            n_bootstrap: 499,
+            alpha: 0.05,
+            seed: None,
+        }
+    }
+}
+
+/// Identification of the shocks in an impulse response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IrfKind {
+    /// One-standard-deviation structural shocks given by
+    /// [`VarDynamics::impact`]
+    #[default]
+    Orthogonalized,
+    /// Pesaran-Shin generalized responses, which do not depend on the
+    /// ordering of the variables
+    Generalized,
+}
+
+impl fmt::Display for IrfKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrfKind::Orthogonalized => write!(f, "Orthogonalized"),
+            IrfKind::Generalized => write!(f, "Generalized"),
+        }
+    }
+}
+
+/// Multi-step forecast of a VAR-type model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VarForecast {
+    /// Point forecasts (steps x k); row `h` is horizon `h + 1`
+    pub mean: Array2<f64>,
+    /// Forecast standard errors (steps x k)
+    pub std_errors: Array2<f64>,
+    pub lower: Array2<f64>,
+    pub upper: Array2<f64>,
+    pub alpha: f64,
+    /// Scheme of bootstrap intervals; `None` for MSE-based intervals
+    pub bootstrap: Option<VarBootstrapScheme>,
+    /// Simulated paths of the bootstrap (n_paths x steps x k)
+    pub paths: Option<Array3<f64>>,
+    /// Imposed future paths of a conditional forecast (NaN = unrestricted)
+    pub conditions: Option<Array2<f64>>,
+    /// Structural shocks that deliver the conditions (steps x k)
+    pub shocks: Option<Array2<f64>>,
+    pub var_names: Vec<String>,
+}
+
+impl VarForecast {
+    /// Lower and upper bounds covering `1 - alpha`: percentiles of the
+    /// bootstrap paths when available, normal intervals otherwise. Calling it
+    /// for several levels gives the bands of a fan chart.
+    pub fn interval(&self, alpha: f64) -> Result<(Array2<f64>, Array2<f64>), GreenersError> {
+        check_alpha(alpha)?;
+        match &self.paths {
+            Some(paths) => Ok(percentile_bands(paths, alpha)),
+            None => {
+                let z = normal_quantile(alpha);
+                Ok((
+                    &self.mean - &(&self.std_errors * z),
+                    &self.mean + &(&self.std_errors * z),
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for VarForecast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = if self.conditions.is_some() {
+            " VAR Conditional Forecast "
+        } else {
+            " VAR Forecast "
+        };
+        writeln!(f, "\n{:=^78}", title)?;
+        let intervals = match self.bootstrap {
+            Some(scheme) => format!("{} percentiles", scheme),
+            None => "MSE-based (normal)".to_string(),
+        };
+        writeln!(f, "{:<20} {}", "Intervals:", intervals)?;
+        writeln!(f, "{:<20} {:.0}%", "Coverage:", 100.0 * (1.0 - self.alpha))?;
+        for (i, name) in self.var_names.iter().enumerate() {
+            writeln!(f, "\n{:-^78}", format!(" {} ", name))?;
+            writeln!(
+                f,
+                "{:>8} {:>14} {:>14} {:>14} {:>14}",
+                "step", "forecast", "std err", "lower", "upper"
+            )?;
+            for h in 0..self.mean.nrows() {
+                let fixed = self
+                    .conditions
+                    .as_ref()
+                    .is_some_and(|c| c[[h, i]].is_finite());
+                writeln!(
+                    f,
+                    "{:>8} {:>14.4} {:>14.4} {:>14.4} {:>14.4}{}",
+                    h + 1,
+                    self.mean[[h, i]],
+                    self.std_errors[[h, i]],
+                    self.lower[[h, i]],
+                    self.upper[[h, i]],
+                    if fixed { " *" } else { "" }
+                )?;
+            }
+        }
+        if self.conditions.is_some() {
+            writeln!(f, "* imposed path")?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Impulse responses with bootstrap confidence bands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IrfBands {
+    pub kind: IrfKind,
+    /// Point estimates (steps x k x k); `[h, i, j]` is the response of
+    /// variable `i` to shock `j` after `h` periods
+    pub irf: Array3<f64>,
+    pub lower: Array3<f64>,
+    pub upper: Array3<f64>,
+    /// Bootstrap standard deviation of each response
+    pub std_errors: Array3<f64>,
+    pub scheme: VarBootstrapScheme,
+    pub alpha: f64,
+    /// Number of successful bootstrap replications
+    pub n_bootstrap: usize,
+    pub var_names: Vec<String>,
+}
+
+impl fmt::Display for IrfBands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", format!(" {} Impulse Responses ", self.kind))?;
+        writeln!(
+            f,
+            "{:<20} {} ({} replications)",
+            "Bands:", self.scheme, self.n_bootstrap
+        )?;
+        writeln!(f, "{:<20} {:.0}%", "Coverage:", 100.0 * (1.0 - self.alpha))?;
+        let k = self.var_names.len();
+        for j in 0..k {
+            for i in 0..k {
+                writeln!(
+                    f,
+                    "\n{:-^78}",
+                    format!(" {} -> {} ", self.var_names[j], self.var_names[i])
+                )?;
+                writeln!(
+                    f,
+                    "{:>8} {:>14} {:>14} {:>14} {:>14}",
+                    "step", "response", "std err", "lower", "upper"
+                )?;
+                for h in 0..self.irf.shape()[0] {
+                    writeln!(
+                        f,
+                        "{:>8} {:>14.4} {:>14.4} {:>14.4} {:>14.4}",
+                        h,
+                        self.irf[[h, i, j]],
+                        self.std_errors[[h, i, j]],
+                        self.lower[[h, i, j]],
+                        self.upper[[h, i, j]]
+                    )?;
+                }
+            }
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Historical decomposition of the estimation sample.
+///
+/// For every observation after the first `p`,
+/// `y_t = baseline_t + Σ_j contributions[t, .., j]` exactly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalDecomposition {
+    /// Path implied by the deterministic terms and the initial observations
+    /// alone ((T - p) x k)
+    pub baseline: Array2<f64>,
+    /// `[t, i, j]`: part of variable `i` at time `t` due to structural shock `j`
+    pub contributions: Array3<f64>,
+    /// Estimated structural shocks ((T - p) x k)
+    pub shocks: Array2<f64>,
+    pub var_names: Vec<String>,
+}
+
+impl HistoricalDecomposition {
+    /// Baseline plus the contributions of all shocks, i.e. the observed series.
+    pub fn total(&self) -> Array2<f64> {
+        &self.baseline + &self.contributions.sum_axis(Axis(2))
+    }
+}
+
+impl fmt::Display for HistoricalDecomposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " Historical Decomposition ")?;
+        writeln!(f, "{:<20} {:>10}", "Observations:", self.baseline.nrows())?;
+        writeln!(f, "\n{:-^78}", " Mean absolute contribution ")?;
+        write!(f, "{:<16}", "")?;
+        for name in &self.var_names {
+            write!(f, " {:>14}", format!("shock {}", name))?;
+        }
+        writeln!(f)?;
+        let n = self.baseline.nrows().max(1) as f64;
+        for (i, name) in self.var_names.iter().enumerate() {
+            write!(f, "{:<16}", name)?;
+            for j in 0..self.var_names.len() {
+                let mean_abs = self
+                    .contributions
+                    .slice(s![.., i, j])
+                    .iter()
+                    .map(|c| c.abs())
+                    .sum::<f64>()
+                    / n;
+                write!(f, " {:>14.4}", mean_abs)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// A fitted VAR-type model written as a VAR in levels.
+///
+/// Implementors supply the levels representation, the estimation sample and
+/// its residuals, and a way to re-estimate the model; forecasting, impulse
+/// responses and historical decompositions are provided.
+pub trait VarDynamics {
+    /// Variable names, in column order.
+    fn var_names(&self) -> Vec<String>;
+
+    /// Intercept `c` (length k).
+    fn intercept(&self) -> Array1<f64>;
+
+    /// Lag matrices `A_1, ..., A_p` of the VAR in levels (each k x k).
+    fn lag_matrices(&self) -> Vec<Array2<f64>>;
+
+    /// Innovation covariance `Σ_u` (k x k).
+    fn innovation_cov(&self) -> Array2<f64>;
+
+    /// Estimation sample in levels (T x k).
+    fn sample(&self) -> &Array2<f64>;
+
+    /// Residuals `u_t` for sample rows `p..T` ((T - p) x k).
+    fn innovations(&self) -> &Array2<f64>;
+
+    /// Impact matrix `P` of the structural shocks, `u_t = P ε_t` with
+    /// `Var(ε_t) = I`. Defaults to the lower Cholesky factor of `Σ_u`.
+    fn impact(&self) -> Result<Array2<f64>, GreenersError> {
+        self.innovation_cov()
+            .cholesky(UPLO::Lower)
+            .map_err(|_| GreenersError::SingularMatrix)
+    }
+
+    /// Re-estimates the model, with the same specification, on `data`.
+    fn refit(&self, data: &Array2<f64>) -> Result<Self, GreenersError>
+    where
+        Self: Sized;
+
+    /// Moving-average coefficients `Φ_0 = I, Φ_1, ..., Φ_{steps-1}`.
+    fn ma_coefficients(&self, steps: usize) -> Vec<Array2<f64>> {
+        ma_coefficients(&self.lag_matrices(), self.innovation_cov().nrows(), steps)
+    }
+
+    /// Responses `Φ_h P` to one-standard-deviation structural shocks
+    /// (steps x k x k, `[h, i, j]` = response of `i` to shock `j`).
+    fn orthogonalized_irf(&self, steps: usize) -> Result<Array3<f64>, GreenersError> {
+        let impact = self.impact()?;
+        Ok(stack_responses(&self.ma_coefficients(steps), &impact))
+    }
+
+    /// Generalized responses `Φ_h Σ_u e_j / sqrt(σ_jj)` (Pesaran & Shin 1998):
+    /// the effect of a one-standard-deviation innovation in variable `j`,
+    /// integrating out the other innovations with their historical
+    /// correlation.
+    fn generalized_irf(&self, steps: usize) -> Result<Array3<f64>, GreenersError> {
+        let sigma = self.innovation_cov();
+        let mut scaled = sigma.clone();
+        for j in 0..sigma.ncols() {
+            let sd = sigma[[j, j]].sqrt();
+            if sd.is_nan() || sd <= 0.0 {
+                return Err(GreenersError::InvalidOperation(
+                    "Innovation variances must be positive".into(),
+                ));
+            }
+            scaled.column_mut(j).mapv_inplace(|v| v / sd);
+        }
+        Ok(stack_responses(&self.ma_coefficients(steps), &scaled))
+    }
+
+    /// Forecasts for horizons `1..=steps` from the end of the sample, with
+    /// normal intervals from the MSE matrices `Σ_{i<h} Φ_i Σ_u Φ_i'`. The
+    /// intervals ignore estimation uncertainty; see
+    /// [`bootstrap_forecast`](VarDynamics::bootstrap_forecast).
+    fn forecast(&self, steps: usize, alpha: f64) -> Result<VarForecast, GreenersError> {
+        let p = check_sample(self)?;
+        check_alpha(alpha)?;
+        let k = self.sample().ncols();
+        let sample = self.sample();
+        let mean = project(
+            &self.intercept(),
+            &self.lag_matrices(),
+            &sample.slice(s![sample.nrows() - p.., ..]).to_owned(),
+            &Array2::zeros((steps, k)),
+        );
+        let sigma = self.innovation_cov();
+        let mut mse = Array2::<f64>::zeros((k, k));
+        let mut std_errors = Array2::<f64>::zeros((steps, k));
+        for (h, phi) in self.ma_coefficients(steps).iter().enumerate() {
+            mse += &phi.dot(&sigma).dot(&phi.t());
+            for i in 0..k {
+                std_errors[[h, i]] = mse[[i, i]].max(0.0).sqrt();
+            }
+        }
+        Ok(analytic_forecast(mean, std_errors, alpha, self.var_names()))
+    }
+
+    /// Forecasts with bootstrap intervals (Pascual, Romo & Ruiz 2004). Each
+    /// replication re-estimates the model on a bootstrap sample and simulates
+    /// a future path from the observed end of the sample with resampled
+    /// innovations, so the intervals reflect both future shocks and
+    /// estimation error. The simulated paths are kept for fan charts.
+    fn bootstrap_forecast(
+        &self,
+        steps: usize,
+        config: &VarBootstrapConfig,
+    ) -> Result<VarForecast, GreenersError>
+    where
+        Self: Sized + Sync,
+    {
+        let p = check_sample(self)?;
+        let k = self.sample().ncols();
+        let sample = self.sample();
+        let tail = sample.slice(s![sample.nrows() - p.., ..]).to_owned();
+        let mean = project(
+            &self.intercept(),
+            &self.lag_matrices(),
+            &tail,
+            &Array2::zeros((steps, k)),
+        );
+        let draws = bootstrap_replications(self, config, |boot, rng| {
+            let resid = centered(boot.innovations());
+            let mut shocks = Array2::<f64>::zeros((steps, k));
+            for h in 0..steps {
+                let row = resid.row(rng.index(resid.nrows()));
+                let w = match config.scheme {
+                    VarBootstrapScheme::Residual => 1.0,
+                    VarBootstrapScheme::Wild => config.weights.draw(rng),
+                };
+                shocks.row_mut(h).assign(&(&row * w));
+            }
+            let path = project(&boot.intercept(), &boot.lag_matrices(), &tail, &shocks);
+            path.into_shape_with_order(steps * k).map_err(shape_error)
+        })?;
+        let paths = stack_draws(&draws, (steps, k))?;
+        let (lower, upper) = percentile_bands(&paths, config.alpha);
+        let std_errors = draw_std(&paths);
+        Ok(VarForecast {
+            mean,
+            std_errors,
+            lower,
+            upper,
+            alpha: config.alpha,
+            bootstrap: Some(config.scheme),
+            paths: Some(paths),
+            conditions: None,
+            shocks: None,
+            var_names: self.var_names(),
+        })
+    }
+
+    /// Forecasts in which some variables follow given paths. `conditions`
+    /// is steps x k with NaN for unrestricted entries. The future structural
+    /// shocks are the minimum-norm ones that reproduce the conditions, which
+    /// makes the forecast the Gaussian conditional expectation (and hence
+    /// independent of the identification). Standard errors come from the
+    /// conditional covariance of the remaining shocks.
+    fn conditional_forecast(
+        &self,
+        conditions: &Array2<f64>,
+        alpha: f64,
+    ) -> Result<VarForecast, GreenersError> {
+        let p = check_sample(self)?;
+        check_alpha(alpha)?;
+        let sample = self.sample();
+        let k = sample.ncols();
+        let steps = conditions.nrows();
+        if conditions.ncols() != k {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Conditions must have {} columns, got {}",
+                k,
+                conditions.ncols()
+            )));
+        }
+        let unconditional = project(
+            &self.intercept(),
+            &self.lag_matrices(),
+            &sample.slice(s![sample.nrows() - p.., ..]).to_owned(),
+            &Array2::zeros((steps, k)),
+        );
+
+        // Stacked future values: y = y_hat + M ε with M block lower
+        // triangular, block (h, s) = Φ_{h-s} P
+        let n = steps * k;
+        let responses = stack_responses(&self.ma_coefficients(steps), &self.impact()?);
+        let mut m = Array2::<f64>::zeros((n, n));
+        for h in 0..steps {
+            for s_ in 0..=h {
+                m.slice_mut(s![h * k..(h + 1) * k, s_ * k..(s_ + 1) * k])
+                    .assign(&responses.slice(s![h - s_, .., ..]));
+            }
+        }
+
+        let fixed: Vec<usize> = (0..n)
+            .filter(|&r| conditions[[r / k, r % k]].is_finite())
+            .collect();
+        let mut eps = Array1::<f64>::zeros(n);
+        let mut shock_cov = Array2::<f64>::eye(n);
+        if !fixed.is_empty() {
+            let r_mat = m.select(Axis(0), &fixed);
+            let gap = Array1::from_iter(
+                fixed
+                    .iter()
+                    .map(|&r| conditions[[r / k, r % k]] - unconditional[[r / k, r % k]]),
+            );
+            let g_inv = r_mat
+                .dot(&r_mat.t())
+                .inv()
+                .map_err(|_| GreenersError::SingularMatrix)?;
+            let proj = r_mat.t().dot(&g_inv);
+            eps = proj.dot(&gap);
+            shock_cov -= &proj.dot(&r_mat);
+        }
+
+        let mean = &unconditional
+            + &m.dot(&eps)
+                .into_shape_with_order((steps, k))
+                .map_err(shape_error)?;
+        let cov = m.dot(&shock_cov).dot(&m.t());
+        let mut std_errors = Array2::<f64>::zeros((steps, k));
+        for r in 0..n {
+            std_errors[[r / k, r % k]] = if fixed.contains(&r) {
+                0.0
+            } else {
+                cov[[r, r]].max(0.0).sqrt()
+            };
+        }
+        let mut forecast = analytic_forecast(mean, std_errors, alpha, self.var_names());
+        forecast.conditions = Some(conditions.clone());
+        forecast.shocks = Some(eps.into_shape_with_order((steps, k)).map_err(shape_error)?);
+        Ok(forecast)
+    }
+
+    /// Orthogonalized or generalized impulse responses with percentile
+    /// confidence bands from a residual or wild bootstrap. Orthogonalized
+    /// responses use the impact matrix of each re-estimated model, so
+    /// structural models are re-identified in every replication.
+    fn irf_bands(
+        &self,
+        steps: usize,
+        kind: IrfKind,
+        config: &VarBootstrapConfig,
+    ) -> Result<IrfBands, GreenersError>
+    where
+        Self: Sized + Sync,
+    {
+        check_sample(self)?;
+        let k = self.sample().ncols();
+        let irf_of = |model: &Self| match kind {
+            IrfKind::Orthogonalized => model.orthogonalized_irf(steps),
+            IrfKind::Generalized => model.generalized_irf(steps),
+        };
+        let irf = irf_of(self)?;
+        let draws = bootstrap_replications(self, config, |boot, _| {
+            let boot_irf = irf_of(boot)?;
+            boot_irf
+                .into_shape_with_order(steps * k * k)
+                .map_err(shape_error)
+        })?;
+        let stacked = stack_draws(&draws, (steps, k * k))?;
+        let (lower, upper) = percentile_bands(&stacked, config.alpha);
+        let std_errors = draw_std(&stacked);
+        let to_cube = |a: Array2<f64>| a.into_shape_with_order((steps, k, k)).map_err(shape_error);
+        Ok(IrfBands {
+            kind,
+            irf,
+            lower: to_cube(lower)?,
+            upper: to_cube(upper)?,
+            std_errors: to_cube(std_errors)?,
+            scheme: config.scheme,
+            alpha: config.alpha,
+            n_bootstrap: draws.len(),
+            var_names: self.var_names(),
+        })
+    }
+
+    /// Splits each observation after the first `p` into the path implied by
+    /// the initial observations and the contributions of the structural
+    /// shocks `ε_t = P⁻¹ u_t` up to that date.
+    fn historical_decomposition(&self) -> Result<HistoricalDecomposition, GreenersError> {
+        let p = check_sample(self)?;
+        let sample = self.sample();
+        let k = sample.ncols();
+        let n = sample.nrows() - p;
+        let impact = self.impact()?;
+        let impact_inv = impact.inv().map_err(|_| GreenersError::SingularMatrix)?;
+        let shocks = self.innovations().dot(&impact_inv.t());
+        let responses = stack_responses(&self.ma_coefficients(n), &impact);
+        let baseline = project(
+            &self.intercept(),
+            &self.lag_matrices(),
+            &sample.slice(s![..p, ..]).to_owned(),
+            &Array2::zeros((n, k)),
+        );
+        let mut contributions = Array3::<f64>::zeros((n, k, k));
+        for t in 0..n {
+            for s_ in 0..=t {
+                let theta = responses.slice(s![t - s_, .., ..]);
+                for j in 0..k {
+                    let e = shocks[[s_, j]];
+                    for i in 0..k {
+                        contributions[[t, i, j]] += theta[[i, j]] * e;
+                    }
+                }
+            }
+        }
+        Ok(HistoricalDecomposition {
+            baseline,
+            contributions,
+            shocks,
+            var_names: self.var_names(),
+        })
+    }
+}
+
+/// Lag order after checking that the sample, the residuals and the
+/// coefficients agree.
+fn check_sample<M: VarDynamics + ?Sized>(model: &M) -> Result<usize, GreenersError> {
+    let p = model.lag_matrices().len();
+    let sample = model.sample();
+    let k = model.innovation_cov().nrows();
+    if sample.nrows() == 0 {
+        return Err(GreenersError::InvalidOperation(
+            "The estimation sample is not stored in this result".into(),
+        ));
+    }
+    if sample.ncols() != k
+        || model.innovations().ncols() != k
+        || model.innovations().nrows() + p != sample.nrows()
+    {
+        return Err(GreenersError::ShapeMismatch(
+            "Sample, residuals and coefficients do not match".into(),
+        ));
+    }
+    Ok(p)
+}
+
+fn check_alpha(alpha: f64) -> Result<(), GreenersError> {
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(GreenersError::InvalidOperation(
+            "alpha must be in (0, 1)".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn shape_error(e: ndarray::ShapeError) -> GreenersError {
+    GreenersError::ShapeMismatch(e.to_string())
+}
+
+fn normal_quantile(alpha: f64) -> f64 {
+    Normal::new(0.0, 1.0)
+        .map(|d| d.inverse_cdf(1.0 - alpha / 2.0))
+        .unwrap_or(1.96)
+}
+
+fn ma_coefficients(lags: &[Array2<f64>], k: usize, steps: usize) -> Vec<Array2<f64>> {
+    let mut phi: Vec<Array2<f64>> = Vec::with_capacity(steps);
+    for h in 0..steps {
+        if h == 0 {
+            phi.push(Array2::eye(k));
+            continue;
+        }
+        let mut phi_h = Array2::<f64>::zeros((k, k));
+        for (j, a) in lags.iter().enumerate().take(h) {
+            phi_h += &a.dot(&phi[h - j - 1]);
+        }
+        phi.push(phi_h);
+    }
+    phi
+}
+
+/// `[h, .., ..] = Φ_h · impact`.
+fn stack_responses(phi: &[Array2<f64>], impact: &Array2<f64>) -> Array3<f64> {
+    let k = impact.nrows();
+    let mut out = Array3::<f64>::zeros((phi.len(), k, impact.ncols()));
+    for (h, phi_h) in phi.iter().enumerate() {
+        out.slice_mut(s![h, .., ..]).assign(&phi_h.dot(impact));
+    }
+    out
+}
+
+/// Runs the recursion forward from `history` (the last p observations, oldest
+/// first) with the given innovations and returns the new values.
+fn project(
+    intercept: &Array1<f64>,
+    lags: &[Array2<f64>],
+    history: &Array2<f64>,
+    shocks: &Array2<f64>,
+) -> Array2<f64> {
+    let p = history.nrows();
+    let steps = shocks.nrows();
+    let mut path = ndarray::concatenate(Axis(0), &[history.view(), shocks.view()])
+        .unwrap_or_else(|_| Array2::zeros((p + steps, intercept.len())));
+    for t in p..p + steps {
+        let mut y_t = intercept + &shocks.row(t - p);
+        for (j, a) in lags.iter().enumerate() {
+            y_t += &a.dot(&path.row(t - j - 1));
+        }
+        path.row_mut(t).assign(&y_t);
+    }
+    path.slice(s![p.., ..]).to_owned()
+}
+
+fn centered(resid: &Array2<f64>) -> Array2<f64> {
+    match resid.mean_axis(Axis(0)) {
+        Some(mean) => resid - &mean,
+        None => resid.clone(),
+    }
+}
+
+/// Re-estimates the model on `config.n_bootstrap` bootstrap samples and
+/// applies `stat` to each fit. Replications whose fit fails are skipped;
+/// more than half failing is an error.
+fn bootstrap_replications<M, F>(
+    model: &M,
+    config: &VarBootstrapConfig,
+    stat: F,
+) -> Result<Vec<Array1<f64>>, GreenersError>
+where
+    M: VarDynamics + Sync,
+    F: Fn(&M, &mut GreenersRng) -> Result<Array1<f64>, GreenersError> + Sync,
+{
+    check_alpha(config.alpha)?;
+    if config.n_bootstrap == 0 {
+        return Err(GreenersError::InvalidOperation(
+            "n_bootstrap must be positive".into(),
+        ));
+    }
+    let p = check_sample(model)?;
+    let sample = model.sample();
+    let resid = model.innovations();
+    let resid_centered = centered(resid);
+    let intercept = model.intercept();
+    let lags = model.lag_matrices();
+    let initial = sample.slice(s![..p, ..]).to_owned();
+    let (n, k) = resid.dim();
+    let base = GreenersRng::new(config.seed);
+
+    let draws: Vec<Option<Array1<f64>>> = (0..config.n_bootstrap)
+        .into_par_iter()
+        .map(|b| {
+            let mut rng = base.stream(b as u64);
+            let mut shocks = Array2::<f64>::zeros((n, k));
+            for t in 0..n {
+                match config.scheme {
+                    VarBootstrapScheme::Residual => {
+                        shocks.row_mut(t).assign(&resid_centered.row(rng.index(n)));
+                    }
+                    VarBootstrapScheme::Wild => {
+                        let w = config.weights.draw(&mut rng);
+                        shocks.row_mut(t).assign(&(&resid.row(t) * w));
+                    }
+                }
+            }
+            let simulated = project(&intercept, &lags, &initial, &shocks);
+            let data = ndarray::concatenate(Axis(0), &[initial.view(), simulated.view()]).ok()?;
+            let boot = model.refit(&data).ok()?;
+            stat(&boot, &mut rng).ok()
+        })
+        .collect();
+
+    let successes: Vec<Array1<f64>> = draws.into_iter().flatten().collect();
+    if successes.len() < config.n_bootstrap / 2 + 1 {
+        return Err(GreenersError::OptimizationFailed);
+    }
+    Ok(successes)
+}
+
+/// Bootstrap draws as (n_draws x rows x cols).
+fn stack_draws(
+    draws: &[Array1<f64>],
+    (rows, cols): (usize, usize),
+) -> Result<Array3<f64>, GreenersError> {
+    let mut out = Array3::<f64>::zeros((draws.len(), rows, cols));
+    for (b, d) in draws.iter().enumerate() {
+        out.slice_mut(s![b, .., ..]).assign(
+            &d.view()
+                .into_shape_with_order((rows, cols))
+                .map_err(shape_error)?,
+        );
+    }
+    Ok(out)
+}
+
+fn flat_draws(draws: &Array3<f64>) -> (Array2<f64>, usize, usize) {
+    let (n, rows, cols) = draws.dim();
+    let flat = Array2::from_shape_fn((n, rows * cols), |(b, c)| draws[[b, c / cols, c % cols]]);
+    (flat, rows, cols)
+}
+
+fn percentile_bands(draws: &Array3<f64>, alpha: f64) -> (Array2<f64>, Array2<f64>) {
+    let (flat, rows, cols) = flat_draws(draws);
+    let (lower, upper) = Bootstrap::percentile_ci(&flat, alpha);
+    let reshape = |v: Array1<f64>| Array2::from_shape_fn((rows, cols), |(r, c)| v[r * cols + c]);
+    (reshape(lower), reshape(upper))
+}
+
+fn draw_std(draws: &Array3<f64>) -> Array2<f64> {
+    let (flat, rows, cols) = flat_draws(draws);
+    let se = Bootstrap::bootstrap_se(&flat);
+    Array2::from_shape_fn((rows, cols), |(r, c)| se[r * cols + c])
+}
+
+fn analytic_forecast(
+    mean: Array2<f64>,
+    std_errors: Array2<f64>,
+    alpha: f64,
+    var_names: Vec<String>,
+) -> VarForecast {
+    let z = normal_quantile(alpha);
+    let lower = &mean - &(&std_errors * z);
+    let upper = &mean + &(&std_errors * z);
+    VarForecast {
+        mean,
+        std_errors,
+        lower,
+        upper,
+        alpha,
+        bootstrap: None,
+        paths: None,
+        conditions: None,
+        shocks: None,
+        var_names,
+    }
+}
@@ -11,7 +11,7 @@ fn test_bootstrap_pairs_invariants() {
     let y: Array1<f64> = x.column(1).mapv(|v| 1.0 + 2.0 * v);
 
     let n_boot = 50;
-    let boot = Bootstrap::pairs_bootstrap(&y, &x, n_boot).unwrap();
+    let boot = Bootstrap::pairs_bootstrap(&y, &x, n_boot, None).unwrap();
 
     assert_eq!(boot.shape(), [n_boot, 2]);
     assert!(boot.iter().all(|v| v.is_finite()));
@@ -62,7 +62,7 @@ fn test_hypothesis_tests_invariants() {
 fn test_bootstrap_input_validation() {
     let y = Array1::from_vec(vec![1.0; 5]);
     let x = Array2::from_shape_vec((4, 2), vec![1.0; 8]).unwrap();
-    assert!(Bootstrap::pairs_bootstrap(&y, &x, 10).is_err());
+    assert!(Bootstrap::pairs_bootstrap(&y, &x, 10, None).is_err());
 
     // Singular covariance matrix makes the Wald test non-invertible
     let beta = Array1::from_vec(vec![0.5, 2.0, 0.0]);
@@ -13,7 +13,7 @@ fn test_bootstrap_pairs_basic() {
     .unwrap();
 
     let n_bootstrap = 100;
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, n_bootstrap).unwrap();
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, n_bootstrap, None).unwrap();
 
     // Check dimensions
     assert_eq!(boot_coefs.nrows(), n_bootstrap);
@@ -35,7 +35,7 @@ fn test_bootstrap_standard_errors() {
     )
     .unwrap();
 
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 200).unwrap();
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 200, None).unwrap();
     let boot_se = Bootstrap::bootstrap_se(&boot_coefs);
 
     // Check dimensions
@@ -60,7 +60,7 @@ fn test_bootstrap_percentile_ci() {
     )
     .unwrap();
 
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 500).unwrap();
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 500, None).unwrap();
     let (lower, upper) = Bootstrap::percentile_ci(&boot_coefs, 0.05);
 
     // Check dimensions
@@ -86,7 +86,7 @@ fn test_bootstrap_confidence_interval_coverage() {
     )
     .unwrap();
 
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 1000).unwrap();
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 1000, None).unwrap();
     let (lower, upper) = Bootstrap::percentile_ci(&boot_coefs, 0.05);
 
     // For perfect data, true parameters should be within CI
@@ -243,7 +243,7 @@ fn test_bootstrap_reproduces_coefficients() {
     )
     .unwrap();
 
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 5000).unwrap();
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 5000, None).unwrap();
 
     // Calculate mean of bootstrap coefficients
     let mean_intercept: f64 = boot_coefs.column(0).mean().unwrap();
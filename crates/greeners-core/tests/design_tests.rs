@@ -0,0 +1,201 @@
+use greeners_core::{DataFrame, DesignInfo, Formula, MissingPolicy, TermKind};
+
+fn train_df() -> DataFrame {
+    DataFrame::builder()
+        .add_column("y", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .add_column("x", vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0])
+        .add_column("g", vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0])
+        .add_categorical(
+            "region",
+            vec![
+                "north".into(),
+                "south".into(),
+                "east".into(),
+                "north".into(),
+                "south".into(),
+                "east".into(),
+            ],
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_design_info_matches_to_design_matrix() {
+    let df = train_df();
+    let formula =
+        Formula::parse("y ~ x + C(g) + C(region) + I(x^2) + poly(x, 2) + bs(x, 4)").unwrap();
+
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+    let (y, x) = info.build(&df).unwrap();
+    let (y2, x2) = df.to_design_matrix(&formula).unwrap();
+
+    assert_eq!(y, y2);
+    assert_eq!(x, x2);
+    assert_eq!(x.ncols(), info.n_columns());
+    assert_eq!(
+        info.column_names(),
+        vec![
+            "const",
+            "x",
+            "C(g)_2",
+            "C(g)_3",
+            "region=south",
+            "region=east",
+            "I(x^2)",
+            "poly(x, 2)[1]",
+            "poly(x, 2)[2]",
+            "bs(x, 4)[0]",
+            "bs(x, 4)[1]",
+            "bs(x, 4)[2]",
+            "bs(x, 4)[3]",
+        ]
+    );
+    assert_eq!(df.formula_var_names(&formula).unwrap(), info.column_names());
+}
+
+#[test]
+fn test_design_info_rebuilds_columns_on_new_data() {
+    let df = train_df();
+    let formula = Formula::parse("y ~ C(g) + C(region) + bs(x, 4)").unwrap();
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+
+    // Holdout sample with a subset of the levels and a narrower x range.
+    let new_df = DataFrame::builder()
+        .add_column("x", vec![1.0, 2.0])
+        .add_column("g", vec![3.0, 3.0])
+        .add_categorical("region", vec!["east".into(), "east".into()])
+        .build()
+        .unwrap();
+    let x_new = info.build_x(&new_df).unwrap();
+    assert_eq!(x_new.ncols(), info.n_columns());
+
+    // Row 1 (x=2, g=3, east) equals training row 2 in every column; row 0
+    // (x=1) shares the spline block with training row 1.
+    let x_train = info.build_x(&df).unwrap();
+    for j in 0..x_new.ncols() {
+        assert!((x_new[[1, j]] - x_train[[2, j]]).abs() < 1e-12);
+    }
+    for j in 5..9 {
+        assert!((x_new[[0, j]] - x_train[[1, j]]).abs() < 1e-12);
+    }
+
+    // Stand-alone rebuild would have collapsed C(g) to a single level.
+    assert!(new_df
+        .to_design_matrix(&Formula::parse("x ~ C(g)").unwrap())
+        .is_err());
+}
+
+#[test]
+fn test_design_info_unseen_level_and_reference() {
+    let df = train_df();
+    let formula = Formula::parse("y ~ C(region, ref=east)").unwrap();
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+    assert_eq!(
+        info.column_names(),
+        vec!["const", "region=north", "region=south"]
+    );
+    match &info.terms[1].kind {
+        TermKind::Categorical { reference, .. } => assert_eq!(reference, "east"),
+        other => panic!("unexpected term {:?}", other),
+    }
+
+    let new_df = DataFrame::builder()
+        .add_categorical("region", vec!["west".into()])
+        .build()
+        .unwrap();
+    assert!(info.build_x(&new_df).is_err());
+
+    let bad_ref = Formula::parse("y ~ C(region, ref=west)").unwrap();
+    assert!(DesignInfo::from_formula(&bad_ref, &df).is_err());
+}
+
+#[test]
+fn test_design_info_centered_poly() {
+    let df = train_df();
+    let formula = Formula::parse("y ~ poly(x, 2, center) - 1").unwrap();
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+    let x = info.build_x(&df).unwrap();
+    // Training mean of x is 2.5
+    assert!((x[[0, 0]] + 2.5).abs() < 1e-12);
+    assert!((x[[0, 1]] - 6.25).abs() < 1e-12);
+
+    let new_df = DataFrame::builder()
+        .add_column("x", vec![10.0])
+        .build()
+        .unwrap();
+    let x_new = info.build_x(&new_df).unwrap();
+    assert!((x_new[[0, 0]] - 7.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_design_matrices_missing_policy() {
+    let df = DataFrame::builder()
+        .add_column("y", vec![1.0, 2.0, f64::NAN, 4.0, 5.0, 6.0])
+        .add_column("x", vec![1.0, 2.0, 3.0, 4.0, f64::NAN, 6.0])
+        .add_column("unused", vec![f64::NAN; 6])
+        .add_categorical(
+            "g",
+            vec![
+                "a".into(),
+                "b".into(),
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "a".into(),
+            ],
+        )
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x + C(g)").unwrap();
+
+    // Only variables referenced by the formula count; level "c" appears only
+    // in a dropped row, so it is not part of the learned design.
+    let dm = df.design_matrices(&formula, MissingPolicy::Drop).unwrap();
+    assert_eq!(dm.rows.dropped, vec![2, 4]);
+    assert_eq!(dm.rows.kept(), vec![0, 1, 3, 5]);
+    assert_eq!(dm.x.shape(), &[4, 3]);
+    assert_eq!(dm.info.column_names(), vec!["const", "x", "g=b"]);
+    assert_eq!(dm.y.to_vec(), vec![1.0, 2.0, 4.0, 6.0]);
+
+    let full = dm.rows.expand(&dm.y);
+    assert_eq!(full.len(), 6);
+    assert!(full[2].is_nan() && full[4].is_nan());
+    assert_eq!(full[5], 6.0);
+
+    let err = df.design_matrices(&formula, MissingPolicy::Raise);
+    assert!(err.is_err());
+
+    let kept = df.design_matrices(&formula, MissingPolicy::Keep).unwrap();
+    assert!(kept.rows.dropped.is_empty());
+    assert_eq!(kept.x.nrows(), 6);
+    assert!(kept.y[2].is_nan());
+
+    assert_eq!(
+        "raise".parse::<MissingPolicy>().unwrap(),
+        MissingPolicy::Raise
+    );
+    assert!("omit".parse::<MissingPolicy>().is_err());
+}
+
+#[test]
+fn test_design_info_ignores_codes_without_level() {
+    use greeners_core::{CategoricalColumn, Column};
+
+    // Code 7 has no level (e.g. a corrupt or missing-value code)
+    let cat = CategoricalColumn::from_codes(
+        vec!["a".into(), "b".into(), "c".into()],
+        vec![0, 1, 0, 1, 7, u32::MAX],
+    );
+    let df = DataFrame::builder()
+        .add_column("y", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .add_typed_column("grp", Column::Categorical(cat))
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ C(grp)").unwrap();
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+    match &info.terms[1].kind {
+        TermKind::Categorical { levels, .. } => assert_eq!(levels, &["a", "b"]),
+        other => panic!("unexpected term {:?}", other),
+    }
+}
@@ -53,7 +53,7 @@ fn assert_gmm_result_finite(result: &GmmClusteringResult, n: usize, k: usize) {
 fn test_gmm_cluster_recovery() {
     let x = make_gmm_data(9436);
     let n = x.nrows();
-    let result = GmmClustering::fit(&x, 3, None, None).unwrap();
+    let result = GmmClustering::fit(&x, 3, None, None, None).unwrap();
     assert_gmm_result_finite(&result, n, 3);
     let mut sizes = vec![0; 3];
     for &l in &result.labels {
@@ -68,7 +68,7 @@ fn test_gmm_cluster_recovery() {
 #[test]
 fn test_gmm_centroids_near_truth() {
     let x = make_gmm_data(9437);
-    let result = GmmClustering::fit(&x, 3, None, None).unwrap();
+    let result = GmmClustering::fit(&x, 3, None, None, None).unwrap();
     let truth = [(0.0, 0.0), (5.0, 0.0), (0.0, 5.0)];
     let mut found = [false; 3];
     for &(tx, ty) in &truth {
@@ -86,9 +86,9 @@ fn test_gmm_centroids_near_truth() {
 #[test]
 fn test_gmm_input_validation() {
     let x = make_gmm_data(9438);
-    assert!(GmmClustering::fit(&x, 0, None, None).is_err());
-    assert!(GmmClustering::fit(&x, 1000, None, None).is_err());
+    assert!(GmmClustering::fit(&x, 0, None, None, None).is_err());
+    assert!(GmmClustering::fit(&x, 1000, None, None, None).is_err());
 
     let x_short = Array2::from_shape_vec((3, 2), vec![1.0; 6]).unwrap();
-    assert!(GmmClustering::fit(&x_short, 2, None, None).is_err());
+    assert!(GmmClustering::fit(&x_short, 2, None, None, None).is_err());
 }
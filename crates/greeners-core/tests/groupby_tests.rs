@@ -0,0 +1,208 @@
+use greeners_core::{DataFrame, GreenersError};
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+/// Two interleaved firms observed over three years.
+fn panel() -> DataFrame {
+    DataFrame::builder()
+        .add_categorical("firm", strings(&["B", "A", "B", "A", "B", "A"]))
+        .add_int("year", vec![2020, 2020, 2021, 2021, 2022, 2022])
+        .add_column("sales", vec![10.0, 1.0, 20.0, 2.0, 40.0, 4.0])
+        .add_column("cost", vec![5.0, 0.5, 6.0, f64::NAN, 7.0, 1.5])
+        .build()
+        .unwrap()
+}
+
+fn assert_same(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        if e.is_nan() {
+            assert!(a.is_nan(), "expected NaN, got {}", a);
+        } else {
+            assert!((a - e).abs() < 1e-12, "expected {}, got {}", e, a);
+        }
+    }
+}
+
+#[test]
+fn test_group_by_multiple_aggregations() {
+    let df = panel();
+    let grouped = df.group_by(&["firm"]).unwrap();
+    assert_eq!(grouped.n_groups(), 2);
+
+    let out = grouped
+        .agg(&[
+            ("sales", "sum"),
+            ("sales", "mean"),
+            ("sales", "first"),
+            ("cost", "mean"),
+            ("cost", "count"),
+            ("cost", "size"),
+            ("sales", "std"),
+        ])
+        .unwrap();
+    // Groups come back in key order with the categorical key intact
+    assert_eq!(out.get_string("firm").unwrap(), strings(&["A", "B"]));
+    assert_same(&out.get("sales_sum").unwrap().to_vec(), &[7.0, 70.0]);
+    assert_same(&out.get("sales_first").unwrap().to_vec(), &[1.0, 10.0]);
+    // NaN skipped by mean and count but not by size
+    assert_same(&out.get("cost_mean").unwrap().to_vec(), &[1.0, 6.0]);
+    assert_same(&out.get("cost_count").unwrap().to_vec(), &[2.0, 3.0]);
+    assert_same(&out.get("cost_size").unwrap().to_vec(), &[3.0, 3.0]);
+    let sd_a = ((1.0f64 - 7.0 / 3.0).powi(2)
+        + (2.0f64 - 7.0 / 3.0).powi(2)
+        + (4.0f64 - 7.0 / 3.0).powi(2))
+        / 2.0;
+    assert!((out.get("sales_std").unwrap()[0] - sd_a.sqrt()).abs() < 1e-12);
+
+    let keys = grouped.keys().unwrap();
+    assert_eq!(keys.get_int("size").unwrap().to_vec(), vec![3, 3]);
+    assert_eq!(grouped.groups()[0], vec![1, 3, 5]);
+}
+
+#[test]
+fn test_group_by_multi_key_matches_filtering() {
+    let df = panel();
+    let out = df
+        .group_by(&["firm", "year"])
+        .unwrap()
+        .agg(&[("sales", "sum")])
+        .unwrap();
+    assert_eq!(out.n_rows(), 6);
+    assert_eq!(
+        out.get_int("year").unwrap().to_vec(),
+        vec![2020, 2021, 2022, 2020, 2021, 2022]
+    );
+    assert_same(
+        &out.get("sales_sum").unwrap().to_vec(),
+        &[1.0, 2.0, 4.0, 10.0, 20.0, 40.0],
+    );
+}
+
+#[test]
+fn test_group_by_closures_and_transform() {
+    let df = panel();
+    let grouped = df.group_by(&["firm"]).unwrap();
+
+    let range = grouped
+        .agg_with("sales", "sales_range", |v| {
+            let max = v.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let min = v.iter().cloned().fold(f64::INFINITY, f64::min);
+            max - min
+        })
+        .unwrap();
+    assert_same(&range.get("sales_range").unwrap().to_vec(), &[3.0, 30.0]);
+
+    let means = grouped.transform("sales", "mean").unwrap();
+    assert_eq!(means.n_rows(), df.n_rows());
+    assert_same(
+        &means.get("sales_mean").unwrap().to_vec(),
+        &[
+            70.0 / 3.0,
+            7.0 / 3.0,
+            70.0 / 3.0,
+            7.0 / 3.0,
+            70.0 / 3.0,
+            7.0 / 3.0,
+        ],
+    );
+
+    // Within transformation: demeaned values sum to zero in every group
+    let sales = df.get("sales").unwrap();
+    let group_mean = means.get("sales_mean").unwrap();
+    for group in grouped.groups() {
+        let total: f64 = group.iter().map(|&i| sales[i] - group_mean[i]).sum();
+        assert!(total.abs() < 1e-10);
+    }
+
+    let max = grouped
+        .transform_with("sales", "sales_peak", |v| {
+            v.iter().cloned().fold(f64::NAN, f64::max)
+        })
+        .unwrap();
+    assert_same(
+        &max.get("sales_peak").unwrap().to_vec(),
+        &[40.0, 4.0, 40.0, 4.0, 40.0, 4.0],
+    );
+}
+
+#[test]
+fn test_group_by_lag_lead_diff_do_not_cross_entities() {
+    let df = panel();
+    let grouped = df.group_by(&["firm"]).unwrap();
+    let nan = f64::NAN;
+
+    let lag = grouped.lag("sales", 1).unwrap();
+    assert_same(
+        &lag.get("sales_lag_1").unwrap().to_vec(),
+        &[nan, nan, 10.0, 1.0, 20.0, 2.0],
+    );
+    // The frame-wide lag leaks firm A's value into firm B
+    let naive = df.lag("sales", 1).unwrap();
+    assert_eq!(naive.get("sales_lag_1").unwrap()[2], 1.0);
+
+    let lead = grouped.lead("sales", 2).unwrap();
+    assert_same(
+        &lead.get("sales_lead_2").unwrap().to_vec(),
+        &[40.0, 4.0, nan, nan, nan, nan],
+    );
+
+    let diff = grouped.diff("sales", 1).unwrap();
+    assert_same(
+        &diff.get("sales_diff_1").unwrap().to_vec(),
+        &[nan, nan, 10.0, 1.0, 20.0, 2.0],
+    );
+
+    let pct = grouped.pct_change("sales", 1).unwrap();
+    assert_same(
+        &pct.get("sales_pct_1").unwrap().to_vec(),
+        &[nan, nan, 1.0, 1.0, 1.0, 1.0],
+    );
+}
+
+#[test]
+fn test_group_by_rolling_and_cumsum() {
+    let df = panel();
+    let grouped = df.group_by(&["firm"]).unwrap();
+    let nan = f64::NAN;
+
+    let rolling = grouped.rolling("sales", 2, "mean").unwrap();
+    assert_same(
+        &rolling.get("sales_rolling_mean").unwrap().to_vec(),
+        &[nan, nan, 15.0, 1.5, 30.0, 3.0],
+    );
+
+    let cumsum = grouped.cumsum("sales").unwrap();
+    assert_same(
+        &cumsum.get("sales_cumsum").unwrap().to_vec(),
+        &[10.0, 1.0, 30.0, 3.0, 70.0, 7.0],
+    );
+}
+
+#[test]
+fn test_group_by_nan_keys_and_errors() {
+    let df = DataFrame::builder()
+        .add_column("g", vec![1.0, f64::NAN, 1.0])
+        .add_column("x", vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+    let grouped = df.group_by(&["g"]).unwrap();
+    assert_eq!(grouped.n_groups(), 1);
+    let lag = grouped.lag("x", 1).unwrap();
+    let lag = lag.get("x_lag_1").unwrap();
+    assert!(lag[1].is_nan());
+    assert_eq!(lag[2], 1.0);
+
+    assert!(matches!(
+        df.group_by(&["missing"]),
+        Err(GreenersError::VariableNotFound(_))
+    ));
+    assert!(df.group_by(&[]).is_err());
+    assert!(grouped.agg(&[("x", "mode")]).is_err());
+    assert!(grouped.agg(&[("x", "sum"), ("x", "sum")]).is_err());
+    assert!(grouped.lag("x", 0).is_err());
+    assert!(grouped.rolling("x", 0, "mean").is_err());
+    assert!(grouped.rolling("x", 2, "median").is_err());
+}
@@ -0,0 +1,354 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use greeners_core::{
+    CategoricalColumn, Column, DataFrame, Formula, GreenersError, MergeConfig, MergeValidate,
+    MissingPolicy,
+};
+
+fn ts(day: u32, hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 3, day)
+        .unwrap()
+        .and_hms_opt(hour, 0, 0)
+        .unwrap()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_merge_multi_key_native_types() {
+    let left = DataFrame::builder()
+        .add_categorical("state", strings(&["SP", "SP", "RJ", "MG"]))
+        .add_int("year", vec![2020, 2021, 2020, 2021])
+        .add_column("gdp", vec![1.0, 2.0, 3.0, 4.0])
+        .build()
+        .unwrap();
+    // String (not categorical) state key on the right side
+    let right = DataFrame::builder()
+        .add_string("state", strings(&["RJ", "SP", "SP", "BA"]))
+        .add_int("year", vec![2020, 2021, 2020, 2020])
+        .add_int("pop", vec![17, 46, 45, 15])
+        .build()
+        .unwrap();
+
+    let inner = left.merge_on(&right, &["state", "year"], "inner").unwrap();
+    assert_eq!(inner.n_rows(), 3);
+    assert_eq!(
+        inner.get_string("state").unwrap(),
+        strings(&["SP", "SP", "RJ"])
+    );
+    assert_eq!(
+        inner.get_int("year").unwrap().to_vec(),
+        vec![2020, 2021, 2020]
+    );
+    // Fully matched int column keeps its type
+    assert_eq!(inner.get_int("pop").unwrap().to_vec(), vec![45, 46, 17]);
+
+    let left_join = left.merge_on(&right, &["state", "year"], "left").unwrap();
+    assert_eq!(left_join.n_rows(), 4);
+    let pop = left_join.get("pop").unwrap();
+    assert_eq!(pop[0], 45.0);
+    assert!(pop[3].is_nan());
+
+    let outer = left.merge_on(&right, &["state", "year"], "outer").unwrap();
+    assert_eq!(outer.n_rows(), 5);
+    // Unmatched right row contributes its key, left values are missing
+    assert_eq!(outer.get_string("state").unwrap()[4], "BA");
+    assert_eq!(outer.get_int("year").unwrap()[4], 2020);
+    assert!(outer.get("gdp").unwrap()[4].is_nan());
+
+    let right_join = left.merge_on(&right, &["state", "year"], "right").unwrap();
+    assert_eq!(right_join.n_rows(), 4);
+    assert_eq!(
+        right_join.get_string("state").unwrap(),
+        strings(&["RJ", "SP", "SP", "BA"])
+    );
+    assert_eq!(right_join.get("gdp").unwrap()[1], 2.0);
+}
+
+#[test]
+fn test_merge_string_keys_do_not_collide() {
+    // Free-text keys used to become NaN and match every row
+    let left = DataFrame::builder()
+        .add_string("code", strings(&["a1", "b2", "c3"]))
+        .add_column("x", vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+    let right = DataFrame::builder()
+        .add_string("code", strings(&["c3", "a1"]))
+        .add_column("y", vec![30.0, 10.0])
+        .build()
+        .unwrap();
+
+    let merged = left.merge(&right, "code", "inner").unwrap();
+    assert_eq!(merged.n_rows(), 2);
+    assert_eq!(merged.get("x").unwrap().to_vec(), vec![1.0, 3.0]);
+    assert_eq!(merged.get("y").unwrap().to_vec(), vec![10.0, 30.0]);
+}
+
+#[test]
+fn test_merge_datetime_keys_keep_subsecond_precision() {
+    let base = ts(1, 9);
+    let left = DataFrame::builder()
+        .add_datetime(
+            "t",
+            vec![
+                base,
+                base + Duration::milliseconds(1),
+                base + Duration::seconds(1),
+            ],
+        )
+        .add_column("x", vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+    let right = DataFrame::builder()
+        .add_datetime("t", vec![base + Duration::milliseconds(1)])
+        .add_column("y", vec![9.0])
+        .build()
+        .unwrap();
+
+    let merged = left.merge(&right, "t", "inner").unwrap();
+    assert_eq!(merged.n_rows(), 1);
+    assert_eq!(merged.get("x").unwrap()[0], 2.0);
+    assert!(matches!(
+        merged.get_column("t").unwrap(),
+        Column::DateTime(_)
+    ));
+}
+
+#[test]
+fn test_merge_suffixes_and_fills() {
+    let left = DataFrame::builder()
+        .add_int("id", vec![1, 2])
+        .add_column("value", vec![1.0, 2.0])
+        .add_categorical("label", strings(&["a", "b"]))
+        .build()
+        .unwrap();
+    let right = DataFrame::builder()
+        .add_int("id", vec![1])
+        .add_column("value", vec![10.0])
+        .add_categorical("label", strings(&["z"]))
+        .add_bool("flag", vec![true])
+        .build()
+        .unwrap();
+
+    let merged = left.merge(&right, "id", "left").unwrap();
+    assert_eq!(
+        merged.column_names(),
+        strings(&["id", "value_x", "label_x", "value_y", "label_y", "flag"])
+    );
+    assert_eq!(merged.get_string("label_y").unwrap(), strings(&["z", ""]));
+    // Bool column with a gap is promoted to float
+    let flag = merged.get("flag").unwrap();
+    assert_eq!(flag[0], 1.0);
+    assert!(flag[1].is_nan());
+
+    let config = MergeConfig {
+        suffixes: ("_l".into(), "_r".into()),
+        ..MergeConfig::default()
+    };
+    let custom = left
+        .merge_with_config(&right, &["id"], "inner", &config)
+        .unwrap();
+    assert!(custom.has_column("value_l") && custom.has_column("value_r"));
+
+    let clash = MergeConfig {
+        suffixes: (String::new(), String::new()),
+        ..MergeConfig::default()
+    };
+    assert!(left
+        .merge_with_config(&right, &["id"], "inner", &clash)
+        .is_err());
+}
+
+#[test]
+fn test_merge_fills_are_missing_for_formulas() {
+    let left = DataFrame::builder()
+        .add_categorical("firm", strings(&["b", "a", "b", "a"]))
+        .add_int("id", vec![1, 2, 3, 4])
+        .add_column("y", vec![1.0, 2.0, 4.0, 3.0])
+        .build()
+        .unwrap();
+    let right = DataFrame::builder()
+        .add_int("id", vec![1, 2, 4, 5])
+        .add_categorical("sector", strings(&["y", "x", "y", "x"]))
+        .add_datetime("t", vec![ts(1, 0), ts(2, 0), ts(3, 0), ts(4, 0)])
+        .build()
+        .unwrap();
+
+    let merged = left.merge(&right, "id", "left").unwrap();
+    // The unmatched row gets a code without a level, not a new "NA" level
+    let sector = merged.get_categorical("sector").unwrap();
+    assert_eq!(sector.levels, strings(&["y", "x"]));
+    assert_eq!(sector.codes[2], CategoricalColumn::MISSING_CODE);
+    let t = merged.get_column("t").unwrap();
+    assert_eq!(t.as_datetime().unwrap()[2], Column::MISSING_DATETIME);
+    assert!(t.to_float()[2].is_nan());
+
+    for formula in ["y ~ C(sector)", "y ~ t"] {
+        let formula = Formula::parse(formula).unwrap();
+        let dm = merged
+            .design_matrices(&formula, MissingPolicy::Drop)
+            .unwrap();
+        assert_eq!(dm.rows.dropped, vec![2]);
+        assert!(merged
+            .design_matrices(&formula, MissingPolicy::Raise)
+            .is_err());
+    }
+
+    // Coalesced categorical keys keep the left level order
+    let right_keys = DataFrame::builder()
+        .add_categorical("firm", strings(&["c", "a"]))
+        .add_column("z", vec![1.0, 2.0])
+        .build()
+        .unwrap();
+    let outer = left.merge(&right_keys, "firm", "outer").unwrap();
+    let firm = outer.get_categorical("firm").unwrap();
+    assert_eq!(firm.levels, strings(&["b", "a", "c"]));
+}
+
+#[test]
+fn test_merge_validate() {
+    let once = DataFrame::builder()
+        .add_int("id", vec![1, 2, 3])
+        .add_column("a", vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+    let many = DataFrame::builder()
+        .add_int("id", vec![1, 1, 2])
+        .add_column("b", vec![1.0, 2.0, 3.0])
+        .build()
+        .unwrap();
+
+    let validate = |v| MergeConfig {
+        validate: v,
+        ..MergeConfig::default()
+    };
+    assert!(once
+        .merge_with_config(&once, &["id"], "inner", &validate(MergeValidate::OneToOne))
+        .is_ok());
+    assert!(many
+        .merge_with_config(&once, &["id"], "left", &validate(MergeValidate::ManyToOne))
+        .is_ok());
+    assert!(once
+        .merge_with_config(&many, &["id"], "left", &validate(MergeValidate::OneToMany))
+        .is_ok());
+    assert!(matches!(
+        once.merge_with_config(&many, &["id"], "left", &validate(MergeValidate::ManyToOne)),
+        Err(GreenersError::InvalidOperation(_))
+    ));
+    assert!(many
+        .merge_with_config(&once, &["id"], "left", &validate(MergeValidate::OneToOne))
+        .is_err());
+}
+
+#[test]
+fn test_merge_errors() {
+    let a = DataFrame::builder()
+        .add_int("id", vec![1, 2])
+        .add_datetime("t", vec![ts(1, 0), ts(2, 0)])
+        .build()
+        .unwrap();
+    let b = DataFrame::builder()
+        .add_string("id", strings(&["1", "2"]))
+        .add_datetime("t", vec![ts(1, 0), ts(2, 0)])
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        a.merge(&b, "id", "inner"),
+        Err(GreenersError::ShapeMismatch(_))
+    ));
+    assert!(matches!(
+        a.merge(&b, "missing", "inner"),
+        Err(GreenersError::VariableNotFound(_))
+    ));
+    assert!(a.merge(&b, "t", "cross").is_err());
+    assert!(a.merge_on(&b, &[], "inner").is_err());
+    assert!(a.merge_on(&b, &["t", "t"], "inner").is_err());
+}
+
+#[test]
+fn test_merge_asof_directions_and_tolerance() {
+    let trades = DataFrame::builder()
+        .add_datetime("time", vec![ts(5, 12), ts(1, 12), ts(3, 0)])
+        .add_column("price", vec![3.0, 1.0, 2.0])
+        .build()
+        .unwrap();
+    // Deliberately unsorted
+    let quotes = DataFrame::builder()
+        .add_datetime("time", vec![ts(4, 0), ts(1, 0), ts(3, 0), ts(6, 0)])
+        .add_column("bid", vec![40.0, 10.0, 30.0, 60.0])
+        .build()
+        .unwrap();
+
+    let backward = trades
+        .merge_asof(&quotes, "time", &[], "backward", None)
+        .unwrap();
+    assert_eq!(backward.n_rows(), 3);
+    // Left order is preserved and exact matches count as backward
+    assert_eq!(backward.get("price").unwrap().to_vec(), vec![3.0, 1.0, 2.0]);
+    assert_eq!(
+        backward.get("bid").unwrap().to_vec(),
+        vec![40.0, 10.0, 30.0]
+    );
+    assert_eq!(backward.n_cols(), 3);
+
+    let forward = trades
+        .merge_asof(&quotes, "time", &[], "forward", None)
+        .unwrap();
+    assert_eq!(forward.get("bid").unwrap().to_vec(), vec![60.0, 30.0, 30.0]);
+
+    let nearest = trades
+        .merge_asof(&quotes, "time", &[], "nearest", None)
+        .unwrap();
+    // 5th 12:00 is 36h after the 4th and 12h before the 6th
+    assert_eq!(nearest.get("bid").unwrap().to_vec(), vec![60.0, 10.0, 30.0]);
+
+    let tight = trades
+        .merge_asof(&quotes, "time", &[], "backward", Some(Duration::hours(12)))
+        .unwrap();
+    let bid = tight.get("bid").unwrap();
+    assert!(bid[0].is_nan());
+    assert_eq!(bid[1], 10.0);
+    assert_eq!(bid[2], 30.0);
+
+    assert!(trades
+        .merge_asof(&quotes, "time", &[], "sideways", None)
+        .is_err());
+    assert!(trades
+        .merge_asof(&quotes, "price", &[], "backward", None)
+        .is_err());
+}
+
+#[test]
+fn test_merge_asof_by_group() {
+    let panel = DataFrame::builder()
+        .add_categorical("firm", strings(&["A", "B", "A", "C"]))
+        .add_datetime("date", vec![ts(10, 0), ts(10, 0), ts(20, 0), ts(10, 0)])
+        .add_column("ret", vec![0.1, 0.2, 0.3, 0.4])
+        .build()
+        .unwrap();
+    let filings = DataFrame::builder()
+        .add_categorical("firm", strings(&["A", "B", "A", "B"]))
+        .add_datetime("date", vec![ts(1, 0), ts(2, 0), ts(15, 0), ts(12, 0)])
+        .add_column("ret", vec![-1.0, -2.0, -3.0, -4.0])
+        .add_column("book", vec![100.0, 200.0, 150.0, 250.0])
+        .build()
+        .unwrap();
+
+    let merged = panel
+        .merge_asof(&filings, "date", &["firm"], "backward", None)
+        .unwrap();
+    assert_eq!(
+        merged.column_names(),
+        strings(&["firm", "date", "ret_x", "ret_y", "book"])
+    );
+    let book = merged.get("book").unwrap();
+    assert_eq!(book[0], 100.0);
+    assert_eq!(book[1], 200.0);
+    assert_eq!(book[2], 150.0);
+    // Firm C has no filings
+    assert!(book[3].is_nan());
+    assert_eq!(merged.get("ret_y").unwrap()[2], -3.0);
+}
@@ -0,0 +1,86 @@
+use greeners_core::{
+    CovarianceType, DataFrame, DesignInfo, Formula, GreenersError, Persist, RowMap,
+};
+use ndarray::array;
+
+fn train_df() -> DataFrame {
+    DataFrame::builder()
+        .add_column("y", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .add_column("x", vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0])
+        .add_categorical(
+            "region",
+            vec![
+                "north".into(),
+                "south".into(),
+                "east".into(),
+                "north".into(),
+                "south".into(),
+                "east".into(),
+            ],
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_design_info_round_trip_rebuilds_same_design() {
+    let df = train_df();
+    let formula = Formula::parse("y ~ x + C(region) + bs(x, 4)").unwrap();
+    let info = DesignInfo::from_formula(&formula, &df).unwrap();
+    let (_, x) = info.build(&df).unwrap();
+
+    let from_json = DesignInfo::from_json(&info.to_json().unwrap()).unwrap();
+    let from_bytes = DesignInfo::from_bytes(&info.to_bytes().unwrap()).unwrap();
+    assert_eq!(from_json.build(&df).unwrap().1, x);
+    assert_eq!(from_bytes.build(&df).unwrap().1, x);
+    assert_eq!(from_json.column_names(), info.column_names());
+}
+
+#[test]
+fn test_persist_non_finite_and_enums() {
+    let v = array![1.5, f64::NAN, -2.0];
+    let back = ndarray::Array1::<f64>::from_json(&v.to_json().unwrap()).unwrap();
+    assert_eq!(back[0], 1.5);
+    assert!(back[1].is_nan());
+    let back = ndarray::Array1::<f64>::from_bytes(&v.to_bytes().unwrap()).unwrap();
+    assert!(back[1].is_nan());
+
+    let cov = CovarianceType::Clustered(vec![0, 0, 1, 1]);
+    let back = CovarianceType::from_json(&cov.to_json().unwrap()).unwrap();
+    assert!(matches!(back, CovarianceType::Clustered(ids) if ids == vec![0, 0, 1, 1]));
+    let hac = CovarianceType::NeweyWest(4).to_bytes().unwrap();
+    let back = CovarianceType::from_bytes(&hac).unwrap();
+    assert!(matches!(back, CovarianceType::NeweyWest(4)));
+}
+
+#[test]
+fn test_persist_rejects_wrong_kind_and_newer_version() {
+    let rows = RowMap::new(5, vec![1, 3]);
+    let json = rows.to_json().unwrap();
+    assert!(json.starts_with(r#"{"format":"greeners","version":1,"kind":"RowMap""#));
+
+    assert!(matches!(
+        DesignInfo::from_json(&json),
+        Err(GreenersError::Serialization(_))
+    ));
+    assert!(DesignInfo::from_bytes(&rows.to_bytes().unwrap()).is_err());
+
+    let newer = json.replace(r#""version":1"#, r#""version":99"#);
+    let err = RowMap::from_json(&newer).unwrap_err();
+    assert!(err.to_string().contains("newer"), "{}", err);
+    assert!(RowMap::from_bytes(&[1, 2, 3]).is_err());
+}
+
+#[test]
+fn test_persist_save_and_load_files() {
+    let rows = RowMap::new(4, vec![2]);
+    let dir = std::env::temp_dir();
+    for name in ["greeners_rowmap_test.json", "greeners_rowmap_test.bin"] {
+        let path = dir.join(name);
+        rows.save(&path).unwrap();
+        let back = RowMap::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(back.n_rows, 4);
+        assert_eq!(back.dropped, vec![2]);
+    }
+}
@@ -0,0 +1,155 @@
+use greeners_core::{ColumnType, DataFrame, DsvRow, RowPredicate, TypeInferenceConfig};
+use std::collections::HashMap;
+
+fn eval(expr: &str, cols: &[(&str, &str)]) -> bool {
+    let pred = RowPredicate::parse(expr).unwrap();
+    let fields: Vec<String> = cols.iter().map(|(_, v)| v.to_string()).collect();
+    let layout: Vec<(usize, String)> = cols
+        .iter()
+        .enumerate()
+        .map(|(i, (n, _))| (i, n.to_string()))
+        .collect();
+    pred.evaluate(&DsvRow {
+        fields: &fields,
+        layout: &layout,
+    })
+}
+
+#[test]
+fn test_predicate_comparisons_and_logic() {
+    let row = [("age", "34"), ("income", "52000.5"), ("region", "north")];
+
+    assert!(eval("age > 30", &row));
+    assert!(!eval("age < 30", &row));
+    assert!(eval("age >= 34 and income <= 52000.5", &row));
+    assert!(eval("age == 10 or region == 'north'", &row));
+    assert!(eval("not (age = 10)", &row));
+    assert!(eval("region != \"south\" && !(age < 18)", &row));
+    assert!(eval("region <> 'south' || false", &row));
+}
+
+#[test]
+fn test_predicate_arithmetic_and_precedence() {
+    let row = [("price", "12.5"), ("qty", "4")];
+
+    assert!(eval("price * qty == 50", &row));
+    assert!(eval("price * qty - 10 > 39", &row));
+    assert!(eval("qty % 3 == 1", &row));
+    assert!(eval("-price < 0", &row));
+    // Multiplication binds tighter than addition
+    assert!(eval("1 + qty * 2 == 9", &row));
+    assert!(eval("(1 + qty) * 2 == 10", &row));
+}
+
+#[test]
+fn test_predicate_in_lists() {
+    let row = [("year", "2021"), ("state", "CA")];
+
+    assert!(eval("year in [2019, 2021]", &row));
+    assert!(!eval("year not in [2019, 2021]", &row));
+    assert!(eval("state in ['CA', 'NY']", &row));
+    assert!(!eval("state in []", &row));
+}
+
+#[test]
+fn test_predicate_null_semantics() {
+    let row = [("x", ""), ("y", "NA"), ("z", "3")];
+
+    assert!(eval("x is null", &row));
+    assert!(eval("y is null and z is not null", &row));
+    // Comparisons with a missing value are null, so the row is rejected
+    assert!(!eval("x > 1", &row));
+    assert!(!eval("x != 1", &row));
+    assert!(!eval("not (x > 1)", &row));
+    // ... unless the other branch of a disjunction is true
+    assert!(eval("x > 1 or z == 3", &row));
+}
+
+#[test]
+fn test_predicate_referenced_columns() {
+    let pred = RowPredicate::parse("a > 1 and (b + a) < 3 or `unit price` is null").unwrap();
+    assert_eq!(pred.referenced_columns(), vec!["a", "b", "unit price"]);
+}
+
+#[test]
+fn test_predicate_parse_errors() {
+    assert!(RowPredicate::parse("x >").is_err());
+    assert!(RowPredicate::parse("(x > 1").is_err());
+    assert!(RowPredicate::parse("x in [1, y]").is_err());
+    assert!(RowPredicate::parse("x is 3").is_err());
+    assert!(RowPredicate::parse("'unterminated").is_err());
+    // Statically ill-typed expressions
+    assert!(RowPredicate::parse("x + 'a' > 1").is_err());
+    assert!(RowPredicate::parse("1 and x").is_err());
+    assert!(RowPredicate::parse("x + 1").is_err());
+    assert!(RowPredicate::parse("x in [1, 'a']").is_err());
+}
+
+#[test]
+fn test_predicate_check_layout() {
+    let layout = vec![(0, "price".to_string()), (1, "ticker".to_string())];
+    let mut types = HashMap::new();
+    types.insert("ticker".to_string(), ColumnType::String);
+    types.insert("price".to_string(), ColumnType::Float);
+
+    let ok = RowPredicate::parse("price > 1 and ticker == 'AAPL'").unwrap();
+    assert!(ok.check_layout(&layout, &types).is_ok());
+
+    let missing = RowPredicate::parse("volume > 1").unwrap();
+    assert!(missing.check_layout(&layout, &types).is_err());
+
+    let string_arith = RowPredicate::parse("ticker * 2 > 1").unwrap();
+    assert!(string_arith.check_layout(&layout, &types).is_err());
+
+    let mismatch = RowPredicate::parse("price == 'AAPL'").unwrap();
+    assert!(mismatch.check_layout(&layout, &types).is_err());
+}
+
+#[test]
+fn test_from_csv_with_predicate() {
+    let path = std::env::temp_dir().join(format!(
+        "greeners_predicate_test_{}.csv",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "id,region,income,year\n\
+         1,north,100,2020\n\
+         2,south,250,2021\n\
+         3,north,,2021\n\
+         4,east,400,2022\n\
+         5,north,500,2022\n",
+    )
+    .unwrap();
+
+    let pred = RowPredicate::parse("region == 'north' and income > 50").unwrap();
+    let cols = vec!["id".to_string(), "income".to_string()];
+    let df = DataFrame::from_csv_with_config(
+        &path,
+        TypeInferenceConfig::default(),
+        Some(&cols),
+        None,
+        Some(&pred),
+    )
+    .unwrap();
+
+    // Row 3 has a missing income and is dropped; `region` is filtered on
+    // without being projected.
+    assert_eq!(df.n_rows(), 2);
+    assert_eq!(df.column_names(), vec!["id", "income"]);
+    let ids = df.get_int("id").unwrap();
+    assert_eq!(ids[0], 1);
+    assert_eq!(ids[1], 5);
+
+    let unknown = RowPredicate::parse("country == 'BR'").unwrap();
+    let err = DataFrame::from_csv_with_config(
+        &path,
+        TypeInferenceConfig::default(),
+        None,
+        None,
+        Some(&unknown),
+    );
+    assert!(err.is_err());
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,64 @@
+use greeners_core::bootstrap::Bootstrap;
+use greeners_core::{GreenersRng, DEFAULT_SEED};
+use ndarray::{Array1, Array2};
+
+fn make_data(n: usize) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = GreenersRng::new(Some(1));
+    let mut x = Array2::ones((n, 2));
+    let mut y = Array1::zeros(n);
+    for i in 0..n {
+        x[[i, 1]] = rng.uniform() * 10.0;
+        y[i] = 1.0 + 0.5 * x[[i, 1]] + rng.normal();
+    }
+    (y, x)
+}
+
+#[test]
+fn test_none_uses_default_seed() {
+    let mut a = GreenersRng::new(None);
+    let mut b = GreenersRng::new(Some(DEFAULT_SEED));
+    assert_eq!(a.seed(), DEFAULT_SEED);
+    for _ in 0..10 {
+        assert_eq!(a.uniform(), b.uniform());
+    }
+}
+
+#[test]
+fn test_streams_independent_of_parent_state() {
+    let mut parent = GreenersRng::new(Some(9));
+    let before = parent.stream(2).uniform();
+    for _ in 0..100 {
+        parent.normal();
+    }
+    assert_eq!(parent.stream(2).uniform(), before);
+    assert_ne!(parent.stream(2).uniform(), parent.stream(3).uniform());
+}
+
+#[test]
+fn test_index_and_bootstrap_indices_in_range() {
+    let mut rng = GreenersRng::new(Some(5));
+    assert_eq!(rng.index(0), 0);
+    let idx = rng.bootstrap_indices(50);
+    assert_eq!(idx.len(), 50);
+    assert!(idx.iter().all(|&i| i < 50));
+}
+
+#[test]
+fn test_pairs_bootstrap_reproducible_across_threads() {
+    let (y, x) = make_data(60);
+    let run = || Bootstrap::pairs_bootstrap(&y, &x, 200, Some(17)).unwrap();
+    let single = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(run);
+    let multi = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(run);
+    assert_eq!(single, multi);
+
+    let other = Bootstrap::pairs_bootstrap(&y, &x, 200, Some(18)).unwrap();
+    assert_ne!(single, other);
+}
@@ -0,0 +1,189 @@
+use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::{GreenersRng, WildBootstrap, WildBootstrapConfig, WildWeights};
+use ndarray::{Array1, Array2};
+
+/// y = 1 + beta·x + cluster shock + noise with `g` clusters of `m` rows;
+/// x varies mostly at the cluster level.
+fn clustered_data(
+    g: usize,
+    m: usize,
+    beta: f64,
+    seed: u64,
+) -> (Array1<f64>, Array2<f64>, Vec<usize>) {
+    let mut rng = GreenersRng::new(Some(seed));
+    let n = g * m;
+    let mut x = Array2::<f64>::ones((n, 2));
+    let mut y = Array1::<f64>::zeros(n);
+    let clusters: Vec<usize> = (0..n).map(|i| i / m).collect();
+    for c in 0..g {
+        let x_c = rng.normal();
+        let shock = 2.0 * rng.normal();
+        for i in c * m..(c + 1) * m {
+            x[[i, 1]] = x_c + 0.3 * rng.normal();
+            y[i] = 1.0 + beta * x[[i, 1]] + shock * (1.0 + x_c.abs()) + rng.normal();
+        }
+    }
+    (y, x, clusters)
+}
+
+#[test]
+fn test_wild_cluster_statistic_matches_cr1() {
+    let (y, x, clusters) = clustered_data(12, 10, 0.5, 1);
+    let res = WildBootstrap::test(
+        &y,
+        &x,
+        Some(&clusters),
+        1,
+        0.0,
+        &WildBootstrapConfig {
+            confidence_interval: false,
+            ..WildBootstrapConfig::default()
+        },
+    )
+    .unwrap();
+
+    // CR1 t statistic computed directly
+    let (n, k, g) = (120.0, 2.0, 12.0);
+    let xtx_inv = x.t().dot(&x).inv().unwrap();
+    let beta = xtx_inv.dot(&x.t().dot(&y));
+    let u = &y - &x.dot(&beta);
+    let mut meat = Array2::<f64>::zeros((2, 2));
+    for c in 0..12 {
+        let mut s = Array1::<f64>::zeros(2);
+        for i in c * 10..(c + 1) * 10 {
+            s = s + &x.row(i) * u[i];
+        }
+        let col = s.view().insert_axis(ndarray::Axis(1));
+        meat = meat + col.dot(&col.t());
+    }
+    let v = xtx_inv.dot(&meat).dot(&xtx_inv) * (g / (g - 1.0) * (n - 1.0) / (n - k));
+    assert!((res.estimate - beta[1]).abs() < 1e-12);
+    assert!((res.t_stat - beta[1] / v[[1, 1]].sqrt()).abs() < 1e-10);
+    assert_eq!(res.n_clusters, Some(12));
+    assert!(res.conf_interval.is_none());
+}
+
+#[test]
+fn test_rademacher_full_enumeration_with_few_clusters() {
+    let (y, x, clusters) = clustered_data(6, 15, 1.0, 2);
+    let res = WildBootstrap::test(
+        &y,
+        &x,
+        Some(&clusters),
+        1,
+        0.0,
+        &WildBootstrapConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(res.n_bootstrap, 64);
+    let scaled = res.p_value * 64.0;
+    assert!((scaled - scaled.round()).abs() < 1e-9);
+
+    // Enumeration does not depend on the seed
+    let other_seed = WildBootstrap::test(
+        &y,
+        &x,
+        Some(&clusters),
+        1,
+        0.0,
+        &WildBootstrapConfig {
+            seed: Some(99),
+            ..WildBootstrapConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(res.p_value, other_seed.p_value);
+    assert_eq!(res.conf_interval, other_seed.conf_interval);
+}
+
+#[test]
+fn test_confidence_interval_inverts_the_test() {
+    let (y, x, clusters) = clustered_data(15, 8, 0.8, 3);
+    let config = WildBootstrapConfig {
+        weights: WildWeights::Webb,
+        n_bootstrap: 399,
+        seed: Some(5),
+        ..WildBootstrapConfig::default()
+    };
+    let res = WildBootstrap::test(&y, &x, Some(&clusters), 1, 0.8, &config).unwrap();
+    let (lo, hi) = res.conf_interval.unwrap();
+    assert!(lo < res.estimate && res.estimate < hi);
+
+    // p-values just inside the bounds exceed alpha, just outside do not
+    let p_at = |r: f64| {
+        WildBootstrap::test(
+            &y,
+            &x,
+            Some(&clusters),
+            1,
+            r,
+            &WildBootstrapConfig {
+                confidence_interval: false,
+                ..config.clone()
+            },
+        )
+        .unwrap()
+        .p_value
+    };
+    let width = hi - lo;
+    assert!(p_at(lo + 1e-3 * width) > 0.05);
+    assert!(p_at(lo - 1e-3 * width) <= 0.05);
+    assert!(p_at(hi - 1e-3 * width) > 0.05);
+    assert!(p_at(hi + 1e-3 * width) <= 0.05);
+    // At the point estimate every |t*| exceeds |t| = 0
+    assert_eq!(p_at(res.estimate), 1.0);
+    assert!(format!("{}", res).contains("Wild Cluster Restricted"));
+}
+
+#[test]
+fn test_wcr_size_with_few_clusters() {
+    // Under H0 the restricted wild cluster bootstrap rejects close to 5%
+    let sims = 100;
+    let mut rejections = 0;
+    for s in 0..sims {
+        let (y, x, clusters) = clustered_data(8, 10, 0.0, 100 + s);
+        let res = WildBootstrap::test(
+            &y,
+            &x,
+            Some(&clusters),
+            1,
+            0.0,
+            &WildBootstrapConfig {
+                weights: WildWeights::Webb,
+                n_bootstrap: 199,
+                seed: Some(s),
+                confidence_interval: false,
+                ..WildBootstrapConfig::default()
+            },
+        )
+        .unwrap();
+        if res.p_value <= 0.05 {
+            rejections += 1;
+        }
+    }
+    let rate = rejections as f64 / sims as f64;
+    assert!(rate < 0.11, "rejection rate {}", rate);
+}
+
+#[test]
+fn test_heteroskedastic_wild_bootstrap_and_errors() {
+    let (y, x, clusters) = clustered_data(20, 5, 0.0, 4);
+    for weights in [WildWeights::Rademacher, WildWeights::Mammen] {
+        let config = WildBootstrapConfig {
+            weights,
+            n_bootstrap: 199,
+            impose_null: false,
+            ..WildBootstrapConfig::default()
+        };
+        let a = WildBootstrap::test(&y, &x, None, 1, 0.0, &config).unwrap();
+        let b = WildBootstrap::test(&y, &x, None, 1, 0.0, &config).unwrap();
+        assert_eq!(a.p_value, b.p_value);
+        assert!(a.n_clusters.is_none());
+        assert!((0.0..=1.0).contains(&a.p_value));
+    }
+
+    let config = WildBootstrapConfig::default();
+    assert!(WildBootstrap::test(&y, &x, Some(&clusters), 2, 0.0, &config).is_err());
+    assert!(WildBootstrap::test(&y, &x, Some(&clusters[..10]), 1, 0.0, &config).is_err());
+    assert!(WildBootstrap::test(&y, &x, Some(&vec![0; 100]), 1, 0.0, &config).is_err());
+}
@@ -4,10 +4,11 @@
 use greeners_core::GreenersError;
 use greeners_glm::discrete::Logit;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
 
 /// Result of the classification table for binary models.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClassificationResult {
     /// Threshold used (default 0.5)
     pub threshold: f64,
@@ -65,7 +66,7 @@ impl std::fmt::Display for ClassificationResult {
 }
 
 /// Result of the ROC / AUC analysis.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RocResult {
     /// Area under the ROC curve
     pub auc: f64,
@@ -150,7 +151,7 @@ impl std::fmt::Display for RocResult {
 }
 
 /// Result of the Hosmer-Lemeshow goodness-of-fit test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct HosmerLemeshowResult {
     /// H-L chi-squared statistic
     pub hl_stat: f64,
@@ -526,7 +527,7 @@ impl BinaryDiagnostics {
 }
 
 /// Result of the linktest (specification error detection).
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LinktestResult {
     /// Coefficient on ŷ (linear predictor) — should be significant
     pub hat_coef: f64,
@@ -3,10 +3,11 @@ use greeners_core::linalg::{LinalgInverse as _, LinalgSVD as _};
 use greeners_core::types::CovarianceType; // Needed to call OLS fit
 use greeners_ols::ols::OLS; // We reuse OLS for the Breusch-Pagan auxiliary regression
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
 
 /// Result of Ljung-Box portmanteau test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LjungBoxResult {
     pub q_stat: f64,
     pub p_value: f64,
@@ -17,7 +18,7 @@ pub struct LjungBoxResult {
 }
 
 /// Result of Engle's ARCH LM test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ArchTestResult {
     pub lm_stat: f64,
     pub lm_pvalue: f64,
@@ -29,7 +30,7 @@ pub struct ArchTestResult {
 }
 
 /// Result of Anderson-Darling normality test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AndersonDarlingResult {
     pub statistic: f64,
     /// Critical values at [15%, 10%, 5%, 2.5%, 1%]
@@ -38,14 +39,14 @@ pub struct AndersonDarlingResult {
     pub n_obs: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ShapiroWilkResult {
     pub w: f64,
     pub p_value: f64,
     pub n_obs: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ShapiroFranciaResult {
     pub w_prime: f64,
     pub p_value: f64,
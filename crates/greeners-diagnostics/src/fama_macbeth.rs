@@ -2,9 +2,10 @@ use greeners_core::error::GreenersError;
 use greeners_core::{CovarianceType, DataFrame, Formula};
 use greeners_ols::ols::OLS;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 
 /// Result of Fama-MacBeth (1973) cross-sectional regression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FamaMacBethResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -3,13 +3,14 @@ use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::CovarianceType;
 use greeners_ols::ols::OLS;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Influence diagnostics for regression models (DFBetas, DFFITS, CUSUM).
 pub struct Influence;
 
 /// Result of influence diagnostics.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct InfluenceResult {
     /// DFBetas: change in each coefficient when observation i is deleted (n x k)
     pub dfbetas: Array2<f64>,
@@ -170,7 +171,7 @@ impl Influence {
 pub struct CUSUMTest;
 
 /// Result of CUSUM test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CUSUMResult {
     /// Cumulative sum of recursive residuals
     pub cusum: Array1<f64>,
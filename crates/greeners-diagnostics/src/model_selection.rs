@@ -1,11 +1,14 @@
+use greeners_core::FittedModel;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF};
 
 type ModelStats = (f64, f64, f64, f64, f64, f64, f64, usize);
 type MundlakResult = (f64, f64, usize, Vec<f64>, Vec<f64>);
+type ModelComparison = Vec<(String, f64, f64, usize, usize)>;
 
 /// Result of a likelihood-ratio test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LrTestResult {
     /// LR statistic: -2 * (ln L_restricted - ln L_unrestricted)
     pub lr_stat: f64,
@@ -159,6 +162,22 @@ impl ModelSelection {
             .collect()
     }
 
+    /// Compare fitted models of any type by information criteria.
+    ///
+    /// Same output as [`compare_models`](Self::compare_models), with the
+    /// log-likelihood, number of parameters and observations taken from each
+    /// model. Returns an error if a model is not likelihood-based.
+    pub fn compare_fitted(models: &[(&str, &dyn FittedModel)]) -> Result<ModelComparison, String> {
+        let mut rows = Vec::with_capacity(models.len());
+        for (name, model) in models {
+            let ll = model
+                .loglike()
+                .ok_or_else(|| format!("compare_fitted: model '{name}' has no log-likelihood"))?;
+            rows.push((*name, ll, model.n_params(), model.nobs()));
+        }
+        Ok(Self::compare_models(rows))
+    }
+
     /// Calculate delta AIC and Akaike weights for model averaging
     ///
     /// # Arguments
@@ -326,3 +326,31 @@ fn test_panel_diagnostics_insufficient_df() {
     // Should return error due to insufficient df
     assert!(result.is_err());
 }
+
+#[test]
+fn test_model_selection_compare_fitted() {
+    use greeners_core::{CovarianceType, FittedModel};
+    use greeners_ols::ols::OLS;
+    use ndarray::Array2;
+
+    let n = 30;
+    let y = Array1::from(
+        (0..n)
+            .map(|i| 0.5 * i as f64 + (i % 3) as f64)
+            .collect::<Vec<_>>(),
+    );
+    let mut x_full = Array2::<f64>::ones((n, 2));
+    for i in 0..n {
+        x_full[[i, 1]] = i as f64;
+    }
+    let x_const = Array2::<f64>::ones((n, 1));
+
+    let full = OLS::fit(&y, &x_full, CovarianceType::NonRobust).unwrap();
+    let null = OLS::fit(&y, &x_const, CovarianceType::NonRobust).unwrap();
+    let models: Vec<(&str, &dyn FittedModel)> = vec![("null", &null), ("full", &full)];
+
+    let comparison = ModelSelection::compare_fitted(&models).unwrap();
+    assert_eq!(comparison.len(), 2);
+    assert_eq!(comparison[0].0, "full");
+    assert_eq!(comparison[0].3, 1);
+}
@@ -1,6 +1,7 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use statrs::function::gamma::{digamma, ln_gamma};
 use std::fmt;
@@ -56,7 +57,7 @@ impl BetaLink {
 }
 
 /// Result of Beta regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BetaResult {
     pub params: Array1<f64>,
     pub precision_param: f64,
@@ -3,11 +3,12 @@ use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::types::InferenceType;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result from Conditional Logit/Poisson models.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConditionalResult {
     pub model_name: String,
     /// Coefficients (no intercept — absorbed by group FE).
@@ -1,12 +1,16 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{DataFrame, Formula, InferenceType};
+use greeners_core::model::align_design;
+use greeners_core::{
+    DataFrame, DesignInfo, FittedModel, Formula, InferenceType, MissingPolicy, Model, RowMap,
+};
 use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 use std::fmt;
 
 /// Structure to store results from binary choice models (Logit/Probit).
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BinaryModelResult {
     pub model_name: String, // "Logit" or "Probit"
     pub params: Array1<f64>,
@@ -16,12 +20,15 @@ pub struct BinaryModelResult {
     pub iterations: usize,
     pub log_likelihood: f64,
     pub pseudo_r2: f64, // McFadden's R2
+    pub n_obs: usize,
     // Store X for marginal effects calculations
     pub(crate) _x_data: Option<Array2<f64>>,
     pub cov_matrix: Option<Array2<f64>>,
     pub inference_type: InferenceType, // Always Normal for MLE
     pub variable_names: Option<Vec<String>>,
     pub omitted_vars: Vec<(usize, String)>,
+    pub design_info: Option<DesignInfo>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for BinaryModelResult {
@@ -100,9 +107,22 @@ impl Logit {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<BinaryModelResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, Some(var_names))
+        Self::from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    /// Fit from a formula with an explicit missing-value policy; the rows
+    /// dropped are recorded in `result.row_map`.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<BinaryModelResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, Some(var_names))?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     pub fn fit(y: &Array1<f64>, x: &Array2<f64>) -> Result<BinaryModelResult, GreenersError> {
@@ -225,6 +245,7 @@ impl Logit {
 
         Ok(BinaryModelResult {
             model_name: "Logit".to_string(),
+            n_obs: n,
             params: beta,
             std_errors,
             z_values,
@@ -237,6 +258,8 @@ impl Logit {
             inference_type: InferenceType::Normal, // MLE always uses Normal
             variable_names,
             omitted_vars: omitted_positioned,
+            design_info: None,
+            row_map: None,
         })
     }
 }
@@ -338,6 +361,18 @@ impl BinaryModelResult {
         Ok(mem)
     }
 
+    /// Predicted probabilities Pr(y=1|x) on a new DataFrame for a model fitted
+    /// with `from_formula`, rebuilding the design learned at fit time.
+    pub fn predict_df(&self, data: &DataFrame) -> Result<Array1<f64>, GreenersError> {
+        let info = self.design_info.as_ref().ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "predict_df requires a model fitted with from_formula".into(),
+            )
+        })?;
+        let x_new = info.build_x_omitting(data, &self.omitted_vars)?;
+        Ok(self.predict_proba(&x_new))
+    }
+
     /// Calculate predicted probabilities
     ///
     /// # Arguments
@@ -507,9 +542,22 @@ impl Probit {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<BinaryModelResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, Some(var_names))
+        Self::from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    /// Fit from a formula with an explicit missing-value policy; the rows
+    /// dropped are recorded in `result.row_map`.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<BinaryModelResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, Some(var_names))?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     pub fn fit(y: &Array1<f64>, x: &Array2<f64>) -> Result<BinaryModelResult, GreenersError> {
@@ -647,6 +695,7 @@ impl Probit {
 
         Ok(BinaryModelResult {
             model_name: "Probit".to_string(),
+            n_obs: n,
             params: beta,
             std_errors,
             z_values,
@@ -659,6 +708,70 @@ impl Probit {
             inference_type: InferenceType::Normal, // MLE always uses Normal
             variable_names,
             omitted_vars: omitted_positioned,
+            design_info: None,
+            row_map: None,
         })
     }
 }
+
+impl FittedModel for BinaryModelResult {
+    fn model_name(&self) -> String {
+        self.model_name.clone()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Predicted probability P(y = 1 | x).
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &self.omitted_vars)?;
+        Ok(self.predict_proba(&x))
+    }
+
+    fn vcov(&self) -> Option<Array2<f64>> {
+        self.cov_matrix.clone()
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.pseudo_r2)
+    }
+}
+
+impl Model for Logit {
+    type Fitted = BinaryModelResult;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<BinaryModelResult, GreenersError> {
+        Logit::fit(y, x)
+    }
+}
+
+impl Model for Probit {
+    type Fitted = BinaryModelResult;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<BinaryModelResult, GreenersError> {
+        Probit::fit(y, x)
+    }
+}
@@ -2,6 +2,7 @@ use crate::glm::{Family, Link};
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
@@ -19,7 +20,7 @@ pub enum CorrStructure {
 }
 
 /// Result of GEE estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GeeResult {
     pub params: Array1<f64>,
     pub robust_se: Array1<f64>,
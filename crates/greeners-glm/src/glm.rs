@@ -1,12 +1,17 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{CovarianceType, DataFrame, Formula, InferenceType};
+use greeners_core::model::align_design;
+use greeners_core::{
+    CovarianceType, DataFrame, DesignInfo, FittedModel, Formula, InferenceType, MissingPolicy,
+    RowMap, Sandwich,
+};
 use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Distribution family for GLM.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Family {
     /// Gaussian (Normal) — canonical link: Identity
     Gaussian,
@@ -25,7 +30,7 @@ pub enum Family {
 }
 
 /// Link function for GLM.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Link {
     Identity,
     Log,
@@ -330,7 +335,7 @@ impl Link {
 }
 
 /// Result from a GLM estimation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlmResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -356,6 +361,9 @@ pub struct GlmResult {
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
     pub omitted_vars: Vec<(usize, String)>,
+    pub design_info: Option<DesignInfo>,
+    pub row_map: Option<RowMap>,
+    pub cov_matrix: Option<Array2<f64>>,
     // Store design matrix and y for predict/residuals
     pub(crate) _x_data: Array2<f64>,
     pub(crate) _y_data: Array1<f64>,
@@ -373,6 +381,18 @@ impl GlmResult {
         eta.mapv(|e| self.link.linkinv(e))
     }
 
+    /// Predict the mean response on a new DataFrame for a model fitted with
+    /// `GLM::from_formula`, rebuilding the design learned at fit time.
+    pub fn predict_df(&self, data: &DataFrame) -> Result<Array1<f64>, GreenersError> {
+        let info = self.design_info.as_ref().ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "predict_df requires a model fitted with from_formula".into(),
+            )
+        })?;
+        let x_new = info.build_x_omitting(data, &self.omitted_vars)?;
+        Ok(self.predict_mean(&x_new))
+    }
+
     /// Fitted values (μ̂) from the training data.
     pub fn fitted_values(&self) -> Array1<f64> {
         self.predict_mean(&self._x_data)
@@ -619,10 +639,27 @@ impl GLM {
         family: Family,
         cov_type: CovarianceType,
     ) -> Result<GlmResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
+        Self::from_formula_with_missing(formula, data, family, cov_type, MissingPolicy::Drop)
+    }
+
+    /// Fit from a formula with an explicit missing-value policy; the rows
+    /// dropped are recorded in `result.row_map`.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        family: Family,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<GlmResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let cov_type = dm.rows.select_cov_type(cov_type);
         let link = family.canonical_link();
-        Self::fit_internal(&y, &x, family, link, cov_type, Some(var_names), None)
+        let mut result =
+            Self::fit_internal(&dm.y, &dm.x, family, link, cov_type, Some(var_names), None)?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Fit GLM from arrays using canonical link.
@@ -884,10 +921,12 @@ impl GLM {
 
         let cov_matrix = match &cov_type {
             CovarianceType::NonRobust => &inv_xtwx * dispersion,
-            CovarianceType::HC1
+            CovarianceType::HC0
+            | CovarianceType::HC1
             | CovarianceType::HC2
             | CovarianceType::HC3
-            | CovarianceType::HC4 => {
+            | CovarianceType::HC4
+            | CovarianceType::HC5 => {
                 // Sandwich estimator: (X'WX)⁻¹ M (X'WX)⁻¹
                 // where M = X' diag(pearson_resid²) X with HC adjustments
                 let pearson_resid: Array1<f64> = (0..n)
@@ -896,7 +935,10 @@ impl GLM {
 
                 let hat_values = if matches!(
                     cov_type,
-                    CovarianceType::HC2 | CovarianceType::HC3 | CovarianceType::HC4
+                    CovarianceType::HC2
+                        | CovarianceType::HC3
+                        | CovarianceType::HC4
+                        | CovarianceType::HC5
                 ) {
                     // h_i = x_i' (X'WX)⁻¹ x_i * w_i
                     let mut h = Array1::<f64>::zeros(n);
@@ -909,6 +951,7 @@ impl GLM {
                     Array1::<f64>::zeros(n)
                 };
 
+                let h_max = hat_values.fold(0.0_f64, |a, &b| a.max(b));
                 let adj_resid2: Array1<f64> = (0..n)
                     .map(|i| {
                         let r2 = pearson_resid[i].powi(2) * family.variance(mu[i]);
@@ -920,6 +963,11 @@ impl GLM {
                                 let delta = (4.0_f64).min(n as f64 * hat_values[i] / k as f64);
                                 r2 / (1.0 - hat_values[i]).max(1e-10).powf(delta)
                             }
+                            CovarianceType::HC5 => {
+                                let cap = (4.0_f64).max(0.7 * n as f64 * h_max / k as f64);
+                                let delta = (n as f64 * hat_values[i] / k as f64).min(cap);
+                                r2 / (1.0 - hat_values[i]).max(1e-10).powf(delta / 2.0)
+                            }
                             _ => r2,
                         }
                     })
@@ -940,9 +988,56 @@ impl GLM {
 
                 inv_xtwx.dot(&meat).dot(&inv_xtwx)
             }
-            _ => {
-                // For NeweyWest, Clustered, etc. — fall back to non-robust for now
-                &inv_xtwx * dispersion
+            CovarianceType::Clustered(_)
+            | CovarianceType::ClusteredTwoWay(_, _)
+            | CovarianceType::Hac(_, _)
+            | CovarianceType::DriscollKraay(_, _)
+            | CovarianceType::Conley(_, _) => {
+                // Score of observation i: x_i (y_i - μ_i) / (V(μ_i) g'(μ_i))
+                let mut scores = x_use.clone();
+                for (i, mut row) in scores.axis_iter_mut(Axis(0)).enumerate() {
+                    row *= (y[i] - mu[i]) * link.deriv(mu[i]) * w_vec[i];
+                }
+                // Cluster-robust errors take the same (N-1)/(N-K) as OLS on
+                // top of the G/(G-1) in the cluster meat
+                let df_correction = match cov_type {
+                    CovarianceType::Clustered(_) | CovarianceType::ClusteredTwoWay(_, _) => {
+                        (n - 1) as f64 / df_resid as f64
+                    }
+                    _ => 1.0,
+                };
+                match Sandwich::score_meat(&scores, &cov_type)? {
+                    Some(meat) => inv_xtwx.dot(&meat).dot(&inv_xtwx) * df_correction,
+                    None => &inv_xtwx * dispersion,
+                }
+            }
+            CovarianceType::CR2(ref cluster_ids) | CovarianceType::CR3(ref cluster_ids) => {
+                // Bias-reduced cluster SE on the whitened working model:
+                // W^(1/2) X has cross-product X'WX, so its hat blocks are the
+                // weighted leverages and W^(1/2) times the working residual
+                // reproduces the score x_i w_i (y_i - μ_i) g'(μ_i)
+                let mut x_w = x_use.clone();
+                for (i, mut row) in x_w.axis_iter_mut(Axis(0)).enumerate() {
+                    row *= w_vec[i].sqrt();
+                }
+                let working_resid: Array1<f64> = (0..n)
+                    .map(|i| (y[i] - mu[i]) * link.deriv(mu[i]) * w_vec[i].sqrt())
+                    .collect();
+                let jackknife = matches!(cov_type, CovarianceType::CR3(_));
+                let (meat, _) = Sandwich::bias_reduced_cluster(
+                    &x_w,
+                    &inv_xtwx,
+                    &working_resid,
+                    cluster_ids,
+                    jackknife,
+                )?;
+                inv_xtwx.dot(&meat).dot(&inv_xtwx)
+            }
+            other => {
+                return Err(GreenersError::InvalidOperation(format!(
+                    "{} standard errors are not supported for GLM",
+                    Sandwich::describe(other)
+                )));
             }
         };
 
@@ -997,8 +1092,65 @@ impl GLM {
             inference_type: InferenceType::Normal,
             variable_names,
             omitted_vars: omitted_positioned,
+            design_info: None,
+            row_map: None,
             _x_data: x_use.clone(),
             _y_data: y.clone(),
+            cov_matrix: Some(cov_matrix),
         })
     }
 }
+
+impl FittedModel for GlmResult {
+    fn model_name(&self) -> String {
+        format!("GLM ({:?})", self.family)
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Predicted mean response μ = g⁻¹(Xβ).
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &self.omitted_vars)?;
+        Ok(self.predict_mean(&x))
+    }
+
+    fn vcov(&self) -> Option<Array2<f64>> {
+        self.cov_matrix.clone()
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+
+    fn aic(&self) -> Option<f64> {
+        Some(self.aic)
+    }
+
+    fn bic(&self) -> Option<f64> {
+        Some(self.bic)
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.pseudo_r2)
+    }
+}
@@ -2,6 +2,7 @@ use crate::glm::{Family, Link};
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
@@ -10,7 +11,7 @@ use greeners_core::bspline::BSplineBasis;
 // ─── GLMGam ────────────────────────────────────────────────────────────────────
 
 /// Result of GLM-GAM estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GamResult {
     pub params: Array1<f64>,
     /// Number of parametric (linear) terms
@@ -1,12 +1,13 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{DataFrame, Formula, InferenceType};
+use greeners_core::{DataFrame, Formula, InferenceType, MissingPolicy, RowMap};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result from Multinomial Logit regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MNLogitResult {
     /// Coefficients: (k x J-1) — one column per non-base category.
     pub params: Array2<f64>,
@@ -29,6 +30,7 @@ pub struct MNLogitResult {
     pub variable_names: Option<Vec<String>>,
     pub omitted_vars: Vec<(usize, String)>,
     _x_data: Array2<f64>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for MNLogitResult {
@@ -174,9 +176,20 @@ impl MNLogit {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<MNLogitResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, Some(var_names))
+        Self::from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    /// Fit via formula with an explicit missing-value policy.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<MNLogitResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Fit from arrays.
@@ -446,6 +459,7 @@ impl MNLogit {
             },
             omitted_vars: omitted_positioned,
             _x_data: x_use.clone(),
+            row_map: None,
         })
     }
 }
@@ -1,12 +1,13 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{DataFrame, Formula, InferenceType};
+use greeners_core::{DataFrame, Formula, InferenceType, MissingPolicy, RowMap};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result from Ordered Logit/Probit regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct OrderedResult {
     pub model_name: String,
     /// Slope coefficients (k, no intercept — absorbed by cutpoints).
@@ -29,6 +30,7 @@ pub struct OrderedResult {
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
     _x_data: Array2<f64>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for OrderedResult {
@@ -191,7 +193,16 @@ impl OrderedLogit {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<OrderedResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
+        Self::from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<OrderedResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let (y, x) = (dm.y, dm.x);
         // For ordered models, remove intercept column — absorbed by cutpoints
         let x_no_const = if formula.intercept {
             x.slice(ndarray::s![.., 1..]).to_owned()
@@ -205,7 +216,9 @@ impl OrderedLogit {
             v.extend(formula.independents.clone());
             v
         };
-        fit_ordered(&y, &x_no_const, true, Some(var_names))
+        let mut result = fit_ordered(&y, &x_no_const, true, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     pub fn fit(y: &Array1<f64>, x: &Array2<f64>) -> Result<OrderedResult, GreenersError> {
@@ -226,7 +239,16 @@ impl OrderedProbit {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<OrderedResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
+        Self::from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<OrderedResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let (y, x) = (dm.y, dm.x);
         let x_no_const = if formula.intercept {
             x.slice(ndarray::s![.., 1..]).to_owned()
         } else {
@@ -239,7 +261,9 @@ impl OrderedProbit {
             v.extend(formula.independents.clone());
             v
         };
-        fit_ordered(&y, &x_no_const, false, Some(var_names))
+        let mut result = fit_ordered(&y, &x_no_const, false, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     pub fn fit(y: &Array1<f64>, x: &Array2<f64>) -> Result<OrderedResult, GreenersError> {
@@ -636,5 +660,6 @@ fn fit_ordered(
         inference_type: InferenceType::Normal,
         variable_names,
         _x_data: x.to_owned(),
+        row_map: None,
     })
 }
@@ -1,13 +1,18 @@
 use crate::glm::{Family, GLM};
 use greeners_core::error::GreenersError;
-use greeners_core::{CovarianceType, DataFrame, Formula, InferenceType};
+use greeners_core::model::align_design;
+use greeners_core::{
+    CovarianceType, DataFrame, DesignInfo, FittedModel, Formula, InferenceType, MissingPolicy,
+    Model, RowMap,
+};
 use greeners_ols::ols::PredictionResult;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result from Poisson regression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoissonResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -30,6 +35,8 @@ pub struct PoissonResult {
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
     pub omitted_vars: Vec<(usize, String)>,
+    pub design_info: Option<DesignInfo>,
+    pub row_map: Option<RowMap>,
     _x_data: Array2<f64>,
     _y_data: Array1<f64>,
 }
@@ -116,6 +123,18 @@ impl PoissonResult {
         &self._x_data
     }
 
+    /// Predict expected counts on a new DataFrame for a model fitted with
+    /// `from_formula`, rebuilding the design learned at fit time.
+    pub fn predict_df(&self, data: &DataFrame) -> Result<Array1<f64>, GreenersError> {
+        let info = self.design_info.as_ref().ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "predict_df requires a model fitted with from_formula".into(),
+            )
+        })?;
+        let x_new = info.build_x_omitting(data, &self.omitted_vars)?;
+        Ok(self.predict_count(&x_new))
+    }
+
     /// Predict expected counts for new data.
     pub fn predict_count(&self, x_new: &Array2<f64>) -> Array1<f64> {
         let eta = x_new.dot(&self.params);
@@ -281,9 +300,24 @@ impl Poisson {
         data: &DataFrame,
         cov_type: CovarianceType,
     ) -> Result<PoissonResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, cov_type, Some(var_names))
+        Self::from_formula_with_missing(formula, data, cov_type, MissingPolicy::Drop)
+    }
+
+    /// Fit from a formula with an explicit missing-value policy; the rows
+    /// dropped are recorded in `result.row_map`.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<PoissonResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let cov_type = dm.rows.select_cov_type(cov_type);
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, cov_type, Some(var_names))?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Fit from arrays.
@@ -326,6 +360,8 @@ impl Poisson {
             inference_type: glm_result.inference_type,
             variable_names: glm_result.variable_names,
             omitted_vars: glm_result.omitted_vars,
+            design_info: None,
+            row_map: None,
             _x_data: glm_result._x_data.clone(),
             _y_data: glm_result._y_data.clone(),
         })
@@ -364,8 +400,69 @@ impl Poisson {
             inference_type: glm_result.inference_type,
             variable_names: glm_result.variable_names,
             omitted_vars: glm_result.omitted_vars,
+            design_info: None,
+            row_map: None,
             _x_data: glm_result._x_data.clone(),
             _y_data: glm_result._y_data.clone(),
         })
     }
 }
+
+impl FittedModel for PoissonResult {
+    fn model_name(&self) -> String {
+        "Poisson".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Predicted expected counts.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &self.omitted_vars)?;
+        Ok(self.predict_count(&x))
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+
+    fn aic(&self) -> Option<f64> {
+        Some(self.aic)
+    }
+
+    fn bic(&self) -> Option<f64> {
+        Some(self.bic)
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.pseudo_r2)
+    }
+}
+
+/// Fits with non-robust standard errors.
+impl Model for Poisson {
+    type Fitted = PoissonResult;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<PoissonResult, GreenersError> {
+        Poisson::fit(y, x, CovarianceType::NonRobust)
+    }
+}
@@ -2,11 +2,12 @@ use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::types::InferenceType;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result from Zero-Inflated models (ZIP/ZINB).
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ZeroInflatedResult {
     pub model_name: String,
     /// Count model coefficients (Poisson or NegBin part).
@@ -459,3 +459,79 @@ fn test_binomial_cloglog_converges() {
         assert!(res.std_errors[j].is_finite() && res.std_errors[j] > 0.0);
     }
 }
+
+// ============================================================
+// 18. HC0/HC5 and kernel HAC match OLS for the Gaussian family
+// ============================================================
+#[test]
+fn test_additional_sandwich_estimators_match_ols() {
+    use greeners_core::types::{HacBandwidth, HacKernel};
+
+    let y = Array1::from(vec![1.0, 2.1, 3.0, 3.9, 5.1, 6.0, 7.2, 7.9, 9.1, 10.0]);
+    let x = with_intercept(
+        &Array2::from_shape_vec(
+            (10, 1),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+        )
+        .unwrap(),
+    );
+    let hac = CovarianceType::Hac(HacKernel::Parzen, HacBandwidth::Fixed(3.0));
+    // OLS applies n/(n-k) to HAC estimators, the GLM sandwich does not
+    for (cov, scale) in [
+        (CovarianceType::HC0, 1.0),
+        (CovarianceType::HC5, 1.0),
+        (hac, (10.0_f64 / 8.0).sqrt()),
+    ] {
+        let glm = GLM::fit(&y, &x, Family::Gaussian, cov.clone()).unwrap();
+        let ols = OLS::fit(&y, &x, cov).unwrap();
+        for j in 0..2 {
+            assert!(
+                (glm.std_errors[j] * scale - ols.std_errors[j]).abs() < 1e-6,
+                "{} vs {}",
+                glm.std_errors[j] * scale,
+                ols.std_errors[j]
+            );
+        }
+    }
+}
+
+// ============================================================
+// 19. CR2/CR3 match OLS on the identity link; other cluster types error
+// ============================================================
+#[test]
+fn test_cluster_se_match_ols() {
+    let y = Array1::from(vec![
+        1.0, 2.4, 2.8, 4.1, 5.3, 5.7, 7.4, 7.6, 9.3, 9.8, 11.5, 11.9,
+    ]);
+    let x = with_intercept(
+        &Array2::from_shape_vec(
+            (12, 1),
+            vec![
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+            ],
+        )
+        .unwrap(),
+    );
+    let ids = vec![0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3];
+    for cov in [
+        CovarianceType::Clustered(ids.clone()),
+        CovarianceType::ClusteredTwoWay(ids.clone(), (0..12).map(|i| i % 3).collect()),
+        CovarianceType::CR2(ids.clone()),
+        CovarianceType::CR3(ids.clone()),
+    ] {
+        let glm = GLM::fit(&y, &x, Family::Gaussian, cov.clone()).unwrap();
+        let ols = OLS::fit(&y, &x, cov).unwrap();
+        for j in 0..2 {
+            assert!(
+                (glm.std_errors[j] - ols.std_errors[j]).abs() < 1e-8,
+                "{} vs {}",
+                glm.std_errors[j],
+                ols.std_errors[j]
+            );
+        }
+    }
+
+    // Unsupported types are rejected rather than reported as non-robust
+    let err = GLM::fit(&y, &x, Family::Poisson, CovarianceType::NeweyWest(2));
+    assert!(err.is_err());
+}
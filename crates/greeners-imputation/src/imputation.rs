@@ -1,12 +1,13 @@
 use greeners_core::error::GreenersError;
-use greeners_core::CovarianceType;
+use greeners_core::{CovarianceType, GreenersRng};
 use greeners_ols::ols::OLS;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of MICE imputation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MICEResult {
     /// Multiple imputed datasets: Vec of column-name -> complete column
     pub datasets: Vec<IndexMap<String, Array1<f64>>>,
@@ -28,7 +29,7 @@ impl fmt::Display for MICEResult {
 }
 
 /// Result of Bayesian Gaussian MI.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BayesGaussMIResult {
     pub datasets: Vec<IndexMap<String, Array1<f64>>>,
     pub n_imputations: usize,
@@ -55,10 +56,12 @@ impl MICE {
     /// - `data`: HashMap of column name -> Array1 (NaN = missing)
     /// - `n_imputations`: number of imputed datasets to generate
     /// - `n_iter`: number of MICE iterations per imputation
+    /// - `seed`: random seed (`None` uses the default seed)
     pub fn impute(
         data: &IndexMap<String, Array1<f64>>,
         n_imputations: usize,
         n_iter: usize,
+        seed: Option<u64>,
     ) -> Result<MICEResult, GreenersError> {
         if data.is_empty() {
             return Err(GreenersError::InvalidOperation(
@@ -88,8 +91,7 @@ impl MICE {
             .collect();
 
         let mut datasets = Vec::with_capacity(n_imputations);
-        // Simple PRNG state
-        let mut rng_state: u64 = 42;
+        let mut rng = GreenersRng::new(seed);
 
         for _imp in 0..n_imputations {
             let mut current = mat.clone();
@@ -163,12 +165,7 @@ impl MICE {
                             let x_i = x_i.slice(ndarray::s![..n_vars]).to_owned();
                             let pred = x_i.dot(&ols_res.params);
                             // Add noise from predictive distribution
-                            rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                            let u1 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                            rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                            let u2 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                            let z = (-2.0 * u1.max(1e-15).ln()).sqrt()
-                                * (2.0 * std::f64::consts::PI * u2).cos();
+                            let z = rng.normal();
                             current[[i, j]] = pred + sigma * z;
                         }
                     }
@@ -201,9 +198,12 @@ pub struct BayesGaussMI;
 
 impl BayesGaussMI {
     /// Impute missing data assuming multivariate normality.
+    ///
+    /// `seed: None` uses the default seed.
     pub fn impute(
         data: &IndexMap<String, Array1<f64>>,
         n_imputations: usize,
+        seed: Option<u64>,
     ) -> Result<BayesGaussMIResult, GreenersError> {
         if data.is_empty() {
             return Err(GreenersError::InvalidOperation(
@@ -251,7 +251,7 @@ impl BayesGaussMI {
         }
         sigma /= (n_complete - 1) as f64;
 
-        let mut rng_state: u64 = 12345;
+        let mut rng = GreenersRng::new(seed);
         let mut datasets = Vec::with_capacity(n_imputations);
 
         for _imp in 0..n_imputations {
@@ -271,12 +271,7 @@ impl BayesGaussMI {
                 if obs_idx.is_empty() {
                     // All missing: draw from marginal
                     for &j in &mis_idx {
-                        rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                        let u1 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                        rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                        let u2 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                        let z = (-2.0 * u1.max(1e-15).ln()).sqrt()
-                            * (2.0 * std::f64::consts::PI * u2).cos();
+                        let z = rng.normal();
                         current[[i, j]] = mu[j] + sigma[[j, j]].sqrt() * z;
                     }
                     continue;
@@ -323,12 +318,7 @@ impl BayesGaussMI {
 
                 // Draw from N(mu_cond, diag of sigma_cond) — simplified
                 for (a, &ja) in mis_idx.iter().enumerate() {
-                    rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                    let u1 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                    rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-                    let u2 = (rng_state >> 33) as f64 / (1u64 << 31) as f64;
-                    let z = (-2.0 * u1.max(1e-15).ln()).sqrt()
-                        * (2.0 * std::f64::consts::PI * u2).cos();
+                    let z = rng.normal();
                     current[[i, ja]] = mu_cond[a] + sigma_cond[[a, a]].max(0.0).sqrt() * z;
                 }
             }
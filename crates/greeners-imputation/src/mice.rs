@@ -18,12 +18,13 @@
 //! imputed datasets and pooled statistics.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of MICE imputation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MiceResult {
     /// Pooled imputed data (n x k), with missing values replaced
     pub imputed_data: Array2<f64>,
@@ -98,12 +99,15 @@ impl MiceChained {
     /// * `n_imputations` - Number of imputations (default 5)
     /// * `n_iterations` - Iterations per imputation (default 10)
     /// * `variable_names` - Optional variable names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         data: &Array2<f64>,
         n_imputations: Option<usize>,
         n_iterations: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<MiceResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = data.nrows();
         let k = data.ncols();
         if n < 5 || k < 2 {
@@ -167,7 +171,7 @@ impl MiceChained {
                 for j in 0..k {
                     if missing_mask[(i, j)] {
                         // Add stochastic noise for different imputations
-                        let noise = Self::rand_normal() * col_means[j].abs().max(1.0) * 0.01;
+                        let noise = rng.normal() * col_means[j].abs().max(1.0) * 0.01;
                         imputed[(i, j)] = col_means[j] + noise * (imp as f64 + 1.0) / m as f64;
                     }
                 }
@@ -234,7 +238,7 @@ impl MiceChained {
                             }
                             let pred = beta.dot(&x_i);
                             // Add stochastic noise
-                            let noise = Self::rand_normal() * sigma;
+                            let noise = rng.normal() * sigma;
                             imputed[(i, j)] = pred + noise;
                         }
                     }
@@ -310,26 +314,4 @@ impl MiceChained {
             missing_info_rate,
         })
     }
-
-    fn rand_normal() -> f64 {
-        // Box-Muller transform
-        let u1 = Self::rand_uniform().max(1e-10);
-        let u2 = Self::rand_uniform();
-        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(2468135790) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
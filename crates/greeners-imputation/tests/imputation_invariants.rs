@@ -30,7 +30,7 @@ fn test_mice_impute_invariants() {
     let data = make_missing_data();
     let n = data["x0"].len();
 
-    let r = MICE::impute(&data, 3, 5).unwrap();
+    let r = MICE::impute(&data, 3, 5, None).unwrap();
     assert_eq!(r.n_obs, n);
     assert_eq!(r.n_vars, 3);
     assert_eq!(r.n_imputations, 3);
@@ -53,7 +53,7 @@ fn test_bayes_gauss_mi_invariants() {
     let data = make_missing_data();
     let n = data["x0"].len();
 
-    let r = BayesGaussMI::impute(&data, 4).unwrap();
+    let r = BayesGaussMI::impute(&data, 4, None).unwrap();
     assert_eq!(r.n_obs, n);
     assert_eq!(r.n_vars, 3);
     assert_eq!(r.n_imputations, 4);
@@ -73,11 +73,11 @@ fn test_bayes_gauss_mi_invariants() {
 #[test]
 fn test_imputation_input_validation() {
     let empty: IndexMap<String, Array1<f64>> = IndexMap::new();
-    assert!(MICE::impute(&empty, 1, 1).is_err());
-    assert!(BayesGaussMI::impute(&empty, 1).is_err());
+    assert!(MICE::impute(&empty, 1, 1, None).is_err());
+    assert!(BayesGaussMI::impute(&empty, 1, None).is_err());
 
     let mut bad = IndexMap::new();
     bad.insert("a".into(), Array1::from_vec(vec![1.0, 2.0, 3.0]));
     bad.insert("b".into(), Array1::from_vec(vec![1.0, 2.0]));
-    assert!(MICE::impute(&bad, 1, 1).is_err());
+    assert!(MICE::impute(&bad, 1, 1, None).is_err());
 }
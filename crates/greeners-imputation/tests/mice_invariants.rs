@@ -28,7 +28,7 @@ fn test_mice_chained_invariants() {
     let data = make_missing_matrix();
     let names = vec!["x0".into(), "x1".into(), "x2".into()];
 
-    let r = MiceChained::fit(&data, Some(3), Some(5), Some(names)).unwrap();
+    let r = MiceChained::fit(&data, Some(3), Some(5), Some(names), None).unwrap();
 
     assert_eq!(r.n_obs, 20);
     assert_eq!(r.n_vars, 3);
@@ -59,11 +59,11 @@ fn test_mice_chained_invariants() {
 fn test_mice_chained_parameters() {
     let data = make_missing_matrix();
 
-    let r1 = MiceChained::fit(&data, Some(2), Some(3), None).unwrap();
+    let r1 = MiceChained::fit(&data, Some(2), Some(3), None, None).unwrap();
     assert_eq!(r1.n_imputations, 2);
     assert_eq!(r1.n_iterations, 3);
 
-    let r2 = MiceChained::fit(&data, Some(5), Some(10), None).unwrap();
+    let r2 = MiceChained::fit(&data, Some(5), Some(10), None, None).unwrap();
     assert_eq!(r2.n_imputations, 5);
     assert_eq!(r2.n_iterations, 10);
 
@@ -76,18 +76,18 @@ fn test_mice_chained_parameters() {
 #[test]
 fn test_mice_chained_input_validation() {
     let complete = Array2::from_shape_vec((10, 2), (1..=20).map(|v| v as f64).collect()).unwrap();
-    assert!(MiceChained::fit(&complete, None, None, None).is_err());
+    assert!(MiceChained::fit(&complete, None, None, None, None).is_err());
 
     let small = Array2::from_shape_vec((4, 2), (1..=8).map(|v| v as f64).collect()).unwrap();
-    assert!(MiceChained::fit(&small, None, None, None).is_err());
+    assert!(MiceChained::fit(&small, None, None, None, None).is_err());
 
     let one_col_2d = Array2::from_shape_vec((20, 1), (1..=20).map(|v| v as f64).collect()).unwrap();
-    assert!(MiceChained::fit(&one_col_2d, None, None, None).is_err());
+    assert!(MiceChained::fit(&one_col_2d, None, None, None, None).is_err());
 
     // Fully missing column is rejected
     let mut fully_missing = make_missing_matrix();
     for i in 0..20 {
         fully_missing[[i, 0]] = f64::NAN;
     }
-    assert!(MiceChained::fit(&fully_missing, None, None, None).is_err());
+    assert!(MiceChained::fit(&fully_missing, None, None, None, None).is_err());
 }
@@ -18,12 +18,14 @@
 //!
 //! This is a lightweight implementation suitable for small datasets.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use rand_distr::{Distribution, Gamma};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single BART tree node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BartNode {
     feature: usize,
     threshold: f64,
@@ -37,7 +39,7 @@ struct BartNode {
 }
 
 /// Result of BART estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BartResult {
     /// In-sample fitted values (posterior mean)
     pub fitted: Array1<f64>,
@@ -65,6 +67,43 @@ pub struct BartResult {
     pub variable_names: Vec<String>,
     /// Variable inclusion proportions
     pub variable_inclusion: Array1<f64>,
+    /// Sum-of-trees ensemble after each post-burn-in iteration (standardized scale)
+    posterior_trees: Vec<Vec<BartNode>>,
+    /// Mean and standard deviation used to standardize y
+    y_scale: (f64, f64),
+}
+
+impl BartResult {
+    /// Posterior mean prediction for new observations.
+    ///
+    /// Averages the sum-of-trees fit over the retained MCMC iterations, so
+    /// predicting on the training features reproduces `fitted`.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "BART: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let (y_mean, y_std) = self.y_scale;
+        let n_post = self.posterior_trees.len();
+        let mut pred = Array1::zeros(x.nrows());
+        if n_post == 0 {
+            return Ok(pred.mapv(|v: f64| v * y_std + y_mean));
+        }
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            let total: f64 = self
+                .posterior_trees
+                .iter()
+                .flat_map(|trees| trees.iter())
+                .map(|tree| BART::predict_tree(tree, &row))
+                .sum();
+            pred[i] = total / n_post as f64 * y_std + y_mean;
+        }
+        Ok(pred)
+    }
 }
 
 impl fmt::Display for BartResult {
@@ -129,6 +168,8 @@ impl BART {
     /// * `n_iter` - MCMC iterations (default 100)
     /// * `burn_in` - Burn-in iterations (default 20)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -137,7 +178,9 @@ impl BART {
         n_iter: Option<usize>,
         burn_in: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<BartResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 10 || k == 0 {
@@ -176,6 +219,7 @@ impl BART {
 
         // MCMC
         let mut sigma2_samples: Vec<f64> = Vec::new();
+        let mut posterior_trees: Vec<Vec<BartNode>> = Vec::new();
         let mut fitted_sum: Array1<f64> = Array1::zeros(n);
 
         for iter in 0..iterations {
@@ -193,7 +237,7 @@ impl BART {
 
                 // Update tree: grow/prune/change (simplified: just re-grow)
                 let (new_tree, splits_used) =
-                    Self::grow_tree(&residual, x, n, k, depth, sigma2, sigma_mu);
+                    Self::grow_tree(&residual, x, n, k, depth, sigma2, sigma_mu, &mut rng);
                 trees[tree_idx] = new_tree;
 
                 for &f in &splits_used {
@@ -215,7 +259,10 @@ impl BART {
             // Inverse-Gamma posterior: shape = n/2 + 1, scale = sse/2
             let shape = n as f64 / 2.0 + 1.0;
             let scale = sse / 2.0;
-            sigma2 = scale / shape; // posterior mean
+            sigma2 = match Gamma::new(shape, 1.0) {
+                Ok(gamma) => scale / gamma.sample(&mut rng).max(1e-12),
+                Err(_) => scale / shape,
+            };
 
             // Record after burn-in
             if iter >= burn {
@@ -227,6 +274,7 @@ impl BART {
                     }
                     fitted_sum[i] += pred;
                 }
+                posterior_trees.push(trees.clone());
             }
         }
 
@@ -281,6 +329,8 @@ impl BART {
             n_features: k,
             variable_names: names,
             variable_inclusion,
+            posterior_trees,
+            y_scale: (y_mean, y_std),
         })
     }
 
@@ -296,6 +346,7 @@ impl BART {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn grow_tree(
         residual: &Array1<f64>,
         x: &Array2<f64>,
@@ -304,6 +355,7 @@ impl BART {
         max_depth: usize,
         sigma2: f64,
         sigma_mu: f64,
+        rng: &mut GreenersRng,
     ) -> (BartNode, Vec<usize>) {
         let indices: Vec<usize> = (0..n).collect();
         let mut splits_used = Vec::new();
@@ -317,6 +369,7 @@ impl BART {
             sigma2,
             sigma_mu,
             &mut splits_used,
+            rng,
         );
         (tree, splits_used)
     }
@@ -332,13 +385,14 @@ impl BART {
         sigma2: f64,
         sigma_mu: f64,
         splits_used: &mut Vec<usize>,
+        rng: &mut GreenersRng,
     ) -> BartNode {
         let n = indices.len();
-        // Leaf value: posterior mean with shrinkage
+        // Leaf value: draw from the conjugate posterior
         // mu | data ~ N(sum(r)/(n + sigma2/sigma_mu^2), sigma2/(n + sigma2/sigma_mu^2))
         let sum_r: f64 = indices.iter().map(|&i| residual[i]).sum();
         let shrink = n as f64 + sigma2 / (sigma_mu * sigma_mu);
-        let leaf_mu = sum_r / shrink;
+        let leaf_mu = sum_r / shrink + (sigma2 / shrink).sqrt() * rng.normal();
         let _leaf_n = n;
 
         if n < 5 || depth >= max_depth {
@@ -406,6 +460,7 @@ impl BART {
                 sigma2,
                 sigma_mu,
                 splits_used,
+                rng,
             ))),
             right: Some(Box::new(Self::build_bart_tree(
                 residual,
@@ -417,6 +472,7 @@ impl BART {
                 sigma2,
                 sigma_mu,
                 splits_used,
+                rng,
             ))),
             leaf_mu,
             _leaf_n,
@@ -0,0 +1,410 @@
+//! Shared pieces of the classification tree ensembles.
+//!
+//! Class labels are arbitrary finite `f64` values. The distinct labels, sorted
+//! in increasing order, are the classes, and every probability matrix has one
+//! column per class in that order: column `j` is `P(y = classes[j] | x)`. For
+//! 0/1 labels column 1 is `P(y = 1 | x)`, which can be passed directly to
+//! `BinaryDiagnostics::roc` and `BinaryDiagnostics::hosmer_lemeshow`.
+//!
+//! Used by [`RandomForestClassifier`](crate::random_forest::RandomForestClassifier),
+//! [`QrfClassifier`](crate::qrf::QrfClassifier),
+//! [`GradientBoostingClassifier`](crate::gradient_boosting::GradientBoostingClassifier)
+//! and [`XGBoostClassifier`](crate::xgboost::XGBoostClassifier).
+
+use greeners_core::{GreenersError, GreenersRng};
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Impurity measure used to choose classification splits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SplitCriterion {
+    /// Gini impurity `1 - sum p_k^2`.
+    #[default]
+    Gini,
+    /// Entropy `-sum p_k ln p_k`.
+    Entropy,
+}
+
+impl SplitCriterion {
+    /// Impurity of a node with (weighted) class counts `counts`.
+    pub fn impurity(self, counts: &[f64]) -> f64 {
+        let total: f64 = counts.iter().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        match self {
+            SplitCriterion::Gini => 1.0 - counts.iter().map(|c| (c / total).powi(2)).sum::<f64>(),
+            SplitCriterion::Entropy => -counts
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|c| {
+                    let p = c / total;
+                    p * p.ln()
+                })
+                .sum::<f64>(),
+        }
+    }
+}
+
+impl fmt::Display for SplitCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitCriterion::Gini => write!(f, "Gini"),
+            SplitCriterion::Entropy => write!(f, "Entropy"),
+        }
+    }
+}
+
+/// Observation weights by class, e.g. to rebalance rare outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClassWeight {
+    /// Weights inversely proportional to class frequencies: `n / (K n_k)`.
+    Balanced,
+    /// One non-negative weight per class, in the order of the sorted labels.
+    Custom(Vec<f64>),
+}
+
+/// Loss minimized by the boosting classifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoostingObjective {
+    /// Binary log-loss: one tree per round on the log-odds of the second class.
+    LogLoss,
+    /// Multinomial log-loss: one tree per class and round on softmax scores.
+    Softmax,
+}
+
+impl fmt::Display for BoostingObjective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoostingObjective::LogLoss => write!(f, "Log-loss"),
+            BoostingObjective::Softmax => write!(f, "Softmax"),
+        }
+    }
+}
+
+/// Labels mapped to class codes, with per-observation weights.
+pub(crate) struct EncodedClasses {
+    /// Sorted distinct labels.
+    pub classes: Vec<f64>,
+    /// Class code of each observation (index into `classes`).
+    pub codes: Vec<usize>,
+    /// Observation weights implied by the class weighting (all 1 without it).
+    pub weights: Vec<f64>,
+}
+
+/// Encode `y` into class codes and observation weights.
+pub(crate) fn encode_classes(
+    y: &Array1<f64>,
+    class_weight: Option<&ClassWeight>,
+    model: &str,
+) -> Result<EncodedClasses, GreenersError> {
+    if y.iter().any(|v| !v.is_finite()) {
+        return Err(GreenersError::InvalidOperation(format!(
+            "{}: class labels must be finite",
+            model
+        )));
+    }
+    let mut classes: Vec<f64> = y.to_vec();
+    classes.sort_by(|a, b| a.total_cmp(b));
+    classes.dedup();
+    if classes.len() < 2 {
+        return Err(GreenersError::InvalidOperation(format!(
+            "{}: y must contain at least two classes",
+            model
+        )));
+    }
+    let codes: Vec<usize> = y
+        .iter()
+        .map(|v| classes.partition_point(|c| c < v))
+        .collect();
+
+    let n_classes = classes.len();
+    let class_w = match class_weight {
+        None => vec![1.0; n_classes],
+        Some(ClassWeight::Balanced) => {
+            let mut counts = vec![0.0; n_classes];
+            for &c in &codes {
+                counts[c] += 1.0;
+            }
+            let n = codes.len() as f64;
+            counts
+                .iter()
+                .map(|&n_k| n / (n_classes as f64 * n_k))
+                .collect()
+        }
+        Some(ClassWeight::Custom(w)) => {
+            if w.len() != n_classes {
+                return Err(GreenersError::ShapeMismatch(format!(
+                    "{}: {} class weights for {} classes",
+                    model,
+                    w.len(),
+                    n_classes
+                )));
+            }
+            if w.iter().any(|v| !v.is_finite() || *v < 0.0) || w.iter().all(|&v| v == 0.0) {
+                return Err(GreenersError::InvalidOperation(format!(
+                    "{}: class weights must be non-negative and not all zero",
+                    model
+                )));
+            }
+            w.clone()
+        }
+    };
+    let weights = codes.iter().map(|&c| class_w[c]).collect();
+
+    Ok(EncodedClasses {
+        classes,
+        codes,
+        weights,
+    })
+}
+
+/// Label of the most probable class in each row of `proba`.
+pub(crate) fn predict_labels(proba: &Array2<f64>, classes: &[f64]) -> Array1<f64> {
+    proba
+        .rows()
+        .into_iter()
+        .map(|row| classes[argmax(row.iter().copied())])
+        .collect()
+}
+
+/// Share of observations whose most probable class is the observed one.
+pub(crate) fn accuracy(codes: &[usize], proba: &Array2<f64>) -> f64 {
+    let hits = codes
+        .iter()
+        .zip(proba.rows())
+        .filter(|(&c, row)| argmax(row.iter().copied()) == c)
+        .count();
+    hits as f64 / codes.len().max(1) as f64
+}
+
+/// Mean negative log-likelihood of the observed classes.
+pub(crate) fn log_loss(codes: &[usize], proba: &Array2<f64>) -> f64 {
+    let total: f64 = codes
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| -proba[(i, c)].clamp(1e-15, 1.0).ln())
+        .sum();
+    total / codes.len().max(1) as f64
+}
+
+/// Class probabilities from boosting scores: the logistic function of the
+/// single score for [`BoostingObjective::LogLoss`], the softmax otherwise.
+pub(crate) fn scores_to_proba(scores: &[f64], objective: BoostingObjective) -> Vec<f64> {
+    match objective {
+        BoostingObjective::LogLoss => {
+            let p = 1.0 / (1.0 + (-scores[0]).exp());
+            vec![1.0 - p, p]
+        }
+        BoostingObjective::Softmax => {
+            let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let exp: Vec<f64> = scores.iter().map(|s| (s - max).exp()).collect();
+            let sum: f64 = exp.iter().sum();
+            exp.iter().map(|e| e / sum).collect()
+        }
+    }
+}
+
+/// Boosting objective and initial scores (weighted log prior odds or log
+/// priors) for the encoded classes.
+pub(crate) fn initial_scores(classes: &EncodedClasses) -> (BoostingObjective, Vec<f64>) {
+    let n_classes = classes.classes.len();
+    let mut prior = vec![0.0; n_classes];
+    for (&c, &w) in classes.codes.iter().zip(&classes.weights) {
+        prior[c] += w;
+    }
+    let total: f64 = prior.iter().sum();
+    let prior: Vec<f64> = prior
+        .iter()
+        .map(|p| (p / total).clamp(1e-6, 1.0 - 1e-6))
+        .collect();
+    if n_classes == 2 {
+        (BoostingObjective::LogLoss, vec![(prior[1] / prior[0]).ln()])
+    } else {
+        (
+            BoostingObjective::Softmax,
+            prior.iter().map(|p| p.ln()).collect(),
+        )
+    }
+}
+
+fn argmax(values: impl Iterator<Item = f64>) -> usize {
+    let mut best = 0;
+    let mut best_val = f64::NEG_INFINITY;
+    for (j, v) in values.enumerate() {
+        if v > best_val {
+            best_val = v;
+            best = j;
+        }
+    }
+    best
+}
+
+/// A classification tree node. Every node keeps the weighted class counts
+/// of the training observations that reached it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ClassNode {
+    feature: usize,
+    threshold: f64,
+    left: Option<Box<ClassNode>>,
+    right: Option<Box<ClassNode>>,
+    counts: Vec<f64>,
+    is_leaf: bool,
+}
+
+impl ClassNode {
+    /// Weighted class counts of the leaf reached by `x`.
+    pub fn leaf_counts(&self, x: &Array1<f64>) -> &[f64] {
+        if self.is_leaf {
+            return &self.counts;
+        }
+        if x[self.feature] <= self.threshold {
+            if let Some(ref left) = self.left {
+                return left.leaf_counts(x);
+            }
+        } else if let Some(ref right) = self.right {
+            return right.leaf_counts(x);
+        }
+        &self.counts
+    }
+
+    /// Class frequencies of the leaf reached by `x`.
+    pub fn leaf_proba(&self, x: &Array1<f64>) -> Vec<f64> {
+        let counts = self.leaf_counts(x);
+        let total: f64 = counts.iter().sum();
+        if total <= 0.0 {
+            return vec![1.0 / counts.len() as f64; counts.len()];
+        }
+        counts.iter().map(|c| c / total).collect()
+    }
+}
+
+/// Data and settings shared by every node of a classification tree.
+pub(crate) struct ClassTreeSpec<'a> {
+    pub x: &'a Array2<f64>,
+    pub codes: &'a [usize],
+    pub weights: &'a [f64],
+    pub n_classes: usize,
+    pub criterion: SplitCriterion,
+    pub max_depth: usize,
+    pub mtry: usize,
+}
+
+impl ClassTreeSpec<'_> {
+    /// Grow a tree on `indices` (duplicates allowed, as in a bootstrap
+    /// sample), adding each split's impurity decrease to `importance`.
+    pub fn build(
+        &self,
+        indices: &[usize],
+        depth: usize,
+        importance: &mut Array1<f64>,
+        rng: &mut GreenersRng,
+    ) -> ClassNode {
+        let counts = self.class_counts(indices);
+        let leaf = |counts: Vec<f64>| ClassNode {
+            feature: 0,
+            threshold: 0.0,
+            left: None,
+            right: None,
+            counts,
+            is_leaf: true,
+        };
+
+        let n_nonzero = counts.iter().filter(|&&c| c > 0.0).count();
+        if indices.len() < 5 || depth >= self.max_depth || n_nonzero < 2 {
+            return leaf(counts);
+        }
+
+        let k = self.x.ncols();
+        let (best_feature, best_threshold, best_gain) = self.find_best_split(indices, rng);
+        if best_gain < 1e-10 || best_feature >= k {
+            return leaf(counts);
+        }
+
+        let (left_idx, right_idx): (Vec<usize>, Vec<usize>) = indices
+            .iter()
+            .partition(|&&i| self.x[(i, best_feature)] <= best_threshold);
+        if left_idx.is_empty() || right_idx.is_empty() {
+            return leaf(counts);
+        }
+
+        importance[best_feature] += best_gain;
+
+        ClassNode {
+            feature: best_feature,
+            threshold: best_threshold,
+            left: Some(Box::new(self.build(&left_idx, depth + 1, importance, rng))),
+            right: Some(Box::new(self.build(&right_idx, depth + 1, importance, rng))),
+            counts,
+            is_leaf: false,
+        }
+    }
+
+    fn class_counts(&self, indices: &[usize]) -> Vec<f64> {
+        let mut counts = vec![0.0; self.n_classes];
+        for &i in indices {
+            counts[self.codes[i]] += self.weights[i];
+        }
+        counts
+    }
+
+    /// Best split over `mtry` random features, by weighted impurity decrease
+    /// (normalized by the node weight).
+    fn find_best_split(&self, indices: &[usize], rng: &mut GreenersRng) -> (usize, f64, f64) {
+        let k = self.x.ncols();
+        let parent = self.class_counts(indices);
+        let parent_w: f64 = parent.iter().sum();
+        if parent_w <= 0.0 {
+            return (k, 0.0, 0.0);
+        }
+        let parent_impurity = self.criterion.impurity(&parent);
+
+        let mut features: Vec<usize> = (0..k).collect();
+        rng.shuffle(&mut features);
+        let features = &features[..self.mtry.min(features.len())];
+
+        let mut best_feature = k;
+        let mut best_threshold = 0.0;
+        let mut best_gain = 0.0;
+
+        for &feat in features {
+            let mut values: Vec<f64> = indices.iter().map(|&i| self.x[(i, feat)]).collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            if values.len() < 2 {
+                continue;
+            }
+
+            let n_thresh = 5.min(values.len() - 1);
+            for t in 0..n_thresh {
+                let idx = (t + 1) * values.len() / (n_thresh + 1);
+                let threshold = values[idx];
+
+                let mut left = vec![0.0; self.n_classes];
+                for &i in indices {
+                    if self.x[(i, feat)] <= threshold {
+                        left[self.codes[i]] += self.weights[i];
+                    }
+                }
+                let right: Vec<f64> = parent.iter().zip(&left).map(|(p, l)| p - l).collect();
+                let left_w: f64 = left.iter().sum();
+                let right_w: f64 = right.iter().sum();
+                if left_w <= 0.0 || right_w <= 0.0 {
+                    continue;
+                }
+
+                let gain = parent_impurity
+                    - (left_w * self.criterion.impurity(&left)
+                        + right_w * self.criterion.impurity(&right))
+                        / parent_w;
+                if gain > best_gain {
+                    best_gain = gain;
+                    best_feature = feat;
+                    best_threshold = threshold;
+                }
+            }
+        }
+
+        (best_feature, best_threshold, best_gain)
+    }
+}
@@ -18,11 +18,12 @@
 
 use greeners_core::GreenersError;
 use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt;
 
 /// Result of DBSCAN clustering.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DbscanResult {
     /// Cluster assignments (n), -1 = noise, 0..k-1 = cluster
     pub labels: Vec<i64>,
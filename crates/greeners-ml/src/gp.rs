@@ -21,10 +21,11 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Gaussian Process Regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GpResult {
     /// Predicted mean at training points
     pub fitted: Array1<f64>,
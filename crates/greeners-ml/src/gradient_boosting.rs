@@ -13,13 +13,20 @@
 //!   - Max depth control per tree
 //!   - Subsample ratio for stochastic GBM
 //!   - Feature importance via impurity decrease accumulation
+//!
+//! [`GradientBoostingClassifier`] boosts the log-odds (binary log-loss) or
+//! one score per class (softmax) with regression trees fit to the gradient
+//! of the log-loss, and sets each leaf to a one-step Newton update
+//! (Friedman 2001, Algorithm 6).
 
-use greeners_core::GreenersError;
+use crate::classification::{self, BoostingObjective, ClassWeight};
+use greeners_core::{FittedModel, GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single decision tree node (shallow, for weak learners).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct GbTreeNode {
     feature: usize,
     threshold: f64,
@@ -30,7 +37,7 @@ struct GbTreeNode {
 }
 
 /// Result of Gradient Boosting estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GradientBoostingResult {
     /// In-sample fitted values
     pub fitted: Array1<f64>,
@@ -54,6 +61,52 @@ pub struct GradientBoostingResult {
     pub n_features: usize,
     /// Variable names
     pub variable_names: Vec<String>,
+    /// Fitted trees, in boosting order
+    trees: Vec<GbTreeNode>,
+}
+
+impl GradientBoostingResult {
+    /// Predictions for new observations using all trees.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "GradientBoosting: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut pred = Array1::from_elem(x.nrows(), self.init_value);
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            for tree in &self.trees {
+                pred[i] += self.learning_rate * GradientBoosting::predict_single(tree, &row);
+            }
+        }
+        Ok(pred)
+    }
+
+    /// Predictions after each boosting iteration: row `m` holds the
+    /// predictions of the first `m + 1` trees (`n_trees x n` array), e.g. to
+    /// choose the number of iterations on a validation sample.
+    pub fn staged_predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "GradientBoosting: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut current = Array1::from_elem(x.nrows(), self.init_value);
+        let mut staged = Array2::zeros((self.trees.len(), x.nrows()));
+        for (m, tree) in self.trees.iter().enumerate() {
+            for (i, row) in x.rows().into_iter().enumerate() {
+                current[i] +=
+                    self.learning_rate * GradientBoosting::predict_single(tree, &row.to_owned());
+            }
+            staged.row_mut(m).assign(&current);
+        }
+        Ok(staged)
+    }
 }
 
 impl fmt::Display for GradientBoostingResult {
@@ -107,6 +160,8 @@ impl GradientBoosting {
     /// * `max_depth` - Max depth per tree (default 3)
     /// * `subsample` - Fraction of observations per tree (default 1.0)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -115,7 +170,9 @@ impl GradientBoosting {
         max_depth: Option<usize>,
         subsample: Option<f64>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<GradientBoostingResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 5 || k == 0 {
@@ -149,7 +206,7 @@ impl GradientBoosting {
             let n_sub = (n as f64 * sub).round() as usize;
             let n_sub = n_sub.max(5).min(n);
             let indices: Vec<usize> = if sub < 1.0 {
-                (0..n_sub).map(|_| Self::rand_int(n)).collect()
+                (0..n_sub).map(|_| rng.index(n)).collect()
             } else {
                 (0..n).collect()
             };
@@ -197,6 +254,7 @@ impl GradientBoosting {
             n_obs: n,
             n_features: k,
             variable_names: names,
+            trees,
         })
     }
 
@@ -378,25 +436,376 @@ impl GradientBoosting {
         tree.value
     }
 
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
+    /// Replace the node values of `tree` by the Newton step
+    /// `scale * sum(gradient) / sum(hessian)` over the observations in
+    /// `indices` that reach each node.
+    fn newton_leaves(
+        tree: &mut GbTreeNode,
+        x: &Array2<f64>,
+        indices: &[usize],
+        gradient: &Array1<f64>,
+        hessian: &[f64],
+        scale: f64,
+    ) {
+        let g: f64 = indices.iter().map(|&i| gradient[i]).sum();
+        let h: f64 = indices.iter().map(|&i| hessian[i]).sum();
+        tree.value = if h > 1e-12 { scale * g / h } else { 0.0 };
+        if tree.is_leaf {
+            return;
+        }
+        let (left_idx, right_idx): (Vec<usize>, Vec<usize>) = indices
+            .iter()
+            .partition(|&&i| x[(i, tree.feature)] <= tree.threshold);
+        if let Some(ref mut left) = tree.left {
+            Self::newton_leaves(left, x, &left_idx, gradient, hessian, scale);
+        }
+        if let Some(ref mut right) = tree.right {
+            Self::newton_leaves(right, x, &right_idx, gradient, hessian, scale);
+        }
+    }
+}
+
+impl FittedModel for GradientBoostingResult {
+    fn model_name(&self) -> String {
+        "GradientBoosting".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        GradientBoostingResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
+
+/// Result of Gradient Boosting classification.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GradientBoostingClassifierResult {
+    /// Sorted class labels; column `j` of the probability matrices is `P(y = classes[j])`
+    pub classes: Vec<f64>,
+    /// In-sample class probabilities (n x n_classes)
+    pub fitted_proba: Array2<f64>,
+    /// Initial scores (log prior odds, or log priors for softmax)
+    pub init_scores: Vec<f64>,
+    /// Boosting objective (log-loss for two classes, softmax otherwise)
+    pub objective: BoostingObjective,
+    /// Learning rate (shrinkage)
+    pub learning_rate: f64,
+    /// Number of boosting iterations
+    pub n_trees: usize,
+    /// Max depth per tree
+    pub max_depth: usize,
+    /// Feature importance (cumulative impurity decrease)
+    pub feature_importance: Array1<f64>,
+    /// In-sample accuracy
+    pub accuracy: f64,
+    /// In-sample log-loss
+    pub log_loss: f64,
+    /// Class weighting applied to the loss
+    pub class_weight: Option<ClassWeight>,
+    /// Number of observations
+    pub n_obs: usize,
+    /// Number of features
+    pub n_features: usize,
+    /// Variable names
+    pub variable_names: Vec<String>,
+    /// Fitted trees: one per score and iteration, in boosting order
+    trees: Vec<Vec<GbTreeNode>>,
+}
+
+impl GradientBoostingClassifierResult {
+    /// Class probabilities for new observations (n x n_classes).
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        let mut staged = self.staged_predict_proba(x)?;
+        Ok(staged
+            .pop()
+            .unwrap_or_else(|| self.proba_from_scores(&self.initial(x.nrows()))))
+    }
+
+    /// Most probable class label for new observations.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let proba = self.predict_proba(x)?;
+        Ok(classification::predict_labels(&proba, &self.classes))
+    }
+
+    /// Class probabilities after each boosting iteration: element `m` holds
+    /// the probabilities given by the first `m + 1` iterations.
+    pub fn staged_predict_proba(&self, x: &Array2<f64>) -> Result<Vec<Array2<f64>>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "GradientBoostingClassifier: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut scores = self.initial(x.nrows());
+        let mut staged = Vec::with_capacity(self.trees.len());
+        for round in &self.trees {
+            for (i, row) in x.rows().into_iter().enumerate() {
+                let row = row.to_owned();
+                for (j, tree) in round.iter().enumerate() {
+                    scores[(i, j)] +=
+                        self.learning_rate * GradientBoosting::predict_single(tree, &row);
+                }
+            }
+            staged.push(self.proba_from_scores(&scores));
+        }
+        Ok(staged)
+    }
+
+    fn initial(&self, n: usize) -> Array2<f64> {
+        let mut scores = Array2::zeros((n, self.init_scores.len()));
+        for mut row in scores.rows_mut() {
+            row.assign(&Array1::from(self.init_scores.clone()));
+        }
+        scores
+    }
+
+    fn proba_from_scores(&self, scores: &Array2<f64>) -> Array2<f64> {
+        let mut proba = Array2::zeros((scores.nrows(), self.classes.len()));
+        for (i, row) in scores.rows().into_iter().enumerate() {
+            let p = classification::scores_to_proba(&row.to_vec(), self.objective);
+            proba.row_mut(i).assign(&Array1::from(p));
+        }
+        proba
+    }
+}
+
+impl fmt::Display for GradientBoostingClassifierResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " Gradient Boosting Classification ")?;
+        writeln!(f, "Friedman (2001) — sequential additive trees")?;
+        writeln!(f, "{:<20} {:>12}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>12}", "Features:", self.n_features)?;
+        writeln!(f, "{:<20} {:>12}", "Classes:", self.classes.len())?;
+        writeln!(f, "{:<20} {:>12}", "Objective:", self.objective)?;
+        writeln!(f, "{:<20} {:>12}", "Trees:", self.n_trees)?;
+        writeln!(f, "{:<20} {:>12}", "Max depth:", self.max_depth)?;
+        writeln!(f, "{:<20} {:>12.6}", "Learning rate:", self.learning_rate)?;
+        writeln!(f, "{:<20} {:>12.6}", "In-sample accuracy:", self.accuracy)?;
+        writeln!(f, "{:<20} {:>12.6}", "Log-loss:", self.log_loss)?;
+
+        // Feature importance
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(f, "  Feature importance:")?;
+        let total: f64 = self.feature_importance.sum().max(1e-10);
+        let mut imp_vec: Vec<(String, f64, f64)> = self
+            .variable_names
+            .iter()
+            .zip(self.feature_importance.iter())
+            .map(|(name, &imp)| (name.clone(), imp, imp / total * 100.0))
+            .collect();
+        imp_vec.sort_by(|a, b| b.1.total_cmp(&a.1));
+        writeln!(
+            f,
+            "  {:<14} {:>12} {:>10}",
+            "Variable", "Importance", "% Total"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (name, imp, pct) in imp_vec {
+            writeln!(f, "  {:<14} {:>12.6} {:>9.4}%", name, imp, pct)?;
         }
-        (Self::rand_uniform() * n as f64) as usize
+
+        write!(f, "{:=^78}", "")
     }
+}
+
+pub struct GradientBoostingClassifier;
 
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1112223334) };
+impl GradientBoostingClassifier {
+    /// Estimate Gradient Boosting classification.
+    ///
+    /// Two classes use the binary log-loss, more classes the softmax
+    /// (multinomial) log-loss.
+    ///
+    /// # Arguments
+    /// * `y` - Class labels (n); any finite values, at least two distinct
+    /// * `x` - Features (n x k)
+    /// * `n_trees` - Number of boosting iterations
+    /// * `learning_rate` - Shrinkage parameter (default 0.1)
+    /// * `max_depth` - Max depth per tree (default 3)
+    /// * `subsample` - Fraction of observations per tree (default 1.0)
+    /// * `class_weight` - Optional class weighting of the loss
+    /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_trees: usize,
+        learning_rate: Option<f64>,
+        max_depth: Option<usize>,
+        subsample: Option<f64>,
+        class_weight: Option<ClassWeight>,
+        variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
+    ) -> Result<GradientBoostingClassifierResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
+        let n = y.len();
+        let k = x.ncols();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "GradientBoostingClassifier: x and y dimension mismatch".into(),
+            ));
         }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
+        if n < 5 || k == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "GradientBoostingClassifier: too few observations or features".into(),
+            ));
+        }
+        if n_trees == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "GradientBoostingClassifier: n_trees must be >= 1".into(),
+            ));
+        }
+        let encoded =
+            classification::encode_classes(y, class_weight.as_ref(), "GradientBoostingClassifier")?;
+        let n_classes = encoded.classes.len();
+
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|i| format!("x{}", i)).collect());
+        let lr = learning_rate.unwrap_or(0.1);
+        let depth = max_depth.unwrap_or(3);
+        let sub = subsample.unwrap_or(1.0).clamp(0.1, 1.0);
+
+        let (objective, init_scores) = classification::initial_scores(&encoded);
+        let n_scores = init_scores.len();
+        // Newton step scaling for the K-class softmax (Friedman 2001, eq. 32)
+        let newton_scale = match objective {
+            BoostingObjective::LogLoss => 1.0,
+            BoostingObjective::Softmax => (n_classes as f64 - 1.0) / n_classes as f64,
+        };
+        let mut scores = Array2::zeros((n, n_scores));
+        for mut row in scores.rows_mut() {
+            row.assign(&Array1::from(init_scores.clone()));
+        }
+        let mut feature_importance = Array1::zeros(k);
+        let mut trees: Vec<Vec<GbTreeNode>> = Vec::with_capacity(n_trees);
+
+        for _ in 0..n_trees {
+            let proba: Vec<Vec<f64>> = scores
+                .rows()
+                .into_iter()
+                .map(|row| classification::scores_to_proba(&row.to_vec(), objective))
+                .collect();
+
+            // Subsample
+            let n_sub = (n as f64 * sub).round() as usize;
+            let n_sub = n_sub.max(5).min(n);
+            let indices: Vec<usize> = if sub < 1.0 {
+                (0..n_sub).map(|_| rng.index(n)).collect()
+            } else {
+                (0..n).collect()
+            };
+
+            let mut round = Vec::with_capacity(n_scores);
+            for j in 0..n_scores {
+                // Score j models the class with code `class`
+                let class = if n_scores == 1 { 1 } else { j };
+                // Negative gradient of the weighted log-loss and its Hessian
+                let mut gradient = Array1::zeros(n);
+                let mut hessian = vec![0.0; n];
+                for i in 0..n {
+                    let target = if encoded.codes[i] == class { 1.0 } else { 0.0 };
+                    let p = proba[i][class];
+                    gradient[i] = encoded.weights[i] * (target - p);
+                    hessian[i] = encoded.weights[i] * p * (1.0 - p);
+                }
+
+                let mut tree = GradientBoosting::build_tree(
+                    &gradient,
+                    x,
+                    &indices,
+                    depth,
+                    k,
+                    0,
+                    &mut feature_importance,
+                );
+                GradientBoosting::newton_leaves(
+                    &mut tree,
+                    x,
+                    &indices,
+                    &gradient,
+                    &hessian,
+                    newton_scale,
+                );
+
+                for i in 0..n {
+                    scores[(i, j)] +=
+                        lr * GradientBoosting::predict_single(&tree, &x.row(i).to_owned());
+                }
+                round.push(tree);
+            }
+            trees.push(round);
+        }
+
+        let mut fitted_proba = Array2::zeros((n, n_classes));
+        for (i, row) in scores.rows().into_iter().enumerate() {
+            let p = classification::scores_to_proba(&row.to_vec(), objective);
+            fitted_proba.row_mut(i).assign(&Array1::from(p));
+        }
+        let accuracy = classification::accuracy(&encoded.codes, &fitted_proba);
+        let log_loss = classification::log_loss(&encoded.codes, &fitted_proba);
+
+        Ok(GradientBoostingClassifierResult {
+            classes: encoded.classes,
+            fitted_proba,
+            init_scores,
+            objective,
+            learning_rate: lr,
+            n_trees,
+            max_depth: depth,
+            feature_importance,
+            accuracy,
+            log_loss,
+            class_weight,
+            n_obs: n,
+            n_features: k,
+            variable_names: names,
+            trees,
         })
     }
 }
+
+impl FittedModel for GradientBoostingClassifierResult {
+    fn model_name(&self) -> String {
+        "GradientBoostingClassifier".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Most probable class label; see `predict_proba` for probabilities.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        GradientBoostingClassifierResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
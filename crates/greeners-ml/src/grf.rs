@@ -19,13 +19,14 @@
 //!   4. Variance-based confidence intervals
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of Generalized Random Forest estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GrfResult {
     /// Predicted CATE for each observation (n)
     pub cate: Array1<f64>,
@@ -126,6 +127,7 @@ impl GRF {
     /// * `n_trees` - Number of trees (default 100)
     /// * `max_depth` - Max tree depth (default 5)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         t: &[bool],
@@ -133,7 +135,9 @@ impl GRF {
         n_trees: Option<usize>,
         max_depth: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<GrfResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if t.len() != n || x.nrows() != n {
@@ -217,7 +221,7 @@ impl GRF {
 
         for _ in 0..trees {
             // Bootstrap
-            let boot_idx: Vec<usize> = (0..n).map(|_| Self::rand_int(n)).collect();
+            let boot_idx: Vec<usize> = (0..n).map(|_| rng.index(n)).collect();
 
             // Build regression tree on pseudo-outcome
             let tree = Self::build_regression_tree(
@@ -229,6 +233,7 @@ impl GRF {
                 k,
                 0,
                 &mut feature_importance,
+                &mut rng,
             );
 
             // Predict CATE for all observations
@@ -312,6 +317,7 @@ impl GRF {
         k: usize,
         depth: usize,
         importance: &mut Array1<f64>,
+        rng: &mut GreenersRng,
     ) -> RegNode {
         let n = indices.len();
         let leaf_val: f64 = if n > 0 {
@@ -332,7 +338,7 @@ impl GRF {
         }
 
         let (best_feature, best_threshold, best_gain) =
-            Self::find_best_split_reg(y, x, indices, mtry, k);
+            Self::find_best_split_reg(y, x, indices, mtry, k, rng);
 
         if best_gain < 1e-10 || best_feature >= k {
             return RegNode {
@@ -380,6 +386,7 @@ impl GRF {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             right: Some(Box::new(Self::build_regression_tree(
                 y,
@@ -390,6 +397,7 @@ impl GRF {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             leaf_val,
             is_leaf: false,
@@ -402,6 +410,7 @@ impl GRF {
         indices: &[usize],
         mtry: usize,
         k: usize,
+        rng: &mut GreenersRng,
     ) -> (usize, f64, f64) {
         let n = indices.len();
         let parent_mean: f64 = indices.iter().map(|&i| y[i]).sum::<f64>() / n as f64;
@@ -412,10 +421,7 @@ impl GRF {
             / n as f64;
 
         let mut features: Vec<usize> = (0..k).collect();
-        for i in 0..features.len() {
-            let j = i + Self::rand_int(features.len() - i);
-            features.swap(i, j);
-        }
+        rng.shuffle(&mut features);
         let features = &features[..mtry.min(features.len())];
 
         let mut best_feature = k;
@@ -493,28 +499,6 @@ impl GRF {
         }
         tree.leaf_val
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1414213562) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
 
 /// Regression tree node (internal use).
@@ -21,10 +21,11 @@
 
 use greeners_core::GreenersError;
 use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Linkage method.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Linkage {
     Ward,
     Single,
@@ -44,7 +45,7 @@ impl Linkage {
 }
 
 /// A merge step in the dendrogram.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Merge {
     /// Index of first cluster merged
     pub cluster_a: usize,
@@ -57,7 +58,7 @@ pub struct Merge {
 }
 
 /// Result of hierarchical clustering.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct HierarchicalResult {
     /// Merge sequence (n-1 merges)
     pub merges: Vec<Merge>,
@@ -14,12 +14,14 @@
 //! Reports cluster labels, centroids, inertia, and silhouette-like
 //! separation metric.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::Array2;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of K-Means clustering.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KmeansResult {
     /// Cluster assignments (n), values 0..k-1
     pub labels: Vec<usize>,
@@ -100,11 +102,13 @@ impl KMeans {
     /// * `n_clusters` - Number of clusters k
     /// * `max_iter` - Max iterations (default 300)
     /// * `n_init` - Number of random restarts (default 10)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         x: &Array2<f64>,
         n_clusters: usize,
         max_iter: Option<usize>,
         n_init: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<KmeansResult, GreenersError> {
         let n = x.nrows();
         let d = x.ncols();
@@ -141,10 +145,17 @@ impl KMeans {
         let mut best_inertia = f64::INFINITY;
         let mut best_n_iter = 0;
 
-        for _ in 0..n_restarts {
-            let (labels, centroids, inertia, n_iter) =
-                Self::kmeans_once(x, n, d, n_clusters, max_iterations);
+        // Restarts run in parallel, each from its own random stream.
+        let rng = GreenersRng::new(seed);
+        let runs: Vec<_> = (0..n_restarts)
+            .into_par_iter()
+            .map(|r| {
+                let mut rng = rng.stream(r as u64);
+                Self::kmeans_once(x, n, d, n_clusters, max_iterations, &mut rng)
+            })
+            .collect();
 
+        for (labels, centroids, inertia, n_iter) in runs {
             if inertia < best_inertia {
                 best_inertia = inertia;
                 best_labels = labels;
@@ -195,10 +206,11 @@ impl KMeans {
         d: usize,
         k: usize,
         max_iter: usize,
+        rng: &mut GreenersRng,
     ) -> (Vec<usize>, Array2<f64>, f64, usize) {
         // k-means++ initialization
         let mut centroids = Array2::<f64>::zeros((k, d));
-        let first = Self::rand_int(n);
+        let first = rng.index(n);
         for j in 0..d {
             centroids[(0, j)] = x[(first, j)];
         }
@@ -217,13 +229,13 @@ impl KMeans {
             }
             let total: f64 = dists.iter().sum();
             if total < 1e-15 {
-                let idx = Self::rand_int(n);
+                let idx = rng.index(n);
                 for j in 0..d {
                     centroids[(c, j)] = x[(idx, j)];
                 }
                 continue;
             }
-            let r = Self::rand_uniform() * total;
+            let r = rng.uniform() * total;
             let mut cumsum = 0.0;
             let mut chosen = 0;
             for (i, &di) in dists.iter().enumerate().take(n) {
@@ -292,26 +304,4 @@ impl KMeans {
 
         (labels, centroids, inertia, n_iter)
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1122334455) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
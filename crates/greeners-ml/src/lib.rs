@@ -4,23 +4,30 @@
 // Items with names duplicated across modules remain namespaced.
 
 pub use bart::{BartResult, BART};
+pub use classification::{BoostingObjective, ClassWeight, SplitCriterion};
 pub use dbscan::{DbscanResult, DBSCAN};
 pub use gp::{GaussianProcess, GpResult};
-pub use gradient_boosting::{GradientBoosting, GradientBoostingResult};
+pub use gradient_boosting::{
+    GradientBoosting, GradientBoostingClassifier, GradientBoostingClassifierResult,
+    GradientBoostingResult,
+};
 pub use grf::{GrfResult, GRF};
 pub use hierarchical::{HierarchicalClustering, HierarchicalResult, Linkage, Merge};
 pub use kmeans::{KMeans, KmeansResult};
 pub use mlp::{MlpResult, MLP};
 pub use orthogonal_forest::{OrfResult, OrthogonalForest};
-pub use qrf::{QrfResult, QRF};
+pub use qrf::{QrfClassifier, QrfClassifierResult, QrfResult, QRF};
 pub use qrf_inference::{QrfInference, QrfInferenceResult};
-pub use random_forest::{RandomForest, RandomForestResult};
+pub use random_forest::{
+    RandomForest, RandomForestClassifier, RandomForestClassifierResult, RandomForestResult,
+};
 pub use transformer::{Transformer, TransformerResult};
 pub use tsne::{TsneResult, TSNE};
 pub use umap::{UmapResult, UMAP};
-pub use xgboost::{XGBoost, XgboostResult};
+pub use xgboost::{XGBoost, XGBoostClassifier, XgboostClassifierResult, XgboostResult};
 
 pub mod bart;
+pub mod classification;
 pub mod dbscan;
 pub mod gp;
 pub mod gradient_boosting;
@@ -9,12 +9,13 @@
 //!
 //! Rumelhart, Hinton & Williams (1986). Goodfellow et al. (2016).
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of MLP regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MlpResult {
     /// In-sample fitted values
     pub fitted: Array1<f64>,
@@ -104,6 +105,7 @@ impl MLP {
     /// * `learning_rate` - Gradient descent step size
     /// * `n_epochs` - Number of training epochs
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -111,7 +113,9 @@ impl MLP {
         learning_rate: Option<f64>,
         n_epochs: Option<usize>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<MlpResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 5 || k == 0 {
@@ -148,9 +152,9 @@ impl MLP {
         let mut w1 = Array2::zeros((n_hidden, k));
         let mut b1 = Array1::zeros(n_hidden);
         for i in 0..n_hidden {
-            b1[i] = (Self::rand_uniform() * 2.0 - 1.0) * 0.1;
+            b1[i] = (rng.uniform() * 2.0 - 1.0) * 0.1;
             for j in 0..k {
-                w1[(i, j)] = (Self::rand_uniform() * 2.0 - 1.0) * limit1;
+                w1[(i, j)] = (rng.uniform() * 2.0 - 1.0) * limit1;
             }
         }
 
@@ -303,19 +307,4 @@ impl MLP {
     fn sigmoid(x: f64) -> f64 {
         1.0 / (1.0 + (-x).exp())
     }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(5566778899) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -19,8 +19,9 @@
 //! This is a simplified implementation suitable for small datasets.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// ORF tree node.
@@ -38,7 +39,7 @@ struct OrfNode {
 }
 
 /// Result of ORF estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct OrfResult {
     /// Predicted CATE for each observation (n)
     pub cate: Array1<f64>,
@@ -127,6 +128,7 @@ impl OrthogonalForest {
     /// * `n_trees` - Number of trees (default 50)
     /// * `max_depth` - Max tree depth (default 5)
     /// * `feature_names` - Optional names for X features
+    /// * `seed` - Random seed (`None` uses the default seed)
     #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
@@ -136,7 +138,9 @@ impl OrthogonalForest {
         n_trees: Option<usize>,
         max_depth: Option<usize>,
         feature_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<OrfResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         let p = w.ncols();
@@ -197,7 +201,7 @@ impl OrthogonalForest {
 
         for _ in 0..m {
             // Bootstrap sample
-            let boot_idx: Vec<usize> = (0..n_forest).map(|_| Self::rand_int(n_forest)).collect();
+            let boot_idx: Vec<usize> = (0..n_forest).map(|_| rng.index(n_forest)).collect();
             let tree = Self::build_tree(
                 &y_tilde,
                 &t_tilde,
@@ -492,26 +496,4 @@ impl OrthogonalForest {
         }
         tree.leaf_cate
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(9876543210) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
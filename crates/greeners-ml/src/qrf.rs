@@ -13,9 +13,16 @@
 //! 3. For prediction at x: traverse all trees, collect the leaf
 //!    weights, and compute the weighted empirical CDF of y
 //! 4. Return the desired quantile(s) from the CDF
+//!
+//! [`QrfClassifier`] grows Gini/entropy trees and pools the (weighted) class
+//! counts of the leaves reached across all trees into a conditional class
+//! distribution. For ordered classes its quantiles give e.g. the conditional
+//! median class.
 
-use greeners_core::GreenersError;
+use crate::classification::{self, ClassNode, ClassTreeSpec, ClassWeight, SplitCriterion};
+use greeners_core::{FittedModel, GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single QRF tree node.
@@ -31,7 +38,7 @@ struct QrfNode {
 }
 
 /// Result of Quantile Regression Forest estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QrfResult {
     /// Predicted quantiles (n x n_quantiles)
     pub quantile_predictions: Array2<f64>,
@@ -117,6 +124,7 @@ impl QRF {
     /// * `n_trees` - Number of trees
     /// * `max_depth` - Maximum tree depth
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -124,7 +132,9 @@ impl QRF {
         n_trees: usize,
         max_depth: usize,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<QrfResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 5 || k == 0 {
@@ -161,7 +171,7 @@ impl QRF {
             let mut oob_indices = Vec::with_capacity(n);
             let mut in_boot = vec![false; n];
             for _ in 0..n {
-                let idx = Self::rand_int(n);
+                let idx = rng.index(n);
                 boot_indices.push(idx);
                 in_boot[idx] = true;
             }
@@ -181,6 +191,7 @@ impl QRF {
                 k,
                 0,
                 &mut feature_importance,
+                &mut rng,
             );
 
             // OOB: collect leaf values for each OOB observation
@@ -256,6 +267,7 @@ impl QRF {
         k: usize,
         depth: usize,
         importance: &mut Array1<f64>,
+        rng: &mut GreenersRng,
     ) -> QrfNode {
         let n = indices.len();
         let leaf_values: Vec<f64> = indices.iter().map(|&i| y[i]).collect();
@@ -272,7 +284,7 @@ impl QRF {
         }
 
         let (best_feature, best_threshold, best_gain) =
-            Self::find_best_split(y, x, indices, mtry, k);
+            Self::find_best_split(y, x, indices, mtry, k, rng);
 
         if best_gain < 1e-10 || best_feature >= k {
             return QrfNode {
@@ -320,6 +332,7 @@ impl QRF {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             right: Some(Box::new(Self::build_tree(
                 y,
@@ -330,6 +343,7 @@ impl QRF {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             leaf_values: Vec::new(),
             is_leaf: false,
@@ -342,6 +356,7 @@ impl QRF {
         indices: &[usize],
         mtry: usize,
         k: usize,
+        rng: &mut GreenersRng,
     ) -> (usize, f64, f64) {
         let n = indices.len();
         let parent_mean: f64 = indices.iter().map(|&i| y[i]).sum::<f64>() / n as f64;
@@ -352,10 +367,7 @@ impl QRF {
             / n as f64;
 
         let mut features: Vec<usize> = (0..k).collect();
-        for i in 0..features.len() {
-            let j = i + Self::rand_int(features.len() - i);
-            features.swap(i, j);
-        }
+        rng.shuffle(&mut features);
         let features = &features[..mtry.min(features.len())];
 
         let mut best_feature = k;
@@ -450,26 +462,296 @@ impl QRF {
         let frac = pos - lower as f64;
         sorted[lower] * (1.0 - frac) + sorted[upper] * frac
     }
+}
+
+/// Result of Quantile Regression Forest classification.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QrfClassifierResult {
+    /// Sorted class labels; column `j` of the probability matrices is `P(y = classes[j])`
+    pub classes: Vec<f64>,
+    /// In-sample conditional class distribution (n x n_classes)
+    pub class_probabilities: Array2<f64>,
+    /// Feature importance (cumulative impurity decrease)
+    pub feature_importance: Array1<f64>,
+    /// OOB accuracy
+    pub oob_accuracy: f64,
+    /// Split criterion
+    pub criterion: SplitCriterion,
+    /// Class weighting used in the splits and leaf counts
+    pub class_weight: Option<ClassWeight>,
+    /// Number of trees
+    pub n_trees: usize,
+    /// Max depth
+    pub max_depth: usize,
+    /// Number of observations
+    pub n_obs: usize,
+    /// Number of features
+    pub n_features: usize,
+    /// Variable names
+    pub variable_names: Vec<String>,
+    /// Fitted trees
+    trees: Vec<ClassNode>,
+}
+
+impl QrfClassifierResult {
+    /// Conditional class distribution for new observations (n x n_classes):
+    /// the pooled class counts of the leaves reached in every tree.
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "QrfClassifier: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut proba = Array2::zeros((x.nrows(), self.classes.len()));
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            let trees = self.trees.iter();
+            proba.row_mut(i).assign(&QrfClassifier::pooled_distribution(
+                trees,
+                &row,
+                self.classes.len(),
+            ));
+        }
+        Ok(proba)
+    }
+
+    /// Most probable class label for new observations.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let proba = self.predict_proba(x)?;
+        Ok(classification::predict_labels(&proba, &self.classes))
+    }
+
+    /// Conditional quantiles of the (ordered) class labels (n x n_quantiles):
+    /// the smallest class whose conditional CDF reaches each level.
+    pub fn predict_quantiles(
+        &self,
+        x: &Array2<f64>,
+        quantiles: &[f64],
+    ) -> Result<Array2<f64>, GreenersError> {
+        if quantiles.iter().any(|&q| q <= 0.0 || q >= 1.0) {
+            return Err(GreenersError::InvalidOperation(
+                "QrfClassifier: quantiles must be in (0, 1)".into(),
+            ));
+        }
+        let proba = self.predict_proba(x)?;
+        let mut out = Array2::zeros((x.nrows(), quantiles.len()));
+        for (i, row) in proba.rows().into_iter().enumerate() {
+            for (j, &q) in quantiles.iter().enumerate() {
+                let mut cdf = 0.0;
+                let mut label = self.classes[self.classes.len() - 1];
+                for (c, &p) in row.iter().enumerate() {
+                    cdf += p;
+                    if cdf >= q - 1e-12 {
+                        label = self.classes[c];
+                        break;
+                    }
+                }
+                out[(i, j)] = label;
+            }
+        }
+        Ok(out)
+    }
+}
 
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
+impl fmt::Display for QrfClassifierResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "\n{:=^78}",
+            " Quantile Regression Forest Classification "
+        )?;
+        writeln!(f, "Meinshausen (2006)")?;
+        writeln!(f, "{:<20} {:>12}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>12}", "Features:", self.n_features)?;
+        writeln!(f, "{:<20} {:>12}", "Classes:", self.classes.len())?;
+        writeln!(f, "{:<20} {:>12}", "Trees:", self.n_trees)?;
+        writeln!(f, "{:<20} {:>12}", "Max depth:", self.max_depth)?;
+        writeln!(f, "{:<20} {:>12}", "Criterion:", self.criterion)?;
+        writeln!(f, "{:<20} {:>12.6}", "OOB accuracy:", self.oob_accuracy)?;
+
+        // Feature importance
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(f, "  Feature importance:")?;
+        let total: f64 = self.feature_importance.sum().max(1e-10);
+        let mut imp_vec: Vec<(String, f64, f64)> = self
+            .variable_names
+            .iter()
+            .zip(self.feature_importance.iter())
+            .map(|(name, &imp)| (name.clone(), imp, imp / total * 100.0))
+            .collect();
+        imp_vec.sort_by(|a, b| b.1.total_cmp(&a.1));
+        writeln!(
+            f,
+            "  {:<14} {:>12} {:>10}",
+            "Variable", "Importance", "% Total"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (name, imp, pct) in imp_vec {
+            writeln!(f, "  {:<14} {:>12.6} {:>9.4}%", name, imp, pct)?;
         }
-        (Self::rand_uniform() * n as f64) as usize
+
+        write!(f, "{:=^78}", "")
     }
+}
 
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(7778889990) };
+pub struct QrfClassifier;
+
+impl QrfClassifier {
+    /// Estimate Quantile Regression Forest classification.
+    ///
+    /// # Arguments
+    /// * `y` - Class labels (n); any finite values, at least two distinct
+    /// * `x` - Features (n x k)
+    /// * `n_trees` - Number of trees
+    /// * `max_depth` - Maximum tree depth
+    /// * `criterion` - Split criterion (default Gini)
+    /// * `class_weight` - Optional class weighting
+    /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_trees: usize,
+        max_depth: usize,
+        criterion: Option<SplitCriterion>,
+        class_weight: Option<ClassWeight>,
+        variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
+    ) -> Result<QrfClassifierResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
+        let n = y.len();
+        let k = x.ncols();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "QrfClassifier: x and y dimension mismatch".into(),
+            ));
+        }
+        if n < 5 || k == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "QrfClassifier: too few observations or features".into(),
+            ));
+        }
+        if n_trees == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "QrfClassifier: n_trees must be >= 1".into(),
+            ));
+        }
+        let encoded = classification::encode_classes(y, class_weight.as_ref(), "QrfClassifier")?;
+        let n_classes = encoded.classes.len();
+
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|i| format!("x{}", i)).collect());
+        let criterion = criterion.unwrap_or_default();
+        let mtry = (k as f64).sqrt().ceil() as usize;
+        let spec = ClassTreeSpec {
+            x,
+            codes: &encoded.codes,
+            weights: &encoded.weights,
+            n_classes,
+            criterion,
+            max_depth,
+            mtry: mtry.max(1).min(k),
+        };
+
+        let mut trees: Vec<ClassNode> = Vec::with_capacity(n_trees);
+        let mut oob_trees: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut feature_importance = Array1::zeros(k);
+
+        for t in 0..n_trees {
+            let boot_indices = rng.bootstrap_indices(n);
+            let mut in_boot = vec![false; n];
+            for &idx in &boot_indices {
+                in_boot[idx] = true;
+            }
+            trees.push(spec.build(&boot_indices, 0, &mut feature_importance, &mut rng));
+            for (i, _) in in_boot.iter().enumerate().filter(|(_, &b)| !b) {
+                oob_trees[i].push(t);
+            }
         }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
+
+        let mut class_probabilities = Array2::zeros((n, n_classes));
+        for i in 0..n {
+            let row = x.row(i).to_owned();
+            class_probabilities
+                .row_mut(i)
+                .assign(&Self::pooled_distribution(trees.iter(), &row, n_classes));
+        }
+
+        // OOB accuracy over observations left out of at least one tree
+        let oob_obs: Vec<usize> = (0..n).filter(|&i| !oob_trees[i].is_empty()).collect();
+        let mut oob_proba = Array2::zeros((oob_obs.len(), n_classes));
+        for (r, &i) in oob_obs.iter().enumerate() {
+            let row = x.row(i).to_owned();
+            let oob = oob_trees[i].iter().map(|&t| &trees[t]);
+            oob_proba
+                .row_mut(r)
+                .assign(&Self::pooled_distribution(oob, &row, n_classes));
+        }
+        let oob_codes: Vec<usize> = oob_obs.iter().map(|&i| encoded.codes[i]).collect();
+        let oob_accuracy = classification::accuracy(&oob_codes, &oob_proba);
+
+        Ok(QrfClassifierResult {
+            classes: encoded.classes,
+            class_probabilities,
+            feature_importance,
+            oob_accuracy,
+            criterion,
+            class_weight,
+            n_trees,
+            max_depth,
+            n_obs: n,
+            n_features: k,
+            variable_names: names,
+            trees,
         })
     }
+
+    /// Class distribution from the leaf counts pooled over `trees`.
+    fn pooled_distribution<'a>(
+        trees: impl Iterator<Item = &'a ClassNode>,
+        x: &Array1<f64>,
+        n_classes: usize,
+    ) -> Array1<f64> {
+        let mut counts = Array1::<f64>::zeros(n_classes);
+        for tree in trees {
+            for (j, &c) in tree.leaf_counts(x).iter().enumerate() {
+                counts[j] += c;
+            }
+        }
+        let total = counts.sum();
+        if total > 0.0 {
+            counts / total
+        } else {
+            Array1::from_elem(n_classes, 1.0 / n_classes as f64)
+        }
+    }
+}
+
+impl FittedModel for QrfClassifierResult {
+    fn model_name(&self) -> String {
+        "QrfClassifier".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Most probable class label; see `predict_proba` for probabilities.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        QrfClassifierResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
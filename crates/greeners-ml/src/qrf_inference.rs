@@ -12,12 +12,13 @@
 //! specified confidence level.
 
 use crate::qrf::QRF;
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of QRF inference.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QrfInferenceResult {
     /// Quantile levels
     pub quantiles: Vec<f64>,
@@ -135,6 +136,7 @@ impl QrfInference {
     /// * `max_depth` - Max tree depth (default 10)
     /// * `confidence` - Confidence level (default 0.95)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
@@ -145,7 +147,9 @@ impl QrfInference {
         max_depth: Option<usize>,
         confidence: Option<f64>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<QrfInferenceResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 10 {
@@ -163,13 +167,14 @@ impl QrfInference {
         let n_q = quantiles.len();
 
         //Fit base QRF
-        let base_result = QRF::fit(y, x, quantiles.clone(), m_trees, depth, None)?;
+        let base_result = QRF::fit(y, x, quantiles.clone(), m_trees, depth, None, seed)?;
 
         // Bootstrap
         let mut boot_estimates: Vec<Array2<f64>> = Vec::with_capacity(n_boot);
         for _ in 0..n_boot {
             // Bootstrap sample
-            let boot_idx: Vec<usize> = (0..n).map(|_| Self::rand_int(n)).collect();
+            let boot_idx = rng.bootstrap_indices(n);
+            let boot_seed = rng.next_seed();
             let y_boot: Array1<f64> = boot_idx.iter().map(|&i| y[i]).collect();
             let x_boot: Array2<f64> = {
                 let mut data: Vec<f64> = Vec::with_capacity(n * k);
@@ -183,9 +188,15 @@ impl QrfInference {
             };
 
             // Fit QRF on bootstrap sample, predict on original x
-            if let Ok(boot_qrf) =
-                QRF::fit(&y_boot, &x_boot, quantiles.clone(), m_trees, depth, None)
-            {
+            if let Ok(boot_qrf) = QRF::fit(
+                &y_boot,
+                &x_boot,
+                quantiles.clone(),
+                m_trees,
+                depth,
+                None,
+                Some(boot_seed),
+            ) {
                 // Extract predictions
                 let mut preds = Array2::zeros((n, n_q));
                 for (i, row) in boot_qrf.quantile_predictions.rows().into_iter().enumerate() {
@@ -279,26 +290,4 @@ impl QrfInference {
             variable_names: names,
         })
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(5544332211) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -1,4 +1,4 @@
-//! Random Forest regression.
+//! Random Forest regression and classification.
 //!
 //! Breiman (2001). Ensemble of decision trees for regression.
 //! Each tree is trained on a bootstrap sample with random feature
@@ -7,16 +7,22 @@
 //! Implementation: CART-style decision trees with:
 //!   - Bootstrap sampling (bagging)
 //!   - mtry = sqrt(n_features) features per split
-//!   - MSE-based split criterion
+//!   - MSE-based split criterion (Gini or entropy for classification)
 //!   - Max depth control
 //!   - OOB (out-of-bag) error estimation
-
-use greeners_core::GreenersError;
-use ndarray::{Array1, Array2};
+//!
+//! [`RandomForestClassifier`] averages the leaf class frequencies of the
+//! trees to give class probabilities.
+
+use crate::classification::{self, ClassNode, ClassTreeSpec, ClassWeight, SplitCriterion};
+use greeners_core::{FittedModel, GreenersError, GreenersRng};
+use ndarray::{Array1, Array2, Axis};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single decision tree node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TreeNode {
     feature: usize,
     threshold: f64,
@@ -27,7 +33,7 @@ struct TreeNode {
 }
 
 /// Result of Random Forest estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RandomForestResult {
     /// Predictions (in-sample fitted values)
     pub fitted: Array1<f64>,
@@ -51,6 +57,32 @@ pub struct RandomForestResult {
     pub n_features: usize,
     /// Variable names
     pub variable_names: Vec<String>,
+    /// Fitted trees
+    trees: Vec<TreeNode>,
+}
+
+impl RandomForestResult {
+    /// Predictions for new observations, averaged across the trees.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "RandomForest: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut pred = Array1::zeros(x.nrows());
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            let sum: f64 = self
+                .trees
+                .iter()
+                .map(|tree| RandomForest::predict_single(tree, &row))
+                .sum();
+            pred[i] = sum / self.trees.len() as f64;
+        }
+        Ok(pred)
+    }
 }
 
 impl fmt::Display for RandomForestResult {
@@ -101,12 +133,14 @@ impl RandomForest {
     /// * `n_trees` - Number of trees in the forest
     /// * `max_depth` - Maximum tree depth
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
         n_trees: usize,
         max_depth: usize,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<RandomForestResult, GreenersError> {
         let n = y.len();
         let k = x.ncols();
@@ -125,41 +159,46 @@ impl RandomForest {
         let mtry = (k as f64).sqrt().ceil() as usize;
         let mtry = mtry.max(1).min(k);
 
+        // Trees are grown in parallel, each from its own random stream, so the
+        // forest does not depend on the number of threads.
+        let rng = GreenersRng::new(seed);
+        let grown: Vec<(TreeNode, Vec<bool>, Array1<f64>)> = (0..n_trees)
+            .into_par_iter()
+            .map(|t| {
+                let mut rng = rng.stream(t as u64);
+                // Bootstrap sample
+                let boot_indices = rng.bootstrap_indices(n);
+                let mut in_boot = vec![false; n];
+                for &idx in &boot_indices {
+                    in_boot[idx] = true;
+                }
+
+                // Build tree
+                let mut importance = Array1::zeros(k);
+                let tree = Self::build_tree(
+                    y,
+                    x,
+                    &boot_indices,
+                    max_depth,
+                    mtry,
+                    k,
+                    0,
+                    &mut importance,
+                    &mut rng,
+                );
+                (tree, in_boot, importance)
+            })
+            .collect();
+
         let mut trees: Vec<TreeNode> = Vec::with_capacity(n_trees);
         let mut feature_importance = Array1::zeros(k);
         let mut oob_sum = Array1::<f64>::zeros(n);
         let mut oob_count = Array1::<f64>::zeros(n);
-
-        for _tree in 0..n_trees {
-            // Bootstrap sample
-            let mut boot_indices = Vec::with_capacity(n);
-            let mut oob_indices = Vec::with_capacity(n);
-            let mut in_boot = vec![false; n];
-            for _ in 0..n {
-                let idx = Self::rand_int(n);
-                boot_indices.push(idx);
-                in_boot[idx] = true;
-            }
-            for (i, &in_b) in in_boot.iter().enumerate().take(n) {
-                if !in_b {
-                    oob_indices.push(i);
-                }
-            }
-
-            // Build tree
-            let tree = Self::build_tree(
-                y,
-                x,
-                &boot_indices,
-                max_depth,
-                mtry,
-                k,
-                0,
-                &mut feature_importance,
-            );
+        for (tree, in_boot, importance) in grown {
+            feature_importance += &importance;
 
             // OOB predictions
-            for &i in &oob_indices {
+            for i in (0..n).filter(|&i| !in_boot[i]) {
                 let pred = Self::predict_single(&tree, &x.row(i).to_owned());
                 oob_sum[i] += pred;
                 oob_count[i] += 1.0;
@@ -229,6 +268,7 @@ impl RandomForest {
             n_obs: n,
             n_features: k,
             variable_names: names,
+            trees,
         })
     }
 
@@ -243,6 +283,7 @@ impl RandomForest {
         k: usize,
         depth: usize,
         importance: &mut Array1<f64>,
+        rng: &mut GreenersRng,
     ) -> TreeNode {
         let n = indices.len();
 
@@ -263,7 +304,7 @@ impl RandomForest {
 
         // Find best split
         let (best_feature, best_threshold, best_gain) =
-            Self::find_best_split(y, x, indices, mtry, k);
+            Self::find_best_split(y, x, indices, mtry, k, rng);
 
         if best_gain < 1e-10 || best_feature >= k {
             return TreeNode {
@@ -313,6 +354,7 @@ impl RandomForest {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             right: Some(Box::new(Self::build_tree(
                 y,
@@ -323,6 +365,7 @@ impl RandomForest {
                 k,
                 depth + 1,
                 importance,
+                rng,
             ))),
             prediction: mean,
             is_leaf: false,
@@ -336,6 +379,7 @@ impl RandomForest {
         indices: &[usize],
         mtry: usize,
         k: usize,
+        rng: &mut GreenersRng,
     ) -> (usize, f64, f64) {
         let n = indices.len();
         let parent_mean: f64 = indices.iter().map(|&i| y[i]).sum::<f64>() / n as f64;
@@ -348,10 +392,7 @@ impl RandomForest {
         // Random feature subset
         let mut features: Vec<usize> = (0..k).collect();
         // Shuffle and take first mtry
-        for i in 0..features.len() {
-            let j = i + Self::rand_int(features.len() - i);
-            features.swap(i, j);
-        }
+        rng.shuffle(&mut features);
         let features = &features[..mtry.min(features.len())];
 
         let mut best_feature = k; // invalid
@@ -433,26 +474,310 @@ impl RandomForest {
         }
         tree.prediction
     }
+}
+
+impl FittedModel for RandomForestResult {
+    fn model_name(&self) -> String {
+        "RandomForest".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        RandomForestResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
+
+/// Result of Random Forest classification.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RandomForestClassifierResult {
+    /// Sorted class labels; column `j` of the probability matrices is `P(y = classes[j])`
+    pub classes: Vec<f64>,
+    /// In-sample class probabilities (n x n_classes)
+    pub fitted_proba: Array2<f64>,
+    /// Out-of-bag class probabilities (n x n_classes)
+    pub oob_proba: Array2<f64>,
+    /// Feature importance (sum of impurity decrease per feature)
+    pub feature_importance: Array1<f64>,
+    /// In-sample accuracy
+    pub accuracy: f64,
+    /// OOB accuracy
+    pub oob_accuracy: f64,
+    /// In-sample log-loss
+    pub log_loss: f64,
+    /// Split criterion
+    pub criterion: SplitCriterion,
+    /// Class weighting used in the splits and leaf frequencies
+    pub class_weight: Option<ClassWeight>,
+    /// Number of trees
+    pub n_trees: usize,
+    /// Max depth
+    pub max_depth: usize,
+    /// Number of observations
+    pub n_obs: usize,
+    /// Number of features
+    pub n_features: usize,
+    /// Variable names
+    pub variable_names: Vec<String>,
+    /// Fitted trees
+    trees: Vec<ClassNode>,
+}
+
+impl RandomForestClassifierResult {
+    /// Class probabilities for new observations (n x n_classes), averaged
+    /// across the trees.
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "RandomForestClassifier: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let n_classes = self.classes.len();
+        let mut proba = Array2::zeros((x.nrows(), n_classes));
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            for tree in &self.trees {
+                for (j, p) in tree.leaf_proba(&row).into_iter().enumerate() {
+                    proba[(i, j)] += p;
+                }
+            }
+        }
+        proba /= self.trees.len() as f64;
+        Ok(proba)
+    }
 
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
+    /// Most probable class label for new observations.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let proba = self.predict_proba(x)?;
+        Ok(classification::predict_labels(&proba, &self.classes))
+    }
+}
+
+impl fmt::Display for RandomForestClassifierResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " Random Forest Classification ")?;
+        writeln!(f, "Breiman (2001) — ensemble of decision trees")?;
+        writeln!(f, "{:<20} {:>12}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>12}", "Features:", self.n_features)?;
+        writeln!(f, "{:<20} {:>12}", "Classes:", self.classes.len())?;
+        writeln!(f, "{:<20} {:>12}", "Trees:", self.n_trees)?;
+        writeln!(f, "{:<20} {:>12}", "Max depth:", self.max_depth)?;
+        writeln!(f, "{:<20} {:>12}", "Criterion:", self.criterion)?;
+        writeln!(f, "{:<20} {:>12.6}", "In-sample accuracy:", self.accuracy)?;
+        writeln!(f, "{:<20} {:>12.6}", "OOB accuracy:", self.oob_accuracy)?;
+        writeln!(f, "{:<20} {:>12.6}", "Log-loss:", self.log_loss)?;
+
+        // Feature importance
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(f, "  Feature importance:")?;
+        let total: f64 = self.feature_importance.sum().max(1e-10);
+        let mut imp_vec: Vec<(String, f64, f64)> = self
+            .variable_names
+            .iter()
+            .zip(self.feature_importance.iter())
+            .map(|(name, &imp)| (name.clone(), imp, imp / total * 100.0))
+            .collect();
+        imp_vec.sort_by(|a, b| b.1.total_cmp(&a.1));
+        writeln!(
+            f,
+            "  {:<14} {:>12} {:>10}",
+            "Variable", "Importance", "% Total"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (name, imp, pct) in imp_vec {
+            writeln!(f, "  {:<14} {:>12.6} {:>9.4}%", name, imp, pct)?;
         }
-        (Self::rand_uniform() * n as f64) as usize
+
+        write!(f, "{:=^78}", "")
     }
+}
+
+pub struct RandomForestClassifier;
+
+impl RandomForestClassifier {
+    /// Estimate Random Forest classification.
+    ///
+    /// # Arguments
+    /// * `y` - Class labels (n); any finite values, at least two distinct
+    /// * `x` - Features (n x k)
+    /// * `n_trees` - Number of trees in the forest
+    /// * `max_depth` - Maximum tree depth
+    /// * `criterion` - Split criterion (default Gini)
+    /// * `class_weight` - Optional class weighting
+    /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_trees: usize,
+        max_depth: usize,
+        criterion: Option<SplitCriterion>,
+        class_weight: Option<ClassWeight>,
+        variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
+    ) -> Result<RandomForestClassifierResult, GreenersError> {
+        let n = y.len();
+        let k = x.ncols();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "RandomForestClassifier: x and y dimension mismatch".into(),
+            ));
+        }
+        if n < 5 || k == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "RandomForestClassifier: too few observations or features".into(),
+            ));
+        }
+        if n_trees == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "RandomForestClassifier: n_trees must be >= 1".into(),
+            ));
+        }
+        let encoded =
+            classification::encode_classes(y, class_weight.as_ref(), "RandomForestClassifier")?;
+        let n_classes = encoded.classes.len();
 
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(987654321) };
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|i| format!("x{}", i)).collect());
+        let criterion = criterion.unwrap_or_default();
+        let mtry = (k as f64).sqrt().ceil() as usize;
+        let spec = ClassTreeSpec {
+            x,
+            codes: &encoded.codes,
+            weights: &encoded.weights,
+            n_classes,
+            criterion,
+            max_depth,
+            mtry: mtry.max(1).min(k),
+        };
+
+        // Same per-tree streams as the regression forest.
+        let rng = GreenersRng::new(seed);
+        let grown: Vec<(ClassNode, Vec<bool>, Array1<f64>)> = (0..n_trees)
+            .into_par_iter()
+            .map(|t| {
+                let mut rng = rng.stream(t as u64);
+                let boot_indices = rng.bootstrap_indices(n);
+                let mut in_boot = vec![false; n];
+                for &idx in &boot_indices {
+                    in_boot[idx] = true;
+                }
+                let mut importance = Array1::zeros(k);
+                let tree = spec.build(&boot_indices, 0, &mut importance, &mut rng);
+                (tree, in_boot, importance)
+            })
+            .collect();
+
+        let mut trees: Vec<ClassNode> = Vec::with_capacity(n_trees);
+        let mut feature_importance = Array1::zeros(k);
+        let mut fitted_proba = Array2::<f64>::zeros((n, n_classes));
+        let mut oob_proba = Array2::<f64>::zeros((n, n_classes));
+        let mut oob_count = vec![0usize; n];
+        for (tree, in_boot, importance) in grown {
+            feature_importance += &importance;
+            for i in 0..n {
+                let proba = tree.leaf_proba(&x.row(i).to_owned());
+                for (j, &p) in proba.iter().enumerate() {
+                    fitted_proba[(i, j)] += p;
+                    if !in_boot[i] {
+                        oob_proba[(i, j)] += p;
+                    }
+                }
+                if !in_boot[i] {
+                    oob_count[i] += 1;
+                }
+            }
+            trees.push(tree);
+        }
+        fitted_proba /= n_trees as f64;
+
+        // Observations that were in every bootstrap sample get the
+        // (weighted) class shares.
+        let mut prior = vec![0.0; n_classes];
+        for (&c, &w) in encoded.codes.iter().zip(&encoded.weights) {
+            prior[c] += w;
         }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
+        let prior_total: f64 = prior.iter().sum();
+        for i in 0..n {
+            for j in 0..n_classes {
+                oob_proba[(i, j)] = if oob_count[i] > 0 {
+                    oob_proba[(i, j)] / oob_count[i] as f64
+                } else {
+                    prior[j] / prior_total
+                };
+            }
+        }
+
+        let accuracy = classification::accuracy(&encoded.codes, &fitted_proba);
+        let log_loss = classification::log_loss(&encoded.codes, &fitted_proba);
+        let oob_obs: Vec<usize> = (0..n).filter(|&i| oob_count[i] > 0).collect();
+        let oob_codes: Vec<usize> = oob_obs.iter().map(|&i| encoded.codes[i]).collect();
+        let oob_accuracy =
+            classification::accuracy(&oob_codes, &oob_proba.select(Axis(0), &oob_obs));
+
+        Ok(RandomForestClassifierResult {
+            classes: encoded.classes,
+            fitted_proba,
+            oob_proba,
+            feature_importance,
+            accuracy,
+            oob_accuracy,
+            log_loss,
+            criterion,
+            class_weight,
+            n_trees,
+            max_depth,
+            n_obs: n,
+            n_features: k,
+            variable_names: names,
+            trees,
         })
     }
 }
+
+impl FittedModel for RandomForestClassifierResult {
+    fn model_name(&self) -> String {
+        "RandomForestClassifier".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Most probable class label; see `predict_proba` for probabilities.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        RandomForestClassifierResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
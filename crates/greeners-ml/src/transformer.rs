@@ -14,12 +14,13 @@
 //! Training: gradient descent on MSE loss. Truncated backprop.
 //! Input: univariate series, converted to sliding windows.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Transformer estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TransformerResult {
     /// In-sample fitted values
     pub fitted: Array1<f64>,
@@ -86,6 +87,7 @@ impl Transformer {
     /// * `learning_rate` - Learning rate (default 0.001)
     /// * `n_epochs` - Training epochs (default 100)
     /// * `n_forecast` - Number of steps to forecast (default 5)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         d_model: Option<usize>,
@@ -93,7 +95,9 @@ impl Transformer {
         learning_rate: Option<f64>,
         n_epochs: Option<usize>,
         n_forecast: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<TransformerResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         if n < 20 {
             return Err(GreenersError::InvalidOperation(
@@ -139,13 +143,13 @@ impl Transformer {
         let mut w_out = Array1::zeros(d);
 
         // Initialize with small random values
-        Self::init_matrix(&mut w_embed, seq, d);
-        Self::init_matrix(&mut w_q, d, d);
-        Self::init_matrix(&mut w_k, d, d);
-        Self::init_matrix(&mut w_v, d, d);
-        Self::init_matrix(&mut w_ff1, d, d);
+        Self::init_matrix(&mut w_embed, seq, d, &mut rng);
+        Self::init_matrix(&mut w_q, d, d, &mut rng);
+        Self::init_matrix(&mut w_k, d, d, &mut rng);
+        Self::init_matrix(&mut w_v, d, d, &mut rng);
+        Self::init_matrix(&mut w_ff1, d, d, &mut rng);
         for i in 0..d {
-            w_out[i] = Self::rand_uniform() * 0.1 - 0.05;
+            w_out[i] = rng.uniform() * 0.1 - 0.05;
         }
 
         let scale = 1.0 / (d as f64).sqrt();
@@ -374,10 +378,10 @@ impl Transformer {
         Self::dot(&ff_hidden, greeners_core::array1_slice(w_out))
     }
 
-    fn init_matrix(m: &mut Array2<f64>, rows: usize, cols: usize) {
+    fn init_matrix(m: &mut Array2<f64>, rows: usize, cols: usize, rng: &mut GreenersRng) {
         for i in 0..rows {
             for j in 0..cols {
-                m[(i, j)] = Self::rand_uniform() * 0.1 - 0.05;
+                m[(i, j)] = rng.uniform() * 0.1 - 0.05;
             }
         }
     }
@@ -403,19 +407,4 @@ impl Transformer {
         // to allow gradient flow
         1.0 / (1.0 + (-x).exp())
     }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(2360679774) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
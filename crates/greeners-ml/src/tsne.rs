@@ -15,12 +15,13 @@
 //!   - Momentum-based gradient descent
 //!   - Early exaggeration
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of t-SNE.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TsneResult {
     /// 2D embedding (n x 2)
     pub embedding: Array2<f64>,
@@ -102,13 +103,16 @@ impl TSNE {
     /// * `n_components` - Output dimensions (default 2)
     /// * `max_iter` - Max iterations (default 500)
     /// * `learning_rate` - Gradient descent learning rate (default 200.0)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         x: &Array2<f64>,
         perplexity: Option<f64>,
         n_components: Option<usize>,
         max_iter: Option<usize>,
         learning_rate: Option<f64>,
+        seed: Option<u64>,
     ) -> Result<TsneResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = x.nrows();
         let d = x.ncols();
         if n < 5 {
@@ -195,7 +199,7 @@ impl TSNE {
         let mut y = Array2::zeros((n, n_comp));
         for i in 0..n {
             for j in 0..n_comp {
-                y[(i, j)] = (Self::rand_normal() * 1e-4).clamp(-1e-4, 1e-4);
+                y[(i, j)] = (rng.normal() * 1e-4).clamp(-1e-4, 1e-4);
             }
         }
 
@@ -310,25 +314,4 @@ impl TSNE {
             costs,
         })
     }
-
-    fn rand_normal() -> f64 {
-        let u1 = Self::rand_uniform().max(1e-10);
-        let u2 = Self::rand_uniform();
-        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(7766554433) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -15,12 +15,13 @@
 //!   - Fuzzy union for graph construction
 //!   - Gradient descent for embedding optimization
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of UMAP.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UmapResult {
     /// Low-D embedding (n x n_components)
     pub embedding: Array2<f64>,
@@ -100,13 +101,16 @@ impl UMAP {
     /// * `n_components` - Output dimensions (default 2)
     /// * `min_dist` - Minimum embedding distance (default 0.1)
     /// * `max_iter` - Max optimization iterations (default 300)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         x: &Array2<f64>,
         n_neighbors: Option<usize>,
         n_components: Option<usize>,
         min_dist: Option<f64>,
         max_iter: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<UmapResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = x.nrows();
         let d = x.ncols();
         if n < 5 {
@@ -196,7 +200,7 @@ impl UMAP {
         let mut y = Array2::zeros((n, n_comp));
         for i in 0..n {
             for j in 0..n_comp {
-                y[(i, j)] = Self::rand_normal() * 0.01;
+                y[(i, j)] = rng.normal() * 0.01;
             }
         }
 
@@ -296,25 +300,4 @@ impl UMAP {
             losses,
         })
     }
-
-    fn rand_normal() -> f64 {
-        let u1 = Self::rand_uniform().max(1e-10);
-        let u2 = Self::rand_uniform();
-        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(6655443322) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -15,13 +15,19 @@
 //! Leaf weight: w* = -G / (H + lambda), with L1 proximal clipping.
 //!
 //! Split gain: 0.5 * [G_L^2/(H_L+lambda) + G_R^2/(H_R+lambda) - G^2/(H+lambda)] - gamma
+//!
+//! [`XGBoostClassifier`] uses the logistic objective for two classes
+//! (gradient = p - y, hessian = p(1 - p) on the log-odds) and the softmax
+//! objective for more, with one tree per class and round.
 
-use greeners_core::GreenersError;
+use crate::classification::{self, BoostingObjective, ClassWeight};
+use greeners_core::{FittedModel, GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A single XGBoost tree node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct XgbNode {
     feature: usize,
     threshold: f64,
@@ -32,7 +38,7 @@ struct XgbNode {
 }
 
 /// Result of XGBoost estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct XgboostResult {
     /// In-sample fitted values
     pub fitted: Array1<f64>,
@@ -62,6 +68,51 @@ pub struct XgboostResult {
     pub n_features: usize,
     /// Variable names
     pub variable_names: Vec<String>,
+    /// Fitted trees, in boosting order
+    trees: Vec<XgbNode>,
+}
+
+impl XgboostResult {
+    /// Predictions for new observations using all trees.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "XGBoost: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut pred = Array1::from_elem(x.nrows(), self.init_value);
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            for tree in &self.trees {
+                pred[i] += self.learning_rate * XGBoost::predict_single(tree, &row);
+            }
+        }
+        Ok(pred)
+    }
+
+    /// Predictions after each boosting iteration: row `m` holds the
+    /// predictions of the first `m + 1` trees (`n_trees x n` array), e.g. to
+    /// choose the number of iterations on a validation sample.
+    pub fn staged_predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "XGBoost: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut current = Array1::from_elem(x.nrows(), self.init_value);
+        let mut staged = Array2::zeros((self.trees.len(), x.nrows()));
+        for (m, tree) in self.trees.iter().enumerate() {
+            for (i, row) in x.rows().into_iter().enumerate() {
+                current[i] += self.learning_rate * XGBoost::predict_single(tree, &row.to_owned());
+            }
+            staged.row_mut(m).assign(&current);
+        }
+        Ok(staged)
+    }
 }
 
 impl fmt::Display for XgboostResult {
@@ -122,6 +173,7 @@ impl XGBoost {
     /// * `subsample` - Fraction of obs per tree (default 1.0)
     /// * `colsample` - Fraction of features per tree (default 1.0)
     /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
     #[allow(clippy::too_many_arguments)]
     pub fn fit(
         y: &Array1<f64>,
@@ -135,7 +187,9 @@ impl XGBoost {
         subsample: Option<f64>,
         colsample: Option<f64>,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<XgboostResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = y.len();
         let k = x.ncols();
         if n < 5 || k == 0 {
@@ -162,6 +216,7 @@ impl XGBoost {
         let init_value = y.mean().unwrap_or(0.0);
         let mut fitted = Array1::from_elem(n, init_value);
         let mut feature_importance = Array1::zeros(k);
+        let mut trees: Vec<XgbNode> = Vec::with_capacity(n_trees);
 
         for _ in 0..n_trees {
             // Gradients and Hessians for squared loss
@@ -175,7 +230,7 @@ impl XGBoost {
             let n_sub = (n as f64 * sub).round() as usize;
             let n_sub = n_sub.max(5).min(n);
             let obs_indices: Vec<usize> = if sub < 1.0 {
-                (0..n_sub).map(|_| Self::rand_int(n)).collect()
+                (0..n_sub).map(|_| rng.index(n)).collect()
             } else {
                 (0..n).collect()
             };
@@ -184,10 +239,7 @@ impl XGBoost {
             let n_feat_sub = (k as f64 * col).round() as usize;
             let n_feat_sub = n_feat_sub.max(1).min(k);
             let mut all_features: Vec<usize> = (0..k).collect();
-            for i in 0..all_features.len() {
-                let j = i + Self::rand_int(all_features.len() - i);
-                all_features.swap(i, j);
-            }
+            rng.shuffle(&mut all_features);
             let feat_indices = &all_features[..n_feat_sub];
 
             // Build tree
@@ -210,6 +262,8 @@ impl XGBoost {
                 let leaf_weight = Self::predict_single(&tree, &x.row(i).to_owned());
                 fitted[i] += lr * leaf_weight;
             }
+
+            trees.push(tree);
         }
 
         // R-squared
@@ -238,6 +292,7 @@ impl XGBoost {
             n_obs: n,
             n_features: k,
             variable_names: names,
+            trees,
         })
     }
 
@@ -436,26 +491,338 @@ impl XGBoost {
         }
         tree.leaf_weight
     }
+}
+
+impl FittedModel for XgboostResult {
+    fn model_name(&self) -> String {
+        "XGBoost".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        XgboostResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
 
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
+/// Result of XGBoost classification.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct XgboostClassifierResult {
+    /// Sorted class labels; column `j` of the probability matrices is `P(y = classes[j])`
+    pub classes: Vec<f64>,
+    /// In-sample class probabilities (n x n_classes)
+    pub fitted_proba: Array2<f64>,
+    /// Initial scores (log prior odds, or log priors for softmax)
+    pub init_scores: Vec<f64>,
+    /// Boosting objective (log-loss for two classes, softmax otherwise)
+    pub objective: BoostingObjective,
+    /// Learning rate (shrinkage)
+    pub learning_rate: f64,
+    /// Number of boosting rounds
+    pub n_trees: usize,
+    /// Max depth per tree
+    pub max_depth: usize,
+    /// L2 regularization (lambda)
+    pub lambda: f64,
+    /// L1 regularization (alpha)
+    pub alpha: f64,
+    /// Gamma (leaf penalty)
+    pub gamma: f64,
+    /// Feature importance (cumulative gain)
+    pub feature_importance: Array1<f64>,
+    /// In-sample accuracy
+    pub accuracy: f64,
+    /// In-sample log-loss
+    pub log_loss: f64,
+    /// Class weighting applied to the loss
+    pub class_weight: Option<ClassWeight>,
+    /// Number of observations
+    pub n_obs: usize,
+    /// Number of features
+    pub n_features: usize,
+    /// Variable names
+    pub variable_names: Vec<String>,
+    /// Fitted trees: one per score and round, in boosting order
+    trees: Vec<Vec<XgbNode>>,
+}
+
+impl XgboostClassifierResult {
+    /// Class probabilities for new observations (n x n_classes).
+    pub fn predict_proba(&self, x: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+        if x.ncols() != self.n_features {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "XGBoostClassifier: expected {} features, got {}",
+                self.n_features,
+                x.ncols()
+            )));
+        }
+        let mut proba = Array2::zeros((x.nrows(), self.classes.len()));
+        for (i, row) in x.rows().into_iter().enumerate() {
+            let row = row.to_owned();
+            let mut scores = self.init_scores.clone();
+            for round in &self.trees {
+                for (j, tree) in round.iter().enumerate() {
+                    scores[j] += self.learning_rate * XGBoost::predict_single(tree, &row);
+                }
+            }
+            let p = classification::scores_to_proba(&scores, self.objective);
+            proba.row_mut(i).assign(&Array1::from(p));
         }
-        (Self::rand_uniform() * n as f64) as usize
+        Ok(proba)
     }
 
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(3141592653) };
+    /// Most probable class label for new observations.
+    pub fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let proba = self.predict_proba(x)?;
+        Ok(classification::predict_labels(&proba, &self.classes))
+    }
+}
+
+impl fmt::Display for XgboostClassifierResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " XGBoost Classification ")?;
+        writeln!(f, "Chen & Guestrin (2016)")?;
+        writeln!(f, "L1/L2 regularized gradient boosting (Newton)")?;
+        writeln!(f, "{:<20} {:>12}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>12}", "Features:", self.n_features)?;
+        writeln!(f, "{:<20} {:>12}", "Classes:", self.classes.len())?;
+        writeln!(f, "{:<20} {:>12}", "Objective:", self.objective)?;
+        writeln!(f, "{:<20} {:>12}", "Trees:", self.n_trees)?;
+        writeln!(f, "{:<20} {:>12}", "Max depth:", self.max_depth)?;
+        writeln!(f, "{:<20} {:>12.6}", "Learning rate:", self.learning_rate)?;
+        writeln!(f, "{:<20} {:>12.6}", "Lambda (L2):", self.lambda)?;
+        writeln!(f, "{:<20} {:>12.6}", "Alpha (L1):", self.alpha)?;
+        writeln!(f, "{:<20} {:>12.6}", "Gamma (leaf penalty):", self.gamma)?;
+        writeln!(f, "{:<20} {:>12.6}", "In-sample accuracy:", self.accuracy)?;
+        writeln!(f, "{:<20} {:>12.6}", "Log-loss:", self.log_loss)?;
+
+        // Feature importance
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(f, "  Feature importance (gain):")?;
+        let total: f64 = self.feature_importance.sum().max(1e-10);
+        let mut imp_vec: Vec<(String, f64, f64)> = self
+            .variable_names
+            .iter()
+            .zip(self.feature_importance.iter())
+            .map(|(name, &imp)| (name.clone(), imp, imp / total * 100.0))
+            .collect();
+        imp_vec.sort_by(|a, b| b.1.total_cmp(&a.1));
+        writeln!(
+            f,
+            "  {:<14} {:>12} {:>10}",
+            "Variable", "Importance", "% Total"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (name, imp, pct) in imp_vec {
+            writeln!(f, "  {:<14} {:>12.6} {:>9.4}%", name, imp, pct)?;
+        }
+
+        write!(f, "{:=^78}", "")
+    }
+}
+
+pub struct XGBoostClassifier;
+
+impl XGBoostClassifier {
+    /// Estimate XGBoost classification.
+    ///
+    /// Two classes use the logistic objective, more classes the softmax.
+    ///
+    /// # Arguments
+    /// * `y` - Class labels (n); any finite values, at least two distinct
+    /// * `x` - Features (n x k)
+    /// * `n_trees` - Number of boosting rounds
+    /// * `learning_rate` - Shrinkage (default 0.3)
+    /// * `max_depth` - Max tree depth (default 6)
+    /// * `lambda` - L2 regularization (default 1.0)
+    /// * `alpha` - L1 regularization (default 0.0)
+    /// * `gamma` - Leaf penalty (default 0.0)
+    /// * `subsample` - Fraction of obs per tree (default 1.0)
+    /// * `colsample` - Fraction of features per tree (default 1.0)
+    /// * `class_weight` - Optional class weighting of the loss
+    /// * `variable_names` - Optional feature names
+    /// * `seed` - Random seed (`None` uses the default seed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        n_trees: usize,
+        learning_rate: Option<f64>,
+        max_depth: Option<usize>,
+        lambda: Option<f64>,
+        alpha: Option<f64>,
+        gamma: Option<f64>,
+        subsample: Option<f64>,
+        colsample: Option<f64>,
+        class_weight: Option<ClassWeight>,
+        variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
+    ) -> Result<XgboostClassifierResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
+        let n = y.len();
+        let k = x.ncols();
+        if x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "XGBoostClassifier: x and y dimension mismatch".into(),
+            ));
+        }
+        if n < 5 || k == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "XGBoostClassifier: too few observations or features".into(),
+            ));
+        }
+        if n_trees == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "XGBoostClassifier: n_trees must be >= 1".into(),
+            ));
         }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
+        let encoded =
+            classification::encode_classes(y, class_weight.as_ref(), "XGBoostClassifier")?;
+        let n_classes = encoded.classes.len();
+
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|i| format!("x{}", i)).collect());
+        let lr = learning_rate.unwrap_or(0.3);
+        let depth = max_depth.unwrap_or(6);
+        let lam = lambda.unwrap_or(1.0);
+        let al = alpha.unwrap_or(0.0);
+        let gam = gamma.unwrap_or(0.0);
+        let sub = subsample.unwrap_or(1.0).clamp(0.1, 1.0);
+        let col = colsample.unwrap_or(1.0).clamp(0.1, 1.0);
+
+        let (objective, init_scores) = classification::initial_scores(&encoded);
+        let n_scores = init_scores.len();
+        let mut scores: Vec<Vec<f64>> = vec![init_scores.clone(); n];
+        let mut feature_importance = Array1::zeros(k);
+        let mut trees: Vec<Vec<XgbNode>> = Vec::with_capacity(n_trees);
+
+        for _ in 0..n_trees {
+            let proba: Vec<Vec<f64>> = scores
+                .iter()
+                .map(|s| classification::scores_to_proba(s, objective))
+                .collect();
+
+            // Subsample observations
+            let n_sub = (n as f64 * sub).round() as usize;
+            let n_sub = n_sub.max(5).min(n);
+            let obs_indices: Vec<usize> = if sub < 1.0 {
+                (0..n_sub).map(|_| rng.index(n)).collect()
+            } else {
+                (0..n).collect()
+            };
+
+            // Subsample features
+            let n_feat_sub = (k as f64 * col).round() as usize;
+            let n_feat_sub = n_feat_sub.max(1).min(k);
+            let mut all_features: Vec<usize> = (0..k).collect();
+            rng.shuffle(&mut all_features);
+            let feat_indices = &all_features[..n_feat_sub];
+
+            let mut round = Vec::with_capacity(n_scores);
+            for j in 0..n_scores {
+                // Score j models the class with code `class`
+                let class = if n_scores == 1 { 1 } else { j };
+                let mut gradients = vec![0.0; n];
+                let mut hessians = vec![0.0; n];
+                for i in 0..n {
+                    let target = if encoded.codes[i] == class { 1.0 } else { 0.0 };
+                    let p = proba[i][class];
+                    gradients[i] = encoded.weights[i] * (p - target);
+                    hessians[i] = (encoded.weights[i] * p * (1.0 - p)).max(1e-16);
+                }
+
+                let tree = XGBoost::build_tree(
+                    x,
+                    &gradients,
+                    &hessians,
+                    &obs_indices,
+                    feat_indices,
+                    depth,
+                    lam,
+                    al,
+                    gam,
+                    0,
+                    &mut feature_importance,
+                );
+                for (i, s) in scores.iter_mut().enumerate() {
+                    s[j] += lr * XGBoost::predict_single(&tree, &x.row(i).to_owned());
+                }
+                round.push(tree);
+            }
+            trees.push(round);
+        }
+
+        let mut fitted_proba = Array2::zeros((n, n_classes));
+        for (i, s) in scores.iter().enumerate() {
+            let p = classification::scores_to_proba(s, objective);
+            fitted_proba.row_mut(i).assign(&Array1::from(p));
+        }
+        let accuracy = classification::accuracy(&encoded.codes, &fitted_proba);
+        let log_loss = classification::log_loss(&encoded.codes, &fitted_proba);
+
+        Ok(XgboostClassifierResult {
+            classes: encoded.classes,
+            fitted_proba,
+            init_scores,
+            objective,
+            learning_rate: lr,
+            n_trees,
+            max_depth: depth,
+            lambda: lam,
+            alpha: al,
+            gamma: gam,
+            feature_importance,
+            accuracy,
+            log_loss,
+            class_weight,
+            n_obs: n,
+            n_features: k,
+            variable_names: names,
+            trees,
         })
     }
 }
+
+impl FittedModel for XgboostClassifierResult {
+    fn model_name(&self) -> String {
+        "XGBoostClassifier".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        Array1::zeros(0)
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Most probable class label; see `predict_proba` for probabilities.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        XgboostClassifierResult::predict(self, x)
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
@@ -25,7 +25,7 @@ fn make_bart_data(n: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
 #[test]
 fn test_bart_shape_and_finite() {
     let (y, x) = make_bart_data(50, 6001);
-    let r = BART::fit(&y, &x, Some(10), Some(2), Some(50), Some(20), None).unwrap();
+    let r = BART::fit(&y, &x, Some(10), Some(2), Some(50), Some(20), None, None).unwrap();
     assert_eq!(r.fitted.len(), y.len());
     assert_eq!(r.n_obs, y.len());
     assert_eq!(r.n_features, x.ncols());
@@ -42,18 +42,18 @@ fn test_bart_shape_and_finite() {
 #[test]
 fn test_bart_input_validation() {
     let (y, x) = make_bart_data(5, 6002);
-    assert!(BART::fit(&y, &x, None, None, None, None, None).is_err());
+    assert!(BART::fit(&y, &x, None, None, None, None, None, None).is_err());
 
     let y_const = Array1::from_vec(vec![1.0; 20]);
     let x = Array2::from_shape_vec((20, 2), vec![0.0; 40]).unwrap();
-    assert!(BART::fit(&y_const, &x, None, None, None, None, None).is_err());
+    assert!(BART::fit(&y_const, &x, None, None, None, None, None, None).is_err());
 }
 
 /// Fitted values and in-sample MSE are self-consistent.
 #[test]
 fn test_bart_fitted_mse_consistency() {
     let (y, x) = make_bart_data(40, 6003);
-    let r = BART::fit(&y, &x, Some(10), Some(2), Some(40), Some(15), None).unwrap();
+    let r = BART::fit(&y, &x, Some(10), Some(2), Some(40), Some(15), None, None).unwrap();
     let sse: f64 = y
         .iter()
         .zip(r.fitted.iter())
@@ -61,3 +61,26 @@ fn test_bart_fitted_mse_consistency() {
         .sum();
     assert!((sse / (r.n_obs as f64) - r.mse).abs() < 1e-10);
 }
+
+/// The retained posterior trees reproduce the fitted values and survive a
+/// save/load round trip.
+#[test]
+fn test_bart_predict_and_persist() {
+    use greeners_core::Persist;
+    use greeners_ml::bart::BartResult;
+
+    let (y, x) = make_bart_data(40, 6004);
+    let r = BART::fit(&y, &x, Some(10), Some(2), Some(30), Some(10), None, None).unwrap();
+    let pred = r.predict(&x).unwrap();
+    for (a, b) in pred.iter().zip(r.fitted.iter()) {
+        assert!((a - b).abs() < 1e-8);
+    }
+    assert!(r.predict(&Array2::zeros((3, 5))).is_err());
+
+    let restored = BartResult::from_bytes(&r.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.predict(&x).unwrap(), pred);
+    let restored = BartResult::from_json(&r.to_json().unwrap()).unwrap();
+    for (a, b) in restored.predict(&x).unwrap().iter().zip(pred.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+}
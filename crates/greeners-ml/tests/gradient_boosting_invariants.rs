@@ -1,5 +1,7 @@
 use greeners_ml::gradient_boosting::GradientBoosting;
+use greeners_ml::gradient_boosting::GradientBoostingClassifier;
 use greeners_ml::gradient_boosting::GradientBoostingResult;
+use greeners_ml::BoostingObjective;
 use ndarray::{Array1, Array2};
 use rand::distributions::Distribution;
 use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -35,12 +37,39 @@ fn assert_gb_result_finite(result: &GradientBoostingResult, n: usize, k: usize)
     assert!(result.n_trees > 0);
 }
 
+fn make_class_data(n: usize, n_classes: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 0.5).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    let mut y = Vec::with_capacity(n);
+    for i in 0..n {
+        let x1 = rng.gen::<f64>() * 4.0 - 2.0;
+        let x2 = rng.gen::<f64>() * 4.0 - 2.0;
+        x[(i, 0)] = x1;
+        x[(i, 1)] = x2;
+        let z = x1 + x2 + noise.sample(&mut rng);
+        // Classes cut the index z into equally spaced bands
+        let band = ((z + 4.0) / 8.0 * n_classes as f64).floor();
+        y.push(band.clamp(0.0, n_classes as f64 - 1.0));
+    }
+    (Array1::from_vec(y), x)
+}
+
+fn assert_proba_valid(proba: &Array2<f64>, n: usize, n_classes: usize) {
+    assert_eq!(proba.dim(), (n, n_classes));
+    for row in proba.rows() {
+        assert!(row.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((row.sum() - 1.0).abs() < 1e-9);
+    }
+}
+
 /// Gradient boosting returns correct shapes and a reasonable fit.
 #[test]
 fn test_gradient_boosting_fit_finite_and_reasonable() {
     let n = 50;
     let (y, x) = make_gb_data(n, 9402);
-    let result = GradientBoosting::fit(&y, &x, 50, Some(0.1), Some(3), Some(1.0), None).unwrap();
+    let result =
+        GradientBoosting::fit(&y, &x, 50, Some(0.1), Some(3), Some(1.0), None, None).unwrap();
     assert_gb_result_finite(&result, n, 2);
     assert!(result.mse < 2.0, "mse = {}", result.mse);
     assert!(result.r_squared > 0.5, "r2 = {}", result.r_squared);
@@ -51,12 +80,89 @@ fn test_gradient_boosting_fit_finite_and_reasonable() {
 fn test_gradient_boosting_input_validation() {
     let y = Array1::from_vec(vec![1.0; 4]);
     let x = Array2::from_shape_vec((4, 1), vec![1.0; 4]).unwrap();
-    assert!(GradientBoosting::fit(&y, &x, 10, None, None, None, None).is_err());
+    assert!(GradientBoosting::fit(&y, &x, 10, None, None, None, None, None).is_err());
 
     let y_ok = Array1::from_vec(vec![1.0; 10]);
     let x_empty = Array2::from_shape_vec((10, 0), vec![]).unwrap();
-    assert!(GradientBoosting::fit(&y_ok, &x_empty, 10, None, None, None, None).is_err());
+    assert!(GradientBoosting::fit(&y_ok, &x_empty, 10, None, None, None, None, None).is_err());
 
     let (y2, x2) = make_gb_data(10, 9403);
-    assert!(GradientBoosting::fit(&y2, &x2, 0, None, None, None, None).is_err());
+    assert!(GradientBoosting::fit(&y2, &x2, 0, None, None, None, None, None).is_err());
+}
+
+/// Predictions on the training data match `fitted`, and the last stage of
+/// the staged predictions equals the full-model prediction.
+#[test]
+fn test_gradient_boosting_predict_and_staged() {
+    let (y, x) = make_gb_data(50, 9403);
+    let result =
+        GradientBoosting::fit(&y, &x, 20, Some(0.1), Some(3), Some(1.0), None, None).unwrap();
+    let pred = result.predict(&x).unwrap();
+    for (a, b) in pred.iter().zip(result.fitted.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    let staged = result.staged_predict(&x).unwrap();
+    assert_eq!(staged.dim(), (20, 50));
+    for (a, b) in staged.row(19).iter().zip(pred.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+    // Training error decreases as trees are added.
+    let mse = |row: ndarray::ArrayView1<f64>| (&y - &row).mapv(|v| v * v).mean().unwrap();
+    assert!(mse(staged.row(19)) < mse(staged.row(0)));
+    assert!(result.staged_predict(&Array2::zeros((2, 3))).is_err());
+}
+
+/// Log-loss boosting for two classes and softmax for three: probabilities are
+/// valid, the in-sample log-loss falls with the iterations, and the staged
+/// probabilities end at `predict_proba`.
+#[test]
+fn test_gradient_boosting_classifier() {
+    let (y, x) = make_class_data(200, 2, 41);
+    let res =
+        GradientBoostingClassifier::fit(&y, &x, 40, Some(0.2), Some(2), None, None, None, None)
+            .unwrap();
+    assert_eq!(res.objective, BoostingObjective::LogLoss);
+    assert_eq!(res.init_scores.len(), 1);
+    assert_proba_valid(&res.fitted_proba, 200, 2);
+    assert!(res.accuracy > 0.85, "accuracy = {}", res.accuracy);
+
+    let staged = res.staged_predict_proba(&x).unwrap();
+    assert_eq!(staged.len(), 40);
+    let loss = |p: &Array2<f64>| -> f64 {
+        y.iter()
+            .enumerate()
+            .map(|(i, &c)| -p[(i, c as usize)].max(1e-15).ln())
+            .sum::<f64>()
+            / 200.0
+    };
+    assert!(loss(&staged[39]) < loss(&staged[0]));
+    assert!((loss(&staged[39]) - res.log_loss).abs() < 1e-9);
+    let proba = res.predict_proba(&x).unwrap();
+    assert!((&proba - &res.fitted_proba).iter().all(|d| d.abs() < 1e-12));
+
+    let (y3, x3) = make_class_data(240, 3, 42);
+    let res3 =
+        GradientBoostingClassifier::fit(&y3, &x3, 30, Some(0.2), Some(2), None, None, None, None)
+            .unwrap();
+    assert_eq!(res3.objective, BoostingObjective::Softmax);
+    assert_eq!(res3.init_scores.len(), 3);
+    assert_proba_valid(&res3.fitted_proba, 240, 3);
+    assert!(res3.accuracy > 0.7, "accuracy = {}", res3.accuracy);
+
+    let labels = GradientBoostingClassifier::fit(
+        &y.mapv(|v| if v > 0.5 { 5.0 } else { -1.0 }),
+        &x,
+        10,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap()
+    .predict(&x)
+    .unwrap();
+    assert!(labels.iter().all(|&v| v == 5.0 || v == -1.0));
 }
@@ -54,7 +54,7 @@ fn assert_grf_result_finite(result: &GrfResult, n: usize, k: usize) {
 fn test_grf_fit_finite_and_reasonable() {
     let n = 60;
     let (y, t, x) = make_grf_data(n, 9416);
-    let result = GRF::fit(&y, &t, &x, Some(50), Some(5), None).unwrap();
+    let result = GRF::fit(&y, &t, &x, Some(50), Some(5), None, None).unwrap();
     assert_grf_result_finite(&result, n, 2);
     assert!((result.ate - 2.0).abs() < 1.0, "ate = {}", result.ate);
 }
@@ -64,14 +64,14 @@ fn test_grf_fit_finite_and_reasonable() {
 fn test_grf_input_validation() {
     let n = 15;
     let (y, t, x) = make_grf_data(n, 9417);
-    assert!(GRF::fit(&y, &t, &x, None, None, None).is_err());
+    assert!(GRF::fit(&y, &t, &x, None, None, None, None).is_err());
 
     let n2 = 60;
     let (y2, _t2, x2) = make_grf_data(n2, 9418);
     let t2_all_false = vec![false; n2];
-    assert!(GRF::fit(&y2, &t2_all_false, &x2, None, None, None).is_err());
+    assert!(GRF::fit(&y2, &t2_all_false, &x2, None, None, None, None).is_err());
 
     let mut t_short = vec![false; n2];
     t_short.pop();
-    assert!(GRF::fit(&y2, &t_short, &x2, None, None, None).is_err());
+    assert!(GRF::fit(&y2, &t_short, &x2, None, None, None, None).is_err());
 }
@@ -42,7 +42,7 @@ fn assert_kmeans_result_finite(result: &KmeansResult, n: usize, k: usize) {
 fn test_kmeans_cluster_recovery() {
     let x = make_kmeans_data(9425);
     let n = x.nrows();
-    let result = KMeans::fit(&x, 3, None, None).unwrap();
+    let result = KMeans::fit(&x, 3, None, None, None).unwrap();
     assert_kmeans_result_finite(&result, n, 3);
     for c in 0..3 {
         assert!(
@@ -58,7 +58,7 @@ fn test_kmeans_cluster_recovery() {
 #[test]
 fn test_kmeans_centroids_near_truth() {
     let x = make_kmeans_data(9426);
-    let result = KMeans::fit(&x, 3, None, None).unwrap();
+    let result = KMeans::fit(&x, 3, None, None, None).unwrap();
     let mut centers: Vec<(f64, f64)> = Vec::new();
     for c in 0..3 {
         centers.push((result.centroids[(c, 0)], result.centroids[(c, 1)]));
@@ -80,6 +80,6 @@ fn test_kmeans_centroids_near_truth() {
 #[test]
 fn test_kmeans_input_validation() {
     let x = make_kmeans_data(9427);
-    assert!(KMeans::fit(&x, 0, None, None).is_err());
-    assert!(KMeans::fit(&x, 1000, None, None).is_err());
+    assert!(KMeans::fit(&x, 0, None, None, None).is_err());
+    assert!(KMeans::fit(&x, 1000, None, None, None).is_err());
 }
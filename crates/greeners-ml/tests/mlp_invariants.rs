@@ -44,7 +44,7 @@ fn test_mlp_fit_finite_and_reasonable() {
     let n = 50;
     let (y, x) = make_mlp_data(n, 9406);
     // Use very few epochs to avoid known gradient instability in the MLP SGD.
-    let result = MLP::fit(&y, &x, 2, Some(0.0001), Some(1), None).unwrap();
+    let result = MLP::fit(&y, &x, 2, Some(0.0001), Some(1), None, None).unwrap();
     assert_mlp_result_finite(&result, n, 2);
     assert!(result.final_mse < 10.0, "mse = {}", result.final_mse);
     assert!(result.r_squared.is_finite(), "r2 = {}", result.r_squared);
@@ -55,12 +55,26 @@ fn test_mlp_fit_finite_and_reasonable() {
 fn test_mlp_input_validation() {
     let y = Array1::from_vec(vec![1.0; 4]);
     let x = Array2::from_shape_vec((4, 1), vec![1.0; 4]).unwrap();
-    assert!(MLP::fit(&y, &x, 5, None, None, None).is_err());
+    assert!(MLP::fit(&y, &x, 5, None, None, None, None).is_err());
 
     let y_ok = Array1::from_vec(vec![1.0; 10]);
     let x_empty = Array2::from_shape_vec((10, 0), vec![]).unwrap();
-    assert!(MLP::fit(&y_ok, &x_empty, 5, None, None, None).is_err());
+    assert!(MLP::fit(&y_ok, &x_empty, 5, None, None, None, None).is_err());
 
     let (y2, x2) = make_mlp_data(10, 9407);
-    assert!(MLP::fit(&y2, &x2, 0, None, None, None).is_err());
+    assert!(MLP::fit(&y2, &x2, 0, None, None, None, None).is_err());
+}
+
+/// Weights depend only on the seed, not on how many fits ran before.
+#[test]
+fn test_mlp_seed_reproducible() {
+    let (y, x) = make_mlp_data(50, 9406);
+    let a = MLP::fit(&y, &x, 3, Some(0.0001), Some(2), None, Some(11)).unwrap();
+    let _ = MLP::fit(&y, &x, 3, Some(0.0001), Some(2), None, None).unwrap();
+    let b = MLP::fit(&y, &x, 3, Some(0.0001), Some(2), None, Some(11)).unwrap();
+    assert_eq!(a.w1, b.w1);
+    assert_eq!(a.fitted, b.fitted);
+
+    let c = MLP::fit(&y, &x, 3, Some(0.0001), Some(2), None, Some(12)).unwrap();
+    assert_ne!(a.w1, c.w1);
 }
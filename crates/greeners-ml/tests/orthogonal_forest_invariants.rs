@@ -54,7 +54,7 @@ fn assert_orf_result_finite(result: &OrfResult, n: usize, k: usize) {
 fn test_orthogonal_forest_fit_finite_and_reasonable() {
     let n = 60;
     let (y, t, x, w) = make_orf_data(n, 9419);
-    let result = OrthogonalForest::fit(&y, &t, &x, &w, Some(30), Some(5), None).unwrap();
+    let result = OrthogonalForest::fit(&y, &t, &x, &w, Some(30), Some(5), None, None).unwrap();
     assert_orf_result_finite(&result, n, 2);
     assert!((result.ate - 2.0).abs() < 2.0, "ate = {}", result.ate);
 }
@@ -64,17 +64,17 @@ fn test_orthogonal_forest_fit_finite_and_reasonable() {
 fn test_orthogonal_forest_input_validation() {
     let n = 20;
     let (y, t, x, w) = make_orf_data(n, 9420);
-    assert!(OrthogonalForest::fit(&y, &t, &x, &w, None, None, None).is_err());
+    assert!(OrthogonalForest::fit(&y, &t, &x, &w, None, None, None, None).is_err());
 
     let n2 = 60;
     let (y2, _t2, x2, w2) = make_orf_data(n2, 9421);
     let t2_all_false = vec![false; n2];
-    assert!(OrthogonalForest::fit(&y2, &t2_all_false, &x2, &w2, None, None, None).is_err());
+    assert!(OrthogonalForest::fit(&y2, &t2_all_false, &x2, &w2, None, None, None, None).is_err());
 
     let mut t_short = vec![false; n2];
     t_short.pop();
-    assert!(OrthogonalForest::fit(&y2, &t_short, &x2, &w2, None, None, None).is_err());
+    assert!(OrthogonalForest::fit(&y2, &t_short, &x2, &w2, None, None, None, None).is_err());
 
     let w_short = Array2::zeros((n2 - 1, 2));
-    assert!(OrthogonalForest::fit(&y2, &_t2, &x2, &w_short, None, None, None).is_err());
+    assert!(OrthogonalForest::fit(&y2, &_t2, &x2, &w_short, None, None, None, None).is_err());
 }
@@ -57,6 +57,7 @@ fn test_qrf_inference_fit_finite_and_bounds() {
         Some(3),
         Some(0.95),
         None,
+        None,
     )
     .unwrap();
     assert_qrfi_result_finite(&result, n, 2, 2);
@@ -77,7 +78,7 @@ fn test_qrf_inference_fit_finite_and_bounds() {
 #[test]
 fn test_qrf_inference_input_validation() {
     let (y, x) = make_qrfi_data(9, 9414);
-    assert!(QrfInference::fit(&y, &x, vec![0.5], None, None, None, None, None).is_err());
+    assert!(QrfInference::fit(&y, &x, vec![0.5], None, None, None, None, None, None).is_err());
 
     let (y2, x2) = make_qrfi_data(12, 9415);
     assert!(QrfInference::fit(
@@ -88,7 +89,8 @@ fn test_qrf_inference_input_validation() {
         Some(5),
         Some(3),
         Some(0.95),
-        None
+        None,
+        None,
     )
     .is_err());
     assert!(QrfInference::fit(
@@ -99,7 +101,8 @@ fn test_qrf_inference_input_validation() {
         Some(5),
         Some(3),
         Some(0.95),
-        None
+        None,
+        None,
     )
     .is_err());
 }
@@ -1,3 +1,4 @@
+use greeners_ml::qrf::QrfClassifier;
 use greeners_ml::qrf::QrfResult;
 use greeners_ml::qrf::QRF;
 use ndarray::{Array1, Array2};
@@ -36,13 +37,39 @@ fn assert_qrf_result_finite(result: &QrfResult, n: usize, k: usize, n_q: usize)
     assert!(result.n_trees > 0);
 }
 
+fn make_class_data(n: usize, n_classes: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 0.5).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    let mut y = Vec::with_capacity(n);
+    for i in 0..n {
+        let x1 = rng.gen::<f64>() * 4.0 - 2.0;
+        let x2 = rng.gen::<f64>() * 4.0 - 2.0;
+        x[(i, 0)] = x1;
+        x[(i, 1)] = x2;
+        let z = x1 + x2 + noise.sample(&mut rng);
+        // Classes cut the index z into equally spaced bands
+        let band = ((z + 4.0) / 8.0 * n_classes as f64).floor();
+        y.push(band.clamp(0.0, n_classes as f64 - 1.0));
+    }
+    (Array1::from_vec(y), x)
+}
+
+fn assert_proba_valid(proba: &Array2<f64>, n: usize, n_classes: usize) {
+    assert_eq!(proba.dim(), (n, n_classes));
+    for row in proba.rows() {
+        assert!(row.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((row.sum() - 1.0).abs() < 1e-9);
+    }
+}
+
 /// QRF quantile predictions have the expected shape, are finite, and are monotonic.
 #[test]
 fn test_qrf_fit_finite_and_monotonic() {
     let n = 50;
     let (y, x) = make_qrf_data(n, 9410);
     let quantiles = vec![0.1, 0.5, 0.9];
-    let result = QRF::fit(&y, &x, quantiles.clone(), 50, 5, None).unwrap();
+    let result = QRF::fit(&y, &x, quantiles.clone(), 50, 5, None, None).unwrap();
     assert_qrf_result_finite(&result, n, 2, 3);
 
     for i in 0..n {
@@ -60,14 +87,41 @@ fn test_qrf_fit_finite_and_monotonic() {
 #[test]
 fn test_qrf_input_validation() {
     let (y, x) = make_qrf_data(10, 9411);
-    assert!(QRF::fit(&y, &x, vec![0.5], 0, 3, None).is_err());
+    assert!(QRF::fit(&y, &x, vec![0.5], 0, 3, None, None).is_err());
 
     let y_short = Array1::from_vec(vec![1.0; 4]);
     let x_short = Array2::from_shape_vec((4, 1), vec![1.0; 4]).unwrap();
-    assert!(QRF::fit(&y_short, &x_short, vec![0.5], 10, 3, None).is_err());
+    assert!(QRF::fit(&y_short, &x_short, vec![0.5], 10, 3, None, None).is_err());
 
     let (y2, x2) = make_qrf_data(10, 9412);
-    assert!(QRF::fit(&y2, &x2, vec![0.0], 10, 3, None).is_err());
-    assert!(QRF::fit(&y2, &x2, vec![1.0], 10, 3, None).is_err());
-    assert!(QRF::fit(&y2, &x2, vec![-0.1], 10, 3, None).is_err());
+    assert!(QRF::fit(&y2, &x2, vec![0.0], 10, 3, None, None).is_err());
+    assert!(QRF::fit(&y2, &x2, vec![1.0], 10, 3, None, None).is_err());
+    assert!(QRF::fit(&y2, &x2, vec![-0.1], 10, 3, None, None).is_err());
+}
+
+/// The pooled class distribution is a valid distribution, and for ordered
+/// classes its median class is between the lowest and the highest label.
+#[test]
+fn test_qrf_classifier() {
+    let (y, x) = make_class_data(200, 3, 91);
+    let res = QrfClassifier::fit(&y, &x, 30, 6, None, None, None, Some(5)).unwrap();
+    assert_eq!(res.classes, vec![0.0, 1.0, 2.0]);
+    assert_proba_valid(&res.class_probabilities, 200, 3);
+    assert!(
+        res.oob_accuracy > 0.6,
+        "oob accuracy = {}",
+        res.oob_accuracy
+    );
+
+    let labels = res.predict(&x).unwrap();
+    let hits = labels.iter().zip(y.iter()).filter(|(a, b)| a == b).count();
+    assert!(hits as f64 / 200.0 > 0.75);
+
+    let q = res.predict_quantiles(&x, &[0.1, 0.5, 0.9]).unwrap();
+    assert_eq!(q.dim(), (200, 3));
+    for row in q.rows() {
+        assert!(row[0] <= row[1] && row[1] <= row[2]);
+        assert!(row.iter().all(|v| res.classes.contains(v)));
+    }
+    assert!(res.predict_quantiles(&x, &[1.0]).is_err());
 }
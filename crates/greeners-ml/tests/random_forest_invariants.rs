@@ -1,5 +1,7 @@
 use greeners_ml::random_forest::RandomForest;
+use greeners_ml::random_forest::RandomForestClassifier;
 use greeners_ml::random_forest::RandomForestResult;
+use greeners_ml::{ClassWeight, SplitCriterion};
 use ndarray::{Array1, Array2};
 use rand::distributions::Distribution;
 use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -38,12 +40,38 @@ fn assert_rf_result_finite(result: &RandomForestResult, n: usize, k: usize) {
     assert!(result.n_trees > 0);
 }
 
+fn make_class_data(n: usize, n_classes: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 0.5).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    let mut y = Vec::with_capacity(n);
+    for i in 0..n {
+        let x1 = rng.gen::<f64>() * 4.0 - 2.0;
+        let x2 = rng.gen::<f64>() * 4.0 - 2.0;
+        x[(i, 0)] = x1;
+        x[(i, 1)] = x2;
+        let z = x1 + x2 + noise.sample(&mut rng);
+        // Classes cut the index z into equally spaced bands
+        let band = ((z + 4.0) / 8.0 * n_classes as f64).floor();
+        y.push(band.clamp(0.0, n_classes as f64 - 1.0));
+    }
+    (Array1::from_vec(y), x)
+}
+
+fn assert_proba_valid(proba: &Array2<f64>, n: usize, n_classes: usize) {
+    assert_eq!(proba.dim(), (n, n_classes));
+    for row in proba.rows() {
+        assert!(row.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((row.sum() - 1.0).abs() < 1e-9);
+    }
+}
+
 /// Random forest returns correct shapes and a reasonable fit.
 #[test]
 fn test_random_forest_fit_finite_and_reasonable() {
     let n = 50;
     let (y, x) = make_rf_data(n, 9404);
-    let result = RandomForest::fit(&y, &x, 50, 5, None).unwrap();
+    let result = RandomForest::fit(&y, &x, 50, 5, None, None).unwrap();
     assert_rf_result_finite(&result, n, 2);
     assert!(result.mse < 2.0, "mse = {}", result.mse);
     assert!(result.r_squared > 0.5, "r2 = {}", result.r_squared);
@@ -54,12 +82,135 @@ fn test_random_forest_fit_finite_and_reasonable() {
 fn test_random_forest_input_validation() {
     let y = Array1::from_vec(vec![1.0; 4]);
     let x = Array2::from_shape_vec((4, 1), vec![1.0; 4]).unwrap();
-    assert!(RandomForest::fit(&y, &x, 10, 3, None).is_err());
+    assert!(RandomForest::fit(&y, &x, 10, 3, None, None).is_err());
 
     let y_ok = Array1::from_vec(vec![1.0; 10]);
     let x_empty = Array2::from_shape_vec((10, 0), vec![]).unwrap();
-    assert!(RandomForest::fit(&y_ok, &x_empty, 10, 3, None).is_err());
+    assert!(RandomForest::fit(&y_ok, &x_empty, 10, 3, None, None).is_err());
 
     let (y2, x2) = make_rf_data(10, 9405);
-    assert!(RandomForest::fit(&y2, &x2, 0, 3, None).is_err());
+    assert!(RandomForest::fit(&y2, &x2, 0, 3, None, None).is_err());
+}
+
+/// The retained trees reproduce the in-sample fit and predict out of sample.
+#[test]
+fn test_random_forest_predict_new_data() {
+    let (y, x) = make_rf_data(60, 9405);
+    let result = RandomForest::fit(&y, &x, 30, 5, None, None).unwrap();
+    let pred = result.predict(&x).unwrap();
+    for (a, b) in pred.iter().zip(result.fitted.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    let (y_new, x_new) = make_rf_data(40, 9406);
+    let pred_new = result.predict(&x_new).unwrap();
+    let mse = (&y_new - &pred_new).mapv(|v| v * v).mean().unwrap();
+    let var = y_new.var(0.0);
+    assert!(mse < var, "mse = {}, var = {}", mse, var);
+    assert!(result.predict(&Array2::zeros((3, 4))).is_err());
+}
+
+/// Trees are built in parallel, but a seeded fit is identical whatever the
+/// number of rayon threads.
+#[test]
+fn test_random_forest_reproducible_across_threads() {
+    let (y, x) = make_rf_data(120, 5150);
+    let fit = || RandomForest::fit(&y, &x, 30, 5, None, Some(3)).unwrap();
+    let single = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(fit);
+    let multi = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(fit);
+    assert_eq!(single.fitted, multi.fitted);
+    assert_eq!(single.oob_predictions, multi.oob_predictions);
+    assert_eq!(single.feature_importance, multi.feature_importance);
+
+    let other = RandomForest::fit(&y, &x, 30, 5, None, Some(4)).unwrap();
+    assert_ne!(single.fitted, other.fitted);
+}
+
+/// Binary and multiclass forests give valid probabilities that separate the
+/// classes; balanced class weights raise the probability of a rare class.
+#[test]
+fn test_random_forest_classifier() {
+    let (y, x) = make_class_data(200, 2, 77);
+    let res = RandomForestClassifier::fit(&y, &x, 40, 5, None, None, None, Some(1)).unwrap();
+    assert_eq!(res.classes, vec![0.0, 1.0]);
+    assert_proba_valid(&res.fitted_proba, 200, 2);
+    assert_proba_valid(&res.oob_proba, 200, 2);
+    assert!(res.accuracy > 0.85, "accuracy = {}", res.accuracy);
+    assert!(
+        res.oob_accuracy > 0.75,
+        "oob accuracy = {}",
+        res.oob_accuracy
+    );
+    assert!(res.log_loss.is_finite());
+    let proba = res.predict_proba(&x).unwrap();
+    assert!((&proba - &res.fitted_proba).iter().all(|d| d.abs() < 1e-12));
+    assert_eq!(res.predict(&x).unwrap().len(), 200);
+
+    let (y3, x3) = make_class_data(240, 3, 78);
+    let res3 = RandomForestClassifier::fit(
+        &y3,
+        &x3,
+        40,
+        6,
+        Some(SplitCriterion::Entropy),
+        None,
+        None,
+        Some(2),
+    )
+    .unwrap();
+    assert_eq!(res3.classes.len(), 3);
+    assert_proba_valid(&res3.fitted_proba, 240, 3);
+    assert!(res3.accuracy > 0.7, "accuracy = {}", res3.accuracy);
+
+    // Rare positive class: balanced weights raise its predicted probability.
+    let y_rare =
+        Array1::from_iter(
+            x.rows()
+                .into_iter()
+                .map(|row| if row[0] + row[1] > 1.5 { 1.0 } else { 0.0 }),
+        );
+    let plain = RandomForestClassifier::fit(&y_rare, &x, 30, 4, None, None, None, Some(3)).unwrap();
+    let balanced = RandomForestClassifier::fit(
+        &y_rare,
+        &x,
+        30,
+        4,
+        None,
+        Some(ClassWeight::Balanced),
+        None,
+        Some(3),
+    )
+    .unwrap();
+    assert!(balanced.fitted_proba.column(1).sum() > plain.fitted_proba.column(1).sum());
+
+    assert!(RandomForestClassifier::fit(
+        &Array1::zeros(20),
+        &Array2::zeros((20, 2)),
+        5,
+        3,
+        None,
+        None,
+        None,
+        None
+    )
+    .is_err());
+    assert!(RandomForestClassifier::fit(
+        &y,
+        &x,
+        5,
+        3,
+        None,
+        Some(ClassWeight::Custom(vec![1.0])),
+        None,
+        None
+    )
+    .is_err());
 }
@@ -35,7 +35,8 @@ fn assert_transformer_result_finite(result: &TransformerResult, n: usize) {
 fn test_transformer_fit_finite_and_reasonable() {
     let n = 60;
     let y = make_transformer_series(n, 9445);
-    let result = Transformer::fit(&y, Some(8), Some(10), Some(0.001), Some(100), Some(5)).unwrap();
+    let result =
+        Transformer::fit(&y, Some(8), Some(10), Some(0.001), Some(100), Some(5), None).unwrap();
     assert_transformer_result_finite(&result, n);
     assert!(result.r_squared > 0.0, "r2 = {}", result.r_squared);
     assert!(result.mse < 20.0, "mse = {}", result.mse);
@@ -45,8 +46,8 @@ fn test_transformer_fit_finite_and_reasonable() {
 #[test]
 fn test_transformer_input_validation() {
     let y_short = Array1::from_vec(vec![1.0; 10]);
-    assert!(Transformer::fit(&y_short, None, None, None, None, None).is_err());
+    assert!(Transformer::fit(&y_short, None, None, None, None, None, None).is_err());
 
     let y_const = Array1::from_vec(vec![1.0; 25]);
-    assert!(Transformer::fit(&y_const, None, None, None, None, None).is_err());
+    assert!(Transformer::fit(&y_const, None, None, None, None, None, None).is_err());
 }
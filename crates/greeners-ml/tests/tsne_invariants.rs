@@ -39,7 +39,7 @@ fn assert_tsne_result_finite(result: &TsneResult, n: usize, n_comp: usize) {
 fn test_tsne_embedding_shape_and_finite() {
     let n = 20;
     let x = make_tsne_data(n, 9439);
-    let result = TSNE::fit(&x, Some(5.0), Some(2), Some(200), Some(50.0)).unwrap();
+    let result = TSNE::fit(&x, Some(5.0), Some(2), Some(200), Some(50.0), None).unwrap();
     assert_tsne_result_finite(&result, n, 2);
 }
 
@@ -48,9 +48,9 @@ fn test_tsne_embedding_shape_and_finite() {
 fn test_tsne_3d_and_validation() {
     let n = 20;
     let x = make_tsne_data(n, 9440);
-    let result = TSNE::fit(&x, Some(5.0), Some(3), Some(200), Some(50.0)).unwrap();
+    let result = TSNE::fit(&x, Some(5.0), Some(3), Some(200), Some(50.0), None).unwrap();
     assert_tsne_result_finite(&result, n, 3);
 
     let x_short = Array2::from_shape_vec((4, 3), vec![1.0; 12]).unwrap();
-    assert!(TSNE::fit(&x_short, None, None, None, None).is_err());
+    assert!(TSNE::fit(&x_short, None, None, None, None, None).is_err());
 }
@@ -39,7 +39,7 @@ fn assert_umap_result_finite(result: &UmapResult, n: usize, n_comp: usize) {
 fn test_umap_embedding_shape_and_finite() {
     let n = 20;
     let x = make_umap_data(n, 9441);
-    let result = UMAP::fit(&x, Some(5), Some(2), Some(0.1), Some(100)).unwrap();
+    let result = UMAP::fit(&x, Some(5), Some(2), Some(0.1), Some(100), None).unwrap();
     assert_umap_result_finite(&result, n, 2);
 }
 
@@ -48,9 +48,9 @@ fn test_umap_embedding_shape_and_finite() {
 fn test_umap_1d_and_validation() {
     let n = 20;
     let x = make_umap_data(n, 9442);
-    let result = UMAP::fit(&x, Some(5), Some(1), Some(0.1), Some(100)).unwrap();
+    let result = UMAP::fit(&x, Some(5), Some(1), Some(0.1), Some(100), None).unwrap();
     assert_umap_result_finite(&result, n, 1);
 
     let x_short = Array2::from_shape_vec((4, 3), vec![1.0; 12]).unwrap();
-    assert!(UMAP::fit(&x_short, None, None, None, None).is_err());
+    assert!(UMAP::fit(&x_short, None, None, None, None, None).is_err());
 }
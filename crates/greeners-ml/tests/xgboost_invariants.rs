@@ -1,5 +1,7 @@
 use greeners_ml::xgboost::XGBoost;
+use greeners_ml::xgboost::XGBoostClassifier;
 use greeners_ml::xgboost::XgboostResult;
+use greeners_ml::{BoostingObjective, ClassWeight};
 use ndarray::{Array1, Array2};
 use rand::distributions::Distribution;
 use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -39,6 +41,32 @@ fn assert_xgb_result_finite(result: &XgboostResult, n: usize, k: usize) {
     assert!(result.gamma >= 0.0);
 }
 
+fn make_class_data(n: usize, n_classes: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 0.5).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    let mut y = Vec::with_capacity(n);
+    for i in 0..n {
+        let x1 = rng.gen::<f64>() * 4.0 - 2.0;
+        let x2 = rng.gen::<f64>() * 4.0 - 2.0;
+        x[(i, 0)] = x1;
+        x[(i, 1)] = x2;
+        let z = x1 + x2 + noise.sample(&mut rng);
+        // Classes cut the index z into equally spaced bands
+        let band = ((z + 4.0) / 8.0 * n_classes as f64).floor();
+        y.push(band.clamp(0.0, n_classes as f64 - 1.0));
+    }
+    (Array1::from_vec(y), x)
+}
+
+fn assert_proba_valid(proba: &Array2<f64>, n: usize, n_classes: usize) {
+    assert_eq!(proba.dim(), (n, n_classes));
+    for row in proba.rows() {
+        assert!(row.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((row.sum() - 1.0).abs() < 1e-9);
+    }
+}
+
 /// XGBoost returns correct shapes and a reasonable fit.
 #[test]
 fn test_xgboost_fit_finite_and_reasonable() {
@@ -56,6 +84,7 @@ fn test_xgboost_fit_finite_and_reasonable() {
         Some(1.0),
         Some(1.0),
         None,
+        None,
     )
     .unwrap();
     assert_xgb_result_finite(&result, n, 2);
@@ -68,14 +97,98 @@ fn test_xgboost_fit_finite_and_reasonable() {
 fn test_xgboost_input_validation() {
     let y = Array1::from_vec(vec![1.0; 4]);
     let x = Array2::from_shape_vec((4, 1), vec![1.0; 4]).unwrap();
-    assert!(XGBoost::fit(&y, &x, 10, None, None, None, None, None, None, None, None).is_err());
+    assert!(
+        XGBoost::fit(&y, &x, 10, None, None, None, None, None, None, None, None, None).is_err()
+    );
 
     let y_ok = Array1::from_vec(vec![1.0; 10]);
     let x_empty = Array2::from_shape_vec((10, 0), vec![]).unwrap();
+    assert!(XGBoost::fit(
+        &y_ok, &x_empty, 10, None, None, None, None, None, None, None, None, None
+    )
+    .is_err());
+
+    let (y2, x2) = make_xgb_data(10, 9409);
     assert!(
-        XGBoost::fit(&y_ok, &x_empty, 10, None, None, None, None, None, None, None, None).is_err()
+        XGBoost::fit(&y2, &x2, 0, None, None, None, None, None, None, None, None, None).is_err()
     );
+}
 
-    let (y2, x2) = make_xgb_data(10, 9409);
-    assert!(XGBoost::fit(&y2, &x2, 0, None, None, None, None, None, None, None, None).is_err());
+/// Predictions on the training data match `fitted`, and staged predictions
+/// end at the full-model prediction.
+#[test]
+fn test_xgboost_predict_and_staged() {
+    let (y, x) = make_xgb_data(50, 9410);
+    let result = XGBoost::fit(
+        &y,
+        &x,
+        15,
+        Some(0.3),
+        Some(3),
+        Some(1.0),
+        Some(0.0),
+        Some(0.0),
+        Some(1.0),
+        Some(1.0),
+        None,
+        None,
+    )
+    .unwrap();
+    let pred = result.predict(&x).unwrap();
+    for (a, b) in pred.iter().zip(result.fitted.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    let staged = result.staged_predict(&x).unwrap();
+    assert_eq!(staged.dim(), (15, 50));
+    for (a, b) in staged.row(14).iter().zip(pred.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    let (_, x_new) = make_xgb_data(10, 9411);
+    assert_eq!(result.predict(&x_new).unwrap().len(), 10);
+    assert!(result.predict(&Array2::zeros((2, 5))).is_err());
+}
+
+/// Logistic and softmax objectives give valid probabilities; class weights
+/// shift probability towards the up-weighted class.
+#[test]
+fn test_xgboost_classifier() {
+    let fit = |y: &Array1<f64>, x: &Array2<f64>, w: Option<ClassWeight>| {
+        XGBoostClassifier::fit(
+            y,
+            x,
+            20,
+            Some(0.3),
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+            None,
+            w,
+            None,
+            None,
+        )
+        .unwrap()
+    };
+
+    let (y, x) = make_class_data(200, 2, 61);
+    let res = fit(&y, &x, None);
+    assert_eq!(res.objective, BoostingObjective::LogLoss);
+    assert_proba_valid(&res.fitted_proba, 200, 2);
+    assert!(res.accuracy > 0.85, "accuracy = {}", res.accuracy);
+    assert!(res.log_loss < 2.0_f64.ln());
+    let proba = res.predict_proba(&x).unwrap();
+    assert!((&proba - &res.fitted_proba).iter().all(|d| d.abs() < 1e-12));
+
+    let weighted = fit(&y, &x, Some(ClassWeight::Custom(vec![1.0, 5.0])));
+    assert!(weighted.fitted_proba.column(1).sum() > res.fitted_proba.column(1).sum());
+
+    let (y3, x3) = make_class_data(240, 3, 62);
+    let res3 = fit(&y3, &x3, None);
+    assert_eq!(res3.objective, BoostingObjective::Softmax);
+    assert_proba_valid(&res3.fitted_proba, 240, 3);
+    assert!(res3.accuracy > 0.7, "accuracy = {}", res3.accuracy);
+    assert_eq!(res3.predict(&x3).unwrap().len(), 240);
 }
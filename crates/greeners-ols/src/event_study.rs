@@ -3,17 +3,33 @@
 //! Estimates dynamic treatment effects by regressing the outcome on
 //! a set of event-time dummies (relative to a reference period),
 //! plus controls and fixed effects.
+//!
+//! Under staggered adoption with heterogeneous effects the single TWFE
+//! regression of [`EventStudy`] mixes already-treated units into the
+//! comparison group. [`StaggeredEventStudy`] offers three estimators that
+//! avoid this, all reported through [`StaggeredResult`]:
+//!
+//! - **Sun & Abraham (2021)**: cohort × relative-period interactions in a
+//!   TWFE regression, averaged with cohort-share weights.
+//! - **Callaway & Sant'Anna (2021)**: group-time ATTs from 2×2 comparisons
+//!   against never-treated or not-yet-treated units.
+//! - **Borusyak, Jaravel & Spiess (2024)**: imputation of untreated
+//!   outcomes from unit and period effects fitted on untreated observations.
 
-use crate::ols::OlsResult;
+use crate::ols::{OlsResult, OLS};
 use greeners_core::error::GreenersError;
-use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::CovarianceType;
+use greeners_core::linalg::{LinalgInverse as _, LinalgPinv, LinalgSVD as _};
+use greeners_core::sandwich::Sandwich;
+use greeners_core::{CovarianceType, DataFrame};
 use ndarray::{Array1, Array2};
-use statrs::distribution::{ContinuousCDF, StudentsT};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal, StudentsT};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 /// Result of an event study estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EventStudyResult {
     /// Coefficients on event-time dummies (excluding reference period)
     pub event_coefs: Array1<f64>,
@@ -146,6 +162,7 @@ impl EventStudy {
         let df_resid = n - k;
         let sigma2 = residuals.dot(&residuals) / df_resid as f64;
 
+        let mut satterthwaite_df = None;
         let cov = match cov_type {
             CovarianceType::NonRobust => xtx_inv * sigma2,
             CovarianceType::HC1 => {
@@ -166,24 +183,23 @@ impl EventStudy {
                 }
                 xtx_inv.dot(&meat).dot(&xtx_inv)
             }
-            _ => {
-                // For other cov types, fall back to HC1
-                let mut meat = Array2::<f64>::zeros((k, k));
-                for i in 0..n {
-                    let xi = x.row(i);
-                    let ei = residuals[i];
-                    let w = if df_resid > 0 {
-                        n as f64 / df_resid as f64
-                    } else {
-                        1.0
-                    };
-                    for a in 0..k {
-                        for b in 0..k {
-                            meat[(a, b)] += w * ei * ei * xi[a] * xi[b];
-                        }
-                    }
+            ref other => {
+                // Every other estimator goes through the OLS sandwich on the
+                // same design, so HAC, spatial and cluster types (including
+                // the CR2/CR3 leverage corrections) are computed exactly
+                let fit = OLS::fit(y, &x, other.clone())?;
+                if !fit.omitted_vars.is_empty() {
+                    return Err(GreenersError::InvalidOperation(
+                        "Event-study design is collinear; cannot compute robust covariance".into(),
+                    ));
                 }
-                xtx_inv.dot(&meat).dot(&xtx_inv)
+                satterthwaite_df = fit.satterthwaite_df;
+                fit.cov_matrix.ok_or_else(|| {
+                    GreenersError::InvalidOperation(format!(
+                        "{} covariance unavailable for event study",
+                        Sandwich::describe(other)
+                    ))
+                })?
             }
         };
 
@@ -239,6 +255,10 @@ impl EventStudy {
             variable_names: None,
             omitted_vars: Vec::new(),
             x_clean: None,
+            design_info: None,
+            row_map: None,
+            cov_matrix: Some(cov),
+            satterthwaite_df,
         };
 
         Ok(EventStudyResult {
@@ -253,3 +273,979 @@ impl EventStudy {
         })
     }
 }
+
+/// Heterogeneity-robust estimator used by [`StaggeredEventStudy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StaggeredMethod {
+    /// Sun & Abraham (2021) interaction-weighted estimator
+    SunAbraham,
+    /// Callaway & Sant'Anna (2021) group-time ATTs
+    CallawaySantAnna,
+    /// Borusyak, Jaravel & Spiess (2024) imputation estimator
+    Imputation,
+}
+
+/// Comparison units for the Callaway–Sant'Anna group-time ATTs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ControlGroup {
+    #[default]
+    NeverTreated,
+    /// Never-treated units and units first treated after both compared periods
+    NotYetTreated,
+}
+
+/// How cohort × period effects are summarized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventAggregation {
+    /// By period relative to first treatment (leads and lags)
+    #[default]
+    EventTime,
+    /// By calendar period, over the cohorts already treated
+    Calendar,
+    /// By cohort, over its post-treatment periods
+    Group,
+}
+
+/// Options for [`StaggeredEventStudy`].
+#[derive(Debug, Clone)]
+pub struct StaggeredConfig {
+    pub aggregation: EventAggregation,
+    /// Comparison group for Callaway–Sant'Anna
+    pub control_group: ControlGroup,
+    /// Omitted relative period for Sun–Abraham and base period for
+    /// Callaway–Sant'Anna; must be negative
+    pub reference: i64,
+    /// Relative periods outside `[min_event_time, max_event_time]` are not
+    /// reported nor used in the pre-trend test
+    pub min_event_time: Option<i64>,
+    pub max_event_time: Option<i64>,
+    /// Significance level of the confidence intervals
+    pub alpha: f64,
+    /// Convergence tolerance of the imputation fixed-effect solver
+    pub tol: f64,
+    pub max_iter: usize,
+}
+
+impl Default for StaggeredConfig {
+    fn default() -> Self {
+        StaggeredConfig {
+            aggregation: EventAggregation::EventTime,
+            control_group: ControlGroup::NeverTreated,
+            reference: -1,
+            min_event_time: None,
+            max_event_time: None,
+            alpha: 0.05,
+            tol: 1e-10,
+            max_iter: 10_000,
+        }
+    }
+}
+
+/// Effect for one treatment cohort in one calendar period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupTimeEffect {
+    /// First treated period of the cohort
+    pub cohort: i64,
+    pub period: i64,
+    pub estimate: f64,
+    pub std_error: f64,
+    /// Treated observations behind the estimate
+    pub n_treated: usize,
+}
+
+impl GroupTimeEffect {
+    /// Period relative to first treatment.
+    pub fn event_time(&self) -> i64 {
+        self.period - self.cohort
+    }
+}
+
+/// Result of [`StaggeredEventStudy`], shared by all three estimators.
+///
+/// Aggregated estimates are averages of the cohort × period effects weighted
+/// by their number of treated observations; the weights are treated as
+/// fixed. Standard errors are clustered by unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaggeredResult {
+    pub method: StaggeredMethod,
+    pub aggregation: EventAggregation,
+    /// Relative period, calendar period or cohort of each estimate
+    pub labels: Vec<i64>,
+    pub estimates: Array1<f64>,
+    pub std_errors: Array1<f64>,
+    pub z_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub conf_lower: Array1<f64>,
+    pub conf_upper: Array1<f64>,
+    /// Treated observations behind each estimate
+    pub n_treated: Vec<usize>,
+    /// Covariance of `estimates`
+    pub cov_matrix: Array2<f64>,
+    /// Average effect over all post-treatment cohort × period cells
+    pub overall_att: f64,
+    pub overall_se: f64,
+    pub overall_p: f64,
+    /// Wald statistic for all reported leads being zero
+    pub pretrend_stat: Option<f64>,
+    /// Rank of the covariance of the leads
+    pub pretrend_df: usize,
+    pub pretrend_p: Option<f64>,
+    /// Underlying cohort × period effects, including leads
+    pub group_time: Vec<GroupTimeEffect>,
+    /// Relative period normalized to zero, if any
+    pub reference: Option<i64>,
+    pub control_group: ControlGroup,
+    pub alpha: f64,
+    pub n_obs: usize,
+    pub n_units: usize,
+    pub n_cohorts: usize,
+    pub n_never_treated: usize,
+    /// Units treated from the first sample period, which are dropped
+    pub n_always_treated: usize,
+}
+
+impl StaggeredResult {
+    fn label_name(&self) -> &'static str {
+        match self.aggregation {
+            EventAggregation::EventTime => "event_time",
+            EventAggregation::Calendar => "period",
+            EventAggregation::Group => "cohort",
+        }
+    }
+
+    /// Estimates and confidence bounds as a table for plotting. With
+    /// event-time aggregation the normalized reference period appears as a
+    /// row of zeros.
+    pub fn plot_data(&self) -> Result<DataFrame, GreenersError> {
+        let mut rows: Vec<(i64, f64, f64, f64, f64)> = (0..self.labels.len())
+            .map(|j| {
+                (
+                    self.labels[j],
+                    self.estimates[j],
+                    self.std_errors[j],
+                    self.conf_lower[j],
+                    self.conf_upper[j],
+                )
+            })
+            .collect();
+        if let (EventAggregation::EventTime, Some(r)) = (self.aggregation, self.reference) {
+            rows.push((r, 0.0, 0.0, 0.0, 0.0));
+            rows.sort_by_key(|row| row.0);
+        }
+        DataFrame::builder()
+            .add_int(self.label_name(), rows.iter().map(|r| r.0).collect())
+            .add_column("estimate", rows.iter().map(|r| r.1).collect())
+            .add_column("std_error", rows.iter().map(|r| r.2).collect())
+            .add_column("conf_lower", rows.iter().map(|r| r.3).collect())
+            .add_column("conf_upper", rows.iter().map(|r| r.4).collect())
+            .build()
+    }
+}
+
+impl fmt::Display for StaggeredResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = match self.method {
+            StaggeredMethod::SunAbraham => " Event Study: Sun-Abraham ",
+            StaggeredMethod::CallawaySantAnna => " Event Study: Callaway-Sant'Anna ",
+            StaggeredMethod::Imputation => " Event Study: Imputation (BJS) ",
+        };
+        writeln!(f, "\n{:=^78}", title)?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "No. Observations:", self.n_obs, "No. Units:", self.n_units
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Cohorts:", self.n_cohorts, "Never treated:", self.n_never_treated
+        )?;
+        if let Some(r) = self.reference {
+            writeln!(f, "{:<20} {:>15}", "Reference period:", r)?;
+        }
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<12} {:>10} {:>10} {:>8} {:>8} {:>10} {:>10}",
+            self.label_name(),
+            "coef",
+            "std err",
+            "z",
+            "P>|z|",
+            "[low",
+            "high]"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for j in 0..self.labels.len() {
+            writeln!(
+                f,
+                "{:<12} {:>10.4} {:>10.4} {:>8.3} {:>8.3} {:>10.4} {:>10.4}",
+                self.labels[j],
+                self.estimates[j],
+                self.std_errors[j],
+                self.z_values[j],
+                self.p_values[j],
+                self.conf_lower[j],
+                self.conf_upper[j]
+            )?;
+        }
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<12} {:>10.4} {:>10.4} {:>8} {:>8.3}",
+            "Overall ATT", self.overall_att, self.overall_se, "", self.overall_p
+        )?;
+        if let (Some(stat), Some(p)) = (self.pretrend_stat, self.pretrend_p) {
+            writeln!(
+                f,
+                "Pre-trend Wald chi2({}) = {:.3}, p = {:.4}",
+                self.pretrend_df, stat, p
+            )?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Event studies for staggered adoption that are robust to effects varying
+/// across cohorts and over time.
+pub struct StaggeredEventStudy;
+
+impl StaggeredEventStudy {
+    /// Estimates from a long panel in a `DataFrame`. `cohort` holds the
+    /// first treated period of each unit, with 0 or a missing value for
+    /// never-treated units. Rows with a missing outcome, unit or period are
+    /// dropped.
+    pub fn from_dataframe(
+        data: &DataFrame,
+        y: &str,
+        unit: &str,
+        time: &str,
+        cohort: &str,
+        method: StaggeredMethod,
+        config: &StaggeredConfig,
+    ) -> Result<StaggeredResult, GreenersError> {
+        let y_col = data.get_column(y)?.to_float();
+        let t_col = data.get_column(time)?.to_float();
+        let g_col = data.get_column(cohort)?.to_float();
+        let mut codes = vec![usize::MAX; data.n_rows()];
+        for (g, rows) in data.group_by(&[unit])?.groups().iter().enumerate() {
+            for &i in rows {
+                codes[i] = g;
+            }
+        }
+        let integral = |v: f64, name: &str| {
+            if v.fract() == 0.0 {
+                Ok(v as i64)
+            } else {
+                Err(GreenersError::InvalidOperation(format!(
+                    "'{}' must hold integer periods",
+                    name
+                )))
+            }
+        };
+        let keep: Vec<usize> = (0..data.n_rows())
+            .filter(|&i| codes[i] != usize::MAX && y_col[i].is_finite() && t_col[i].is_finite())
+            .collect();
+        let times = keep
+            .iter()
+            .map(|&i| integral(t_col[i], time))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cohorts = keep
+            .iter()
+            .map(|&i| match g_col[i] {
+                g if g.is_nan() || g == 0.0 => Ok(None),
+                g => integral(g, cohort).map(Some),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let units: Vec<usize> = keep.iter().map(|&i| codes[i]).collect();
+        let y_kept: Array1<f64> = keep.iter().map(|&i| y_col[i]).collect();
+        Self::fit(&y_kept, &units, &times, &cohorts, method, config)
+    }
+
+    /// Sun–Abraham interaction-weighted event study.
+    pub fn sun_abraham(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        cohort: &[Option<i64>],
+        config: &StaggeredConfig,
+    ) -> Result<StaggeredResult, GreenersError> {
+        Self::fit(y, unit, time, cohort, StaggeredMethod::SunAbraham, config)
+    }
+
+    /// Callaway–Sant'Anna group-time ATTs and their aggregation.
+    pub fn callaway_santanna(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        cohort: &[Option<i64>],
+        config: &StaggeredConfig,
+    ) -> Result<StaggeredResult, GreenersError> {
+        Self::fit(
+            y,
+            unit,
+            time,
+            cohort,
+            StaggeredMethod::CallawaySantAnna,
+            config,
+        )
+    }
+
+    /// Borusyak–Jaravel–Spiess imputation estimator.
+    pub fn imputation(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        cohort: &[Option<i64>],
+        config: &StaggeredConfig,
+    ) -> Result<StaggeredResult, GreenersError> {
+        Self::fit(y, unit, time, cohort, StaggeredMethod::Imputation, config)
+    }
+
+    /// Estimates an event study from a long panel.
+    ///
+    /// # Arguments
+    /// * `y` - Outcome for each observation
+    /// * `unit` - Unit identifier of each observation (arbitrary codes)
+    /// * `time` - Integer calendar period of each observation
+    /// * `cohort` - First treated period of the observation's unit, `None`
+    ///   if never treated. Treatment is absorbing.
+    ///
+    /// Units treated from the first sample period have no untreated
+    /// outcome and are dropped. Without never-treated units the last
+    /// cohort to be treated serves as the comparison group and the periods
+    /// from its adoption on are dropped.
+    pub fn fit(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        cohort: &[Option<i64>],
+        method: StaggeredMethod,
+        config: &StaggeredConfig,
+    ) -> Result<StaggeredResult, GreenersError> {
+        if config.reference >= 0 {
+            return Err(GreenersError::InvalidOperation(
+                "The reference period must precede treatment (reference < 0)".into(),
+            ));
+        }
+        if !(config.alpha > 0.0 && config.alpha < 1.0) {
+            return Err(GreenersError::InvalidOperation(
+                "alpha must lie in (0, 1)".into(),
+            ));
+        }
+        let panel = StaggeredPanel::new(y, unit, time, cohort)?;
+        let cells = match method {
+            StaggeredMethod::SunAbraham => sun_abraham(&panel, config)?,
+            StaggeredMethod::CallawaySantAnna => callaway_santanna(&panel, config)?,
+            StaggeredMethod::Imputation => imputation(&panel, config)?,
+        };
+
+        let in_window = |e: i64| {
+            config.min_event_time.is_none_or(|m| e >= m)
+                && config.max_event_time.is_none_or(|m| e <= m)
+        };
+        let agg = match config.aggregation {
+            EventAggregation::EventTime => {
+                cells.aggregate(|g, t| Some(t - g).filter(|&e| in_window(e)))
+            }
+            EventAggregation::Calendar => cells.aggregate(|g, t| (t >= g).then_some(t)),
+            EventAggregation::Group => cells.aggregate(|g, t| (t >= g).then_some(g)),
+        };
+        let overall = cells.aggregate(|g, t| (t >= g).then_some(0));
+        if overall.labels.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "No post-treatment effect could be estimated".into(),
+            ));
+        }
+
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let crit = normal.inverse_cdf(1.0 - config.alpha / 2.0);
+        let p_value = |z: f64| 2.0 * (1.0 - normal.cdf(z.abs()));
+        let std_errors = agg.cov.diag().mapv(|v| v.max(0.0).sqrt());
+        let z_values = &agg.estimates / &std_errors;
+        let overall_se = overall.cov[[0, 0]].max(0.0).sqrt();
+        // Without sampling variation a zero effect is no evidence against
+        // the null; a nonzero one gets z = ±∞
+        let overall_z = if overall_se > 0.0 || overall.estimates[0] != 0.0 {
+            overall.estimates[0] / overall_se
+        } else {
+            0.0
+        };
+
+        // Joint test of the leads; the pseudo-inverse only uses the
+        // directions the covariance spans, so its rank gives the df
+        let pre = cells.aggregate(|g, t| Some(t - g).filter(|&e| e < 0 && in_window(e)));
+        let pretrend_df = numerical_rank(&pre.cov)?;
+        let (pretrend_stat, pretrend_p) = if pretrend_df == 0 {
+            (None, None)
+        } else {
+            let stat = pre.estimates.dot(&pre.cov.pinv()?.dot(&pre.estimates));
+            let chi2 = ChiSquared::new(pretrend_df as f64)
+                .map_err(|e| GreenersError::InvalidOperation(e.to_string()))?;
+            (Some(stat), Some(1.0 - chi2.cdf(stat)))
+        };
+
+        let group_time = cells
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(c, &(g, t))| GroupTimeEffect {
+                cohort: g,
+                period: t,
+                estimate: cells.estimates[c],
+                std_error: cells.cov[[c, c]].max(0.0).sqrt(),
+                n_treated: cells.counts[c],
+            })
+            .collect();
+        let reference = match method {
+            StaggeredMethod::Imputation => None,
+            _ => Some(config.reference).filter(|&r| in_window(r)),
+        };
+        let mut cohorts: Vec<i64> = panel.cohort.iter().flatten().copied().collect();
+        cohorts.sort_unstable();
+        cohorts.dedup();
+
+        Ok(StaggeredResult {
+            method,
+            aggregation: config.aggregation,
+            labels: agg.labels,
+            p_values: z_values.mapv(p_value),
+            conf_lower: &agg.estimates - &(&std_errors * crit),
+            conf_upper: &agg.estimates + &(&std_errors * crit),
+            estimates: agg.estimates,
+            std_errors,
+            z_values,
+            n_treated: agg.counts,
+            cov_matrix: agg.cov,
+            overall_att: overall.estimates[0],
+            overall_se,
+            overall_p: p_value(overall_z),
+            pretrend_stat,
+            pretrend_df,
+            pretrend_p,
+            group_time,
+            reference,
+            control_group: config.control_group,
+            alpha: config.alpha,
+            n_obs: panel.y.len(),
+            n_units: panel.cohort.len(),
+            n_cohorts: cohorts.len(),
+            n_never_treated: panel.cohort.iter().filter(|g| g.is_none()).count(),
+            n_always_treated: panel.n_always_treated,
+        })
+    }
+}
+
+/// Long panel with dense unit and period codes.
+struct StaggeredPanel {
+    y: Vec<f64>,
+    unit: Vec<usize>,
+    period: Vec<usize>,
+    periods: Vec<i64>,
+    /// First treated period of each unit, `None` if untreated in the sample
+    cohort: Vec<Option<i64>>,
+    n_always_treated: usize,
+}
+
+impl StaggeredPanel {
+    fn new(
+        y: &Array1<f64>,
+        unit: &[usize],
+        time: &[i64],
+        cohort: &[Option<i64>],
+    ) -> Result<Self, GreenersError> {
+        let n = y.len();
+        if unit.len() != n || time.len() != n || cohort.len() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "y, unit, time and cohort must have the same length".into(),
+            ));
+        }
+        if n == 0 || y.iter().any(|v| !v.is_finite()) {
+            return Err(GreenersError::InvalidOperation(
+                "y must be non-empty and finite".into(),
+            ));
+        }
+        let t_min = *time.iter().min().unwrap();
+        let t_max = *time.iter().max().unwrap();
+
+        // Cohorts first treated after the sample are never treated in it
+        let mut unit_cohort: HashMap<usize, Option<i64>> = HashMap::new();
+        for i in 0..n {
+            let g = cohort[i].filter(|&g| g <= t_max);
+            if let Some(prev) = unit_cohort.insert(unit[i], g) {
+                if prev != g {
+                    return Err(GreenersError::InvalidOperation(format!(
+                        "Unit {} has more than one first-treatment period",
+                        unit[i]
+                    )));
+                }
+            }
+        }
+        let always = |u: usize| matches!(unit_cohort[&u], Some(g) if g <= t_min);
+        let n_always_treated = unit_cohort.keys().filter(|&&u| always(u)).count();
+        let mut keep: Vec<usize> = (0..n).filter(|&i| !always(unit[i])).collect();
+
+        // Without never-treated units the last cohort is the comparison group
+        let mut last = None;
+        if keep.iter().all(|&i| unit_cohort[&unit[i]].is_some()) {
+            last = keep.iter().filter_map(|&i| unit_cohort[&unit[i]]).max();
+            if let Some(g_last) = last {
+                keep.retain(|&i| time[i] < g_last);
+            }
+        }
+
+        let mut periods: Vec<i64> = keep.iter().map(|&i| time[i]).collect();
+        periods.sort_unstable();
+        periods.dedup();
+        let mut codes: HashMap<usize, usize> = HashMap::new();
+        let mut cohorts = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let (mut y_out, mut unit_out, mut period_out) = (vec![], vec![], vec![]);
+        for &i in &keep {
+            let next = codes.len();
+            let u = *codes.entry(unit[i]).or_insert_with(|| {
+                cohorts.push(unit_cohort[&unit[i]].filter(|&g| Some(g) != last));
+                next
+            });
+            let p = periods.binary_search(&time[i]).unwrap();
+            if !seen.insert((u, p)) {
+                return Err(GreenersError::InvalidOperation(format!(
+                    "Unit {} is observed more than once in period {}",
+                    unit[i], time[i]
+                )));
+            }
+            y_out.push(y[i]);
+            unit_out.push(u);
+            period_out.push(p);
+        }
+        if !cohorts.iter().any(Option::is_some) || !cohorts.iter().any(Option::is_none) {
+            return Err(GreenersError::InvalidOperation(
+                "Need a treated cohort and a comparison group".into(),
+            ));
+        }
+        Ok(StaggeredPanel {
+            y: y_out,
+            unit: unit_out,
+            period: period_out,
+            periods,
+            cohort: cohorts,
+            n_always_treated,
+        })
+    }
+
+    fn n_units(&self) -> usize {
+        self.cohort.len()
+    }
+
+    fn is_treated(&self, i: usize) -> bool {
+        matches!(self.cohort[self.unit[i]], Some(g) if self.periods[self.period[i]] >= g)
+    }
+
+    /// Observations of treated cohorts by (cohort, period index), for the
+    /// cohort and calendar period pairs accepted by `include`.
+    fn cells(&self, include: impl Fn(i64, i64) -> bool) -> BTreeMap<(i64, usize), Vec<usize>> {
+        let mut cells: BTreeMap<(i64, usize), Vec<usize>> = BTreeMap::new();
+        for i in 0..self.y.len() {
+            if let Some(g) = self.cohort[self.unit[i]] {
+                if include(g, self.periods[self.period[i]]) {
+                    cells.entry((g, self.period[i])).or_default().push(i);
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Cohort × period effects and their joint covariance.
+struct CellEstimates {
+    /// (cohort, calendar period)
+    cells: Vec<(i64, i64)>,
+    estimates: Array1<f64>,
+    counts: Vec<usize>,
+    cov: Array2<f64>,
+}
+
+/// Count-weighted averages of cells sharing a label.
+struct Aggregate {
+    labels: Vec<i64>,
+    estimates: Array1<f64>,
+    cov: Array2<f64>,
+    counts: Vec<usize>,
+}
+
+impl CellEstimates {
+    fn aggregate(&self, label: impl Fn(i64, i64) -> Option<i64>) -> Aggregate {
+        let mut groups: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+        for (c, &(g, t)) in self.cells.iter().enumerate() {
+            if let Some(l) = label(g, t) {
+                groups.entry(l).or_default().push(c);
+            }
+        }
+        let mut w = Array2::<f64>::zeros((groups.len(), self.cells.len()));
+        let mut counts = Vec::with_capacity(groups.len());
+        for (r, members) in groups.values().enumerate() {
+            let total: usize = members.iter().map(|&c| self.counts[c]).sum();
+            for &c in members {
+                w[[r, c]] = self.counts[c] as f64 / total as f64;
+            }
+            counts.push(total);
+        }
+        Aggregate {
+            labels: groups.into_keys().collect(),
+            estimates: w.dot(&self.estimates),
+            cov: w.dot(&self.cov).dot(&w.t()),
+            counts,
+        }
+    }
+
+    /// Stacks two independently estimated sets of cells.
+    fn append(self, other: CellEstimates) -> CellEstimates {
+        let (a, b) = (self.cells.len(), other.cells.len());
+        let mut cov = Array2::<f64>::zeros((a + b, a + b));
+        cov.slice_mut(ndarray::s![..a, ..a]).assign(&self.cov);
+        cov.slice_mut(ndarray::s![a.., a..]).assign(&other.cov);
+        CellEstimates {
+            cells: self.cells.into_iter().chain(other.cells).collect(),
+            estimates: self
+                .estimates
+                .iter()
+                .chain(&other.estimates)
+                .copied()
+                .collect(),
+            counts: self.counts.into_iter().chain(other.counts).collect(),
+            cov,
+        }
+    }
+}
+
+/// Number of singular values above the cutoff `pinv` uses.
+fn numerical_rank(m: &Array2<f64>) -> Result<usize, GreenersError> {
+    if m.is_empty() {
+        return Ok(0);
+    }
+    let (_, s, _) = m.svd(false, false)?;
+    let tol =
+        s.iter().copied().fold(0.0, f64::max) * f64::EPSILON * m.nrows().max(m.ncols()) as f64;
+    Ok(s.iter().filter(|&&v| v > tol).count())
+}
+
+/// Regresses `y` on `x` and unit fixed effects, absorbed by demeaning, with
+/// standard errors clustered by unit. Returns the cells of `x` that survive
+/// the collinearity check and their estimates.
+fn within_unit_cells(
+    panel: &StaggeredPanel,
+    rows: &[usize],
+    cells: &BTreeMap<(i64, usize), Vec<usize>>,
+) -> Result<CellEstimates, GreenersError> {
+    let n = rows.len();
+    let n_periods = panel.periods.len();
+    let mut row_of = vec![usize::MAX; panel.y.len()];
+    for (r, &i) in rows.iter().enumerate() {
+        row_of[i] = r;
+    }
+    // Period dummies (first period omitted) followed by cell dummies
+    let mut x = Array2::<f64>::zeros((n, n_periods - 1 + cells.len()));
+    for (r, &i) in rows.iter().enumerate() {
+        if panel.period[i] > 0 {
+            x[[r, panel.period[i] - 1]] = 1.0;
+        }
+    }
+    for (c, obs) in cells.values().enumerate() {
+        for &i in obs {
+            x[[row_of[i], n_periods - 1 + c]] = 1.0;
+        }
+    }
+    let units: Vec<usize> = rows.iter().map(|&i| panel.unit[i]).collect();
+    let mut size = vec![0.0; panel.n_units()];
+    for &u in &units {
+        size[u] += 1.0;
+    }
+    let mut y: Array1<f64> = rows.iter().map(|&i| panel.y[i]).collect();
+    for mut col in std::iter::once(y.view_mut()).chain(x.columns_mut()) {
+        let mut mean = vec![0.0; panel.n_units()];
+        for (v, &u) in col.iter().zip(&units) {
+            mean[u] += v / size[u];
+        }
+        for (v, &u) in col.iter_mut().zip(&units) {
+            *v -= mean[u];
+        }
+    }
+
+    let names: Vec<String> = (0..x.ncols()).map(|j| format!("c{}", j)).collect();
+    let ols = OLS::fit_with_names(
+        &y,
+        &x,
+        CovarianceType::Clustered(units),
+        Some(names.clone()),
+    )?;
+    let cov = ols
+        .cov_matrix
+        .as_ref()
+        .ok_or_else(|| GreenersError::InvalidOperation("Missing covariance matrix".into()))?;
+    let kept = ols.variable_names.clone().unwrap_or(names);
+    let position: HashMap<&str, usize> = kept
+        .iter()
+        .enumerate()
+        .map(|(p, name)| (name.as_str(), p))
+        .collect();
+    let found: Vec<((i64, usize), usize, usize)> = cells
+        .iter()
+        .enumerate()
+        .filter_map(|(c, (&key, obs))| {
+            let p = position.get(format!("c{}", n_periods - 1 + c).as_str())?;
+            Some((key, *p, obs.len()))
+        })
+        .collect();
+    Ok(CellEstimates {
+        cells: found
+            .iter()
+            .map(|&((g, t), _, _)| (g, panel.periods[t]))
+            .collect(),
+        estimates: found.iter().map(|&(_, p, _)| ols.params[p]).collect(),
+        counts: found.iter().map(|&(_, _, count)| count).collect(),
+        cov: Array2::from_shape_fn((found.len(), found.len()), |(a, b)| {
+            cov[[found[a].1, found[b].1]]
+        }),
+    })
+}
+
+/// Saturated TWFE regression with cohort × period dummies for every
+/// treated cohort except in its reference period.
+fn sun_abraham(
+    panel: &StaggeredPanel,
+    config: &StaggeredConfig,
+) -> Result<CellEstimates, GreenersError> {
+    let cells = panel.cells(|g, t| t - g != config.reference);
+    let rows: Vec<usize> = (0..panel.y.len()).collect();
+    within_unit_cells(panel, &rows, &cells)
+}
+
+/// 2×2 comparisons of the change since the cohort's base period between
+/// the cohort and the comparison units.
+fn callaway_santanna(
+    panel: &StaggeredPanel,
+    config: &StaggeredConfig,
+) -> Result<CellEstimates, GreenersError> {
+    let (n_units, n_periods) = (panel.n_units(), panel.periods.len());
+    let mut y = Array2::from_elem((n_units, n_periods), f64::NAN);
+    for i in 0..panel.y.len() {
+        y[[panel.unit[i], panel.period[i]]] = panel.y[i];
+    }
+    let mut cohorts: Vec<i64> = panel.cohort.iter().flatten().copied().collect();
+    cohorts.sort_unstable();
+    cohorts.dedup();
+
+    let (mut cells, mut estimates, mut counts, mut scores) = (vec![], vec![], vec![], vec![]);
+    for &g in &cohorts {
+        let base = match panel.periods.binary_search(&(g + config.reference)) {
+            Ok(b) => b,
+            Err(_) => continue,
+        };
+        for t in (0..n_periods).filter(|&t| t != base) {
+            let last = panel.periods[t].max(panel.periods[base]);
+            let diff = |u: usize| y[[u, t]] - y[[u, base]];
+            let treated: Vec<usize> = (0..n_units)
+                .filter(|&u| panel.cohort[u] == Some(g) && diff(u).is_finite())
+                .collect();
+            let control: Vec<usize> = (0..n_units)
+                .filter(|&u| {
+                    let clean = match panel.cohort[u] {
+                        None => true,
+                        Some(h) => {
+                            config.control_group == ControlGroup::NotYetTreated
+                                && h != g
+                                && h > last
+                        }
+                    };
+                    clean && diff(u).is_finite()
+                })
+                .collect();
+            if treated.is_empty() || control.is_empty() {
+                continue;
+            }
+            let mean =
+                |units: &[usize]| units.iter().map(|&u| diff(u)).sum::<f64>() / units.len() as f64;
+            let (m_treated, m_control) = (mean(&treated), mean(&control));
+            // Influence function, scaled so that the covariance is S'S
+            let mut score = vec![0.0; n_units];
+            for &u in &treated {
+                score[u] = (diff(u) - m_treated) / treated.len() as f64;
+            }
+            for &u in &control {
+                score[u] = -(diff(u) - m_control) / control.len() as f64;
+            }
+            cells.push((g, panel.periods[t]));
+            estimates.push(m_treated - m_control);
+            counts.push(treated.len());
+            scores.push(score);
+        }
+    }
+    let s = Array2::from_shape_fn((n_units, scores.len()), |(u, c)| scores[c][u]);
+    Ok(CellEstimates {
+        cells,
+        estimates: Array1::from(estimates),
+        counts,
+        cov: s.t().dot(&s),
+    })
+}
+
+/// Gauss–Seidel solver for the normal equations of a regression on unit and
+/// period dummies over a set of observations.
+struct TwoWaySolver<'a> {
+    obs: &'a [(usize, usize)],
+    n_unit: &'a [usize],
+    n_period: &'a [usize],
+    tol: f64,
+    max_iter: usize,
+}
+
+impl TwoWaySolver<'_> {
+    /// Unit and period coefficients given the right-hand sides of the
+    /// normal equations.
+    fn solve(
+        &self,
+        h_unit: &[f64],
+        h_period: &[f64],
+    ) -> Result<(Vec<f64>, Vec<f64>), GreenersError> {
+        let mut a = vec![0.0; self.n_unit.len()];
+        let mut b = vec![0.0; self.n_period.len()];
+        for _ in 0..self.max_iter {
+            let mut delta: f64 = 0.0;
+            let mut s = h_unit.to_vec();
+            for &(u, t) in self.obs {
+                s[u] -= b[t];
+            }
+            for (u, &count) in self.n_unit.iter().enumerate().filter(|(_, &c)| c > 0) {
+                let new = s[u] / count as f64;
+                delta = delta.max((new - a[u]).abs());
+                a[u] = new;
+            }
+            let mut s = h_period.to_vec();
+            for &(u, t) in self.obs {
+                s[t] -= a[u];
+            }
+            for (t, &count) in self.n_period.iter().enumerate().filter(|(_, &c)| c > 0) {
+                let new = s[t] / count as f64;
+                delta = delta.max((new - b[t]).abs());
+                b[t] = new;
+            }
+            let scale = a.iter().chain(&b).fold(1.0_f64, |m, v| m.max(v.abs()));
+            if delta <= self.tol * scale {
+                return Ok((a, b));
+            }
+        }
+        Err(GreenersError::OptimizationFailed)
+    }
+}
+
+/// Imputes untreated outcomes of treated observations from unit and period
+/// effects fitted on untreated observations. Leads come from a TWFE
+/// regression on the untreated observations, each cohort's earliest period
+/// serving as reference; they are taken as independent of the effects.
+fn imputation(
+    panel: &StaggeredPanel,
+    config: &StaggeredConfig,
+) -> Result<CellEstimates, GreenersError> {
+    let n = panel.y.len();
+    let (n_units, n_periods) = (panel.n_units(), panel.periods.len());
+    let untreated: Vec<usize> = (0..n).filter(|&i| !panel.is_treated(i)).collect();
+    let obs: Vec<(usize, usize)> = untreated
+        .iter()
+        .map(|&i| (panel.unit[i], panel.period[i]))
+        .collect();
+    let (mut n_unit, mut n_period) = (vec![0; n_units], vec![0; n_periods]);
+    let (mut h_unit, mut h_period) = (vec![0.0; n_units], vec![0.0; n_periods]);
+    for &i in &untreated {
+        n_unit[panel.unit[i]] += 1;
+        n_period[panel.period[i]] += 1;
+        h_unit[panel.unit[i]] += panel.y[i];
+        h_period[panel.period[i]] += panel.y[i];
+    }
+    let solver = TwoWaySolver {
+        obs: &obs,
+        n_unit: &n_unit,
+        n_period: &n_period,
+        tol: config.tol,
+        max_iter: config.max_iter,
+    };
+    let (alpha, lambda) = solver.solve(&h_unit, &h_period)?;
+    let residual = |i: usize| panel.y[i] - alpha[panel.unit[i]] - lambda[panel.period[i]];
+
+    // Treated observations whose unit and period both have untreated ones
+    let cells: Vec<((i64, usize), Vec<usize>)> = panel
+        .cells(|g, t| t >= g)
+        .into_iter()
+        .map(|(key, obs)| {
+            let obs: Vec<usize> = obs
+                .into_iter()
+                .filter(|&i| n_unit[panel.unit[i]] > 0 && n_period[panel.period[i]] > 0)
+                .collect();
+            (key, obs)
+        })
+        .filter(|(_, obs)| !obs.is_empty())
+        .collect();
+    let estimates: Vec<f64> = cells
+        .iter()
+        .map(|(_, obs)| obs.iter().map(|&i| residual(i)).sum::<f64>() / obs.len() as f64)
+        .collect();
+
+    // Untreated fit errors and treated effects net of their cell average
+    let mut resid = vec![0.0; n];
+    for &i in &untreated {
+        resid[i] = residual(i);
+    }
+    for ((_, obs), &est) in cells.iter().zip(&estimates) {
+        for &i in obs {
+            resid[i] = residual(i) - est;
+        }
+    }
+    // Each cell average is linear in y; the weights on untreated outcomes
+    // solve the normal equations with the cell's unit and period totals
+    let scores: Vec<Vec<f64>> = cells
+        .par_iter()
+        .map(|((_, t), obs)| {
+            let w = 1.0 / obs.len() as f64;
+            let mut h_unit = vec![0.0; n_units];
+            let mut h_period = vec![0.0; n_periods];
+            for &i in obs {
+                h_unit[panel.unit[i]] += w;
+            }
+            h_period[*t] = 1.0;
+            let (a, b) = solver.solve(&h_unit, &h_period)?;
+            let mut score = vec![0.0; n_units];
+            for &i in &untreated {
+                let (u, p) = (panel.unit[i], panel.period[i]);
+                score[u] -= (a[u] + b[p]) * resid[i];
+            }
+            for &i in obs {
+                score[panel.unit[i]] += w * resid[i];
+            }
+            Ok(score)
+        })
+        .collect::<Result<_, GreenersError>>()?;
+    let s = Array2::from_shape_fn((n_units, scores.len()), |(u, c)| scores[c][u]);
+    let effects = CellEstimates {
+        cells: cells
+            .iter()
+            .map(|&((g, t), _)| (g, panel.periods[t]))
+            .collect(),
+        estimates: Array1::from(estimates),
+        counts: cells.iter().map(|(_, obs)| obs.len()).collect(),
+        cov: s.t().dot(&s),
+    };
+
+    let mut first: HashMap<i64, usize> = HashMap::new();
+    for &i in &untreated {
+        if let Some(g) = panel.cohort[panel.unit[i]] {
+            let p = first.entry(g).or_insert(panel.period[i]);
+            *p = (*p).min(panel.period[i]);
+        }
+    }
+    let lo = config.min_event_time.unwrap_or(i64::MIN);
+    let mut leads = panel.cells(|g, t| t < g && t - g >= lo);
+    leads.retain(|&(g, p), _| p > first[&g]);
+    if leads.is_empty() {
+        return Ok(effects);
+    }
+    Ok(effects.append(within_unit_cells(panel, &untreated, &leads)?))
+}
@@ -18,11 +18,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of FMOLS estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FmolsResult {
     /// Intercept
     pub alpha: f64,
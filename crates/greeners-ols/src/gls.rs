@@ -1,10 +1,11 @@
 use crate::ols::OLS;
-use greeners_core::{CovarianceType, DataFrame, Formula, GreenersError};
+use greeners_core::{CovarianceType, DataFrame, Formula, GreenersError, MissingPolicy, RowMap};
 use ndarray::{s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// FGLS Result
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FglsResult {
     pub method: String, // "WLS" or "Cochrane-Orcutt"
     pub params: Array1<f64>,
@@ -15,6 +16,7 @@ pub struct FglsResult {
     pub rho: Option<f64>,    // Only for Cochrane-Orcutt
     pub iter: Option<usize>, // Iterations until convergence
     pub variable_names: Option<Vec<String>>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for FglsResult {
@@ -86,10 +88,36 @@ impl FGLS {
         data: &DataFrame,
         weights: &Array1<f64>,
     ) -> Result<FglsResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
+        Self::wls_from_formula_with_missing(formula, data, weights, MissingPolicy::Drop)
+    }
 
-        Self::wls_with_names(&y, &x, weights, Some(var_names))
+    /// [`FGLS::wls_from_formula`] with an explicit missing-value policy.
+    ///
+    /// `weights` must be aligned with the rows of `data`; rows with a NaN
+    /// weight are treated as missing.
+    pub fn wls_from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        weights: &Array1<f64>,
+        missing: MissingPolicy,
+    ) -> Result<FglsResult, GreenersError> {
+        if weights.len() != data.n_rows() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "weights length ({}) must match DataFrame rows ({})",
+                weights.len(),
+                data.n_rows()
+            )));
+        }
+        let nan_weights: Vec<usize> = (0..weights.len())
+            .filter(|&i| weights[i].is_nan())
+            .collect();
+        let dm = data.design_matrices_with_mask(formula, missing, &nan_weights)?;
+        let var_names = dm.info.column_names();
+        let weights = dm.rows.select(weights);
+
+        let mut result = Self::wls_with_names(&dm.y, &dm.x, &weights, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Weighted Least Squares (WLS)
@@ -160,6 +188,7 @@ impl FGLS {
             rho: None,
             iter: None,
             variable_names,
+            row_map: None,
         })
     }
 
@@ -168,10 +197,23 @@ impl FGLS {
         formula: &Formula,
         data: &DataFrame,
     ) -> Result<FglsResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
+        Self::cochrane_orcutt_from_formula_with_missing(formula, data, MissingPolicy::Drop)
+    }
+
+    /// [`FGLS::cochrane_orcutt_from_formula`] with an explicit missing-value
+    /// policy. Dropping rows removes them from the time series, so the AR(1)
+    /// is estimated across the gaps.
+    pub fn cochrane_orcutt_from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        missing: MissingPolicy,
+    ) -> Result<FglsResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
 
-        Self::cochrane_orcutt_with_names(&y, &x, Some(var_names))
+        let mut result = Self::cochrane_orcutt_with_names(&dm.y, &dm.x, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Cochrane-Orcutt Iterative Procedure (AR(1))
@@ -252,6 +294,7 @@ impl FGLS {
             rho: Some(rho),
             iter: Some(iter),
             variable_names,
+            row_map: None,
         })
     }
 }
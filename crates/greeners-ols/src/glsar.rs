@@ -1,12 +1,13 @@
 use crate::ols::OLS;
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{CovarianceType, DataFrame, Formula};
+use greeners_core::{CovarianceType, DataFrame, Formula, MissingPolicy, RowMap};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of GLSAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GlsarResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -19,6 +20,7 @@ pub struct GlsarResult {
     pub n_obs: usize,
     pub df_resid: usize,
     pub variable_names: Option<Vec<String>>,
+    pub row_map: Option<RowMap>,
 }
 
 impl GlsarResult {
@@ -87,9 +89,22 @@ impl GLSAR {
         ar_order: usize,
         max_iter: usize,
     ) -> Result<GlsarResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, ar_order, max_iter, Some(var_names))
+        Self::from_formula_with_missing(formula, data, ar_order, max_iter, MissingPolicy::Drop)
+    }
+
+    /// GLSAR from a formula with an explicit missing-value policy.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        ar_order: usize,
+        max_iter: usize,
+        missing: MissingPolicy,
+    ) -> Result<GlsarResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, ar_order, max_iter, Some(var_names))?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     pub fn fit(
@@ -239,6 +254,7 @@ impl GLSAR {
             n_obs: n,
             df_resid,
             variable_names,
+            row_map: None,
         })
     }
 }
@@ -1,11 +1,14 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::types::CovarianceType;
+use greeners_core::Sandwich;
 use ndarray as nd;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GmmResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -192,3 +195,790 @@ impl GMM {
         })
     }
 }
+
+// ─── Nonlinear GMM ───────────────────────────────────────────────────────────
+
+/// Per-observation moment function g(θ, data_i) returning m moments.
+pub type MomentFn<'a> = &'a dyn Fn(&[f64], &[f64]) -> Array1<f64>;
+
+/// Per-observation Jacobian ∂g(θ, data_i)/∂θ' (m × k).
+pub type MomentJacobianFn<'a> = &'a dyn Fn(&[f64], &[f64]) -> Array2<f64>;
+
+/// Weighting scheme for [`NonlinearGMM`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GmmWeighting {
+    /// Identity weight (or `initial_weight`); inefficient but consistent
+    OneStep,
+    /// Efficient weight estimated at the one-step estimates
+    TwoStep,
+    /// Re-estimate the weight until the parameters converge (at most the
+    /// given number of weight updates)
+    Iterated(usize),
+    /// Continuously-updated estimator: the weight is a function of θ
+    ContinuouslyUpdated,
+}
+
+impl fmt::Display for GmmWeighting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GmmWeighting::OneStep => write!(f, "One-Step"),
+            GmmWeighting::TwoStep => write!(f, "Two-Step"),
+            GmmWeighting::Iterated(_) => write!(f, "Iterated"),
+            GmmWeighting::ContinuouslyUpdated => write!(f, "CUE"),
+        }
+    }
+}
+
+/// Options for [`NonlinearGMM`].
+///
+/// `cov_type` selects the long-run covariance S of the moments used for the
+/// efficient weight matrix and the standard errors: `NonRobust` and HC0–HC4
+/// all use the heteroskedasticity-robust outer product (1/n)Σ g_i g_i',
+/// `NeweyWest` adds Bartlett-weighted autocovariances and the clustered
+/// variants sum moments within clusters, with the same G/(G-1) correction
+/// as the other cluster-robust estimators.
+#[derive(Debug, Clone)]
+pub struct NonlinearGmmOptions {
+    pub weighting: GmmWeighting,
+    pub cov_type: CovarianceType,
+    /// First-step weight matrix (m × m); identity when `None`
+    pub initial_weight: Option<Array2<f64>>,
+    /// Apply the Windmeijer (2005) finite-sample correction to two-step
+    /// standard errors
+    pub windmeijer: bool,
+    /// Maximum optimiser iterations per step
+    pub max_iter: usize,
+    /// Convergence tolerance on the relative parameter change
+    pub tol: f64,
+}
+
+impl Default for NonlinearGmmOptions {
+    fn default() -> Self {
+        Self {
+            weighting: GmmWeighting::TwoStep,
+            cov_type: CovarianceType::HC1,
+            initial_weight: None,
+            windmeijer: false,
+            max_iter: 500,
+            tol: 1e-10,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NonlinearGmmResult {
+    pub params: Array1<f64>,
+    pub std_errors: Array1<f64>,
+    pub z_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub cov_matrix: Array2<f64>,
+    /// Hansen's J statistic, n·ḡ'Wḡ with an efficient weight
+    pub j_stat: f64,
+    pub j_p_value: f64,
+    pub df_overid: usize,
+    /// Sample moment means ḡ(θ̂)
+    pub moment_means: Array1<f64>,
+    /// Weight matrix of the final step
+    pub weight_matrix: Array2<f64>,
+    pub n_obs: usize,
+    pub n_moments: usize,
+    pub weighting: GmmWeighting,
+    pub cov_type: CovarianceType,
+    /// Whether the Windmeijer correction was applied
+    pub windmeijer: bool,
+    /// Total optimiser iterations across steps
+    pub n_iter: usize,
+    pub converged: bool,
+    pub param_names: Option<Vec<String>>,
+}
+
+impl fmt::Display for NonlinearGmmResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = format!(" Nonlinear GMM ({}) Results ", self.weighting);
+        writeln!(f, "\n{:=^78}", title)?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "No. Observations:", self.n_obs, "J-Statistic:", self.j_stat
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "No. Moments:", self.n_moments, "Prob(J-Stat):", self.j_p_value
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Converged:", self.converged, "DF Overid:", self.df_overid
+        )?;
+        if self.windmeijer {
+            writeln!(f, "Std. errors: Windmeijer-corrected")?;
+        }
+
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<10} | {:>10} | {:>10} | {:>8} | {:>8}",
+            "Parameter", "coef", "std err", "z", "P>|z|"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for i in 0..self.params.len() {
+            let name = match &self.param_names {
+                Some(names) if i < names.len() => names[i].clone(),
+                _ => format!("theta{}", i),
+            };
+            writeln!(
+                f,
+                "{:<10} | {:>10.4} | {:>10.4} | {:>8.3} | {:>8.3}",
+                name, self.params[i], self.std_errors[i], self.z_values[i], self.p_values[i]
+            )?;
+        }
+        write!(f, "{:=^78}", "")
+    }
+}
+
+/// Difference-in-Hansen (C / D) test for the validity of a subset of moments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GmmDTestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub df: usize,
+    /// J of the model with all moments
+    pub j_full: f64,
+    /// J of the model without the suspect moments
+    pub j_restricted: f64,
+    pub suspect_moments: Vec<usize>,
+}
+
+impl fmt::Display for GmmDTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^60}", " GMM Difference-in-Hansen Test ")?;
+        writeln!(f, "H0: moments {:?} are valid", self.suspect_moments)?;
+        writeln!(f, "{:-^60}", "")?;
+        writeln!(f, "{:<30} {:>15.4}", "J (all moments):", self.j_full)?;
+        writeln!(f, "{:<30} {:>15.4}", "J (restricted):", self.j_restricted)?;
+        writeln!(f, "{:<30} {:>15.4}", "D statistic:", self.statistic)?;
+        writeln!(f, "{:<30} {:>15}", "Degrees of freedom:", self.df)?;
+        writeln!(f, "{:<30} {:>15.4}", "P-value:", self.p_value)?;
+        write!(f, "{:=^60}", "")
+    }
+}
+
+/// GMM estimator for moment conditions that are nonlinear in the
+/// parameters, E[g(θ, data_i)] = 0.
+///
+/// The objective ḡ(θ)'Wḡ(θ) is minimised with a damped Gauss–Newton
+/// (Levenberg–Marquardt) iteration; Jacobians are analytic when supplied and
+/// central finite differences otherwise.
+pub struct NonlinearGMM;
+
+impl NonlinearGMM {
+    /// Fits a nonlinear GMM model.
+    ///
+    /// # Arguments
+    /// * `data` - Data matrix; row i is passed to the moment function
+    /// * `moments` - g(θ, data_i) returning the m moments of observation i
+    /// * `jacobian` - Optional analytic ∂g(θ, data_i)/∂θ' (m × k)
+    /// * `start` - Starting values (k)
+    /// * `options` - Weighting, covariance and optimiser settings
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_ols::gmm::{NonlinearGMM, NonlinearGmmOptions};
+    /// use ndarray::{array, Array1, Array2};
+    ///
+    /// // E[y - exp(θ x)] = 0 and E[x (y - exp(θ x))] = 0
+    /// let data = Array2::from_shape_fn((50, 2), |(i, j)| {
+    ///     let x = i as f64 / 50.0;
+    ///     if j == 0 { (0.5 * x).exp() } else { x }
+    /// });
+    /// let moments = |theta: &[f64], row: &[f64]| -> Array1<f64> {
+    ///     let u = row[0] - (theta[0] * row[1]).exp();
+    ///     array![u, u * row[1]]
+    /// };
+    /// let result =
+    ///     NonlinearGMM::fit(&data, &moments, None, &[0.1], &NonlinearGmmOptions::default())
+    ///         .unwrap();
+    /// assert!((result.params[0] - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn fit(
+        data: &Array2<f64>,
+        moments: MomentFn<'_>,
+        jacobian: Option<MomentJacobianFn<'_>>,
+        start: &[f64],
+        options: &NonlinearGmmOptions,
+    ) -> Result<NonlinearGmmResult, GreenersError> {
+        let problem = MomentProblem::new(data, moments, jacobian, start, None)?;
+        let fit = problem.estimate(start, options)?;
+        problem.finish(fit, options)
+    }
+
+    /// Fits with parameter names (for display).
+    pub fn fit_with_names(
+        data: &Array2<f64>,
+        moments: MomentFn<'_>,
+        jacobian: Option<MomentJacobianFn<'_>>,
+        start: &[f64],
+        options: &NonlinearGmmOptions,
+        param_names: Vec<String>,
+    ) -> Result<NonlinearGmmResult, GreenersError> {
+        let mut result = Self::fit(data, moments, jacobian, start, options)?;
+        result.param_names = Some(param_names);
+        Ok(result)
+    }
+
+    /// Difference-in-Hansen test of H0: the `suspect` moments are valid.
+    ///
+    /// The full model is estimated with `options`; the restricted model
+    /// drops the suspect moments and reuses the corresponding block of the
+    /// full model's moment covariance, so D = J_full − J_restricted is
+    /// non-negative and χ²(#suspect) under H0.
+    pub fn d_test(
+        data: &Array2<f64>,
+        moments: MomentFn<'_>,
+        jacobian: Option<MomentJacobianFn<'_>>,
+        start: &[f64],
+        options: &NonlinearGmmOptions,
+        suspect: &[usize],
+    ) -> Result<GmmDTestResult, GreenersError> {
+        let full = Self::fit(data, moments, jacobian, start, options)?;
+        let m = full.n_moments;
+        let k = start.len();
+        if suspect.is_empty() || suspect.iter().any(|&j| j >= m) {
+            return Err(GreenersError::InvalidOperation(format!(
+                "suspect moments must be non-empty indices below {}",
+                m
+            )));
+        }
+        let keep: Vec<usize> = (0..m).filter(|j| !suspect.contains(j)).collect();
+        if keep.len() < k {
+            return Err(GreenersError::InvalidOperation(format!(
+                "Restricted model has {} moments for {} parameters",
+                keep.len(),
+                k
+            )));
+        }
+        let df = m - keep.len();
+
+        let s_full = full.weight_matrix.inv()?;
+        let w_restricted = s_full
+            .select(nd::Axis(0), &keep)
+            .select(nd::Axis(1), &keep)
+            .inv()?;
+        let problem = MomentProblem::new(data, moments, jacobian, start, Some(keep))?;
+        let start_r = full.params.to_vec();
+        let (theta_r, _, _) =
+            problem.minimize(&start_r, &Weight::Fixed(w_restricted.clone()), options)?;
+        let gbar = problem.moment_means(&theta_r)?;
+        let j_restricted = problem.n as f64 * gbar.dot(&w_restricted.dot(&gbar));
+
+        let statistic = (full.j_stat - j_restricted).max(0.0);
+        let chi2 = ChiSquared::new(df as f64).map_err(|_| GreenersError::OptimizationFailed)?;
+        Ok(GmmDTestResult {
+            statistic,
+            p_value: 1.0 - chi2.cdf(statistic),
+            df,
+            j_full: full.j_stat,
+            j_restricted,
+            suspect_moments: suspect.to_vec(),
+        })
+    }
+}
+
+enum Weight {
+    Fixed(Array2<f64>),
+    Continuous,
+}
+
+/// Outcome of the estimation steps before inference.
+struct StepFit {
+    theta: Vec<f64>,
+    weight: Array2<f64>,
+    /// One-step estimates and weight (for the Windmeijer correction)
+    first_step: Option<(Vec<f64>, Array2<f64>)>,
+    n_iter: usize,
+    converged: bool,
+}
+
+struct MomentProblem<'a> {
+    rows: Vec<Vec<f64>>,
+    moments: MomentFn<'a>,
+    jacobian: Option<MomentJacobianFn<'a>>,
+    /// Moments retained (all when `None`)
+    subset: Option<Vec<usize>>,
+    n: usize,
+    /// Moments returned by the closure, before `subset` is applied
+    m_all: usize,
+    m: usize,
+    k: usize,
+}
+
+impl<'a> MomentProblem<'a> {
+    fn new(
+        data: &Array2<f64>,
+        moments: MomentFn<'a>,
+        jacobian: Option<MomentJacobianFn<'a>>,
+        start: &[f64],
+        subset: Option<Vec<usize>>,
+    ) -> Result<Self, GreenersError> {
+        let n = data.nrows();
+        let k = start.len();
+        if n == 0 || k == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "GMM: empty data or parameters".into(),
+            ));
+        }
+        let rows: Vec<Vec<f64>> = data.outer_iter().map(|r| r.to_vec()).collect();
+        let m_all = moments(start, &rows[0]).len();
+        let m = subset.as_ref().map_or(m_all, |s| s.len());
+        if m < k {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "GMM requires at least as many moments as parameters. Got m={}, k={}",
+                m, k
+            )));
+        }
+        if n <= k {
+            return Err(GreenersError::ShapeMismatch(
+                "Not enough observations for the number of parameters".into(),
+            ));
+        }
+        Ok(Self {
+            rows,
+            moments,
+            jacobian,
+            subset,
+            n,
+            m_all,
+            m,
+            k,
+        })
+    }
+
+    /// Moment contributions g_i(θ) stacked as rows (n × m).
+    fn moment_matrix(&self, theta: &[f64]) -> Result<Array2<f64>, GreenersError> {
+        let mut g = Array2::<f64>::zeros((self.n, self.m));
+        for (i, row) in self.rows.iter().enumerate() {
+            let gi = (self.moments)(theta, row);
+            if gi.len() != self.m_all {
+                return Err(GreenersError::ShapeMismatch(format!(
+                    "Moment function returned {} moments for row {}, expected {}",
+                    gi.len(),
+                    i,
+                    self.m_all
+                )));
+            }
+            match &self.subset {
+                Some(idx) => {
+                    for (j, &c) in idx.iter().enumerate() {
+                        g[[i, j]] = gi[c];
+                    }
+                }
+                None => g.row_mut(i).assign(&gi),
+            }
+        }
+        Ok(g)
+    }
+
+    fn moment_means(&self, theta: &[f64]) -> Result<Array1<f64>, GreenersError> {
+        Ok(self.moment_matrix(theta)?.sum_axis(nd::Axis(0)) / self.n as f64)
+    }
+
+    /// Average Jacobian G = ∂ḡ/∂θ' (m × k).
+    fn jacobian_mean(&self, theta: &[f64]) -> Result<Array2<f64>, GreenersError> {
+        if let Some(jac) = self.jacobian {
+            let mut total = Array2::<f64>::zeros((self.m, self.k));
+            for (i, row) in self.rows.iter().enumerate() {
+                let ji = jac(theta, row);
+                if ji.dim() != (self.m_all, self.k) {
+                    return Err(GreenersError::ShapeMismatch(format!(
+                        "Jacobian function returned a {}×{} matrix for row {}, expected {}×{}",
+                        ji.nrows(),
+                        ji.ncols(),
+                        i,
+                        self.m_all,
+                        self.k
+                    )));
+                }
+                match &self.subset {
+                    Some(idx) => total += &ji.select(nd::Axis(0), idx),
+                    None => total += &ji,
+                }
+            }
+            return Ok(total / self.n as f64);
+        }
+        let mut g = Array2::<f64>::zeros((self.m, self.k));
+        for j in 0..self.k {
+            let h = 1e-6 * (1.0 + theta[j].abs());
+            let mut up = theta.to_vec();
+            let mut down = theta.to_vec();
+            up[j] += h;
+            down[j] -= h;
+            let diff = (self.moment_means(&up)? - self.moment_means(&down)?) / (2.0 * h);
+            g.column_mut(j).assign(&diff);
+        }
+        Ok(g)
+    }
+
+    /// Long-run covariance S of the moments at θ.
+    fn moment_covariance(
+        &self,
+        theta: &[f64],
+        cov_type: &CovarianceType,
+    ) -> Result<Array2<f64>, GreenersError> {
+        moment_covariance(&self.moment_matrix(theta)?, cov_type)
+    }
+
+    /// ḡ'Wḡ, or infinity where it cannot be evaluated; malformed moment
+    /// output is an error.
+    fn objective(
+        &self,
+        theta: &[f64],
+        weight: &Weight,
+        cov_type: &CovarianceType,
+    ) -> Result<f64, GreenersError> {
+        let gbar = self.moment_means(theta)?;
+        let w = match weight {
+            Weight::Fixed(w) => w.clone(),
+            Weight::Continuous => match self
+                .moment_covariance(theta, cov_type)
+                .and_then(|s| s.inv())
+            {
+                Ok(w) => w,
+                Err(_) => return Ok(f64::INFINITY),
+            },
+        };
+        let q = gbar.dot(&w.dot(&gbar));
+        Ok(if q.is_finite() { q } else { f64::INFINITY })
+    }
+
+    /// Minimises ḡ'Wḡ by damped Gauss–Newton. For the continuously-updated
+    /// objective the gradient is taken numerically from the full objective
+    /// and G'S(θ)⁻¹G serves as the curvature.
+    fn minimize(
+        &self,
+        start: &[f64],
+        weight: &Weight,
+        options: &NonlinearGmmOptions,
+    ) -> Result<(Vec<f64>, usize, bool), GreenersError> {
+        let cov_type = &options.cov_type;
+        let mut theta = start.to_vec();
+        let mut q = self.objective(&theta, weight, cov_type)?;
+        if !q.is_finite() {
+            return Err(GreenersError::InvalidOperation(
+                "GMM objective is not finite at the starting values".into(),
+            ));
+        }
+        let mut lambda = 1e-3_f64;
+        let mut n_iter = 0;
+        let mut converged = false;
+
+        while n_iter < options.max_iter {
+            n_iter += 1;
+            let g_mat = self.jacobian_mean(&theta)?;
+            let (w, grad) = match weight {
+                Weight::Fixed(w) => {
+                    let gbar = self.moment_means(&theta)?;
+                    (w.clone(), g_mat.t().dot(&w.dot(&gbar)))
+                }
+                Weight::Continuous => {
+                    let w = self.moment_covariance(&theta, cov_type)?.inv()?;
+                    let mut grad = Array1::<f64>::zeros(self.k);
+                    for j in 0..self.k {
+                        let h = 1e-6 * (1.0 + theta[j].abs());
+                        let mut up = theta.clone();
+                        let mut down = theta.clone();
+                        up[j] += h;
+                        down[j] -= h;
+                        grad[j] = (self.objective(&up, weight, cov_type)?
+                            - self.objective(&down, weight, cov_type)?)
+                            / (4.0 * h);
+                    }
+                    (w, grad)
+                }
+            };
+            let hess = g_mat.t().dot(&w).dot(&g_mat);
+
+            let mut accepted = false;
+            while lambda <= 1e12 {
+                let mut a = hess.clone();
+                for j in 0..self.k {
+                    a[[j, j]] += lambda * hess[[j, j]].abs().max(1e-12);
+                }
+                let delta = match a.inv() {
+                    Ok(a_inv) => -a_inv.dot(&grad),
+                    Err(_) => {
+                        lambda *= 10.0;
+                        continue;
+                    }
+                };
+                let trial: Vec<f64> = theta.iter().zip(delta.iter()).map(|(t, d)| t + d).collect();
+                let q_trial = self.objective(&trial, weight, cov_type)?;
+                if q_trial <= q {
+                    let step = delta
+                        .iter()
+                        .zip(theta.iter())
+                        .map(|(d, t)| d.abs() / (1.0 + t.abs()))
+                        .fold(0.0_f64, f64::max);
+                    theta = trial;
+                    let improvement = q - q_trial;
+                    q = q_trial;
+                    lambda = (lambda * 0.3).max(1e-12);
+                    accepted = true;
+                    if step < options.tol || improvement <= options.tol * options.tol * (1.0 + q) {
+                        converged = true;
+                    }
+                    break;
+                }
+                lambda *= 10.0;
+            }
+            if !accepted {
+                // No descent step left: at a (numerical) minimum
+                converged = grad.iter().all(|g| g.abs() < 1e-6 * (1.0 + q.sqrt()));
+                break;
+            }
+            if converged {
+                break;
+            }
+        }
+        Ok((theta, n_iter, converged))
+    }
+
+    fn estimate(
+        &self,
+        start: &[f64],
+        options: &NonlinearGmmOptions,
+    ) -> Result<StepFit, GreenersError> {
+        let w1 = match &options.initial_weight {
+            Some(w) => {
+                if w.dim() != (self.m, self.m) {
+                    return Err(GreenersError::ShapeMismatch(format!(
+                        "initial_weight must be {}x{}",
+                        self.m, self.m
+                    )));
+                }
+                w.clone()
+            }
+            None => Array2::eye(self.m),
+        };
+        let (theta1, iter1, conv1) = self.minimize(start, &Weight::Fixed(w1.clone()), options)?;
+
+        match options.weighting {
+            GmmWeighting::OneStep => Ok(StepFit {
+                theta: theta1,
+                weight: w1,
+                first_step: None,
+                n_iter: iter1,
+                converged: conv1,
+            }),
+            GmmWeighting::TwoStep => {
+                let w2 = self.moment_covariance(&theta1, &options.cov_type)?.inv()?;
+                let (theta2, iter2, conv2) =
+                    self.minimize(&theta1, &Weight::Fixed(w2.clone()), options)?;
+                Ok(StepFit {
+                    theta: theta2,
+                    weight: w2,
+                    first_step: Some((theta1, w1)),
+                    n_iter: iter1 + iter2,
+                    converged: conv1 && conv2,
+                })
+            }
+            GmmWeighting::Iterated(max_updates) => {
+                let mut theta = theta1;
+                let mut weight = w1;
+                let mut n_iter = iter1;
+                let mut converged = false;
+                for _ in 0..max_updates.max(1) {
+                    weight = self.moment_covariance(&theta, &options.cov_type)?.inv()?;
+                    let (next, iters, _) =
+                        self.minimize(&theta, &Weight::Fixed(weight.clone()), options)?;
+                    n_iter += iters;
+                    let change = next
+                        .iter()
+                        .zip(theta.iter())
+                        .map(|(a, b)| (a - b).abs() / (1.0 + b.abs()))
+                        .fold(0.0_f64, f64::max);
+                    theta = next;
+                    if change < options.tol.sqrt() {
+                        converged = true;
+                        break;
+                    }
+                }
+                Ok(StepFit {
+                    theta,
+                    weight,
+                    first_step: None,
+                    n_iter,
+                    converged,
+                })
+            }
+            GmmWeighting::ContinuouslyUpdated => {
+                // Two-step estimates are a good starting point for the CUE
+                let w2 = self.moment_covariance(&theta1, &options.cov_type)?.inv()?;
+                let (theta2, iter2, _) = self.minimize(&theta1, &Weight::Fixed(w2), options)?;
+                let (theta, iter3, converged) =
+                    self.minimize(&theta2, &Weight::Continuous, options)?;
+                let weight = self.moment_covariance(&theta, &options.cov_type)?.inv()?;
+                Ok(StepFit {
+                    theta,
+                    weight,
+                    first_step: None,
+                    n_iter: iter1 + iter2 + iter3,
+                    converged,
+                })
+            }
+        }
+    }
+
+    fn finish(
+        &self,
+        fit: StepFit,
+        options: &NonlinearGmmOptions,
+    ) -> Result<NonlinearGmmResult, GreenersError> {
+        let n = self.n as f64;
+        let theta = &fit.theta;
+        let g_mat = self.jacobian_mean(theta)?;
+        let gbar = self.moment_means(theta)?;
+        let s_hat = self.moment_covariance(theta, &options.cov_type)?;
+
+        let bread = g_mat.t().dot(&fit.weight).dot(&g_mat).inv()?;
+        let mut windmeijer = false;
+        let cov_matrix = match (&options.weighting, &fit.first_step) {
+            (GmmWeighting::OneStep, _) => {
+                let meat = g_mat
+                    .t()
+                    .dot(&fit.weight)
+                    .dot(&s_hat)
+                    .dot(&fit.weight)
+                    .dot(&g_mat);
+                bread.dot(&meat).dot(&bread) / n
+            }
+            (GmmWeighting::TwoStep, Some((theta1, w1))) if options.windmeijer => {
+                windmeijer = true;
+                self.windmeijer_cov(theta, &fit.weight, theta1, w1, &options.cov_type)?
+            }
+            _ => &bread / n,
+        };
+
+        let std_errors = cov_matrix.diag().mapv(|v| v.max(0.0).sqrt());
+        let params = Array1::from(fit.theta.clone());
+        let z_values = &params / &std_errors;
+        let dist = statrs::distribution::Normal::standard();
+        let p_values = z_values.mapv(|z| 2.0 * (1.0 - dist.cdf(z.abs())));
+
+        // J uses an efficient weight; for one-step it is re-estimated at θ̂
+        let w_eff = match options.weighting {
+            GmmWeighting::OneStep => s_hat.inv()?,
+            _ => fit.weight.clone(),
+        };
+        let j_stat = n * gbar.dot(&w_eff.dot(&gbar));
+        let df_overid = self.m - self.k;
+        let j_p_value = if df_overid > 0 {
+            let chi2 =
+                ChiSquared::new(df_overid as f64).map_err(|_| GreenersError::OptimizationFailed)?;
+            1.0 - chi2.cdf(j_stat)
+        } else {
+            f64::NAN
+        };
+
+        Ok(NonlinearGmmResult {
+            params,
+            std_errors,
+            z_values,
+            p_values,
+            cov_matrix,
+            j_stat,
+            j_p_value,
+            df_overid,
+            moment_means: gbar,
+            weight_matrix: w_eff,
+            n_obs: self.n,
+            n_moments: self.m,
+            weighting: options.weighting,
+            cov_type: options.cov_type.clone(),
+            windmeijer,
+            n_iter: fit.n_iter,
+            converged: fit.converged,
+            param_names: None,
+        })
+    }
+
+    /// Windmeijer (2005) corrected covariance of the two-step estimator:
+    /// V₂ + D V₂ + V₂ D' + D V₁ D', where column j of D is
+    /// (G'W₂G)⁻¹ G'W₂ (∂S/∂θ_j at θ₁) W₂ ḡ(θ₂) with G = ∂ḡ/∂θ' (the sign
+    /// flips relative to Windmeijer's X'Z notation) and V₁ is the robust
+    /// one-step covariance.
+    fn windmeijer_cov(
+        &self,
+        theta2: &[f64],
+        w2: &Array2<f64>,
+        theta1: &[f64],
+        w1: &Array2<f64>,
+        cov_type: &CovarianceType,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let n = self.n as f64;
+        let g2 = self.jacobian_mean(theta2)?;
+        let gbar2 = self.moment_means(theta2)?;
+        let a2 = g2.t().dot(w2).dot(&g2).inv()?;
+        let v2 = &a2 / n;
+
+        let g1 = self.jacobian_mean(theta1)?;
+        let a1 = g1.t().dot(w1).dot(&g1).inv()?;
+        let s1 = self.moment_covariance(theta1, cov_type)?;
+        let v1 = a1.dot(&g1.t().dot(w1).dot(&s1).dot(w1).dot(&g1)).dot(&a1) / n;
+
+        let left = a2.dot(&g2.t().dot(w2));
+        let right = w2.dot(&gbar2);
+        let mut d = Array2::<f64>::zeros((self.k, self.k));
+        for j in 0..self.k {
+            let h = 1e-6 * (1.0 + theta1[j].abs());
+            let mut up = theta1.to_vec();
+            let mut down = theta1.to_vec();
+            up[j] += h;
+            down[j] -= h;
+            let ds = (self.moment_covariance(&up, cov_type)?
+                - self.moment_covariance(&down, cov_type)?)
+                / (2.0 * h);
+            d.column_mut(j).assign(&left.dot(&ds.dot(&right)));
+        }
+        Ok(&v2 + &d.dot(&v2) + &v2.dot(&d.t()) + &d.dot(&v1).dot(&d.t()))
+    }
+}
+
+/// Long-run covariance of moment contributions `g` (n × m), scaled by 1/n.
+fn moment_covariance(
+    g: &Array2<f64>,
+    cov_type: &CovarianceType,
+) -> Result<Array2<f64>, GreenersError> {
+    let n = g.nrows();
+    let total = match cov_type {
+        CovarianceType::CR2(_) | CovarianceType::CR3(_) => {
+            // The leverage corrections need a hat matrix, which general
+            // moment conditions do not have
+            return Err(GreenersError::InvalidOperation(format!(
+                "{} is not available for GMM; use Clustered instead",
+                Sandwich::describe(cov_type)
+            )));
+        }
+        CovarianceType::NeweyWest(lags) => {
+            let mut s = g.t().dot(g);
+            for lag in 1..=(*lags).min(n.saturating_sub(1)) {
+                let w = 1.0 - lag as f64 / (*lags + 1) as f64;
+                let gamma = g
+                    .slice(nd::s![lag.., ..])
+                    .t()
+                    .dot(&g.slice(nd::s![..n - lag, ..]));
+                s = s + (&gamma + &gamma.t()) * w;
+            }
+            s
+        }
+        CovarianceType::Clustered(_)
+        | CovarianceType::ClusteredTwoWay(_, _)
+        | CovarianceType::Hac(_, _)
+        | CovarianceType::DriscollKraay(_, _)
+        | CovarianceType::Conley(_, _) => {
+            Sandwich::score_meat(g, cov_type)?.unwrap_or_else(|| g.t().dot(g))
+        }
+        _ => g.t().dot(g),
+    };
+    Ok(total / n as f64)
+}
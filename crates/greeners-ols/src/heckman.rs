@@ -10,6 +10,7 @@ use crate::ols::OLS;
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
@@ -28,7 +29,7 @@ fn norm_cdf(x: f64) -> f64 {
 // HeckmanResult
 // ===========================================================================
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct HeckmanResult {
     /// Coeficientes da equação de resultado (β)
     pub params: Array1<f64>,
@@ -1,16 +1,20 @@
+use crate::ols::OlsResult;
 use greeners_core::error::GreenersError;
 use greeners_core::f_pvalue;
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{CovarianceType, InferenceType};
-use greeners_core::{DataFrame, Formula};
+use greeners_core::linalg::{LinalgCholesky as _, LinalgEigh as _, LinalgSVD as _, UPLO};
+use greeners_core::model::align_design;
+use greeners_core::{CovarianceType, InferenceType, Sandwich};
+use greeners_core::{DataFrame, FittedModel, Formula, MissingPolicy, RowMap};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, Normal, StudentsT};
 use std::fmt;
 // Alias to facilitate Axis usage in Newey-West loop
 use ndarray as nd;
 
 /// Result of the Sargan / Hansen J overidentification test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SarganTestResult {
     /// Sargan statistic: n * R² from regression of IV residuals on Z
     pub sargan_stat: f64,
@@ -52,7 +56,7 @@ impl fmt::Display for SarganTestResult {
 }
 
 /// Result of the Durbin-Wu-Hausman endogeneity test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EndogeneityTestResult {
     /// F-statistic from the augmented regression
     pub f_stat: f64,
@@ -89,7 +93,37 @@ impl fmt::Display for EndogeneityTestResult {
     }
 }
 
-#[derive(Debug)]
+/// Member of the k-class family of IV estimators,
+/// β(κ) = [X'(I − κM_Z)X]⁻¹ X'(I − κM_Z)y.
+///
+/// κ = 0 is OLS and κ = 1 is 2SLS. LIML sets κ to the smallest root of
+/// det(Ȳ'M_W Ȳ − κ Ȳ'M_Z Ȳ) = 0, where Ȳ = [y, endogenous regressors] and W
+/// are the included exogenous regressors; Fuller(α) uses
+/// κ_LIML − α / (n − L), with L the number of instruments.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum KClassEstimator {
+    #[default]
+    TwoSls,
+    Liml,
+    /// Fuller's modified LIML with constant α (1 is nearly unbiased,
+    /// 4 minimises the MSE)
+    Fuller(f64),
+    /// User-supplied κ
+    Kappa(f64),
+}
+
+impl fmt::Display for KClassEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KClassEstimator::TwoSls => write!(f, "2SLS"),
+            KClassEstimator::Liml => write!(f, "LIML"),
+            KClassEstimator::Fuller(alpha) => write!(f, "Fuller({})", alpha),
+            KClassEstimator::Kappa(_) => write!(f, "k-class"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IvResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -103,6 +137,22 @@ pub struct IvResult {
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
     pub omitted_vars: Vec<(usize, String)>,
+    pub row_map: Option<RowMap>,
+    pub cov_matrix: Option<Array2<f64>>,
+    /// k-class member that produced the estimates
+    #[serde(default)]
+    pub estimator: KClassEstimator,
+    /// κ used by the estimator (1 for 2SLS)
+    #[serde(default = "two_sls_kappa")]
+    pub kappa: f64,
+    /// Per-coefficient Satterthwaite df (CR2/CR3 only)
+    #[serde(default)]
+    pub satterthwaite_df: Option<Array1<f64>>,
+}
+
+/// κ of results saved before the k-class fields existed, which were all 2SLS.
+fn two_sls_kappa() -> f64 {
+    1.0
 }
 
 impl fmt::Display for IvResult {
@@ -112,35 +162,10 @@ impl fmt::Display for IvResult {
             InferenceType::Normal => "z",
         };
 
-        // FIX 1: Added NeweyWest option in Display
-        let cov_str = match &self.cov_type {
-            CovarianceType::NonRobust => "Non-Robust".to_string(),
-            CovarianceType::HC1 => "Robust (HC1)".to_string(),
-            CovarianceType::HC2 => "Robust (HC2)".to_string(),
-            CovarianceType::HC3 => "Robust (HC3)".to_string(),
-            CovarianceType::HC4 => "Robust (HC4)".to_string(),
-            CovarianceType::NeweyWest(lags) => format!("HAC (Newey-West, L={})", lags),
-            CovarianceType::Clustered(clusters) => {
-                let n_clusters = clusters
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                format!("Clustered ({} clusters)", n_clusters)
-            }
-            CovarianceType::ClusteredTwoWay(clusters1, clusters2) => {
-                let n_clusters_1 = clusters1
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                let n_clusters_2 = clusters2
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                format!("Two-Way Clustered ({}×{})", n_clusters_1, n_clusters_2)
-            }
-        };
+        let cov_str = Sandwich::describe(&self.cov_type);
 
-        writeln!(f, "\n{:=^78}", " IV (2SLS) Regression Results ")?;
+        let title = format!(" IV ({}) Regression Results ", self.estimator);
+        writeln!(f, "\n{:=^78}", title)?;
         writeln!(
             f,
             "{:<20} {:>15} || {:<20} {:>15.4}",
@@ -149,8 +174,14 @@ impl fmt::Display for IvResult {
         writeln!(
             f,
             "{:<20} {:>15} || {:<20} {:>15.4}",
-            "Estimator:", "2SLS", "Sigma:", self.sigma
+            "Estimator:",
+            self.estimator.to_string(),
+            "Sigma:",
+            self.sigma
         )?;
+        if self.estimator != KClassEstimator::TwoSls {
+            writeln!(f, "{:<20} {:>15.6}", "Kappa:", self.kappa)?;
+        }
         writeln!(
             f,
             "{:<20} {:>15} || {:<20} {:>15}",
@@ -307,7 +338,7 @@ impl IvResult {
     /// # Example
     /// ```
     /// use greeners_ols::iv::{IV};
-    /// use greeners_core::{CovarianceType, InferenceType};
+    /// use greeners_core::{CovarianceType, InferenceType, Sandwich};
     /// use ndarray::{Array1, Array2};
     ///
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -324,7 +355,18 @@ impl IvResult {
     /// # }
     /// ```
     pub fn with_inference(mut self, inference_type: InferenceType) -> Result<Self, GreenersError> {
-        let p_values = Self::compute_p_values(&self.t_values, self.df_resid, &inference_type)?;
+        let p_values = match (&self.satterthwaite_df, &inference_type) {
+            (Some(df), InferenceType::StudentT) => {
+                OlsResult::compute_inference_per_coef(
+                    &self.t_values,
+                    &self.std_errors,
+                    &self.params,
+                    df,
+                )?
+                .0
+            }
+            _ => Self::compute_p_values(&self.t_values, self.df_resid, &inference_type)?,
+        };
 
         self.p_values = p_values;
         self.inference_type = inference_type;
@@ -368,19 +410,118 @@ impl IV {
         data: &DataFrame,
         cov_type: CovarianceType,
     ) -> Result<IvResult, GreenersError> {
-        // Get y and X from endogenous formula
-        let (y, x) = data.to_design_matrix(endog_formula)?;
+        Self::from_formula_k_class(
+            endog_formula,
+            instrument_formula,
+            data,
+            KClassEstimator::TwoSls,
+            cov_type,
+        )
+    }
 
+    /// 2SLS from formulas with an explicit missing-value policy; the policy
+    /// applies to the variables of both formulas.
+    pub fn from_formula_with_missing(
+        endog_formula: &Formula,
+        instrument_formula: &Formula,
+        data: &DataFrame,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<IvResult, GreenersError> {
+        Self::from_formula_k_class_with_missing(
+            endog_formula,
+            instrument_formula,
+            data,
+            KClassEstimator::TwoSls,
+            cov_type,
+            missing,
+        )
+    }
+
+    /// Estimates a k-class IV model (2SLS, LIML, Fuller or fixed κ) using
+    /// formulas and DataFrame.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use greeners_ols::iv::{KClassEstimator, IV};
+    /// use greeners_core::{DataFrame, Formula, CovarianceType};
+    /// # fn demo(df: &DataFrame) -> Result<(), greeners_core::GreenersError> {
+    /// let endog_formula = Formula::parse("y ~ x1 + x_endog")?;
+    /// let instrument_formula = Formula::parse("x_endog ~ x1 + z1 + z2")?;
+    /// let liml = IV::from_formula_k_class(
+    ///     &endog_formula,
+    ///     &instrument_formula,
+    ///     df,
+    ///     KClassEstimator::Liml,
+    ///     CovarianceType::HC1,
+    /// )?;
+    /// println!("kappa = {}", liml.kappa);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_formula_k_class(
+        endog_formula: &Formula,
+        instrument_formula: &Formula,
+        data: &DataFrame,
+        estimator: KClassEstimator,
+        cov_type: CovarianceType,
+    ) -> Result<IvResult, GreenersError> {
+        Self::from_formula_k_class_with_missing(
+            endog_formula,
+            instrument_formula,
+            data,
+            estimator,
+            cov_type,
+            MissingPolicy::Drop,
+        )
+    }
+
+    /// k-class IV from formulas with an explicit missing-value policy.
+    pub fn from_formula_k_class_with_missing(
+        endog_formula: &Formula,
+        instrument_formula: &Formula,
+        data: &DataFrame,
+        estimator: KClassEstimator,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<IvResult, GreenersError> {
         // Get Z from instrument formula (just the instruments, with intercept if specified)
         let temp_formula = Formula {
             dependent: endog_formula.dependent.clone(),
             independents: instrument_formula.independents.clone(),
             intercept: instrument_formula.intercept,
         };
-        let (_, z) = data.to_design_matrix(&temp_formula)?;
 
-        let var_names = data.formula_var_names(endog_formula)?;
-        Self::fit_with_names(&y, &x, &z, cov_type, Some(var_names))
+        // Listwise deletion over the variables of both formulas
+        let mut dropped = Vec::new();
+        if missing != MissingPolicy::Keep {
+            dropped = data.design_info(endog_formula)?.missing_rows(data)?;
+            dropped.extend(data.design_info(&temp_formula)?.missing_rows(data)?);
+        }
+        let rows = RowMap::new(data.n_rows(), dropped);
+        if missing == MissingPolicy::Raise && !rows.dropped.is_empty() {
+            return Err(GreenersError::InvalidOperation(format!(
+                "{} row(s) have missing values in variables of '{}' (first at row {})",
+                rows.dropped.len(),
+                endog_formula.dependent,
+                rows.dropped[0]
+            )));
+        }
+        let subset = if rows.dropped.is_empty() {
+            data.clone()
+        } else {
+            data.drop_rows(&rows.dropped)?
+        };
+
+        let (y, x) = subset.to_design_matrix(endog_formula)?;
+        let (_, z) = subset.to_design_matrix(&temp_formula)?;
+
+        let var_names = subset.formula_var_names(endog_formula)?;
+        let cov_type = rows.select_cov_type(cov_type);
+        let mut result =
+            Self::fit_k_class_with_names(&y, &x, &z, estimator, cov_type, Some(var_names))?;
+        result.row_map = Some(rows);
+        Ok(result)
     }
 
     pub fn fit(
@@ -398,6 +539,41 @@ impl IV {
         z: &Array2<f64>,
         cov_type: CovarianceType,
         variable_names: Option<Vec<String>>,
+    ) -> Result<IvResult, GreenersError> {
+        Self::fit_k_class_with_names(y, x, z, KClassEstimator::TwoSls, cov_type, variable_names)
+    }
+
+    /// Fits a k-class IV estimator (see [`KClassEstimator`]).
+    ///
+    /// Columns of X that also appear in Z are treated as included exogenous
+    /// regressors when computing the LIML κ. Covariance matrices are
+    /// sandwiches with bread [X'(I − κM_Z)X]⁻¹ and scores built from
+    /// (I − κM_Z)X, so every `CovarianceType` (including clustered and
+    /// HAC) is available; for κ = 1 they reduce to the 2SLS formulas.
+    ///
+    /// # Arguments
+    /// * `y` - Dependent variable (n × 1)
+    /// * `x` - Regressor matrix (n × K)
+    /// * `z` - Instrument matrix (n × L)
+    /// * `estimator` - k-class member
+    /// * `cov_type` - Covariance type
+    pub fn fit_k_class(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        z: &Array2<f64>,
+        estimator: KClassEstimator,
+        cov_type: CovarianceType,
+    ) -> Result<IvResult, GreenersError> {
+        Self::fit_k_class_with_names(y, x, z, estimator, cov_type, None)
+    }
+
+    pub fn fit_k_class_with_names(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        z: &Array2<f64>,
+        estimator: KClassEstimator,
+        cov_type: CovarianceType,
+        variable_names: Option<Vec<String>>,
     ) -> Result<IvResult, GreenersError> {
         let n = x.nrows();
         let k = x.ncols();
@@ -447,6 +623,19 @@ impl IV {
             ));
         }
 
+        let kappa = match estimator {
+            KClassEstimator::TwoSls => 1.0,
+            KClassEstimator::Kappa(kappa) => kappa,
+            KClassEstimator::Liml => liml_kappa(y, x_to_use, z)?,
+            KClassEstimator::Fuller(alpha) => liml_kappa(y, x_to_use, z)? - alpha / (n - l) as f64,
+        };
+        if !kappa.is_finite() {
+            return Err(GreenersError::InvalidOperation(format!(
+                "kappa must be finite, got {}",
+                kappa
+            )));
+        }
+
         // --- STAGE 1: Regress X on Z to get X_hat ---
         let z_t = z.t();
         let zt_z = z_t.dot(z);
@@ -454,11 +643,21 @@ impl IV {
 
         let zt_x = z_t.dot(x_to_use);
         let first_stage_coeffs = zt_z_inv.dot(&zt_x);
-        let x_hat = z.dot(&first_stage_coeffs);
+        let x_fitted = z.dot(&first_stage_coeffs);
+        // (I − κM_Z)X = X − κ(X − P_Z X); equals P_Z X for 2SLS
+        let x_hat = if kappa == 1.0 {
+            x_fitted
+        } else {
+            x_to_use * (1.0 - kappa) + &(x_fitted * kappa)
+        };
 
         // --- STAGE 2: Regress y on X_hat ---
         let x_hat_t = x_hat.t();
-        let xht_xh = x_hat_t.dot(&x_hat);
+        let xht_xh = if kappa == 1.0 {
+            x_hat_t.dot(&x_hat)
+        } else {
+            x_hat_t.dot(x_to_use)
+        };
         let xht_xh_inv = xht_xh.inv()?;
 
         let xht_y = x_hat_t.dot(y);
@@ -476,8 +675,17 @@ impl IV {
 
         // --- Covariance Matrix ---
         // FIX 2: NeweyWest implementation in match
+        let mut satterthwaite_df = None;
         let cov_matrix = match cov_type {
             CovarianceType::NonRobust => &xht_xh_inv * sigma2,
+            CovarianceType::HC0 => {
+                let mut xhat_weighted = x_hat.clone();
+                for (i, mut row) in xhat_weighted.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    row *= residuals[i].powi(2);
+                }
+                let meat = x_hat_t.dot(&xhat_weighted);
+                xht_xh_inv.dot(&meat).dot(&xht_xh_inv)
+            }
             CovarianceType::HC1 => {
                 let u_squared = residuals.mapv(|r| r.powi(2));
                 let mut xhat_weighted = x_hat.clone();
@@ -578,6 +786,61 @@ impl IV {
                 let bread = &xht_xh_inv;
                 bread.dot(&meat).dot(bread)
             }
+            CovarianceType::HC5 => {
+                // HC5 for IV: leverage from X_hat, δᵢ capped by the largest leverage
+                let leverage: Array1<f64> = (0..n)
+                    .map(|i| {
+                        let xhat_i = x_hat.row(i);
+                        xhat_i.dot(&xht_xh_inv.dot(&xhat_i))
+                    })
+                    .collect();
+                let h_max = leverage.fold(0.0_f64, |a, &b| a.max(b));
+                let k_f = k_clean as f64;
+                let cap = 4.0_f64.max(0.7 * (n as f64) * h_max / k_f);
+
+                let mut xhat_weighted = x_hat.clone();
+                for (i, mut row) in xhat_weighted.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    let h_i = leverage[i];
+                    let weight = if h_i >= 0.9999 {
+                        residuals[i].powi(2)
+                    } else {
+                        let delta_i = ((n as f64) * h_i / k_f).min(cap);
+                        residuals[i].powi(2) / (1.0 - h_i).powf(delta_i / 2.0)
+                    };
+                    row *= weight;
+                }
+
+                let meat = x_hat_t.dot(&xhat_weighted);
+                xht_xh_inv.dot(&meat).dot(&xht_xh_inv)
+            }
+            CovarianceType::Hac(_, _)
+            | CovarianceType::DriscollKraay(_, _)
+            | CovarianceType::Conley(_, _) => {
+                // Score-based kernel/spatial meat using X_hat, n/(n-k) as Newey-West
+                let mut scores = x_hat.clone();
+                for (i, mut row) in scores.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    row *= residuals[i];
+                }
+                let meat = Sandwich::score_meat(&scores, &cov_type)?.ok_or_else(|| {
+                    GreenersError::InvalidOperation("Unsupported covariance type".into())
+                })?;
+                let sandwich = xht_xh_inv.dot(&meat).dot(&xht_xh_inv);
+                sandwich * ((n as f64) / (df_resid as f64))
+            }
+            CovarianceType::CR2(ref cluster_ids) | CovarianceType::CR3(ref cluster_ids) => {
+                // Bias-reduced clustering with the second-stage hat matrix of X_hat
+                let jackknife = matches!(cov_type, CovarianceType::CR3(_));
+                let hat_bread = x_hat_t.dot(&x_hat).inv()?;
+                let (meat, df) = Sandwich::bias_reduced_cluster(
+                    &x_hat,
+                    &hat_bread,
+                    &residuals,
+                    cluster_ids,
+                    jackknife,
+                )?;
+                satterthwaite_df = Some(df);
+                xht_xh_inv.dot(&meat).dot(&xht_xh_inv)
+            }
             CovarianceType::NeweyWest(lags) => {
                 // HAC Implementation for IV
                 // We use X_hat in the "meat" calculation instead of X.
@@ -618,141 +881,32 @@ impl IV {
                 let correction = (n as f64) / (df_resid as f64);
                 sandwich * correction
             }
-            CovarianceType::Clustered(ref cluster_ids) => {
-                // Clustered Standard Errors for IV
-                // Same logic as OLS but using X_hat instead of X
-
-                if cluster_ids.len() != n {
-                    return Err(GreenersError::ShapeMismatch(format!(
-                        "Cluster IDs length ({}) must match number of observations ({})",
-                        cluster_ids.len(),
-                        n
-                    )));
-                }
-
-                use indexmap::IndexMap;
-                let mut clusters: IndexMap<usize, Vec<usize>> = IndexMap::new();
-                for (obs_idx, &cluster_id) in cluster_ids.iter().enumerate() {
-                    clusters.entry(cluster_id).or_default().push(obs_idx);
-                }
-
-                let n_clusters = clusters.len();
-                let mut meat = Array2::<f64>::zeros((k_clean, k_clean));
-
-                for (_cluster_id, obs_indices) in clusters.iter() {
-                    let cluster_size = obs_indices.len();
-                    let mut xhat_g = Array2::<f64>::zeros((cluster_size, k_clean));
-                    let mut u_g = Array1::<f64>::zeros(cluster_size);
-
-                    for (i, &obs_idx) in obs_indices.iter().enumerate() {
-                        xhat_g.row_mut(i).assign(&x_hat.row(obs_idx));
-                        u_g[i] = residuals[obs_idx];
-                    }
-
-                    for i in 0..cluster_size {
-                        for j in 0..cluster_size {
-                            let scale = u_g[i] * u_g[j];
-                            let x_i = xhat_g.row(i);
-                            let x_j = xhat_g.row(j);
-
-                            for p in 0..k_clean {
-                                for q in 0..k_clean {
-                                    meat[[p, q]] += scale * x_i[p] * x_j[q];
-                                }
-                            }
-                        }
-                    }
-                }
-
-                let bread = &xht_xh_inv;
-                let sandwich = bread.dot(&meat).dot(bread);
-
-                let g_correction = (n_clusters as f64) / ((n_clusters - 1) as f64);
-                let df_correction = ((n - 1) as f64) / (df_resid as f64);
-                sandwich * g_correction * df_correction
-            }
-            CovarianceType::ClusteredTwoWay(ref cluster_ids_1, ref cluster_ids_2) => {
-                // Two-Way Clustered Standard Errors for IV (Cameron-Gelbach-Miller, 2011)
-                // Uses X_hat instead of X (same as one-way clustering for IV)
-
-                if cluster_ids_1.len() != n || cluster_ids_2.len() != n {
-                    return Err(GreenersError::ShapeMismatch(format!(
-                        "Both cluster ID vectors must match number of observations ({})",
-                        n
-                    )));
+            CovarianceType::Clustered(_) | CovarianceType::ClusteredTwoWay(_, _) => {
+                // Same cluster meat as OLS but with scores X_hat_i u_i; the
+                // two-way version follows Cameron-Gelbach-Miller (2011)
+                let mut scores = x_hat.clone();
+                for (i, mut row) in scores.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    row *= residuals[i];
                 }
-
-                let compute_clustered_meat = |cluster_ids: &[usize]| -> Array2<f64> {
-                    use indexmap::IndexMap;
-                    let mut clusters: IndexMap<usize, Vec<usize>> = IndexMap::new();
-                    for (obs_idx, &cluster_id) in cluster_ids.iter().enumerate() {
-                        clusters.entry(cluster_id).or_default().push(obs_idx);
-                    }
-
-                    let mut meat = Array2::<f64>::zeros((k_clean, k_clean));
-
-                    for (_cluster_id, obs_indices) in clusters.iter() {
-                        let cluster_size = obs_indices.len();
-                        let mut xhat_g = Array2::<f64>::zeros((cluster_size, k_clean));
-                        let mut u_g = Array1::<f64>::zeros(cluster_size);
-
-                        for (i, &obs_idx) in obs_indices.iter().enumerate() {
-                            xhat_g.row_mut(i).assign(&x_hat.row(obs_idx));
-                            u_g[i] = residuals[obs_idx];
-                        }
-
-                        for i in 0..cluster_size {
-                            for j in 0..cluster_size {
-                                let scale = u_g[i] * u_g[j];
-                                let x_i = xhat_g.row(i);
-                                let x_j = xhat_g.row(j);
-
-                                for p in 0..k_clean {
-                                    for q in 0..k_clean {
-                                        meat[[p, q]] += scale * x_i[p] * x_j[q];
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    meat
-                };
-
-                let meat_1 = compute_clustered_meat(cluster_ids_1);
-                let meat_2 = compute_clustered_meat(cluster_ids_2);
-
-                let max_cluster2 = cluster_ids_2.iter().max().unwrap_or(&0) + 1;
-                let intersection_ids: Vec<usize> = cluster_ids_1
-                    .iter()
-                    .zip(cluster_ids_2.iter())
-                    .map(|(&c1, &c2)| c1 * max_cluster2 + c2)
-                    .collect();
-
-                let meat_12 = compute_clustered_meat(&intersection_ids);
-
-                let meat = &meat_1 + &meat_2 - &meat_12;
-
+                let meat = Sandwich::score_meat(&scores, &cov_type)?.ok_or_else(|| {
+                    GreenersError::InvalidOperation("Unsupported covariance type".into())
+                })?;
                 let bread = &xht_xh_inv;
-                let sandwich = bread.dot(&meat).dot(bread);
-
-                use std::collections::HashSet;
-                let n_clusters_1: HashSet<_> = cluster_ids_1.iter().collect();
-                let n_clusters_2: HashSet<_> = cluster_ids_2.iter().collect();
-                let g = n_clusters_1.len().min(n_clusters_2.len());
-
-                let g_correction = (g as f64) / ((g - 1) as f64);
                 let df_correction = ((n - 1) as f64) / (df_resid as f64);
-                sandwich * g_correction * df_correction
+                bread.dot(&meat).dot(bread) * df_correction
             }
         };
 
         let std_errors = cov_matrix.diag().mapv(f64::sqrt);
         let t_values = &beta / &std_errors;
 
-        // Use default inference type (StudentT)
+        // Use default inference type (StudentT); CR2/CR3 keep their
+        // Satterthwaite df
         let default_inference = InferenceType::default();
-        let p_values = IvResult::compute_p_values(&t_values, df_resid, &default_inference)?;
+        let p_values = match &satterthwaite_df {
+            Some(df) => OlsResult::compute_inference_per_coef(&t_values, &std_errors, &beta, df)?.0,
+            None => IvResult::compute_p_values(&t_values, df_resid, &default_inference)?,
+        };
 
         let y_mean = y.mean().unwrap_or(0.0);
         let sst = y.mapv(|val| (val - y_mean).powi(2)).sum();
@@ -771,6 +925,11 @@ impl IV {
             inference_type: InferenceType::default(),
             variable_names,
             omitted_vars: omitted_positioned,
+            row_map: None,
+            cov_matrix: Some(cov_matrix),
+            estimator,
+            kappa,
+            satterthwaite_df,
         })
     }
 
@@ -932,3 +1091,1069 @@ impl IV {
         })
     }
 }
+
+impl FittedModel for IvResult {
+    fn model_name(&self) -> String {
+        "IV-2SLS".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &self.omitted_vars)?;
+        Ok(x.dot(&self.params))
+    }
+
+    fn vcov(&self) -> Option<Array2<f64>> {
+        self.cov_matrix.clone()
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
+
+// ─── Weak-instrument diagnostics ─────────────────────────────────────────────
+
+/// Stock–Yogo (2005) critical values for the Cragg–Donald F statistic.
+///
+/// Each entry is `(threshold, critical value)`: maximal 2SLS bias relative to
+/// OLS (5%, 10%, 20%, 30%) or maximal actual size of a nominal 5% Wald test
+/// (10%, 15%, 20%, 25%). Entries are empty where the table does not cover the
+/// number of endogenous regressors / excluded instruments (bias: K2 ≥ p + 2;
+/// size: p ≤ 2; both tabulated here for K2 ≤ 10).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockYogoCritical {
+    pub relative_bias: Vec<(f64, f64)>,
+    pub size: Vec<(f64, f64)>,
+}
+
+/// First-stage reporting for IV/2SLS.
+///
+/// Included exogenous regressors are the columns of X that also appear in Z;
+/// they are partialled out of the endogenous regressors and the excluded
+/// instruments before any statistic is computed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirstStageResult {
+    /// Names of the endogenous regressors
+    pub endogenous_vars: Vec<String>,
+    /// Indices of the endogenous columns in X
+    pub endogenous_cols: Vec<usize>,
+    /// Indices of the excluded instruments in Z
+    pub excluded_cols: Vec<usize>,
+    /// Excluded-instrument coefficients (K2 × p), one column per endogenous
+    pub coefficients: Array2<f64>,
+    /// Partial R² of each endogenous regressor on the excluded instruments
+    pub partial_r_squared: Array1<f64>,
+    /// First-stage F (Wald / K2) on the excluded instruments, per endogenous
+    pub f_stats: Array1<f64>,
+    pub f_pvalues: Array1<f64>,
+    /// Sanderson–Windmeijer conditional F, per endogenous (df K2 - p + 1)
+    pub sw_f_stats: Array1<f64>,
+    pub sw_pvalues: Array1<f64>,
+    /// Cragg–Donald Wald F (homoskedastic)
+    pub cragg_donald_f: f64,
+    /// Kleibergen–Paap rk Wald statistic, chi²(K2 - p + 1) under
+    /// underidentification
+    pub kp_rk_stat: f64,
+    pub kp_rk_pvalue: f64,
+    /// Kleibergen–Paap rk Wald F (rk / K2)
+    pub kp_f: f64,
+    /// Montiel Olea–Pflueger effective F (single endogenous regressor only)
+    pub effective_f: Option<f64>,
+    /// Effective degrees of freedom of the Patnaik approximation
+    pub effective_k: Option<f64>,
+    /// Simplified 5% critical value of the effective F for a worst-case
+    /// bias of 10% of the benchmark (Montiel Olea–Pflueger, TSLS)
+    pub effective_f_critical: Option<f64>,
+    /// Stock–Yogo critical values for the Cragg–Donald F
+    pub stock_yogo: StockYogoCritical,
+    pub n_obs: usize,
+    /// Number of excluded instruments (K2)
+    pub n_excluded: usize,
+    /// Number of included exogenous regressors
+    pub n_exogenous: usize,
+    pub cov_type: CovarianceType,
+}
+
+impl fmt::Display for FirstStageResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " First-Stage Diagnostics ")?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "No. Observations:", self.n_obs, "Excluded instr.:", self.n_excluded
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Endogenous:",
+            self.endogenous_vars.len(),
+            "Included exog.:",
+            self.n_exogenous
+        )?;
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<14} | {:>10} | {:>10} | {:>8} | {:>10} | {:>8}",
+            "Variable", "Partial R2", "F", "P>F", "SW F", "P>SW F"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (j, name) in self.endogenous_vars.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<14} | {:>10.4} | {:>10.3} | {:>8.4} | {:>10.3} | {:>8.4}",
+                name,
+                self.partial_r_squared[j],
+                self.f_stats[j],
+                self.f_pvalues[j],
+                self.sw_f_stats[j],
+                self.sw_pvalues[j]
+            )?;
+        }
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<40} {:>12.3}",
+            "Cragg-Donald Wald F:", self.cragg_donald_f
+        )?;
+        writeln!(
+            f,
+            "{:<40} {:>12.3} (p = {:.4})",
+            "Kleibergen-Paap rk Wald stat:", self.kp_rk_stat, self.kp_rk_pvalue
+        )?;
+        writeln!(
+            f,
+            "{:<40} {:>12.3}",
+            "Kleibergen-Paap rk Wald F:", self.kp_f
+        )?;
+        if let (Some(eff), Some(crit)) = (self.effective_f, self.effective_f_critical) {
+            writeln!(
+                f,
+                "{:<40} {:>12.3} (5% c.v. for tau = 10%: {:.2})",
+                "Montiel Olea-Pflueger effective F:", eff, crit
+            )?;
+        }
+        if !self.stock_yogo.relative_bias.is_empty() || !self.stock_yogo.size.is_empty() {
+            writeln!(f, "{:-^78}", " Stock-Yogo critical values ")?;
+            for (level, cv) in &self.stock_yogo.relative_bias {
+                writeln!(
+                    f,
+                    "{:<40} {:>12.2}",
+                    format!("{:.0}% maximal IV relative bias", level * 100.0),
+                    cv
+                )?;
+            }
+            for (level, cv) in &self.stock_yogo.size {
+                writeln!(
+                    f,
+                    "{:<40} {:>12.2}",
+                    format!("{:.0}% maximal IV size", level * 100.0),
+                    cv
+                )?;
+            }
+        }
+        write!(f, "{:=^78}", "")
+    }
+}
+
+/// Weak-instrument-robust inference for a single endogenous regressor.
+///
+/// Anderson–Rubin and Moreira's conditional likelihood ratio (CLR) tests are
+/// computed under homoskedastic errors from the reduced-form sufficient
+/// statistics, so they remain valid however weak the instruments are.
+/// Confidence sets are lists of intervals; bounds may be infinite and the
+/// set may be empty or the whole real line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeakIvRobustResult {
+    /// Name of the endogenous regressor
+    pub endogenous_var: String,
+    /// 2SLS point estimate
+    pub beta_2sls: f64,
+    /// Significance level used for the confidence sets
+    pub alpha: f64,
+    /// Anderson–Rubin confidence set (exact inversion)
+    pub ar_set: Vec<(f64, f64)>,
+    /// CLR confidence set (grid inversion refined by bisection)
+    pub clr_set: Vec<(f64, f64)>,
+    /// Ȳ'P Ȳ for Ȳ = [y, x] after partialling out the exogenous regressors
+    pub reduced_form_pz: Array2<f64>,
+    /// Reduced-form error covariance of [y, x] (df = n - L)
+    pub reduced_form_cov: Array2<f64>,
+    pub n_obs: usize,
+    /// Number of excluded instruments (K2)
+    pub n_excluded: usize,
+    /// Residual degrees of freedom n - L
+    pub df_resid: usize,
+}
+
+impl WeakIvRobustResult {
+    /// Anderson–Rubin test of H0: β = β0, returning `(F statistic, p-value)`
+    /// with the statistic distributed F(K2, n - L) under normal errors.
+    pub fn ar_test(&self, beta0: f64) -> (f64, f64) {
+        let (qs, _, _) = self.sufficient_stats(beta0);
+        let k = self.n_excluded as f64;
+        let stat = qs / k;
+        (stat, f_pvalue(stat, k, self.df_resid as f64))
+    }
+
+    /// Moreira CLR test of H0: β = β0, returning `(LR statistic, p-value)`.
+    ///
+    /// The p-value conditions on the Q_T statistic (Andrews, Moreira and
+    /// Stock, 2007); with one instrument CLR and AR coincide.
+    pub fn clr_test(&self, beta0: f64) -> (f64, f64) {
+        let (qs, qt, qst) = self.sufficient_stats(beta0);
+        let disc = ((qs + qt).powi(2) - 4.0 * (qs * qt - qst * qst)).max(0.0);
+        let lr = 0.5 * (qs - qt + disc.sqrt());
+        (lr, clr_pvalue(lr, qt, self.n_excluded))
+    }
+
+    /// Q_S, Q_T and Q_ST at β0 (Moreira, 2003).
+    fn sufficient_stats(&self, beta0: f64) -> (f64, f64, f64) {
+        let p = &self.reduced_form_pz;
+        let om = &self.reduced_form_cov;
+        let b0 = [1.0, -beta0];
+        let det = om[[0, 0]] * om[[1, 1]] - om[[0, 1]] * om[[1, 0]];
+        // Ω⁻¹ a0 with a0 = (β0, 1)
+        let oa = [
+            (om[[1, 1]] * beta0 - om[[0, 1]]) / det,
+            (-om[[1, 0]] * beta0 + om[[0, 0]]) / det,
+        ];
+        let quad = |u: [f64; 2], m: &Array2<f64>, v: [f64; 2]| {
+            u[0] * (m[[0, 0]] * v[0] + m[[0, 1]] * v[1])
+                + u[1] * (m[[1, 0]] * v[0] + m[[1, 1]] * v[1])
+        };
+        let b_om_b = quad(b0, om, b0);
+        let a_oinv_a = beta0 * oa[0] + oa[1];
+        let qs = quad(b0, p, b0) / b_om_b;
+        let qt = quad(oa, p, oa) / a_oinv_a;
+        let qst = quad(b0, p, oa) / (b_om_b * a_oinv_a).sqrt();
+        (qs, qt, qst)
+    }
+}
+
+impl fmt::Display for WeakIvRobustResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt_set = |set: &[(f64, f64)]| -> String {
+            if set.is_empty() {
+                return "empty".to_string();
+            }
+            set.iter()
+                .map(|(lo, hi)| format!("[{:.4}, {:.4}]", lo, hi))
+                .collect::<Vec<_>>()
+                .join(" U ")
+        };
+        let level = (1.0 - self.alpha) * 100.0;
+        writeln!(f, "\n{:=^78}", " Weak-IV-Robust Inference ")?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Endogenous:", self.endogenous_var, "Excluded instr.:", self.n_excluded
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15.4} || {:<20} {:>15}",
+            "2SLS estimate:", self.beta_2sls, "No. Observations:", self.n_obs
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        let (ar0, ar0_p) = self.ar_test(0.0);
+        let (clr0, clr0_p) = self.clr_test(0.0);
+        writeln!(
+            f,
+            "{:<20} {:>12.4} (p = {:.4})",
+            "AR (beta = 0):", ar0, ar0_p
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>12.4} (p = {:.4})",
+            "CLR (beta = 0):", clr0, clr0_p
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(f, "{:.0}% AR set:  {}", level, fmt_set(&self.ar_set))?;
+        writeln!(f, "{:.0}% CLR set: {}", level, fmt_set(&self.clr_set))?;
+        write!(f, "{:=^78}", "")
+    }
+}
+
+impl IV {
+    /// First-stage diagnostics: partial R², first-stage and
+    /// Sanderson–Windmeijer F per endogenous regressor, Cragg–Donald and
+    /// Kleibergen–Paap rk Wald statistics, Stock–Yogo critical values and,
+    /// with a single endogenous regressor, the Montiel Olea–Pflueger
+    /// effective F.
+    ///
+    /// `x` and `z` are the matrices passed to [`IV::fit`]; columns of X that
+    /// also appear in Z are treated as included exogenous regressors. The
+    /// first-stage F, SW F, KP and effective F use `cov_type` (HC2–HC4 fall
+    /// back to HC1, CR2/CR3 apply the leverage correction of the full
+    /// first stage); the Cragg–Donald F is always homoskedastic.
+    ///
+    /// # Arguments
+    /// * `x` - Regressor matrix (n × K)
+    /// * `z` - Instrument matrix (n × L)
+    /// * `cov_type` - Covariance used for the robust statistics
+    /// * `variable_names` - Names of the columns of X (for display)
+    pub fn first_stage(
+        x: &Array2<f64>,
+        z: &Array2<f64>,
+        cov_type: CovarianceType,
+        variable_names: Option<&[String]>,
+    ) -> Result<FirstStageResult, GreenersError> {
+        let split = split_instruments(x, z)?;
+        let n = x.nrows();
+        let p = split.endog.len();
+        let kz = split.excluded.len();
+        let l = z.ncols();
+        let df_resid = n - l;
+
+        let (yt, zt) = partial_out(x, z, &split)?;
+        let ztz_inv = zt.t().dot(&zt).inv()?;
+        let pi = ztz_inv.dot(&zt.t().dot(&yt)); // K2 × p
+        let v = &yt - &zt.dot(&pi);
+
+        // Robust covariance of vec(Π̂), blocks of K2 per endogenous
+        let cov_vec = pi_covariance(&zt, &v, &ztz_inv, z, &cov_type, df_resid)?;
+
+        let mut partial_r_squared = Array1::<f64>::zeros(p);
+        let mut f_stats = Array1::<f64>::zeros(p);
+        let mut f_pvalues = Array1::<f64>::zeros(p);
+        let mut sw_f_stats = Array1::<f64>::zeros(p);
+        let mut sw_pvalues = Array1::<f64>::zeros(p);
+        for j in 0..p {
+            let yj = yt.column(j);
+            let vj = v.column(j);
+            partial_r_squared[j] = 1.0 - vj.dot(&vj) / yj.dot(&yj);
+
+            let block = cov_vec
+                .slice(nd::s![j * kz..(j + 1) * kz, j * kz..(j + 1) * kz])
+                .to_owned();
+            let pi_j = pi.column(j).to_owned();
+            f_stats[j] = pi_j.dot(&block.inv()?.dot(&pi_j)) / kz as f64;
+            f_pvalues[j] = f_pvalue(f_stats[j], kz as f64, df_resid as f64);
+
+            // Sanderson–Windmeijer: 2SLS of y_j on the other endogenous
+            // variables, then Wald test of the residual on Z̃
+            let eps = if p == 1 {
+                yj.to_owned()
+            } else {
+                let others: Vec<usize> = (0..p).filter(|&m| m != j).collect();
+                let y_other = yt.select(nd::Axis(1), &others);
+                let y_other_hat = zt.dot(&ztz_inv.dot(&zt.t().dot(&y_other)));
+                let delta = y_other_hat
+                    .t()
+                    .dot(&y_other)
+                    .inv()?
+                    .dot(&y_other_hat.t().dot(&yj));
+                &yj - &y_other.dot(&delta)
+            };
+            let gamma = ztz_inv.dot(&zt.t().dot(&eps));
+            let e_resid = (&eps - &zt.dot(&gamma)).insert_axis(nd::Axis(1));
+            let cov_gamma = pi_covariance(&zt, &e_resid, &ztz_inv, z, &cov_type, df_resid)?;
+            let df_sw = kz + 1 - p;
+            sw_f_stats[j] = gamma.dot(&cov_gamma.inv()?.dot(&gamma)) / df_sw as f64;
+            sw_pvalues[j] = f_pvalue(sw_f_stats[j], df_sw as f64, df_resid as f64);
+        }
+
+        // Cragg–Donald: min eigenvalue of Σ̂^{-1/2} Ỹ'PỸ Σ̂^{-1/2} / K2
+        let y_pz_y = yt.t().dot(&zt).dot(&pi);
+        let sigma_vv = v.t().dot(&v) / df_resid as f64;
+        let s_inv_half = sym_power(&sigma_vv, -0.5)?;
+        let g = s_inv_half.dot(&y_pz_y).dot(&s_inv_half);
+        let (g_eigs, _) = g.eigh(UPLO::Lower)?;
+        let cragg_donald_f = g_eigs.iter().copied().fold(f64::INFINITY, f64::min) / kz as f64;
+
+        let kp_rk_stat = kleibergen_paap_rk(&yt, &zt, &pi, &cov_vec)?;
+        let kp_df = (kz + 1 - p) as f64;
+        let kp_rk_pvalue = ChiSquared::new(kp_df)
+            .map(|chi2| 1.0 - chi2.cdf(kp_rk_stat))
+            .unwrap_or(f64::NAN);
+        let kp_f = kp_rk_stat / kz as f64;
+
+        let (effective_f, effective_k, effective_f_critical) = if p == 1 {
+            let (eff, k_eff, crit) = effective_f_stat(&zt, &pi, &cov_vec)?;
+            (Some(eff), Some(k_eff), Some(crit))
+        } else {
+            (None, None, None)
+        };
+
+        let endogenous_vars = split
+            .endog
+            .iter()
+            .map(|&c| match variable_names {
+                Some(names) if c < names.len() => names[c].clone(),
+                _ => format!("x{}", c),
+            })
+            .collect();
+
+        Ok(FirstStageResult {
+            endogenous_vars,
+            endogenous_cols: split.endog,
+            excluded_cols: split.excluded,
+            coefficients: pi,
+            partial_r_squared,
+            f_stats,
+            f_pvalues,
+            sw_f_stats,
+            sw_pvalues,
+            cragg_donald_f,
+            kp_rk_stat,
+            kp_rk_pvalue,
+            kp_f,
+            effective_f,
+            effective_k,
+            effective_f_critical,
+            stock_yogo: Self::stock_yogo(p, kz),
+            n_obs: n,
+            n_excluded: kz,
+            n_exogenous: split.exog.len(),
+            cov_type,
+        })
+    }
+
+    /// Stock–Yogo critical values for `n_endog` endogenous regressors and
+    /// `n_excluded` excluded instruments (see [`StockYogoCritical`]).
+    pub fn stock_yogo(n_endog: usize, n_excluded: usize) -> StockYogoCritical {
+        let pick = |table: &[[f64; 4]], first_k: usize| -> Option<[f64; 4]> {
+            n_excluded
+                .checked_sub(first_k)
+                .and_then(|i| table.get(i))
+                .copied()
+        };
+        let bias = match n_endog {
+            1 => pick(&SY_BIAS_1, 3),
+            2 => pick(&SY_BIAS_2, 4),
+            3 => pick(&SY_BIAS_3, 5),
+            _ => None,
+        };
+        let size = match n_endog {
+            1 => pick(&SY_SIZE_1, 1),
+            2 => pick(&SY_SIZE_2, 2),
+            _ => None,
+        };
+        StockYogoCritical {
+            relative_bias: bias
+                .map(|cv| [0.05, 0.10, 0.20, 0.30].into_iter().zip(cv).collect())
+                .unwrap_or_default(),
+            size: size
+                .map(|cv| [0.10, 0.15, 0.20, 0.25].into_iter().zip(cv).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Anderson–Rubin and CLR tests and confidence sets for the coefficient
+    /// of the single endogenous regressor.
+    ///
+    /// # Arguments
+    /// * `y` - Dependent variable (n × 1)
+    /// * `x` - Regressor matrix (n × K) with exactly one column not in Z
+    /// * `z` - Instrument matrix (n × L)
+    /// * `alpha` - Significance level of the confidence sets (e.g. 0.05)
+    /// * `variable_names` - Names of the columns of X (for display)
+    pub fn weak_iv_robust(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        z: &Array2<f64>,
+        alpha: f64,
+        variable_names: Option<&[String]>,
+    ) -> Result<WeakIvRobustResult, GreenersError> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(GreenersError::InvalidOperation(
+                "alpha must lie in (0, 1)".into(),
+            ));
+        }
+        if y.len() != x.nrows() {
+            return Err(GreenersError::ShapeMismatch("Row count mismatch".into()));
+        }
+        let split = split_instruments(x, z)?;
+        if split.endog.len() != 1 {
+            return Err(GreenersError::InvalidOperation(format!(
+                "weak_iv_robust requires exactly one endogenous regressor, found {}",
+                split.endog.len()
+            )));
+        }
+        let n = x.nrows();
+        let kz = split.excluded.len();
+        let df_resid = n - z.ncols();
+
+        // Ȳ = [y, x_endog], both partialled on the included exogenous
+        let mut ybar_raw = Array2::<f64>::zeros((n, 2));
+        ybar_raw.column_mut(0).assign(y);
+        ybar_raw.column_mut(1).assign(&x.column(split.endog[0]));
+        let (ybar, zt) = partial_out_columns(&ybar_raw, x, z, &split)?;
+
+        let ztz_inv = zt.t().dot(&zt).inv()?;
+        let coef = ztz_inv.dot(&zt.t().dot(&ybar));
+        let reduced_form_pz = ybar.t().dot(&zt).dot(&coef);
+        let resid = &ybar - &zt.dot(&coef);
+        let reduced_form_cov = resid.t().dot(&resid) / df_resid as f64;
+        let beta_2sls = reduced_form_pz[[0, 1]] / reduced_form_pz[[1, 1]];
+
+        let mut result = WeakIvRobustResult {
+            endogenous_var: match variable_names {
+                Some(names) if split.endog[0] < names.len() => names[split.endog[0]].clone(),
+                _ => format!("x{}", split.endog[0]),
+            },
+            beta_2sls,
+            alpha,
+            ar_set: Vec::new(),
+            clr_set: Vec::new(),
+            reduced_form_pz,
+            reduced_form_cov,
+            n_obs: n,
+            n_excluded: kz,
+            df_resid,
+        };
+
+        // AR set: b0'(Ȳ'PȲ - K2·F_crit·Ω)b0 <= 0 is a quadratic in β0
+        let f_crit = statrs::distribution::FisherSnedecor::new(kz as f64, df_resid as f64)
+            .map_err(|e| GreenersError::InvalidOperation(e.to_string()))?
+            .inverse_cdf(1.0 - alpha);
+        let g = &result.reduced_form_pz - &(&result.reduced_form_cov * (kz as f64 * f_crit));
+        result.ar_set = quadratic_set(g[[1, 1]], -2.0 * g[[0, 1]], g[[0, 0]]);
+
+        // CLR set: invert the test on a grid around the 2SLS estimate
+        let om = &result.reduced_form_cov;
+        let u_var = om[[0, 0]] - 2.0 * beta_2sls * om[[0, 1]] + beta_2sls.powi(2) * om[[1, 1]];
+        let se = (u_var / result.reduced_form_pz[[1, 1]]).sqrt().max(1e-8);
+        let accept = |b: f64| result.clr_test(b).1 > alpha;
+        result.clr_set = invert_on_grid(accept, beta_2sls, 50.0 * se, 4000);
+
+        Ok(result)
+    }
+}
+
+/// Stock–Yogo relative-bias critical values (5%, 10%, 20%, 30%), p = 1,
+/// K2 = 3..=10.
+const SY_BIAS_1: [[f64; 4]; 8] = [
+    [13.91, 9.08, 6.46, 5.39],
+    [16.85, 10.27, 6.71, 5.34],
+    [18.37, 10.83, 6.77, 5.25],
+    [19.28, 11.12, 6.76, 5.15],
+    [19.86, 11.29, 6.73, 5.07],
+    [20.25, 11.39, 6.69, 4.99],
+    [20.53, 11.46, 6.65, 4.92],
+    [20.74, 11.49, 6.61, 4.86],
+];
+
+/// p = 2, K2 = 4..=10.
+const SY_BIAS_2: [[f64; 4]; 7] = [
+    [11.04, 7.56, 5.57, 4.73],
+    [13.97, 8.78, 5.91, 4.79],
+    [15.72, 9.48, 6.08, 4.78],
+    [16.88, 9.92, 6.16, 4.76],
+    [17.70, 10.22, 6.20, 4.73],
+    [18.30, 10.43, 6.22, 4.70],
+    [18.76, 10.58, 6.23, 4.67],
+];
+
+/// p = 3, K2 = 5..=10.
+const SY_BIAS_3: [[f64; 4]; 6] = [
+    [9.53, 6.61, 4.99, 4.30],
+    [12.20, 7.77, 5.35, 4.40],
+    [13.95, 8.50, 5.56, 4.44],
+    [15.18, 9.01, 5.69, 4.46],
+    [16.10, 9.37, 5.78, 4.46],
+    [16.80, 9.64, 5.83, 4.45],
+];
+
+/// Stock–Yogo size critical values (10%, 15%, 20%, 25%), p = 1, K2 = 1..=10.
+const SY_SIZE_1: [[f64; 4]; 10] = [
+    [16.38, 8.96, 6.66, 5.53],
+    [19.93, 11.59, 8.75, 7.25],
+    [22.30, 12.83, 9.54, 7.80],
+    [24.58, 13.96, 10.26, 8.31],
+    [26.87, 15.09, 10.98, 8.84],
+    [29.18, 16.23, 11.72, 9.38],
+    [31.50, 17.38, 12.48, 9.93],
+    [33.84, 18.54, 13.24, 10.50],
+    [36.19, 19.71, 14.01, 11.07],
+    [38.54, 20.88, 14.78, 11.65],
+];
+
+/// p = 2, K2 = 2..=10.
+#[allow(clippy::approx_constant)] // 6.28 is a tabulated value, not τ
+const SY_SIZE_2: [[f64; 4]; 9] = [
+    [7.03, 4.58, 3.95, 3.63],
+    [13.43, 8.18, 6.40, 5.45],
+    [16.87, 9.93, 7.54, 6.28],
+    [19.45, 11.22, 8.38, 6.89],
+    [21.68, 12.33, 9.10, 7.42],
+    [23.72, 13.34, 9.77, 7.91],
+    [25.64, 14.31, 10.41, 8.39],
+    [27.51, 15.24, 11.03, 8.85],
+    [29.32, 16.16, 11.65, 9.31],
+];
+
+/// LIML κ: smallest eigenvalue of (Ȳ'M_Z Ȳ)⁻¹ Ȳ'M_W Ȳ with Ȳ = [y, X_endog].
+fn liml_kappa(y: &Array1<f64>, x: &Array2<f64>, z: &Array2<f64>) -> Result<f64, GreenersError> {
+    let split = split_instruments(x, z)?;
+    let n = x.nrows();
+    let mut ybar = Array2::<f64>::zeros((n, split.endog.len() + 1));
+    ybar.column_mut(0).assign(y);
+    for (j, &c) in split.endog.iter().enumerate() {
+        ybar.column_mut(j + 1).assign(&x.column(c));
+    }
+
+    let residual_cross = |basis: &Array2<f64>| -> Result<Array2<f64>, GreenersError> {
+        if basis.ncols() == 0 {
+            return Ok(ybar.t().dot(&ybar));
+        }
+        let coef = basis.t().dot(basis).inv()?.dot(&basis.t().dot(&ybar));
+        let resid = &ybar - &basis.dot(&coef);
+        Ok(resid.t().dot(&resid))
+    };
+    let w = x.select(nd::Axis(1), &split.exog);
+    let cross_w = residual_cross(&w)?;
+    let cross_z = residual_cross(z)?;
+
+    let root = sym_power(&cross_z, -0.5)?;
+    let (eigs, _) = root.dot(&cross_w).dot(&root).eigh(UPLO::Lower)?;
+    Ok(eigs.iter().copied().fold(f64::INFINITY, f64::min))
+}
+
+/// Column roles in an IV design: included exogenous columns of X, endogenous
+/// columns of X and excluded instrument columns of Z.
+struct InstrumentSplit {
+    exog: Vec<usize>,
+    /// Column of Z matching each exogenous column of X
+    exog_in_z: Vec<usize>,
+    endog: Vec<usize>,
+    excluded: Vec<usize>,
+}
+
+fn split_instruments(x: &Array2<f64>, z: &Array2<f64>) -> Result<InstrumentSplit, GreenersError> {
+    if x.nrows() != z.nrows() {
+        return Err(GreenersError::ShapeMismatch("Row count mismatch".into()));
+    }
+    let same = |a: nd::ArrayView1<f64>, b: nd::ArrayView1<f64>| {
+        let scale = a.iter().fold(1.0_f64, |m, v| m.max(v.abs()));
+        a.iter()
+            .zip(b.iter())
+            .all(|(u, v)| (u - v).abs() <= 1e-12 * scale)
+    };
+
+    let mut split = InstrumentSplit {
+        exog: Vec::new(),
+        exog_in_z: Vec::new(),
+        endog: Vec::new(),
+        excluded: Vec::new(),
+    };
+    for j in 0..x.ncols() {
+        match (0..z.ncols()).find(|&m| same(x.column(j), z.column(m))) {
+            Some(m) => {
+                split.exog.push(j);
+                split.exog_in_z.push(m);
+            }
+            None => split.endog.push(j),
+        }
+    }
+    split.excluded = (0..z.ncols())
+        .filter(|m| !split.exog_in_z.contains(m))
+        .collect();
+
+    if split.endog.is_empty() {
+        return Err(GreenersError::InvalidOperation(
+            "No endogenous regressors: every column of X also appears in Z".into(),
+        ));
+    }
+    if split.excluded.len() < split.endog.len() {
+        return Err(GreenersError::ShapeMismatch(format!(
+            "Order Condition Failed: {} excluded instruments for {} endogenous regressors",
+            split.excluded.len(),
+            split.endog.len()
+        )));
+    }
+    if x.nrows() <= z.ncols() {
+        return Err(GreenersError::ShapeMismatch(
+            "Not enough observations for the number of instruments".into(),
+        ));
+    }
+    Ok(split)
+}
+
+/// Endogenous regressors and excluded instruments with the included
+/// exogenous regressors partialled out.
+fn partial_out(
+    x: &Array2<f64>,
+    z: &Array2<f64>,
+    split: &InstrumentSplit,
+) -> Result<(Array2<f64>, Array2<f64>), GreenersError> {
+    partial_out_columns(&x.select(nd::Axis(1), &split.endog), x, z, split)
+}
+
+fn partial_out_columns(
+    target: &Array2<f64>,
+    x: &Array2<f64>,
+    z: &Array2<f64>,
+    split: &InstrumentSplit,
+) -> Result<(Array2<f64>, Array2<f64>), GreenersError> {
+    let z1 = z.select(nd::Axis(1), &split.excluded);
+    if split.exog.is_empty() {
+        return Ok((target.clone(), z1));
+    }
+    let w = x.select(nd::Axis(1), &split.exog);
+    let wtw_inv = w.t().dot(&w).inv()?;
+    let resid = |a: &Array2<f64>| a - &w.dot(&wtw_inv.dot(&w.t().dot(a)));
+    Ok((resid(target), resid(&z1)))
+}
+
+/// Covariance of vec(Π̂) for the multivariate regression of `v`'s columns on
+/// `zt` (residuals `v`), stacked column by column. CR2/CR3 take the cluster
+/// leverages from `z_full`, the design before the exogenous regressors were
+/// partialled out.
+fn pi_covariance(
+    zt: &Array2<f64>,
+    v: &Array2<f64>,
+    ztz_inv: &Array2<f64>,
+    z_full: &Array2<f64>,
+    cov_type: &CovarianceType,
+    df_resid: usize,
+) -> Result<Array2<f64>, GreenersError> {
+    let n = zt.nrows();
+    let kz = zt.ncols();
+    let p = v.ncols();
+    let m = kz * p;
+
+    let mut bread = Array2::<f64>::zeros((m, m));
+    for j in 0..p {
+        bread
+            .slice_mut(nd::s![j * kz..(j + 1) * kz, j * kz..(j + 1) * kz])
+            .assign(ztz_inv);
+    }
+
+    if *cov_type == CovarianceType::NonRobust {
+        let sigma = v.t().dot(v) / df_resid as f64;
+        let mut cov = Array2::<f64>::zeros((m, m));
+        for a in 0..p {
+            for b in 0..p {
+                cov.slice_mut(nd::s![a * kz..(a + 1) * kz, b * kz..(b + 1) * kz])
+                    .assign(&(ztz_inv * sigma[[a, b]]));
+            }
+        }
+        return Ok(cov);
+    }
+
+    // Scores s_i = v_i ⊗ z_i
+    let mut scores = Array2::<f64>::zeros((n, m));
+    for i in 0..n {
+        for j in 0..p {
+            for l in 0..kz {
+                scores[[i, j * kz + l]] = v[[i, j]] * zt[[i, l]];
+            }
+        }
+    }
+
+    let small = n as f64 / df_resid as f64;
+
+    let meat = match cov_type {
+        CovarianceType::Clustered(ids) | CovarianceType::CR2(ids) | CovarianceType::CR3(ids) => {
+            if ids.len() != n {
+                return Err(GreenersError::ShapeMismatch(format!(
+                    "Cluster IDs length ({}) must match number of observations ({})",
+                    ids.len(),
+                    n
+                )));
+            }
+            if let CovarianceType::Clustered(_) = cov_type {
+                Sandwich::cluster_meat(&scores, ids)? * ((n as f64 - 1.0) / df_resid as f64)
+            } else {
+                // Every equation shares Z, so one adjustment A_g per cluster
+                // scales all columns of V_g; P_Z = P_W + P_Z̃ gives the
+                // leverage of the full first stage
+                let zz_inv = z_full.t().dot(z_full).inv()?;
+                let jackknife = matches!(cov_type, CovarianceType::CR3(_));
+                let mut clusters: indexmap::IndexMap<usize, Vec<usize>> = indexmap::IndexMap::new();
+                for (i, &g) in ids.iter().enumerate() {
+                    clusters.entry(g).or_default().push(i);
+                }
+                let g = clusters.len() as f64;
+                if g < 2.0 {
+                    return Err(GreenersError::InvalidOperation(
+                        "Cluster-robust SE need at least two clusters".into(),
+                    ));
+                }
+                let mut meat = Array2::<f64>::zeros((m, m));
+                for rows in clusters.values() {
+                    let z_g = zt.select(nd::Axis(0), rows);
+                    let v_g = v.select(nd::Axis(0), rows);
+                    let a_g = Sandwich::cluster_adjustment(
+                        &z_full.select(nd::Axis(0), rows),
+                        &zz_inv,
+                        jackknife,
+                    )?;
+                    let u_g = z_g.t().dot(&a_g.dot(&v_g));
+                    let s_g = Array1::from_iter(u_g.t().iter().copied());
+                    let col = s_g.view().insert_axis(nd::Axis(1));
+                    meat += &col.dot(&col.t());
+                }
+                if jackknife {
+                    meat *= (g - 1.0) / g;
+                }
+                meat
+            }
+        }
+        CovarianceType::ClusteredTwoWay(ids1, ids2) => {
+            Sandwich::two_way_cluster_meat(&scores, ids1, ids2)?
+                * ((n as f64 - 1.0) / df_resid as f64)
+        }
+        CovarianceType::NeweyWest(lags) => {
+            let mut meat = scores.t().dot(&scores);
+            for lag in 1..=*lags {
+                let w = 1.0 - lag as f64 / (*lags + 1) as f64;
+                let gamma = scores
+                    .slice(nd::s![lag.., ..])
+                    .t()
+                    .dot(&scores.slice(nd::s![..n - lag, ..]));
+                meat = meat + (&gamma + &gamma.t()) * w;
+            }
+            meat * small
+        }
+        CovarianceType::Hac(_, _)
+        | CovarianceType::DriscollKraay(_, _)
+        | CovarianceType::Conley(_, _) => {
+            Sandwich::score_meat(&scores, cov_type)?.unwrap_or_else(|| scores.t().dot(&scores))
+                * small
+        }
+        CovarianceType::HC0 => scores.t().dot(&scores),
+        _ => scores.t().dot(&scores) * small,
+    };
+    Ok(bread.dot(&meat).dot(&bread))
+}
+
+/// Kleibergen–Paap (2006) rk Wald statistic for H0: rank(Π) = p - 1.
+fn kleibergen_paap_rk(
+    yt: &Array2<f64>,
+    zt: &Array2<f64>,
+    pi: &Array2<f64>,
+    cov_vec: &Array2<f64>,
+) -> Result<f64, GreenersError> {
+    let n = yt.nrows() as f64;
+    let p = yt.ncols();
+    let q = p - 1;
+
+    // Normalised coefficient matrix Θ = F Π G⁻¹ with F'F = Z̃'Z̃/n, G'G = Ỹ'Ỹ/n
+    let f_mat = (zt.t().dot(zt) / n).cholesky(UPLO::Upper)?;
+    let g_mat = (yt.t().dot(yt) / n).cholesky(UPLO::Upper)?;
+    let g_inv = g_mat.inv()?;
+    let theta = f_mat.dot(pi).dot(&g_inv);
+    // vec(F Π G⁻¹) = (G⁻¹' ⊗ F) vec(Π)
+    let k_mat = kron(&g_inv.t().to_owned(), &f_mat);
+    let v_theta = k_mat.dot(cov_vec).dot(&k_mat.t());
+
+    let (u, _, vt) = theta.svd(true, true)?;
+    let u = u.ok_or(GreenersError::OptimizationFailed)?;
+    let v = vt.ok_or(GreenersError::OptimizationFailed)?.t().to_owned();
+
+    let u_q = u.slice(nd::s![.., q..]).to_owned();
+    let u22 = u.slice(nd::s![q.., q..]).to_owned();
+    let v_q = v.slice(nd::s![.., q..]).to_owned();
+    let v22 = v.slice(nd::s![q.., q..]).to_owned();
+
+    let a_perp = u_q
+        .dot(&u22.inv()?)
+        .dot(&sym_power(&u22.dot(&u22.t()), 0.5)?);
+    let b_perp = sym_power(&v22.dot(&v22.t()), 0.5)?
+        .dot(&v22.t().to_owned().inv()?)
+        .dot(&v_q.t());
+
+    let lambda_mat = a_perp.t().dot(&theta).dot(&b_perp.t());
+    // Column-major vec of the (K2 - q) × (p - q) matrix
+    let lambda: Array1<f64> = lambda_mat.t().iter().copied().collect();
+    let k_lambda = kron(&b_perp, &a_perp.t().to_owned());
+    let omega = k_lambda.dot(&v_theta).dot(&k_lambda.t());
+    Ok(lambda.dot(&omega.inv()?.dot(&lambda)))
+}
+
+/// Montiel Olea–Pflueger effective F with its Patnaik-approximated 5%
+/// critical value for τ = 10% (TSLS, simplified procedure).
+fn effective_f_stat(
+    zt: &Array2<f64>,
+    pi: &Array2<f64>,
+    cov_vec: &Array2<f64>,
+) -> Result<(f64, f64, f64), GreenersError> {
+    let ztz = zt.t().dot(zt);
+    let pi_col = pi.column(0).to_owned();
+    // W = (Z̃'Z̃)^{1/2} V(π̂) (Z̃'Z̃)^{1/2}
+    let root = sym_power(&ztz, 0.5)?;
+    let w = root.dot(cov_vec).dot(&root);
+    let trace = w.diag().sum();
+    let eff_f = pi_col.dot(&ztz.dot(&pi_col)) / trace;
+
+    let x = 10.0; // 1 / τ
+    let (eigs, _) = w.eigh(UPLO::Lower)?;
+    let max_eig = eigs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let trace_sq = w.dot(&w).diag().sum();
+    let k_eff = trace * trace * (1.0 + 2.0 * x) / (trace_sq + 2.0 * x * trace * max_eig);
+
+    // Noncentral χ²(K_eff, x·K_eff) quantile via Patnaik: c·χ²(ν)
+    let lambda = x * k_eff;
+    let c = (k_eff + 2.0 * lambda) / (k_eff + lambda);
+    let nu = (k_eff + lambda).powi(2) / (k_eff + 2.0 * lambda);
+    let chi2 = ChiSquared::new(nu).map_err(|e| GreenersError::InvalidOperation(e.to_string()))?;
+    let crit = c * chi2.inverse_cdf(0.95) / k_eff;
+    Ok((eff_f, k_eff, crit))
+}
+
+/// Conditional p-value of the CLR statistic given Q_T
+/// (Andrews, Moreira and Stock, 2007, eq. 3.2), integrated with s = sin θ.
+fn clr_pvalue(lr: f64, qt: f64, k: usize) -> f64 {
+    let chi_k = match ChiSquared::new(k as f64) {
+        Ok(d) => d,
+        Err(_) => return f64::NAN,
+    };
+    if lr <= 0.0 {
+        return 1.0;
+    }
+    if k == 1 {
+        return 1.0 - chi_k.cdf(lr);
+    }
+    let kf = k as f64;
+    let k4 = (statrs::function::gamma::ln_gamma(kf / 2.0)
+        - statrs::function::gamma::ln_gamma((kf - 1.0) / 2.0))
+    .exp()
+        / std::f64::consts::PI.sqrt();
+
+    // Simpson's rule on θ ∈ [0, π/2]
+    let steps = 200;
+    let h = std::f64::consts::FRAC_PI_2 / steps as f64;
+    let integrand = |theta: f64| {
+        let s = theta.sin();
+        chi_k.cdf((qt + lr) / (1.0 + qt * s * s / lr)) * theta.cos().powf(kf - 2.0)
+    };
+    let mut total = integrand(0.0) + integrand(std::f64::consts::FRAC_PI_2);
+    for i in 1..steps {
+        let w = if i % 2 == 1 { 4.0 } else { 2.0 };
+        total += w * integrand(i as f64 * h);
+    }
+    (1.0 - 2.0 * k4 * total * h / 3.0).clamp(0.0, 1.0)
+}
+
+/// Solution set of a β² + b β + c ≤ 0 as a list of intervals.
+fn quadratic_set(a: f64, b: f64, c: f64) -> Vec<(f64, f64)> {
+    let scale = a.abs().max(b.abs()).max(c.abs()).max(f64::MIN_POSITIVE);
+    if a.abs() <= 1e-12 * scale {
+        if b.abs() <= 1e-12 * scale {
+            return if c <= 0.0 {
+                vec![(f64::NEG_INFINITY, f64::INFINITY)]
+            } else {
+                Vec::new()
+            };
+        }
+        let root = -c / b;
+        return if b > 0.0 {
+            vec![(f64::NEG_INFINITY, root)]
+        } else {
+            vec![(root, f64::INFINITY)]
+        };
+    }
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return if a > 0.0 {
+            Vec::new()
+        } else {
+            vec![(f64::NEG_INFINITY, f64::INFINITY)]
+        };
+    }
+    let r1 = (-b - disc.sqrt()) / (2.0 * a);
+    let r2 = (-b + disc.sqrt()) / (2.0 * a);
+    let (lo, hi) = (r1.min(r2), r1.max(r2));
+    if a > 0.0 {
+        vec![(lo, hi)]
+    } else {
+        vec![(f64::NEG_INFINITY, lo), (hi, f64::INFINITY)]
+    }
+}
+
+/// Acceptance region of a test on `center ± half_width`, with boundaries
+/// refined by bisection. Regions reaching the grid edge are reported as
+/// unbounded.
+fn invert_on_grid<F: Fn(f64) -> bool>(
+    accept: F,
+    center: f64,
+    half_width: f64,
+    points: usize,
+) -> Vec<(f64, f64)> {
+    let grid: Vec<f64> = (0..=points)
+        .map(|i| center - half_width + 2.0 * half_width * i as f64 / points as f64)
+        .collect();
+    let flags: Vec<bool> = grid.iter().map(|&b| accept(b)).collect();
+    let boundary = |lo: f64, hi: f64, lo_accepted: bool| {
+        let (mut a, mut b) = (lo, hi);
+        for _ in 0..50 {
+            let mid = 0.5 * (a + b);
+            if accept(mid) == lo_accepted {
+                a = mid;
+            } else {
+                b = mid;
+            }
+        }
+        0.5 * (a + b)
+    };
+
+    let mut set = Vec::new();
+    let mut start: Option<f64> = None;
+    for i in 0..grid.len() {
+        match (flags[i], start) {
+            (true, None) => {
+                start = Some(if i == 0 {
+                    f64::NEG_INFINITY
+                } else {
+                    boundary(grid[i - 1], grid[i], false)
+                });
+            }
+            (false, Some(lo)) => {
+                set.push((lo, boundary(grid[i - 1], grid[i], true)));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(lo) = start {
+        set.push((lo, f64::INFINITY));
+    }
+    set
+}
+
+/// Symmetric matrix power via the eigendecomposition (eigenvalues floored
+/// at zero for non-negative powers).
+fn sym_power(a: &Array2<f64>, power: f64) -> Result<Array2<f64>, GreenersError> {
+    let (eigs, vecs) = a.eigh(UPLO::Lower)?;
+    if power < 0.0 && eigs.iter().any(|&e| e <= 0.0) {
+        return Err(GreenersError::SingularMatrix);
+    }
+    let d = eigs.mapv(|e| e.max(0.0).powf(power));
+    Ok((&vecs * &d).dot(&vecs.t()))
+}
+
+fn kron(a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+    let (ar, ac) = a.dim();
+    let (br, bc) = b.dim();
+    let mut out = Array2::<f64>::zeros((ar * br, ac * bc));
+    for i in 0..ar {
+        for j in 0..ac {
+            out.slice_mut(nd::s![i * br..(i + 1) * br, j * bc..(j + 1) * bc])
+                .assign(&(b * a[[i, j]]));
+        }
+    }
+    out
+}
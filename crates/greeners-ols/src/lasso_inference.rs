@@ -0,0 +1,300 @@
+//! Inference on a treatment coefficient after lasso selection of controls
+//! (Belloni, Chernozhukov & Hansen 2014; Chernozhukov, Hansen & Spindler
+//! 2015).
+//!
+//! Both estimators select controls with the plug-in lasso of
+//! [`RegPath`](crate::reg_path::RegPath), once for the outcome and once for
+//! the treatment, so that a control that matters for either equation is not
+//! lost:
+//!
+//! - **Post-double-selection**: OLS of y on d and the union of the selected
+//!   controls.
+//! - **Partialling-out**: OLS of the post-lasso residuals of y on those of d.
+//!
+//! Standard errors come from the final OLS fit with the requested covariance.
+
+use crate::ols::OLS;
+use crate::reg_path::{LambdaRule, Penalty, RegPath, RegPathConfig};
+use greeners_core::{
+    CovarianceType, DataFrame, Formula, GreenersError, InferenceType, MissingPolicy,
+};
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How the selected controls enter the final regression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LassoInferenceMethod {
+    PostDoubleSelection,
+    PartiallingOut,
+}
+
+/// Result of [`LassoInference`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LassoInferenceResult {
+    pub method: LassoInferenceMethod,
+    /// Estimated treatment effect
+    pub coef: f64,
+    pub std_error: f64,
+    pub t_value: f64,
+    pub p_value: f64,
+    pub conf_lower: f64,
+    pub conf_upper: f64,
+    pub n_obs: usize,
+    /// Number of candidate controls
+    pub n_controls: usize,
+    /// Controls selected by the lasso for the outcome
+    pub selected_outcome: Vec<String>,
+    /// Controls selected by the lasso for the treatment
+    pub selected_treatment: Vec<String>,
+    /// Union of both selections
+    pub selected_controls: Vec<String>,
+    pub treatment_name: String,
+    pub cov_type: CovarianceType,
+    pub inference_type: InferenceType,
+}
+
+impl fmt::Display for LassoInferenceResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = match self.method {
+            LassoInferenceMethod::PostDoubleSelection => " Post-Double-Selection Lasso ",
+            LassoInferenceMethod::PartiallingOut => " Partialling-Out Lasso ",
+        };
+        let stat_label = match self.inference_type {
+            InferenceType::StudentT => "t",
+            InferenceType::Normal => "z",
+        };
+        writeln!(f, "\n{:=^78}", title)?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "No. Observations:", self.n_obs, "Candidate controls:", self.n_controls
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Selected (y):",
+            self.selected_outcome.len(),
+            "Selected (d):",
+            self.selected_treatment.len()
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15}",
+            "Controls used:",
+            self.selected_controls.len()
+        )?;
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<12} {:>10} {:>10} {:>8} {:>8} {:>10} {:>10}",
+            "",
+            "coef",
+            "std err",
+            stat_label,
+            format!("P>|{}|", stat_label),
+            "[0.025",
+            "0.975]"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<12} {:>10.4} {:>10.4} {:>8.3} {:>8.3} {:>10.4} {:>10.4}",
+            self.treatment_name,
+            self.coef,
+            self.std_error,
+            self.t_value,
+            self.p_value,
+            self.conf_lower,
+            self.conf_upper
+        )?;
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Lasso-based inference on the effect of `d` on `y` with many controls.
+pub struct LassoInference;
+
+impl LassoInference {
+    /// Estimates the coefficient of `treatment` in `formula`, treating every
+    /// other regressor as a candidate control. Rows with missing values are
+    /// dropped.
+    pub fn from_formula(
+        formula: &Formula,
+        treatment: &str,
+        data: &DataFrame,
+        method: LassoInferenceMethod,
+        cov_type: CovarianceType,
+    ) -> Result<LassoInferenceResult, GreenersError> {
+        Self::from_formula_with_missing(
+            formula,
+            treatment,
+            data,
+            method,
+            cov_type,
+            MissingPolicy::Drop,
+        )
+    }
+
+    /// [`LassoInference::from_formula`] with an explicit missing-value policy.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        treatment: &str,
+        data: &DataFrame,
+        method: LassoInferenceMethod,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<LassoInferenceResult, GreenersError> {
+        let mut formula = formula.clone();
+        formula.intercept = false;
+        let dm = data.design_matrices(&formula, missing)?;
+        let names = dm.info.column_names();
+        let pos = names.iter().position(|n| n == treatment).ok_or_else(|| {
+            GreenersError::VariableNotFound(format!(
+                "Treatment '{}' is not a regressor of the formula",
+                treatment
+            ))
+        })?;
+        let controls: Vec<usize> = (0..names.len()).filter(|&j| j != pos).collect();
+        let d = dm.x.column(pos).to_owned();
+        let x = dm.x.select(Axis(1), &controls);
+        let control_names = controls.iter().map(|&j| names[j].clone()).collect();
+        let cov_type = dm.rows.select_cov_type(cov_type);
+        let mut result = Self::fit(&dm.y, &d, &x, method, cov_type, Some(control_names))?;
+        result.treatment_name = treatment.to_string();
+        Ok(result)
+    }
+
+    /// Post-double-selection estimate of the effect of `d`.
+    pub fn post_double_selection(
+        y: &Array1<f64>,
+        d: &Array1<f64>,
+        x: &Array2<f64>,
+        cov_type: CovarianceType,
+        control_names: Option<Vec<String>>,
+    ) -> Result<LassoInferenceResult, GreenersError> {
+        Self::fit(
+            y,
+            d,
+            x,
+            LassoInferenceMethod::PostDoubleSelection,
+            cov_type,
+            control_names,
+        )
+    }
+
+    /// Partialling-out estimate of the effect of `d`.
+    pub fn partialling_out(
+        y: &Array1<f64>,
+        d: &Array1<f64>,
+        x: &Array2<f64>,
+        cov_type: CovarianceType,
+        control_names: Option<Vec<String>>,
+    ) -> Result<LassoInferenceResult, GreenersError> {
+        Self::fit(
+            y,
+            d,
+            x,
+            LassoInferenceMethod::PartiallingOut,
+            cov_type,
+            control_names,
+        )
+    }
+
+    /// `x` holds the candidate controls without an intercept.
+    pub fn fit(
+        y: &Array1<f64>,
+        d: &Array1<f64>,
+        x: &Array2<f64>,
+        method: LassoInferenceMethod,
+        cov_type: CovarianceType,
+        control_names: Option<Vec<String>>,
+    ) -> Result<LassoInferenceResult, GreenersError> {
+        let n = y.len();
+        let p = x.ncols();
+        if d.len() != n || x.nrows() != n {
+            return Err(GreenersError::ShapeMismatch(
+                "y, d and the controls must have the same number of rows".into(),
+            ));
+        }
+        let names = control_names.unwrap_or_else(|| (0..p).map(|j| format!("x{}", j)).collect());
+        let config = RegPathConfig {
+            penalty: Penalty::Lasso,
+            rule: LambdaRule::Plugin,
+            // Only the plug-in fit is used; keep the path short
+            n_lambdas: 10,
+            ..RegPathConfig::default()
+        };
+        let lasso_y = RegPath::fit_with_config(y, x, &config, Some(names.clone()))?;
+        let lasso_d = RegPath::fit_with_config(d, x, &config, Some(names.clone()))?;
+        let sel_y = lasso_y.selected_indices();
+        let sel_d = lasso_d.selected_indices();
+        let mut union: Vec<usize> = sel_y.iter().chain(&sel_d).copied().collect();
+        union.sort_unstable();
+        union.dedup();
+
+        let fit = match method {
+            LassoInferenceMethod::PostDoubleSelection => {
+                let mut design = Array2::<f64>::ones((n, 2 + union.len()));
+                design.column_mut(1).assign(d);
+                for (c, &j) in union.iter().enumerate() {
+                    design.column_mut(2 + c).assign(&x.column(j));
+                }
+                let mut cols = vec!["const".to_string(), "d".to_string()];
+                cols.extend(union.iter().map(|&j| names[j].clone()));
+                let ols = OLS::fit_with_names(y, &design, cov_type.clone(), Some(cols))?;
+                // The treatment is column 1 of the design; locate it by
+                // position so a control that is also named "d" cannot match
+                if ols.omitted_vars.iter().any(|&(p, _)| p == 1) {
+                    return Err(GreenersError::InvalidOperation(
+                        "Treatment is collinear with the selected controls".into(),
+                    ));
+                }
+                let pos = 1 - ols.omitted_vars.iter().filter(|&&(p, _)| p < 1).count();
+                (ols, pos)
+            }
+            LassoInferenceMethod::PartiallingOut => {
+                let ry = post_lasso_residuals(y, x, &sel_y)?;
+                let rd = post_lasso_residuals(d, x, &sel_d)?;
+                let design = rd.insert_axis(Axis(1));
+                (OLS::fit(&ry, &design, cov_type.clone())?, 0)
+            }
+        };
+        let (ols, pos) = fit;
+
+        Ok(LassoInferenceResult {
+            method,
+            coef: ols.params[pos],
+            std_error: ols.std_errors[pos],
+            t_value: ols.t_values[pos],
+            p_value: ols.p_values[pos],
+            conf_lower: ols.conf_lower[pos],
+            conf_upper: ols.conf_upper[pos],
+            n_obs: n,
+            n_controls: p,
+            selected_outcome: lasso_y.selected_vars,
+            selected_treatment: lasso_d.selected_vars,
+            selected_controls: union.iter().map(|&j| names[j].clone()).collect(),
+            treatment_name: "d".to_string(),
+            cov_type,
+            inference_type: ols.inference_type,
+        })
+    }
+}
+
+/// Residuals of `v` on an intercept and the selected controls.
+fn post_lasso_residuals(
+    v: &Array1<f64>,
+    x: &Array2<f64>,
+    selected: &[usize],
+) -> Result<Array1<f64>, GreenersError> {
+    let n = v.len();
+    let mut design = Array2::<f64>::ones((n, 1 + selected.len()));
+    for (c, &j) in selected.iter().enumerate() {
+        design.column_mut(1 + c).assign(&x.column(j));
+    }
+    let names = (0..design.ncols()).map(|j| format!("c{}", j)).collect();
+    let fit = OLS::fit_with_names(v, &design, CovarianceType::NonRobust, Some(names))?;
+    let used = fit.x_clean.as_ref().unwrap_or(&design);
+    Ok(v - &used.dot(&fit.params))
+}
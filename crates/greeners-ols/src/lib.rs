@@ -3,19 +3,29 @@
 // Re-export public items that are unique within this crate at the crate root.
 // Items with names duplicated across modules remain namespaced.
 
-pub use event_study::{EventStudy, EventStudyResult};
+pub use event_study::{
+    ControlGroup, EventAggregation, EventStudy, EventStudyResult, GroupTimeEffect, StaggeredConfig,
+    StaggeredEventStudy, StaggeredMethod, StaggeredResult,
+};
 pub use fmols::{FmolsResult, FMOLS};
 pub use gls::{FglsResult, FGLS};
 pub use glsar::{GlsarResult, GLSAR};
-pub use gmm::{GmmResult, GMM};
+pub use gmm::{
+    GmmDTestResult, GmmResult, GmmWeighting, MomentFn, MomentJacobianFn, NonlinearGMM,
+    NonlinearGmmOptions, NonlinearGmmResult, GMM,
+};
 pub use heckman::{Heckman, HeckmanResult};
-pub use iv::{EndogeneityTestResult, IvResult, SarganTestResult, IV};
+pub use iv::{
+    EndogeneityTestResult, FirstStageResult, IvResult, KClassEstimator, SarganTestResult,
+    StockYogoCritical, WeakIvRobustResult, IV,
+};
+pub use lasso_inference::{LassoInference, LassoInferenceMethod, LassoInferenceResult};
 pub use nls::{
     predict_ces, predict_cobb_douglas, predict_exp, predict_logistic, predict_power, NlsResult, NLS,
 };
 pub use ols::{OlsResult, PredictionResult, OLS};
 pub use quantile::{QuantileReg, QuantileResult};
-pub use reg_path::{RegPath, RegPathResult};
+pub use reg_path::{LambdaRule, Penalty, RegPath, RegPathConfig, RegPathResult};
 pub use rlm::{RlmResult, RobustNorm, RLM};
 pub use rolling::{RecursiveLS, RecursiveLSResult, RollingOLS, RollingResult, RollingWLS};
 pub use sur::{SurEquation, SurEquationResult, SurResult, SUR};
@@ -30,6 +40,7 @@ pub mod glsar;
 pub mod gmm;
 pub mod heckman;
 pub mod iv;
+pub mod lasso_inference;
 pub mod nls;
 pub mod ols;
 pub mod quantile;
@@ -1,8 +1,13 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::{LinalgInverse as _, LinalgQR as _};
-use greeners_core::{CovarianceType, InferenceType};
-use greeners_core::{DataFrame, Formula};
+use greeners_core::model::align_design;
+use greeners_core::{
+    CovarianceType, InferenceType, Sandwich, WildBootstrap, WildBootstrapConfig,
+    WildBootstrapResult,
+};
+use greeners_core::{DataFrame, DesignInfo, FittedModel, Formula, MissingPolicy, Model, RowMap};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, FisherSnedecor, Normal, StudentsT};
 use std::fmt;
 
@@ -10,7 +15,7 @@ use std::fmt;
 type InferenceResult = (Array1<f64>, Array1<f64>, Array1<f64>);
 
 /// Prediction with standard errors and confidence intervals.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionResult {
     pub mean: Array1<f64>,
     pub se: Array1<f64>,
@@ -18,7 +23,7 @@ pub struct PredictionResult {
     pub ci_upper: Array1<f64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OlsResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -42,6 +47,11 @@ pub struct OlsResult {
     pub variable_names: Option<Vec<String>>, // Names of variables (from Formula)
     pub omitted_vars: Vec<(usize, String)>,  // (position, name) of vars dropped for collinearity
     pub x_clean: Option<Array2<f64>>,        // Design matrix after collinearity removal
+    pub design_info: Option<DesignInfo>,     // Formula design learned at fit time
+    pub row_map: Option<RowMap>,             // Rows dropped for missing values (from_formula)
+    pub cov_matrix: Option<Array2<f64>>,     // Covariance matrix of the coefficients
+    #[serde(default)]
+    pub satterthwaite_df: Option<Array1<f64>>, // Per-coefficient df (CR2/CR3 only)
 }
 
 impl OlsResult {
@@ -71,6 +81,21 @@ impl OlsResult {
         x_new.dot(&self.params)
     }
 
+    /// Predict on a new DataFrame for a model fitted with `OLS::from_formula`.
+    ///
+    /// The design matrix is rebuilt with the categorical levels, polynomial
+    /// centering and spline knots learned at fit time, and columns omitted for
+    /// collinearity are dropped.
+    pub fn predict_df(&self, data: &DataFrame) -> Result<Array1<f64>, GreenersError> {
+        let info = self.design_info.as_ref().ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "predict_df requires a model fitted with from_formula".into(),
+            )
+        })?;
+        let x_new = info.build_x_omitting(data, &self.omitted_vars)?;
+        Ok(self.predict(&x_new))
+    }
+
     /// Calculate residuals for given data
     ///
     /// # Arguments
@@ -405,6 +430,49 @@ impl OlsResult {
         Ok((g_hat, se, t, p))
     }
 
+    /// Wild bootstrap test of H0: β_`coef` = `null` with a test-inversion
+    /// confidence interval (Stata's `boottest`).
+    ///
+    /// When the model was fitted with `CovarianceType::Clustered` the wild
+    /// cluster bootstrap is used with those clusters; otherwise observations
+    /// are perturbed individually and the statistic is HC1-robust. Two-way
+    /// clustering is not supported.
+    ///
+    /// # Arguments
+    /// * `y` - Dependent variable used in the fit
+    /// * `x` - Design matrix used in the fit (collinear columns dropped by
+    ///   the fit are removed automatically)
+    /// * `coef` - Index of the coefficient in `params`
+    /// * `null` - Hypothesised value
+    /// * `config` - Bootstrap settings
+    pub fn wild_bootstrap(
+        &self,
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        coef: usize,
+        null: f64,
+        config: &WildBootstrapConfig,
+    ) -> Result<WildBootstrapResult, GreenersError> {
+        let x = self.x_clean.as_ref().unwrap_or(x);
+        if x.ncols() != self.params.len() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "X has {} columns but the model has {} coefficients",
+                x.ncols(),
+                self.params.len()
+            )));
+        }
+        let clusters = match &self.cov_type {
+            CovarianceType::Clustered(ids) => Some(ids.as_slice()),
+            CovarianceType::ClusteredTwoWay(_, _) => {
+                return Err(GreenersError::InvalidOperation(
+                    "Wild bootstrap does not support two-way clustering".into(),
+                ))
+            }
+            _ => None,
+        };
+        WildBootstrap::test(y, x, clusters, coef, null, config)
+    }
+
     /// Helper function to compute p-values and confidence intervals
     ///
     /// This function computes statistical inference quantities using either
@@ -464,6 +532,42 @@ impl OlsResult {
         Ok((p_values, conf_lower, conf_upper))
     }
 
+    /// Student-t inference with a separate (possibly fractional) number of
+    /// degrees of freedom per coefficient, as produced by CR2/CR3.
+    pub fn compute_inference_per_coef(
+        t_values: &Array1<f64>,
+        std_errors: &Array1<f64>,
+        params: &Array1<f64>,
+        df: &Array1<f64>,
+    ) -> Result<InferenceResult, GreenersError> {
+        let k = params.len();
+        let mut p_values = Array1::<f64>::zeros(k);
+        let mut conf_lower = Array1::<f64>::zeros(k);
+        let mut conf_upper = Array1::<f64>::zeros(k);
+        for j in 0..k {
+            if df[j].is_nan() || df[j] <= 0.0 {
+                p_values[j] = f64::NAN;
+                conf_lower[j] = f64::NAN;
+                conf_upper[j] = f64::NAN;
+                continue;
+            }
+            let t_dist =
+                StudentsT::new(0.0, 1.0, df[j]).map_err(|_| GreenersError::OptimizationFailed)?;
+            let t = t_values[j];
+            p_values[j] = if t.is_nan() {
+                f64::NAN
+            } else if !t.is_finite() {
+                0.0
+            } else {
+                2.0 * (1.0 - t_dist.cdf(t.abs()))
+            };
+            let margin = std_errors[j] * t_dist.inverse_cdf(0.975);
+            conf_lower[j] = params[j] - margin;
+            conf_upper[j] = params[j] + margin;
+        }
+        Ok((p_values, conf_lower, conf_upper))
+    }
+
     /// Change inference type and recompute p-values and confidence intervals
     ///
     /// This method allows you to switch between Student's t-distribution and
@@ -498,13 +602,21 @@ impl OlsResult {
     /// # }
     /// ```
     pub fn with_inference(mut self, inference_type: InferenceType) -> Result<Self, GreenersError> {
-        let (p_values, conf_lower, conf_upper) = Self::compute_inference(
-            &self.t_values,
-            &self.std_errors,
-            &self.params,
-            self.df_resid,
-            &inference_type,
-        )?;
+        let (p_values, conf_lower, conf_upper) = match (&self.satterthwaite_df, &inference_type) {
+            (Some(df), InferenceType::StudentT) => Self::compute_inference_per_coef(
+                &self.t_values,
+                &self.std_errors,
+                &self.params,
+                df,
+            )?,
+            _ => Self::compute_inference(
+                &self.t_values,
+                &self.std_errors,
+                &self.params,
+                self.df_resid,
+                &inference_type,
+            )?,
+        };
 
         self.p_values = p_values;
         self.conf_lower = conf_lower;
@@ -522,32 +634,7 @@ impl fmt::Display for OlsResult {
             InferenceType::Normal => "z",
         };
 
-        let cov_str = match &self.cov_type {
-            CovarianceType::NonRobust => "Non-Robust".to_string(),
-            CovarianceType::HC1 => "Robust (HC1)".to_string(),
-            CovarianceType::HC2 => "Robust (HC2)".to_string(),
-            CovarianceType::HC3 => "Robust (HC3)".to_string(),
-            CovarianceType::HC4 => "Robust (HC4)".to_string(),
-            CovarianceType::NeweyWest(lags) => format!("HAC (Newey-West, L={})", lags),
-            CovarianceType::Clustered(clusters) => {
-                let n_clusters = clusters
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                format!("Clustered ({} clusters)", n_clusters)
-            }
-            CovarianceType::ClusteredTwoWay(clusters1, clusters2) => {
-                let n_clusters_1 = clusters1
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                let n_clusters_2 = clusters2
-                    .iter()
-                    .collect::<std::collections::HashSet<_>>()
-                    .len();
-                format!("Two-Way Clustered ({}×{})", n_clusters_1, n_clusters_2)
-            }
-        };
+        let cov_str = Sandwich::describe(&self.cov_type);
 
         writeln!(f, "\n{:=^78}", " OLS Regression Results ")?;
         writeln!(
@@ -676,9 +763,27 @@ impl OLS {
         data: &DataFrame,
         cov_type: CovarianceType,
     ) -> Result<OlsResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, cov_type, Some(var_names))
+        Self::from_formula_with_missing(formula, data, cov_type, MissingPolicy::Drop)
+    }
+
+    /// Fit OLS from a formula with an explicit missing-value policy.
+    ///
+    /// `from_formula` uses `MissingPolicy::Drop`. The dropped rows are stored
+    /// in `result.row_map`; cluster ids in `cov_type` may be given for all
+    /// rows of `data` and are subset accordingly.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<OlsResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let cov_type = dm.rows.select_cov_type(cov_type);
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, cov_type, Some(var_names))?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Detect and remove perfectly collinear columns using QR decomposition.
@@ -828,8 +933,20 @@ impl OLS {
         // src/ols.rs (inside OLS::fit, replace the 'match cov_type' block)
 
         // 3. Covariance Matrix Selection
+        // Bell-McCaffrey degrees of freedom, only set by CR2/CR3
+        let mut satterthwaite_df = None;
         let cov_matrix = match &cov_type {
             CovarianceType::NonRobust => &xt_x_inv * sigma2,
+            CovarianceType::HC0 => {
+                // HC0: White (1980) without small-sample correction
+                // V = (X'X)^-1 * X' diag(u²) X * (X'X)^-1
+                let mut x_weighted = x_to_use.clone();
+                for (i, mut row) in x_weighted.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    row *= residuals[i].powi(2);
+                }
+                let meat = x_t.dot(&x_weighted);
+                xt_x_inv.dot(&meat).dot(&xt_x_inv)
+            }
             CovarianceType::HC1 => {
                 // HC1: White's heteroscedasticity-robust SE with small-sample correction
                 // V = (X'X)^-1 * X' diag(u²) X * (X'X)^-1 * (n / (n-k))
@@ -950,6 +1067,35 @@ impl OLS {
                 let bread = &xt_x_inv;
                 bread.dot(&meat).dot(bread)
             }
+            CovarianceType::HC5 => {
+                // HC5: Cribari-Neto, Souza & Vasconcellos (2007)
+                // V = (X'X)^-1 * X' diag(u² / (1 - h_i)^(δᵢ/2)) X * (X'X)^-1
+                // where δᵢ = min(n·h_i/k, max(4, 0.7·n·h_max/k))
+                let leverage: Array1<f64> = (0..n)
+                    .map(|i| {
+                        let x_i = x_to_use.row(i);
+                        x_i.dot(&xt_x_inv.dot(&x_i))
+                    })
+                    .collect();
+                let h_max = leverage.fold(0.0_f64, |a, &b| a.max(b));
+                let k_f = k_clean as f64;
+                let cap = 4.0_f64.max(0.7 * (n as f64) * h_max / k_f);
+
+                let mut x_weighted = x_to_use.clone();
+                for (i, mut row) in x_weighted.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    let h_i = leverage[i];
+                    let weight = if h_i >= 0.9999 {
+                        residuals[i].powi(2)
+                    } else {
+                        let delta_i = ((n as f64) * h_i / k_f).min(cap);
+                        residuals[i].powi(2) / (1.0 - h_i).powf(delta_i / 2.0)
+                    };
+                    row *= weight;
+                }
+
+                let meat = x_t.dot(&x_weighted);
+                xt_x_inv.dot(&meat).dot(&xt_x_inv)
+            }
             CovarianceType::NeweyWest(lags) => {
                 // HAC Estimator (Newey-West)
                 // Formula: (X'X)^-1 * [ Omega_0 + sum(w_l * (Omega_l + Omega_l')) ] * (X'X)^-1
@@ -1010,6 +1156,35 @@ impl OLS {
                 let correction = (n as f64) / (df_resid as f64);
                 sandwich * correction
             }
+            CovarianceType::Hac(_, _)
+            | CovarianceType::DriscollKraay(_, _)
+            | CovarianceType::Conley(_, _) => {
+                // Kernel HAC, Driscoll-Kraay and Conley share the score-based meat
+                // and the same n/(n-k) correction as Newey-West
+                let mut scores = x_to_use.clone();
+                for (i, mut row) in scores.axis_iter_mut(nd::Axis(0)).enumerate() {
+                    row *= residuals[i];
+                }
+                let meat = Sandwich::score_meat(&scores, &cov_type)?.ok_or_else(|| {
+                    GreenersError::InvalidOperation("Unsupported covariance type".into())
+                })?;
+                let sandwich = xt_x_inv.dot(&meat).dot(&xt_x_inv);
+                sandwich * ((n as f64) / (df_resid as f64))
+            }
+            CovarianceType::CR2(ref cluster_ids) | CovarianceType::CR3(ref cluster_ids) => {
+                // Bias-reduced cluster-robust SE (Bell-McCaffrey, 2002)
+                // No further small-sample factor; inference uses Satterthwaite df
+                let jackknife = matches!(cov_type, CovarianceType::CR3(_));
+                let (meat, df) = Sandwich::bias_reduced_cluster(
+                    x_to_use,
+                    &xt_x_inv,
+                    &residuals,
+                    cluster_ids,
+                    jackknife,
+                )?;
+                satterthwaite_df = Some(df);
+                xt_x_inv.dot(&meat).dot(&xt_x_inv)
+            }
             CovarianceType::Clustered(ref cluster_ids) => {
                 // Clustered Standard Errors
                 // Formula: V_cluster = (X'X)^-1 * [Σ_g (X_g' u_g u_g' X_g)] * (X'X)^-1
@@ -1168,13 +1343,16 @@ impl OLS {
 
         // Use default inference type (StudentT)
         let default_inference = InferenceType::default();
-        let (p_values, conf_lower, conf_upper) = OlsResult::compute_inference(
-            &t_values,
-            &std_errors,
-            &beta,
-            df_resid,
-            &default_inference,
-        )?;
+        let (p_values, conf_lower, conf_upper) = match &satterthwaite_df {
+            Some(df) => OlsResult::compute_inference_per_coef(&t_values, &std_errors, &beta, df)?,
+            None => OlsResult::compute_inference(
+                &t_values,
+                &std_errors,
+                &beta,
+                df_resid,
+                &default_inference,
+            )?,
+        };
 
         // 5. Statistics
         let sst = if has_intercept {
@@ -1246,9 +1424,76 @@ impl OLS {
             },
             omitted_vars: omitted_positioned,
             x_clean: x_clean_out,
+            design_info: None,
+            row_map: None,
+            cov_matrix: Some(cov_matrix),
+            satterthwaite_df,
         })
     }
 }
 
 // Helper alias for simpler axis usage inside the function
 use ndarray as nd;
+
+impl FittedModel for OlsResult {
+    fn model_name(&self) -> String {
+        "OLS".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &self.omitted_vars)?;
+        Ok(x.dot(&self.params))
+    }
+
+    fn vcov(&self) -> Option<Array2<f64>> {
+        self.cov_matrix.clone()
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+
+    fn aic(&self) -> Option<f64> {
+        Some(self.aic)
+    }
+
+    fn bic(&self) -> Option<f64> {
+        Some(self.bic)
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
+
+/// Fits with non-robust standard errors; use a closure such as
+/// `|y, x| OLS::fit(y, x, CovarianceType::HC1)` for other covariance types.
+impl Model for OLS {
+    type Fitted = OlsResult;
+
+    fn fit(&self, y: &Array1<f64>, x: &Array2<f64>) -> Result<OlsResult, GreenersError> {
+        OLS::fit(y, x, CovarianceType::NonRobust)
+    }
+}
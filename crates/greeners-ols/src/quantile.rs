@@ -1,11 +1,14 @@
 use crate::ols::OLS;
-use greeners_core::{CovarianceType, DataFrame, Formula, GreenersError};
+use greeners_core::model::align_design;
+use greeners_core::{
+    CovarianceType, DataFrame, FittedModel, Formula, GreenersError, GreenersRng, MissingPolicy,
+    RowMap,
+};
 use ndarray::{Array1, Array2, Axis};
-use rand::distributions::Distribution;
-use rand_distr::Uniform;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QuantileResult {
     pub tau: f64,
     pub params: Array1<f64>,
@@ -13,8 +16,10 @@ pub struct QuantileResult {
     pub t_values: Array1<f64>,
     pub p_values: Array1<f64>,
     pub r_squared: f64, // Pseudo-R2 (Koenker & Machado)
+    pub n_obs: usize,
     pub iterations: usize,
     pub variable_names: Option<Vec<String>>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for QuantileResult {
@@ -65,10 +70,26 @@ impl QuantileReg {
         data: &DataFrame,
         tau: f64,
         n_boot: usize,
+        seed: Option<u64>,
     ) -> Result<QuantileResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, tau, n_boot, Some(var_names))
+        Self::from_formula_with_missing(formula, data, tau, n_boot, seed, MissingPolicy::Drop)
+    }
+
+    /// Quantile regression from a formula with an explicit missing-value
+    /// policy (`from_formula` drops incomplete rows).
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        tau: f64,
+        n_boot: usize,
+        seed: Option<u64>,
+        missing: MissingPolicy,
+    ) -> Result<QuantileResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let var_names = dm.info.column_names();
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, tau, n_boot, Some(var_names), seed)?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Estimate Quantyl Regression via IRLS.
@@ -76,13 +97,15 @@ impl QuantileReg {
     /// # Arguments
     /// * `tau` - The desired quantil (e.g. 0.5 for median, 0.9 for upper decile).
     /// * `n_boot` - Número de repetições de Bootstrap para erro padrão (rec: 200+).
+    /// * `seed` - Random seed for the bootstrap (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
         tau: f64,
         n_boot: usize,
+        seed: Option<u64>,
     ) -> Result<QuantileResult, GreenersError> {
-        Self::fit_with_names(y, x, tau, n_boot, None)
+        Self::fit_with_names(y, x, tau, n_boot, None, seed)
     }
 
     pub fn fit_with_names(
@@ -91,6 +114,7 @@ impl QuantileReg {
         tau: f64,
         n_boot: usize,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<QuantileResult, GreenersError> {
         if tau <= 0.0 || tau >= 1.0 {
             return Err(GreenersError::OptimizationFailed); // "Tau must be in (0, 1)"
@@ -112,7 +136,7 @@ impl QuantileReg {
         let std_errors = if n_boot == 0 {
             Array1::from_elem(k, f64::NAN)
         } else {
-            Self::bootstrap_se(y, x, tau, n_boot, &params)?
+            Self::bootstrap_se(y, x, tau, n_boot, &params, seed)?
         };
 
         //3. Final Statistics
@@ -141,8 +165,10 @@ impl QuantileReg {
             t_values,
             p_values,
             r_squared: pseudo_r2,
+            n_obs: n,
             iterations: iter,
             variable_names,
+            row_map: None,
         })
     }
 
@@ -222,6 +248,7 @@ impl QuantileReg {
         tau: f64,
         n_boot: usize,
         point_estimate: &Array1<f64>,
+        seed: Option<u64>,
     ) -> Result<Array1<f64>, GreenersError> {
         let n = y.len();
         let k = x.ncols();
@@ -230,11 +257,11 @@ impl QuantileReg {
             row.assign(point_estimate);
         }
 
-        let mut rng = rand::thread_rng();
+        let mut rng = GreenersRng::new(seed);
 
         for b in 0..n_boot {
             //Resampling with replacement
-            let indices = Array1::from_iter((0..n).map(|_| Uniform::new(0, n).sample(&mut rng)));
+            let indices = rng.bootstrap_indices(n);
 
             let mut y_boot_vec = Vec::with_capacity(n);
             let mut x_boot_vec = Vec::with_capacity(n * k);
@@ -276,3 +303,41 @@ impl QuantileReg {
             .sum()
     }
 }
+
+impl FittedModel for QuantileResult {
+    fn model_name(&self) -> String {
+        format!("QuantReg({})", self.tau)
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    /// Predicted conditional quantile.
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &[])?;
+        Ok(x.dot(&self.params))
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
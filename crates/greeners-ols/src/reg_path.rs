@@ -1,24 +1,107 @@
 //! Regularization Path (Efron, Hastie, Johnstone & Tibshirani 2004;
-//! Zou & Hastie 2005).
+//! Zou & Hastie 2005; Friedman, Hastie & Tibshirani 2010).
 //!
 //! Computes the full path of Ridge, Lasso, and ElasticNet
 //! coefficients as the regularization parameter lambda varies.
 //!
 //! Ridge: beta(lambda) = (X'X + lambda*I)^{-1} X'y
-//! Lasso: coordinate descent (cyclic)
-//! ElasticNet: beta(lambda) = argmin ||y - X*beta||^2 +
+//! Lasso: coordinate descent (cyclic, warm-started along the path)
+//! ElasticNet: beta(lambda) = argmin ||y - X*beta||^2 / (2n) +
 //!   lambda * [alpha * ||beta||_1 + (1-alpha)/2 * ||beta||^2]
 //!
-//! Reports coefficient paths, optimal lambda (via CV or BIC),
-//! and selected variables.
+//! Lambda is chosen by BIC, K-fold cross-validation (minimum or one
+//! standard error rule) or the data-driven plug-in penalty of Belloni,
+//! Chen, Chernozhukov & Hansen (2012). The path also works when p > n.
 
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::GreenersError;
-use ndarray::{Array1, Array2};
+use greeners_core::{GreenersError, GreenersRng};
+use ndarray::{Array1, Array2, Axis, ShapeBuilder};
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
+/// Penalty of the regularization path.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Penalty {
+    /// L2 penalty, closed form
+    Ridge,
+    /// L1 penalty
+    Lasso,
+    /// Mix of L1 and L2 with the given alpha (1.0 = lasso, 0.0 = ridge)
+    ElasticNet(f64),
+}
+
+impl Penalty {
+    /// Weight on the L1 part of the penalty.
+    pub fn alpha(&self) -> f64 {
+        match self {
+            Penalty::Ridge => 0.0,
+            Penalty::Lasso => 1.0,
+            Penalty::ElasticNet(a) => a.clamp(0.0, 1.0),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Penalty::Ridge => "ridge",
+            Penalty::Lasso => "lasso",
+            Penalty::ElasticNet(_) => "elasticnet",
+        }
+    }
+}
+
+/// Rule used to pick the reported lambda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LambdaRule {
+    /// Minimum BIC along the path
+    #[default]
+    Bic,
+    /// Minimum K-fold cross-validated MSE
+    CvMin,
+    /// Largest lambda within one standard error of the CV minimum
+    Cv1se,
+    /// Belloni-Chen-Chernozhukov-Hansen plug-in penalty with
+    /// heteroskedasticity-robust penalty loadings (lasso/elastic net only)
+    Plugin,
+}
+
+/// Settings for [`RegPath::fit_with_config`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegPathConfig {
+    pub penalty: Penalty,
+    pub rule: LambdaRule,
+    /// Number of lambda values on the path
+    pub n_lambdas: usize,
+    /// Smallest lambda as a fraction of the largest (default 1e-4 when
+    /// n > p and 1e-2 otherwise)
+    pub lambda_min_ratio: Option<f64>,
+    /// Number of cross-validation folds
+    pub n_folds: usize,
+    /// Seed for the fold assignment
+    pub seed: Option<u64>,
+    /// Coordinate descent tolerance on the largest coefficient change
+    pub tol: f64,
+    /// Maximum number of coordinate descent sweeps per lambda
+    pub max_iter: usize,
+}
+
+impl Default for RegPathConfig {
+    fn default() -> Self {
+        RegPathConfig {
+            penalty: Penalty::Lasso,
+            rule: LambdaRule::Bic,
+            n_lambdas: 100,
+            lambda_min_ratio: None,
+            n_folds: 10,
+            seed: None,
+            tol: 1e-7,
+            max_iter: 10_000,
+        }
+    }
+}
+
 /// Result of regularization path.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RegPathResult {
     /// Lambda values (n_lambdas)
     pub lambdas: Array1<f64>,
@@ -26,13 +109,13 @@ pub struct RegPathResult {
     pub coef_path: Array2<f64>,
     /// Intercept path (n_lambdas)
     pub intercept_path: Array1<f64>,
-    /// Optimal lambda (via BIC)
+    /// Optimal lambda (under `rule`)
     pub optimal_lambda: f64,
     /// Coefficients at optimal lambda
     pub optimal_coefs: Array1<f64>,
     /// Intercept at optimal lambda
     pub optimal_intercept: f64,
-    /// BIC path (n_lambdas)
+    /// BIC path (n_lambdas); infinite where the model is saturated
     pub bic_path: Array1<f64>,
     /// Type: "ridge", "lasso", or "elasticnet"
     pub reg_type: String,
@@ -48,6 +131,38 @@ pub struct RegPathResult {
     pub variable_names: Vec<String>,
     /// Variables selected at optimal lambda (non-zero)
     pub selected_vars: Vec<String>,
+    /// Rule that chose `optimal_lambda`
+    #[serde(default)]
+    pub rule: LambdaRule,
+    /// Mean cross-validated MSE per lambda (CV rules only)
+    #[serde(default)]
+    pub cv_error: Option<Array1<f64>>,
+    /// Standard error of the cross-validated MSE per lambda
+    #[serde(default)]
+    pub cv_se: Option<Array1<f64>>,
+    /// Lambda minimising the CV error
+    #[serde(default)]
+    pub lambda_cv_min: Option<f64>,
+    /// Largest lambda within one standard error of the minimum
+    #[serde(default)]
+    pub lambda_cv_1se: Option<f64>,
+    /// Plug-in penalty loadings on the standardized scale (Plugin only)
+    #[serde(default)]
+    pub penalty_loadings: Option<Array1<f64>>,
+}
+
+impl RegPathResult {
+    /// Positions of the predictors selected at the optimal lambda.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        (0..self.n_pred)
+            .filter(|&j| self.optimal_coefs[j].abs() > 1e-10)
+            .collect()
+    }
+
+    /// Predictions at the optimal lambda.
+    pub fn predict(&self, x_new: &Array2<f64>) -> Array1<f64> {
+        x_new.dot(&self.optimal_coefs) + self.optimal_intercept
+    }
 }
 
 impl fmt::Display for RegPathResult {
@@ -57,7 +172,12 @@ impl fmt::Display for RegPathResult {
         writeln!(f, "{:<20} {:>12}", "Observations:", self.n_obs)?;
         writeln!(f, "{:<20} {:>12}", "Predictors:", self.n_pred)?;
         writeln!(f, "{:<20} {:>12}", "Lambda values:", self.n_lambdas)?;
+        writeln!(f, "{:<20} {:>12?}", "Lambda rule:", self.rule)?;
         writeln!(f, "{:<20} {:>12.6}", "Optimal lambda:", self.optimal_lambda)?;
+        if let (Some(min), Some(se1)) = (self.lambda_cv_min, self.lambda_cv_1se) {
+            writeln!(f, "{:<20} {:>12.6}", "CV lambda (min):", min)?;
+            writeln!(f, "{:<20} {:>12.6}", "CV lambda (1se):", se1)?;
+        }
         writeln!(
             f,
             "{:<20} {:>12}",
@@ -98,6 +218,56 @@ impl fmt::Display for RegPathResult {
     }
 }
 
+/// Centered and scaled copy of the data.
+struct Standardized {
+    x: Array2<f64>,
+    y: Array1<f64>,
+    x_mean: Array1<f64>,
+    x_std: Array1<f64>,
+    y_mean: f64,
+    y_std: f64,
+    col_sq: Vec<f64>,
+}
+
+impl Standardized {
+    fn new(y: &Array1<f64>, x: &Array2<f64>) -> Self {
+        let n = y.len() as f64;
+        let x_mean = x
+            .mean_axis(Axis(0))
+            .unwrap_or_else(|| Array1::zeros(x.ncols()));
+        let x_std: Array1<f64> = x
+            .axis_iter(Axis(1))
+            .zip(x_mean.iter())
+            .map(|(col, &m)| {
+                let var = col.iter().map(|v| (v - m).powi(2)).sum::<f64>() / n;
+                var.sqrt().max(1e-10)
+            })
+            .collect();
+        let y_mean = y.mean().unwrap_or(0.0);
+        let y_std = y.std(0.0).max(1e-10);
+        // Column-major copy: coordinate descent walks the columns
+        let mut x_norm = Array2::zeros(x.raw_dim().f());
+        x_norm.assign(&((x - &x_mean) / &x_std));
+        let col_sq = x_norm.axis_iter(Axis(1)).map(|c| c.dot(&c)).collect();
+        Standardized {
+            x: x_norm,
+            y: y.mapv(|v| (v - y_mean) / y_std),
+            x_mean,
+            x_std,
+            y_mean,
+            y_std,
+            col_sq,
+        }
+    }
+
+    /// Coefficients and intercept on the original scale.
+    fn original(&self, beta: &Array1<f64>) -> (Array1<f64>, f64) {
+        let coefs = beta * self.y_std / &self.x_std;
+        let intercept = self.y_mean - coefs.dot(&self.x_mean);
+        (coefs, intercept)
+    }
+}
+
 pub struct RegPath;
 
 impl RegPath {
@@ -110,6 +280,9 @@ impl RegPath {
     /// * `alpha` - ElasticNet mixing (1.0=lasso, 0.0=ridge), default 1.0
     /// * `n_lambdas` - Number of lambda values (default 50)
     /// * `variable_names` - Optional variable names
+    ///
+    /// Lambda is chosen by BIC; see [`RegPath::fit_with_config`] for
+    /// cross-validation and the plug-in penalty.
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
@@ -117,6 +290,45 @@ impl RegPath {
         alpha: Option<f64>,
         n_lambdas: Option<usize>,
         variable_names: Option<Vec<String>>,
+    ) -> Result<RegPathResult, GreenersError> {
+        let penalty = match reg_type {
+            "ridge" => Penalty::Ridge,
+            "lasso" => Penalty::Lasso,
+            _ => Penalty::ElasticNet(alpha.unwrap_or(1.0)),
+        };
+        let config = RegPathConfig {
+            penalty,
+            n_lambdas: n_lambdas.unwrap_or(50),
+            ..RegPathConfig::default()
+        };
+        Self::fit_with_config(y, x, &config, variable_names)
+    }
+
+    /// Compute the path with an explicit penalty and lambda rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_ols::reg_path::{LambdaRule, Penalty, RegPath, RegPathConfig};
+    /// use ndarray::{Array1, Array2};
+    ///
+    /// // 20 observations, 40 candidate predictors, only the first matters
+    /// let x = Array2::from_shape_fn((20, 40), |(i, j)| ((i * 7 + j * 13) % 17) as f64 - 8.0);
+    /// let y: Array1<f64> = x.column(0).mapv(|v| 2.0 * v) + 0.1;
+    /// let config = RegPathConfig {
+    ///     penalty: Penalty::Lasso,
+    ///     rule: LambdaRule::CvMin,
+    ///     n_folds: 5,
+    ///     seed: Some(1),
+    ///     ..RegPathConfig::default()
+    /// };
+    /// let path = RegPath::fit_with_config(&y, &x, &config, None).unwrap();
+    /// assert_eq!(path.selected_vars[0], "x0");
+    /// ```
+    pub fn fit_with_config(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        config: &RegPathConfig,
+        variable_names: Option<Vec<String>>,
     ) -> Result<RegPathResult, GreenersError> {
         let n = y.len();
         let p = x.ncols();
@@ -125,46 +337,46 @@ impl RegPath {
                 "RegPath: y and x must have same n".into(),
             ));
         }
-        if n < p + 2 {
+        if n < 3 || p == 0 {
+            return Err(GreenersError::InvalidOperation(
+                "RegPath: need at least 3 observations and one predictor".into(),
+            ));
+        }
+        if config.n_lambdas < 2 {
+            return Err(GreenersError::InvalidOperation(
+                "RegPath: need at least two lambda values".into(),
+            ));
+        }
+        if config.rule == LambdaRule::Plugin && config.penalty == Penalty::Ridge {
             return Err(GreenersError::InvalidOperation(
-                "RegPath: need more observations than predictors".into(),
+                "RegPath: the plug-in penalty needs an L1 component".into(),
             ));
         }
+        let is_cv = matches!(config.rule, LambdaRule::CvMin | LambdaRule::Cv1se);
+        if is_cv && (config.n_folds < 2 || config.n_folds > n) {
+            return Err(GreenersError::InvalidOperation(format!(
+                "RegPath: n_folds must be between 2 and n ({}), got {}",
+                n, config.n_folds
+            )));
+        }
 
         let names = variable_names.unwrap_or_else(|| (0..p).map(|i| format!("x{}", i)).collect());
-        let a = alpha.unwrap_or(1.0).clamp(0.0, 1.0);
-        let n_lam = n_lambdas.unwrap_or(50);
-
-        // Standardize x and y
-        let x_mean: Array1<f64> = (0..p)
-            .map(|j| (0..n).map(|i| x[(i, j)]).sum::<f64>() / n as f64)
-            .collect();
-        let x_std: Array1<f64> = (0..p)
-            .map(|j| {
-                let var = (0..n).map(|i| (x[(i, j)] - x_mean[j]).powi(2)).sum::<f64>() / n as f64;
-                var.sqrt().max(1e-10)
-            })
-            .collect();
-        let y_mean = y.mean().unwrap_or(0.0);
-        let y_std = y.std(0.0).max(1e-10);
-
-        let x_norm: Array2<f64> = {
-            let mut data: Vec<f64> = Vec::with_capacity(n * p);
-            for i in 0..n {
-                for j in 0..p {
-                    data.push((x[(i, j)] - x_mean[j]) / x_std[j]);
-                }
-            }
-            Array2::from_shape_vec((n, p), data)
-                .map_err(|e| GreenersError::ShapeMismatch(format!("{}", e)))?
-        };
-        let y_norm: Array1<f64> = y.mapv(|v| (v - y_mean) / y_std);
+        let a = config.penalty.alpha();
+        let n_lam = config.n_lambdas;
+        let data = Standardized::new(y, x);
 
         // Compute lambda_max = max |x_j' y| / (n * alpha)
-        let xty: Array1<f64> = x_norm.t().dot(&y_norm);
+        let xty: Array1<f64> = data.x.t().dot(&data.y);
+        let path_alpha = match config.penalty {
+            Penalty::Ridge => 1.0,
+            _ => a.max(0.01),
+        };
         let lambda_max =
-            xty.iter().map(|&v| v.abs()).fold(0.0_f64, f64::max) / (n as f64 * a.max(0.01));
-        let lambda_min = lambda_max * 1e-4;
+            xty.iter().map(|&v| v.abs()).fold(0.0_f64, f64::max) / (n as f64 * path_alpha);
+        let ratio = config
+            .lambda_min_ratio
+            .unwrap_or(if n > p { 1e-4 } else { 1e-2 });
+        let lambda_min = lambda_max * ratio;
 
         // Generate lambda path (log-spaced)
         let lambdas: Array1<f64> = (0..n_lam)
@@ -174,52 +386,67 @@ impl RegPath {
             })
             .collect();
 
+        let betas = Self::solve_path(&data, &lambdas, config)?;
         let mut coef_path = Array2::zeros((n_lam, p));
         let mut intercept_path = Array1::zeros(n_lam);
         let mut bic_path = Array1::zeros(n_lam);
-
-        for (idx, &lam) in lambdas.iter().enumerate() {
-            let coefs = if reg_type == "ridge" {
-                Self::ridge_fit(&x_norm, &y_norm, lam, n, p)?
-            } else if reg_type == "lasso" {
-                Self::lasso_fit(&x_norm, &y_norm, lam, 1.0, n, p)?
+        for (idx, beta) in betas.outer_iter().enumerate() {
+            let (coefs, intercept) = data.original(&beta.to_owned());
+            let resid = y - &x.dot(&coefs) - intercept;
+            let sse = resid.dot(&resid);
+            let n_nonzero = coefs.iter().filter(|c| c.abs() > 1e-10).count();
+            // Saturated models fit perfectly and have no usable BIC
+            bic_path[idx] = if n_nonzero + 1 >= n {
+                f64::INFINITY
             } else {
-                Self::lasso_fit(&x_norm, &y_norm, lam, a, n, p)?
+                let sigma2 = (sse / n as f64).max(1e-300);
+                n as f64 * sigma2.ln() + n_nonzero as f64 * (n as f64).ln()
             };
+            coef_path.row_mut(idx).assign(&coefs);
+            intercept_path[idx] = intercept;
+        }
 
-            // Un-standardize coefficients
-            for j in 0..p {
-                coef_path[(idx, j)] = coefs[j] * y_std / x_std[j];
+        let (mut cv_error, mut cv_se, mut lambda_cv_min, mut lambda_cv_1se) =
+            (None, None, None, None);
+        let mut penalty_loadings = None;
+        let (optimal_lambda, optimal_coefs, optimal_intercept) = match config.rule {
+            LambdaRule::Bic => {
+                let best = argmin(&bic_path);
+                (
+                    lambdas[best],
+                    coef_path.row(best).to_owned(),
+                    intercept_path[best],
+                )
             }
-            intercept_path[idx] =
-                y_mean - (0..p).map(|j| coef_path[(idx, j)] * x_mean[j]).sum::<f64>();
-
-            // BIC: compute residual SS
-            let mut sse = 0.0;
-            for i in 0..n {
-                let pred = intercept_path[idx]
-                    + (0..p).map(|j| coef_path[(idx, j)] * x[(i, j)]).sum::<f64>();
-                sse += (y[i] - pred).powi(2);
+            LambdaRule::CvMin | LambdaRule::Cv1se => {
+                let (mean, se) = Self::cross_validate(y, x, &lambdas, config)?;
+                let best = argmin(&mean);
+                let threshold = mean[best] + se[best];
+                // Lambdas decrease along the path: the first one under the
+                // threshold is the largest
+                let one_se = (0..n_lam).find(|&i| mean[i] <= threshold).unwrap_or(best);
+                let chosen = if config.rule == LambdaRule::CvMin {
+                    best
+                } else {
+                    one_se
+                };
+                lambda_cv_min = Some(lambdas[best]);
+                lambda_cv_1se = Some(lambdas[one_se]);
+                cv_error = Some(mean);
+                cv_se = Some(se);
+                (
+                    lambdas[chosen],
+                    coef_path.row(chosen).to_owned(),
+                    intercept_path[chosen],
+                )
             }
-            let n_nonzero = (0..p)
-                .filter(|&j| coef_path[(idx, j)].abs() > 1e-10)
-                .count();
-            let sigma2 = sse / n as f64;
-            bic_path[idx] = n as f64 * sigma2.ln() + n_nonzero as f64 * (n as f64).ln();
-        }
-
-        // Find optimal lambda (min BIC)
-        let mut best_idx = 0;
-        let mut best_bic = bic_path[0];
-        for i in 1..n_lam {
-            if bic_path[i] < best_bic {
-                best_bic = bic_path[i];
-                best_idx = i;
+            LambdaRule::Plugin => {
+                let (lambda, beta, loadings) = Self::plugin(&data, config)?;
+                penalty_loadings = Some(loadings);
+                let (coefs, intercept) = data.original(&beta);
+                (lambda, coefs, intercept)
             }
-        }
-        let optimal_lambda = lambdas[best_idx];
-        let optimal_coefs = coef_path.row(best_idx).to_owned();
-        let optimal_intercept = intercept_path[best_idx];
+        };
 
         let selected_vars: Vec<String> = (0..p)
             .filter(|&j| optimal_coefs[j].abs() > 1e-10)
@@ -234,21 +461,151 @@ impl RegPath {
             optimal_coefs,
             optimal_intercept,
             bic_path,
-            reg_type: reg_type.to_string(),
+            reg_type: config.penalty.name().to_string(),
             alpha: a,
             n_lambdas: n_lam,
             n_obs: n,
             n_pred: p,
             variable_names: names,
             selected_vars,
+            rule: config.rule,
+            cv_error,
+            cv_se,
+            lambda_cv_min,
+            lambda_cv_1se,
+            penalty_loadings,
         })
     }
 
+    /// Standardized coefficients for every lambda (n_lambdas x p).
+    fn solve_path(
+        data: &Standardized,
+        lambdas: &Array1<f64>,
+        config: &RegPathConfig,
+    ) -> Result<Array2<f64>, GreenersError> {
+        let p = data.x.ncols();
+        let mut betas = Array2::zeros((lambdas.len(), p));
+        let mut beta = Array1::zeros(p);
+        for (idx, &lam) in lambdas.iter().enumerate() {
+            if config.penalty == Penalty::Ridge {
+                beta = Self::ridge_fit(&data.x, &data.y, lam, p)?;
+            } else {
+                // Warm start from the previous lambda
+                Self::coordinate_descent(
+                    data,
+                    lam,
+                    config.penalty.alpha(),
+                    None,
+                    &mut beta,
+                    config,
+                );
+            }
+            betas.row_mut(idx).assign(&beta);
+        }
+        Ok(betas)
+    }
+
+    /// Mean and standard error of the held-out MSE for every lambda.
+    fn cross_validate(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        lambdas: &Array1<f64>,
+        config: &RegPathConfig,
+    ) -> Result<(Array1<f64>, Array1<f64>), GreenersError> {
+        let n = y.len();
+        let k = config.n_folds;
+        let mut order: Vec<usize> = (0..n).collect();
+        GreenersRng::new(config.seed).shuffle(&mut order);
+        let mut fold = vec![0; n];
+        for (pos, &i) in order.iter().enumerate() {
+            fold[i] = pos % k;
+        }
+
+        let mut errors = Array2::<f64>::zeros((k, lambdas.len()));
+        for f in 0..k {
+            let train: Vec<usize> = (0..n).filter(|&i| fold[i] != f).collect();
+            let test: Vec<usize> = (0..n).filter(|&i| fold[i] == f).collect();
+            let data = Standardized::new(&y.select(Axis(0), &train), &x.select(Axis(0), &train));
+            let betas = Self::solve_path(&data, lambdas, config)?;
+            let x_test = x.select(Axis(0), &test);
+            let y_test = y.select(Axis(0), &test);
+            for (idx, beta) in betas.outer_iter().enumerate() {
+                let (coefs, intercept) = data.original(&beta.to_owned());
+                let resid = &y_test - &x_test.dot(&coefs) - intercept;
+                errors[[f, idx]] = resid.dot(&resid) / test.len() as f64;
+            }
+        }
+        let mean = errors.mean_axis(Axis(0)).unwrap_or_default();
+        let se = errors.std_axis(Axis(0), 1.0) / (k as f64).sqrt();
+        Ok((mean, se))
+    }
+
+    /// Plug-in lambda of Belloni et al. (2012) with iterated
+    /// heteroskedasticity-robust loadings ψ_j = sqrt(mean(x_ij² e_i²)),
+    /// where e are post-lasso residuals. Returns the lambda on the scale of
+    /// the path, the standardized coefficients and the loadings.
+    fn plugin(
+        data: &Standardized,
+        config: &RegPathConfig,
+    ) -> Result<(f64, Array1<f64>, Array1<f64>), GreenersError> {
+        let (n, p) = data.x.dim();
+        let nf = n as f64;
+        let gamma = 0.1 / nf.ln();
+        let normal = Normal::new(0.0, 1.0).map_err(|_| GreenersError::OptimizationFailed)?;
+        let lambda_bch = 2.0 * 1.1 * nf.sqrt() * normal.inverse_cdf(1.0 - gamma / (2.0 * p as f64));
+        // (1/n)||e||² + (λ/n)Σψ|b|  ==  2 × [(1/2n)||e||² + λ/(2n) Σψ|b|]
+        let lambda = lambda_bch / (2.0 * nf);
+
+        let loadings = |resid: &Array1<f64>| -> Array1<f64> {
+            let e2 = resid.mapv(|e| e * e);
+            data.x
+                .axis_iter(Axis(1))
+                .map(|col| (col.mapv(|v| v * v).dot(&e2) / nf).sqrt().max(1e-10))
+                .collect()
+        };
+        let mut psi = loadings(&data.y);
+        let mut beta = Array1::zeros(p);
+        for _ in 0..15 {
+            Self::coordinate_descent(
+                data,
+                lambda,
+                config.penalty.alpha(),
+                Some(&psi),
+                &mut beta,
+                config,
+            );
+            let resid = Self::post_lasso_residuals(data, &beta);
+            let next = loadings(&resid);
+            let change = (&next - &psi).iter().fold(0.0_f64, |m, d| m.max(d.abs()));
+            psi = next;
+            if change < 1e-6 {
+                break;
+            }
+        }
+        Ok((lambda, beta, psi))
+    }
+
+    /// Residuals of OLS on the selected standardized columns (falling back
+    /// to the penalized fit when the selected design is not invertible).
+    fn post_lasso_residuals(data: &Standardized, beta: &Array1<f64>) -> Array1<f64> {
+        let support: Vec<usize> = (0..beta.len()).filter(|&j| beta[j] != 0.0).collect();
+        if support.is_empty() {
+            return data.y.clone();
+        }
+        let xs = data.x.select(Axis(1), &support);
+        if support.len() < data.y.len() {
+            if let Ok(inv) = xs.t().dot(&xs).inv() {
+                let b = inv.dot(&xs.t().dot(&data.y));
+                return &data.y - &xs.dot(&b);
+            }
+        }
+        &data.y - &data.x.dot(beta)
+    }
+
     fn ridge_fit(
         x: &Array2<f64>,
         y: &Array1<f64>,
         lambda: f64,
-        _n: usize,
         p: usize,
     ) -> Result<Array1<f64>, GreenersError> {
         let xt = x.t();
@@ -262,68 +619,85 @@ impl RegPath {
         Ok(xtx_inv.dot(&xty))
     }
 
-    fn lasso_fit(
-        x: &Array2<f64>,
-        y: &Array1<f64>,
+    /// Cyclic coordinate descent on (1/2n)||y - Xb||² + lambda *
+    /// Σ w_j [alpha |b_j| + (1-alpha)/2 b_j²], updating `beta` in place.
+    fn coordinate_descent(
+        data: &Standardized,
         lambda: f64,
         alpha: f64,
-        n: usize,
-        p: usize,
-    ) -> Result<Array1<f64>, GreenersError> {
-        // Coordinate descent
-        let mut beta: Array1<f64> = Array1::zeros(p);
-        let lam = lambda * n as f64; // scale
+        weights: Option<&Array1<f64>>,
+        beta: &mut Array1<f64>,
+        config: &RegPathConfig,
+    ) {
+        let x = &data.x;
+        let n = x.nrows() as f64;
+        let lam = lambda * n; // scale
+        let mut resid = &data.y - &x.dot(beta);
 
-        // Precompute column-wise products
-        let mut col_sq = vec![0.0_f64; p];
-        for j in 0..p {
-            for i in 0..n {
-                col_sq[j] += x[(i, j)] * x[(i, j)];
+        // One coordinate update; returns the scaled size of the change
+        let update = |j: usize, beta: &mut Array1<f64>, resid: &mut Array1<f64>| -> f64 {
+            let col_sq = data.col_sq[j];
+            if col_sq < 1e-15 {
+                return 0.0;
             }
-        }
-
-        let max_iter = 100;
-        for _ in 0..max_iter {
-            let mut max_change = 0.0;
-            for j in 0..p {
-                // Compute partial residual: r = y - X * beta + X_j * beta_j
-                let mut rho = 0.0;
-                for i in 0..n {
-                    let mut pred = 0.0;
-                    for jj in 0..p {
-                        if jj != j {
-                            pred += x[(i, jj)] * beta[jj];
-                        }
-                    }
-                    rho += x[(i, j)] * (y[i] - pred);
-                }
+            let xj = x.column(j);
+            let old = beta[j];
+            let rho = xj.dot(resid) + col_sq * old;
 
-                // Soft thresholding
-                let l1 = lam * alpha;
-                let l2 = lam * (1.0 - alpha);
-                let denom = col_sq[j] + l2;
-                if denom < 1e-15 {
-                    continue;
-                }
-                let new_beta = if rho > l1 {
-                    (rho - l1) / denom
-                } else if rho < -l1 {
-                    (rho + l1) / denom
-                } else {
-                    0.0
-                };
+            // Soft thresholding
+            let w = weights.map_or(1.0, |w| w[j]);
+            let l1 = lam * alpha * w;
+            let l2 = lam * (1.0 - alpha) * w;
+            let denom = col_sq + l2;
+            let new_beta = if rho > l1 {
+                (rho - l1) / denom
+            } else if rho < -l1 {
+                (rho + l1) / denom
+            } else {
+                0.0
+            };
+            beta[j] = new_beta;
+            if new_beta == old {
+                return 0.0;
+            }
+            resid.scaled_add(old - new_beta, &xj);
+            (new_beta - old).abs() * (col_sq / n).sqrt()
+        };
 
-                let change = (new_beta - beta[j]).abs();
-                if change > max_change {
-                    max_change = change;
-                }
-                beta[j] = new_beta;
+        // Full sweeps alternate with sweeps over the active set until a
+        // full sweep changes nothing
+        let p = beta.len();
+        let mut sweeps = 0;
+        while sweeps < config.max_iter {
+            let mut max_change = 0.0_f64;
+            for j in 0..p {
+                max_change = max_change.max(update(j, beta, &mut resid));
             }
-            if max_change < 1e-8 {
+            sweeps += 1;
+            if max_change < config.tol {
                 break;
             }
+            let active: Vec<usize> = (0..p).filter(|&j| beta[j] != 0.0).collect();
+            while sweeps < config.max_iter {
+                let mut change = 0.0_f64;
+                for &j in &active {
+                    change = change.max(update(j, beta, &mut resid));
+                }
+                sweeps += 1;
+                if change < config.tol {
+                    break;
+                }
+            }
         }
+    }
+}
 
-        Ok(beta)
+fn argmin(values: &Array1<f64>) -> usize {
+    let mut best = 0;
+    for (i, &v) in values.iter().enumerate() {
+        if v < values[best] {
+            best = i;
+        }
     }
+    best
 }
@@ -3,6 +3,7 @@ use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::CovarianceType;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
@@ -107,7 +108,7 @@ impl Default for RobustNorm {
 }
 
 /// Result of Robust Linear Model estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RlmResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
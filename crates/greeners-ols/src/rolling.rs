@@ -3,12 +3,13 @@ use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::CovarianceType;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 // ─── RecursiveLS ───────────────────────────────────────────────────────────────
 
 /// Result of Recursive Least Squares estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RecursiveLSResult {
     /// Parameter estimates at each time step (T x k)
     pub params_history: Array2<f64>,
@@ -146,7 +147,7 @@ impl RecursiveLS {
 // ─── RollingOLS ────────────────────────────────────────────────────────────────
 
 /// Result of Rolling OLS/WLS estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RollingResult {
     /// Parameter estimates at each time step (T x k), NaN before window fills
     pub params_history: Array2<f64>,
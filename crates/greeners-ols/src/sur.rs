@@ -2,6 +2,7 @@ use crate::ols::OLS;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::{CovarianceType, GreenersError};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Entry structure for the SUR
@@ -12,14 +13,14 @@ pub struct SurEquation {
     pub name: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SurResult {
     pub equations: Vec<SurEquationResult>,
     pub sigma_cross: Array2<f64>,
     pub system_r2: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SurEquationResult {
     pub name: String,
     pub params: Array1<f64>,
@@ -1,6 +1,7 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::ContinuousCDF;
 use std::fmt;
 
@@ -14,14 +15,14 @@ pub struct Equation {
 }
 
 /// 3SLS System Result
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ThreeSLSResult {
     pub equations: Vec<EquationResult>,
     pub sigma_cross: Array2<f64>, //Covariance matrix of errors between equations
     pub system_r2: f64,           //McElroy's R2 (Optional but chic)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EquationResult {
     pub name: String,
     pub params: Array1<f64>,
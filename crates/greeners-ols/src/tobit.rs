@@ -2,6 +2,7 @@ use crate::ols::OLS;
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
@@ -20,7 +21,7 @@ fn norm_cdf(x: f64) -> f64 {
 // TobitResult
 // ===========================================================================
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TobitResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -1,7 +1,7 @@
 use crate::ols::OlsResult;
 use crate::ols::OLS;
 use greeners_core::error::GreenersError;
-use greeners_core::{CovarianceType, DataFrame, Formula};
+use greeners_core::{CovarianceType, DataFrame, Formula, MissingPolicy};
 use ndarray::{Array1, Array2};
 
 /// Weighted Least Squares estimator.
@@ -12,15 +12,45 @@ pub struct WLS;
 
 impl WLS {
     /// Fit WLS from a formula, DataFrame, and weight column name.
+    ///
+    /// Rows with a missing value in the formula variables or in `weights`
+    /// are dropped. `weights` must be aligned with the rows of `data`.
     pub fn from_formula(
         formula: &Formula,
         data: &DataFrame,
         weights: &Array1<f64>,
         cov_type: CovarianceType,
     ) -> Result<OlsResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let var_names = data.formula_var_names(formula)?;
-        Self::fit_with_names(&y, &x, weights, cov_type, Some(var_names))
+        Self::from_formula_with_missing(formula, data, weights, cov_type, MissingPolicy::Drop)
+    }
+
+    /// Fit WLS from a formula with an explicit missing-value policy; a NaN
+    /// weight counts as a missing value of its row.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        weights: &Array1<f64>,
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<OlsResult, GreenersError> {
+        if weights.len() != data.n_rows() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "weights length ({}) must match DataFrame rows ({})",
+                weights.len(),
+                data.n_rows()
+            )));
+        }
+        let nan_weights: Vec<usize> = (0..weights.len())
+            .filter(|&i| weights[i].is_nan())
+            .collect();
+        let dm = data.design_matrices_with_mask(formula, missing, &nan_weights)?;
+        let var_names = dm.info.column_names();
+        let weights = dm.rows.select(weights);
+        let cov_type = dm.rows.select_cov_type(cov_type);
+        let mut result = Self::fit_with_names(&dm.y, &dm.x, &weights, cov_type, Some(var_names))?;
+        result.design_info = Some(dm.info);
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Fit WLS from arrays.
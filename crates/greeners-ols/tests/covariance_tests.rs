@@ -0,0 +1,286 @@
+use greeners_core::types::{CovarianceType, HacBandwidth, HacKernel};
+use greeners_core::{DataFrame, Formula, Sandwich};
+use greeners_ols::iv::IV;
+use greeners_ols::ols::OLS;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+/// y = 1 + 0.5·x1 - 0.3·x2 + u with AR(1) regressors and heteroscedastic
+/// AR(1) errors. Columns: X = [1, x1, x2].
+fn series(n: usize, seed: u64) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut x = Array2::<f64>::ones((n, 3));
+    let mut y = Array1::<f64>::zeros(n);
+    let (mut x1, mut u) = (0.0_f64, 0.0);
+    for i in 0..n {
+        x1 = 0.6 * x1 + normal.sample(&mut rng);
+        u = 0.5 * u + (1.0 + 0.5 * x1.abs()) * normal.sample(&mut rng);
+        let x2: f64 = normal.sample(&mut rng);
+        x[[i, 1]] = x1;
+        x[[i, 2]] = x2;
+        y[i] = 1.0 + 0.5 * x1 - 0.3 * x2 + u;
+    }
+    (y, x)
+}
+
+fn assert_close(a: &Array1<f64>, b: &Array1<f64>, tol: f64) {
+    for (p, q) in a.iter().zip(b.iter()) {
+        assert!((p - q).abs() < tol * q.abs().max(1.0), "{} vs {}", p, q);
+    }
+}
+
+#[test]
+fn test_hc0_and_hc5() {
+    let (y, x) = series(80, 1);
+    let (n, k) = (80.0_f64, 3.0);
+    let hc0 = OLS::fit(&y, &x, CovarianceType::HC0).unwrap();
+    let hc1 = OLS::fit(&y, &x, CovarianceType::HC1).unwrap();
+    assert_close(
+        &hc0.std_errors.mapv(|s| s * (n / (n - k)).sqrt()),
+        &hc1.std_errors,
+        1e-10,
+    );
+
+    // HC5 by hand
+    let xtx_inv = {
+        use greeners_core::linalg::LinalgInverse as _;
+        x.t().dot(&x).inv().unwrap()
+    };
+    let u = &y - &x.dot(&hc0.params);
+    let h: Vec<f64> = x
+        .rows()
+        .into_iter()
+        .map(|r| r.dot(&xtx_inv.dot(&r)))
+        .collect();
+    let h_max = h.iter().cloned().fold(0.0, f64::max);
+    let cap = 4.0_f64.max(0.7 * n * h_max / k);
+    let mut meat = Array2::<f64>::zeros((3, 3));
+    for i in 0..80 {
+        let delta = (n * h[i] / k).min(cap);
+        let w = u[i] * u[i] / (1.0 - h[i]).powf(delta / 2.0);
+        let r = x.row(i);
+        for p in 0..3 {
+            for q in 0..3 {
+                meat[[p, q]] += w * r[p] * r[q];
+            }
+        }
+    }
+    let expected = xtx_inv.dot(&meat).dot(&xtx_inv).diag().mapv(f64::sqrt);
+    let hc5 = OLS::fit(&y, &x, CovarianceType::HC5).unwrap();
+    assert_close(&hc5.std_errors, &expected, 1e-10);
+    assert!(format!("{}", hc5).contains("HC5"));
+}
+
+#[test]
+fn test_kernel_hac_and_bandwidth_selection() {
+    let (y, x) = series(300, 2);
+    // Bartlett with S = L + 1 is Newey-West with L lags
+    let nw = OLS::fit(&y, &x, CovarianceType::NeweyWest(4)).unwrap();
+    let bartlett = OLS::fit(
+        &y,
+        &x,
+        CovarianceType::Hac(HacKernel::Bartlett, HacBandwidth::Fixed(5.0)),
+    )
+    .unwrap();
+    assert_close(&bartlett.std_errors, &nw.std_errors, 1e-10);
+
+    assert_eq!(Sandwich::kernel_weight(HacKernel::Parzen, 0.5), 0.25);
+    assert_eq!(Sandwich::kernel_weight(HacKernel::Parzen, 1.2), 0.0);
+    assert!((Sandwich::kernel_weight(HacKernel::QuadraticSpectral, 1e-6) - 1.0).abs() < 1e-6);
+
+    // Data-driven bandwidths grow with persistence of the scores
+    let mut rng = StdRng::seed_from_u64(3);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let ar = |rho: f64, rng: &mut StdRng| {
+        let mut s = Array2::<f64>::zeros((500, 1));
+        let mut v = 0.0;
+        for t in 0..500 {
+            v = rho * v + normal.sample(rng);
+            s[[t, 0]] = v;
+        }
+        s
+    };
+    let weak = ar(0.1, &mut rng);
+    let strong = ar(0.8, &mut rng);
+    for kernel in [
+        HacKernel::Bartlett,
+        HacKernel::Parzen,
+        HacKernel::QuadraticSpectral,
+    ] {
+        for rule in [HacBandwidth::Andrews, HacBandwidth::NeweyWest] {
+            let lo = Sandwich::bandwidth(&weak, kernel, rule);
+            let hi = Sandwich::bandwidth(&strong, kernel, rule);
+            assert!(
+                lo >= 1.0 && hi > lo,
+                "{:?} {:?}: {} {}",
+                kernel,
+                rule,
+                lo,
+                hi
+            );
+
+            let fit = OLS::fit(&y, &x, CovarianceType::Hac(kernel, rule)).unwrap();
+            assert!(fit.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+        }
+    }
+    // Andrews (1991) Bartlett rule for a single AR(1) with ρ = 0.8
+    let rho: f64 = 0.8;
+    let alpha = 4.0 * rho * rho / ((1.0 - rho).powi(2) * (1.0 + rho).powi(2));
+    let bw = Sandwich::bandwidth(&strong, HacKernel::Bartlett, HacBandwidth::Andrews);
+    let target = 1.1447 * (alpha * 500.0).powf(1.0 / 3.0);
+    assert!((bw - target).abs() / target < 0.25, "{} vs {}", bw, target);
+}
+
+#[test]
+fn test_driscoll_kraay() {
+    let (y, x) = series(120, 4);
+    // One observation per period: Driscoll-Kraay is Newey-West
+    let periods: Vec<usize> = (0..120).collect();
+    let dk = OLS::fit(&y, &x, CovarianceType::DriscollKraay(periods.clone(), 3)).unwrap();
+    let nw = OLS::fit(&y, &x, CovarianceType::NeweyWest(3)).unwrap();
+    assert_close(&dk.std_errors, &nw.std_errors, 1e-10);
+
+    // Row order does not matter, only the period labels
+    let perm: Vec<usize> = (0..120).rev().collect();
+    let y_p = y.select(ndarray::Axis(0), &perm);
+    let x_p = x.select(ndarray::Axis(0), &perm);
+    let ids_p: Vec<usize> = perm.iter().map(|&i| periods[i]).collect();
+    let dk_p = OLS::fit(&y_p, &x_p, CovarianceType::DriscollKraay(ids_p, 3)).unwrap();
+    assert_close(&dk_p.std_errors, &dk.std_errors, 1e-9);
+
+    // Panel of 4 entities × 30 periods
+    let panel_t: Vec<usize> = (0..120).map(|i| i % 30).collect();
+    let fit = OLS::fit(&y, &x, CovarianceType::DriscollKraay(panel_t, 2)).unwrap();
+    assert!(format!("{}", fit).contains("Driscoll-Kraay (30 periods, L=2)"));
+
+    let err = OLS::fit(&y, &x, CovarianceType::DriscollKraay(vec![0; 10], 2));
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_conley_limits() {
+    let (y, x) = series(60, 5);
+    // Ten sites far apart, six observations stacked on each
+    let coords: Vec<(f64, f64)> = (0..60)
+        .map(|i| (-30.0 + 5.0 * (i % 10) as f64, 10.0 * (i % 10) as f64))
+        .collect();
+    let sites: Vec<usize> = (0..60).map(|i| i % 10).collect();
+
+    // A small cutoff only pairs observations at the same site: clustering by
+    // site up to the different small-sample factors
+    let conley = OLS::fit(&y, &x, CovarianceType::Conley(coords.clone(), 50.0)).unwrap();
+    let clustered = OLS::fit(&y, &x, CovarianceType::Clustered(sites)).unwrap();
+    let (n, k, g) = (60.0_f64, 3.0, 10.0);
+    let ratio = ((n / (n - k)) / (g / (g - 1.0) * (n - 1.0) / (n - k))).sqrt();
+    assert_close(
+        &conley.std_errors,
+        &clustered.std_errors.mapv(|s| s * ratio),
+        1e-9,
+    );
+
+    // Distinct points with a tiny cutoff reduce to HC1
+    let spread: Vec<(f64, f64)> = (0..60).map(|i| (i as f64, 0.0)).collect();
+    let tiny = OLS::fit(&y, &x, CovarianceType::Conley(spread.clone(), 1.0)).unwrap();
+    let hc1 = OLS::fit(&y, &x, CovarianceType::HC1).unwrap();
+    assert_close(&tiny.std_errors, &hc1.std_errors, 1e-10);
+
+    assert!(OLS::fit(&y, &x, CovarianceType::Conley(spread.clone(), 0.0)).is_err());
+    assert!(OLS::fit(&y, &x, CovarianceType::Conley(spread[..5].to_vec(), 10.0)).is_err());
+}
+
+#[test]
+fn test_cr2_cr3_and_satterthwaite_df() {
+    let (y, x) = series(40, 6);
+    let n = 40.0_f64;
+    // Singleton clusters: CR2 is HC2 and CR3 is HC3 times (n-1)/n
+    let singletons: Vec<usize> = (0..40).collect();
+    let cr2 = OLS::fit(&y, &x, CovarianceType::CR2(singletons.clone())).unwrap();
+    let hc2 = OLS::fit(&y, &x, CovarianceType::HC2).unwrap();
+    assert_close(&cr2.std_errors, &hc2.std_errors, 1e-9);
+    let cr3 = OLS::fit(&y, &x, CovarianceType::CR3(singletons)).unwrap();
+    let hc3 = OLS::fit(&y, &x, CovarianceType::HC3).unwrap();
+    assert_close(
+        &cr3.std_errors,
+        &hc3.std_errors.mapv(|s| s * ((n - 1.0) / n).sqrt()),
+        1e-9,
+    );
+    assert!(hc2.satterthwaite_df.is_none());
+
+    // Intercept-only model with G balanced clusters has G - 1 df
+    let ones = Array2::<f64>::ones((40, 1));
+    let groups: Vec<usize> = (0..40).map(|i| i / 5).collect();
+    let mean = OLS::fit(&y, &ones, CovarianceType::CR2(groups.clone())).unwrap();
+    let df = mean.satterthwaite_df.clone().unwrap();
+    assert!((df[0] - 7.0).abs() < 1e-8, "df = {}", df[0]);
+    let t_dist = StudentsT::new(0.0, 1.0, df[0]).unwrap();
+    let p = 2.0 * (1.0 - t_dist.cdf(mean.t_values[0].abs()));
+    assert!((mean.p_values[0] - p).abs() < 1e-12);
+    assert!(format!("{}", mean).contains("CR2 (8 clusters)"));
+
+    // Satterthwaite df never exceed G - 1 and survive with_inference
+    let fit = OLS::fit(&y, &x, CovarianceType::CR2(groups)).unwrap();
+    let df = fit.satterthwaite_df.clone().unwrap();
+    assert!(df.iter().all(|&d| d > 0.0 && d <= 7.0 + 1e-8));
+    let refit = fit
+        .clone()
+        .with_inference(greeners_core::InferenceType::StudentT)
+        .unwrap();
+    assert_close(&refit.p_values, &fit.p_values, 1e-12);
+}
+
+#[test]
+fn test_new_covariances_across_estimators() {
+    let (y, x) = series(100, 7);
+    let mut rng = StdRng::seed_from_u64(8);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let z = {
+        let mut z = x.clone();
+        for i in 0..100 {
+            z[[i, 2]] = x[[i, 2]] + normal.sample(&mut rng);
+        }
+        z
+    };
+    let (n, k) = (100.0_f64, 3.0);
+    let iv0 = IV::fit(&y, &x, &z, CovarianceType::HC0).unwrap();
+    let iv1 = IV::fit(&y, &x, &z, CovarianceType::HC1).unwrap();
+    assert_close(
+        &iv0.std_errors.mapv(|s| s * (n / (n - k)).sqrt()),
+        &iv1.std_errors,
+        1e-10,
+    );
+    let groups: Vec<usize> = (0..100).map(|i| i / 10).collect();
+    for cov in [
+        CovarianceType::HC5,
+        CovarianceType::Hac(HacKernel::QuadraticSpectral, HacBandwidth::Andrews),
+        CovarianceType::DriscollKraay(groups.clone(), 1),
+        CovarianceType::CR2(groups.clone()),
+        CovarianceType::CR3(groups.clone()),
+    ] {
+        let fit = IV::fit(&y, &x, &z, cov).unwrap();
+        assert!(fit.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+    }
+
+    // Formula fits subset per-row covariance data alongside dropped rows
+    let mut x1 = x.column(1).to_vec();
+    x1[3] = f64::NAN;
+    let df = DataFrame::builder()
+        .add_column("y", y.to_vec())
+        .add_column("x1", x1)
+        .add_column("x2", x.column(2).to_vec())
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x1 + x2").unwrap();
+    let coords: Vec<(f64, f64)> = (0..100).map(|i| (i as f64 * 0.5, 0.0)).collect();
+    for cov in [
+        CovarianceType::CR2(groups.clone()),
+        CovarianceType::DriscollKraay(groups.clone(), 2),
+        CovarianceType::Conley(coords, 100.0),
+    ] {
+        let fit = OLS::from_formula(&formula, &df, cov).unwrap();
+        assert_eq!(fit.n_obs, 99);
+    }
+}
@@ -108,3 +108,42 @@ fn event_study_input_validation() {
     )
     .is_err());
 }
+
+#[test]
+fn event_study_robust_covariances_match_ols_sandwich() {
+    use greeners_ols::ols::OLS;
+
+    let mut rng = StdRng::seed_from_u64(33);
+    let noise = Normal::new(0.0, 1.0).unwrap();
+    let n = 84;
+    let event_time: Vec<i64> = (0..n).map(|i| (i % 7) as i64 - 3).collect();
+    let y = Array1::from_iter(
+        event_time
+            .iter()
+            .map(|&t| if t >= 0 { 0.5 } else { 0.0 } + noise.sample(&mut rng)),
+    );
+    let x_controls = Array2::zeros((n, 0));
+    let clusters: Vec<usize> = (0..n).map(|i| i / 6).collect();
+
+    // Rebuild the event-study design: intercept plus dummies for t != -1
+    let times = [-3i64, -2, 0, 1, 2, 3];
+    let mut x = Array2::<f64>::zeros((n, 1 + times.len()));
+    for (i, &t) in event_time.iter().enumerate() {
+        x[(i, 0)] = 1.0;
+        if let Some(j) = times.iter().position(|&e| e == t) {
+            x[(i, 1 + j)] = 1.0;
+        }
+    }
+
+    for cov in [
+        CovarianceType::HC3,
+        CovarianceType::Clustered(clusters.clone()),
+        CovarianceType::CR2(clusters),
+    ] {
+        let es = EventStudy::fit(&y, &event_time, &x_controls, -1, -3, 3, cov.clone()).unwrap();
+        let ols = OLS::fit(&y, &x, cov).unwrap();
+        for j in 0..x.ncols() {
+            assert!((es.ols.std_errors[j] - ols.std_errors[j]).abs() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use greeners_core::dataframe::DataFrame;
+use greeners_core::formula::Formula;
+use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::types::CovarianceType;
+use greeners_ols::iv::{KClassEstimator, IV};
+use indexmap::IndexMap;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+
+/// y = 1 + 0.5·w + 2·x + u, x endogenous, three excluded instruments.
+/// Columns: X = [1, w, x], Z = [1, w, z1, z2, z3].
+fn design(n: usize, seed: u64) -> (Array1<f64>, Array2<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut x = Array2::<f64>::ones((n, 3));
+    let mut z = Array2::<f64>::ones((n, 5));
+    let mut y = Array1::<f64>::zeros(n);
+    for i in 0..n {
+        let w: f64 = normal.sample(&mut rng);
+        let zs: Vec<f64> = (0..3).map(|_| normal.sample(&mut rng)).collect();
+        let common: f64 = normal.sample(&mut rng);
+        let xi =
+            0.3 * w + 0.4 * zs[0] + 0.2 * zs[1] + 0.1 * zs[2] + common + normal.sample(&mut rng);
+        x[[i, 1]] = w;
+        x[[i, 2]] = xi;
+        z[[i, 1]] = w;
+        for j in 0..3 {
+            z[[i, j + 2]] = zs[j];
+        }
+        y[i] = 1.0 + 0.5 * w + 2.0 * xi + common + normal.sample(&mut rng);
+    }
+    (y, x, z)
+}
+
+/// u'M u for the residual maker of `basis`.
+fn residual_ss(u: &Array1<f64>, basis: &Array2<f64>) -> f64 {
+    let coef = basis.t().dot(basis).inv().unwrap().dot(&basis.t().dot(u));
+    let r = u - &basis.dot(&coef);
+    r.dot(&r)
+}
+
+#[test]
+fn test_liml_minimises_variance_ratio() {
+    let (y, x, z) = design(300, 1);
+    let liml =
+        IV::fit_k_class(&y, &x, &z, KClassEstimator::Liml, CovarianceType::NonRobust).unwrap();
+    assert!(liml.kappa >= 1.0);
+    assert_eq!(liml.estimator, KClassEstimator::Liml);
+
+    // κ_LIML is the minimised ratio u'M_W u / u'M_Z u, attained at β_LIML
+    let w = x.slice(ndarray::s![.., 0..2]).to_owned();
+    let ratio = |beta: &Array1<f64>| {
+        let u = &y - &x.dot(beta);
+        residual_ss(&u, &w) / residual_ss(&u, &z)
+    };
+    assert!((ratio(&liml.params) - liml.kappa).abs() < 1e-8);
+    let tsls = IV::fit(&y, &x, &z, CovarianceType::NonRobust).unwrap();
+    assert!(ratio(&tsls.params) >= liml.kappa);
+    assert!((liml.params[2] - 2.0).abs() < 3.0 * liml.std_errors[2]);
+}
+
+#[test]
+fn test_k_class_special_cases() {
+    let (y, x, z) = design(200, 2);
+    let n = 200.0;
+
+    let tsls = IV::fit(&y, &x, &z, CovarianceType::HC1).unwrap();
+    let k1 = IV::fit_k_class(&y, &x, &z, KClassEstimator::Kappa(1.0), CovarianceType::HC1).unwrap();
+    for j in 0..3 {
+        assert!((tsls.params[j] - k1.params[j]).abs() < 1e-12);
+        assert!((tsls.std_errors[j] - k1.std_errors[j]).abs() < 1e-12);
+    }
+    assert_eq!(tsls.kappa, 1.0);
+
+    // κ = 0 is OLS
+    let ols = IV::fit_k_class(
+        &y,
+        &x,
+        &z,
+        KClassEstimator::Kappa(0.0),
+        CovarianceType::NonRobust,
+    )
+    .unwrap();
+    let beta_ols = x.t().dot(&x).inv().unwrap().dot(&x.t().dot(&y));
+    for j in 0..3 {
+        assert!((ols.params[j] - beta_ols[j]).abs() < 1e-10);
+    }
+
+    let liml =
+        IV::fit_k_class(&y, &x, &z, KClassEstimator::Liml, CovarianceType::NonRobust).unwrap();
+    let fuller1 = IV::fit_k_class(
+        &y,
+        &x,
+        &z,
+        KClassEstimator::Fuller(1.0),
+        CovarianceType::NonRobust,
+    )
+    .unwrap();
+    let fuller4 = IV::fit_k_class(
+        &y,
+        &x,
+        &z,
+        KClassEstimator::Fuller(4.0),
+        CovarianceType::NonRobust,
+    )
+    .unwrap();
+    assert!((fuller1.kappa - (liml.kappa - 1.0 / (n - 5.0))).abs() < 1e-12);
+    assert!((fuller4.kappa - (liml.kappa - 4.0 / (n - 5.0))).abs() < 1e-12);
+
+    // Exact identification: LIML coincides with 2SLS
+    let z_exact = z.slice(ndarray::s![.., 0..3]).to_owned();
+    let liml_exact = IV::fit_k_class(
+        &y,
+        &x,
+        &z_exact,
+        KClassEstimator::Liml,
+        CovarianceType::NonRobust,
+    )
+    .unwrap();
+    let tsls_exact = IV::fit(&y, &x, &z_exact, CovarianceType::NonRobust).unwrap();
+    assert!((liml_exact.kappa - 1.0).abs() < 1e-10);
+    for j in 0..3 {
+        assert!((liml_exact.params[j] - tsls_exact.params[j]).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn test_k_class_covariances() {
+    let (y, x, z) = design(240, 3);
+    let liml =
+        IV::fit_k_class(&y, &x, &z, KClassEstimator::Liml, CovarianceType::NonRobust).unwrap();
+
+    // Homoskedastic covariance is σ²[X'(I − κM_Z)X]⁻¹
+    let pz = z.dot(&z.t().dot(&z).inv().unwrap()).dot(&z.t());
+    let x_fit = pz.dot(&x);
+    let x_k = &x * (1.0 - liml.kappa) + &(&x_fit * liml.kappa);
+    let expected = x_k.t().dot(&x).inv().unwrap() * liml.sigma.powi(2);
+    let cov = liml.cov_matrix.as_ref().unwrap();
+    for a in 0..3 {
+        for b in 0..3 {
+            assert!((cov[[a, b]] - expected[[a, b]]).abs() < 1e-10);
+        }
+    }
+
+    let ids: Vec<usize> = (0..240).map(|i| i / 8).collect();
+    for cov_type in [
+        CovarianceType::HC1,
+        CovarianceType::NeweyWest(4),
+        CovarianceType::Clustered(ids),
+    ] {
+        let res = IV::fit_k_class(&y, &x, &z, KClassEstimator::Fuller(1.0), cov_type).unwrap();
+        assert!(res.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+    }
+
+    let text = format!("{}", liml);
+    assert!(text.contains("IV (LIML)") && text.contains("Kappa"));
+    assert!(IV::fit_k_class(
+        &y,
+        &x,
+        &z,
+        KClassEstimator::Kappa(f64::NAN),
+        CovarianceType::NonRobust
+    )
+    .is_err());
+}
+
+#[test]
+fn test_k_class_from_formula() {
+    let (y, x, z) = design(150, 4);
+    let mut data = IndexMap::new();
+    data.insert("y".to_string(), y.clone());
+    data.insert("w".to_string(), x.column(1).to_owned());
+    data.insert("x".to_string(), x.column(2).to_owned());
+    for j in 0..3 {
+        data.insert(format!("z{}", j + 1), z.column(j + 2).to_owned());
+    }
+    let df = DataFrame::new(data).unwrap();
+    let endog = Formula::parse("y ~ w + x").unwrap();
+    let instruments = Formula::parse("x ~ w + z1 + z2 + z3").unwrap();
+
+    let res = IV::from_formula_k_class(
+        &endog,
+        &instruments,
+        &df,
+        KClassEstimator::Liml,
+        CovarianceType::HC1,
+    )
+    .unwrap();
+    let direct = IV::fit_k_class(&y, &x, &z, KClassEstimator::Liml, CovarianceType::HC1).unwrap();
+    assert!((res.kappa - direct.kappa).abs() < 1e-10);
+    for j in 0..3 {
+        assert!((res.params[j] - direct.params[j]).abs() < 1e-10);
+    }
+    assert_eq!(res.variable_names.as_ref().unwrap()[2], "x");
+}
+
+#[test]
+fn test_bias_reduced_cluster_df_match_ols_when_just_identified() {
+    use greeners_ols::OLS;
+
+    let (y, x, _) = design(60, 8);
+    let ids: Vec<usize> = (0..60).map(|i| i / 5).collect();
+    let iv = IV::fit(&y, &x, &x, CovarianceType::CR2(ids.clone())).unwrap();
+    let ols = OLS::fit(&y, &x, CovarianceType::CR2(ids)).unwrap();
+    let iv_df = iv.satterthwaite_df.as_ref().unwrap();
+    let ols_df = ols.satterthwaite_df.as_ref().unwrap();
+    for j in 0..3 {
+        assert!((iv_df[j] - ols_df[j]).abs() < 1e-8);
+        assert!((iv.std_errors[j] - ols.std_errors[j]).abs() < 1e-10);
+        assert!((iv.p_values[j] - ols.p_values[j]).abs() < 1e-10);
+    }
+
+    // Results saved before the k-class fields existed read back as 2SLS
+    let mut json = serde_json::to_value(&iv).unwrap();
+    let fields = json.as_object_mut().unwrap();
+    for key in ["estimator", "kappa", "satterthwaite_df"] {
+        fields.remove(key);
+    }
+    let old: greeners_ols::iv::IvResult = serde_json::from_value(json).unwrap();
+    assert_eq!(old.estimator, KClassEstimator::TwoSls);
+    assert_eq!(old.kappa, 1.0);
+    assert!(old.satterthwaite_df.is_none());
+}
@@ -0,0 +1,249 @@
+use greeners_core::{CovarianceType, DataFrame, Formula};
+use greeners_ols::lasso_inference::{LassoInference, LassoInferenceMethod};
+use greeners_ols::ols::OLS;
+use greeners_ols::reg_path::{LambdaRule, Penalty, RegPath, RegPathConfig};
+use ndarray::{Array1, Array2};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::Normal;
+
+/// Sparse design with p > n: y depends on the first three columns only.
+fn sparse_design(seed: u64, n: usize, p: usize) -> (Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let x = Array2::from_shape_fn((n, p), |_| normal.sample(&mut rng));
+    let y = Array1::from_shape_fn(n, |i| {
+        1.0 + 3.0 * x[[i, 0]] - 2.0 * x[[i, 1]] + 1.5 * x[[i, 2]] + 0.5 * normal.sample(&mut rng)
+    });
+    (y, x)
+}
+
+#[test]
+fn test_lasso_path_with_more_predictors_than_observations() {
+    let (y, x) = sparse_design(1, 60, 200);
+    let path = RegPath::fit(&y, &x, "lasso", None, Some(40), None).unwrap();
+    assert_eq!(path.n_pred, 200);
+    // Largest lambda selects nothing, the path then grows
+    assert!(path.coef_path.row(0).iter().all(|&c| c == 0.0));
+    let nonzero = |i: usize| {
+        path.coef_path
+            .row(i)
+            .iter()
+            .filter(|c| c.abs() > 0.0)
+            .count()
+    };
+    assert!(nonzero(10) <= nonzero(39));
+    // Saturated models get no BIC and are never chosen
+    assert!(path.bic_path.iter().any(|b| b.is_finite()));
+    assert!(path.selected_vars.len() < 59);
+    for name in ["x0", "x1", "x2"] {
+        assert!(
+            path.selected_vars.iter().any(|v| v == name),
+            "{} missing",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_coordinate_descent_satisfies_kkt() {
+    let (y, x) = sparse_design(2, 80, 30);
+    let config = RegPathConfig {
+        penalty: Penalty::ElasticNet(0.7),
+        n_lambdas: 20,
+        tol: 1e-12,
+        ..RegPathConfig::default()
+    };
+    let path = RegPath::fit_with_config(&y, &x, &config, None).unwrap();
+
+    // KKT conditions on the standardized problem at an interior lambda
+    let n = y.len() as f64;
+    let x_mean = x.mean_axis(ndarray::Axis(0)).unwrap();
+    let x_std = x.std_axis(ndarray::Axis(0), 0.0);
+    let y_std = y.std(0.0);
+    let xs = (&x - &x_mean) / &x_std;
+    let idx = 10;
+    let lam = path.lambdas[idx];
+    let beta = path.coef_path.row(idx).to_owned() * &x_std / y_std;
+    let resid = (&y - &x.dot(&path.coef_path.row(idx)) - path.intercept_path[idx]) / y_std;
+    let grad = xs.t().dot(&resid) / n;
+    for j in 0..30 {
+        let g = grad[j] - lam * 0.3 * beta[j];
+        if beta[j] != 0.0 {
+            assert!((g - lam * 0.7 * beta[j].signum()).abs() < 1e-6);
+        } else {
+            assert!(g.abs() <= lam * 0.7 + 1e-8);
+        }
+    }
+}
+
+#[test]
+fn test_cross_validation_min_and_one_se_rules() {
+    let (y, x) = sparse_design(3, 100, 50);
+    let cv_min = RegPathConfig {
+        rule: LambdaRule::CvMin,
+        n_lambdas: 40,
+        n_folds: 5,
+        seed: Some(7),
+        ..RegPathConfig::default()
+    };
+    let min = RegPath::fit_with_config(&y, &x, &cv_min, None).unwrap();
+    let cv_1se = RegPathConfig {
+        rule: LambdaRule::Cv1se,
+        ..cv_min.clone()
+    };
+    let one_se = RegPath::fit_with_config(&y, &x, &cv_1se, None).unwrap();
+
+    let err = min.cv_error.as_ref().unwrap();
+    let se = min.cv_se.as_ref().unwrap();
+    assert_eq!(err.len(), 40);
+    let best = err.iter().cloned().fold(f64::INFINITY, f64::min);
+    let i_min = err.iter().position(|&e| e == best).unwrap();
+    assert_eq!(min.optimal_lambda, min.lambdas[i_min]);
+    assert_eq!(min.lambda_cv_min, one_se.lambda_cv_min);
+
+    // The one-SE lambda is at least as large and within one SE of the best
+    assert!(one_se.optimal_lambda >= min.optimal_lambda);
+    let i_1se = one_se
+        .lambdas
+        .iter()
+        .position(|&l| l == one_se.optimal_lambda)
+        .unwrap();
+    assert!(err[i_1se] <= best + se[i_min] + 1e-12);
+    assert!(one_se.selected_vars.len() <= min.selected_vars.len());
+
+    // Same seed, same folds
+    let again = RegPath::fit_with_config(&y, &x, &cv_min, None).unwrap();
+    assert_eq!(again.cv_error, min.cv_error);
+
+    let bad = RegPathConfig {
+        n_folds: 1,
+        ..cv_min
+    };
+    assert!(RegPath::fit_with_config(&y, &x, &bad, None).is_err());
+}
+
+#[test]
+fn test_plugin_penalty_recovers_sparse_support() {
+    let (y, x) = sparse_design(4, 150, 300);
+    let config = RegPathConfig {
+        rule: LambdaRule::Plugin,
+        n_lambdas: 10,
+        ..RegPathConfig::default()
+    };
+    let fit = RegPath::fit_with_config(&y, &x, &config, None).unwrap();
+    assert_eq!(fit.selected_vars, vec!["x0", "x1", "x2"]);
+    assert_eq!(fit.penalty_loadings.as_ref().unwrap().len(), 300);
+    // Penalized estimates are shrunk towards zero
+    assert!(fit.optimal_coefs[0] > 2.0 && fit.optimal_coefs[0] < 3.0);
+
+    let ridge = RegPathConfig {
+        penalty: Penalty::Ridge,
+        ..config
+    };
+    assert!(RegPath::fit_with_config(&y, &x, &ridge, None).is_err());
+}
+
+/// Treatment driven by a few of many controls that also affect y.
+fn confounded(seed: u64, n: usize, p: usize) -> (Array1<f64>, Array1<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let x = Array2::from_shape_fn((n, p), |_| normal.sample(&mut rng));
+    let d = Array1::from_shape_fn(n, |i| {
+        0.8 * x[[i, 0]] + 0.8 * x[[i, 1]] + 0.5 * x[[i, 3]] + normal.sample(&mut rng)
+    });
+    let y = Array1::from_shape_fn(n, |i| {
+        0.5 * d[i] + 1.0 * x[[i, 0]] + 1.0 * x[[i, 1]] + 0.6 * x[[i, 2]] + normal.sample(&mut rng)
+    });
+    (y, d, x)
+}
+
+#[test]
+fn test_double_selection_and_partialling_out() {
+    let (y, d, x) = confounded(5, 400, 150);
+
+    let pds = LassoInference::post_double_selection(&y, &d, &x, CovarianceType::HC1, None).unwrap();
+    assert!(pds.selected_controls.contains(&"x0".to_string()));
+    assert!(pds.selected_controls.contains(&"x1".to_string()));
+    assert!(pds.conf_lower < 0.5 && 0.5 < pds.conf_upper);
+
+    // Same number as OLS on d and the selected controls
+    let cols: Vec<usize> = pds
+        .selected_controls
+        .iter()
+        .map(|n| n[1..].parse().unwrap())
+        .collect();
+    let mut design = Array2::<f64>::ones((400, 2 + cols.len()));
+    design.column_mut(1).assign(&d);
+    for (c, &j) in cols.iter().enumerate() {
+        design.column_mut(2 + c).assign(&x.column(j));
+    }
+    let ols = OLS::fit(&y, &design, CovarianceType::HC1).unwrap();
+    assert!((ols.params[1] - pds.coef).abs() < 1e-10);
+    assert!((ols.std_errors[1] - pds.std_error).abs() < 1e-10);
+
+    let po = LassoInference::partialling_out(&y, &d, &x, CovarianceType::HC1, None).unwrap();
+    assert!((po.coef - pds.coef).abs() < 0.1);
+    assert!(po.conf_lower < 0.5 && 0.5 < po.conf_upper);
+
+    // Short regression of y on d alone is badly biased
+    let short = OLS::fit(
+        &y,
+        &ndarray::stack![ndarray::Axis(1), Array1::ones(400), d],
+        CovarianceType::HC1,
+    )
+    .unwrap();
+    assert!(short.params[1] - 0.5 > 0.3);
+}
+
+#[test]
+fn test_double_selection_control_named_like_treatment() {
+    let (y, d, x) = confounded(5, 400, 150);
+    let base =
+        LassoInference::post_double_selection(&y, &d, &x, CovarianceType::HC1, None).unwrap();
+
+    // A control called "d" must not be mistaken for the treatment
+    let mut names: Vec<String> = (0..150).map(|j| format!("x{}", j)).collect();
+    names[0] = "d".to_string();
+    let renamed =
+        LassoInference::post_double_selection(&y, &d, &x, CovarianceType::HC1, Some(names))
+            .unwrap();
+    assert!(renamed.selected_controls.contains(&"d".to_string()));
+    assert!((renamed.coef - base.coef).abs() < 1e-12);
+    assert!((renamed.std_error - base.std_error).abs() < 1e-12);
+}
+
+#[test]
+fn test_lasso_inference_from_formula() {
+    let (y, d, x) = confounded(6, 300, 8);
+    let mut builder = DataFrame::builder()
+        .add_column("y", y.to_vec())
+        .add_column("treat", d.to_vec());
+    for j in 0..8 {
+        builder = builder.add_column(&format!("c{}", j), x.column(j).to_vec());
+    }
+    let df = builder.build().unwrap();
+    let formula = Formula::parse("y ~ treat + c0 + c1 + c2 + c3 + c4 + c5 + c6 + c7").unwrap();
+    let fit = LassoInference::from_formula(
+        &formula,
+        "treat",
+        &df,
+        LassoInferenceMethod::PostDoubleSelection,
+        CovarianceType::HC1,
+    )
+    .unwrap();
+    assert_eq!(fit.treatment_name, "treat");
+    assert_eq!(fit.n_controls, 8);
+    assert!(fit.selected_controls.contains(&"c0".to_string()));
+    assert!((fit.coef - 0.5).abs() < 0.2);
+    assert!(format!("{}", fit).contains("treat"));
+
+    assert!(LassoInference::from_formula(
+        &formula,
+        "nope",
+        &df,
+        LassoInferenceMethod::PartiallingOut,
+        CovarianceType::HC1,
+    )
+    .is_err());
+}
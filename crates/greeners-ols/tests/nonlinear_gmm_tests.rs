@@ -0,0 +1,326 @@
+use greeners_core::error::GreenersError;
+use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::types::CovarianceType;
+use greeners_ols::gmm::{GmmWeighting, NonlinearGMM, NonlinearGmmOptions, GMM};
+use ndarray::{array, Array1, Array2};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+
+/// Rows [y, x, z1, z2, z3]: y = 1 + 2x + u with x endogenous and
+/// heteroskedastic errors.
+fn linear_data(n: usize, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut data = Array2::<f64>::zeros((n, 5));
+    for i in 0..n {
+        let z: Vec<f64> = (0..3).map(|_| normal.sample(&mut rng)).collect();
+        let common: f64 = normal.sample(&mut rng);
+        let x = 0.6 * z[0] + 0.4 * z[1] + 0.3 * z[2] + common + normal.sample(&mut rng);
+        let u = (common + normal.sample(&mut rng)) * (1.0 + 0.5 * z[0].abs());
+        data[[i, 0]] = 1.0 + 2.0 * x + u;
+        data[[i, 1]] = x;
+        for j in 0..3 {
+            data[[i, j + 2]] = z[j];
+        }
+    }
+    data
+}
+
+fn linear_moments(theta: &[f64], row: &[f64]) -> Array1<f64> {
+    let u = row[0] - theta[0] - theta[1] * row[1];
+    array![u, u * row[2], u * row[3], u * row[4]]
+}
+
+fn linear_jacobian(_theta: &[f64], row: &[f64]) -> Array2<f64> {
+    let mut j = Array2::<f64>::zeros((4, 2));
+    let z = [1.0, row[2], row[3], row[4]];
+    for (m, zm) in z.iter().enumerate() {
+        j[[m, 0]] = -zm;
+        j[[m, 1]] = -zm * row[1];
+    }
+    j
+}
+
+/// Exponential (Poisson-type) conditional mean with an endogenous regressor:
+/// E[z (y − exp(a + b x))] = 0. Rows [y, x, z1, z2].
+fn exponential_data(n: usize, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut data = Array2::<f64>::zeros((n, 4));
+    for i in 0..n {
+        let z1: f64 = normal.sample(&mut rng);
+        let z2: f64 = normal.sample(&mut rng);
+        let v: f64 = normal.sample(&mut rng);
+        let x = 0.5 * z1 + 0.5 * z2 + 0.5 * v;
+        let mult = (0.3 * v + 0.3 * normal.sample(&mut rng) - 0.09).exp();
+        data[[i, 0]] = (0.5 + 0.7 * x).exp() * mult;
+        data[[i, 1]] = x;
+        data[[i, 2]] = z1;
+        data[[i, 3]] = z2;
+    }
+    data
+}
+
+fn exponential_moments(theta: &[f64], row: &[f64]) -> Array1<f64> {
+    let u = row[0] - (theta[0] + theta[1] * row[1]).exp();
+    array![u, u * row[2], u * row[3]]
+}
+
+#[test]
+fn test_linear_moments_match_linear_gmm() {
+    let data = linear_data(400, 1);
+    let y = data.column(0).to_owned();
+    let mut x = Array2::<f64>::ones((400, 2));
+    x.column_mut(1).assign(&data.column(1));
+    let mut z = Array2::<f64>::ones((400, 4));
+    for j in 0..3 {
+        z.column_mut(j + 1).assign(&data.column(j + 2));
+    }
+    let linear = GMM::fit(&y, &x, &z).unwrap();
+
+    // Same first-step weight as the linear estimator (2SLS)
+    let options = NonlinearGmmOptions {
+        initial_weight: Some((z.t().dot(&z) / 400.0).inv().unwrap()),
+        ..NonlinearGmmOptions::default()
+    };
+    let res = NonlinearGMM::fit(&data, &linear_moments, None, &[0.0, 0.0], &options).unwrap();
+    assert!(res.converged);
+    for j in 0..2 {
+        assert!((res.params[j] - linear.params[j]).abs() < 1e-6);
+        assert!((res.std_errors[j] - linear.std_errors[j]).abs() < 1e-6);
+    }
+    assert!((res.j_stat - linear.j_stat).abs() < 1e-6);
+    assert_eq!(res.df_overid, 2);
+
+    // Analytic Jacobian gives the same estimates
+    let analytic = NonlinearGMM::fit(
+        &data,
+        &linear_moments,
+        Some(&linear_jacobian),
+        &[0.0, 0.0],
+        &options,
+    )
+    .unwrap();
+    for j in 0..2 {
+        assert!((analytic.params[j] - res.params[j]).abs() < 1e-8);
+        assert!((analytic.std_errors[j] - res.std_errors[j]).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_weighting_schemes_on_exponential_model() {
+    let data = exponential_data(800, 7);
+    let start = [0.0, 0.0];
+    let mut estimates = Vec::new();
+    for weighting in [
+        GmmWeighting::OneStep,
+        GmmWeighting::TwoStep,
+        GmmWeighting::Iterated(50),
+        GmmWeighting::ContinuouslyUpdated,
+    ] {
+        let options = NonlinearGmmOptions {
+            weighting,
+            ..NonlinearGmmOptions::default()
+        };
+        let res = NonlinearGMM::fit(&data, &exponential_moments, None, &start, &options).unwrap();
+        assert!(res.converged, "{:?} did not converge", weighting);
+        assert!((res.params[1] - 0.7).abs() < 4.0 * res.std_errors[1]);
+        assert!(res.j_p_value > 0.001 && res.j_p_value <= 1.0);
+        estimates.push(res);
+    }
+
+    // The CUE minimises its own objective: perturbations only increase it
+    let cue = &estimates[3];
+    let q = |theta: &[f64]| {
+        let mut g = Array2::<f64>::zeros((800, 3));
+        for i in 0..800 {
+            g.row_mut(i)
+                .assign(&exponential_moments(theta, &data.row(i).to_vec()));
+        }
+        let gbar = g.sum_axis(ndarray::Axis(0)) / 800.0;
+        let s = g.t().dot(&g) / 800.0;
+        gbar.dot(&s.inv().unwrap().dot(&gbar))
+    };
+    let q0 = q(&cue.params.to_vec());
+    for d in [[1e-3, 0.0], [0.0, 1e-3], [-1e-3, 0.0], [0.0, -1e-3]] {
+        assert!(q(&[cue.params[0] + d[0], cue.params[1] + d[1]]) >= q0);
+    }
+    assert!((cue.j_stat - 800.0 * q0).abs() < 1e-8);
+
+    // Iterated weights are a fixed point: one more update changes nothing
+    let iterated = &estimates[2];
+    let two_step_from_iterated = NonlinearGMM::fit(
+        &data,
+        &exponential_moments,
+        None,
+        &iterated.params.to_vec(),
+        &NonlinearGmmOptions {
+            initial_weight: Some(iterated.weight_matrix.clone()),
+            weighting: GmmWeighting::OneStep,
+            ..NonlinearGmmOptions::default()
+        },
+    )
+    .unwrap();
+    assert!((two_step_from_iterated.params[1] - iterated.params[1]).abs() < 1e-5);
+    assert!(format!("{}", cue).contains("Nonlinear GMM (CUE)"));
+}
+
+#[test]
+fn test_windmeijer_and_robust_weights() {
+    let data = linear_data(120, 3);
+    let plain = NonlinearGMM::fit(
+        &data,
+        &linear_moments,
+        None,
+        &[0.0, 0.0],
+        &NonlinearGmmOptions::default(),
+    )
+    .unwrap();
+    let corrected = NonlinearGMM::fit(
+        &data,
+        &linear_moments,
+        None,
+        &[0.0, 0.0],
+        &NonlinearGmmOptions {
+            windmeijer: true,
+            ..NonlinearGmmOptions::default()
+        },
+    )
+    .unwrap();
+    assert!(corrected.windmeijer && !plain.windmeijer);
+    assert_eq!(corrected.params, plain.params);
+
+    // Closed-form linear Windmeijer (2005) correction in Z'X notation with
+    // the analytic derivative of S(β) = Σ z z' u² / n and identity first step
+    let n = 120;
+    let nf = n as f64;
+    let mut x = Array2::<f64>::ones((n, 2));
+    x.column_mut(1).assign(&data.column(1));
+    let mut z = Array2::<f64>::ones((n, 4));
+    for j in 0..3 {
+        z.column_mut(j + 1).assign(&data.column(j + 2));
+    }
+    let y = data.column(0).to_owned();
+    let a = z.t().dot(&x) / nf;
+    let w1 = Array2::<f64>::eye(4);
+    let beta1 = a.t().dot(&a).inv().unwrap().dot(&a.t().dot(&z.t().dot(&y))) / nf;
+    let u1 = &y - &x.dot(&beta1);
+    let s_of = |u: &Array1<f64>, weights: &Array1<f64>| {
+        let mut zw = z.clone();
+        for i in 0..n {
+            zw.row_mut(i).mapv_inplace(|v| v * u[i] * weights[i]);
+        }
+        z.t().dot(&zw) / nf
+    };
+    let s1 = s_of(&u1, &u1);
+    let w2 = s1.inv().unwrap();
+    let beta2 = Array1::from(plain.params.to_vec());
+    let u2 = &y - &x.dot(&beta2);
+    let g2 = z.t().dot(&u2) / nf;
+    let a2_inv = a.t().dot(&w2).dot(&a).inv().unwrap();
+    let v2 = &a2_inv / nf;
+    let a1_inv = a.t().dot(&w1).dot(&a).inv().unwrap();
+    let v1 = a1_inv.dot(&a.t().dot(&s1).dot(&a)).dot(&a1_inv) / nf;
+    let mut d = Array2::<f64>::zeros((2, 2));
+    for j in 0..2 {
+        let xj = x.column(j).to_owned();
+        let ds = s_of(&u1, &(&xj * -2.0));
+        let col = -a2_inv.dot(&a.t().dot(&w2).dot(&ds).dot(&w2).dot(&g2));
+        d.column_mut(j).assign(&col);
+    }
+    let vc = &v2 + &d.dot(&v2) + &v2.dot(&d.t()) + &d.dot(&v1).dot(&d.t());
+    for j in 0..2 {
+        assert!((corrected.std_errors[j] - vc[[j, j]].sqrt()).abs() < 1e-6);
+    }
+
+    let ids: Vec<usize> = (0..120).map(|i| i / 4).collect();
+    for cov_type in [
+        CovarianceType::NeweyWest(3),
+        CovarianceType::Clustered(ids.clone()),
+        CovarianceType::ClusteredTwoWay(ids, (0..120).map(|i| i % 5).collect()),
+    ] {
+        let res = NonlinearGMM::fit(
+            &data,
+            &linear_moments,
+            None,
+            &[0.0, 0.0],
+            &NonlinearGmmOptions {
+                cov_type,
+                ..NonlinearGmmOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(res.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+    }
+}
+
+#[test]
+fn test_d_test_detects_invalid_moment() {
+    let mut data = linear_data(600, 11);
+    let options = NonlinearGmmOptions::default();
+    let valid =
+        NonlinearGMM::d_test(&data, &linear_moments, None, &[0.0, 0.0], &options, &[3]).unwrap();
+    assert_eq!(valid.df, 1);
+    assert!(valid.statistic >= 0.0);
+    assert!(valid.p_value > 0.01);
+    assert!((valid.statistic - (valid.j_full - valid.j_restricted)).abs() < 1e-10);
+
+    // Make z3 correlated with the structural error
+    for i in 0..600 {
+        let u = data[[i, 0]] - 1.0 - 2.0 * data[[i, 1]];
+        data[[i, 4]] += 0.8 * u;
+    }
+    let invalid =
+        NonlinearGMM::d_test(&data, &linear_moments, None, &[0.0, 0.0], &options, &[3]).unwrap();
+    assert!(invalid.p_value < 0.01);
+    assert!(format!("{}", invalid).contains("Difference-in-Hansen"));
+}
+
+#[test]
+fn test_nonlinear_gmm_errors() {
+    let data = linear_data(50, 2);
+    let options = NonlinearGmmOptions::default();
+    // Fewer moments than parameters
+    let one_moment = |theta: &[f64], row: &[f64]| array![row[0] - theta[0] - theta[1] * row[1]];
+    assert!(NonlinearGMM::fit(&data, &one_moment, None, &[0.0, 0.0], &options).is_err());
+    // Moment vectors whose length changes between rows
+    let ragged = |theta: &[f64], row: &[f64]| {
+        let mut g = linear_moments(theta, row);
+        if row[1] > 0.0 {
+            g = g.slice(ndarray::s![..3]).to_owned();
+        }
+        g
+    };
+    assert!(matches!(
+        NonlinearGMM::fit(&data, &ragged, None, &[0.0, 0.0], &options),
+        Err(GreenersError::ShapeMismatch(_))
+    ));
+    // Restricted model would be underidentified
+    assert!(NonlinearGMM::d_test(
+        &data,
+        &linear_moments,
+        None,
+        &[0.0, 0.0],
+        &options,
+        &[1, 2, 3]
+    )
+    .is_err());
+    assert!(
+        NonlinearGMM::d_test(&data, &linear_moments, None, &[0.0, 0.0], &options, &[9]).is_err()
+    );
+    let bad_weight = NonlinearGmmOptions {
+        initial_weight: Some(Array2::eye(2)),
+        ..NonlinearGmmOptions::default()
+    };
+    assert!(NonlinearGMM::fit(&data, &linear_moments, None, &[0.0, 0.0], &bad_weight).is_err());
+    // Leverage-corrected cluster SE have no GMM analogue
+    let ids: Vec<usize> = (0..50).map(|i| i / 5).collect();
+    for cov_type in [CovarianceType::CR2(ids.clone()), CovarianceType::CR3(ids)] {
+        let options = NonlinearGmmOptions {
+            cov_type,
+            ..NonlinearGmmOptions::default()
+        };
+        assert!(NonlinearGMM::fit(&data, &linear_moments, None, &[0.0, 0.0], &options).is_err());
+    }
+}
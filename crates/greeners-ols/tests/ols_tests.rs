@@ -1,6 +1,7 @@
 use greeners_core::dataframe::DataFrame;
 use greeners_core::formula::Formula;
 use greeners_core::types::CovarianceType;
+use greeners_core::MissingPolicy;
 use greeners_ols::ols::OLS;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2};
@@ -256,3 +257,238 @@ fn test_ols_confidence_intervals() {
         assert!(param < ci_upper);
     }
 }
+
+#[test]
+fn test_ols_predict_df_uses_fit_time_design() {
+    let train = DataFrame::builder()
+        .add_column("y", vec![1.0, 2.1, 3.9, 1.2, 2.0, 4.1, 0.9, 2.2])
+        .add_column("x", vec![0.0, 1.0, 2.0, 0.5, 1.5, 2.5, 0.2, 1.1])
+        .add_categorical(
+            "g",
+            vec![
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "a".into(),
+                "b".into(),
+            ],
+        )
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x + C(g)").unwrap();
+    let result = OLS::from_formula(&formula, &train, CovarianceType::NonRobust).unwrap();
+
+    // In-sample predictions match the fitted values.
+    let (_, x) = train.to_design_matrix(&formula).unwrap();
+    let fitted = result.fitted_values(&x);
+    let pred = result.predict_df(&train).unwrap();
+    for i in 0..fitted.len() {
+        assert!((fitted[i] - pred[i]).abs() < 1e-10);
+    }
+
+    // Holdout with a single level still produces a full design.
+    let test = DataFrame::builder()
+        .add_column("x", vec![1.0])
+        .add_categorical("g", vec!["c".into()])
+        .build()
+        .unwrap();
+    let p = result.predict_df(&test).unwrap();
+    let expected = result.params[0] + result.params[1] + result.params[3];
+    assert!((p[0] - expected).abs() < 1e-10);
+
+    // Models fitted on raw matrices have no design to rebuild.
+    let raw = OLS::fit(&fitted, &x, CovarianceType::NonRobust).unwrap();
+    assert!(raw.predict_df(&test).is_err());
+}
+
+#[test]
+fn test_ols_from_formula_drops_missing_rows() {
+    let df = DataFrame::builder()
+        .add_column("y", vec![3.1, 4.9, f64::NAN, 9.2, 10.8, 13.1, 15.0])
+        .add_column("x", vec![1.0, 2.0, 3.0, f64::NAN, 5.0, 6.0, 7.0])
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x").unwrap();
+
+    let result = OLS::from_formula(&formula, &df, CovarianceType::HC1).unwrap();
+    assert_eq!(result.n_obs, 5);
+    assert!(result.params.iter().all(|b| b.is_finite()));
+
+    let rows = result.row_map.as_ref().unwrap();
+    assert_eq!(rows.dropped, vec![2, 3]);
+
+    // Residuals line up with the original DataFrame rows.
+    let x = rows.select_rows(&df.to_design_matrix(&formula).unwrap().1);
+    let y = rows.select(df.get("y").unwrap());
+    let resid = rows.expand(&result.residuals(&y, &x));
+    assert_eq!(resid.len(), 7);
+    assert!(resid[2].is_nan() && resid[3].is_nan());
+    let y_hat_6 = result.params[0] + result.params[1] * 7.0;
+    assert!((resid[6] - (15.0 - y_hat_6)).abs() < 1e-10);
+
+    // Cluster ids given for every DataFrame row are subset along with the data.
+    let clusters = vec![0, 0, 1, 1, 2, 2, 3];
+    assert!(OLS::from_formula(&formula, &df, CovarianceType::Clustered(clusters)).is_ok());
+
+    assert!(OLS::from_formula_with_missing(
+        &formula,
+        &df,
+        CovarianceType::NonRobust,
+        MissingPolicy::Raise
+    )
+    .is_err());
+}
+
+#[test]
+fn test_ols_fitted_model_generic_tooling() {
+    use greeners_core::{Bootstrap, CrossValidation, FittedModel, SummaryCol};
+
+    let n = 40;
+    let x1: Vec<f64> = (0..n).map(|i| i as f64 / 4.0).collect();
+    let y = Array1::from(
+        x1.iter()
+            .enumerate()
+            .map(|(i, v)| 1.0 + 2.0 * v + ((i * 7 % 5) as f64 - 2.0) * 0.1)
+            .collect::<Vec<_>>(),
+    );
+    let mut x = Array2::<f64>::ones((n, 2));
+    for i in 0..n {
+        x[[i, 1]] = x1[i];
+    }
+
+    let fitted = OLS::fit(&y, &x, CovarianceType::HC1).unwrap();
+    let model: &dyn FittedModel = &fitted;
+    assert_eq!(model.nobs(), n);
+    assert_eq!(model.params(), fitted.params);
+    let vcov = model.vcov().unwrap();
+    assert!((vcov[[1, 1]].sqrt() - fitted.std_errors[1]).abs() < 1e-12);
+    assert_eq!(model.aic(), Some(fitted.aic));
+    let resid = model.residuals(&y, &x).unwrap();
+    assert!((resid.sum()).abs() < 1e-8);
+
+    // Default estimator and a configured closure are both `Model`s.
+    let cv = CrossValidation::k_fold(&OLS, &y, &x, 5).unwrap();
+    assert_eq!(cv.fold_mse.len(), 5);
+    assert!(cv.mse < 0.1);
+    let hc3 = |y: &Array1<f64>, x: &Array2<f64>| OLS::fit(y, x, CovarianceType::HC3);
+    let boot = Bootstrap::pairs_bootstrap_model(&hc3, &y, &x, 50, None).unwrap();
+    assert_eq!(boot.ncols(), 2);
+    assert!(boot.nrows() > 0);
+
+    let table = SummaryCol::compare_fitted(&[("(1)", &fitted), ("(2)", &fit_slope_only(&y, &x))]);
+    assert_eq!(table.models.len(), 2);
+    assert_eq!(table.models[0].n_obs, n);
+    assert_eq!(table.models[0].aic, Some(fitted.aic));
+}
+
+fn fit_slope_only(y: &Array1<f64>, x: &Array2<f64>) -> greeners_ols::ols::OlsResult {
+    let x_slope = x.column(1).to_owned().insert_axis(ndarray::Axis(1));
+    OLS::fit(y, &x_slope, CovarianceType::NonRobust).unwrap()
+}
+
+#[test]
+fn test_ols_result_persists_with_design() {
+    use greeners_core::Persist;
+    use greeners_ols::ols::OlsResult;
+
+    let df = DataFrame::builder()
+        .add_column("y", vec![1.2, 2.1, 2.9, 4.2, 5.1, 5.8, 7.2, 7.9])
+        .add_column("x", vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0])
+        .add_column("g", vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0])
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x + C(g)").unwrap();
+    let fitted = OLS::from_formula(&formula, &df, CovarianceType::HC1).unwrap();
+    let expected = fitted.predict_df(&df).unwrap();
+
+    for restored in [
+        OlsResult::from_json(&fitted.to_json().unwrap()).unwrap(),
+        OlsResult::from_bytes(&fitted.to_bytes().unwrap()).unwrap(),
+    ] {
+        assert_eq!(restored.params, fitted.params);
+        assert_eq!(restored.std_errors, fitted.std_errors);
+        assert_eq!(restored.variable_names, fitted.variable_names);
+        let pred = restored.predict_df(&df).unwrap();
+        for (a, b) in pred.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_ols_wild_bootstrap_uses_model_clustering() {
+    let n = 60;
+    let x = Array2::from_shape_fn((n, 2), |(i, j)| {
+        if j == 0 {
+            1.0
+        } else {
+            ((i * 7) % 11) as f64 / 3.0
+        }
+    });
+    let y = Array1::from_shape_fn(n, |i| {
+        1.0 + 0.4 * x[[i, 1]] + ((i / 6) % 3) as f64 - 1.0 + ((i * 13) % 5) as f64 / 4.0
+    });
+    let clusters: Vec<usize> = (0..n).map(|i| i / 6).collect();
+    let config = greeners_core::WildBootstrapConfig {
+        n_bootstrap: 199,
+        ..Default::default()
+    };
+
+    let clustered = OLS::fit(&y, &x, CovarianceType::Clustered(clusters)).unwrap();
+    let boot = clustered.wild_bootstrap(&y, &x, 1, 0.0, &config).unwrap();
+    assert_eq!(boot.n_clusters, Some(10));
+    assert!((boot.t_stat - clustered.t_values[1]).abs() < 1e-8);
+    let (lo, hi) = boot.conf_interval.unwrap();
+    assert!(lo < clustered.params[1] && clustered.params[1] < hi);
+
+    let robust = OLS::fit(&y, &x, CovarianceType::HC1).unwrap();
+    let boot = robust.wild_bootstrap(&y, &x, 1, 0.0, &config).unwrap();
+    assert!(boot.n_clusters.is_none());
+    assert!((boot.t_stat - robust.t_values[1]).abs() < 1e-8);
+}
+
+#[test]
+fn test_wls_and_iv_formula_missing_policies() {
+    use greeners_ols::iv::IV;
+    use greeners_ols::wls::WLS;
+
+    let df = DataFrame::builder()
+        .add_column("y", vec![3.1, 4.9, f64::NAN, 9.2, 10.8, 13.1, 15.0, 16.7])
+        .add_column("x", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0])
+        .add_column("z", vec![1.2, 1.9, 3.1, 4.2, 4.8, 6.3, 6.9, 8.1])
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x").unwrap();
+
+    // A NaN weight drops its row just like a missing regressor
+    let weights = Array1::from(vec![1.0, 2.0, 1.0, 1.0, f64::NAN, 2.0, 1.0, 1.0]);
+    let wls = WLS::from_formula(&formula, &df, &weights, CovarianceType::HC1).unwrap();
+    assert_eq!(wls.n_obs, 6);
+    assert_eq!(wls.row_map.as_ref().unwrap().dropped, vec![2, 4]);
+    assert!(WLS::from_formula_with_missing(
+        &formula,
+        &df,
+        &weights,
+        CovarianceType::HC1,
+        MissingPolicy::Raise
+    )
+    .is_err());
+    // Weights for the kept rows only are no longer guessed at
+    let short = Array1::from(vec![1.0; 7]);
+    assert!(WLS::from_formula(&formula, &df, &short, CovarianceType::HC1).is_err());
+
+    let instruments = Formula::parse("x ~ z").unwrap();
+    let iv = IV::from_formula(&formula, &instruments, &df, CovarianceType::HC1).unwrap();
+    assert_eq!(iv.row_map.as_ref().unwrap().dropped, vec![2]);
+    assert!(IV::from_formula_with_missing(
+        &formula,
+        &instruments,
+        &df,
+        CovarianceType::HC1,
+        MissingPolicy::Raise
+    )
+    .is_err());
+}
@@ -17,7 +17,7 @@ fn test_quantile_perfect_fit_independent_of_tau() {
     let y = x.column(0).to_owned() * 1.0 + x.column(1).to_owned() * 2.0;
 
     for &tau in &[0.25, 0.5, 0.75] {
-        let result = QuantileReg::fit(&y, &x, tau, 0).unwrap();
+        let result = QuantileReg::fit(&y, &x, tau, 0, None).unwrap();
         approx_zero((result.params[0] - 1.0).abs(), 1e-8);
         approx_zero((result.params[1] - 2.0).abs(), 1e-8);
     }
@@ -33,8 +33,8 @@ fn test_quantile_scale_invariance() {
     .unwrap();
     let y = Array1::from(vec![0.5, 1.8, 3.1, 4.0, 5.2, 6.9]);
 
-    let base = QuantileReg::fit(&y, &x, 0.5, 0).unwrap();
-    let scaled = QuantileReg::fit(&y.mapv(|v| 2.0 * v), &x, 0.5, 0).unwrap();
+    let base = QuantileReg::fit(&y, &x, 0.5, 0, None).unwrap();
+    let scaled = QuantileReg::fit(&y.mapv(|v| 2.0 * v), &x, 0.5, 0, None).unwrap();
 
     for i in 0..base.params.len() {
         approx_zero((scaled.params[i] - 2.0 * base.params[i]).abs(), 1e-5);
@@ -52,8 +52,8 @@ fn test_quantile_translation_invariance() {
     .unwrap();
     let y = Array1::from(vec![0.5, 1.8, 3.1, 4.0, 5.2, 6.9]);
 
-    let base = QuantileReg::fit(&y, &x, 0.5, 0).unwrap();
-    let shifted = QuantileReg::fit(&y.mapv(|v| v + 10.0), &x, 0.5, 0).unwrap();
+    let base = QuantileReg::fit(&y, &x, 0.5, 0, None).unwrap();
+    let shifted = QuantileReg::fit(&y.mapv(|v| v + 10.0), &x, 0.5, 0, None).unwrap();
 
     approx_zero((shifted.params[0] - (base.params[0] + 10.0)).abs(), 1e-8);
     for i in 1..base.params.len() {
@@ -67,7 +67,7 @@ fn test_quantile_invalid_tau_fails() {
     let x = Array2::from_shape_vec((3, 1), vec![1.0, 1.0, 1.0]).unwrap();
     let y = Array1::from(vec![1.0, 2.0, 3.0]);
 
-    assert!(QuantileReg::fit(&y, &x, 0.0, 0).is_err());
-    assert!(QuantileReg::fit(&y, &x, 1.0, 0).is_err());
-    assert!(QuantileReg::fit(&y, &x, -0.1, 0).is_err());
+    assert!(QuantileReg::fit(&y, &x, 0.0, 0, None).is_err());
+    assert!(QuantileReg::fit(&y, &x, 1.0, 0, None).is_err());
+    assert!(QuantileReg::fit(&y, &x, -0.1, 0, None).is_err());
 }
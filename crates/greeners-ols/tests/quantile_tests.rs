@@ -17,7 +17,7 @@ fn test_quantile_median() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 50).unwrap(); // tau=0.5, n_boot=50
+    let result = QuantileReg::fit(&y, &x, 0.5, 50, None).unwrap(); // tau=0.5, n_boot=50
 
     assert_eq!(result.params.len(), 2);
     assert_eq!(result.tau, 0.5);
@@ -37,9 +37,9 @@ fn test_quantile_different_taus() {
     )
     .unwrap();
 
-    let result_25 = QuantileReg::fit(&y, &x, 0.25, 50).unwrap();
-    let result_50 = QuantileReg::fit(&y, &x, 0.50, 50).unwrap();
-    let result_75 = QuantileReg::fit(&y, &x, 0.75, 50).unwrap();
+    let result_25 = QuantileReg::fit(&y, &x, 0.25, 50, None).unwrap();
+    let result_50 = QuantileReg::fit(&y, &x, 0.50, 50, None).unwrap();
+    let result_75 = QuantileReg::fit(&y, &x, 0.75, 50, None).unwrap();
 
     // Different quantiles should give different estimates
     assert!((result_25.params[1] - result_50.params[1]).abs() > 1e-10);
@@ -66,7 +66,7 @@ fn test_quantile_from_formula() {
     let df = DataFrame::new(data).unwrap();
     let formula = Formula::parse("y ~ x1").unwrap();
 
-    let result = QuantileReg::from_formula(&formula, &df, 0.5, 50).unwrap();
+    let result = QuantileReg::from_formula(&formula, &df, 0.5, 50, None).unwrap();
 
     assert_eq!(result.params.len(), 2);
     assert!(result.variable_names.is_some());
@@ -80,10 +80,10 @@ fn test_quantile_invalid_tau() {
     let x = ndarray::Array2::from_shape_vec((3, 1), vec![1.0, 2.0, 3.0]).unwrap();
 
     // Tau must be in (0, 1)
-    let result_zero = QuantileReg::fit(&y, &x, 0.0, 50);
-    let result_one = QuantileReg::fit(&y, &x, 1.0, 50);
-    let result_negative = QuantileReg::fit(&y, &x, -0.5, 50);
-    let result_above_one = QuantileReg::fit(&y, &x, 1.5, 50);
+    let result_zero = QuantileReg::fit(&y, &x, 0.0, 50, None);
+    let result_one = QuantileReg::fit(&y, &x, 1.0, 50, None);
+    let result_negative = QuantileReg::fit(&y, &x, -0.5, 50, None);
+    let result_above_one = QuantileReg::fit(&y, &x, 1.5, 50, None);
 
     assert!(result_zero.is_err());
     assert!(result_one.is_err());
@@ -103,8 +103,8 @@ fn test_quantile_extreme_quantiles() {
     )
     .unwrap();
 
-    let result_low = QuantileReg::fit(&y, &x, 0.1, 50).unwrap();
-    let result_high = QuantileReg::fit(&y, &x, 0.9, 50).unwrap();
+    let result_low = QuantileReg::fit(&y, &x, 0.1, 50, None).unwrap();
+    let result_high = QuantileReg::fit(&y, &x, 0.9, 50, None).unwrap();
 
     // Both should converge
     assert!(result_low.params[0].is_finite());
@@ -122,7 +122,7 @@ fn test_quantile_standard_errors() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 100).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.5, 100, None).unwrap();
 
     // Standard errors should be positive and finite
     for se in result.std_errors.iter() {
@@ -142,7 +142,7 @@ fn test_quantile_t_values_and_p_values() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 100).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.5, 100, None).unwrap();
 
     // t-values should be finite
     for t in result.t_values.iter() {
@@ -164,7 +164,7 @@ fn test_quantile_convergence() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 50).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.5, 50, None).unwrap();
 
     // Should converge in reasonable iterations
     assert!(result.iterations > 0);
@@ -181,7 +181,7 @@ fn test_quantile_with_outliers() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 50).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.5, 50, None).unwrap();
 
     // Should still produce reasonable estimates despite outlier
     assert!(result.params[1].is_finite());
@@ -0,0 +1,389 @@
+use greeners_core::DataFrame;
+use greeners_ols::event_study::{
+    ControlGroup, EventAggregation, StaggeredConfig, StaggeredEventStudy, StaggeredMethod,
+};
+use ndarray::Array1;
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::Normal;
+
+const METHODS: [StaggeredMethod; 3] = [
+    StaggeredMethod::SunAbraham,
+    StaggeredMethod::CallawaySantAnna,
+    StaggeredMethod::Imputation,
+];
+
+struct Panel {
+    y: Array1<f64>,
+    unit: Vec<usize>,
+    time: Vec<i64>,
+    cohort: Vec<Option<i64>>,
+}
+
+/// Effect grows with time since treatment and differs by cohort.
+fn effect(g: i64, t: i64) -> f64 {
+    if t < g {
+        0.0
+    } else {
+        (t - g + 1) as f64 * (g as f64 / 2.0 - 1.0)
+    }
+}
+
+/// Staggered panel over periods 1..=10 with cohorts 4, 6, 8 and never
+/// treated units; `pretrend` adds a linear lead to treated units.
+fn make_panel(seed: u64, n_units: usize, noise: f64, pretrend: f64) -> Panel {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let cohorts = [Some(4), Some(6), Some(8), None];
+    let (mut y, mut unit, mut time, mut cohort) = (vec![], vec![], vec![], vec![]);
+    for i in 0..n_units {
+        let g = cohorts[i % 4];
+        let alpha = normal.sample(&mut rng);
+        for t in 1..=10 {
+            let lead = match g {
+                Some(g) if t < g => pretrend * (t - g) as f64,
+                _ => 0.0,
+            };
+            let tau = g.map_or(0.0, |g| effect(g, t));
+            y.push(alpha + 0.3 * t as f64 + tau + lead + noise * normal.sample(&mut rng));
+            unit.push(1000 + i);
+            time.push(t);
+            cohort.push(g);
+        }
+    }
+    Panel {
+        y: Array1::from(y),
+        unit,
+        time,
+        cohort,
+    }
+}
+
+#[test]
+fn test_estimators_recover_heterogeneous_effects_exactly() {
+    let p = make_panel(1, 40, 0.0, 0.0);
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &p.y,
+            &p.unit,
+            &p.time,
+            &p.cohort,
+            method,
+            &StaggeredConfig::default(),
+        )
+        .unwrap();
+        for cell in &fit.group_time {
+            let truth = effect(cell.cohort, cell.period);
+            assert!(
+                (cell.estimate - truth).abs() < 1e-6,
+                "{:?} ({}, {}): {} vs {}",
+                method,
+                cell.cohort,
+                cell.period,
+                cell.estimate,
+                truth
+            );
+        }
+        // Event time 0: every cohort contributes equally
+        let j = fit.labels.iter().position(|&e| e == 0).unwrap();
+        assert!((fit.estimates[j] - 2.0).abs() < 1e-6);
+        // Event time 4 is only observed for cohorts 4 and 6
+        let j = fit.labels.iter().position(|&e| e == 4).unwrap();
+        assert!((fit.estimates[j] - 5.0 * 1.5).abs() < 1e-6);
+        assert!(!fit.labels.contains(&-1) || method == StaggeredMethod::Imputation);
+        assert!((fit.overall_att - fit_overall_truth()).abs() < 1e-6);
+    }
+}
+
+/// Average effect over all treated cohort-period observations.
+fn fit_overall_truth() -> f64 {
+    let cells: Vec<f64> = [4, 6, 8]
+        .iter()
+        .flat_map(|&g| (g..=10).map(move |t| effect(g, t)))
+        .collect();
+    cells.iter().sum::<f64>() / cells.len() as f64
+}
+
+#[test]
+fn test_two_period_design_reduces_to_difference_in_differences() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let (mut y, mut unit, mut time, mut cohort) = (vec![], vec![], vec![], vec![]);
+    for i in 0..60 {
+        let g = if i < 25 { Some(2) } else { None };
+        for t in 1..=2 {
+            let tau = if g.is_some() && t == 2 { 1.0 } else { 0.0 };
+            y.push(normal.sample(&mut rng) + tau);
+            unit.push(i);
+            time.push(t);
+            cohort.push(g);
+        }
+    }
+    let diff: Vec<f64> = (0..60).map(|i| y[2 * i + 1] - y[2 * i]).collect();
+    let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+    let var = |v: &[f64]| {
+        let m = mean(v);
+        v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / v.len() as f64
+    };
+    let did = mean(&diff[..25]) - mean(&diff[25..]);
+    let y = Array1::from(y);
+
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &y,
+            &unit,
+            &time,
+            &cohort,
+            method,
+            &StaggeredConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(fit.labels, vec![0]);
+        assert!((fit.estimates[0] - did).abs() < 1e-8);
+        assert_eq!(fit.pretrend_stat, None);
+    }
+    // Influence-function variance of a difference of two means
+    let cs = StaggeredEventStudy::callaway_santanna(
+        &y,
+        &unit,
+        &time,
+        &cohort,
+        &StaggeredConfig::default(),
+    )
+    .unwrap();
+    let se = (var(&diff[..25]) / 25.0 + var(&diff[25..]) / 35.0).sqrt();
+    assert!((cs.std_errors[0] - se).abs() < 1e-10);
+}
+
+#[test]
+fn test_pretrend_test_and_inference() {
+    let clean = make_panel(3, 400, 1.0, 0.0);
+    let config = StaggeredConfig {
+        min_event_time: Some(-4),
+        max_event_time: Some(3),
+        ..StaggeredConfig::default()
+    };
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &clean.y,
+            &clean.unit,
+            &clean.time,
+            &clean.cohort,
+            method,
+            &config,
+        )
+        .unwrap();
+        assert!(*fit.labels.first().unwrap() >= -4 && *fit.labels.last().unwrap() == 3);
+        assert!(fit.pretrend_p.unwrap() > 0.001, "{:?}", method);
+        for (j, &e) in fit.labels.iter().enumerate() {
+            // Cohort 8 is not observed three periods after treatment
+            let truth = match e {
+                e if e < 0 => 0.0,
+                3 => 6.0,
+                e => 2.0 * (e + 1) as f64,
+            };
+            assert!(fit.conf_lower[j] - 0.3 < truth && truth < fit.conf_upper[j] + 0.3);
+            assert!(fit.std_errors[j] > 0.0 && fit.std_errors[j] < 0.5);
+        }
+    }
+
+    let violated = make_panel(3, 400, 1.0, 0.5);
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &violated.y,
+            &violated.unit,
+            &violated.time,
+            &violated.cohort,
+            method,
+            &config,
+        )
+        .unwrap();
+        assert!(fit.pretrend_p.unwrap() < 1e-6, "{:?}", method);
+    }
+}
+
+#[test]
+fn test_pretrend_df_is_rank_of_lead_covariance() {
+    // Two treated and two never-treated units: five leads, but the unit
+    // scores span fewer directions
+    let mut rng = StdRng::seed_from_u64(6);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let (mut y, mut unit, mut time, mut cohort) = (vec![], vec![], vec![], vec![]);
+    for i in 0..4 {
+        for t in 1..=8 {
+            y.push(normal.sample(&mut rng));
+            unit.push(i);
+            time.push(t);
+            cohort.push(if i < 2 { Some(7) } else { None });
+        }
+    }
+    let y = Array1::from(y);
+    let fit = StaggeredEventStudy::callaway_santanna(
+        &y,
+        &unit,
+        &time,
+        &cohort,
+        &StaggeredConfig::default(),
+    )
+    .unwrap();
+    let n_leads = fit.labels.iter().filter(|&&e| e < 0).count();
+    assert_eq!(n_leads, 5);
+    assert!(fit.pretrend_df >= 1 && fit.pretrend_df < n_leads);
+    assert!(fit.pretrend_p.unwrap().is_finite());
+
+    // No variation at all: no pre-trend test and a finite overall p-value
+    let flat = Array1::zeros(y.len());
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &flat,
+            &unit,
+            &time,
+            &cohort,
+            method,
+            &StaggeredConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(fit.overall_se, 0.0);
+        assert_eq!(fit.overall_p, 1.0, "{:?}", method);
+        assert_eq!(fit.pretrend_df, 0);
+        assert_eq!(fit.pretrend_stat, None);
+    }
+}
+
+#[test]
+fn test_callaway_santanna_aggregations_and_control_groups() {
+    let p = make_panel(4, 200, 0.5, 0.0);
+    let fit = |aggregation, control_group| {
+        StaggeredEventStudy::callaway_santanna(
+            &p.y,
+            &p.unit,
+            &p.time,
+            &p.cohort,
+            &StaggeredConfig {
+                aggregation,
+                control_group,
+                ..StaggeredConfig::default()
+            },
+        )
+        .unwrap()
+    };
+    let group = fit(EventAggregation::Group, ControlGroup::NeverTreated);
+    assert_eq!(group.labels, vec![4, 6, 8]);
+    for (j, &g) in group.labels.iter().enumerate() {
+        let truth = (g..=10).map(|t| effect(g, t)).sum::<f64>() / (11 - g) as f64;
+        assert!((group.estimates[j] - truth).abs() < 4.0 * group.std_errors[j]);
+    }
+    let calendar = fit(EventAggregation::Calendar, ControlGroup::NeverTreated);
+    assert_eq!(calendar.labels, (4..=10).collect::<Vec<_>>());
+    assert_eq!(calendar.n_treated[0], 50);
+    assert_eq!(calendar.n_treated[6], 150);
+
+    // Not-yet-treated units enlarge the comparison group
+    let never = fit(EventAggregation::EventTime, ControlGroup::NeverTreated);
+    let not_yet = fit(EventAggregation::EventTime, ControlGroup::NotYetTreated);
+    assert_eq!(not_yet.control_group, ControlGroup::NotYetTreated);
+    assert!((never.overall_att - not_yet.overall_att).abs() > 1e-8);
+    assert!(not_yet.overall_se < never.overall_se);
+    assert!((not_yet.overall_att - fit_overall_truth()).abs() < 4.0 * not_yet.overall_se);
+}
+
+#[test]
+fn test_dataframe_interface_and_plot_data() {
+    let p = make_panel(5, 40, 0.5, 0.0);
+    let mut y = p.y.to_vec();
+    y[3] = f64::NAN;
+    // Unit 39 is treated from the start
+    let cohort: Vec<i64> = p
+        .cohort
+        .iter()
+        .zip(&p.unit)
+        .map(|(g, &u)| if u == 1039 { 1 } else { g.unwrap_or(0) })
+        .collect();
+    let df = DataFrame::builder()
+        .add_column("y", y)
+        .add_int("id", p.unit.iter().map(|&u| u as i64).collect())
+        .add_int("year", p.time.clone())
+        .add_int("first", cohort.clone())
+        .build()
+        .unwrap();
+    let fit = StaggeredEventStudy::from_dataframe(
+        &df,
+        "y",
+        "id",
+        "year",
+        "first",
+        StaggeredMethod::Imputation,
+        &StaggeredConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(fit.n_always_treated, 1);
+    assert_eq!(fit.n_obs, 400 - 1 - 10);
+    assert_eq!(fit.n_cohorts, 3);
+    assert_eq!(fit.n_never_treated, 9);
+    assert!(format!("{}", fit).contains("Imputation"));
+
+    let sa = StaggeredEventStudy::from_dataframe(
+        &df,
+        "y",
+        "id",
+        "year",
+        "first",
+        StaggeredMethod::SunAbraham,
+        &StaggeredConfig::default(),
+    )
+    .unwrap();
+    let plot = sa.plot_data().unwrap();
+    assert_eq!(plot.n_rows(), sa.labels.len() + 1);
+    let e = plot.get_int("event_time").unwrap();
+    let r = e.iter().position(|&v| v == -1).unwrap();
+    assert_eq!(plot.get("estimate").unwrap()[r], 0.0);
+    assert!(e.to_vec().windows(2).all(|w| w[0] < w[1]));
+
+    // A unit with two first-treatment periods
+    let mut bad = cohort;
+    bad[385] = 6;
+    let df = DataFrame::builder()
+        .add_column("y", p.y.to_vec())
+        .add_int("id", p.unit.iter().map(|&u| u as i64).collect())
+        .add_int("year", p.time.clone())
+        .add_int("first", bad)
+        .build()
+        .unwrap();
+    assert!(StaggeredEventStudy::from_dataframe(
+        &df,
+        "y",
+        "id",
+        "year",
+        "first",
+        StaggeredMethod::CallawaySantAnna,
+        &StaggeredConfig::default(),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_last_cohort_serves_as_control_without_never_treated() {
+    let p = make_panel(6, 40, 0.0, 0.0);
+    let keep: Vec<usize> = (0..p.y.len()).filter(|&i| p.cohort[i].is_some()).collect();
+    let y: Array1<f64> = keep.iter().map(|&i| p.y[i]).collect();
+    let unit: Vec<usize> = keep.iter().map(|&i| p.unit[i]).collect();
+    let time: Vec<i64> = keep.iter().map(|&i| p.time[i]).collect();
+    let cohort: Vec<Option<i64>> = keep.iter().map(|&i| p.cohort[i]).collect();
+    for method in METHODS {
+        let fit = StaggeredEventStudy::fit(
+            &y,
+            &unit,
+            &time,
+            &cohort,
+            method,
+            &StaggeredConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(fit.n_cohorts, 2);
+        assert_eq!(fit.n_never_treated, 10);
+        assert!(fit.group_time.iter().all(|c| c.period < 8));
+        for cell in &fit.group_time {
+            assert!((cell.estimate - effect(cell.cohort, cell.period)).abs() < 1e-6);
+        }
+    }
+}
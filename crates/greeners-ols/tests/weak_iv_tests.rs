@@ -0,0 +1,175 @@
+use greeners_core::types::CovarianceType;
+use greeners_ols::iv::IV;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+
+/// y = 1 + 2·x + u with x endogenous and `k` excluded instruments whose
+/// first-stage coefficients all equal `strength`.
+fn design(n: usize, k: usize, strength: f64, seed: u64) -> (Array1<f64>, Array2<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut x = Array2::<f64>::ones((n, 2));
+    let mut z = Array2::<f64>::ones((n, k + 1));
+    let mut y = Array1::<f64>::zeros(n);
+    for i in 0..n {
+        let common: f64 = normal.sample(&mut rng);
+        let mut xi = 0.8 * common + normal.sample(&mut rng);
+        for j in 0..k {
+            let zij: f64 = normal.sample(&mut rng);
+            z[[i, j + 1]] = zij;
+            xi += strength * zij;
+        }
+        x[[i, 1]] = xi;
+        y[i] = 1.0 + 2.0 * xi + common + 0.5 * normal.sample(&mut rng);
+    }
+    (y, x, z)
+}
+
+fn contains(set: &[(f64, f64)], value: f64) -> bool {
+    set.iter().any(|&(lo, hi)| lo <= value && value <= hi)
+}
+
+#[test]
+fn test_first_stage_single_endogenous_matches_textbook_f() {
+    let (_, x, z) = design(400, 3, 0.4, 11);
+    let fs = IV::first_stage(&x, &z, CovarianceType::NonRobust, None).unwrap();
+
+    assert_eq!(fs.endogenous_cols, vec![1]);
+    assert_eq!(fs.excluded_cols, vec![1, 2, 3]);
+    assert_eq!(fs.n_exogenous, 1);
+
+    // Homoskedastic first-stage F from the restricted/unrestricted R²
+    let r2 = fs.partial_r_squared[0];
+    let df = (400 - 4) as f64;
+    let f_textbook = (r2 / 3.0) / ((1.0 - r2) / df);
+    assert!((fs.f_stats[0] - f_textbook).abs() < 1e-8 * f_textbook);
+    // With one endogenous regressor CD, SW and non-robust KP F coincide
+    assert!((fs.cragg_donald_f - f_textbook).abs() < 1e-8 * f_textbook);
+    assert!((fs.sw_f_stats[0] - f_textbook).abs() < 1e-8 * f_textbook);
+    assert!((fs.kp_f - fs.cragg_donald_f).abs() < 1e-6 * f_textbook);
+    // Effective F equals the non-robust F under homoskedasticity
+    assert!((fs.effective_f.unwrap() - f_textbook).abs() < 1e-8 * f_textbook);
+
+    assert!(fs.f_stats[0] > 10.0);
+    assert!(fs.kp_rk_pvalue < 1e-6);
+    assert_eq!(fs.stock_yogo.relative_bias[1], (0.10, 9.08));
+    assert_eq!(fs.stock_yogo.size[0], (0.10, 22.30));
+    assert!(fs.effective_f_critical.unwrap() > 0.0);
+    assert!(format!("{}", fs).contains("Cragg-Donald"));
+}
+
+#[test]
+fn test_first_stage_robust_and_multiple_endogenous() {
+    let n = 300;
+    let (_, x1, z1) = design(n, 4, 0.5, 5);
+    // Second endogenous regressor driven by the last two instruments
+    let mut x = Array2::<f64>::ones((n, 3));
+    for i in 0..n {
+        x[[i, 1]] = x1[[i, 1]];
+        x[[i, 2]] = z1[[i, 3]] - z1[[i, 4]] + 0.3 * x1[[i, 1]].sin();
+    }
+
+    let fs = IV::first_stage(&x, &z1, CovarianceType::HC1, None).unwrap();
+    assert_eq!(fs.endogenous_cols, vec![1, 2]);
+    assert_eq!(fs.sw_f_stats.len(), 2);
+    assert!(fs.effective_f.is_none());
+    assert!(fs.kp_rk_stat > 0.0 && fs.kp_rk_pvalue < 0.01);
+    assert!(fs.sw_f_stats.iter().all(|&f| f.is_finite() && f > 0.0));
+    assert_eq!(fs.stock_yogo.size[0], (0.10, 16.87));
+    assert_eq!(fs.stock_yogo.relative_bias[0], (0.05, 11.04));
+
+    let ids: Vec<usize> = (0..n).map(|i| i % 30).collect();
+    let clustered = IV::first_stage(&x, &z1, CovarianceType::Clustered(ids), None).unwrap();
+    assert!(clustered.f_stats.iter().all(|f| f.is_finite()));
+
+    // Nothing to instrument
+    assert!(IV::first_stage(&z1, &z1, CovarianceType::NonRobust, None).is_err());
+}
+
+#[test]
+fn test_anderson_rubin_and_clr_sets_strong_instruments() {
+    let (y, x, z) = design(500, 3, 0.6, 21);
+    let res = IV::weak_iv_robust(&y, &x, &z, 0.05, None).unwrap();
+
+    assert!(
+        (res.beta_2sls
+            - IV::fit(&y, &x, &z, CovarianceType::NonRobust)
+                .unwrap()
+                .params[1])
+            .abs()
+            < 1e-8
+    );
+    assert_eq!(res.ar_set.len(), 1);
+    assert_eq!(res.clr_set.len(), 1);
+    assert!(contains(&res.ar_set, 2.0));
+    assert!(contains(&res.clr_set, 2.0));
+    assert!(res.ar_set[0].0.is_finite() && res.ar_set[0].1.is_finite());
+
+    // Set boundaries sit exactly at the critical value of the AR test
+    let (_, p_lo) = res.ar_test(res.ar_set[0].0);
+    assert!((p_lo - 0.05).abs() < 1e-6);
+    let (_, p_clr) = res.clr_test(res.clr_set[0].1);
+    assert!((p_clr - 0.05).abs() < 1e-4);
+
+    // β = 0 is firmly rejected by both tests
+    assert!(res.ar_test(0.0).1 < 1e-6);
+    assert!(res.clr_test(0.0).1 < 1e-6);
+    assert!(format!("{}", res).contains("AR set"));
+}
+
+#[test]
+fn test_weak_instruments_give_unbounded_ar_set() {
+    let (y, x, z) = design(200, 1, 0.0, 3);
+    let res = IV::weak_iv_robust(&y, &x, &z, 0.05, None).unwrap();
+    // With one instrument CLR reduces to AR
+    let (ar, _) = res.ar_test(1.5);
+    let (clr, clr_p) = res.clr_test(1.5);
+    assert!((ar - clr).abs() < 1e-8 * ar.max(1.0));
+    let chi2 = ChiSquared::new(1.0).unwrap();
+    assert!((clr_p - (1.0 - chi2.cdf(clr))).abs() < 1e-12);
+    assert!(res
+        .ar_set
+        .iter()
+        .any(|&(lo, hi)| lo.is_infinite() || hi.is_infinite()));
+}
+
+#[test]
+fn test_weak_iv_robust_requires_single_endogenous() {
+    let n = 50;
+    let (y, x, z) = design(n, 3, 0.5, 9);
+    let mut x2 = Array2::<f64>::ones((n, 3));
+    for i in 0..n {
+        x2[[i, 1]] = x[[i, 1]];
+        x2[[i, 2]] = x[[i, 1]].powi(2);
+    }
+    assert!(IV::weak_iv_robust(&y, &x2, &z, 0.05, None).is_err());
+    assert!(IV::weak_iv_robust(&y, &x, &z, 1.5, None).is_err());
+}
+
+#[test]
+fn test_first_stage_bias_reduced_cluster_matches_ols() {
+    use greeners_ols::ols::OLS;
+
+    let n = 120;
+    let (_, x, z) = design(n, 1, 0.5, 9);
+    let ids: Vec<usize> = (0..n).map(|i| i / 8).collect();
+    let x_endog = x.column(1).to_owned();
+    for cov in [
+        CovarianceType::CR2(ids.clone()),
+        CovarianceType::CR3(ids.clone()),
+    ] {
+        // One instrument: the first-stage F is the squared t of the OLS fit
+        let fs = IV::first_stage(&x, &z, cov.clone(), None).unwrap();
+        let ols = OLS::fit(&x_endog, &z, cov).unwrap();
+        let t2 = (ols.params[1] / ols.std_errors[1]).powi(2);
+        assert!(
+            (fs.f_stats[0] - t2).abs() < 1e-8 * t2,
+            "{} vs {}",
+            fs.f_stats[0],
+            t2
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of the Arellano-Bond estimator (Diff-GMM).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArellanoBondResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -504,7 +505,7 @@ fn compute_m_stats(
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// System estimator result GMM (Blundell-Bond 1998).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemGmmResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
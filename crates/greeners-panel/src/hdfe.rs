@@ -0,0 +1,922 @@
+//! Linear models with several high-dimensional fixed effects (`reghdfe`,
+//! `fixest::feols`).
+//!
+//! Every factor set is absorbed by the method of alternating projections:
+//! each column is demeaned by one factor after the other until the group
+//! means of all factors vanish. Singleton groups are dropped iteratively,
+//! the degrees of freedom used by the fixed effects account for redundant
+//! levels (exactly for the first two factors through the connected
+//! components of their bipartite graph, conservatively by one level for
+//! each further factor) and factors nested within the clusters are not
+//! charged to the cluster-robust small-sample correction.
+
+use greeners_core::{
+    CovarianceType, DataFrame, Formula, GreenersError, InferenceType, RowMap, Sandwich,
+};
+use greeners_ols::iv::IV;
+use greeners_ols::ols::{OlsResult, OLS};
+use ndarray::{Array1, Array2, Axis};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Settings for [`HDFE`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HdfeConfig {
+    /// Convergence tolerance on the largest group mean removed in a sweep,
+    /// relative to the scale of the column
+    pub tol: f64,
+    /// Maximum number of sweeps over all factors
+    pub max_iter: usize,
+    /// Drop observations that are alone in a level of some factor
+    pub drop_singletons: bool,
+    /// Recover the fixed-effect estimates after the fit
+    pub save_fe: bool,
+}
+
+impl Default for HdfeConfig {
+    fn default() -> Self {
+        HdfeConfig {
+            tol: 1e-8,
+            max_iter: 10_000,
+            drop_singletons: true,
+            save_fe: false,
+        }
+    }
+}
+
+/// Result of an [`HDFE`] fit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HdfeResult {
+    pub params: Array1<f64>,
+    pub std_errors: Array1<f64>,
+    pub t_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub conf_lower: Array1<f64>,
+    pub conf_upper: Array1<f64>,
+    pub cov_matrix: Array2<f64>,
+    /// R² of the full model, fixed effects included
+    pub r_squared: f64,
+    /// R² of the regression on the demeaned data
+    pub r_squared_within: f64,
+    pub sigma: f64,
+    pub n_obs: usize,
+    /// Observations dropped as singletons
+    pub n_singletons: usize,
+    /// n - k - df_absorbed
+    pub df_resid: usize,
+    /// Degrees of freedom used by the fixed effects
+    pub df_absorbed: usize,
+    /// Names of the absorbed factors (`industry^year` for interactions)
+    pub fe_names: Vec<String>,
+    /// Number of levels of each factor in the estimation sample
+    pub fe_levels: Vec<usize>,
+    /// Levels of each factor that are redundant given the previous factors
+    pub fe_redundant: Vec<usize>,
+    /// Whether each factor is nested within the clusters
+    pub fe_nested: Vec<bool>,
+    /// Level of each factor for every estimation observation
+    pub fe_codes: Vec<Vec<usize>>,
+    /// Estimated fixed effects, indexed by level (`save_fe` only). They are
+    /// identified up to normalisation across factors; their sum for an
+    /// observation is unique.
+    pub fixed_effects: Option<Vec<Array1<f64>>>,
+    /// Largest number of sweeps used to demean a column
+    pub n_iter: usize,
+    pub converged: bool,
+    /// Whether the model was estimated by 2SLS
+    pub iv: bool,
+    pub cov_type: CovarianceType,
+    pub inference_type: InferenceType,
+    pub variable_names: Option<Vec<String>>,
+    /// (position, name) of regressors dropped as collinear with the fixed effects
+    pub omitted_vars: Vec<(usize, String)>,
+    /// Rows of the input used in estimation (missing values and singletons dropped)
+    pub row_map: RowMap,
+}
+
+impl HdfeResult {
+    /// Sum of the estimated fixed effects for every estimation observation.
+    pub fn fitted_fe(&self) -> Option<Array1<f64>> {
+        let fe = self.fixed_effects.as_ref()?;
+        let mut total = Array1::<f64>::zeros(self.n_obs);
+        for (alpha, codes) in fe.iter().zip(&self.fe_codes) {
+            for (i, &c) in codes.iter().enumerate() {
+                total[i] += alpha[c];
+            }
+        }
+        Some(total)
+    }
+}
+
+impl fmt::Display for HdfeResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stat_label = match self.inference_type {
+            InferenceType::StudentT => "t",
+            InferenceType::Normal => "z",
+        };
+        let title = if self.iv {
+            " HDFE IV (2SLS) Regression "
+        } else {
+            " HDFE Linear Regression "
+        };
+        writeln!(f, "\n{:=^78}", title)?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "No. Observations:", self.n_obs, "R-squared:", self.r_squared
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "Singletons:", self.n_singletons, "Within R-sq:", self.r_squared_within
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Df Residuals:", self.df_resid, "Df Absorbed:", self.df_absorbed
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4e}",
+            "Iterations:", self.n_iter, "Sigma:", self.sigma
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15}",
+            "Covariance:",
+            Sandwich::describe(&self.cov_type)
+        )?;
+
+        writeln!(f, "\n{:-^78}", " Absorbed Fixed Effects ")?;
+        writeln!(
+            f,
+            "{:<30} | {:>10} | {:>10} | {:>8}",
+            "Factor", "Levels", "Redundant", "Nested"
+        )?;
+        for (j, name) in self.fe_names.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<30} | {:>10} | {:>10} | {:>8}",
+                name,
+                self.fe_levels[j],
+                self.fe_redundant[j],
+                if self.fe_nested[j] { "yes" } else { "no" }
+            )?;
+        }
+
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<10} | {:>10} | {:>10} | {:>8} | {:>8} | {:>8}",
+            "Variable",
+            "coef",
+            "std err",
+            stat_label,
+            format!("P>|{}|", stat_label),
+            "[0.025"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for i in 0..self.params.len() {
+            let name = self
+                .variable_names
+                .as_ref()
+                .and_then(|v| v.get(i).cloned())
+                .unwrap_or_else(|| format!("x{}", i));
+            writeln!(
+                f,
+                "{:<10} | {:>10.4} | {:>10.4} | {:>8.3} | {:>8.3} | {:>8.3}",
+                name,
+                self.params[i],
+                self.std_errors[i],
+                self.t_values[i],
+                self.p_values[i],
+                self.conf_lower[i]
+            )?;
+        }
+        if !self.omitted_vars.is_empty() {
+            let names: Vec<&str> = self.omitted_vars.iter().map(|(_, n)| n.as_str()).collect();
+            writeln!(f, "Omitted (collinear): {}", names.join(", "))?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Regression with any number of absorbed fixed effects.
+pub struct HDFE;
+
+impl HDFE {
+    /// Estimates `y ~ x1 + x2 | fe1 + fe2^fe3` by OLS, or
+    /// `y ~ x1 | fe1 + fe2 | x_endog ~ z1 + z2` by 2SLS.
+    ///
+    /// Factors are separated by `+`; `a^b` absorbs the interaction of `a`
+    /// and `b` (e.g. industry-year effects). Factor columns may be of any
+    /// type. The intercept is absorbed. Cluster ids, time ids or coordinates
+    /// in `cov_type` must be aligned with the rows of `data`.
+    ///
+    /// # Examples
+    /// ```
+    /// use greeners_core::{CovarianceType, DataFrame};
+    /// use greeners_panel::{HdfeConfig, HDFE};
+    ///
+    /// let df = DataFrame::builder()
+    ///     .add_column("y", vec![1.0, 2.5, 2.0, 4.1, 3.2, 5.9, 4.0, 7.2])
+    ///     .add_column("x", vec![0.0, 1.0, 0.5, 1.5, 1.0, 2.5, 1.2, 3.1])
+    ///     .add_int("firm", vec![1, 1, 2, 2, 3, 3, 4, 4])
+    ///     .add_int("year", vec![1, 2, 1, 2, 1, 2, 1, 2])
+    ///     .build()
+    ///     .unwrap();
+    /// let fit = HDFE::from_formula(
+    ///     "y ~ x | firm + year",
+    ///     &df,
+    ///     CovarianceType::NonRobust,
+    ///     &HdfeConfig::default(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(fit.fe_levels, vec![4, 2]);
+    /// assert_eq!(fit.df_absorbed, 5);
+    /// ```
+    pub fn from_formula(
+        formula: &str,
+        data: &DataFrame,
+        cov_type: CovarianceType,
+        config: &HdfeConfig,
+    ) -> Result<HdfeResult, GreenersError> {
+        let parts: Vec<&str> = formula.split('|').map(str::trim).collect();
+        if parts.len() < 2 || parts.len() > 3 || parts[1].is_empty() {
+            return Err(GreenersError::FormulaError(format!(
+                "Expected 'y ~ x | fe1 + fe2' or 'y ~ x | fe | endog ~ z', got: '{}'",
+                formula
+            )));
+        }
+        let mut main = Formula::parse(parts[0])?;
+        main.intercept = false;
+
+        // Instrumented regressors follow the exogenous ones in X
+        let (x_formula, z_formula) = match parts.get(2) {
+            Some(iv) => {
+                let iv = Formula::parse(iv)?;
+                let endog: Vec<String> = iv
+                    .dependent
+                    .split('+')
+                    .map(|s| s.trim().to_string())
+                    .collect();
+                let mut x_terms = main.independents.clone();
+                x_terms.extend(endog);
+                let mut z_terms = main.independents.clone();
+                z_terms.extend(iv.independents.iter().cloned());
+                (
+                    Formula {
+                        dependent: main.dependent.clone(),
+                        independents: x_terms,
+                        intercept: false,
+                    },
+                    Some(Formula {
+                        dependent: main.dependent.clone(),
+                        independents: z_terms,
+                        intercept: false,
+                    }),
+                )
+            }
+            None => (main, None),
+        };
+
+        let (fe_names, codes, mut dropped) = absorbed_factors(parts[1], data)?;
+        dropped.extend(data.design_info(&x_formula)?.missing_rows(data)?);
+        if let Some(zf) = &z_formula {
+            dropped.extend(data.design_info(zf)?.missing_rows(data)?);
+        }
+        let rows = RowMap::new(data.n_rows(), dropped);
+        let subset = if rows.dropped.is_empty() {
+            data.clone()
+        } else {
+            data.drop_rows(&rows.dropped)?
+        };
+
+        let (y, x) = subset.to_design_matrix(&x_formula)?;
+        let z = match &z_formula {
+            Some(zf) => Some(subset.to_design_matrix(zf)?.1),
+            None => None,
+        };
+        let names = subset.formula_var_names(&x_formula)?;
+        let codes: Vec<Vec<usize>> = codes
+            .iter()
+            .map(|c| rows.select_slice(c))
+            .collect::<Result<_, _>>()?;
+        let cov_type = rows.select_cov_type(cov_type);
+
+        let mut result = Self::estimate(
+            &y,
+            &x,
+            z.as_ref(),
+            &codes,
+            cov_type,
+            config,
+            Some(names),
+            Some(fe_names),
+        )?;
+
+        // Map the singleton rows back to the rows of `data`
+        let kept = rows.kept();
+        let mut all_dropped = rows.dropped.clone();
+        all_dropped.extend(result.row_map.dropped.iter().map(|&i| kept[i]));
+        result.row_map = RowMap::new(data.n_rows(), all_dropped);
+        Ok(result)
+    }
+
+    /// Fits `y` on `x` (no intercept column) absorbing the factors in `fe`.
+    ///
+    /// Each element of `fe` holds one factor with an arbitrary integer code
+    /// per observation.
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        fe: &[Vec<usize>],
+        cov_type: CovarianceType,
+        config: &HdfeConfig,
+    ) -> Result<HdfeResult, GreenersError> {
+        Self::estimate(y, x, None, fe, cov_type, config, None, None)
+    }
+
+    /// 2SLS with absorbed factors. `x` holds every regressor (endogenous
+    /// included) and `z` the exogenous regressors plus the excluded
+    /// instruments, both without an intercept column.
+    pub fn fit_iv(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        z: &Array2<f64>,
+        fe: &[Vec<usize>],
+        cov_type: CovarianceType,
+        config: &HdfeConfig,
+    ) -> Result<HdfeResult, GreenersError> {
+        Self::estimate(y, x, Some(z), fe, cov_type, config, None, None)
+    }
+
+    /// Residualises every column of `data` on all factors in `fe` by
+    /// alternating projections.
+    ///
+    /// Returns the demeaned columns, the largest number of sweeps used and
+    /// whether every column converged.
+    pub fn demean(
+        data: &Array2<f64>,
+        fe: &[Vec<usize>],
+        config: &HdfeConfig,
+    ) -> Result<(Array2<f64>, usize, bool), GreenersError> {
+        check_factors(fe, data.nrows())?;
+        let factors: Vec<Factor> = fe.iter().map(|c| Factor::new(c)).collect();
+        Ok(demean_columns(data, &factors, config))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn estimate(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        z: Option<&Array2<f64>>,
+        fe: &[Vec<usize>],
+        cov_type: CovarianceType,
+        config: &HdfeConfig,
+        variable_names: Option<Vec<String>>,
+        fe_names: Option<Vec<String>>,
+    ) -> Result<HdfeResult, GreenersError> {
+        let n_input = y.len();
+        if x.nrows() != n_input || z.is_some_and(|z| z.nrows() != n_input) {
+            return Err(GreenersError::ShapeMismatch(
+                "y, X and Z must have the same number of rows".into(),
+            ));
+        }
+        check_factors(fe, n_input)?;
+        if y.iter().any(|v| !v.is_finite())
+            || x.iter().any(|v| !v.is_finite())
+            || z.is_some_and(|z| z.iter().any(|v| !v.is_finite()))
+        {
+            return Err(GreenersError::InvalidOperation(
+                "Input data contains NaN or Inf values".into(),
+            ));
+        }
+
+        // 1. Singletons
+        let singletons = if config.drop_singletons {
+            find_singletons(fe, n_input)
+        } else {
+            Vec::new()
+        };
+        let rows = RowMap::new(n_input, singletons);
+        let (y, x, z, fe, cov_type) = if rows.dropped.is_empty() {
+            (y.clone(), x.clone(), z.cloned(), fe.to_vec(), cov_type)
+        } else {
+            (
+                rows.select(y),
+                rows.select_rows(x),
+                z.map(|z| rows.select_rows(z)),
+                fe.iter()
+                    .map(|c| rows.select_slice(c))
+                    .collect::<Result<_, _>>()?,
+                rows.select_cov_type(cov_type),
+            )
+        };
+        let n = y.len();
+        let factors: Vec<Factor> = fe.iter().map(|c| Factor::new(c)).collect();
+
+        // 2. Degrees of freedom of the fixed effects
+        let fe_levels: Vec<usize> = factors.iter().map(|f| f.n_levels).collect();
+        let fe_redundant = redundant_levels(&factors);
+        let fe_nested: Vec<bool> = factors
+            .iter()
+            .map(|f| match &cov_type {
+                CovarianceType::Clustered(ids)
+                | CovarianceType::CR2(ids)
+                | CovarianceType::CR3(ids) => f.nested_in(ids),
+                CovarianceType::ClusteredTwoWay(a, b) => f.nested_in(a) || f.nested_in(b),
+                _ => false,
+            })
+            .collect();
+        let absorbed = |skip_nested: bool| -> usize {
+            (0..factors.len())
+                .filter(|&j| !(skip_nested && fe_nested[j]))
+                .map(|j| fe_levels[j] - fe_redundant[j])
+                .sum()
+        };
+        let df_absorbed = absorbed(false);
+
+        // 3. Demean y, X and Z jointly
+        let k = x.ncols();
+        let l = z.as_ref().map_or(0, |z| z.ncols());
+        let mut stacked = Array2::<f64>::zeros((n, 1 + k + l));
+        stacked.column_mut(0).assign(&y);
+        stacked.slice_mut(ndarray::s![.., 1..1 + k]).assign(&x);
+        if let Some(z) = &z {
+            stacked.slice_mut(ndarray::s![.., 1 + k..]).assign(z);
+        }
+        let (demeaned, n_iter, converged) = demean_columns(&stacked, &factors, config);
+        let y_dm = demeaned.column(0).to_owned();
+        let x_dm = demeaned.slice(ndarray::s![.., 1..1 + k]).to_owned();
+        let z_dm = demeaned.slice(ndarray::s![.., 1 + k..]).to_owned();
+
+        // Regressors fully explained by the fixed effects
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|j| format!("x{}", j)).collect());
+        let mut keep = Vec::new();
+        let mut omitted_vars = Vec::new();
+        for (j, name) in names.iter().enumerate().take(k) {
+            if fully_absorbed(&x, &x_dm, j) {
+                omitted_vars.push((j, name.clone()));
+            } else {
+                keep.push(j);
+            }
+        }
+        if keep.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "All regressors are absorbed by the fixed effects".into(),
+            ));
+        }
+        let x_dm = x_dm.select(Axis(1), &keep);
+        // Absorbed exogenous regressors and instruments are zero columns of
+        // Z after demeaning and would make Z'Z singular
+        let z_dm = match &z {
+            Some(z) => {
+                let keep_z: Vec<usize> = (0..l).filter(|&j| !fully_absorbed(z, &z_dm, j)).collect();
+                if keep_z.is_empty() {
+                    return Err(GreenersError::InvalidOperation(
+                        "All instruments are absorbed by the fixed effects".into(),
+                    ));
+                }
+                z_dm.select(Axis(1), &keep_z)
+            }
+            None => z_dm,
+        };
+        let kept_names: Vec<String> = keep.iter().map(|&j| names[j].clone()).collect();
+
+        // 4. OLS or 2SLS on the demeaned data
+        let (params, cov, ols_df, inner_omitted, satterthwaite_df, clean_names, resid) = match z {
+            None => {
+                let fit = OLS::fit_with_names(&y_dm, &x_dm, cov_type.clone(), Some(kept_names))?;
+                let x_used = fit.x_clean.as_ref().unwrap_or(&x_dm);
+                let resid = &y_dm - &x_used.dot(&fit.params);
+                (
+                    fit.params.clone(),
+                    fit.cov_matrix.clone().unwrap_or_default(),
+                    fit.df_resid,
+                    fit.omitted_vars.clone(),
+                    fit.satterthwaite_df.clone(),
+                    fit.variable_names.clone(),
+                    resid,
+                )
+            }
+            Some(_) => {
+                let fit =
+                    IV::fit_with_names(&y_dm, &x_dm, &z_dm, cov_type.clone(), Some(kept_names))?;
+                let x_used = if fit.omitted_vars.is_empty() {
+                    x_dm.clone()
+                } else {
+                    let dropped: Vec<usize> = fit.omitted_vars.iter().map(|(p, _)| *p).collect();
+                    let cols: Vec<usize> =
+                        (0..x_dm.ncols()).filter(|c| !dropped.contains(c)).collect();
+                    x_dm.select(Axis(1), &cols)
+                };
+                let resid = &y_dm - &x_used.dot(&fit.params);
+                (
+                    fit.params.clone(),
+                    fit.cov_matrix.clone().unwrap_or_default(),
+                    fit.df_resid,
+                    fit.omitted_vars.clone(),
+                    fit.satterthwaite_df.clone(),
+                    fit.variable_names.clone(),
+                    resid,
+                )
+            }
+        };
+        for (pos, name) in inner_omitted {
+            omitted_vars.push((keep[pos], name));
+        }
+        omitted_vars.sort_by_key(|(p, _)| *p);
+
+        let k_used = params.len();
+        if n <= k_used + df_absorbed {
+            return Err(GreenersError::ShapeMismatch(
+                "Not enough degrees of freedom after absorbing the fixed effects".into(),
+            ));
+        }
+        let df_resid = n - k_used - df_absorbed;
+
+        // 5. Small-sample corrections: replace the n - k of the demeaned fit
+        //    by n - k - df_absorbed (clusters: without the nested factors)
+        let rescale = |df_a: usize| ols_df as f64 / (n - k_used - df_a) as f64;
+        let factor = match &cov_type {
+            CovarianceType::NonRobust
+            | CovarianceType::HC1
+            | CovarianceType::NeweyWest(_)
+            | CovarianceType::Hac(_, _)
+            | CovarianceType::DriscollKraay(_, _)
+            | CovarianceType::Conley(_, _) => rescale(df_absorbed),
+            CovarianceType::Clustered(_) | CovarianceType::ClusteredTwoWay(_, _) => {
+                rescale(absorbed(true))
+            }
+            _ => 1.0,
+        };
+        let cov_matrix = cov * factor;
+        let std_errors = cov_matrix.diag().mapv(|v| v.max(0.0).sqrt());
+        let t_values = &params / &std_errors;
+        let inference_type = InferenceType::default();
+        let (p_values, conf_lower, conf_upper) = match &satterthwaite_df {
+            Some(df) => OlsResult::compute_inference_per_coef(&t_values, &std_errors, &params, df)?,
+            None => OlsResult::compute_inference(
+                &t_values,
+                &std_errors,
+                &params,
+                df_resid,
+                &inference_type,
+            )?,
+        };
+
+        // 6. Fit statistics
+        let ssr = resid.dot(&resid);
+        let y_mean = y.mean().unwrap_or(0.0);
+        let tss = y.mapv(|v| (v - y_mean).powi(2)).sum();
+        let tss_within = y_dm.dot(&y_dm);
+        let r_squared = if tss > 1e-300 { 1.0 - ssr / tss } else { 0.0 };
+        let r_squared_within = if tss_within > 1e-300 {
+            1.0 - ssr / tss_within
+        } else {
+            0.0
+        };
+        let sigma = (ssr / df_resid as f64).sqrt();
+
+        // 7. Fixed-effect estimates by backfitting on y - Xβ
+        let fixed_effects = if config.save_fe {
+            let x_used = if omitted_vars.is_empty() {
+                x.clone()
+            } else {
+                let dropped: Vec<usize> = omitted_vars.iter().map(|(p, _)| *p).collect();
+                let cols: Vec<usize> = (0..k).filter(|c| !dropped.contains(c)).collect();
+                x.select(Axis(1), &cols)
+            };
+            let partial = &y - &x_used.dot(&params);
+            Some(recover_fixed_effects(&partial, &factors, config))
+        } else {
+            None
+        };
+
+        let fe_names =
+            fe_names.unwrap_or_else(|| (0..factors.len()).map(|j| format!("fe{}", j)).collect());
+        let variable_names = clean_names.or(Some(
+            keep.iter()
+                .filter(|&&j| !omitted_vars.iter().any(|(p, _)| *p == j))
+                .map(|&j| names[j].clone())
+                .collect(),
+        ));
+
+        Ok(HdfeResult {
+            params,
+            std_errors,
+            t_values,
+            p_values,
+            conf_lower,
+            conf_upper,
+            cov_matrix,
+            r_squared,
+            r_squared_within,
+            sigma,
+            n_obs: n,
+            n_singletons: rows.dropped.len(),
+            df_resid,
+            df_absorbed,
+            fe_names,
+            fe_levels,
+            fe_redundant,
+            fe_nested,
+            fe_codes: factors.into_iter().map(|f| f.codes).collect(),
+            fixed_effects,
+            n_iter,
+            converged,
+            iv: z_dm.ncols() > 0,
+            cov_type,
+            inference_type,
+            variable_names,
+            omitted_vars,
+            row_map: rows,
+        })
+    }
+}
+
+/// One absorbed factor with compact level codes and optional observation
+/// weights.
+pub(crate) struct Factor {
+    pub(crate) codes: Vec<usize>,
+    counts: Vec<f64>,
+    weights: Option<Array1<f64>>,
+    pub(crate) n_levels: usize,
+}
+
+impl Factor {
+    pub(crate) fn new(raw: &[usize]) -> Self {
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let codes: Vec<usize> = raw
+            .iter()
+            .map(|&c| {
+                let next = index.len();
+                *index.entry(c).or_insert(next)
+            })
+            .collect();
+        let n_levels = index.len();
+        let mut counts = vec![0.0; n_levels];
+        for &c in &codes {
+            counts[c] += 1.0;
+        }
+        Factor {
+            codes,
+            counts,
+            weights: None,
+            n_levels,
+        }
+    }
+
+    /// The same factor projecting with weights `w` (strictly positive).
+    pub(crate) fn weighted(&self, w: &Array1<f64>) -> Self {
+        let mut counts = vec![0.0; self.n_levels];
+        for (&c, &wi) in self.codes.iter().zip(w.iter()) {
+            counts[c] += wi;
+        }
+        Factor {
+            codes: self.codes.clone(),
+            counts,
+            weights: Some(w.clone()),
+            n_levels: self.n_levels,
+        }
+    }
+
+    /// (Weighted) group means of `v`.
+    fn means(&self, v: &Array1<f64>) -> Vec<f64> {
+        let mut sums = vec![0.0; self.n_levels];
+        match &self.weights {
+            Some(w) => {
+                for ((&c, &val), &wi) in self.codes.iter().zip(v.iter()).zip(w.iter()) {
+                    sums[c] += wi * val;
+                }
+            }
+            None => {
+                for (&c, &val) in self.codes.iter().zip(v.iter()) {
+                    sums[c] += val;
+                }
+            }
+        }
+        for (s, &cnt) in sums.iter_mut().zip(&self.counts) {
+            *s /= cnt;
+        }
+        sums
+    }
+
+    /// Every level lies within a single cluster.
+    pub(crate) fn nested_in(&self, clusters: &[usize]) -> bool {
+        if clusters.len() != self.codes.len() {
+            return false;
+        }
+        let mut owner: Vec<Option<usize>> = vec![None; self.n_levels];
+        for (&c, &g) in self.codes.iter().zip(clusters) {
+            match owner[c] {
+                None => owner[c] = Some(g),
+                Some(prev) if prev != g => return false,
+                _ => {}
+            }
+        }
+        true
+    }
+}
+
+pub(crate) fn check_factors(fe: &[Vec<usize>], n: usize) -> Result<(), GreenersError> {
+    if fe.is_empty() {
+        return Err(GreenersError::InvalidOperation(
+            "At least one fixed-effect factor is required".into(),
+        ));
+    }
+    if let Some(bad) = fe.iter().find(|c| c.len() != n) {
+        return Err(GreenersError::ShapeMismatch(format!(
+            "Fixed-effect codes length ({}) must match number of observations ({})",
+            bad.len(),
+            n
+        )));
+    }
+    Ok(())
+}
+
+/// Observations that are alone in a level of some factor, removed
+/// iteratively until no singleton remains.
+pub(crate) fn find_singletons(fe: &[Vec<usize>], n: usize) -> Vec<usize> {
+    let mut active = vec![true; n];
+    let compact: Vec<Factor> = fe.iter().map(|c| Factor::new(c)).collect();
+    loop {
+        let mut changed = false;
+        for f in &compact {
+            let mut counts = vec![0usize; f.n_levels];
+            for (i, &c) in f.codes.iter().enumerate() {
+                if active[i] {
+                    counts[c] += 1;
+                }
+            }
+            for (i, &c) in f.codes.iter().enumerate() {
+                if active[i] && counts[c] == 1 {
+                    active[i] = false;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    (0..n).filter(|&i| !active[i]).collect()
+}
+
+/// (names, codes per factor, rows with a missing key)
+pub(crate) type AbsorbedFactors = (Vec<String>, Vec<Vec<usize>>, Vec<usize>);
+
+/// Factor codes for the `+`-separated terms of `spec` (`a^b` interacts
+/// columns), with the rows whose key is missing.
+pub(crate) fn absorbed_factors(
+    spec: &str,
+    data: &DataFrame,
+) -> Result<AbsorbedFactors, GreenersError> {
+    let mut names = Vec::new();
+    let mut codes = Vec::new();
+    let mut missing = Vec::new();
+    for term in spec.split('+').map(str::trim) {
+        let columns: Vec<&str> = term.split('^').map(str::trim).collect();
+        if term.is_empty() || columns.iter().any(|c| c.is_empty()) {
+            return Err(GreenersError::FormulaError(format!(
+                "Invalid fixed-effect term '{}'",
+                term
+            )));
+        }
+        // Rows with a missing key belong to no group
+        let grouped = data.group_by(&columns)?;
+        let mut code = vec![usize::MAX; data.n_rows()];
+        for (g, rows) in grouped.groups().iter().enumerate() {
+            for &i in rows {
+                code[i] = g;
+            }
+        }
+        missing.extend((0..data.n_rows()).filter(|&i| code[i] == usize::MAX));
+        names.push(columns.join("^"));
+        codes.push(code);
+    }
+    Ok((names, codes, missing))
+}
+
+/// Levels of each factor that are redundant given the previous ones.
+pub(crate) fn redundant_levels(factors: &[Factor]) -> Vec<usize> {
+    (0..factors.len())
+        .map(|j| match j {
+            0 => 0,
+            1 => connected_components(&factors[0], &factors[1]),
+            _ => 1,
+        })
+        .collect()
+}
+
+/// Connected components of the bipartite graph linking the levels of two
+/// factors; each component makes one level of the second factor redundant.
+fn connected_components(a: &Factor, b: &Factor) -> usize {
+    let mut parent: Vec<usize> = (0..a.n_levels + b.n_levels).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for (&ca, &cb) in a.codes.iter().zip(&b.codes) {
+        let ra = find(&mut parent, ca);
+        let rb = find(&mut parent, a.n_levels + cb);
+        if ra != rb {
+            parent[ra] = rb;
+        }
+    }
+    (0..parent.len())
+        .filter(|&i| find(&mut parent, i) == i)
+        .count()
+}
+
+/// Whether column `j` of `raw` is (numerically) removed by demeaning.
+fn fully_absorbed(raw: &Array2<f64>, demeaned: &Array2<f64>, j: usize) -> bool {
+    let before = raw.column(j).mapv(|v| v * v).sum().sqrt();
+    let after = demeaned.column(j).mapv(|v| v * v).sum().sqrt();
+    after <= 1e-8 * before.max(1e-300)
+}
+
+/// Alternating projections on every column in parallel.
+pub(crate) fn demean_columns(
+    data: &Array2<f64>,
+    factors: &[Factor],
+    config: &HdfeConfig,
+) -> (Array2<f64>, usize, bool) {
+    let columns: Vec<(Array1<f64>, usize, bool)> = (0..data.ncols())
+        .into_par_iter()
+        .map(|j| demean_column(data.column(j).to_owned(), factors, config))
+        .collect();
+    let mut out = Array2::<f64>::zeros(data.dim());
+    let (mut n_iter, mut converged) = (0, true);
+    for (j, (col, iters, ok)) in columns.into_iter().enumerate() {
+        out.column_mut(j).assign(&col);
+        n_iter = n_iter.max(iters);
+        converged &= ok;
+    }
+    (out, n_iter, converged)
+}
+
+fn demean_column(
+    mut v: Array1<f64>,
+    factors: &[Factor],
+    config: &HdfeConfig,
+) -> (Array1<f64>, usize, bool) {
+    let scale = 1.0 + v.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    for iter in 1..=config.max_iter {
+        let mut largest = 0.0_f64;
+        for f in factors {
+            let means = f.means(&v);
+            for (val, &c) in v.iter_mut().zip(&f.codes) {
+                *val -= means[c];
+            }
+            largest = means.iter().fold(largest, |m, x| m.max(x.abs()));
+        }
+        // A single factor is removed exactly in one sweep
+        if factors.len() == 1 || largest <= config.tol * scale {
+            return (v, iter, true);
+        }
+    }
+    (v, config.max_iter, false)
+}
+
+/// Backfitting: α_f = group means of r - Σ_{g≠f} α_g until stable.
+fn recover_fixed_effects(
+    partial: &Array1<f64>,
+    factors: &[Factor],
+    config: &HdfeConfig,
+) -> Vec<Array1<f64>> {
+    let mut alphas: Vec<Array1<f64>> = factors.iter().map(|f| Array1::zeros(f.n_levels)).collect();
+    let mut remainder = partial.clone();
+    let scale = 1.0 + partial.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    for _ in 0..config.max_iter {
+        let mut largest = 0.0_f64;
+        for (f, alpha) in factors.iter().zip(alphas.iter_mut()) {
+            let step = f.means(&remainder);
+            for (val, &c) in remainder.iter_mut().zip(&f.codes) {
+                *val -= step[c];
+            }
+            for (a, s) in alpha.iter_mut().zip(&step) {
+                *a += s;
+            }
+            largest = step.iter().fold(largest, |m, x| m.max(x.abs()));
+        }
+        if factors.len() == 1 || largest <= config.tol * scale {
+            break;
+        }
+    }
+    // Normalise every factor after the first to mean zero over the sample
+    let mut shift = 0.0;
+    for (f, alpha) in factors.iter().zip(alphas.iter_mut()).skip(1) {
+        let mean = f.codes.iter().map(|&c| alpha[c]).sum::<f64>() / f.codes.len() as f64;
+        alpha.mapv_inplace(|a| a - mean);
+        shift += mean;
+    }
+    alphas[0].mapv_inplace(|a| a + shift);
+    alphas
+}
@@ -6,6 +6,7 @@
 pub use dynamic_panel::{ArellanoBond, ArellanoBondResult, SystemGmm, SystemGmmResult};
 pub use fa_panel::{FAPanel, FaPanelResult};
 pub use hausman::HausmanTest;
+pub use hdfe::{HdfeConfig, HdfeResult, HDFE};
 pub use panel::{
     BetweenEstimator, BetweenResult, FixedEffects, GlsPanels, PanelGLS, PanelGlsResult,
     PanelIvResult, PanelResult, PcseResult, RandomEffects, RandomEffectsResult, FE2SLS, PCSE,
@@ -15,17 +16,20 @@ pub use panel_quantile::{PanelQuantile, PanelQuantileResult};
 pub use panel_robust::{RobustFTest, RobustFTestResult, RobustHausman, RobustHausmanResult};
 pub use panel_tobit::{PanelTobit, PanelTobitResult};
 pub use panel_var::{PanelVAR, PanelVarResult};
+pub use ppml_hdfe::{PpmlHdfe, PpmlHdfeConfig, PpmlHdfeResult};
 pub use pstr::{PstrResult, PSTR};
 pub use threshold::{PanelThreshold, ThresholdResult};
 
 pub mod dynamic_panel;
 pub mod fa_panel;
 pub mod hausman;
+pub mod hdfe;
 pub mod panel;
 pub mod panel_heckman;
 pub mod panel_quantile;
 pub mod panel_robust;
 pub mod panel_tobit;
 pub mod panel_var;
+pub mod ppml_hdfe;
 pub mod pstr;
 pub mod threshold;
@@ -1,8 +1,14 @@
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{CovarianceType, DataFrame, Formula, GreenersError, InferenceType};
+use greeners_core::model::align_design;
+use greeners_core::{
+    CovarianceType, DataFrame, FittedModel, Formula, GreenersError, InferenceType, MissingPolicy,
+    RowMap, WildBootstrap, WildBootstrapConfig, WildBootstrapResult,
+};
 use greeners_ols::ols::OLS;
 use indexmap::IndexMap;
 use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::Hash;
 
@@ -11,7 +17,7 @@ use std::hash::Hash;
 // ===========================================================================
 
 /// Struct to hold Fixed Effects estimation results.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -24,6 +30,7 @@ pub struct PanelResult {
     pub sigma: f64,
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for PanelResult {
@@ -139,12 +146,33 @@ impl FixedEffects {
     where
         T: Eq + Hash + Clone,
     {
-        let (y, x) = data.to_design_matrix(formula)?;
+        Self::from_formula_with_missing(formula, data, entity_ids, cov_type, MissingPolicy::Drop)
+    }
+
+    /// Fixed Effects from a formula with an explicit missing-value policy.
+    /// `entity_ids` and cluster ids in `cov_type` are aligned with the rows
+    /// of `data`.
+    pub fn from_formula_with_missing<T>(
+        formula: &Formula,
+        data: &DataFrame,
+        entity_ids: &[T],
+        cov_type: CovarianceType,
+        missing: MissingPolicy,
+    ) -> Result<PanelResult, GreenersError>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let dm = data.design_matrices(formula, missing)?;
+        let entity_ids = dm.rows.select_slice(entity_ids)?;
+        let cov_type = dm.rows.select_cov_type(cov_type);
 
         // Build variable names from formula (no intercept in FE)
         let var_names: Vec<String> = formula.independents.to_vec();
 
-        Self::fit_with_names(&y, &x, entity_ids, Some(var_names), cov_type)
+        let mut result =
+            Self::fit_with_names(&dm.y, &dm.x, &entity_ids, Some(var_names), cov_type)?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Performs the "Within Transformation" (Demeaning) on a matrix/vector.
@@ -259,15 +287,29 @@ impl FixedEffects {
         let sigma2 = ssr / (df_resid_correct as f64);
         let sigma = sigma2.sqrt();
 
-        // For NonRobust covariance, scale SEs by the FE degrees-of-freedom
-        // correction. For robust/clustered/Newey-West, use the SEs already
-        // produced by OLS on the demeaned data and only recompute inference.
-        let std_errors = if matches!(cov_type, CovarianceType::NonRobust) {
-            let adjustment_factor = (ols_result.df_resid as f64) / (df_resid_correct as f64);
-            let old_vars = ols_result.std_errors.mapv(|se| se.powi(2));
-            (old_vars * adjustment_factor).mapv(f64::sqrt)
-        } else {
-            ols_result.std_errors.clone()
+        // Replace the n - k of the demeaned fit by the FE degrees of freedom,
+        // as HDFE does; entity effects nested in the clusters are not
+        // counted. HC0 and HC2-HC5 carry no n - k factor.
+        let absorbed = match &cov_type {
+            CovarianceType::Clustered(ids) => absorbed_df(groups, &[ids], n_entities),
+            CovarianceType::ClusteredTwoWay(a, b) => absorbed_df(groups, &[a, b], n_entities),
+            _ => n_entities - 1,
+        };
+        let std_errors = match cov_type {
+            CovarianceType::NonRobust
+            | CovarianceType::HC1
+            | CovarianceType::NeweyWest(_)
+            | CovarianceType::Hac(_, _)
+            | CovarianceType::DriscollKraay(_, _)
+            | CovarianceType::Conley(_, _)
+            | CovarianceType::Clustered(_)
+            | CovarianceType::ClusteredTwoWay(_, _) => {
+                let adjustment_factor =
+                    (ols_result.df_resid as f64) / ((ols_result.df_resid - absorbed) as f64);
+                let old_vars = ols_result.std_errors.mapv(|se| se.powi(2));
+                (old_vars * adjustment_factor).mapv(f64::sqrt)
+            }
+            _ => ols_result.std_errors.clone(),
         };
 
         let t_values = &ols_result.params / &std_errors;
@@ -275,15 +317,24 @@ impl FixedEffects {
         // Extract inference type from OLS result
         let inference_type = ols_result.inference_type.clone();
 
-        // Recalculate p-values with corrected df_resid
+        // Recalculate p-values with corrected df_resid (CR2/CR3 keep their
+        // Satterthwaite df)
         use greeners_ols::ols::OlsResult;
-        let (p_values, _, _) = OlsResult::compute_inference(
-            &t_values,
-            &std_errors,
-            &ols_result.params,
-            df_resid_correct,
-            &inference_type,
-        )?;
+        let (p_values, _, _) = match (&ols_result.satterthwaite_df, &inference_type) {
+            (Some(df), InferenceType::StudentT) => OlsResult::compute_inference_per_coef(
+                &t_values,
+                &std_errors,
+                &ols_result.params,
+                df,
+            )?,
+            _ => OlsResult::compute_inference(
+                &t_values,
+                &std_errors,
+                &ols_result.params,
+                df_resid_correct,
+                &inference_type,
+            )?,
+        };
 
         Ok(PanelResult {
             params: ols_result.params,
@@ -297,26 +348,112 @@ impl FixedEffects {
             sigma,
             inference_type,
             variable_names,
+            row_map: None,
         })
     }
+
+    /// Wild cluster bootstrap test of H0: β_`coef` = `null` for the within
+    /// estimator, with a test-inversion confidence interval.
+    ///
+    /// The entity effects are partialled out first; bootstrap samples are
+    /// then drawn from the demeaned regression. Errors are clustered by
+    /// `clusters` when given and by entity otherwise, and the t statistic
+    /// uses the same fixed-effect degrees-of-freedom correction as the
+    /// `Clustered` standard errors of [`FixedEffects::fit_with_names`].
+    ///
+    /// # Arguments
+    /// * `y` - Dependent variable
+    /// * `x` - Regressors, without an intercept (as in [`FixedEffects::fit`])
+    /// * `groups` - Entity ids
+    /// * `coef` - Index of the tested coefficient
+    /// * `null` - Hypothesised value
+    /// * `clusters` - Cluster ids (`None`: cluster by entity)
+    /// * `config` - Bootstrap settings
+    pub fn wild_bootstrap<T>(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        groups: &[T],
+        coef: usize,
+        null: f64,
+        clusters: Option<&[usize]>,
+        config: &WildBootstrapConfig,
+    ) -> Result<WildBootstrapResult, GreenersError>
+    where
+        T: Eq + Hash + Clone,
+    {
+        if y.len() != x.nrows() {
+            return Err(GreenersError::ShapeMismatch(
+                "y and X must have the same number of rows".into(),
+            ));
+        }
+        let y_mat = y.view().insert_axis(Axis(1)).to_owned();
+        let y_demeaned = Self::within_transform(&y_mat, groups)?.column(0).to_owned();
+        let x_demeaned = Self::within_transform(x, groups)?;
+
+        let entity_ids: Vec<usize>;
+        let clusters = match clusters {
+            Some(ids) => ids,
+            None => {
+                let mut index: IndexMap<T, usize> = IndexMap::new();
+                entity_ids = groups
+                    .iter()
+                    .map(|g| {
+                        let next = index.len();
+                        *index.entry(g.clone()).or_insert(next)
+                    })
+                    .collect();
+                &entity_ids
+            }
+        };
+        let n_entities = groups.iter().collect::<HashSet<_>>().len();
+        WildBootstrap::test_absorbed(
+            &y_demeaned,
+            &x_demeaned,
+            Some(clusters),
+            coef,
+            null,
+            absorbed_df(groups, &[clusters], n_entities),
+            config,
+        )
+    }
+}
+
+/// Entity effects counted against the residual degrees of freedom of a
+/// cluster-robust fit: none when every entity lies within one cluster of
+/// some dimension, otherwise `n_entities - 1`.
+fn absorbed_df<T: Eq + Hash>(groups: &[T], clusters: &[&[usize]], n_entities: usize) -> usize {
+    let nested = clusters.iter().any(|ids| {
+        let mut owner: HashMap<&T, usize> = HashMap::new();
+        groups
+            .iter()
+            .zip(ids.iter())
+            .all(|(g, &c)| *owner.entry(g).or_insert(c) == c)
+    });
+    if nested {
+        0
+    } else {
+        n_entities - 1
+    }
 }
 
 // ===========================================================================
 // RANDOM EFFECTS (SWAMY-ARORA GLS)
 // ===========================================================================
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RandomEffectsResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
     pub t_values: Array1<f64>,
     pub p_values: Array1<f64>,
     pub r_squared_overall: f64,
+    pub n_obs: usize,
     pub sigma_u: f64, //Standard deviation of idiosyncratic error
     pub sigma_e: f64, //Standard deviation of the individual effect
     pub theta: f64,   //Weight of transformation GLS
     pub inference_type: InferenceType,
     pub variable_names: Option<Vec<String>>,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for RandomEffectsResult {
@@ -398,8 +535,20 @@ impl RandomEffects {
         data: &DataFrame,
         entity_ids: &Array1<i64>,
     ) -> Result<RandomEffectsResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        let mut result = Self::fit(&y, &x, entity_ids)?;
+        Self::from_formula_with_missing(formula, data, entity_ids, MissingPolicy::Drop)
+    }
+
+    /// Random Effects from a formula with an explicit missing-value policy.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        entity_ids: &Array1<i64>,
+        missing: MissingPolicy,
+    ) -> Result<RandomEffectsResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let entity_ids = Array1::from(dm.rows.select_slice(&entity_ids.to_vec())?);
+        let mut result = Self::fit(&dm.y, &dm.x, &entity_ids)?;
+        result.row_map = Some(dm.rows);
         // to_design_matrix coloca intercepto primeiro quando formula.intercept == true
         let mut var_names: Vec<String> = if formula.intercept {
             let mut v = vec!["const".to_string()];
@@ -572,16 +721,18 @@ impl RandomEffects {
             t_values: final_model.t_values,
             p_values: final_model.p_values,
             r_squared_overall: r2_overall,
+            n_obs,
             sigma_u: sigma_u_sq.sqrt(),
             sigma_e: sigma_e_sq.sqrt(),
             theta,
             inference_type: final_model.inference_type,
             variable_names: None,
+            row_map: None,
         })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BetweenResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -590,6 +741,7 @@ pub struct BetweenResult {
     pub r_squared: f64,
     pub n_entities: usize,
     pub inference_type: InferenceType,
+    pub row_map: Option<RowMap>,
 }
 
 impl fmt::Display for BetweenResult {
@@ -658,8 +810,21 @@ impl BetweenEstimator {
         data: &DataFrame,
         entity_ids: &Array1<i64>,
     ) -> Result<BetweenResult, GreenersError> {
-        let (y, x) = data.to_design_matrix(formula)?;
-        Self::fit(&y, &x, entity_ids)
+        Self::from_formula_with_missing(formula, data, entity_ids, MissingPolicy::Drop)
+    }
+
+    /// Between estimator from a formula with an explicit missing-value policy.
+    pub fn from_formula_with_missing(
+        formula: &Formula,
+        data: &DataFrame,
+        entity_ids: &Array1<i64>,
+        missing: MissingPolicy,
+    ) -> Result<BetweenResult, GreenersError> {
+        let dm = data.design_matrices(formula, missing)?;
+        let entity_ids = Array1::from(dm.rows.select_slice(&entity_ids.to_vec())?);
+        let mut result = Self::fit(&dm.y, &dm.x, &entity_ids)?;
+        result.row_map = Some(dm.rows);
+        Ok(result)
     }
 
     /// Estimates the regression in the temporal means of each individual.
@@ -725,6 +890,7 @@ impl BetweenEstimator {
             r_squared: ols.r_squared,
             n_entities,
             inference_type: ols.inference_type,
+            row_map: None,
         })
     }
 }
@@ -733,7 +899,7 @@ impl BetweenEstimator {
 // FE-2SLS (xtivreg, fe) — Hausman (1978)
 // ===========================================================================
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelIvResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -1004,7 +1170,7 @@ fn t_pvalues(t_vals: &Array1<f64>, df: usize) -> Result<Array1<f64>, GreenersErr
 // Stata: xtpcse y x1 x2, id(firm) t(year)
 // ===========================================================================
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PcseResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -1160,13 +1326,13 @@ impl PCSE {
 // panels=corr   : Σ completa entre entidades (Parks clássico)
 // ===========================================================================
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GlsPanels {
     Hetero,
     Correlated,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelGlsResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -1356,3 +1522,79 @@ impl PanelGLS {
         })
     }
 }
+
+/// Coefficients of the within (demeaned) regression; `predict` returns
+/// `Xβ` without the entity effects, so pass demeaned data for in-sample fit.
+impl FittedModel for PanelResult {
+    fn model_name(&self) -> String {
+        "FixedEffects".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &[])?;
+        Ok(x.dot(&self.params))
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared)
+    }
+}
+
+impl FittedModel for RandomEffectsResult {
+    fn model_name(&self) -> String {
+        "RandomEffects".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &[])?;
+        Ok(x.dot(&self.params))
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    fn r_squared(&self) -> Option<f64> {
+        Some(self.r_squared_overall)
+    }
+}
@@ -14,11 +14,12 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of Panel Heckman estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelHeckmanResult {
     /// Selection equation coefficients (gamma)
     pub gamma: Array1<f64>,
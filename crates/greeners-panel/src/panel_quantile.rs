@@ -14,11 +14,12 @@
 use greeners_core::error::GreenersError;
 use greeners_ols::quantile::QuantileReg;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of panel quantile regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelQuantileResult {
     /// Quantile level (0-1)
     pub tau: f64,
@@ -96,12 +97,14 @@ impl PanelQuantile {
     /// * `entity_ids` - Entity identifier (n)
     /// * `tau` - Quantile level (0-1)
     /// * `variable_names` - Optional names
+    /// * `seed` - Random seed for the bootstrap SE (`None` uses the default seed)
     pub fn fit(
         y: &Array1<f64>,
         x: &Array2<f64>,
         entity_ids: &[i64],
         tau: f64,
         variable_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<PanelQuantileResult, GreenersError> {
         let n = y.len();
         let k = x.ncols();
@@ -158,7 +161,7 @@ impl PanelQuantile {
         }
 
         // Quantile regression on demeaned data
-        let result = QuantileReg::fit(&y_dm, &x_dm, tau, 100)
+        let result = QuantileReg::fit(&y_dm, &x_dm, tau, 100, seed)
             .map_err(|e| GreenersError::InvalidOperation(e.to_string()))?;
 
         // SE from bootstrap
@@ -16,11 +16,12 @@ use crate::panel::{PanelResult, RandomEffectsResult};
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, FisherSnedecor};
 use std::fmt;
 
 /// Result of robust Hausman test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RobustHausmanResult {
     /// Chi-squared statistic
     pub chi2: f64,
@@ -75,7 +76,7 @@ impl fmt::Display for RobustHausmanResult {
 }
 
 /// Result of robust F-test for panel.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RobustFTestResult {
     /// Wald statistic (chi2 version)
     pub wald_chi2: f64,
@@ -13,11 +13,12 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of Panel Tobit (random effects) estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelTobitResult {
     /// Coefficients (beta, no intercept — intercept is in random effects)
     pub beta: Array1<f64>,
@@ -15,11 +15,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::ContinuousCDF;
 use std::fmt;
 
 /// Result of Panel VAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PanelVarResult {
     /// VAR coefficient matrix (k x (k*p)), each column = coefficients for one equation
     pub coeffs: Array2<f64>,
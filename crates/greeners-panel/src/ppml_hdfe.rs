@@ -0,0 +1,637 @@
+//! Poisson pseudo-maximum likelihood with high-dimensional fixed effects
+//! (`ppmlhdfe`, `fixest::fepois`).
+//!
+//! The model is fitted by iteratively reweighted least squares in which
+//! every step absorbs the fixed effects by weighted alternating projections
+//! (see [`crate::hdfe`]). Before estimation, observations whose fixed
+//! effects or regressors perfectly separate them from the rest are dropped:
+//! levels with only zero outcomes are removed together with singletons, and
+//! separation by the regressors is detected with the ReLU algorithm of
+//! Correia, Guimarães and Zylkin (2021).
+
+use crate::hdfe::{
+    absorbed_factors, check_factors, demean_columns, find_singletons, redundant_levels, Factor,
+    HdfeConfig,
+};
+use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::{
+    CovarianceType, DataFrame, Formula, GreenersError, InferenceType, RowMap, Sandwich,
+};
+use greeners_ols::ols::OlsResult;
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
+use statrs::function::gamma::ln_gamma;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Settings for [`PpmlHdfe`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PpmlHdfeConfig {
+    /// Convergence tolerance on the relative change in deviance
+    pub tol: f64,
+    /// Maximum number of IRLS iterations
+    pub max_iter: usize,
+    /// Tolerance and sweep limit of the weighted demeaning
+    pub demean: HdfeConfig,
+    /// Drop observations that are alone in a level of some factor
+    pub drop_singletons: bool,
+    /// Detect and drop observations separated by the regressors
+    pub check_separation: bool,
+    /// Maximum number of iterations of each ReLU separation check
+    pub separation_max_iter: usize,
+}
+
+impl Default for PpmlHdfeConfig {
+    fn default() -> Self {
+        PpmlHdfeConfig {
+            tol: 1e-8,
+            max_iter: 1000,
+            demean: HdfeConfig {
+                tol: 1e-10,
+                ..HdfeConfig::default()
+            },
+            drop_singletons: true,
+            check_separation: true,
+            separation_max_iter: 100,
+        }
+    }
+}
+
+/// Result of a [`PpmlHdfe`] fit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PpmlHdfeResult {
+    pub params: Array1<f64>,
+    pub std_errors: Array1<f64>,
+    pub z_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub conf_lower: Array1<f64>,
+    pub conf_upper: Array1<f64>,
+    pub cov_matrix: Array2<f64>,
+    /// Fitted means for the estimation observations
+    pub fitted_values: Array1<f64>,
+    pub log_likelihood: f64,
+    /// Log-likelihood of the constant-only model
+    pub log_likelihood_null: f64,
+    /// 1 - ll / ll_null
+    pub pseudo_r_squared: f64,
+    pub deviance: f64,
+    pub n_obs: usize,
+    /// Observations dropped because they are separated (fixed-effect levels
+    /// with only zeros, or separation by the regressors)
+    pub n_separated: usize,
+    /// Observations dropped as singletons
+    pub n_singletons: usize,
+    /// Degrees of freedom used by the fixed effects
+    pub df_absorbed: usize,
+    pub fe_names: Vec<String>,
+    pub fe_levels: Vec<usize>,
+    pub iterations: usize,
+    pub converged: bool,
+    /// Iterations used by the ReLU separation checks
+    pub separation_iterations: usize,
+    /// False if a separation check hit `separation_max_iter`; separated
+    /// observations may then remain in the sample
+    pub separation_converged: bool,
+    pub cov_type: CovarianceType,
+    pub inference_type: InferenceType,
+    pub variable_names: Option<Vec<String>>,
+    /// (position, name) of regressors dropped as collinear with the fixed effects
+    pub omitted_vars: Vec<(usize, String)>,
+    /// Rows of the input used in estimation
+    pub row_map: RowMap,
+}
+
+impl fmt::Display for PpmlHdfeResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " PPML with High-Dimensional Fixed Effects ")?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "No. Observations:", self.n_obs, "Log-Likelihood:", self.log_likelihood
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "Separated:", self.n_separated, "Pseudo R-sq:", self.pseudo_r_squared
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15.4}",
+            "Singletons:", self.n_singletons, "Deviance:", self.deviance
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15} || {:<20} {:>15}",
+            "Df Absorbed:", self.df_absorbed, "Iterations:", self.iterations
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>15}",
+            "Covariance:",
+            Sandwich::describe(&self.cov_type)
+        )?;
+        let fes: Vec<String> = self
+            .fe_names
+            .iter()
+            .zip(&self.fe_levels)
+            .map(|(n, l)| format!("{} ({})", n, l))
+            .collect();
+        writeln!(f, "{:<20} {}", "Absorbed:", fes.join(", "))?;
+
+        writeln!(f, "\n{:-^78}", "")?;
+        writeln!(
+            f,
+            "{:<12} {:>10} {:>10} {:>8} {:>8} {:>10} {:>10}",
+            "", "coef", "std err", "z", "P>|z|", "[0.025", "0.975]"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for i in 0..self.params.len() {
+            let name = self
+                .variable_names
+                .as_ref()
+                .and_then(|n| n.get(i).cloned())
+                .unwrap_or_else(|| format!("x{}", i));
+            writeln!(
+                f,
+                "{:<12} {:>10.4} {:>10.4} {:>8.3} {:>8.3} {:>10.4} {:>10.4}",
+                name,
+                self.params[i],
+                self.std_errors[i],
+                self.z_values[i],
+                self.p_values[i],
+                self.conf_lower[i],
+                self.conf_upper[i]
+            )?;
+        }
+        if !self.omitted_vars.is_empty() {
+            let names: Vec<&str> = self.omitted_vars.iter().map(|(_, n)| n.as_str()).collect();
+            writeln!(f, "Omitted (collinear): {}", names.join(", "))?;
+        }
+        if !self.converged {
+            writeln!(f, "Warning: IRLS did not converge")?;
+        }
+        if !self.separation_converged {
+            writeln!(
+                f,
+                "Warning: separation check did not converge ({} iterations)",
+                self.separation_iterations
+            )?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Poisson pseudo-maximum likelihood with absorbed fixed effects.
+pub struct PpmlHdfe;
+
+impl PpmlHdfe {
+    /// Estimates `y ~ x1 + x2 | fe1 + fe2^fe3` (e.g.
+    /// `trade ~ rta | exporter^year + importer^year + exporter^importer`).
+    ///
+    /// The outcome must be non-negative; it need not be an integer. Cluster
+    /// ids, time ids or coordinates in `cov_type` must be aligned with the
+    /// rows of `data`.
+    pub fn from_formula(
+        formula: &str,
+        data: &DataFrame,
+        cov_type: CovarianceType,
+        config: &PpmlHdfeConfig,
+    ) -> Result<PpmlHdfeResult, GreenersError> {
+        let parts: Vec<&str> = formula.split('|').map(str::trim).collect();
+        if parts.len() != 2 || parts[1].is_empty() {
+            return Err(GreenersError::FormulaError(format!(
+                "Expected 'y ~ x | fe1 + fe2', got: '{}'",
+                formula
+            )));
+        }
+        let mut main = Formula::parse(parts[0])?;
+        main.intercept = false;
+
+        let (fe_names, codes, mut dropped) = absorbed_factors(parts[1], data)?;
+        dropped.extend(data.design_info(&main)?.missing_rows(data)?);
+        let rows = RowMap::new(data.n_rows(), dropped);
+        let subset = if rows.dropped.is_empty() {
+            data.clone()
+        } else {
+            data.drop_rows(&rows.dropped)?
+        };
+
+        let (y, x) = subset.to_design_matrix(&main)?;
+        let names = subset.formula_var_names(&main)?;
+        let codes: Vec<Vec<usize>> = codes
+            .iter()
+            .map(|c| rows.select_slice(c))
+            .collect::<Result<_, _>>()?;
+        let cov_type = rows.select_cov_type(cov_type);
+
+        let mut result = Self::estimate(
+            &y,
+            &x,
+            &codes,
+            cov_type,
+            config,
+            Some(names),
+            Some(fe_names),
+        )?;
+
+        let kept = rows.kept();
+        let mut all_dropped = rows.dropped.clone();
+        all_dropped.extend(result.row_map.dropped.iter().map(|&i| kept[i]));
+        result.row_map = RowMap::new(data.n_rows(), all_dropped);
+        Ok(result)
+    }
+
+    /// Fits `E[y|x, fe] = exp(xβ + Σ α_fe)` with `x` excluding the intercept.
+    ///
+    /// Supported covariances: `NonRobust`, `HC0`, `HC1`, `Clustered`,
+    /// `ClusteredTwoWay`, `Hac`, `DriscollKraay` and `Conley`. Cluster-robust
+    /// errors use the G/(G-1) correction of [`Sandwich::cluster_meat`].
+    pub fn fit(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        fe: &[Vec<usize>],
+        cov_type: CovarianceType,
+        config: &PpmlHdfeConfig,
+    ) -> Result<PpmlHdfeResult, GreenersError> {
+        Self::estimate(y, x, fe, cov_type, config, None, None)
+    }
+
+    fn estimate(
+        y: &Array1<f64>,
+        x: &Array2<f64>,
+        fe: &[Vec<usize>],
+        cov_type: CovarianceType,
+        config: &PpmlHdfeConfig,
+        variable_names: Option<Vec<String>>,
+        fe_names: Option<Vec<String>>,
+    ) -> Result<PpmlHdfeResult, GreenersError> {
+        let n_input = y.len();
+        if x.nrows() != n_input {
+            return Err(GreenersError::ShapeMismatch(
+                "y and X must have the same number of rows".into(),
+            ));
+        }
+        check_factors(fe, n_input)?;
+        if y.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return Err(GreenersError::InvalidOperation(
+                "PPML requires a finite, non-negative dependent variable".into(),
+            ));
+        }
+        if x.iter().any(|v| !v.is_finite()) {
+            return Err(GreenersError::InvalidOperation(
+                "Input data contains NaN or Inf values".into(),
+            ));
+        }
+        if !matches!(
+            cov_type,
+            CovarianceType::NonRobust
+                | CovarianceType::HC0
+                | CovarianceType::HC1
+                | CovarianceType::Clustered(_)
+                | CovarianceType::ClusteredTwoWay(_, _)
+                | CovarianceType::Hac(_, _)
+                | CovarianceType::DriscollKraay(_, _)
+                | CovarianceType::Conley(_, _)
+        ) {
+            return Err(GreenersError::InvalidOperation(format!(
+                "{} is not available for PPML-HDFE",
+                Sandwich::describe(&cov_type)
+            )));
+        }
+        let k = x.ncols();
+        let names = variable_names.unwrap_or_else(|| (0..k).map(|j| format!("x{}", j)).collect());
+
+        // 1. Singletons and separated observations, until nothing changes
+        let mut singleton = vec![false; n_input];
+        let mut separated = vec![false; n_input];
+        let (mut separation_iterations, mut separation_converged) = (0, true);
+        loop {
+            let active: Vec<usize> = (0..n_input)
+                .filter(|&i| !singleton[i] && !separated[i])
+                .collect();
+            let sub_fe: Vec<Vec<usize>> = fe
+                .iter()
+                .map(|c| active.iter().map(|&i| c[i]).collect())
+                .collect();
+            let mut changed = false;
+            if config.drop_singletons {
+                for i in find_singletons(&sub_fe, active.len()) {
+                    singleton[active[i]] = true;
+                    changed = true;
+                }
+            }
+            for i in zero_levels(y, &active, &sub_fe) {
+                if !singleton[active[i]] {
+                    separated[active[i]] = true;
+                    changed = true;
+                }
+            }
+            if changed {
+                continue;
+            }
+            if config.check_separation {
+                let y_a = active.iter().map(|&i| y[i]).collect::<Array1<f64>>();
+                let x_a = x.select(Axis(0), &active);
+                let (found, iters, ok) = relu_separation(
+                    &y_a,
+                    &x_a,
+                    &sub_fe,
+                    &config.demean,
+                    config.separation_max_iter,
+                )?;
+                separation_iterations += iters;
+                separation_converged &= ok;
+                for i in found {
+                    separated[active[i]] = true;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        let dropped: Vec<usize> = (0..n_input)
+            .filter(|&i| singleton[i] || separated[i])
+            .collect();
+        let rows = RowMap::new(n_input, dropped);
+        let (y, x, fe, cov_type) = if rows.dropped.is_empty() {
+            (y.clone(), x.clone(), fe.to_vec(), cov_type)
+        } else {
+            (
+                rows.select(y),
+                rows.select_rows(x),
+                fe.iter()
+                    .map(|c| rows.select_slice(c))
+                    .collect::<Result<_, _>>()?,
+                rows.select_cov_type(cov_type),
+            )
+        };
+        let n = y.len();
+        if n == 0 || y.sum() <= 0.0 {
+            return Err(GreenersError::InvalidOperation(
+                "No observations with a positive outcome remain after dropping separated rows"
+                    .into(),
+            ));
+        }
+        let factors: Vec<Factor> = fe.iter().map(|c| Factor::new(c)).collect();
+        let fe_levels: Vec<usize> = factors.iter().map(|f| f.n_levels).collect();
+        let df_absorbed: usize = fe_levels
+            .iter()
+            .zip(redundant_levels(&factors))
+            .map(|(l, r)| l - r)
+            .sum();
+
+        // 2. Regressors absorbed by the fixed effects
+        let (x_dm, _, _) = demean_columns(&x, &factors, &config.demean);
+        let mut keep = Vec::new();
+        let mut omitted_vars = Vec::new();
+        for (j, name) in names.iter().enumerate().take(k) {
+            let before = x.column(j).mapv(|v| v * v).sum().sqrt();
+            let after = x_dm.column(j).mapv(|v| v * v).sum().sqrt();
+            if after <= 1e-8 * before.max(1e-300) {
+                omitted_vars.push((j, name.clone()));
+            } else {
+                keep.push(j);
+            }
+        }
+        if keep.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "All regressors are absorbed by the fixed effects".into(),
+            ));
+        }
+        let x = x.select(Axis(1), &keep);
+        let k = keep.len();
+        if n <= k + df_absorbed {
+            return Err(GreenersError::ShapeMismatch(
+                "Not enough degrees of freedom after absorbing the fixed effects".into(),
+            ));
+        }
+
+        // 3. IRLS with weighted demeaning of the working variable
+        let y_mean = y.mean().unwrap_or(0.0);
+        let mut mu = y.mapv(|v| (v + y_mean) / 2.0);
+        let mut eta = mu.mapv(f64::ln);
+        let mut params = Array1::<f64>::zeros(k);
+        let mut deviance = poisson_deviance(&y, &mu);
+        let mut iterations = 0;
+        let mut converged = false;
+        for iter in 1..=config.max_iter {
+            iterations = iter;
+            let z = &eta + &((&y - &mu) / &mu);
+            let (z_dm, x_w) = weighted_demean(&z, &x, &factors, &mu, &config.demean);
+            params = weighted_ls(&z_dm, &x_w, &mu)?;
+            let resid = &z_dm - &x_w.dot(&params);
+            eta = &z - &resid;
+            mu = eta.mapv(f64::exp);
+
+            let new_deviance = poisson_deviance(&y, &mu);
+            let change = (new_deviance - deviance).abs() / new_deviance.max(0.1);
+            deviance = new_deviance;
+            if change < config.tol {
+                converged = true;
+                break;
+            }
+        }
+
+        // 4. Covariance from the demeaned regressors at the final weights
+        let (_, x_w) = weighted_demean(&y, &x, &factors, &mu, &config.demean);
+        let mut hessian = Array2::<f64>::zeros((k, k));
+        for (row, &m) in x_w.outer_iter().zip(mu.iter()) {
+            for a in 0..k {
+                for b in 0..k {
+                    hessian[[a, b]] += m * row[a] * row[b];
+                }
+            }
+        }
+        let bread = hessian.inv()?;
+        let mut scores = x_w.clone();
+        for (mut row, r) in scores.outer_iter_mut().zip((&y - &mu).iter()) {
+            row *= *r;
+        }
+        let meat = match &cov_type {
+            CovarianceType::NonRobust => None,
+            CovarianceType::HC0 => Some(scores.t().dot(&scores)),
+            CovarianceType::HC1 => {
+                let factor = n as f64 / (n - k - df_absorbed) as f64;
+                Some(scores.t().dot(&scores) * factor)
+            }
+            other => Sandwich::score_meat(&scores, other)?,
+        };
+        let cov_matrix = match meat {
+            Some(meat) => bread.dot(&meat).dot(&bread),
+            None => bread,
+        };
+
+        let std_errors = cov_matrix.diag().mapv(|v| v.max(0.0).sqrt());
+        let z_values = &params / &std_errors;
+        let inference_type = InferenceType::Normal;
+        let (p_values, conf_lower, conf_upper) =
+            OlsResult::compute_inference(&z_values, &std_errors, &params, 0, &inference_type)?;
+
+        let log_likelihood = poisson_loglik(&y, &mu);
+        let log_likelihood_null = poisson_loglik(&y, &Array1::from_elem(n, y_mean));
+        let pseudo_r_squared = if log_likelihood_null.abs() > 1e-300 {
+            1.0 - log_likelihood / log_likelihood_null
+        } else {
+            0.0
+        };
+
+        let fe_names =
+            fe_names.unwrap_or_else(|| (0..factors.len()).map(|j| format!("fe{}", j)).collect());
+        Ok(PpmlHdfeResult {
+            params,
+            std_errors,
+            z_values,
+            p_values,
+            conf_lower,
+            conf_upper,
+            cov_matrix,
+            fitted_values: mu,
+            log_likelihood,
+            log_likelihood_null,
+            pseudo_r_squared,
+            deviance,
+            n_obs: n,
+            n_separated: separated.iter().filter(|&&s| s).count(),
+            n_singletons: singleton.iter().filter(|&&s| s).count(),
+            df_absorbed,
+            fe_names,
+            fe_levels,
+            iterations,
+            converged,
+            separation_iterations,
+            separation_converged,
+            cov_type,
+            inference_type,
+            variable_names: Some(keep.iter().map(|&j| names[j].clone()).collect()),
+            omitted_vars,
+            row_map: rows,
+        })
+    }
+}
+
+/// Positions (within `active`) of observations in a level whose outcomes
+/// are all zero.
+fn zero_levels(y: &Array1<f64>, active: &[usize], fe: &[Vec<usize>]) -> Vec<usize> {
+    let mut out = Vec::new();
+    for codes in fe {
+        let mut totals: HashMap<usize, f64> = HashMap::new();
+        for (pos, &c) in codes.iter().enumerate() {
+            *totals.entry(c).or_insert(0.0) += y[active[pos]];
+        }
+        out.extend((0..codes.len()).filter(|&pos| totals[&codes[pos]] <= 0.0));
+    }
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// ReLU separation check: finds z = Xγ + fixed effects with z = 0 where
+/// y > 0 and z ≥ 0 where y = 0. Observations with z > 0 are separated.
+///
+/// Returns the separated positions, the iterations used and whether the
+/// check converged within `max_iter`; without convergence nothing is
+/// reported as separated.
+fn relu_separation(
+    y: &Array1<f64>,
+    x: &Array2<f64>,
+    fe: &[Vec<usize>],
+    demean: &HdfeConfig,
+    max_iter: usize,
+) -> Result<(Vec<usize>, usize, bool), GreenersError> {
+    let zeros: Vec<bool> = y.iter().map(|&v| v == 0.0).collect();
+    if !zeros.iter().any(|&z| z) {
+        return Ok((Vec::new(), 0, true));
+    }
+    let factors: Vec<Factor> = fe.iter().map(|c| Factor::new(c)).collect();
+    // Heavy weights force the fit to zero on the positive outcomes
+    let heavy = 1e4_f64.max(y.len() as f64);
+    let w = zeros
+        .iter()
+        .map(|&z| if z { 1.0 } else { heavy })
+        .collect::<Array1<f64>>();
+    let mut u = zeros
+        .iter()
+        .map(|&z| if z { 1.0 } else { 0.0 })
+        .collect::<Array1<f64>>();
+    let tol = 1e-5;
+
+    for iter in 1..=max_iter {
+        let (u_dm, x_w) = weighted_demean(&u, x, &factors, &w, demean);
+        let gamma = if x_w.ncols() > 0 {
+            // Collinear regressors are harmless here: any solution works
+            weighted_ls(&u_dm, &x_w, &w).unwrap_or_else(|_| Array1::zeros(x_w.ncols()))
+        } else {
+            Array1::zeros(0)
+        };
+        let fitted = &u - &(&u_dm - &x_w.dot(&gamma));
+        let fitted = fitted.mapv(|v| if v.abs() < tol { 0.0 } else { v });
+
+        if fitted
+            .iter()
+            .zip(&zeros)
+            .all(|(&v, &z)| if z { v >= 0.0 } else { v == 0.0 })
+        {
+            let found = (0..y.len())
+                .filter(|&i| zeros[i] && fitted[i] > 0.0)
+                .collect();
+            return Ok((found, iter, true));
+        }
+        for i in 0..y.len() {
+            u[i] = if zeros[i] { fitted[i].max(0.0) } else { 0.0 };
+        }
+        // Nothing left that could be separated
+        if u.iter().all(|&v| v == 0.0) {
+            return Ok((Vec::new(), iter, true));
+        }
+    }
+    Ok((Vec::new(), max_iter, false))
+}
+
+/// Demeans `v` and the columns of `x` with weights `w` on all factors.
+fn weighted_demean(
+    v: &Array1<f64>,
+    x: &Array2<f64>,
+    factors: &[Factor],
+    w: &Array1<f64>,
+    config: &HdfeConfig,
+) -> (Array1<f64>, Array2<f64>) {
+    let weighted: Vec<Factor> = factors.iter().map(|f| f.weighted(w)).collect();
+    let mut stacked = Array2::<f64>::zeros((v.len(), 1 + x.ncols()));
+    stacked.column_mut(0).assign(v);
+    stacked.slice_mut(ndarray::s![.., 1..]).assign(x);
+    let (out, _, _) = demean_columns(&stacked, &weighted, config);
+    (
+        out.column(0).to_owned(),
+        out.slice(ndarray::s![.., 1..]).to_owned(),
+    )
+}
+
+/// (X'WX)^{-1} X'Wv
+fn weighted_ls(
+    v: &Array1<f64>,
+    x: &Array2<f64>,
+    w: &Array1<f64>,
+) -> Result<Array1<f64>, GreenersError> {
+    let xw = x * &w.view().insert_axis(Axis(1));
+    let xtwx = xw.t().dot(x);
+    Ok(xtwx.inv()?.dot(&xw.t().dot(v)))
+}
+
+fn poisson_deviance(y: &Array1<f64>, mu: &Array1<f64>) -> f64 {
+    2.0 * y
+        .iter()
+        .zip(mu.iter())
+        .map(|(&yi, &mi)| {
+            let log_term = if yi > 0.0 { yi * (yi / mi).ln() } else { 0.0 };
+            log_term - (yi - mi)
+        })
+        .sum::<f64>()
+}
+
+fn poisson_loglik(y: &Array1<f64>, mu: &Array1<f64>) -> f64 {
+    y.iter()
+        .zip(mu.iter())
+        .map(|(&yi, &mi)| yi * mi.ln() - mi - ln_gamma(yi + 1.0))
+        .sum()
+}
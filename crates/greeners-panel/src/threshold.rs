@@ -1,9 +1,10 @@
 use crate::panel::FixedEffects;
 use greeners_core::GreenersError;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ThresholdResult {
     pub threshold_gamma: f64,
     pub params_regime1: Array1<f64>, //Coefficients when q <= gamma
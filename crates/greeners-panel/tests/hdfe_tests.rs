@@ -0,0 +1,414 @@
+use greeners_core::{CovarianceType, DataFrame};
+use greeners_ols::iv::IV;
+use greeners_ols::ols::OLS;
+use greeners_panel::{FixedEffects, HdfeConfig, HDFE};
+use ndarray::{Array1, Array2};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::Normal;
+
+/// Unbalanced firm-year panel with firm and year effects correlated with x.
+struct Panel {
+    y: Array1<f64>,
+    x: Array2<f64>,
+    firm: Vec<usize>,
+    year: Vec<usize>,
+    z: Array1<f64>,
+}
+
+fn make_panel(seed: u64, n_firms: usize, n_years: usize) -> Panel {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 1.0).unwrap();
+    let firm_fx: Vec<f64> = (0..n_firms).map(|_| noise.sample(&mut rng)).collect();
+    let year_fx: Vec<f64> = (0..n_years).map(|_| noise.sample(&mut rng)).collect();
+
+    let (mut y, mut x, mut firm, mut year, mut z) = (vec![], vec![], vec![], vec![], vec![]);
+    for (f, &alpha) in firm_fx.iter().enumerate() {
+        for (t, &gamma) in year_fx.iter().enumerate() {
+            // Drop a few cells so that the panel is unbalanced
+            if (f * 7 + t * 3) % 11 == 0 {
+                continue;
+            }
+            let zi = noise.sample(&mut rng);
+            let u = noise.sample(&mut rng);
+            let x1 = 0.5 * alpha + gamma + zi + 0.5 * u;
+            let x2 = noise.sample(&mut rng);
+            let e = 0.5 * noise.sample(&mut rng) + 0.5 * u;
+            y.push(1.5 * x1 - 0.7 * x2 + alpha + 2.0 * gamma + e);
+            x.push(x1);
+            x.push(x2);
+            firm.push(f);
+            year.push(t);
+            z.push(zi);
+        }
+    }
+    let n = y.len();
+    Panel {
+        y: Array1::from(y),
+        x: Array2::from_shape_vec((n, 2), x).unwrap(),
+        firm,
+        year,
+        z: Array1::from(z),
+    }
+}
+
+/// Regressors followed by an intercept and dummies for every factor level
+/// except the first.
+fn with_dummies(x: &Array2<f64>, factors: &[&[usize]]) -> Array2<f64> {
+    let n = x.nrows();
+    let mut cols: Vec<Array1<f64>> = x.columns().into_iter().map(|c| c.to_owned()).collect();
+    cols.push(Array1::ones(n));
+    for codes in factors {
+        let levels = codes.iter().max().unwrap() + 1;
+        for level in 1..levels {
+            cols.push(codes.iter().map(|&c| (c == level) as u8 as f64).collect());
+        }
+    }
+    let mut out = Array2::<f64>::zeros((n, cols.len()));
+    for (j, c) in cols.iter().enumerate() {
+        out.column_mut(j).assign(c);
+    }
+    out
+}
+
+fn assert_close(a: &Array1<f64>, b: &Array1<f64>, tol: f64) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < tol * (1.0 + y.abs()), "{} vs {}", x, y);
+    }
+}
+
+#[test]
+fn test_one_way_matches_within_estimator() {
+    let p = make_panel(1, 30, 6);
+    let fe = HDFE::fit(
+        &p.y,
+        &p.x,
+        std::slice::from_ref(&p.firm),
+        CovarianceType::NonRobust,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    let within = FixedEffects::fit(&p.y, &p.x, &p.firm).unwrap();
+
+    assert_eq!(fe.n_iter, 1);
+    assert_eq!(fe.df_absorbed, 30);
+    assert_close(&fe.params, &within.params, 1e-10);
+    // The within estimator charges one level less to the entity effects
+    assert_eq!(fe.df_resid + 1, within.df_resid);
+    let ratio = (within.df_resid as f64 / fe.df_resid as f64).sqrt();
+    assert_close(&fe.std_errors, &(&within.std_errors * ratio), 1e-10);
+}
+
+#[test]
+fn test_two_way_matches_dummy_regression() {
+    let p = make_panel(2, 25, 8);
+    let dummies = with_dummies(&p.x, &[&p.firm, &p.year]);
+    let factors = vec![p.firm.clone(), p.year.clone()];
+
+    for cov in [CovarianceType::NonRobust, CovarianceType::HC1] {
+        let fe = HDFE::fit(&p.y, &p.x, &factors, cov.clone(), &HdfeConfig::default()).unwrap();
+        let ols = OLS::fit(&p.y, &dummies, cov).unwrap();
+
+        assert!(fe.converged);
+        assert_eq!(fe.fe_redundant, vec![0, 1]);
+        assert_eq!(fe.df_absorbed, 25 + 8 - 1);
+        assert_eq!(fe.df_resid, ols.df_resid);
+        assert_close(
+            &fe.params,
+            &ols.params.slice(ndarray::s![..2]).to_owned(),
+            1e-7,
+        );
+        assert_close(
+            &fe.std_errors,
+            &ols.std_errors.slice(ndarray::s![..2]).to_owned(),
+            1e-6,
+        );
+        assert!((fe.r_squared - ols.r_squared).abs() < 1e-8);
+        assert!(fe.r_squared_within < fe.r_squared);
+    }
+}
+
+#[test]
+fn test_clusters_nest_firm_effects() {
+    let p = make_panel(3, 40, 5);
+    let factors = vec![p.firm.clone(), p.year.clone()];
+    let fe = HDFE::fit(
+        &p.y,
+        &p.x,
+        &factors,
+        CovarianceType::Clustered(p.firm.clone()),
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    let ols = OLS::fit(
+        &p.y,
+        &with_dummies(&p.x, &[&p.firm, &p.year]),
+        CovarianceType::Clustered(p.firm.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(fe.fe_nested, vec![true, false]);
+    // Firm dummies are not charged to the small-sample factor: only the
+    // 4 year dummies count against the 46 parameters of the dummy regression
+    let n = fe.n_obs as f64;
+    let ratio = ((n - 2.0 - 44.0) / (n - 2.0 - 4.0)).sqrt();
+    for j in 0..2 {
+        assert!((fe.std_errors[j] / ols.std_errors[j] - ratio).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_singletons_are_dropped_iteratively() {
+    // Firm 2 appears once; dropping it leaves year 2 with a single row
+    let y = Array1::from(vec![1.0, 2.0, 1.2, 1.5, 2.5, 2.2, 3.0, 0.7, 1.9]);
+    let x =
+        Array2::from_shape_vec((9, 1), vec![0.1, 0.4, 0.8, 0.3, 0.9, 0.4, 1.1, 0.2, 0.5]).unwrap();
+    let firm = vec![0, 0, 0, 1, 1, 1, 2, 3, 3];
+    let year = vec![0, 1, 3, 0, 1, 3, 2, 0, 2];
+    let fe = HDFE::fit(
+        &y,
+        &x,
+        &[firm.clone(), year.clone()],
+        CovarianceType::NonRobust,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    // Firm 2 goes first, then year 2 (row 8) and finally firm 3 (row 7)
+    assert_eq!(fe.n_singletons, 3);
+    assert_eq!(fe.n_obs, 6);
+    assert_eq!(fe.row_map.dropped, vec![6, 7, 8]);
+    assert_eq!(fe.fe_levels, vec![2, 3]);
+
+    let keep = HdfeConfig {
+        drop_singletons: false,
+        ..HdfeConfig::default()
+    };
+    let all = HDFE::fit(&y, &x, &[firm, year], CovarianceType::NonRobust, &keep).unwrap();
+    assert_eq!(all.n_singletons, 0);
+    assert_eq!(all.n_obs, 9);
+    // Singletons are fitted perfectly and do not move the slope
+    assert!((all.params[0] - fe.params[0]).abs() < 1e-8);
+}
+
+#[test]
+fn test_disconnected_mobility_groups() {
+    // Workers 0-2 only meet firms 0-1, workers 3-5 only firms 2-3
+    let mut rng = StdRng::seed_from_u64(4);
+    let noise = Normal::new(0.0, 1.0).unwrap();
+    let (mut worker, mut firm, mut xs, mut ys) = (vec![], vec![], vec![], vec![]);
+    for w in 0..6 {
+        for spell in 0..4 {
+            let f = if w < 3 { spell % 2 } else { 2 + spell % 2 };
+            let xv = noise.sample(&mut rng);
+            worker.push(w);
+            firm.push(f);
+            xs.push(xv);
+            ys.push(0.8 * xv + w as f64 - f as f64 + 0.1 * noise.sample(&mut rng));
+        }
+    }
+    let y = Array1::from(ys);
+    let x = Array2::from_shape_vec((24, 1), xs).unwrap();
+    let fe = HDFE::fit(
+        &y,
+        &x,
+        &[worker.clone(), firm.clone()],
+        CovarianceType::NonRobust,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(fe.fe_redundant, vec![0, 2]);
+    assert_eq!(fe.df_absorbed, 6 + 4 - 2);
+    assert_eq!(fe.df_resid, 24 - 1 - 8);
+    assert!((fe.params[0] - 0.8).abs() < 0.1);
+}
+
+#[test]
+fn test_formula_with_interactions_and_iv() {
+    let p = make_panel(5, 20, 6);
+    let n = p.y.len();
+    let industry: Vec<i64> = p.firm.iter().map(|&f| (f % 4) as i64).collect();
+    let mut y = p.y.to_vec();
+    y[3] = f64::NAN;
+    let df = DataFrame::builder()
+        .add_column("y", y)
+        .add_column("x1", p.x.column(0).to_vec())
+        .add_column("x2", p.x.column(1).to_vec())
+        .add_column("z", p.z.to_vec())
+        .add_int("firm", p.firm.iter().map(|&f| f as i64).collect())
+        .add_int("industry", industry)
+        .add_int("year", p.year.iter().map(|&t| t as i64).collect())
+        .build()
+        .unwrap();
+
+    let fit = HDFE::from_formula(
+        "y ~ x2 | firm + industry^year | x1 ~ z",
+        &df,
+        CovarianceType::HC1,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    assert!(fit.iv);
+    assert_eq!(fit.fe_names, vec!["firm", "industry^year"]);
+    assert_eq!(fit.row_map.dropped, vec![3]);
+    assert_eq!(fit.n_obs, n - 1);
+    assert_eq!(
+        fit.variable_names.as_deref().unwrap(),
+        &["x2".to_string(), "x1".to_string()]
+    );
+    assert!((fit.params[1] - 1.5).abs() < 0.25);
+
+    // Same model through the matrix interface and 2SLS on dummies
+    let keep = fit.row_map.kept();
+    let y_k = Array1::from(keep.iter().map(|&i| p.y[i]).collect::<Vec<_>>());
+    let x_k = Array2::from_shape_fn((keep.len(), 2), |(i, j)| p.x[[keep[i], 1 - j]]);
+    let z_k = Array2::from_shape_fn((keep.len(), 2), |(i, j)| {
+        if j == 0 {
+            p.x[[keep[i], 1]]
+        } else {
+            p.z[keep[i]]
+        }
+    });
+    let firm_k: Vec<usize> = keep.iter().map(|&i| p.firm[i]).collect();
+    let cell_k: Vec<usize> = keep
+        .iter()
+        .map(|&i| (p.firm[i] % 4) * 6 + p.year[i])
+        .collect();
+    let direct = HDFE::fit_iv(
+        &y_k,
+        &x_k,
+        &z_k,
+        &[firm_k.clone(), cell_k.clone()],
+        CovarianceType::HC1,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    assert_close(&direct.params, &fit.params, 1e-10);
+    assert_close(&direct.std_errors, &fit.std_errors, 1e-10);
+
+    // Industry is constant within firm: one industry^year level per
+    // industry is redundant
+    assert_eq!(fit.fe_redundant, vec![0, 4]);
+
+    // Firm and year effects against 2SLS with explicit dummies
+    let year_k: Vec<usize> = keep.iter().map(|&i| p.year[i]).collect();
+    let two_way = HDFE::fit_iv(
+        &y_k,
+        &x_k,
+        &z_k,
+        &[firm_k.clone(), year_k.clone()],
+        CovarianceType::NonRobust,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    let xd = with_dummies(&x_k, &[&firm_k, &year_k]);
+    let zd = with_dummies(&z_k, &[&firm_k, &year_k]);
+    let iv = IV::fit(&y_k, &xd, &zd, CovarianceType::NonRobust).unwrap();
+    assert_eq!(two_way.df_resid, iv.df_resid);
+    assert_close(
+        &two_way.params,
+        &iv.params.slice(ndarray::s![..2]).to_owned(),
+        1e-6,
+    );
+    assert_close(
+        &two_way.std_errors,
+        &iv.std_errors.slice(ndarray::s![..2]).to_owned(),
+        1e-6,
+    );
+
+    assert!(HDFE::from_formula(
+        "y ~ x1",
+        &df,
+        CovarianceType::NonRobust,
+        &HdfeConfig::default()
+    )
+    .is_err());
+    assert!(HDFE::from_formula(
+        "y ~ x1 | nope",
+        &df,
+        CovarianceType::NonRobust,
+        &HdfeConfig::default()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_iv_drops_exogenous_regressor_absorbed_by_fixed_effects() {
+    let p = make_panel(8, 15, 6);
+    let n = p.y.len();
+    // Firm size is constant within firm
+    let size: Vec<f64> = p.firm.iter().map(|&f| (f % 5) as f64 + 0.5).collect();
+    let x = Array2::from_shape_fn((n, 3), |(i, j)| match j {
+        0 => p.x[[i, 1]],
+        1 => size[i],
+        _ => p.x[[i, 0]],
+    });
+    let z = Array2::from_shape_fn((n, 3), |(i, j)| match j {
+        0 => p.x[[i, 1]],
+        1 => size[i],
+        _ => p.z[i],
+    });
+    let fe = [p.firm.clone(), p.year.clone()];
+    let fit = HDFE::fit_iv(
+        &p.y,
+        &x,
+        &z,
+        &fe,
+        CovarianceType::HC1,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(fit.omitted_vars, vec![(1, "x1".to_string())]);
+    assert_eq!(fit.params.len(), 2);
+
+    // Same estimates as without the absorbed column
+    let drop = |m: &Array2<f64>| m.select(ndarray::Axis(1), &[0, 2]);
+    let reduced = HDFE::fit_iv(
+        &p.y,
+        &drop(&x),
+        &drop(&z),
+        &fe,
+        CovarianceType::HC1,
+        &HdfeConfig::default(),
+    )
+    .unwrap();
+    assert_close(&fit.params, &reduced.params, 1e-10);
+    assert_close(&fit.std_errors, &reduced.std_errors, 1e-10);
+    assert_eq!(fit.df_resid, reduced.df_resid);
+}
+
+#[test]
+fn test_recovered_fixed_effects_reproduce_fit() {
+    let p = make_panel(6, 15, 7);
+    let config = HdfeConfig {
+        save_fe: true,
+        tol: 1e-12,
+        ..HdfeConfig::default()
+    };
+    let fe = HDFE::fit(
+        &p.y,
+        &p.x,
+        &[p.firm.clone(), p.year.clone()],
+        CovarianceType::NonRobust,
+        &config,
+    )
+    .unwrap();
+    let alphas = fe.fixed_effects.as_ref().unwrap();
+    assert_eq!(alphas[0].len(), 15);
+    assert_eq!(alphas[1].len(), 7);
+
+    // y = Xβ + fe + e with residuals orthogonal to every factor
+    let resid = &p.y - &p.x.dot(&fe.params) - &fe.fitted_fe().unwrap();
+    let ssr = resid.dot(&resid);
+    assert!((ssr / fe.df_resid as f64 - fe.sigma.powi(2)).abs() < 1e-8);
+    for codes in &fe.fe_codes {
+        let mut sums = vec![0.0; codes.iter().max().unwrap() + 1];
+        for (&c, r) in codes.iter().zip(resid.iter()) {
+            sums[c] += r;
+        }
+        assert!(sums.iter().all(|s| s.abs() < 1e-8));
+    }
+
+    let demeaned = HDFE::demean(&p.x, &fe.fe_codes, &config).unwrap();
+    assert!(demeaned.2);
+    assert!(demeaned.0.column(1).sum().abs() < 1e-8);
+}
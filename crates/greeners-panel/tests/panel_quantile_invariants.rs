@@ -40,6 +40,7 @@ fn panel_quantile_runs_and_recovers_parameters() {
         &entity_ids,
         0.5,
         Some(vec!["x1".into(), "x2".into()]),
+        Some(5),
     )
     .unwrap();
 
@@ -50,6 +51,10 @@ fn panel_quantile_runs_and_recovers_parameters() {
     assert!(result.n_entities == n_entities);
     assert!(result.pseudo_r2 >= 0.0 && result.pseudo_r2 <= 1.0);
 
+    // Bootstrap SE are reproducible from the seed
+    let again = PanelQuantile::fit(&y, &x, &entity_ids, 0.5, None, Some(5)).unwrap();
+    assert_eq!(result.std_errors, again.std_errors);
+
     // Approximate recovery of median coefficients.
     assert!(
         (result.beta[0] - 2.0).abs() < 0.6,
@@ -71,10 +76,10 @@ fn panel_quantile_input_validation() {
     let entity_ids = vec![0i64; n];
 
     // tau outside (0, 1)
-    assert!(PanelQuantile::fit(&y, &x, &entity_ids, 0.0, None).is_err());
-    assert!(PanelQuantile::fit(&y, &x, &entity_ids, 1.0, None).is_err());
+    assert!(PanelQuantile::fit(&y, &x, &entity_ids, 0.0, None, None).is_err());
+    assert!(PanelQuantile::fit(&y, &x, &entity_ids, 1.0, None, None).is_err());
 
     // Mismatched entity ids
     let short_ids = vec![0i64; n - 1];
-    assert!(PanelQuantile::fit(&y, &x, &short_ids, 0.5, None).is_err());
+    assert!(PanelQuantile::fit(&y, &x, &short_ids, 0.5, None, None).is_err());
 }
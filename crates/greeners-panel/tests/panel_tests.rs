@@ -176,3 +176,140 @@ fn test_fixed_effects_string_ids() {
     assert_eq!(result.n_entities, 2);
     assert!(result.params[0].is_finite());
 }
+
+#[test]
+fn test_fixed_effects_wild_cluster_bootstrap() {
+    use greeners_core::types::CovarianceType;
+    use greeners_core::WildBootstrapConfig;
+    use ndarray::Array2;
+
+    let entities: Vec<usize> = (0..48).map(|i| i / 6).collect();
+    let x = Array2::from_shape_fn((48, 1), |(i, _)| ((i * 5) % 7) as f64 + (i / 6) as f64);
+    let y = Array1::from_shape_fn(48, |i| {
+        2.0 * (i / 6) as f64 + 0.7 * x[[i, 0]] + (((i * 11) % 9) as f64 - 4.0) / 3.0
+    });
+
+    let fe = FixedEffects::fit_with_names(
+        &y,
+        &x,
+        &entities,
+        None,
+        CovarianceType::Clustered(entities.clone()),
+    )
+    .unwrap();
+    let boot = FixedEffects::wild_bootstrap(
+        &y,
+        &x,
+        &entities,
+        0,
+        0.0,
+        None,
+        &WildBootstrapConfig::default(),
+    )
+    .unwrap();
+    // Eight entities: all 2^8 Rademacher patterns are enumerated
+    assert_eq!(boot.n_bootstrap, 256);
+    assert!((boot.estimate - fe.params[0]).abs() < 1e-10);
+    assert!((boot.t_stat - fe.t_values[0]).abs() < 1e-8);
+    assert!(boot.p_value < 0.05);
+
+    // Periods cut across entities, so the absorbed entity effects count
+    // against the degrees of freedom in both statistics
+    let periods: Vec<usize> = (0..48).map(|i| i % 6).collect();
+    let fe_t = FixedEffects::fit_with_names(
+        &y,
+        &x,
+        &entities,
+        None,
+        CovarianceType::Clustered(periods.clone()),
+    )
+    .unwrap();
+    let boot_t = FixedEffects::wild_bootstrap(
+        &y,
+        &x,
+        &entities,
+        0,
+        0.0,
+        Some(&periods),
+        &WildBootstrapConfig::default(),
+    )
+    .unwrap();
+    assert!((boot_t.t_stat - fe_t.t_values[0]).abs() < 1e-8);
+}
+
+#[test]
+fn test_fixed_effects_clustered_se_match_reghdfe() {
+    use greeners_core::types::CovarianceType;
+    use ndarray::Array2;
+
+    let entities: Vec<usize> = (0..48).map(|i| i / 6).collect();
+    let periods: Vec<usize> = (0..48).map(|i| i % 6).collect();
+    let x = Array2::from_shape_fn((48, 1), |(i, _)| ((i * 5) % 7) as f64 + (i / 6) as f64);
+    let y = Array1::from_shape_fn(48, |i| {
+        2.0 * (i / 6) as f64 + 0.7 * x[[i, 0]] + (((i * 11) % 9) as f64 - 4.0) / 3.0
+    });
+
+    // Reference values follow reghdfe's small-sample convention,
+    // G/(G-1) * (N-1)/(N-K-A) with A the entity effects not nested in a
+    // cluster dimension, computed from the LSDV scores:
+    //   reghdfe y x, absorb(id) vce(cluster id)          -> A = 0
+    //   reghdfe y x, absorb(id) vce(cluster period)      -> A = 7
+    //   reghdfe y x, absorb(id) vce(cluster id period)   -> A = 0, G = 6
+    let cases = [
+        (
+            CovarianceType::Clustered(entities.clone()),
+            0.07008168556430881,
+        ),
+        (
+            CovarianceType::Clustered(periods.clone()),
+            0.05470019022567854,
+        ),
+        (
+            CovarianceType::ClusteredTwoWay(entities.clone(), periods.clone()),
+            0.055618931028371565,
+        ),
+    ];
+    for (cov_type, se) in cases {
+        let fe = FixedEffects::fit_with_names(&y, &x, &entities, None, cov_type).unwrap();
+        assert!((fe.params[0] - 0.7315512708150744).abs() < 1e-10);
+        assert!(
+            (fe.std_errors[0] - se).abs() < 1e-10,
+            "{} vs {}",
+            fe.std_errors[0],
+            se
+        );
+    }
+}
+
+#[test]
+fn test_panel_formula_missing_rows_and_short_ids() {
+    use greeners_core::types::CovarianceType;
+    use greeners_core::MissingPolicy;
+
+    let df = DataFrame::builder()
+        .add_column("y", vec![1.0, 2.2, 2.9, 4.1, f64::NAN, 6.2, 6.8, 8.1, 9.0])
+        .add_column("x", vec![1.0, 2.0, 3.0, 1.5, 2.5, 3.5, 2.0, 3.0, 4.5])
+        .build()
+        .unwrap();
+    let formula = Formula::parse("y ~ x - 1").unwrap();
+    let ids = vec![1, 1, 1, 2, 2, 2, 3, 3, 3];
+
+    let fe = FixedEffects::from_formula(&formula, &df, &ids).unwrap();
+    assert_eq!(fe.n_obs, 8);
+    assert!(FixedEffects::from_formula_with_missing(
+        &formula,
+        &df,
+        &ids,
+        CovarianceType::NonRobust,
+        MissingPolicy::Raise
+    )
+    .is_err());
+
+    // Ids for fewer rows than the DataFrame are an error, not a panic
+    let short = &ids[..7];
+    assert!(FixedEffects::from_formula(&formula, &df, short).is_err());
+    let short_i64 = Array1::from(vec![1i64, 1, 1, 2, 2, 2, 3]);
+    let formula_c = Formula::parse("y ~ x").unwrap();
+    assert!(RandomEffects::from_formula(&formula_c, &df, &short_i64).is_err());
+    assert!(BetweenEstimator::from_formula(&formula_c, &df, &short_i64).is_err());
+}
@@ -0,0 +1,271 @@
+use greeners_core::{CovarianceType, DataFrame, GreenersError};
+use greeners_panel::{PpmlHdfe, PpmlHdfeConfig};
+use ndarray::{Array1, Array2};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Normal, Poisson};
+
+/// Gravity panel: exporter-year, importer-year and pair effects, a
+/// time-varying trade agreement dummy and the (pair-invariant) distance.
+struct Gravity {
+    y: Array1<f64>,
+    x: Array2<f64>,
+    exporter: Vec<usize>,
+    importer: Vec<usize>,
+    year: Vec<usize>,
+}
+
+fn make_gravity(seed: u64, n_countries: usize, n_years: usize) -> Gravity {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let noise = Normal::new(0.0, 0.5).unwrap();
+    let (mut y, mut x) = (vec![], vec![]);
+    let (mut exporter, mut importer, mut year) = (vec![], vec![], vec![]);
+    let exp_fx: Vec<f64> = (0..n_countries * n_years)
+        .map(|_| noise.sample(&mut rng))
+        .collect();
+    let imp_fx: Vec<f64> = (0..n_countries * n_years)
+        .map(|_| noise.sample(&mut rng))
+        .collect();
+    for i in 0..n_countries {
+        for j in 0..n_countries {
+            if i == j {
+                continue;
+            }
+            let pair_fx = noise.sample(&mut rng);
+            let dist = 1.0 + ((i as f64 - j as f64).abs()).ln_1p();
+            let start = rng.gen_range(0..n_years + 2);
+            for t in 0..n_years {
+                let rta = if t >= start { 1.0 } else { 0.0 };
+                let eta = 3.0 + 0.5 * rta - 0.8 * dist
+                    + exp_fx[i * n_years + t]
+                    + imp_fx[j * n_years + t]
+                    + pair_fx;
+                let draw: f64 = Poisson::new(eta.exp()).unwrap().sample(&mut rng);
+                y.push(draw);
+                x.push(rta);
+                x.push(dist);
+                exporter.push(i);
+                importer.push(j);
+                year.push(t);
+            }
+        }
+    }
+    let n = y.len();
+    Gravity {
+        y: Array1::from(y),
+        x: Array2::from_shape_vec((n, 2), x).unwrap(),
+        exporter,
+        importer,
+        year,
+    }
+}
+
+fn factors(g: &Gravity, n_years: usize, n_countries: usize) -> Vec<Vec<usize>> {
+    let n = g.y.len();
+    vec![
+        (0..n)
+            .map(|i| g.exporter[i] * n_years + g.year[i])
+            .collect(),
+        (0..n)
+            .map(|i| g.importer[i] * n_years + g.year[i])
+            .collect(),
+        (0..n)
+            .map(|i| g.exporter[i] * n_countries + g.importer[i])
+            .collect(),
+    ]
+}
+
+#[test]
+fn test_three_way_gravity_score_equations() {
+    let g = make_gravity(1, 12, 5);
+    let fe = factors(&g, 5, 12);
+    let fit = PpmlHdfe::fit(
+        &g.y,
+        &g.x,
+        &fe,
+        CovarianceType::NonRobust,
+        &PpmlHdfeConfig::default(),
+    )
+    .unwrap();
+
+    assert!(fit.converged);
+    // Distance is constant within pair and gets absorbed
+    assert_eq!(fit.omitted_vars, vec![(1, "x1".to_string())]);
+    assert_eq!(fit.params.len(), 1);
+    assert!((fit.params[0] - 0.5).abs() < 4.0 * fit.std_errors[0]);
+
+    // First-order conditions: residuals orthogonal to x and to every level
+    let keep = fit.row_map.kept();
+    let resid: Vec<f64> = keep
+        .iter()
+        .zip(fit.fitted_values.iter())
+        .map(|(&i, &mu)| g.y[i] - mu)
+        .collect();
+    let score: f64 = keep.iter().zip(&resid).map(|(&i, r)| g.x[[i, 0]] * r).sum();
+    assert!(score.abs() < 1e-4, "score {}", score);
+    for codes in &fe {
+        let mut sums = std::collections::HashMap::new();
+        for (&i, r) in keep.iter().zip(&resid) {
+            *sums.entry(codes[i]).or_insert(0.0) += r;
+        }
+        assert!(sums.values().all(|s: &f64| s.abs() < 1e-4));
+    }
+    assert!(fit.pseudo_r_squared > 0.0 && fit.pseudo_r_squared < 1.0);
+}
+
+#[test]
+fn test_separated_levels_and_regressors_are_dropped() {
+    let g = make_gravity(2, 8, 4);
+    let mut y = g.y.clone();
+    let n = y.len();
+    // Exporter 0 never trades in year 0: its exporter-year level is separated
+    for i in 0..n {
+        if g.exporter[i] == 0 && g.year[i] == 0 {
+            y[i] = 0.0;
+        }
+    }
+    // A dummy that is positive only on some zero-trade rows separates them
+    let mut x = Array2::<f64>::zeros((n, 2));
+    x.column_mut(0).assign(&g.x.column(0));
+    let mut flagged = 0;
+    for i in 0..n {
+        if g.exporter[i] == 3 && g.year[i] == 2 && g.importer[i] < 4 {
+            y[i] = 0.0;
+            x[[i, 1]] = 1.0;
+            flagged += 1;
+        }
+    }
+    let fe = factors(&g, 4, 8);
+    let fit = PpmlHdfe::fit(
+        &y,
+        &x,
+        &fe[..2],
+        CovarianceType::NonRobust,
+        &PpmlHdfeConfig::default(),
+    )
+    .unwrap();
+
+    assert!(fit.converged);
+    assert!(fit.separation_converged);
+    assert!(fit.separation_iterations > 1);
+    assert_eq!(fit.n_separated, 7 + flagged);
+    for &i in &fit.row_map.dropped {
+        assert_eq!(y[i], 0.0);
+    }
+    // Once its support is gone the dummy is absorbed
+    assert_eq!(fit.omitted_vars.len(), 1);
+    assert!(fit.params[0].is_finite());
+
+    // A check cut short is reported instead of finding no separation
+    let short = PpmlHdfeConfig {
+        separation_max_iter: 1,
+        ..PpmlHdfeConfig::default()
+    };
+    let cut = PpmlHdfe::fit(&y, &x, &fe[..2], CovarianceType::NonRobust, &short);
+    if let Ok(cut) = cut {
+        assert!(!cut.separation_converged);
+        assert_eq!(cut.separation_iterations, 1);
+        assert_eq!(cut.n_separated, 7);
+        assert!(cut
+            .to_string()
+            .contains("separation check did not converge"));
+    }
+
+    let unchecked = PpmlHdfeConfig {
+        check_separation: false,
+        ..PpmlHdfeConfig::default()
+    };
+    let naive = PpmlHdfe::fit(&y, &x, &fe[..2], CovarianceType::NonRobust, &unchecked);
+    // Without the check the separating coefficient diverges
+    if let Ok(naive) = naive {
+        assert!(naive.params[1] < -10.0 || !naive.converged);
+    }
+}
+
+#[test]
+fn test_robust_and_clustered_covariances() {
+    let g = make_gravity(3, 10, 4);
+    let fe = factors(&g, 4, 10);
+    let config = PpmlHdfeConfig::default();
+    let x = g.x.column(0).to_owned().insert_axis(ndarray::Axis(1));
+
+    let hc0 = PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::HC0, &config).unwrap();
+    let n = hc0.n_obs;
+    let ids: Vec<usize> = (0..g.y.len()).collect();
+    let own = PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::Clustered(ids), &config).unwrap();
+    // Clusters of one observation reproduce HC0 up to G/(G-1)
+    let ratio = (n as f64 / (n - 1) as f64).sqrt();
+    assert!((own.std_errors[0] / hc0.std_errors[0] - ratio).abs() < 1e-8);
+
+    // Data are Poisson, so the model-based errors are of the same size as
+    // the robust ones once the latter are corrected for the absorbed levels
+    let plain = PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::NonRobust, &config).unwrap();
+    let hc1 = PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::HC1, &config).unwrap();
+    let rel = plain.std_errors[0] / hc1.std_errors[0];
+    assert!(rel > 0.7 && rel < 1.4, "ratio {}", rel);
+
+    let pairs = fe[2].clone();
+    let clustered =
+        PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::Clustered(pairs), &config).unwrap();
+    assert!(clustered.std_errors[0] > 0.0);
+    assert!((clustered.params[0] - hc0.params[0]).abs() < 1e-12);
+
+    assert!(matches!(
+        PpmlHdfe::fit(&g.y, &x, &fe, CovarianceType::HC3, &config),
+        Err(GreenersError::InvalidOperation(_))
+    ));
+}
+
+#[test]
+fn test_formula_interface() {
+    let g = make_gravity(4, 8, 3);
+    let mut trade = g.y.to_vec();
+    trade[5] = f64::NAN;
+    let df = DataFrame::builder()
+        .add_column("trade", trade)
+        .add_column("rta", g.x.column(0).to_vec())
+        .add_column("dist", g.x.column(1).to_vec())
+        .add_int("exp", g.exporter.iter().map(|&v| v as i64).collect())
+        .add_int("imp", g.importer.iter().map(|&v| v as i64).collect())
+        .add_int("year", g.year.iter().map(|&v| v as i64).collect())
+        .build()
+        .unwrap();
+
+    let fit = PpmlHdfe::from_formula(
+        "trade ~ rta + dist | exp^year + imp^year",
+        &df,
+        CovarianceType::HC0,
+        &PpmlHdfeConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(fit.fe_names, vec!["exp^year", "imp^year"]);
+    assert_eq!(fit.fe_levels, vec![24, 24]);
+    assert!(fit.row_map.dropped.contains(&5));
+    assert_eq!(
+        fit.variable_names.as_deref().unwrap(),
+        &["rta".to_string(), "dist".to_string()]
+    );
+    assert!((fit.params[1] + 0.8).abs() < 0.3);
+    assert!(format!("{}", fit).contains("PPML"));
+
+    let negative = DataFrame::builder()
+        .add_column("y", vec![1.0, -1.0, 2.0, 0.0])
+        .add_column("x", vec![0.1, 0.2, 0.3, 0.4])
+        .add_int("g", vec![0, 0, 1, 1])
+        .build()
+        .unwrap();
+    assert!(PpmlHdfe::from_formula(
+        "y ~ x | g",
+        &negative,
+        CovarianceType::NonRobust,
+        &PpmlHdfeConfig::default()
+    )
+    .is_err());
+    assert!(PpmlHdfe::from_formula(
+        "y ~ x",
+        &negative,
+        CovarianceType::NonRobust,
+        &PpmlHdfeConfig::default()
+    )
+    .is_err());
+}
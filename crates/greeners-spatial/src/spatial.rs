@@ -8,11 +8,12 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::{LinalgEig as _, LinalgInverse as _};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of spatial econometric estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SpatialResult {
     /// Model type: "sar" or "without"
     pub model_type: String,
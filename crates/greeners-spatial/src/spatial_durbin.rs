@@ -11,11 +11,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of spatial panel Durbin model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SpatialDurbinResult {
     /// Spatial autoregressive parameter (rho)
     pub rho: f64,
@@ -11,11 +11,12 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of spatial panel Durbin error model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SpatialDurbinErrorResult {
     /// Spatial error parameter (lambda)
     pub lambda: f64,
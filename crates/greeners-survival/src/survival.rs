@@ -1,13 +1,16 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::model::align_design;
+use greeners_core::FittedModel;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 // ─── Kaplan-Meier ──────────────────────────────────────────────────────────────
 
 /// Result of Kaplan-Meier estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KMResult {
     /// Unique event times
     pub times: Array1<f64>,
@@ -165,7 +168,7 @@ impl KaplanMeier {
 // ─── Cox PH ────────────────────────────────────────────────────────────────────
 
 /// Result of Cox Proportional Hazards model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CoxResult {
     /// Coefficients (log hazard ratios)
     pub params: Array1<f64>,
@@ -437,3 +440,49 @@ fn compute_concordance(times: &Array1<f64>, events: &Array1<u8>, risk_scores: &A
         concordant as f64 / total as f64
     }
 }
+
+/// `predict` returns the log-partial hazard `Xβ`; response residuals are not
+/// defined for the Cox model.
+impl FittedModel for CoxResult {
+    fn model_name(&self) -> String {
+        "CoxPH".to_string()
+    }
+
+    fn params(&self) -> Array1<f64> {
+        self.params.clone()
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        let x = align_design(x, self.params.len(), &[])?;
+        Ok(self.predict_log_hazard(&x))
+    }
+
+    fn residuals(&self, _y: &Array1<f64>, _x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        Err(GreenersError::InvalidOperation(
+            "Response residuals are not defined for the Cox model".into(),
+        ))
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.variable_names
+            .clone()
+            .unwrap_or_else(|| (0..self.params.len()).map(|i| format!("x{}", i)).collect())
+    }
+
+    /// Partial log-likelihood.
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+}
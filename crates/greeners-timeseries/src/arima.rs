@@ -3,19 +3,20 @@ use argmin::{
     solver::neldermead::NelderMead,
 };
 use greeners_core::linalg::LinalgInverse as _;
-use greeners_core::{GreenersError, InferenceType};
+use greeners_core::{FittedModel, GreenersError, GreenersRng, InferenceType};
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, Normal as NormalDist};
 use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArimaOrder {
     pub p: usize,
     pub d: usize,
     pub q: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SeasonalOrder {
     pub p: usize,
     pub d: usize,
@@ -23,7 +24,7 @@ pub struct SeasonalOrder {
     pub s: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ArimaResult {
     pub ar_params: Array1<f64>,
     pub ma_params: Array1<f64>,
@@ -60,7 +61,7 @@ pub struct ArimaResult {
 pub struct ARIMA;
 
 /// Apply regular differencing d times
-fn difference(y: &Array1<f64>, d: usize) -> Array1<f64> {
+pub(crate) fn difference(y: &Array1<f64>, d: usize) -> Array1<f64> {
     let mut result = y.clone();
     for _ in 0..d {
         let n = result.len();
@@ -78,7 +79,7 @@ fn difference(y: &Array1<f64>, d: usize) -> Array1<f64> {
 }
 
 /// Apply seasonal differencing D times with period s
-fn seasonal_difference(y: &Array1<f64>, d_seasonal: usize, s: usize) -> Array1<f64> {
+pub(crate) fn seasonal_difference(y: &Array1<f64>, d_seasonal: usize, s: usize) -> Array1<f64> {
     let mut result = y.clone();
     for _ in 0..d_seasonal {
         let n = result.len();
@@ -109,6 +110,9 @@ impl ARIMA {
     /// Uses Hannan-Rissanen two-step estimation:
     /// 1. Fit a long AR to get residual estimates
     /// 2. Regress on AR lags, estimated MA residual lags, seasonal lags, and exogenous vars
+    ///
+    /// For exact maximum likelihood, or a series with missing values, use
+    /// `sarimax::SARIMAX::fit`.
     pub fn fit_sarimax(
         y: &Array1<f64>,
         order: (usize, usize, usize),
@@ -1057,7 +1061,8 @@ impl ArimaResult {
     ///
     /// Returns an `Array2<f64>` with shape `(steps, n_simulations)` where each column
     /// is one simulated future path using the model parameters with random Normal(0, sigma2) shocks.
-    pub fn simulate(&self, steps: usize, n_simulations: usize) -> Array2<f64> {
+    /// `seed: None` uses the default seed.
+    pub fn simulate(&self, steps: usize, n_simulations: usize, seed: Option<u64>) -> Array2<f64> {
         let p = self.order.p;
         let q = self.order.q;
         let d = self.order.d;
@@ -1069,8 +1074,7 @@ impl ArimaResult {
 
         let mut result = Array2::<f64>::zeros((steps, n_simulations));
 
-        // Simple LCG random number generator state
-        let mut rng_state: u64 = 123_456_789;
+        let mut rng = GreenersRng::new(seed);
 
         for sim in 0..n_simulations {
             // Copy the tail of the differenced series for AR context
@@ -1078,21 +1082,7 @@ impl ArimaResult {
             let mut res_ext: Vec<f64> = res_vec.clone();
 
             for h in 0..steps {
-                // Generate a normal random variate using Box-Muller with LCG
-                // LCG: x_{n+1} = (a * x_n + c) mod m
-                rng_state = rng_state
-                    .wrapping_mul(6_364_136_223_846_793_005)
-                    .wrapping_add(1_442_695_040_888_963_407);
-                let u1 = (rng_state >> 11) as f64 / (1u64 << 53) as f64;
-                let u1 = if u1 < 1e-15 { 1e-15 } else { u1 };
-                rng_state = rng_state
-                    .wrapping_mul(6_364_136_223_846_793_005)
-                    .wrapping_add(1_442_695_040_888_963_407);
-                let u2 = (rng_state >> 11) as f64 / (1u64 << 53) as f64;
-
-                let normal_variate =
-                    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
-                let shock = sigma * normal_variate;
+                let shock = sigma * rng.normal();
 
                 let ti = n + h;
                 let mut val = self.intercept;
@@ -1216,7 +1206,7 @@ impl ArimaResult {
 
 /// Check if a polynomial 1 - c1*z - c2*z^2 - ... has all roots outside the unit circle.
 /// Equivalent to checking that the companion matrix eigenvalues have modulus < 1.
-fn check_roots_outside_unit_circle(coeffs: &Array1<f64>) -> bool {
+pub(crate) fn check_roots_outside_unit_circle(coeffs: &Array1<f64>) -> bool {
     let p = coeffs.len();
     if p == 0 {
         return true;
@@ -1467,3 +1457,66 @@ impl fmt::Display for ArimaResult {
         writeln!(f, "{:=^70}", "")
     }
 }
+
+/// ARIMA predictions depend on the history of the series rather than on a
+/// design matrix: `predict` is not supported (use `ArimaResult::predict` with a
+/// forecast horizon) and `residuals` returns the in-sample residuals.
+impl FittedModel for ArimaResult {
+    fn model_name(&self) -> String {
+        format!("ARIMA({},{},{})", self.order.p, self.order.d, self.order.q)
+    }
+
+    fn params(&self) -> Array1<f64> {
+        let mut v = vec![self.intercept];
+        v.extend(self.ar_params.iter());
+        v.extend(self.ma_params.iter());
+        v.extend(self.seasonal_ar_params.iter());
+        v.extend(self.seasonal_ma_params.iter());
+        if let Some(ref ep) = self.exog_params {
+            v.extend(ep.iter());
+        }
+        Array1::from(v)
+    }
+
+    fn bse(&self) -> Array1<f64> {
+        self.std_errors.clone()
+    }
+
+    fn nobs(&self) -> usize {
+        self.n_obs
+    }
+
+    fn predict(&self, _x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        Err(GreenersError::InvalidOperation(
+            "ARIMA predictions require a forecast horizon; use ArimaResult::predict".into(),
+        ))
+    }
+
+    fn residuals(&self, _y: &Array1<f64>, _x: &Array2<f64>) -> Result<Array1<f64>, GreenersError> {
+        Ok(self.residuals.clone())
+    }
+
+    fn pvalues(&self) -> Option<Array1<f64>> {
+        Some(self.p_values.clone())
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        self.param_names.clone()
+    }
+
+    fn n_params(&self) -> usize {
+        self.df_model
+    }
+
+    fn loglike(&self) -> Option<f64> {
+        Some(self.log_likelihood)
+    }
+
+    fn aic(&self) -> Option<f64> {
+        Some(self.aic)
+    }
+
+    fn bic(&self) -> Option<f64> {
+        Some(self.bic)
+    }
+}
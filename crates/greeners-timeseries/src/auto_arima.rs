@@ -0,0 +1,611 @@
+//! Automatic ARIMA order selection (Hyndman & Khandakar 2008).
+//!
+//! The differencing orders are chosen first with unit-root tests: the
+//! seasonal order `D` from the STL seasonal strength or the OCSB test
+//! (Osborn, Chui, Smith & Birchenhall 1988), then `d` by differencing until
+//! the KPSS test stops rejecting (or the ADF test rejects). With `d` and `D`
+//! fixed, the orders `(p, q)(P, Q)` minimize an information criterion,
+//! AICc by default, either over the full grid or with the stepwise search:
+//! start from the best of four simple models and move to the best of the
+//! neighbouring orders until none improves. Each set of candidates is
+//! estimated in parallel.
+//!
+//! Only stationary and invertible fits (`ArimaResult::is_stationary` and
+//! `is_invertible`) can be selected. The criteria are computed on the
+//! differenced series, so candidates with different lag orders are compared
+//! on the same number of observations.
+
+use crate::arima::{difference, seasonal_difference, ArimaResult, ARIMA};
+use crate::decomposition::Decomposition;
+use crate::timeseries::TimeSeries;
+use greeners_core::{CovarianceType, GreenersError};
+use greeners_ols::ols::OLS;
+use ndarray::{Array1, Array2};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Test used to choose the number of regular differences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnitRootTest {
+    /// Difference while KPSS rejects level stationarity at 5% (short
+    /// bandwidth `4 (n/100)^{1/4}`)
+    #[default]
+    Kpss,
+    /// Difference while ADF does not reject a unit root at 5%
+    Adf,
+}
+
+/// Test used to choose the number of seasonal differences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SeasonalTest {
+    /// STL seasonal strength `max(0, 1 - Var(R) / Var(S + R))` above 0.64
+    #[default]
+    SeasonalStrength,
+    /// OCSB test of a seasonal unit root
+    Ocsb,
+}
+
+/// Criterion minimized over the candidate orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InformationCriterion {
+    #[default]
+    Aicc,
+    Aic,
+    Bic,
+}
+
+impl fmt::Display for InformationCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InformationCriterion::Aicc => write!(f, "AICc"),
+            InformationCriterion::Aic => write!(f, "AIC"),
+            InformationCriterion::Bic => write!(f, "BIC"),
+        }
+    }
+}
+
+/// Estimator used for each candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ArimaMethod {
+    /// `ARIMA::fit_sarimax` (Hannan-Rissanen)
+    #[default]
+    HannanRissanen,
+    /// `ARIMA::fit_mle` (exact likelihood); non-seasonal only, p + q <= 4
+    Mle,
+}
+
+/// Settings for [`AutoArima`].
+#[derive(Debug, Clone)]
+pub struct AutoArimaConfig {
+    /// Seasonal period `s` (1 for a non-seasonal search)
+    pub seasonal_period: usize,
+    pub max_p: usize,
+    pub max_q: usize,
+    pub max_seasonal_p: usize,
+    pub max_seasonal_q: usize,
+    /// Largest `p + q + P + Q`
+    pub max_order: usize,
+    pub max_d: usize,
+    pub max_seasonal_d: usize,
+    /// Fixed number of differences (`None` = chosen by `unit_root_test`)
+    pub d: Option<usize>,
+    /// Fixed number of seasonal differences (`None` = chosen by `seasonal_test`)
+    pub seasonal_d: Option<usize>,
+    pub unit_root_test: UnitRootTest,
+    pub seasonal_test: SeasonalTest,
+    pub criterion: InformationCriterion,
+    pub method: ArimaMethod,
+    /// Stepwise search; `false` fits every order within the limits
+    pub stepwise: bool,
+    /// Maximum number of models fitted by the stepwise search
+    pub max_models: usize,
+}
+
+impl Default for AutoArimaConfig {
+    fn default() -> Self {
+        AutoArimaConfig {
+            seasonal_period: 1,
+            max_p: 5,
+            max_q: 5,
+            max_seasonal_p: 2,
+            max_seasonal_q: 2,
+            max_order: 5,
+            max_d: 2,
+            max_seasonal_d: 1,
+            d: None,
+            seasonal_d: None,
+            unit_root_test: UnitRootTest::Kpss,
+            seasonal_test: SeasonalTest::SeasonalStrength,
+            criterion: InformationCriterion::Aicc,
+            method: ArimaMethod::HannanRissanen,
+            stepwise: true,
+            max_models: 94,
+        }
+    }
+}
+
+/// One fitted candidate of the search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArimaCandidate {
+    /// (p, d, q)
+    pub order: (usize, usize, usize),
+    /// (P, D, Q, s)
+    pub seasonal_order: (usize, usize, usize, usize),
+    pub log_likelihood: f64,
+    /// Estimated parameters, including the intercept and the variance
+    pub n_params: usize,
+    pub aic: f64,
+    pub aicc: f64,
+    pub bic: f64,
+    pub stationary: bool,
+    pub invertible: bool,
+}
+
+impl ArimaCandidate {
+    /// Value of `criterion` for this candidate.
+    pub fn criterion(&self, criterion: InformationCriterion) -> f64 {
+        match criterion {
+            InformationCriterion::Aicc => self.aicc,
+            InformationCriterion::Aic => self.aic,
+            InformationCriterion::Bic => self.bic,
+        }
+    }
+
+    /// Whether the candidate can be selected: a stationary, invertible fit
+    /// with a finite criterion.
+    pub fn admissible(&self, criterion: InformationCriterion) -> bool {
+        self.stationary && self.invertible && self.criterion(criterion).is_finite()
+    }
+
+    fn label(&self) -> String {
+        let (p, d, q) = self.order;
+        let (sp, sd, sq, s) = self.seasonal_order;
+        if s > 1 {
+            format!("ARIMA({},{},{})({},{},{})[{}]", p, d, q, sp, sd, sq, s)
+        } else {
+            format!("ARIMA({},{},{})", p, d, q)
+        }
+    }
+}
+
+/// Result of [`AutoArima`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoArimaResult {
+    /// Fit of the selected orders
+    pub model: ArimaResult,
+    /// Selected (p, d, q)
+    pub order: (usize, usize, usize),
+    /// Selected (P, D, Q, s)
+    pub seasonal_order: (usize, usize, usize, usize),
+    /// Every fitted candidate: admissible ones ranked by the criterion, then
+    /// the non-stationary or non-invertible ones
+    pub candidates: Vec<ArimaCandidate>,
+    pub criterion: InformationCriterion,
+    pub unit_root_test: UnitRootTest,
+    /// `None` when `D` was fixed or the series is not seasonal
+    pub seasonal_test: Option<SeasonalTest>,
+    pub stepwise: bool,
+    /// Candidates whose estimation failed
+    pub n_failed: usize,
+}
+
+impl fmt::Display for AutoArimaResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^78}", " Automatic ARIMA Selection ")?;
+        if let Some(best) = self.candidates.first() {
+            writeln!(f, "{:<24} {:>20}", "Selected model:", best.label())?;
+        }
+        writeln!(
+            f,
+            "{:<24} {:>20}",
+            "Search:",
+            if self.stepwise {
+                "stepwise"
+            } else {
+                "full grid"
+            }
+        )?;
+        writeln!(
+            f,
+            "{:<24} {:>20}",
+            "Models fitted:",
+            self.candidates.len() + self.n_failed
+        )?;
+        writeln!(f, "{:<24} {:>20}", "Criterion:", self.criterion.to_string())?;
+        writeln!(f, "\n{:-^78}", " Candidates ")?;
+        writeln!(
+            f,
+            "{:<5} {:<26} {:>12} {:>12} {:>12} {:>6}",
+            "rank", "model", "AICc", "AIC", "BIC", "ok"
+        )?;
+        writeln!(f, "{:-^78}", "")?;
+        for (i, c) in self.candidates.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<5} {:<26} {:>12.3} {:>12.3} {:>12.3} {:>6}",
+                i + 1,
+                c.label(),
+                c.aicc,
+                c.aic,
+                c.bic,
+                if c.admissible(self.criterion) {
+                    "yes"
+                } else {
+                    "no"
+                }
+            )?;
+        }
+        writeln!(f, "{:=^78}", "")
+    }
+}
+
+/// Stepwise Hyndman-Khandakar ARIMA order selection.
+pub struct AutoArima;
+
+/// Candidate orders (p, q, P, Q); `d` and `D` are fixed during the search.
+type Orders = (usize, usize, usize, usize);
+
+impl AutoArima {
+    /// Selects and fits an ARIMA model for `y`.
+    pub fn fit(
+        y: &Array1<f64>,
+        config: &AutoArimaConfig,
+    ) -> Result<AutoArimaResult, GreenersError> {
+        let m = config.seasonal_period.max(1);
+        if config.method == ArimaMethod::Mle && m > 1 {
+            return Err(GreenersError::InvalidOperation(
+                "Exact MLE supports non-seasonal searches only".into(),
+            ));
+        }
+        if y.iter().any(|v| !v.is_finite()) {
+            return Err(GreenersError::InvalidOperation(
+                "Input series contains NaN or Inf values".into(),
+            ));
+        }
+
+        let seasonal_test = (m > 1 && config.seasonal_d.is_none()).then_some(config.seasonal_test);
+        let sd = match config.seasonal_d {
+            Some(sd) => sd,
+            None if m > 1 => Self::nsdiffs(y, m, config.seasonal_test, config.max_seasonal_d)?,
+            None => 0,
+        };
+        let seasonally_differenced = if sd > 0 {
+            seasonal_difference(y, sd, m)
+        } else {
+            y.clone()
+        };
+        let d = match config.d {
+            Some(d) => d,
+            None => Self::ndiffs(&seasonally_differenced, config.unit_root_test, config.max_d)?,
+        };
+
+        let search = Search {
+            y,
+            d,
+            sd,
+            m,
+            config,
+        };
+        let mut fitted: HashMap<Orders, Option<(ArimaCandidate, ArimaResult)>> = HashMap::new();
+        let seasonal = m > 1;
+
+        if config.stepwise {
+            let start: Vec<Orders> = if seasonal {
+                vec![(2, 2, 1, 1), (0, 0, 0, 0), (1, 0, 1, 0), (0, 1, 0, 1)]
+            } else {
+                vec![(2, 2, 0, 0), (0, 0, 0, 0), (1, 0, 0, 0), (0, 1, 0, 0)]
+            };
+            search.fit_all(&start, &mut fitted);
+            let mut current = search.best(&fitted);
+            while let Some(best) = current {
+                if fitted.len() >= config.max_models {
+                    break;
+                }
+                let mut neighbours: Vec<Orders> = search
+                    .neighbours(best)
+                    .into_iter()
+                    .filter(|o| !fitted.contains_key(o))
+                    .collect();
+                neighbours.truncate(config.max_models - fitted.len());
+                if neighbours.is_empty() {
+                    break;
+                }
+                search.fit_all(&neighbours, &mut fitted);
+                let next = search.best(&fitted);
+                if next == current {
+                    break;
+                }
+                current = next;
+            }
+        } else {
+            let mut grid = Vec::new();
+            for p in 0..=config.max_p {
+                for q in 0..=config.max_q {
+                    for sp in 0..=if seasonal { config.max_seasonal_p } else { 0 } {
+                        for sq in 0..=if seasonal { config.max_seasonal_q } else { 0 } {
+                            if p + q + sp + sq <= config.max_order {
+                                grid.push((p, q, sp, sq));
+                            }
+                        }
+                    }
+                }
+            }
+            search.fit_all(&grid, &mut fitted);
+        }
+
+        let best = search.best(&fitted).ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "No stationary and invertible ARIMA candidate could be fitted".into(),
+            )
+        })?;
+        let n_failed = fitted.values().filter(|v| v.is_none()).count();
+        let model = fitted
+            .remove(&best)
+            .flatten()
+            .map(|(_, fit)| fit)
+            .ok_or(GreenersError::OptimizationFailed)?;
+
+        let criterion = config.criterion;
+        let mut candidates: Vec<ArimaCandidate> =
+            fitted.into_values().flatten().map(|(c, _)| c).collect();
+        candidates.push(search.candidate(best, &model));
+        candidates.sort_by(|a, b| {
+            b.admissible(criterion)
+                .cmp(&a.admissible(criterion))
+                .then(a.criterion(criterion).total_cmp(&b.criterion(criterion)))
+        });
+
+        Ok(AutoArimaResult {
+            order: (best.0, d, best.1),
+            seasonal_order: (best.2, sd, best.3, m),
+            model,
+            candidates,
+            criterion,
+            unit_root_test: config.unit_root_test,
+            seasonal_test,
+            stepwise: config.stepwise,
+            n_failed,
+        })
+    }
+
+    /// Number of regular differences needed to make `y` stationary, up to
+    /// `max_d`.
+    pub fn ndiffs(
+        y: &Array1<f64>,
+        test: UnitRootTest,
+        max_d: usize,
+    ) -> Result<usize, GreenersError> {
+        let mut x = y.clone();
+        let mut d = 0;
+        while d < max_d && x.len() > 10 {
+            let stationary = match test {
+                UnitRootTest::Kpss => {
+                    // Short bandwidth, as in Hyndman & Khandakar
+                    let lags = (4.0 * (x.len() as f64 / 100.0).powf(0.25)) as usize;
+                    TimeSeries::kpss(&x, "c", Some(lags))?.is_stationary
+                }
+                UnitRootTest::Adf => TimeSeries::adf(&x, None)?.is_stationary,
+            };
+            if stationary {
+                break;
+            }
+            x = difference(&x, 1);
+            d += 1;
+        }
+        Ok(d)
+    }
+
+    /// Number of seasonal differences of period `m` needed, up to
+    /// `max_seasonal_d`. Series too short for the test are not differenced.
+    pub fn nsdiffs(
+        y: &Array1<f64>,
+        m: usize,
+        test: SeasonalTest,
+        max_seasonal_d: usize,
+    ) -> Result<usize, GreenersError> {
+        if m < 2 {
+            return Ok(0);
+        }
+        let mut x = y.clone();
+        let mut sd = 0;
+        while sd < max_seasonal_d && x.len() >= 2 * m + 10 {
+            let seasonal = match test {
+                SeasonalTest::SeasonalStrength => Self::seasonal_strength(&x, m)? > 0.64,
+                SeasonalTest::Ocsb => {
+                    let (stat, crit) = Self::ocsb(&x, m)?;
+                    stat > crit
+                }
+            };
+            if !seasonal {
+                break;
+            }
+            x = seasonal_difference(&x, 1, m);
+            sd += 1;
+        }
+        Ok(sd)
+    }
+
+    /// Seasonal strength `max(0, 1 - Var(R) / Var(S + R))` from an STL
+    /// decomposition (Wang, Smith & Hyndman 2006).
+    pub fn seasonal_strength(y: &Array1<f64>, m: usize) -> Result<f64, GreenersError> {
+        let stl = Decomposition::stl(y, m, 13, 0)?;
+        let detrended = &stl.seasonal + &stl.residual;
+        let var_sr = detrended.var(1.0);
+        if var_sr.is_nan() || var_sr <= 0.0 {
+            return Ok(0.0);
+        }
+        Ok((1.0 - stl.residual.var(1.0) / var_sr).max(0.0))
+    }
+
+    /// OCSB statistic and its 5% critical value. `Δ_m Δ y_t` is regressed on
+    /// an intercept, `Δ_m y_{t-1}`, `Δ y_{t-m}` and up to three of its own
+    /// lags chosen by AIC; the statistic is the t-value of `Δ y_{t-m}`. A
+    /// statistic above the critical value (Hyndman's response surface) does
+    /// not reject a seasonal unit root.
+    pub fn ocsb(y: &Array1<f64>, m: usize) -> Result<(f64, f64), GreenersError> {
+        const MAX_LAG: usize = 3;
+        let n = y.len();
+        // First usable t: needs y_{t-m-1} and MAX_LAG lags of the dependent
+        let start = m + 1 + MAX_LAG;
+        if n <= start + 10 {
+            return Err(GreenersError::ShapeMismatch(
+                "Series too short for the OCSB test".into(),
+            ));
+        }
+        let dep = |t: usize| (y[t] - y[t - m]) - (y[t - 1] - y[t - 1 - m]);
+        let rows = n - start;
+        let target = Array1::from_shape_fn(rows, |i| dep(start + i));
+
+        let mut best: Option<(f64, f64)> = None;
+        for lags in 0..=MAX_LAG {
+            let mut x = Array2::<f64>::ones((rows, 3 + lags));
+            for i in 0..rows {
+                let t = start + i;
+                x[[i, 1]] = y[t - 1] - y[t - 1 - m];
+                x[[i, 2]] = y[t - m] - y[t - m - 1];
+                for l in 1..=lags {
+                    x[[i, 2 + l]] = dep(t - l);
+                }
+            }
+            let fit = OLS::fit(&target, &x, CovarianceType::NonRobust)?;
+            if best.is_none_or(|(aic, _)| fit.aic < aic) {
+                best = Some((fit.aic, fit.t_values[2]));
+            }
+        }
+        let stat = best.map(|(_, t)| t).unwrap_or(f64::NAN);
+        let log_m = (m as f64).ln();
+        let crit = -0.2937411
+            * (-0.2850853 * (log_m - 0.7656451) - 0.05983644 * (log_m - 0.7656451).powi(2)).exp()
+            - 1.652202;
+        Ok((stat, crit))
+    }
+}
+
+/// State shared by the candidate fits of one search.
+struct Search<'a> {
+    y: &'a Array1<f64>,
+    d: usize,
+    sd: usize,
+    m: usize,
+    config: &'a AutoArimaConfig,
+}
+
+impl Search<'_> {
+    fn fit_one(&self, (p, q, sp, sq): Orders) -> Option<(ArimaCandidate, ArimaResult)> {
+        let fit = match self.config.method {
+            ArimaMethod::HannanRissanen => {
+                ARIMA::fit_sarimax(self.y, (p, self.d, q), (sp, self.sd, sq, self.m), None)
+            }
+            ArimaMethod::Mle => ARIMA::fit_mle(self.y, (p, self.d, q)),
+        }
+        .ok()?;
+        if !fit.sigma2.is_finite() || fit.sigma2 <= 0.0 {
+            return None;
+        }
+        Some((self.candidate((p, q, sp, sq), &fit), fit))
+    }
+
+    fn fit_all(
+        &self,
+        orders: &[Orders],
+        fitted: &mut HashMap<Orders, Option<(ArimaCandidate, ArimaResult)>>,
+    ) {
+        let results: Vec<_> = orders.par_iter().map(|&o| (o, self.fit_one(o))).collect();
+        fitted.extend(results);
+    }
+
+    /// Criteria on the `t` observations of the differenced series; the
+    /// Hannan-Rissanen likelihood is rescaled from its shorter regression
+    /// sample so that all candidates are comparable.
+    fn candidate(&self, (p, q, sp, sq): Orders, fit: &ArimaResult) -> ArimaCandidate {
+        let t = self.y.len() - self.d - self.sd * self.m;
+        let nt = t as f64;
+        let log_likelihood = match self.config.method {
+            ArimaMethod::Mle => fit.log_likelihood,
+            ArimaMethod::HannanRissanen => {
+                -0.5 * nt * (1.0 + (2.0 * std::f64::consts::PI * fit.sigma2).ln())
+            }
+        };
+        let k = p + q + sp + sq + 2;
+        let kf = k as f64;
+        let aic = -2.0 * log_likelihood + 2.0 * kf;
+        let aicc = if t > k + 1 {
+            aic + 2.0 * kf * (kf + 1.0) / (nt - kf - 1.0)
+        } else {
+            f64::INFINITY
+        };
+        ArimaCandidate {
+            order: (p, self.d, q),
+            seasonal_order: (sp, self.sd, sq, self.m),
+            log_likelihood,
+            n_params: k,
+            aic,
+            aicc,
+            bic: -2.0 * log_likelihood + kf * nt.ln(),
+            stationary: fit.is_stationary(),
+            invertible: fit.is_invertible(),
+        }
+    }
+
+    fn best(
+        &self,
+        fitted: &HashMap<Orders, Option<(ArimaCandidate, ArimaResult)>>,
+    ) -> Option<Orders> {
+        let criterion = self.config.criterion;
+        fitted
+            .iter()
+            .filter_map(|(o, v)| v.as_ref().map(|(c, _)| (o, c)))
+            .filter(|(_, c)| c.admissible(criterion))
+            .min_by(|(oa, a), (ob, b)| {
+                a.criterion(criterion)
+                    .total_cmp(&b.criterion(criterion))
+                    .then(oa.cmp(ob))
+            })
+            .map(|(o, _)| *o)
+    }
+
+    /// Orders reached by changing p, q, P or Q by one, or p and q (P and Q)
+    /// together, within the configured limits.
+    fn neighbours(&self, (p, q, sp, sq): Orders) -> Vec<Orders> {
+        let c = self.config;
+        let seasonal = self.m > 1;
+        let step = |v: usize, delta: i64| -> Option<usize> {
+            let next = v as i64 + delta;
+            (next >= 0).then_some(next as usize)
+        };
+        let mut out = Vec::new();
+        let mut push = |o: Option<Orders>| {
+            if let Some((p, q, sp, sq)) = o {
+                let within = p <= c.max_p
+                    && q <= c.max_q
+                    && sp <= if seasonal { c.max_seasonal_p } else { 0 }
+                    && sq <= if seasonal { c.max_seasonal_q } else { 0 }
+                    && p + q + sp + sq <= c.max_order;
+                if within && !out.contains(&(p, q, sp, sq)) {
+                    out.push((p, q, sp, sq));
+                }
+            }
+        };
+        for delta in [-1, 1] {
+            push(step(p, delta).map(|p| (p, q, sp, sq)));
+            push(step(q, delta).map(|q| (p, q, sp, sq)));
+            push(
+                step(p, delta)
+                    .zip(step(q, delta))
+                    .map(|(p, q)| (p, q, sp, sq)),
+            );
+            if seasonal {
+                push(step(sp, delta).map(|sp| (p, q, sp, sq)));
+                push(step(sq, delta).map(|sq| (p, q, sp, sq)));
+                push(
+                    step(sp, delta)
+                        .zip(step(sq, delta))
+                        .map(|(sp, sq)| (p, q, sp, sq)),
+                );
+            }
+        }
+        out
+    }
+}
@@ -1,10 +1,11 @@
 use greeners_core::{CovarianceType, GreenersError};
 use greeners_ols::ols::OLS;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// AutoReg result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AutoRegResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -196,7 +197,7 @@ impl AutoReg {
 }
 
 /// ARDL result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ARDLResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
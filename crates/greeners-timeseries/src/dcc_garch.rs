@@ -16,10 +16,11 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of DCC-GARCH estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DccGarchResult {
     /// DCC parameters: alpha (a), beta (b)
     pub dcc_alpha: f64,
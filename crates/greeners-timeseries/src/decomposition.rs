@@ -1,9 +1,10 @@
 use greeners_core::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of time series decomposition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecompositionResult {
     pub observed: Array1<f64>,
     pub trend: Array1<f64>,
@@ -16,10 +16,11 @@ use greeners_core::linalg::LinalgDeterminant as _;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Dynamic Factor Model estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DfmResult {
     /// Extracted factors (T x n_factors)
     pub factors: Array2<f64>,
@@ -2,6 +2,7 @@ use crate::statespace::{KalmanFilter, KalmanSmoother, StateSpaceModel};
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::{LinalgEigh as _, LinalgInverse as _, UPLO};
 use ndarray::{s, Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Dynamic Factor Model estimator.
@@ -17,7 +18,7 @@ use std::fmt;
 pub struct DynamicFactor;
 
 /// Result of a Dynamic Factor Model estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DynamicFactorResult {
     /// Factor loadings matrix Lambda (k x r)
     pub factor_loadings: Array2<f64>,
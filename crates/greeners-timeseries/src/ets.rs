@@ -1,9 +1,10 @@
 use greeners_core::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Exponential smoothing result.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ETSResult {
     pub level: Array1<f64>,
     pub trend: Array1<f64>,
@@ -570,14 +571,14 @@ fn run_ets(y: &Array1<f64>, ep: &ETSParams) -> ETSState {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Error component type for ETS models.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ETSError {
     Additive,
     Multiplicative,
 }
 
 /// Trend component type for ETS models.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ETSTrend {
     None,
     Additive,
@@ -587,7 +588,7 @@ pub enum ETSTrend {
 }
 
 /// Seasonal component type for ETS models.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ETSSeasonal {
     None,
     Additive(usize),
@@ -598,7 +599,7 @@ pub enum ETSSeasonal {
 pub struct ETSModel;
 
 /// Result of fitting an ETS model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ETSModelResult {
     pub error: ETSError,
     pub trend: ETSTrend,
@@ -1,19 +1,27 @@
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal as NormalDist};
 use std::f64::consts::PI;
 use std::fmt;
 
 /// GARCH model type
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GarchModelType {
     GARCH,
     EGARCH,
     GJRGARCH,
+    /// Fractionally integrated GARCH (long memory)
+    FIGARCH,
+    /// Asymmetric power ARCH
+    APARCH,
+    /// Heterogeneous ARCH over averages of past shocks
+    HARCH,
+    ConstantVariance,
 }
 
 /// Error distribution for GARCH models
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GarchDist {
     Normal,
     StudentT,
@@ -25,6 +33,10 @@ impl fmt::Display for GarchModelType {
             GarchModelType::GARCH => write!(f, "GARCH"),
             GarchModelType::EGARCH => write!(f, "EGARCH"),
             GarchModelType::GJRGARCH => write!(f, "GJR-GARCH"),
+            GarchModelType::FIGARCH => write!(f, "FIGARCH"),
+            GarchModelType::APARCH => write!(f, "APARCH"),
+            GarchModelType::HARCH => write!(f, "HARCH"),
+            GarchModelType::ConstantVariance => write!(f, "Constant Variance"),
         }
     }
 }
@@ -39,7 +51,7 @@ impl fmt::Display for GarchDist {
 }
 
 /// Result from a GARCH-family model estimation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GarchResult {
     pub params: Array1<f64>,
     pub std_errors: Array1<f64>,
@@ -61,10 +73,17 @@ pub struct GarchResult {
     pub model_type: GarchModelType,
     pub dist: GarchDist,
     pub variable_names: Vec<String>,
+    /// HARCH lag lengths (empty for the other models)
+    #[serde(default)]
+    pub lags: Vec<usize>,
 }
 
 impl GarchResult {
     /// Forecast conditional variance `steps` periods ahead
+    ///
+    /// APARCH with `delta != 2` has no closed form and is simulated with
+    /// [`forecast_simulation`](Self::forecast_simulation) (10,000 paths);
+    /// the other models are forecast analytically.
     pub fn forecast(&self, steps: usize) -> Array1<f64> {
         let n = self.n_obs;
         match self.model_type {
@@ -165,6 +184,118 @@ impl GarchResult {
                 }
                 forecasts
             }
+            GarchModelType::ConstantVariance => Array1::from_elem(steps, self.params[1]),
+            GarchModelType::FIGARCH => {
+                // Future squared shocks are replaced by their forecasts
+                let eq = VarianceEquation::new(self);
+                let mut eps = self.residuals.to_vec();
+                let mut forecasts: Array1<f64> = Array1::zeros(steps);
+                for s in 0..steps {
+                    let h = eq.next(&eps, &[]);
+                    forecasts[s] = h;
+                    eps.push(h.sqrt());
+                }
+                forecasts
+            }
+            GarchModelType::HARCH => {
+                // Known shocks enter each average through their sum, future
+                // ones through their forecast variance
+                let omega = self.params[1];
+                let eps = self.residuals.as_slice().unwrap_or(&[]);
+                let backcast = eps.iter().map(|e| e * e).sum::<f64>() / n.max(1) as f64;
+                let mut forecasts: Array1<f64> = Array1::zeros(steps);
+                for s in 0..steps {
+                    let mut val = omega;
+                    for (k, &lag) in self.lags.iter().enumerate() {
+                        let mut sum = 0.0;
+                        let mut var = 0.0;
+                        for i in 1..=lag {
+                            if i <= s {
+                                var += forecasts[s - i];
+                            } else if n + s >= i {
+                                sum += eps[n + s - i];
+                            } else {
+                                var += backcast;
+                            }
+                        }
+                        val += self.params[2 + k] * (sum * sum + var) / (lag * lag) as f64;
+                    }
+                    forecasts[s] = val.max(1e-10);
+                }
+                forecasts
+            }
+            GarchModelType::APARCH => {
+                let (omega, alphas, gammas, betas, delta) =
+                    aparch_parts(self.params.as_slice().unwrap_or(&[]), self.p, self.q);
+                if (delta - 2.0).abs() > 1e-8 {
+                    return self.forecast_simulation(steps, 10_000, None);
+                }
+                // delta = 2: E[(|e| - g e)^2] = (1 + g^2) h for a future shock
+                let eps = self.residuals.as_slice().unwrap_or(&[]);
+                let h = self.conditional_variance.as_slice().unwrap_or(&[]);
+                let mut forecasts: Array1<f64> = Array1::zeros(steps);
+                for s in 0..steps {
+                    let mut val = omega;
+                    for i in 0..self.q {
+                        if s > i {
+                            val += alphas[i] * (1.0 + gammas[i] * gammas[i]) * forecasts[s - 1 - i];
+                        } else if n + s > i {
+                            let e = eps[n + s - 1 - i];
+                            val += alphas[i] * (e.abs() - gammas[i] * e).powi(2);
+                        }
+                    }
+                    for j in 0..self.p {
+                        if s > j {
+                            val += betas[j] * forecasts[s - 1 - j];
+                        } else if n + s > j {
+                            val += betas[j] * h[n + s - 1 - j];
+                        }
+                    }
+                    forecasts[s] = val.max(1e-10);
+                }
+                forecasts
+            }
+        }
+    }
+
+    /// Simulated conditional variance forecasts: the mean over `n_sims`
+    /// paths that draw shocks from the standardized residuals (filtered
+    /// historical simulation). Works for every model type.
+    pub fn forecast_simulation(
+        &self,
+        steps: usize,
+        n_sims: usize,
+        seed: Option<u64>,
+    ) -> Array1<f64> {
+        let n = self.n_obs;
+        let eq = VarianceEquation::new(self);
+        let z = &self.standardized_residuals;
+        let mut rng = GreenersRng::new(seed);
+        let mut eps = self.residuals.to_vec();
+        let mut h = self.conditional_variance.to_vec();
+        let mut total: Array1<f64> = Array1::zeros(steps);
+        for _ in 0..n_sims {
+            eps.truncate(n);
+            h.truncate(n);
+            for s in 0..steps {
+                let hs = eq.next(&eps, &h);
+                total[s] += hs;
+                eps.push(hs.sqrt() * z[rng.index(n)]);
+                h.push(hs);
+            }
+        }
+        total / n_sims.max(1) as f64
+    }
+
+    fn order_label(&self) -> String {
+        match self.model_type {
+            GarchModelType::ConstantVariance => format!("{}", self.model_type),
+            GarchModelType::HARCH => {
+                let lags: Vec<String> = self.lags.iter().map(|l| l.to_string()).collect();
+                format!("HARCH({})", lags.join(","))
+            }
+            GarchModelType::FIGARCH => format!("FIGARCH({},d,{})", self.q, self.p),
+            _ => format!("{}({},{})", self.model_type, self.p, self.q),
         }
     }
 
@@ -176,16 +307,13 @@ impl GarchResult {
 
 impl fmt::Display for GarchResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let title = format!(
-            " {}({},{}) - {} Results ",
-            self.model_type, self.p, self.q, self.dist
-        );
+        let title = format!(" {} - {} Results ", self.order_label(), self.dist);
         writeln!(f, "\n{:=^78}", title)?;
         writeln!(
             f,
             "{:<20} {:>15} || {:<20} {:>15}",
             "Model:",
-            format!("{}({},{})", self.model_type, self.p, self.q),
+            self.order_label(),
             "No. Observations:",
             self.n_obs
         )?;
@@ -400,6 +528,7 @@ struct BuildResultArgs {
     model_type: GarchModelType,
     dist: GarchDist,
     variable_names: Vec<String>,
+    lags: Vec<usize>,
     log_likelihood: f64,
     n_iter: usize,
     converged: bool,
@@ -419,6 +548,7 @@ fn build_result(
         model_type,
         dist,
         variable_names,
+        lags,
         log_likelihood,
         n_iter,
         converged,
@@ -459,6 +589,7 @@ fn build_result(
         model_type,
         dist,
         variable_names,
+        lags,
     }
 }
 
@@ -687,6 +818,276 @@ fn gjrgarch_conditional_variance(
     h
 }
 
+/// APARCH power variance `sigma_t^delta` from the shocks and variances before `t`.
+#[allow(clippy::too_many_arguments)]
+fn aparch_power_at(
+    t: usize,
+    eps: &[f64],
+    h: &[f64],
+    omega: f64,
+    alphas: &[f64],
+    gammas: &[f64],
+    betas: &[f64],
+    delta: f64,
+) -> f64 {
+    let mut val = omega;
+    for i in 0..alphas.len() {
+        if t > i {
+            let e = eps[t - 1 - i];
+            val += alphas[i] * (e.abs() - gammas[i] * e).powf(delta);
+        }
+    }
+    for j in 0..betas.len() {
+        if t > j {
+            val += betas[j] * h[t - 1 - j].powf(delta / 2.0);
+        }
+    }
+    val
+}
+
+#[allow(clippy::too_many_arguments)]
+fn aparch_conditional_variance(
+    eps: &[f64],
+    omega: f64,
+    alphas: &[f64],
+    gammas: &[f64],
+    betas: &[f64],
+    delta: f64,
+    var_init: f64,
+) -> Vec<f64> {
+    let mut h = vec![var_init; eps.len()];
+    for t in 1..eps.len() {
+        let power = aparch_power_at(t, eps, &h, omega, alphas, gammas, betas, delta);
+        h[t] = power.max(1e-10).powf(2.0 / delta).clamp(1e-10, 1e10);
+    }
+    h
+}
+
+/// `(omega, alphas, gammas, betas, delta)` from APARCH parameters
+/// `[mu, omega, alpha_1..q, gamma_1..q, beta_1..p, delta, (nu)]`.
+fn aparch_parts(params: &[f64], p: usize, q: usize) -> (f64, Vec<f64>, Vec<f64>, Vec<f64>, f64) {
+    (
+        params[1],
+        params[2..2 + q].to_vec(),
+        params[2 + q..2 + 2 * q].to_vec(),
+        params[2 + 2 * q..2 + 2 * q + p].to_vec(),
+        params[2 + 2 * q + p],
+    )
+}
+
+/// `E[(|z| - gamma z)^delta]` for a standardized Normal (`nu = None`) or
+/// Student-t shock; requires `nu > delta`.
+fn aparch_kappa(gamma: f64, delta: f64, nu: Option<f64>) -> f64 {
+    use statrs::function::gamma::ln_gamma;
+    let abs_moment = match nu {
+        None => (0.5 * delta * 2.0_f64.ln() + ln_gamma((delta + 1.0) / 2.0)).exp() / PI.sqrt(),
+        Some(nu) => {
+            (0.5 * delta * (nu - 2.0).ln()
+                + ln_gamma((delta + 1.0) / 2.0)
+                + ln_gamma((nu - delta) / 2.0)
+                - ln_gamma(nu / 2.0))
+            .exp()
+                / PI.sqrt()
+        }
+    };
+    0.5 * ((1.0 - gamma).powf(delta) + (1.0 + gamma).powf(delta)) * abs_moment
+}
+
+/// Lags of the FIGARCH ARCH(inf) representation kept in the recursion.
+const FIGARCH_TRUNCATION: usize = 1000;
+
+/// ARCH(inf) weights `lambda_1..lambda_K` of FIGARCH(1,d,1):
+/// `1 - (1 - beta L)^{-1} (1 - phi L) (1 - L)^d`.
+fn figarch_weights(phi: f64, d: f64, beta: f64) -> Vec<f64> {
+    let mut lambdas = Vec::with_capacity(FIGARCH_TRUNCATION);
+    let mut delta_prev = d;
+    let mut lambda = d - beta + phi;
+    lambdas.push(lambda);
+    for i in 2..=FIGARCH_TRUNCATION {
+        let delta_i = (i as f64 - 1.0 - d) / i as f64 * delta_prev;
+        lambda = beta * lambda + delta_i - phi * delta_prev;
+        lambdas.push(lambda);
+        delta_prev = delta_i;
+    }
+    lambdas
+}
+
+/// `(omega, phi, d, beta)` from FIGARCH parameters `[mu, omega, (phi), d, (beta), (nu)]`.
+fn figarch_parts(params: &[f64], p: usize, q: usize) -> (f64, f64, f64, f64) {
+    let phi = if q == 1 { params[2] } else { 0.0 };
+    let d = params[2 + q];
+    let beta = if p == 1 { params[3 + q] } else { 0.0 };
+    (params[1], phi, d, beta)
+}
+
+/// FIGARCH variance at `t`: `omega / (1 - beta) + sum_i lambda_i eps_{t-i}^2`,
+/// with the pre-sample squared shocks set to `backcast`. `tail[k]` is the sum
+/// of the weights beyond lag `k`.
+fn figarch_at(
+    t: usize,
+    eps: &[f64],
+    lambdas: &[f64],
+    tail: &[f64],
+    level: f64,
+    backcast: f64,
+) -> f64 {
+    let known = t.min(lambdas.len());
+    let mut val = level + backcast * tail[known];
+    for (i, lambda) in lambdas.iter().enumerate().take(known) {
+        let e = eps[t - 1 - i];
+        val += lambda * e * e;
+    }
+    val
+}
+
+fn figarch_tail(lambdas: &[f64]) -> Vec<f64> {
+    let mut tail = vec![0.0; lambdas.len() + 1];
+    for k in (0..lambdas.len()).rev() {
+        tail[k] = tail[k + 1] + lambdas[k];
+    }
+    tail
+}
+
+fn figarch_conditional_variance(
+    eps: &[f64],
+    omega: f64,
+    phi: f64,
+    d: f64,
+    beta: f64,
+    var_init: f64,
+) -> Vec<f64> {
+    let lambdas = figarch_weights(phi, d, beta);
+    let tail = figarch_tail(&lambdas);
+    let level = omega / (1.0 - beta);
+    (0..eps.len())
+        .map(|t| figarch_at(t, eps, &lambdas, &tail, level, var_init).clamp(1e-10, 1e10))
+        .collect()
+}
+
+/// HARCH variance at `t`: `omega + sum_k alpha_k (mean of the last l_k
+/// shocks)^2`; pre-sample shocks enter through their expected square
+/// `backcast`.
+fn harch_at(
+    t: usize,
+    eps: &[f64],
+    omega: f64,
+    alphas: &[f64],
+    lags: &[usize],
+    backcast: f64,
+) -> f64 {
+    let mut val = omega;
+    for (alpha, &lag) in alphas.iter().zip(lags) {
+        let known = lag.min(t);
+        let sum: f64 = eps[t - known..t].iter().sum();
+        val += alpha * (sum * sum + (lag - known) as f64 * backcast) / (lag * lag) as f64;
+    }
+    val
+}
+
+fn harch_conditional_variance(
+    eps: &[f64],
+    omega: f64,
+    alphas: &[f64],
+    lags: &[usize],
+    var_init: f64,
+) -> Vec<f64> {
+    let mut h = vec![var_init; eps.len()];
+    for (t, ht) in h.iter_mut().enumerate().skip(1) {
+        *ht = harch_at(t, eps, omega, alphas, lags, var_init).max(1e-10);
+    }
+    h
+}
+
+/// Variance equation of a fitted model, for recursions past the sample.
+struct VarianceEquation<'a> {
+    result: &'a GarchResult,
+    /// FIGARCH weights and their tail sums (empty otherwise)
+    lambdas: Vec<f64>,
+    tail: Vec<f64>,
+    backcast: f64,
+}
+
+impl<'a> VarianceEquation<'a> {
+    fn new(result: &'a GarchResult) -> Self {
+        let n = result.residuals.len().max(1) as f64;
+        let backcast = result.residuals.iter().map(|e| e * e).sum::<f64>() / n;
+        let (lambdas, tail) = if result.model_type == GarchModelType::FIGARCH {
+            let params = result.params.as_slice().unwrap_or(&[]);
+            let (_, phi, d, beta) = figarch_parts(params, result.p, result.q);
+            let lambdas = figarch_weights(phi, d, beta);
+            let tail = figarch_tail(&lambdas);
+            (lambdas, tail)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        VarianceEquation {
+            result,
+            lambdas,
+            tail,
+            backcast,
+        }
+    }
+
+    /// Variance of period `eps.len()` given the shocks and variances before it.
+    fn next(&self, eps: &[f64], h: &[f64]) -> f64 {
+        let r = self.result;
+        let (p, q) = (r.p, r.q);
+        let params = r.params.as_slice().unwrap_or(&[]);
+        let t = eps.len();
+        let omega = params[1];
+        let val = match r.model_type {
+            GarchModelType::GARCH | GarchModelType::GJRGARCH => {
+                let gamma_at = if r.model_type == GarchModelType::GJRGARCH {
+                    2 + q + p
+                } else {
+                    0
+                };
+                let mut val = omega;
+                for i in 0..q.min(t) {
+                    let e = eps[t - 1 - i];
+                    val += params[2 + i] * e * e;
+                    if gamma_at > 0 && e < 0.0 {
+                        val += params[gamma_at + i] * e * e;
+                    }
+                }
+                for j in 0..p.min(t) {
+                    val += params[2 + q + j] * h[t - 1 - j];
+                }
+                val
+            }
+            GarchModelType::EGARCH => {
+                let e_abs_z = (2.0_f64 / PI).sqrt();
+                let mut log_val = omega;
+                for i in 0..q.min(t) {
+                    let z = eps[t - 1 - i] / h[t - 1 - i].sqrt().max(1e-10);
+                    log_val += params[2 + i] * (z.abs() - e_abs_z) + params[2 + q + i] * z;
+                }
+                for j in 0..p.min(t) {
+                    log_val += params[2 + 2 * q + j] * h[t - 1 - j].ln();
+                }
+                log_val.exp().min(1e10)
+            }
+            GarchModelType::APARCH => {
+                let (omega, alphas, gammas, betas, delta) = aparch_parts(params, p, q);
+                aparch_power_at(t, eps, h, omega, &alphas, &gammas, &betas, delta)
+                    .max(1e-10)
+                    .powf(2.0 / delta)
+            }
+            GarchModelType::FIGARCH => {
+                let beta = figarch_parts(params, p, q).3;
+                let level = omega / (1.0 - beta);
+                figarch_at(t, eps, &self.lambdas, &self.tail, level, self.backcast)
+            }
+            GarchModelType::HARCH => {
+                let alphas = &params[2..2 + r.lags.len()];
+                harch_at(t, eps, omega, alphas, &r.lags, self.backcast)
+            }
+            GarchModelType::ConstantVariance => omega,
+        };
+        val.max(1e-10)
+    }
+}
+
 // ─── Shared helpers for fit methods ──────────────────────────────────────────
 
 fn check_finite(y: &Array1<f64>) -> Result<(), GreenersError> {
@@ -800,6 +1201,7 @@ impl GARCH {
                 model_type: GarchModelType::GARCH,
                 dist: GarchDist::Normal,
                 variable_names: garch_param_names(p, q),
+                lags: Vec::new(),
                 log_likelihood,
                 n_iter,
                 converged,
@@ -883,6 +1285,7 @@ impl GARCH {
                 model_type: GarchModelType::GARCH,
                 dist: GarchDist::StudentT,
                 variable_names: names,
+                lags: Vec::new(),
                 log_likelihood,
                 n_iter,
                 converged,
@@ -1050,6 +1453,7 @@ impl EGARCH {
                     GarchDist::Normal
                 },
                 variable_names: names,
+                lags: Vec::new(),
                 log_likelihood,
                 n_iter,
                 converged,
@@ -1213,6 +1617,552 @@ impl GJRGARCH {
                     GarchDist::Normal
                 },
                 variable_names: names,
+                lags: Vec::new(),
+                log_likelihood,
+                n_iter,
+                converged,
+            },
+            &neg_ll,
+        ))
+    }
+}
+
+// ─── FIGARCH struct ──────────────────────────────────────────────────────────
+
+/// FIGARCH(p,d,q) model — long-memory volatility (Baillie, Bollerslev &
+/// Mikkelsen 1996):
+/// `(1 - beta L) sigma_t^2 = omega + [(1 - beta L) - (1 - phi L)(1 - L)^d] eps_t^2`.
+///
+/// `q` (0 or 1) adds `phi`, `p` (0 or 1) adds `beta`. The ARCH(inf)
+/// representation is truncated at 1000 lags, with the pre-sample squared
+/// shocks set to their sample mean.
+pub struct FIGARCH;
+
+impl FIGARCH {
+    /// Fit FIGARCH(p,d,q) with Normal errors
+    pub fn fit(y: &Array1<f64>, p: usize, q: usize) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, p, q, false)
+    }
+
+    /// Fit FIGARCH(p,d,q) with Student-t errors (df estimated via MLE)
+    pub fn fit_t(y: &Array1<f64>, p: usize, q: usize) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, p, q, true)
+    }
+
+    fn fit_inner(
+        y: &Array1<f64>,
+        p: usize,
+        q: usize,
+        use_t: bool,
+    ) -> Result<GarchResult, GreenersError> {
+        check_finite(y)?;
+        if y.len() < 10 {
+            return Err(GreenersError::InvalidOperation(
+                "Need at least 10 observations".into(),
+            ));
+        }
+        if p > 1 || q > 1 {
+            return Err(GreenersError::InvalidOperation(
+                "FIGARCH supports p and q of 0 or 1".into(),
+            ));
+        }
+
+        let (mean_y, var_y) = sample_moments(y);
+        // params: mu, omega, (phi), d, (beta), (nu)
+        let n_base = 3 + p + q;
+        let n_params = if use_t { n_base + 1 } else { n_base };
+        let mut init = vec![0.0; n_params];
+        init[0] = mean_y;
+        init[1] = 0.05 * var_y;
+        if q == 1 {
+            init[2] = 0.1;
+        }
+        init[2 + q] = 0.4;
+        if p == 1 {
+            init[3 + q] = 0.3;
+        }
+        if use_t {
+            init[n_params - 1] = 8.0;
+        }
+
+        let y_clone = y.clone();
+        let neg_ll = move |params: &[f64]| -> f64 {
+            let (omega, phi, d, beta) = figarch_parts(params, p, q);
+            let nu = if use_t { params[n_base] } else { 0.0 };
+            if use_t && nu <= 2.0 {
+                return 1e18;
+            }
+            let eps: Vec<f64> = y_clone.iter().map(|v| v - params[0]).collect();
+            let var_init = eps.iter().map(|e| e * e).sum::<f64>() / eps.len() as f64;
+            let h = figarch_conditional_variance(&eps, omega, phi, d, beta, var_init);
+            let mut ll = 0.0;
+            for (e, ht) in eps.iter().zip(h.iter()) {
+                if use_t {
+                    ll += student_t_log_pdf(*e, *ht, nu);
+                } else {
+                    ll += normal_log_pdf(*e, *ht);
+                }
+            }
+            if ll.is_finite() {
+                -ll
+            } else {
+                1e18
+            }
+        };
+
+        // 0 <= d <= 1, 0 <= phi <= (1 - d) / 2, 0 <= beta <= d + phi
+        let constrain = move |params: &mut [f64]| {
+            params[1] = params[1].max(1e-10);
+            params[2 + q] = params[2 + q].clamp(0.0, 1.0);
+            let d = params[2 + q];
+            let phi = if q == 1 {
+                params[2] = params[2].clamp(0.0, (1.0 - d) / 2.0);
+                params[2]
+            } else {
+                0.0
+            };
+            if p == 1 {
+                params[3 + q] = params[3 + q].clamp(0.0, (d + phi).min(0.999));
+            }
+            if use_t {
+                params[n_base] = params[n_base].clamp(2.1, 100.0);
+            }
+        };
+
+        let (opt_params, n_iter, converged) = optimize(&neg_ll, &init, 500, constrain);
+
+        let (omega, phi, d, beta) = figarch_parts(&opt_params, p, q);
+        let final_eps: Vec<f64> = y.iter().map(|v| v - opt_params[0]).collect();
+        let var_init = final_eps.iter().map(|e| e * e).sum::<f64>() / final_eps.len() as f64;
+        let h = figarch_conditional_variance(&final_eps, omega, phi, d, beta, var_init);
+        let log_likelihood = -neg_ll(&opt_params);
+
+        let mut names = vec!["mu".to_string(), "omega".to_string()];
+        if q == 1 {
+            names.push("phi".to_string());
+        }
+        names.push("d".to_string());
+        if p == 1 {
+            names.push("beta".to_string());
+        }
+        if use_t {
+            names.push("nu".to_string());
+        }
+
+        Ok(build_result(
+            &opt_params,
+            y.len(),
+            BuildResultArgs {
+                residuals: Array1::from_vec(final_eps),
+                cond_var: Array1::from_vec(h),
+                p,
+                q,
+                model_type: GarchModelType::FIGARCH,
+                dist: if use_t {
+                    GarchDist::StudentT
+                } else {
+                    GarchDist::Normal
+                },
+                variable_names: names,
+                lags: Vec::new(),
+                log_likelihood,
+                n_iter,
+                converged,
+            },
+            &neg_ll,
+        ))
+    }
+}
+
+// ─── APARCH struct ───────────────────────────────────────────────────────────
+
+/// APARCH(p,q) model — asymmetric power ARCH (Ding, Granger & Engle 1993):
+/// `sigma_t^delta = omega + sum alpha_i (|eps_{t-i}| - gamma_i eps_{t-i})^delta
+/// + sum beta_j sigma_{t-j}^delta`, with the power `delta` estimated.
+pub struct APARCH;
+
+impl APARCH {
+    /// Fit APARCH(p,q) with Normal errors
+    pub fn fit(y: &Array1<f64>, p: usize, q: usize) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, p, q, false)
+    }
+
+    /// Fit APARCH(p,q) with Student-t errors (df estimated via MLE)
+    pub fn fit_t(y: &Array1<f64>, p: usize, q: usize) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, p, q, true)
+    }
+
+    fn fit_inner(
+        y: &Array1<f64>,
+        p: usize,
+        q: usize,
+        use_t: bool,
+    ) -> Result<GarchResult, GreenersError> {
+        check_finite(y)?;
+        if y.len() < 10 {
+            return Err(GreenersError::InvalidOperation(
+                "Need at least 10 observations".into(),
+            ));
+        }
+        if q == 0 {
+            return Err(GreenersError::InvalidOperation("q must be >= 1".into()));
+        }
+
+        let (mean_y, var_y) = sample_moments(y);
+        // params: mu, omega, alpha_1..q, gamma_1..q, beta_1..p, delta, (nu)
+        let delta_at = 2 + 2 * q + p;
+        let n_base = delta_at + 1;
+        let n_params = if use_t { n_base + 1 } else { n_base };
+        let mut init = vec![0.0; n_params];
+        init[0] = mean_y;
+        init[1] = 0.1 * var_y.powf(0.75);
+        for i in 0..q {
+            init[2 + i] = 0.05;
+            init[2 + q + i] = 0.1;
+        }
+        for j in 0..p {
+            init[2 + 2 * q + j] = 0.85 / p.max(1) as f64;
+        }
+        init[delta_at] = 1.5;
+        if use_t {
+            init[n_params - 1] = 8.0;
+        }
+
+        let y_clone = y.clone();
+        let neg_ll = move |params: &[f64]| -> f64 {
+            let (omega, alphas, gammas, betas, delta) = aparch_parts(params, p, q);
+            let nu = if use_t { params[n_base] } else { 0.0 };
+            if use_t && nu <= 2.0 {
+                return 1e18;
+            }
+            let eps: Vec<f64> = y_clone.iter().map(|v| v - params[0]).collect();
+            let var_init = eps.iter().map(|e| e * e).sum::<f64>() / eps.len() as f64;
+            let h =
+                aparch_conditional_variance(&eps, omega, &alphas, &gammas, &betas, delta, var_init);
+            let mut ll = 0.0;
+            for (e, ht) in eps.iter().zip(h.iter()) {
+                if use_t {
+                    ll += student_t_log_pdf(*e, *ht, nu);
+                } else {
+                    ll += normal_log_pdf(*e, *ht);
+                }
+            }
+            if ll.is_finite() {
+                -ll
+            } else {
+                1e18
+            }
+        };
+
+        // Positivity, |gamma| < 1, and persistence sum alpha kappa + sum beta < 1
+        let constrain = move |params: &mut [f64]| {
+            params[1] = params[1].max(1e-10);
+            params[delta_at] = params[delta_at].clamp(0.1, 4.0);
+            let delta = params[delta_at];
+            let nu = if use_t {
+                params[n_base] = params[n_base].clamp((delta + 0.1).max(2.1), 100.0);
+                Some(params[n_base])
+            } else {
+                None
+            };
+            for i in 0..q {
+                params[2 + i] = params[2 + i].max(0.0);
+                params[2 + q + i] = params[2 + q + i].clamp(-0.999, 0.999);
+            }
+            for j in 0..p {
+                params[2 + 2 * q + j] = params[2 + 2 * q + j].max(0.0);
+            }
+            let persistence = (0..q)
+                .map(|i| params[2 + i] * aparch_kappa(params[2 + q + i], delta, nu))
+                .sum::<f64>()
+                + (0..p).map(|j| params[2 + 2 * q + j]).sum::<f64>();
+            if persistence >= 0.9999 {
+                let scale = 0.999 / persistence;
+                for i in 0..q {
+                    params[2 + i] *= scale;
+                }
+                for j in 0..p {
+                    params[2 + 2 * q + j] *= scale;
+                }
+            }
+        };
+
+        let (opt_params, n_iter, converged) = optimize(&neg_ll, &init, 500, constrain);
+
+        let (omega, alphas, gammas, betas, delta) = aparch_parts(&opt_params, p, q);
+        let final_eps: Vec<f64> = y.iter().map(|v| v - opt_params[0]).collect();
+        let var_init = final_eps.iter().map(|e| e * e).sum::<f64>() / final_eps.len() as f64;
+        let h = aparch_conditional_variance(
+            &final_eps, omega, &alphas, &gammas, &betas, delta, var_init,
+        );
+        let log_likelihood = -neg_ll(&opt_params);
+
+        let mut names = vec!["mu".to_string(), "omega".to_string()];
+        for i in 0..q {
+            names.push(format!("alpha[{}]", i + 1));
+        }
+        for i in 0..q {
+            names.push(format!("gamma[{}]", i + 1));
+        }
+        for j in 0..p {
+            names.push(format!("beta[{}]", j + 1));
+        }
+        names.push("delta".to_string());
+        if use_t {
+            names.push("nu".to_string());
+        }
+
+        Ok(build_result(
+            &opt_params,
+            y.len(),
+            BuildResultArgs {
+                residuals: Array1::from_vec(final_eps),
+                cond_var: Array1::from_vec(h),
+                p,
+                q,
+                model_type: GarchModelType::APARCH,
+                dist: if use_t {
+                    GarchDist::StudentT
+                } else {
+                    GarchDist::Normal
+                },
+                variable_names: names,
+                lags: Vec::new(),
+                log_likelihood,
+                n_iter,
+                converged,
+            },
+            &neg_ll,
+        ))
+    }
+}
+
+// ─── HARCH struct ────────────────────────────────────────────────────────────
+
+/// HARCH model — heterogeneous ARCH (Müller et al. 1997):
+/// `sigma_t^2 = omega + sum_k alpha_k (1/l_k sum_{i=1}^{l_k} eps_{t-i})^2`,
+/// one term per horizon `l_k` (e.g. 1, 5, 22 for daily, weekly, monthly).
+pub struct HARCH;
+
+impl HARCH {
+    /// Fit HARCH with Normal errors
+    pub fn fit(y: &Array1<f64>, lags: &[usize]) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, lags, false)
+    }
+
+    /// Fit HARCH with Student-t errors (df estimated via MLE)
+    pub fn fit_t(y: &Array1<f64>, lags: &[usize]) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, lags, true)
+    }
+
+    fn fit_inner(
+        y: &Array1<f64>,
+        lags: &[usize],
+        use_t: bool,
+    ) -> Result<GarchResult, GreenersError> {
+        check_finite(y)?;
+        if y.len() < 10 {
+            return Err(GreenersError::InvalidOperation(
+                "Need at least 10 observations".into(),
+            ));
+        }
+        if lags.is_empty() || lags[0] == 0 || lags.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(GreenersError::InvalidOperation(
+                "HARCH lags must be positive and strictly increasing".into(),
+            ));
+        }
+
+        let k = lags.len();
+        let (mean_y, var_y) = sample_moments(y);
+        // params: mu, omega, alpha_1..k, (nu)
+        let n_base = 2 + k;
+        let n_params = if use_t { n_base + 1 } else { n_base };
+        let mut init = vec![0.0; n_params];
+        init[0] = mean_y;
+        init[1] = 0.5 * var_y;
+        for i in 0..k {
+            init[2 + i] = 0.3 / k as f64;
+        }
+        if use_t {
+            init[n_params - 1] = 8.0;
+        }
+
+        let y_clone = y.clone();
+        let lag_vec = lags.to_vec();
+        let neg_ll = move |params: &[f64]| -> f64 {
+            let nu = if use_t { params[n_base] } else { 0.0 };
+            if use_t && nu <= 2.0 {
+                return 1e18;
+            }
+            let eps: Vec<f64> = y_clone.iter().map(|v| v - params[0]).collect();
+            let var_init = eps.iter().map(|e| e * e).sum::<f64>() / eps.len() as f64;
+            let h =
+                harch_conditional_variance(&eps, params[1], &params[2..n_base], &lag_vec, var_init);
+            let mut ll = 0.0;
+            for (e, ht) in eps.iter().zip(h.iter()) {
+                if use_t {
+                    ll += student_t_log_pdf(*e, *ht, nu);
+                } else {
+                    ll += normal_log_pdf(*e, *ht);
+                }
+            }
+            if ll.is_finite() {
+                -ll
+            } else {
+                1e18
+            }
+        };
+
+        let constrain = move |params: &mut [f64]| {
+            params[1] = params[1].max(1e-10);
+            for i in 0..k {
+                params[2 + i] = params[2 + i].max(0.0);
+            }
+            let sum_a: f64 = params[2..n_base].iter().sum();
+            if sum_a >= 0.9999 {
+                let scale = 0.999 / sum_a;
+                for i in 0..k {
+                    params[2 + i] *= scale;
+                }
+            }
+            if use_t {
+                params[n_base] = params[n_base].clamp(2.1, 100.0);
+            }
+        };
+
+        let (opt_params, n_iter, converged) = optimize(&neg_ll, &init, 500, constrain);
+
+        let final_eps: Vec<f64> = y.iter().map(|v| v - opt_params[0]).collect();
+        let var_init = final_eps.iter().map(|e| e * e).sum::<f64>() / final_eps.len() as f64;
+        let h = harch_conditional_variance(
+            &final_eps,
+            opt_params[1],
+            &opt_params[2..n_base],
+            lags,
+            var_init,
+        );
+        let log_likelihood = -neg_ll(&opt_params);
+
+        let mut names = vec!["mu".to_string(), "omega".to_string()];
+        for lag in lags {
+            names.push(format!("alpha[{}]", lag));
+        }
+        if use_t {
+            names.push("nu".to_string());
+        }
+
+        Ok(build_result(
+            &opt_params,
+            y.len(),
+            BuildResultArgs {
+                residuals: Array1::from_vec(final_eps),
+                cond_var: Array1::from_vec(h),
+                p: 0,
+                q: k,
+                model_type: GarchModelType::HARCH,
+                dist: if use_t {
+                    GarchDist::StudentT
+                } else {
+                    GarchDist::Normal
+                },
+                variable_names: names,
+                lags: lags.to_vec(),
+                log_likelihood,
+                n_iter,
+                converged,
+            },
+            &neg_ll,
+        ))
+    }
+}
+
+// ─── Constant variance ───────────────────────────────────────────────────────
+
+/// Constant variance `sigma_t^2 = sigma2` — the homoskedastic benchmark.
+pub struct ConstantVariance;
+
+impl ConstantVariance {
+    /// Fit with Normal errors
+    pub fn fit(y: &Array1<f64>) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, false)
+    }
+
+    /// Fit with Student-t errors (df estimated via MLE)
+    pub fn fit_t(y: &Array1<f64>) -> Result<GarchResult, GreenersError> {
+        Self::fit_inner(y, true)
+    }
+
+    fn fit_inner(y: &Array1<f64>, use_t: bool) -> Result<GarchResult, GreenersError> {
+        check_finite(y)?;
+        if y.len() < 10 {
+            return Err(GreenersError::InvalidOperation(
+                "Need at least 10 observations".into(),
+            ));
+        }
+
+        // params: mu, sigma2, (nu); Normal starts at the MLE
+        let (mean_y, var_y) = sample_moments(y);
+        let mut init = vec![mean_y, var_y];
+        if use_t {
+            init.push(8.0);
+        }
+
+        let y_clone = y.clone();
+        let neg_ll = move |params: &[f64]| -> f64 {
+            let (mu, sigma2) = (params[0], params[1]);
+            if use_t && params[2] <= 2.0 {
+                return 1e18;
+            }
+            let ll: f64 = y_clone
+                .iter()
+                .map(|v| {
+                    if use_t {
+                        student_t_log_pdf(v - mu, sigma2, params[2])
+                    } else {
+                        normal_log_pdf(v - mu, sigma2)
+                    }
+                })
+                .sum();
+            if ll.is_finite() {
+                -ll
+            } else {
+                1e18
+            }
+        };
+
+        let constrain = move |params: &mut [f64]| {
+            params[1] = params[1].max(1e-10);
+            if use_t {
+                params[2] = params[2].clamp(2.1, 100.0);
+            }
+        };
+
+        let (opt_params, n_iter, converged) = optimize(&neg_ll, &init, 500, constrain);
+        let residuals: Array1<f64> = y.mapv(|v| v - opt_params[0]);
+        let cond_var = Array1::from_elem(y.len(), opt_params[1]);
+        let log_likelihood = -neg_ll(&opt_params);
+
+        let mut names = vec!["mu".to_string(), "sigma2".to_string()];
+        if use_t {
+            names.push("nu".to_string());
+        }
+
+        Ok(build_result(
+            &opt_params,
+            y.len(),
+            BuildResultArgs {
+                residuals,
+                cond_var,
+                p: 0,
+                q: 0,
+                model_type: GarchModelType::ConstantVariance,
+                dist: if use_t {
+                    GarchDist::StudentT
+                } else {
+                    GarchDist::Normal
+                },
+                variable_names: names,
+                lags: Vec::new(),
                 log_likelihood,
                 n_iter,
                 converged,
@@ -18,10 +18,11 @@
 
 use greeners_core::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Hawkes process estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct HawkesResult {
     /// Baseline intensity (mu)
     pub mu: f64,
@@ -15,10 +15,11 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Johansen test with structural breaks.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct JohansenBreakResult {
     /// Trace statistics for each rank r = 0, 1, ..., k-1
     pub trace_stats: Array1<f64>,
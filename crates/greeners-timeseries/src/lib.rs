@@ -4,6 +4,10 @@
 // Items with names duplicated across modules remain namespaced.
 
 pub use arima::{ArimaOrder, ArimaResult, SeasonalOrder, ARIMA};
+pub use auto_arima::{
+    ArimaCandidate, ArimaMethod, AutoArima, AutoArimaConfig, AutoArimaResult, InformationCriterion,
+    SeasonalTest, UnitRootTest,
+};
 pub use autoreg::{ARDLResult, AutoReg, AutoRegResult, ARDL};
 pub use dcc_garch::{DccGarchResult, DCCGARCH};
 pub use decomposition::{Decomposition, DecompositionResult};
@@ -12,17 +16,25 @@ pub use dynamic_factor::{DynamicFactor, DynamicFactorResult};
 pub use ets::{
     ETSError, ETSModel, ETSModelResult, ETSResult, ETSSeasonal, ETSTrend, ExponentialSmoothing,
 };
-pub use garch::{GarchDist, GarchModelType, GarchResult, EGARCH, GARCH, GJRGARCH};
+pub use garch::{
+    ConstantVariance, GarchDist, GarchModelType, GarchResult, APARCH, EGARCH, FIGARCH, GARCH,
+    GJRGARCH, HARCH,
+};
 pub use hawkes::{Hawkes, HawkesResult};
 pub use johansen_break::{JohansenBreak, JohansenBreakResult};
 pub use lstm::{LstmResult, LSTM};
 pub use markov::{MarkovSwitching, MarkovSwitchingResult};
 pub use markov_autoreg::{MarkovAutoregResult, MarkovAutoregression};
 pub use midas::{Midas, MidasResult};
+pub use mlemodel::{
+    DiffuseFilterResult, DiffuseKalmanFilter, DiffuseSmootherResult, MleConfig, MleModel,
+    MleResult, ParamTransform, SimulationSmoother, StateInit, StateSpaceSystem, SystemMatrices,
+};
 pub use ms_var::{MsVarResult, MSVAR};
 pub use mstl::{MSTLResult, MSTL};
 pub use nardl::{NardlResult, NARDL};
 pub use quantile_var::{QuantileVAR, QuantileVarResult};
+pub use sarimax::{SarimaxConfig, SarimaxForecast, SarimaxInitialization, SarimaxResult, SARIMAX};
 pub use setar::{SetarResult, SETAR};
 pub use spectral::{SpectralClustering, SpectralResult};
 pub use statespace::{
@@ -47,6 +59,7 @@ pub use vecm::{VecmResult, VECM};
 pub use wavelet::{ModwtResult, MODWT};
 
 pub mod arima;
+pub mod auto_arima;
 pub mod autoreg;
 pub mod dcc_garch;
 pub mod decomposition;
@@ -60,10 +73,12 @@ pub mod lstm;
 pub mod markov;
 pub mod markov_autoreg;
 pub mod midas;
+pub mod mlemodel;
 pub mod ms_var;
 pub mod mstl;
 pub mod nardl;
 pub mod quantile_var;
+pub mod sarimax;
 pub mod setar;
 pub mod spectral;
 pub mod statespace;
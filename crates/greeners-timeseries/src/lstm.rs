@@ -16,12 +16,13 @@
 //! Training: truncated backpropagation through time (BPTT).
 //! Input: univariate series, converted to sliding windows.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of LSTM estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LstmResult {
     /// In-sample fitted values
     pub fitted: Array1<f64>,
@@ -103,6 +104,8 @@ impl LSTM {
     /// * `learning_rate` - Learning rate (default 0.01)
     /// * `n_epochs` - Training epochs (default 100)
     /// * `n_forecast` - Number of steps to forecast (default 5)
+    /// * `seed` - Random seed for the weight initialisation (`None` uses the
+    ///   default seed)
     pub fn fit(
         y: &Array1<f64>,
         n_hidden: Option<usize>,
@@ -110,6 +113,7 @@ impl LSTM {
         learning_rate: Option<f64>,
         n_epochs: Option<usize>,
         n_forecast: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<LstmResult, GreenersError> {
         let n = y.len();
         if n < 20 {
@@ -149,11 +153,12 @@ impl LSTM {
         // For simplicity, we use a compact representation
         let input_size = 1 + 1; // h_{t-1} + x_t (univariate)
 
-        let mut w_f = Self::init_weights(input_size);
-        let mut w_i = Self::init_weights(input_size);
-        let mut w_g = Self::init_weights(input_size);
-        let mut w_o = Self::init_weights(input_size);
-        let mut w_y = Self::init_weights(hidden + 1); // h + bias
+        let mut rng = GreenersRng::new(seed);
+        let mut w_f = Self::init_weights(&mut rng, input_size);
+        let mut w_i = Self::init_weights(&mut rng, input_size);
+        let mut w_g = Self::init_weights(&mut rng, input_size);
+        let mut w_o = Self::init_weights(&mut rng, input_size);
+        let mut w_y = Self::init_weights(&mut rng, hidden + 1); // h + bias
 
         // Training loop
         let mut final_mse = 0.0;
@@ -324,14 +329,9 @@ impl LSTM {
         })
     }
 
-    fn init_weights(size: usize) -> Vec<f64> {
-        (0..size)
-            .map(|i| {
-                let seed = (i as u64).wrapping_mul(12345) + 67890;
-                let val = ((seed % 1000) as f64 / 1000.0) - 0.5;
-                val * 0.1
-            })
-            .collect()
+    /// Uniform weights on [-0.05, 0.05).
+    fn init_weights(rng: &mut GreenersRng, size: usize) -> Vec<f64> {
+        (0..size).map(|_| (rng.uniform() - 0.5) * 0.1).collect()
     }
 
     fn dot(w: &[f64], x: &[f64]) -> f64 {
@@ -1,10 +1,11 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Markov switching model result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MarkovSwitchingResult {
     /// Regime-specific parameters (intercept + AR coefficients per regime).
     pub regime_params: Vec<Array1<f64>>,
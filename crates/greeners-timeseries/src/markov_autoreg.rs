@@ -1,6 +1,7 @@
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Markov-Switching Autoregression model.
@@ -10,7 +11,7 @@ use std::fmt;
 pub struct MarkovAutoregression;
 
 /// Result of fitting a Markov-Switching AR(p) model.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MarkovAutoregResult {
     /// Regime-specific intercepts (k).
     pub regime_means: Array1<f64>,
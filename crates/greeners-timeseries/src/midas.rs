@@ -15,11 +15,12 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{array, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Result of MIDAS regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MidasResult {
     /// Intercept
     pub alpha: f64,
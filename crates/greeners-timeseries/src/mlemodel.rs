@@ -0,0 +1,1155 @@
+//! General linear Gaussian state-space models estimated by maximum likelihood.
+//!
+//! A model implements [`MleModel`]: it maps a parameter vector to the
+//! (possibly time-varying) system
+//!
+//! ```text
+//! y_t     = d_t + Z_t a_t + eps_t,          eps_t ~ N(0, H_t)
+//! a_{t+1} = c_t + T_t a_t + R_t eta_t,      eta_t ~ N(0, Q_t)
+//! ```
+//!
+//! and an initial distribution for `a_1`, and gets the likelihood, the
+//! filtered and smoothed states, BFGS estimation over unconstrained
+//! parameters and posterior state draws for free.
+//!
+//! Filtering uses the univariate treatment of Koopman & Durbin (2000): the
+//! elements of `y_t` are processed one at a time, after a Cholesky
+//! transformation when `H_t` is not diagonal, so missing elements are simply
+//! skipped. Diffuse initial states are handled exactly (Durbin & Koopman
+//! 2012, ch. 5): the diffuse part `P_inf` of the state covariance is
+//! carried separately until it vanishes, and the diffuse log-likelihood
+//! drops the prediction-error term of the periods that identify it. The
+//! simulation smoother is the mean-corrected algorithm of Durbin & Koopman
+//! (2002).
+
+use crate::statespace::StateSpaceModel;
+use greeners_core::linalg::{LinalgCholesky as _, LinalgEigh as _, LinalgInverse as _};
+use greeners_core::{GreenersError, GreenersRng, UPLO};
+use ndarray::{Array1, Array2, Axis};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal as NormalDist};
+use std::fmt;
+
+/// Forecast-error variance of the diffuse part below which a step is regular
+const DIFFUSE_TOL: f64 = 1e-8;
+const LN_2PI: f64 = 1.8378770664093453;
+
+/// System matrices of one period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemMatrices {
+    /// Z (k_endog x k_states)
+    pub design: Array2<f64>,
+    /// d (k_endog)
+    pub obs_intercept: Array1<f64>,
+    /// H (k_endog x k_endog)
+    pub obs_cov: Array2<f64>,
+    /// T (k_states x k_states)
+    pub transition: Array2<f64>,
+    /// c (k_states)
+    pub state_intercept: Array1<f64>,
+    /// R (k_states x k_posdef)
+    pub selection: Array2<f64>,
+    /// Q (k_posdef x k_posdef)
+    pub state_cov: Array2<f64>,
+}
+
+impl SystemMatrices {
+    /// System without intercepts.
+    pub fn new(
+        design: Array2<f64>,
+        obs_cov: Array2<f64>,
+        transition: Array2<f64>,
+        selection: Array2<f64>,
+        state_cov: Array2<f64>,
+    ) -> Self {
+        SystemMatrices {
+            obs_intercept: Array1::zeros(design.nrows()),
+            state_intercept: Array1::zeros(transition.nrows()),
+            design,
+            obs_cov,
+            transition,
+            selection,
+            state_cov,
+        }
+    }
+
+    fn check(&self, k_endog: usize, k_states: usize) -> Result<(), GreenersError> {
+        let k_posdef = self.selection.ncols();
+        let ok = self.design.dim() == (k_endog, k_states)
+            && self.obs_intercept.len() == k_endog
+            && self.obs_cov.dim() == (k_endog, k_endog)
+            && self.transition.dim() == (k_states, k_states)
+            && self.state_intercept.len() == k_states
+            && self.selection.nrows() == k_states
+            && self.state_cov.dim() == (k_posdef, k_posdef);
+        if ok {
+            Ok(())
+        } else {
+            Err(GreenersError::ShapeMismatch(format!(
+                "System matrices do not match {} observed series and {} states",
+                k_endog, k_states
+            )))
+        }
+    }
+}
+
+/// The fixed-matrix model `s_t = F s_{t-1} + R u_t`, `y_t = H s_t + e_t`.
+impl From<&StateSpaceModel> for SystemMatrices {
+    fn from(model: &StateSpaceModel) -> Self {
+        SystemMatrices::new(
+            model.h.clone(),
+            model.r_obs.clone(),
+            model.f.clone(),
+            model.r.clone(),
+            model.q.clone(),
+        )
+    }
+}
+
+/// Distribution of the initial state `a_1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StateInit {
+    /// `a_1 ~ N(mean, cov)`
+    Known { mean: Array1<f64>, cov: Array2<f64> },
+    /// Unconditional distribution implied by the first period's `c`, `T`, `RQR'`
+    Stationary,
+    /// Exact diffuse prior on every state
+    Diffuse,
+    /// Exact diffuse prior on the flagged states; the others are
+    /// `N(mean, cov)` (rows and columns of diffuse states are ignored)
+    Mixed {
+        mean: Array1<f64>,
+        cov: Array2<f64>,
+        diffuse: Vec<bool>,
+    },
+}
+
+/// Initial mean `a_1`, finite covariance `P_star` and diffuse part `P_inf`.
+type InitialMoments = (Array1<f64>, Array2<f64>, Array2<f64>);
+
+/// A model evaluated at one parameter vector: system matrices and initial state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSpaceSystem {
+    /// One entry per period, or a single entry for a time-invariant system
+    pub matrices: Vec<SystemMatrices>,
+    pub init: StateInit,
+}
+
+impl StateSpaceSystem {
+    /// Time-invariant system.
+    pub fn new(matrices: SystemMatrices, init: StateInit) -> Self {
+        StateSpaceSystem {
+            matrices: vec![matrices],
+            init,
+        }
+    }
+
+    /// Matrices of period `t` (the last entry past the end).
+    pub fn at(&self, t: usize) -> &SystemMatrices {
+        &self.matrices[t.min(self.matrices.len() - 1)]
+    }
+
+    pub fn k_states(&self) -> usize {
+        self.matrices.first().map_or(0, |m| m.transition.nrows())
+    }
+
+    fn validate(&self, y: &[Array1<f64>]) -> Result<(), GreenersError> {
+        if self.matrices.is_empty() {
+            return Err(GreenersError::InvalidOperation(
+                "State space system has no matrices".into(),
+            ));
+        }
+        if self.matrices.len() != 1 && self.matrices.len() < y.len() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "{} periods of system matrices for {} observations",
+                self.matrices.len(),
+                y.len()
+            )));
+        }
+        let k_states = self.k_states();
+        for (t, obs) in y.iter().enumerate() {
+            let sys = self.at(t);
+            if t < self.matrices.len() {
+                sys.check(obs.len(), k_states)?;
+            } else if obs.len() != sys.design.nrows() {
+                // Time-invariant matrices were checked once; every period still
+                // has to carry the same number of observed series
+                return Err(GreenersError::ShapeMismatch(format!(
+                    "Observation {} has {} series, expected {}",
+                    t,
+                    obs.len(),
+                    sys.design.nrows()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Moments of the initial state implied by `init`.
+    fn initial(&self) -> Result<InitialMoments, GreenersError> {
+        let m = self.k_states();
+        match &self.init {
+            StateInit::Known { mean, cov } => {
+                if mean.len() != m || cov.dim() != (m, m) {
+                    return Err(GreenersError::ShapeMismatch(
+                        "Initial state has the wrong dimension".into(),
+                    ));
+                }
+                Ok((mean.clone(), cov.clone(), Array2::zeros((m, m))))
+            }
+            StateInit::Stationary => {
+                let sys = self.at(0);
+                let rqr = sys.selection.dot(&sys.state_cov).dot(&sys.selection.t());
+                let cov = solve_discrete_lyapunov(&sys.transition, &rqr)?;
+                let mean = (Array2::<f64>::eye(m) - &sys.transition)
+                    .inv()?
+                    .dot(&sys.state_intercept);
+                Ok((mean, cov, Array2::zeros((m, m))))
+            }
+            StateInit::Diffuse => Ok((Array1::zeros(m), Array2::zeros((m, m)), Array2::eye(m))),
+            StateInit::Mixed { mean, cov, diffuse } => {
+                if mean.len() != m || cov.dim() != (m, m) || diffuse.len() != m {
+                    return Err(GreenersError::ShapeMismatch(
+                        "Initial state has the wrong dimension".into(),
+                    ));
+                }
+                let p_star = Array2::from_shape_fn((m, m), |(i, j)| {
+                    if diffuse[i] || diffuse[j] {
+                        0.0
+                    } else {
+                        cov[[i, j]]
+                    }
+                });
+                let p_inf =
+                    Array2::from_shape_fn(
+                        (m, m),
+                        |(i, j)| {
+                            if i == j && diffuse[i] {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        },
+                    );
+                Ok((mean.clone(), p_star, p_inf))
+            }
+        }
+    }
+}
+
+/// Map from an unconstrained optimizer value to a model parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ParamTransform {
+    Identity,
+    /// `exp(u)`, for variances and other positive parameters
+    Positive,
+    /// Logistic map onto `(lower, upper)`
+    Bounded(f64, f64),
+    /// The next `k` parameters are the coefficients `phi` of a stationary
+    /// polynomial `1 - phi_1 L - ... - phi_k L^k`, through partial
+    /// autocorrelations (Monahan 1984)
+    Stationary(usize),
+    /// The next `k` parameters are the coefficients `theta` of an invertible
+    /// polynomial `1 + theta_1 L + ... + theta_k L^k`
+    Invertible(usize),
+}
+
+impl ParamTransform {
+    /// Number of parameters covered by the transform.
+    pub fn width(&self) -> usize {
+        match self {
+            ParamTransform::Stationary(k) | ParamTransform::Invertible(k) => *k,
+            _ => 1,
+        }
+    }
+}
+
+/// Apply `transforms` (in order, each covering `width()` values) to `u`.
+pub fn constrain_params(
+    transforms: &[ParamTransform],
+    u: &[f64],
+) -> Result<Vec<f64>, GreenersError> {
+    check_transforms(transforms, u.len())?;
+    let mut out = Vec::with_capacity(u.len());
+    let mut at = 0;
+    for tr in transforms {
+        let block = &u[at..at + tr.width()];
+        match *tr {
+            ParamTransform::Identity => out.push(block[0]),
+            ParamTransform::Positive => out.push(block[0].exp()),
+            ParamTransform::Bounded(lo, hi) => out.push(lo + (hi - lo) / (1.0 + (-block[0]).exp())),
+            ParamTransform::Stationary(_) => out.extend(constrain_stationary(block)),
+            ParamTransform::Invertible(_) => {
+                out.extend(constrain_stationary(block).into_iter().map(|v| -v))
+            }
+        }
+        at += tr.width();
+    }
+    Ok(out)
+}
+
+/// Inverse of [`constrain_params`]. Values outside the admissible region are
+/// pulled to its edge (bounds, positivity) or reset to zero (polynomials).
+pub fn unconstrain_params(
+    transforms: &[ParamTransform],
+    params: &[f64],
+) -> Result<Vec<f64>, GreenersError> {
+    check_transforms(transforms, params.len())?;
+    let mut out = Vec::with_capacity(params.len());
+    let mut at = 0;
+    for tr in transforms {
+        let k = tr.width();
+        let block = &params[at..at + k];
+        match *tr {
+            ParamTransform::Identity => out.push(block[0]),
+            ParamTransform::Positive => out.push(block[0].max(1e-300).ln()),
+            ParamTransform::Bounded(lo, hi) => {
+                let eps = 1e-12 * (hi - lo);
+                let x = block[0].clamp(lo + eps, hi - eps);
+                out.push(((x - lo) / (hi - x)).ln());
+            }
+            ParamTransform::Stationary(_) => {
+                out.extend(unconstrain_stationary(block).unwrap_or_else(|| vec![0.0; k]))
+            }
+            ParamTransform::Invertible(_) => {
+                let neg: Vec<f64> = block.iter().map(|v| -v).collect();
+                out.extend(unconstrain_stationary(&neg).unwrap_or_else(|| vec![0.0; k]))
+            }
+        }
+        at += k;
+    }
+    Ok(out)
+}
+
+fn check_transforms(transforms: &[ParamTransform], n: usize) -> Result<(), GreenersError> {
+    let width: usize = transforms.iter().map(|t| t.width()).sum();
+    if width != n {
+        return Err(GreenersError::ShapeMismatch(format!(
+            "Transforms cover {} parameters, expected {}",
+            width, n
+        )));
+    }
+    Ok(())
+}
+
+/// Coefficients of a stationary AR polynomial from unconstrained values.
+pub(crate) fn constrain_stationary(u: &[f64]) -> Vec<f64> {
+    let mut phi: Vec<f64> = Vec::with_capacity(u.len());
+    for (k, &x) in u.iter().enumerate() {
+        let r = x / (1.0 + x * x).sqrt();
+        let prev = phi.clone();
+        for j in 0..k {
+            phi[j] = prev[j] - r * prev[k - 1 - j];
+        }
+        phi.push(r);
+    }
+    phi
+}
+
+/// Inverse of [`constrain_stationary`]; `None` outside the stationary region.
+pub(crate) fn unconstrain_stationary(phi: &[f64]) -> Option<Vec<f64>> {
+    let mut cur = phi.to_vec();
+    let mut u = vec![0.0; phi.len()];
+    for k in (0..phi.len()).rev() {
+        let r = cur[k];
+        if !r.is_finite() || r.abs() >= 1.0 {
+            return None;
+        }
+        u[k] = r / (1.0 - r * r).sqrt();
+        cur = (0..k)
+            .map(|j| (cur[j] + r * cur[k - 1 - j]) / (1.0 - r * r))
+            .collect();
+    }
+    Some(u)
+}
+
+/// Solve `P = T P T' + V` by doubling.
+pub fn solve_discrete_lyapunov(
+    t: &Array2<f64>,
+    v: &Array2<f64>,
+) -> Result<Array2<f64>, GreenersError> {
+    let mut a = t.clone();
+    let mut p = v.clone();
+    for _ in 0..64 {
+        let step = a.dot(&p).dot(&a.t());
+        p += &step;
+        a = a.dot(&a);
+        if !p.iter().all(|x| x.is_finite()) {
+            break;
+        }
+        let scale = p.iter().fold(1.0_f64, |m, x| m.max(x.abs()));
+        if step.iter().all(|x| x.abs() <= 1e-14 * scale) {
+            return Ok(p);
+        }
+    }
+    Err(GreenersError::InvalidOperation(
+        "Transition matrix is not stationary".into(),
+    ))
+}
+
+/// One element of `y_t` processed by the univariate filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UnivariateStep {
+    z: Array1<f64>,
+    v: f64,
+    f_star: f64,
+    m_star: Array1<f64>,
+    /// `F_inf` and `M_inf = P_inf z'` of a diffuse update
+    diffuse: Option<(f64, Array1<f64>)>,
+}
+
+/// Output of [`DiffuseKalmanFilter::filter`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffuseFilterResult {
+    /// `a_t = E[a_t | y_1..y_{t-1}]`
+    pub predicted_states: Vec<Array1<f64>>,
+    /// Finite part `P_star` of the prediction covariance
+    pub predicted_cov: Vec<Array2<f64>>,
+    pub filtered_states: Vec<Array1<f64>>,
+    /// Finite part of the filtered covariance
+    pub filtered_cov: Vec<Array2<f64>>,
+    /// One-step-ahead predictions of `y_t`
+    pub forecasts: Vec<Array1<f64>>,
+    /// `y_t` minus its prediction (NaN where missing)
+    pub forecast_errors: Vec<Array1<f64>>,
+    /// Exact (diffuse) log-likelihood
+    pub log_likelihood: f64,
+    pub loglik_contributions: Vec<f64>,
+    /// Periods before the diffuse part of the state covariance vanishes
+    pub n_diffuse: usize,
+    pub n_obs: usize,
+    pub n_states: usize,
+    predicted_inf: Vec<Array2<f64>>,
+    steps: Vec<Vec<UnivariateStep>>,
+}
+
+/// Output of [`DiffuseKalmanFilter::smooth`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffuseSmootherResult {
+    /// `E[a_t | y_1..y_n]`
+    pub smoothed_states: Vec<Array1<f64>>,
+    /// `Var[a_t | y_1..y_n]`
+    pub smoothed_cov: Vec<Array2<f64>>,
+    pub log_likelihood: f64,
+    pub n_obs: usize,
+}
+
+/// Univariate Kalman filter and smoother with exact diffuse initialization.
+pub struct DiffuseKalmanFilter;
+
+impl DiffuseKalmanFilter {
+    /// Filter `y` (one vector per period, NaN for missing elements).
+    pub fn filter(
+        system: &StateSpaceSystem,
+        y: &[Array1<f64>],
+    ) -> Result<DiffuseFilterResult, GreenersError> {
+        system.validate(y)?;
+        let n = y.len();
+        let m = system.k_states();
+        let (mut a, mut p, mut p_inf) = system.initial()?;
+        let mut diffuse = p_inf.iter().any(|v| *v != 0.0);
+        let mut n_diffuse = 0;
+
+        let mut predicted_states = Vec::with_capacity(n);
+        let mut predicted_cov = Vec::with_capacity(n);
+        let mut predicted_inf = Vec::new();
+        let mut filtered_states = Vec::with_capacity(n);
+        let mut filtered_cov = Vec::with_capacity(n);
+        let mut forecasts = Vec::with_capacity(n);
+        let mut forecast_errors = Vec::with_capacity(n);
+        let mut loglik_contributions = Vec::with_capacity(n);
+        let mut steps = Vec::with_capacity(n);
+        let mut log_lik = 0.0;
+
+        for (t, obs) in y.iter().enumerate() {
+            let sys = system.at(t);
+            predicted_states.push(a.clone());
+            predicted_cov.push(p.clone());
+            if diffuse {
+                predicted_inf.push(p_inf.clone());
+            }
+            let forecast = sys.design.dot(&a) + &sys.obs_intercept;
+            forecast_errors.push(obs - &forecast);
+            forecasts.push(forecast);
+
+            let observed: Vec<usize> = (0..obs.len()).filter(|&i| obs[i].is_finite()).collect();
+            let mut steps_t = Vec::with_capacity(observed.len());
+            let mut ll_t = 0.0;
+            if !observed.is_empty() {
+                let z_o = sys.design.select(Axis(0), &observed);
+                let e_o =
+                    Array1::from_iter(observed.iter().map(|&i| obs[i] - sys.obs_intercept[i]));
+                let h_o = sys
+                    .obs_cov
+                    .select(Axis(0), &observed)
+                    .select(Axis(1), &observed);
+                let (z_o, e_o, h_diag, log_jacobian) = decorrelate(z_o, e_o, &h_o)?;
+                ll_t -= log_jacobian;
+
+                for i in 0..observed.len() {
+                    let z = z_o.row(i).to_owned();
+                    let v = e_o[i] - z.dot(&a);
+                    let m_star = p.dot(&z);
+                    let f_star = z.dot(&m_star) + h_diag[i];
+                    if diffuse {
+                        let m_inf = p_inf.dot(&z);
+                        let f_inf = z.dot(&m_inf);
+                        if f_inf > DIFFUSE_TOL {
+                            a = &a + &(&m_inf * (v / f_inf));
+                            let mm = outer(&m_inf, &m_inf);
+                            let cross = outer(&m_star, &m_inf);
+                            p = &p + &(&mm * (f_star / (f_inf * f_inf)))
+                                - &((&cross + &cross.t()) / f_inf);
+                            p_inf = &p_inf - &(mm / f_inf);
+                            ll_t -= 0.5 * (LN_2PI + f_inf.ln());
+                            steps_t.push(UnivariateStep {
+                                z,
+                                v,
+                                f_star,
+                                m_star,
+                                diffuse: Some((f_inf, m_inf)),
+                            });
+                            continue;
+                        }
+                    }
+                    // A zero-variance element that is already known adds nothing
+                    if f_star > 1e-14 {
+                        a = &a + &(&m_star * (v / f_star));
+                        p = &p - &(outer(&m_star, &m_star) / f_star);
+                        ll_t -= 0.5 * (LN_2PI + f_star.ln() + v * v / f_star);
+                        steps_t.push(UnivariateStep {
+                            z,
+                            v,
+                            f_star,
+                            m_star,
+                            diffuse: None,
+                        });
+                    }
+                }
+            }
+            log_lik += ll_t;
+            loglik_contributions.push(ll_t);
+            filtered_states.push(a.clone());
+            filtered_cov.push(p.clone());
+            steps.push(steps_t);
+
+            // Predict the next period
+            let tt = &sys.transition;
+            a = tt.dot(&a) + &sys.state_intercept;
+            let rqr = sys.selection.dot(&sys.state_cov).dot(&sys.selection.t());
+            p = tt.dot(&p).dot(&tt.t()) + &rqr;
+            p = (&p + &p.t()) * 0.5;
+            if diffuse {
+                n_diffuse = t + 1;
+                p_inf = tt.dot(&p_inf).dot(&tt.t());
+                if p_inf.iter().all(|v| v.abs() < DIFFUSE_TOL) {
+                    diffuse = false;
+                }
+            }
+        }
+
+        Ok(DiffuseFilterResult {
+            predicted_states,
+            predicted_cov,
+            filtered_states,
+            filtered_cov,
+            forecasts,
+            forecast_errors,
+            log_likelihood: log_lik,
+            loglik_contributions,
+            n_diffuse,
+            n_obs: n,
+            n_states: m,
+            predicted_inf,
+            steps,
+        })
+    }
+
+    /// Fixed-interval smoother, including the exact diffuse periods.
+    pub fn smooth(
+        system: &StateSpaceSystem,
+        filtered: &DiffuseFilterResult,
+    ) -> Result<DiffuseSmootherResult, GreenersError> {
+        let (smoothed_states, smoothed_cov) = smooth_states(system, filtered, true);
+        Ok(DiffuseSmootherResult {
+            smoothed_states,
+            smoothed_cov,
+            log_likelihood: filtered.log_likelihood,
+            n_obs: filtered.n_obs,
+        })
+    }
+}
+
+/// Backward recursions for `r` and `N` (Durbin & Koopman 2012, sec. 5.3 and
+/// 6.4), split into the finite and diffuse parts while `P_inf` is non-zero.
+fn smooth_states(
+    system: &StateSpaceSystem,
+    filtered: &DiffuseFilterResult,
+    with_cov: bool,
+) -> (Vec<Array1<f64>>, Vec<Array2<f64>>) {
+    let n = filtered.n_obs;
+    let m = filtered.n_states;
+    let eye = Array2::<f64>::eye(m);
+    let mut r0 = Array1::<f64>::zeros(m);
+    let mut r1 = Array1::<f64>::zeros(m);
+    let mut n0 = Array2::<f64>::zeros((m, m));
+    let mut n1 = Array2::<f64>::zeros((m, m));
+    let mut n2 = Array2::<f64>::zeros((m, m));
+    let mut states = vec![Array1::zeros(0); n];
+    let mut covs = vec![Array2::zeros((0, 0)); if with_cov { n } else { 0 }];
+
+    for t in (0..n).rev() {
+        let in_diffuse = t < filtered.n_diffuse;
+        for step in filtered.steps[t].iter().rev() {
+            let z = &step.z;
+            match &step.diffuse {
+                None => {
+                    let l = &eye - &(outer(&step.m_star, z) / step.f_star);
+                    r0 = z * (step.v / step.f_star) + l.t().dot(&r0);
+                    if with_cov {
+                        n0 = outer(z, z) / step.f_star + l.t().dot(&n0).dot(&l);
+                    }
+                    if in_diffuse {
+                        r1 = l.t().dot(&r1);
+                        if with_cov {
+                            n1 = l.t().dot(&n1).dot(&l);
+                            n2 = l.t().dot(&n2).dot(&l);
+                        }
+                    }
+                }
+                Some((f_inf, m_inf)) => {
+                    let k0 = m_inf / *f_inf;
+                    let k1 = (&step.m_star - &(&k0 * step.f_star)) / *f_inf;
+                    let l0 = &eye - &outer(&k0, z);
+                    let l1 = -outer(&k1, z);
+                    r1 = z * (step.v / f_inf) + l0.t().dot(&r1) + l1.t().dot(&r0);
+                    r0 = l0.t().dot(&r0);
+                    if with_cov {
+                        let zz = outer(z, z);
+                        let l0t = l0.t();
+                        let l1t = l1.t();
+                        let n2_new = &zz * (-step.f_star / (f_inf * f_inf))
+                            + l0t.dot(&n2).dot(&l0)
+                            + l0t.dot(&n1).dot(&l1)
+                            + l1t.dot(&n1).dot(&l0)
+                            + l1t.dot(&n0).dot(&l1);
+                        let n1_new = &zz / *f_inf
+                            + l0t.dot(&n1).dot(&l0)
+                            + l1t.dot(&n0).dot(&l0)
+                            + l0t.dot(&n0).dot(&l1);
+                        n0 = l0t.dot(&n0).dot(&l0);
+                        n1 = n1_new;
+                        n2 = n2_new;
+                    }
+                }
+            }
+        }
+
+        let a = &filtered.predicted_states[t];
+        let p = &filtered.predicted_cov[t];
+        let mut alpha = a + &p.dot(&r0);
+        let mut v = if with_cov {
+            p - &p.dot(&n0).dot(p)
+        } else {
+            Array2::zeros((0, 0))
+        };
+        if in_diffuse {
+            let p_inf = &filtered.predicted_inf[t];
+            alpha = alpha + p_inf.dot(&r1);
+            if with_cov {
+                let cross = p_inf.dot(&n1).dot(p);
+                v = v - &cross - cross.t() - p_inf.dot(&n2).dot(p_inf);
+            }
+        }
+        states[t] = alpha;
+        if with_cov {
+            covs[t] = (&v + &v.t()) * 0.5;
+        }
+
+        if t > 0 {
+            let tt = &system.at(t - 1).transition;
+            r0 = tt.t().dot(&r0);
+            if with_cov {
+                n0 = tt.t().dot(&n0).dot(tt);
+            }
+            if t - 1 < filtered.n_diffuse {
+                r1 = tt.t().dot(&r1);
+                if with_cov {
+                    n1 = tt.t().dot(&n1).dot(tt);
+                    n2 = tt.t().dot(&n2).dot(tt);
+                }
+            }
+        }
+    }
+    (states, covs)
+}
+
+/// Durbin-Koopman simulation smoother.
+pub struct SimulationSmoother;
+
+impl SimulationSmoother {
+    /// Draw `n_draws` state paths from `p(a_1, ..., a_n | y)`; each draw is
+    /// an `n x k_states` matrix.
+    ///
+    /// Each draw simulates states and observations from the model (diffuse
+    /// states start at their initial mean), then corrects the simulated path
+    /// by the difference between the smoothed means given `y` and given the
+    /// simulated data with the same missing pattern. Draws run in parallel
+    /// on independent streams of the seeded generator.
+    pub fn draw(
+        system: &StateSpaceSystem,
+        y: &[Array1<f64>],
+        n_draws: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<Array2<f64>>, GreenersError> {
+        let filtered = DiffuseKalmanFilter::filter(system, y)?;
+        let (smoothed, _) = smooth_states(system, &filtered, false);
+        let n = y.len();
+        let m = system.k_states();
+        let (a1, p1, _) = system.initial()?;
+        let p1_sqrt = psd_sqrt(&p1)?;
+        let roots = system
+            .matrices
+            .iter()
+            .map(|sys| Ok((psd_sqrt(&sys.obs_cov)?, psd_sqrt(&sys.state_cov)?)))
+            .collect::<Result<Vec<_>, GreenersError>>()?;
+
+        let rng = GreenersRng::new(seed);
+        (0..n_draws)
+            .into_par_iter()
+            .map(|i| {
+                let mut rng = rng.stream(i as u64);
+                let mut normals = |k: usize| Array1::from_shape_fn(k, |_| rng.normal());
+                let mut state = &a1 + &p1_sqrt.dot(&normals(m));
+                let mut path = Array2::zeros((n, m));
+                let mut y_plus = Vec::with_capacity(n);
+                for (t, obs) in y.iter().enumerate() {
+                    let sys = system.at(t);
+                    let (h_sqrt, q_sqrt) = &roots[t.min(roots.len() - 1)];
+                    path.row_mut(t).assign(&state);
+                    let mut yp = sys.design.dot(&state)
+                        + &sys.obs_intercept
+                        + h_sqrt.dot(&normals(obs.len()));
+                    for (v, o) in yp.iter_mut().zip(obs.iter()) {
+                        if !o.is_finite() {
+                            *v = f64::NAN;
+                        }
+                    }
+                    y_plus.push(yp);
+                    state = sys.transition.dot(&state)
+                        + &sys.state_intercept
+                        + sys.selection.dot(&q_sqrt.dot(&normals(q_sqrt.ncols())));
+                }
+                let f_plus = DiffuseKalmanFilter::filter(system, &y_plus)?;
+                let (s_plus, _) = smooth_states(system, &f_plus, false);
+                for t in 0..n {
+                    let mut row = path.row_mut(t);
+                    row += &(&smoothed[t] - &s_plus[t]);
+                }
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+/// Settings for [`MleModel::fit`].
+#[derive(Debug, Clone)]
+pub struct MleConfig {
+    /// BFGS iterations
+    pub max_iter: usize,
+    /// Convergence tolerance on the gradient norm
+    pub tol: f64,
+}
+
+impl Default for MleConfig {
+    fn default() -> Self {
+        MleConfig {
+            max_iter: 500,
+            tol: 1e-6,
+        }
+    }
+}
+
+/// Result of [`MleModel::fit`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MleResult {
+    pub params: Array1<f64>,
+    /// Standard errors from the inverse observed information
+    pub std_errors: Array1<f64>,
+    pub z_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub conf_lower: Array1<f64>,
+    pub conf_upper: Array1<f64>,
+    pub cov_params: Array2<f64>,
+    pub param_names: Vec<String>,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub bic: f64,
+    /// Periods with observations after the diffuse phase
+    pub n_obs: usize,
+    pub n_diffuse: usize,
+    pub n_iter: usize,
+    pub converged: bool,
+    pub filtered: DiffuseFilterResult,
+    pub smoothed: DiffuseSmootherResult,
+}
+
+impl fmt::Display for MleResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{:=^70}", " State Space Model (MLE) ")?;
+        writeln!(f, "{:<20} {:>10}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>10}", "Diffuse periods:", self.n_diffuse)?;
+        writeln!(f, "{:<20} {:>10}", "States:", self.filtered.n_states)?;
+        writeln!(f, "{:<20} {:>10.4}", "Log-Likelihood:", self.log_likelihood)?;
+        writeln!(f, "{:<20} {:>10.4}", "AIC:", self.aic)?;
+        writeln!(f, "{:<20} {:>10.4}", "BIC:", self.bic)?;
+        writeln!(f, "{:<20} {:>10}", "Converged:", self.converged)?;
+        writeln!(f, "\n{:-^70}", " Parameters ")?;
+        writeln!(
+            f,
+            "{:<15} {:>10} {:>10} {:>8} {:>8} {:>10} {:>10}",
+            "", "coef", "std err", "z", "P>|z|", "[0.025", "0.975]"
+        )?;
+        writeln!(f, "{:-^70}", "")?;
+        for (i, name) in self.param_names.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<15} {:>10.4} {:>10.4} {:>8.3} {:>8.3} {:>10.4} {:>10.4}",
+                name,
+                self.params[i],
+                self.std_errors[i],
+                self.z_values[i],
+                self.p_values[i],
+                self.conf_lower[i],
+                self.conf_upper[i],
+            )?;
+        }
+        writeln!(f, "{:=^70}", "")
+    }
+}
+
+/// A state-space model whose system matrices are functions of a parameter
+/// vector.
+///
+/// Implementors supply the data, starting values and the map from parameters
+/// to [`StateSpaceSystem`]; `transforms` keeps the optimizer inside the
+/// admissible region (positive variances, stationary AR blocks, ...).
+pub trait MleModel {
+    /// Observations, one vector per period; NaN marks a missing element
+    fn observations(&self) -> &[Array1<f64>];
+
+    fn n_params(&self) -> usize;
+
+    fn start_params(&self) -> Array1<f64>;
+
+    /// System matrices and initial state at `params`
+    fn update(&self, params: &Array1<f64>) -> Result<StateSpaceSystem, GreenersError>;
+
+    fn param_names(&self) -> Vec<String> {
+        (1..=self.n_params())
+            .map(|i| format!("param.{}", i))
+            .collect()
+    }
+
+    /// Transforms from unconstrained values, covering all parameters in order
+    fn transforms(&self) -> Vec<ParamTransform> {
+        vec![ParamTransform::Identity; self.n_params()]
+    }
+
+    fn loglike(&self, params: &Array1<f64>) -> Result<f64, GreenersError> {
+        Ok(self.filter(params)?.log_likelihood)
+    }
+
+    fn filter(&self, params: &Array1<f64>) -> Result<DiffuseFilterResult, GreenersError> {
+        DiffuseKalmanFilter::filter(&self.update(params)?, self.observations())
+    }
+
+    fn smooth(&self, params: &Array1<f64>) -> Result<DiffuseSmootherResult, GreenersError> {
+        let system = self.update(params)?;
+        let filtered = DiffuseKalmanFilter::filter(&system, self.observations())?;
+        DiffuseKalmanFilter::smooth(&system, &filtered)
+    }
+
+    /// Posterior draws of the state paths at `params`.
+    fn simulation_smoother(
+        &self,
+        params: &Array1<f64>,
+        n_draws: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<Array2<f64>>, GreenersError> {
+        SimulationSmoother::draw(&self.update(params)?, self.observations(), n_draws, seed)
+    }
+
+    /// Maximum likelihood by BFGS over the unconstrained parameters, with
+    /// standard errors from the numerical Hessian at the optimum.
+    fn fit(&self, config: &MleConfig) -> Result<MleResult, GreenersError> {
+        let transforms = self.transforms();
+        let start = self.start_params();
+        if start.len() != self.n_params() {
+            return Err(GreenersError::ShapeMismatch(
+                "start_params length differs from n_params".into(),
+            ));
+        }
+        let u0 = unconstrain_params(&transforms, start.as_slice().unwrap_or(&start.to_vec()))?;
+        let neg_ll = |u: &[f64]| -> f64 {
+            constrain_params(&transforms, u)
+                .and_then(|p| self.loglike(&Array1::from(p)))
+                .map_or(f64::INFINITY, |ll| {
+                    if ll.is_finite() {
+                        -ll
+                    } else {
+                        f64::INFINITY
+                    }
+                })
+        };
+        let (u, n_iter, converged) = bfgs(&neg_ll, &u0, config.max_iter, config.tol);
+        let params = Array1::from(constrain_params(&transforms, &u)?);
+
+        let system = self.update(&params)?;
+        let filtered = DiffuseKalmanFilter::filter(&system, self.observations())?;
+        if !filtered.log_likelihood.is_finite() {
+            return Err(GreenersError::OptimizationFailed);
+        }
+        let smoothed = DiffuseKalmanFilter::smooth(&system, &filtered)?;
+        let cov_params = inverse_hessian(
+            &|p: &[f64]| {
+                self.loglike(&Array1::from(p.to_vec()))
+                    .map_or(f64::NAN, |ll| -ll)
+            },
+            params.as_slice().unwrap_or(&params.to_vec()),
+        );
+        let (std_errors, z_values, p_values, conf_lower, conf_upper) =
+            wald_inference(&params, &cov_params);
+
+        let log_likelihood = filtered.log_likelihood;
+        let n_obs = filtered
+            .forecast_errors
+            .iter()
+            .skip(filtered.n_diffuse)
+            .filter(|e| e.iter().any(|v| v.is_finite()))
+            .count();
+        let k = params.len() as f64;
+        Ok(MleResult {
+            std_errors,
+            z_values,
+            p_values,
+            conf_lower,
+            conf_upper,
+            cov_params,
+            param_names: self.param_names(),
+            aic: -2.0 * log_likelihood + 2.0 * k,
+            bic: -2.0 * log_likelihood + k * (n_obs.max(1) as f64).ln(),
+            log_likelihood,
+            n_obs,
+            n_diffuse: filtered.n_diffuse,
+            n_iter,
+            converged,
+            params,
+            filtered,
+            smoothed,
+        })
+    }
+}
+
+/// Inverse of the central-difference Hessian of `f` at `params` (NaN if it
+/// cannot be inverted).
+pub(crate) fn inverse_hessian(f: &dyn Fn(&[f64]) -> f64, params: &[f64]) -> Array2<f64> {
+    let k = params.len();
+    let steps: Vec<f64> = params.iter().map(|v| 1e-4 * v.abs().max(1.0)).collect();
+    let mut x = params.to_vec();
+    let mut hess = Array2::zeros((k, k));
+    for i in 0..k {
+        for j in i..k {
+            let mut corner = |si: f64, sj: f64| {
+                x[i] += si * steps[i];
+                x[j] += sj * steps[j];
+                let v = f(&x);
+                x[i] = params[i];
+                x[j] = params[j];
+                v
+            };
+            let val = (corner(1.0, 1.0) - corner(1.0, -1.0) - corner(-1.0, 1.0)
+                + corner(-1.0, -1.0))
+                / (4.0 * steps[i] * steps[j]);
+            hess[[i, j]] = val;
+            hess[[j, i]] = val;
+        }
+    }
+    if hess.iter().all(|v| v.is_finite()) {
+        if let Ok(cov) = hess.inv() {
+            return cov;
+        }
+    }
+    Array2::from_elem((k, k), f64::NAN)
+}
+
+/// Standard errors, z statistics, two-sided p-values and 95% intervals.
+#[allow(clippy::type_complexity)]
+pub(crate) fn wald_inference(
+    params: &Array1<f64>,
+    cov: &Array2<f64>,
+) -> (
+    Array1<f64>,
+    Array1<f64>,
+    Array1<f64>,
+    Array1<f64>,
+    Array1<f64>,
+) {
+    let normal = NormalDist::standard();
+    let se = Array1::from_shape_fn(params.len(), |i| {
+        let v = cov[[i, i]];
+        if v > 0.0 {
+            v.sqrt()
+        } else {
+            f64::NAN
+        }
+    });
+    let z = params / &se;
+    let p = z.mapv(|z| 2.0 * (1.0 - normal.cdf(z.abs())));
+    let lower = params - &(&se * 1.959963984540054);
+    let upper = params + &(&se * 1.959963984540054);
+    (se, z, p, lower, upper)
+}
+
+/// Quasi-Newton minimization with central-difference gradients and a
+/// backtracking Armijo line search. Returns `(argmin, iterations, converged)`.
+fn bfgs(
+    f: &dyn Fn(&[f64]) -> f64,
+    x0: &[f64],
+    max_iter: usize,
+    tol: f64,
+) -> (Vec<f64>, usize, bool) {
+    let n = x0.len();
+    let gradient = |x: &[f64]| -> Vec<f64> {
+        let mut xp = x.to_vec();
+        (0..n)
+            .map(|i| {
+                let h = 1e-6 * x[i].abs().max(1.0);
+                xp[i] = x[i] + h;
+                let fp = f(&xp);
+                xp[i] = x[i] - h;
+                let fm = f(&xp);
+                xp[i] = x[i];
+                (fp - fm) / (2.0 * h)
+            })
+            .collect()
+    };
+    let mut x = x0.to_vec();
+    let mut fx = f(&x);
+    if n == 0 || !fx.is_finite() {
+        return (x, 0, false);
+    }
+    let mut g = gradient(&x);
+    let mut h_inv = Array2::<f64>::eye(n);
+    for iter in 0..max_iter {
+        let g_norm = g.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if g_norm < tol {
+            return (x, iter, true);
+        }
+        let ga = Array1::from(g.clone());
+        let mut dir = -h_inv.dot(&ga);
+        let mut slope = dir.dot(&ga);
+        if slope >= 0.0 {
+            // Not a descent direction: restart from steepest descent
+            h_inv = Array2::eye(n);
+            dir = -ga.clone();
+            slope = dir.dot(&ga);
+        }
+        let mut step = 1.0;
+        let mut accepted = None;
+        for _ in 0..40 {
+            let cand: Vec<f64> = x
+                .iter()
+                .zip(dir.iter())
+                .map(|(a, d)| a + step * d)
+                .collect();
+            let fc = f(&cand);
+            if fc.is_finite() && fc <= fx + 1e-4 * step * slope {
+                accepted = Some((cand, fc));
+                break;
+            }
+            step *= 0.5;
+        }
+        let Some((x_new, f_new)) = accepted else {
+            return (x, iter, g_norm < tol.sqrt());
+        };
+        let g_new = gradient(&x_new);
+        let s = Array1::from(
+            x_new
+                .iter()
+                .zip(x.iter())
+                .map(|(a, b)| a - b)
+                .collect::<Vec<_>>(),
+        );
+        let yk = Array1::from(
+            g_new
+                .iter()
+                .zip(g.iter())
+                .map(|(a, b)| a - b)
+                .collect::<Vec<_>>(),
+        );
+        let sy = s.dot(&yk);
+        if sy > 1e-12 {
+            let rho = 1.0 / sy;
+            let eye = Array2::<f64>::eye(n);
+            let left = &eye - &(outer(&s, &yk) * rho);
+            let right = &eye - &(outer(&yk, &s) * rho);
+            h_inv = left.dot(&h_inv).dot(&right) + outer(&s, &s) * rho;
+        }
+        let df = (fx - f_new).abs();
+        x = x_new;
+        fx = f_new;
+        g = g_new;
+        if df < 1e-12 * (1.0 + fx.abs()) && step < 1.0 {
+            return (x, iter + 1, true);
+        }
+    }
+    (x, max_iter, false)
+}
+
+/// `L^{-1} Z`, `L^{-1} e` and unit variances for `H = L L'`, or the inputs
+/// with the diagonal of `H` when it is already diagonal. The last value is
+/// `log |L|`, the Jacobian of the transformation.
+#[allow(clippy::type_complexity)]
+fn decorrelate(
+    z: Array2<f64>,
+    e: Array1<f64>,
+    h: &Array2<f64>,
+) -> Result<(Array2<f64>, Array1<f64>, Array1<f64>, f64), GreenersError> {
+    let k = h.nrows();
+    let diagonal = (0..k).all(|i| (0..k).all(|j| i == j || h[[i, j]] == 0.0));
+    if diagonal {
+        return Ok((z, e, h.diag().to_owned(), 0.0));
+    }
+    let l = h.cholesky(UPLO::Lower)?;
+    let mut z_out = z;
+    let mut e_out = e;
+    for i in 0..k {
+        for j in 0..i {
+            let lij = l[[i, j]];
+            let (zi, zj) = (z_out.row(i).to_owned(), z_out.row(j).to_owned());
+            z_out.row_mut(i).assign(&(&zi - &(&zj * lij)));
+            e_out[i] -= lij * e_out[j];
+        }
+        let lii = l[[i, i]];
+        z_out.row_mut(i).mapv_inplace(|v| v / lii);
+        e_out[i] /= lii;
+    }
+    let log_det = l.diag().iter().map(|v| v.ln()).sum();
+    Ok((z_out, e_out, Array1::ones(k), log_det))
+}
+
+/// Symmetric square root of a positive semi-definite matrix.
+fn psd_sqrt(a: &Array2<f64>) -> Result<Array2<f64>, GreenersError> {
+    if a.iter().all(|v| *v == 0.0) {
+        return Ok(Array2::zeros(a.dim()));
+    }
+    let (vals, vecs) = a.eigh(UPLO::Lower)?;
+    let roots = vals.mapv(|v| v.max(0.0).sqrt());
+    Ok((&vecs * &roots).dot(&vecs.t()))
+}
+
+fn outer(x: &Array1<f64>, y: &Array1<f64>) -> Array2<f64> {
+    Array2::from_shape_fn((x.len(), y.len()), |(i, j)| x[i] * y[j])
+}
@@ -13,10 +13,11 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of MS-VAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MsVarResult {
     /// Regime-specific intercepts (K x n_vars)
     pub regime_intercepts: Array2<f64>,
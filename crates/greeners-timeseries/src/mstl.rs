@@ -1,10 +1,11 @@
 use greeners_core::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 pub struct MSTL;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MSTLResult {
     pub trend: Array1<f64>,
     pub seasonal: Vec<Array1<f64>>,
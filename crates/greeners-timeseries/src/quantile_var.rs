@@ -13,12 +13,14 @@
 //! bootstrap standard errors.
 
 use greeners_core::error::GreenersError;
+use greeners_core::GreenersRng;
 use greeners_ols::quantile::QuantileReg;
 use ndarray::{Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of Quantile VAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QuantileVarResult {
     /// Quantile level
     pub tau: f64,
@@ -118,12 +120,15 @@ impl QuantileVAR {
     /// * `tau` - Quantile level (0-1)
     /// * `n_boot` - Number of bootstrap replications for SE
     /// * `var_names` - Optional variable names
+    /// * `seed` - Random seed for the bootstrap (`None` uses the default
+    ///   seed); each equation draws from its own stream
     pub fn fit(
         y: &Array2<f64>,
         lags: usize,
         tau: f64,
         n_boot: usize,
         var_names: Option<Vec<String>>,
+        seed: Option<u64>,
     ) -> Result<QuantileVarResult, GreenersError> {
         let t = y.nrows();
         let k = y.ncols();
@@ -166,6 +171,7 @@ impl QuantileVAR {
         let mut p_values = Array2::zeros((k, n_reg));
         let mut pseudo_r2 = Array1::zeros(k);
 
+        let rng = GreenersRng::new(seed);
         for eq in 0..k {
             // Dependent variable for this equation
             let mut y_eq = Array1::zeros(n_eff);
@@ -174,7 +180,8 @@ impl QuantileVAR {
                 y_eq[i] = y[(t_i, eq)];
             }
 
-            let result = QuantileReg::fit(&y_eq, &z, tau, n_boot)
+            let eq_seed = rng.stream(eq as u64).seed();
+            let result = QuantileReg::fit(&y_eq, &z, tau, n_boot, Some(eq_seed))
                 .map_err(|e| GreenersError::InvalidOperation(e.to_string()))?;
 
             for col in 0..n_reg {
@@ -0,0 +1,947 @@
+//! SARIMAX estimated by exact Gaussian maximum likelihood in state-space form.
+//!
+//! The model is a regression with seasonal ARIMA errors,
+//!
+//! ```text
+//! y_t = mu + x_t' beta + w_t
+//! phi(L) Phi(L^s) (1 - L)^d (1 - L^s)^D w_t = theta(L) Theta(L^s) e_t,  e_t ~ N(0, sigma2)
+//! ```
+//!
+//! The state stacks the Harvey representation of the ARMA part with the last
+//! `d + D s` values of `w`, so the differencing is undone inside the filter
+//! and the likelihood is evaluated on the series in levels. Missing values
+//! (NaN) are skipped by `KalmanFilter::filter`, which keeps the likelihood
+//! exact for the observed values.
+//!
+//! Diffuse states are approximated by a prior variance of
+//! `SarimaxConfig::diffuse_variance` rather than handled by the exact diffuse
+//! filter. With `SarimaxInitialization::Stationary` the ARMA block starts from
+//! its unconditional distribution and only the differencing states get the
+//! large variance; the contributions of the first `d + D s` observed periods
+//! are dropped, so without gaps the log-likelihood equals the exact likelihood
+//! of the differenced series. `SarimaxInitialization::ApproximateDiffuse`
+//! gives every state the large variance and drops as many periods as there
+//! are states; the remaining contributions still carry an `O(1/kappa)` error
+//! from the prior, so that log-likelihood depends on `diffuse_variance` and is
+//! only comparable across fits using the same value.
+
+use crate::arima::{
+    check_roots_outside_unit_circle, difference, seasonal_difference, ArimaOrder, SeasonalOrder,
+    ARIMA,
+};
+use crate::mlemodel::{
+    constrain_stationary, inverse_hessian, solve_discrete_lyapunov, unconstrain_stationary,
+    wald_inference,
+};
+use crate::statespace::{KalmanFilter, KalmanResult, StateSpaceModel};
+use argmin::{
+    core::{CostFunction, Error as ArgminError, Executor, IterState, State},
+    solver::neldermead::NelderMead,
+};
+use greeners_core::linalg::LinalgInverse as _;
+use greeners_core::GreenersError;
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal as NormalDist};
+use std::fmt;
+
+/// Initial distribution of the state vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SarimaxInitialization {
+    /// Unconditional distribution of the ARMA block, diffuse differencing states
+    #[default]
+    Stationary,
+    /// Every state starts with variance `diffuse_variance`, an approximation
+    /// of a diffuse prior whose log-likelihood depends on that variance
+    ApproximateDiffuse,
+}
+
+/// Settings for [`SARIMAX`].
+#[derive(Debug, Clone)]
+pub struct SarimaxConfig {
+    pub initialization: SarimaxInitialization,
+    /// Estimate a constant mean `mu` (ignored when `d + D > 0`)
+    pub include_mean: bool,
+    /// Restrict the AR polynomials to the stationary region
+    pub enforce_stationarity: bool,
+    /// Restrict the MA polynomials to the invertible region
+    pub enforce_invertibility: bool,
+    /// Prior variance standing in for the diffuse states
+    pub diffuse_variance: f64,
+    /// Iterations of each Nelder-Mead run
+    pub max_iter: u64,
+}
+
+impl Default for SarimaxConfig {
+    fn default() -> Self {
+        SarimaxConfig {
+            initialization: SarimaxInitialization::Stationary,
+            include_mean: true,
+            enforce_stationarity: true,
+            enforce_invertibility: true,
+            diffuse_variance: 1e6,
+            max_iter: 2000,
+        }
+    }
+}
+
+/// Result of an exact-likelihood SARIMAX fit.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarimaxResult {
+    /// `[mu, beta, ar, ma, seasonal ar, seasonal ma, sigma2]`
+    pub params: Array1<f64>,
+    /// Standard errors from the inverse observed information
+    pub std_errors: Array1<f64>,
+    pub z_values: Array1<f64>,
+    pub p_values: Array1<f64>,
+    pub conf_lower: Array1<f64>,
+    pub conf_upper: Array1<f64>,
+    /// Inverse of the numerical Hessian of the negative log-likelihood
+    pub cov_params: Array2<f64>,
+    pub param_names: Vec<String>,
+    pub intercept: f64,
+    pub exog_params: Option<Array1<f64>>,
+    pub ar_params: Array1<f64>,
+    pub ma_params: Array1<f64>,
+    pub seasonal_ar_params: Array1<f64>,
+    pub seasonal_ma_params: Array1<f64>,
+    pub sigma2: f64,
+    pub order: ArimaOrder,
+    pub seasonal_order: Option<SeasonalOrder>,
+    /// Exact log-likelihood of the observed values
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub aicc: f64,
+    pub bic: f64,
+    /// One-step-ahead predictions `E[y_t | y_1, ..., y_{t-1}]` from the filter
+    pub fitted_values: Array1<f64>,
+    /// Variance of the one-step-ahead prediction errors
+    pub prediction_variance: Array1<f64>,
+    /// `y_t` minus its one-step prediction (NaN where `y_t` is missing)
+    pub residuals: Array1<f64>,
+    /// Observations entering the likelihood (observed, after the diffuse periods)
+    pub n_obs: usize,
+    pub n_missing: usize,
+    /// Observed periods dropped from the likelihood for the diffuse states
+    pub n_diffuse: usize,
+    pub initialization: SarimaxInitialization,
+    pub n_iter: u64,
+    pub converged: bool,
+    ssm: StateSpaceModel,
+    last_state: Array1<f64>,
+    last_cov: Array2<f64>,
+}
+
+/// Multi-step forecast from the Kalman filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarimaxForecast {
+    pub mean: Array1<f64>,
+    pub std_errors: Array1<f64>,
+    pub lower: Array1<f64>,
+    pub upper: Array1<f64>,
+    pub alpha: f64,
+}
+
+/// Seasonal ARIMA with regression errors, estimated by exact MLE.
+pub struct SARIMAX;
+
+impl SARIMAX {
+    /// Fit a SARIMAX(p,d,q)(P,D,Q,s) model by exact maximum likelihood.
+    ///
+    /// `y` may contain NaN for missing periods; `exog` (one row per period)
+    /// must be fully observed. The likelihood is maximised with Nelder-Mead
+    /// over unconstrained parameters (partial autocorrelations for the AR and
+    /// MA polynomials, log variance), starting from Hannan-Rissanen estimates.
+    pub fn fit(
+        y: &Array1<f64>,
+        order: (usize, usize, usize),
+        seasonal_order: (usize, usize, usize, usize),
+        exog: Option<&Array2<f64>>,
+        config: &SarimaxConfig,
+    ) -> Result<SarimaxResult, GreenersError> {
+        let spec = Spec::new(order, seasonal_order, exog.map_or(0, |x| x.ncols()), config)?;
+        validate(y, exog)?;
+        let n_observed = y.iter().filter(|v| v.is_finite()).count();
+        if n_observed < spec.burn() + spec.n_params() + 2 {
+            return Err(GreenersError::ShapeMismatch(
+                "Not enough observed values for SARIMAX estimation".into(),
+            ));
+        }
+
+        let mut start = spec.start_params(y, exog);
+        if spec.evaluate(y, exog, &start).is_err() {
+            // Non-stationary starting values under a stationary prior
+            let (lo, hi) = spec.arma_range();
+            start[lo..hi].iter_mut().for_each(|v| *v = 0.0);
+        }
+        let problem = SarimaxProblem {
+            spec: spec.clone(),
+            y: y.clone(),
+            exog: exog.cloned(),
+        };
+
+        // Two Nelder-Mead runs, the second restarted from the first optimum
+        let mut u = spec.unconstrain(&start);
+        let mut n_iter = 0;
+        let mut converged = false;
+        for scale in [0.25, 0.05] {
+            let vertices = build_simplex(&u, scale);
+            let solver: NelderMead<Vec<f64>, f64> = NelderMead::new(vertices)
+                .with_sd_tolerance(1e-8)
+                .map_err(|e| GreenersError::InvalidOperation(format!("Nelder-Mead config: {e}")))?;
+            let result = Executor::new(problem.clone(), solver)
+                .configure(|state: IterState<Vec<f64>, (), (), (), (), f64>| {
+                    state.max_iters(config.max_iter)
+                })
+                .run()
+                .map_err(|e| {
+                    GreenersError::InvalidOperation(format!("Optimisation failed: {e}"))
+                })?;
+            let state = result.state();
+            u = state.get_best_param().cloned().ok_or_else(|| {
+                GreenersError::InvalidOperation(
+                    "Optimisation did not return a best parameter".into(),
+                )
+            })?;
+            n_iter += state.get_iter();
+            converged = state.get_iter() < config.max_iter;
+        }
+        let params = spec.constrain(&u);
+
+        let (kf, ssm, log_likelihood, n_obs) = spec.evaluate(y, exog, &params)?;
+        let cov_params = spec.observed_information_inverse(y, exog, &params);
+        spec.build_result(
+            y,
+            exog,
+            params,
+            cov_params,
+            kf,
+            ssm,
+            log_likelihood,
+            n_obs,
+            n_iter,
+            converged,
+        )
+    }
+
+    /// Exact log-likelihood at the parameters `[mu, beta, ar, ma, sar, sma, sigma2]`
+    /// (`mu` only when `config.include_mean` and `d + D = 0`).
+    pub fn log_likelihood(
+        y: &Array1<f64>,
+        order: (usize, usize, usize),
+        seasonal_order: (usize, usize, usize, usize),
+        exog: Option<&Array2<f64>>,
+        params: &Array1<f64>,
+        config: &SarimaxConfig,
+    ) -> Result<f64, GreenersError> {
+        let spec = Spec::new(order, seasonal_order, exog.map_or(0, |x| x.ncols()), config)?;
+        validate(y, exog)?;
+        spec.check_len(params.len())?;
+        Ok(spec.evaluate(y, exog, &params.to_vec())?.2)
+    }
+
+    /// State-space form of the ARIMA error process `w_t` at the given
+    /// parameters (same layout as in [`SARIMAX::log_likelihood`]; the mean and
+    /// regression coefficients are not part of the state).
+    pub fn state_space(
+        order: (usize, usize, usize),
+        seasonal_order: (usize, usize, usize, usize),
+        k_exog: usize,
+        params: &Array1<f64>,
+        config: &SarimaxConfig,
+    ) -> Result<StateSpaceModel, GreenersError> {
+        let spec = Spec::new(order, seasonal_order, k_exog, config)?;
+        spec.check_len(params.len())?;
+        spec.state_space(&params.to_vec())
+    }
+}
+
+impl SarimaxResult {
+    /// State-space model at the estimated parameters.
+    pub fn state_space_model(&self) -> &StateSpaceModel {
+        &self.ssm
+    }
+
+    /// Forecast `steps` periods ahead by iterating the filter's prediction
+    /// step from the last filtered state.
+    ///
+    /// `exog` must have `steps` rows when the model has regressors.
+    pub fn forecast(
+        &self,
+        steps: usize,
+        exog: Option<&Array2<f64>>,
+        alpha: f64,
+    ) -> Result<SarimaxForecast, GreenersError> {
+        if alpha <= 0.0 || alpha >= 1.0 {
+            return Err(GreenersError::InvalidOperation(
+                "alpha must be between 0 and 1 (exclusive)".into(),
+            ));
+        }
+        let beta = self.exog_params.clone().unwrap_or_else(|| Array1::zeros(0));
+        match exog {
+            Some(x) if x.nrows() != steps || x.ncols() != beta.len() => {
+                return Err(GreenersError::ShapeMismatch(format!(
+                    "Future exogenous matrix must be {} x {}",
+                    steps,
+                    beta.len()
+                )));
+            }
+            None if !beta.is_empty() => {
+                return Err(GreenersError::InvalidOperation(
+                    "Model has exogenous regressors: future values are required".into(),
+                ));
+            }
+            _ => {}
+        }
+
+        let z = NormalDist::new(0.0, 1.0)
+            .map_err(|e| GreenersError::InvalidOperation(e.to_string()))?
+            .inverse_cdf(1.0 - alpha / 2.0);
+        let rqr = self.ssm.r.dot(&self.ssm.q).dot(&self.ssm.r.t());
+        let mut state = self.last_state.clone();
+        let mut cov = self.last_cov.clone();
+        let mut mean = Array1::zeros(steps);
+        let mut std_errors = Array1::zeros(steps);
+        for h in 0..steps {
+            state = self.ssm.f.dot(&state);
+            cov = self.ssm.f.dot(&cov).dot(&self.ssm.f.t()) + &rqr;
+            let xb = exog.map_or(0.0, |x| x.row(h).dot(&beta));
+            mean[h] = self.intercept + xb + self.ssm.h.row(0).dot(&state);
+            let var = self.ssm.h.dot(&cov).dot(&self.ssm.h.t())[[0, 0]];
+            std_errors[h] = var.max(0.0).sqrt();
+        }
+        Ok(SarimaxForecast {
+            lower: &mean - &(&std_errors * z),
+            upper: &mean + &(&std_errors * z),
+            mean,
+            std_errors,
+            alpha,
+        })
+    }
+
+    /// Check that the AR polynomials have all roots outside the unit circle.
+    pub fn is_stationary(&self) -> bool {
+        check_roots_outside_unit_circle(&self.ar_params)
+            && check_roots_outside_unit_circle(&self.seasonal_ar_params)
+    }
+
+    /// Check that the MA polynomials have all roots outside the unit circle.
+    pub fn is_invertible(&self) -> bool {
+        check_roots_outside_unit_circle(&self.ma_params)
+            && check_roots_outside_unit_circle(&self.seasonal_ma_params)
+    }
+}
+
+impl fmt::Display for SarimaxResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let model_name = match &self.seasonal_order {
+            Some(so) => format!(
+                "SARIMAX({},{},{})({},{},{},{})",
+                self.order.p, self.order.d, self.order.q, so.p, so.d, so.q, so.s
+            ),
+            None => format!(
+                "SARIMAX({},{},{})",
+                self.order.p, self.order.d, self.order.q
+            ),
+        };
+        writeln!(f, "\n{:=^70}", format!(" {} via exact MLE ", model_name))?;
+        writeln!(f, "{:<20} {:>10}", "Observations:", self.n_obs)?;
+        writeln!(f, "{:<20} {:>10}", "Missing:", self.n_missing)?;
+        writeln!(f, "{:<20} {:>10}", "Diffuse periods:", self.n_diffuse)?;
+        writeln!(f, "{:<20} {:>10?}", "Initialization:", self.initialization)?;
+        writeln!(f, "{:<20} {:>10.4}", "Log-Likelihood:", self.log_likelihood)?;
+        writeln!(f, "{:<20} {:>10.4}", "AIC:", self.aic)?;
+        writeln!(f, "{:<20} {:>10.4}", "AICc:", self.aicc)?;
+        writeln!(f, "{:<20} {:>10.4}", "BIC:", self.bic)?;
+        writeln!(f, "{:<20} {:>10}", "Converged:", self.converged)?;
+
+        writeln!(f, "\n{:-^70}", " Parameters ")?;
+        writeln!(
+            f,
+            "{:<15} {:>10} {:>10} {:>8} {:>8} {:>10} {:>10}",
+            "", "coef", "std err", "z", "P>|z|", "[0.025", "0.975]"
+        )?;
+        writeln!(f, "{:-^70}", "")?;
+        for (i, name) in self.param_names.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<15} {:>10.4} {:>10.4} {:>8.3} {:>8.3} {:>10.4} {:>10.4}",
+                name,
+                self.params[i],
+                self.std_errors[i],
+                self.z_values[i],
+                self.p_values[i],
+                self.conf_lower[i],
+                self.conf_upper[i],
+            )?;
+        }
+        writeln!(f, "{:=^70}", "")
+    }
+}
+
+fn validate(y: &Array1<f64>, exog: Option<&Array2<f64>>) -> Result<(), GreenersError> {
+    if y.iter().any(|v| v.is_infinite()) {
+        return Err(GreenersError::InvalidOperation(
+            "Series contains infinite values (use NaN for missing periods)".into(),
+        ));
+    }
+    if let Some(x) = exog {
+        if x.nrows() != y.len() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Exogenous matrix has {} rows but series has {} observations",
+                x.nrows(),
+                y.len()
+            )));
+        }
+        if x.iter().any(|v| !v.is_finite()) {
+            return Err(GreenersError::InvalidOperation(
+                "Exogenous regressors must be fully observed".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Model orders and options shared by the likelihood, the transforms and
+/// the result builder.
+#[derive(Debug, Clone)]
+struct Spec {
+    p: usize,
+    d: usize,
+    q: usize,
+    sp: usize,
+    sd: usize,
+    sq: usize,
+    s: usize,
+    k_exog: usize,
+    mean: bool,
+    initialization: SarimaxInitialization,
+    enforce_stationarity: bool,
+    enforce_invertibility: bool,
+    kappa: f64,
+}
+
+impl Spec {
+    fn new(
+        order: (usize, usize, usize),
+        seasonal_order: (usize, usize, usize, usize),
+        k_exog: usize,
+        config: &SarimaxConfig,
+    ) -> Result<Self, GreenersError> {
+        let (p, d, q) = order;
+        let (sp, sd, sq, s) = seasonal_order;
+        if sp + sd + sq > 0 && s < 2 {
+            return Err(GreenersError::InvalidOperation(
+                "Seasonal terms require a seasonal period s >= 2".into(),
+            ));
+        }
+        if config.diffuse_variance.is_nan() || config.diffuse_variance <= 0.0 {
+            return Err(GreenersError::InvalidOperation(
+                "diffuse_variance must be positive".into(),
+            ));
+        }
+        Ok(Spec {
+            p,
+            d,
+            q,
+            sp,
+            sd,
+            sq,
+            s: if sp + sd + sq > 0 { s } else { 1 },
+            k_exog,
+            mean: config.include_mean && d + sd == 0,
+            initialization: config.initialization,
+            enforce_stationarity: config.enforce_stationarity,
+            enforce_invertibility: config.enforce_invertibility,
+            kappa: config.diffuse_variance,
+        })
+    }
+
+    fn n_params(&self) -> usize {
+        self.mean as usize + self.k_exog + self.p + self.q + self.sp + self.sq + 1
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), GreenersError> {
+        if len != self.n_params() {
+            return Err(GreenersError::ShapeMismatch(format!(
+                "Expected {} parameters, got {}",
+                self.n_params(),
+                len
+            )));
+        }
+        Ok(())
+    }
+
+    /// Positions of the ARMA coefficients in the parameter vector
+    fn arma_range(&self) -> (usize, usize) {
+        let lo = self.mean as usize + self.k_exog;
+        (lo, lo + self.p + self.q + self.sp + self.sq)
+    }
+
+    fn n_diff(&self) -> usize {
+        self.d + self.sd * self.s
+    }
+
+    fn n_arma_states(&self) -> usize {
+        (self.p + self.sp * self.s).max(self.q + self.sq * self.s + 1)
+    }
+
+    /// Observed periods whose likelihood contributions are dropped
+    fn burn(&self) -> usize {
+        match self.initialization {
+            SarimaxInitialization::Stationary => self.n_diff(),
+            SarimaxInitialization::ApproximateDiffuse => self.n_arma_states() + self.n_diff(),
+        }
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.n_params());
+        if self.mean {
+            names.push("intercept".to_string());
+        }
+        names.extend((1..=self.k_exog).map(|j| format!("x{}", j)));
+        names.extend((1..=self.p).map(|l| format!("ar.L{}", l)));
+        names.extend((1..=self.q).map(|l| format!("ma.L{}", l)));
+        names.extend((1..=self.sp).map(|l| format!("ar.S.L{}", l * self.s)));
+        names.extend((1..=self.sq).map(|l| format!("ma.S.L{}", l * self.s)));
+        names.push("sigma2".to_string());
+        names
+    }
+
+    /// Split `[mu, beta, ar, ma, sar, sma, sigma2]` into its blocks.
+    fn split<'a>(&self, params: &'a [f64]) -> Blocks<'a> {
+        let mut at = 0;
+        let mut take = |k: usize| {
+            let block = &params[at..at + k];
+            at += k;
+            block
+        };
+        let mean = take(self.mean as usize).first().copied().unwrap_or(0.0);
+        Blocks {
+            mean,
+            beta: take(self.k_exog),
+            ar: take(self.p),
+            ma: take(self.q),
+            sar: take(self.sp),
+            sma: take(self.sq),
+            sigma2: take(1)[0],
+        }
+    }
+
+    /// State-space form of `w_t`: state `[a_t (Harvey ARMA), w_{t-1}, ..., w_{t-K}]`.
+    fn state_space(&self, params: &[f64]) -> Result<StateSpaceModel, GreenersError> {
+        let b = self.split(params);
+        // phi*(L) = 1 - sum phi*_k L^k, theta*(L) = 1 + sum theta*_k L^k
+        let ar_poly = poly_mul(&lag_poly(b.ar, -1.0, 1), &lag_poly(b.sar, -1.0, self.s));
+        let ma_poly = poly_mul(&lag_poly(b.ma, 1.0, 1), &lag_poly(b.sma, 1.0, self.s));
+        let mut diff_poly = vec![1.0];
+        for _ in 0..self.d {
+            diff_poly = poly_mul(&diff_poly, &[1.0, -1.0]);
+        }
+        for _ in 0..self.sd {
+            diff_poly = poly_mul(&diff_poly, &lag_poly(&[1.0], -1.0, self.s));
+        }
+
+        let r = self.n_arma_states();
+        let k = self.n_diff();
+        let m = r + k;
+        let mut f = Array2::zeros((m, m));
+        let mut h = Array2::zeros((1, m));
+        let mut sel = Array2::zeros((m, 1));
+        for (i, &c) in ar_poly.iter().enumerate().skip(1) {
+            f[[i - 1, 0]] = -c;
+        }
+        for i in 0..r - 1 {
+            f[[i, i + 1]] = 1.0;
+        }
+        sel[[0, 0]] = 1.0;
+        for (i, &c) in ma_poly.iter().enumerate().skip(1) {
+            sel[[i, 0]] = c;
+        }
+        h[[0, 0]] = 1.0;
+        if k > 0 {
+            // w_t = a_t[0] + sum delta_j w_{t-j} enters the first lag slot
+            f[[r, 0]] = 1.0;
+            for j in 1..=k {
+                h[[0, r + j - 1]] = -diff_poly[j];
+                f[[r, r + j - 1]] = -diff_poly[j];
+            }
+            for j in 1..k {
+                f[[r + j, r + j - 1]] = 1.0;
+            }
+        }
+
+        let q = Array2::from_elem((1, 1), b.sigma2);
+        let mut p0 = Array2::<f64>::eye(m) * self.kappa;
+        if self.initialization == SarimaxInitialization::Stationary {
+            let t = f.slice(ndarray::s![..r, ..r]).to_owned();
+            let rr = sel.slice(ndarray::s![..r, ..]).to_owned();
+            let cov = solve_discrete_lyapunov(&t, &(rr.dot(&rr.t()) * b.sigma2)).map_err(|_| {
+                GreenersError::InvalidOperation(
+                    "AR part is not stationary; use the approximate diffuse initialization".into(),
+                )
+            })?;
+            p0.slice_mut(ndarray::s![..r, ..r]).assign(&cov);
+        }
+        Ok(StateSpaceModel {
+            h,
+            f,
+            r: sel,
+            q,
+            r_obs: Array2::zeros((1, 1)),
+            s0: Array1::zeros(m),
+            p0,
+        })
+    }
+
+    /// `mu + x_t' beta` for every period.
+    fn regression_mean(&self, params: &[f64], exog: Option<&Array2<f64>>, n: usize) -> Array1<f64> {
+        let b = self.split(params);
+        let mut xb = Array1::from_elem(n, b.mean);
+        if let Some(x) = exog {
+            xb += &x.dot(&Array1::from(b.beta.to_vec()));
+        }
+        xb
+    }
+
+    /// Filter the model and return the likelihood without the diffuse periods.
+    fn evaluate(
+        &self,
+        y: &Array1<f64>,
+        exog: Option<&Array2<f64>>,
+        params: &[f64],
+    ) -> Result<(KalmanResult, StateSpaceModel, f64, usize), GreenersError> {
+        let b = self.split(params);
+        if b.sigma2.is_nan() || b.sigma2 <= 0.0 {
+            return Err(GreenersError::InvalidOperation(
+                "sigma2 must be positive".into(),
+            ));
+        }
+        let model = self.state_space(params)?;
+        let xb = self.regression_mean(params, exog, y.len());
+        let obs: Vec<Array1<f64>> = y
+            .iter()
+            .zip(xb.iter())
+            .map(|(v, m)| Array1::from_elem(1, v - m))
+            .collect();
+        let kf = KalmanFilter::filter(&model, &obs)?;
+
+        let burn = self.burn();
+        let mut seen = 0;
+        let mut log_lik = 0.0;
+        let mut n_obs = 0;
+        for (v, c) in y.iter().zip(kf.loglik_contributions.iter()) {
+            if v.is_finite() {
+                if seen >= burn {
+                    log_lik += c;
+                    n_obs += 1;
+                }
+                seen += 1;
+            }
+        }
+        Ok((kf, model, log_lik, n_obs))
+    }
+
+    fn neg_loglik(&self, y: &Array1<f64>, exog: Option<&Array2<f64>>, params: &[f64]) -> f64 {
+        match self.evaluate(y, exog, params) {
+            Ok((_, _, ll, _)) if ll.is_finite() => -ll,
+            _ => f64::NAN,
+        }
+    }
+
+    /// Map unconstrained optimizer values to model parameters.
+    fn constrain(&self, u: &[f64]) -> Vec<f64> {
+        let mut params = u.to_vec();
+        let (lo, _) = self.arma_range();
+        let mut at = lo;
+        for (k, ar) in [
+            (self.p, true),
+            (self.q, false),
+            (self.sp, true),
+            (self.sq, false),
+        ] {
+            let block = &mut params[at..at + k];
+            if ar && self.enforce_stationarity {
+                block.copy_from_slice(&constrain_stationary(block));
+            } else if !ar && self.enforce_invertibility {
+                let phi = constrain_stationary(block);
+                block.iter_mut().zip(phi).for_each(|(b, v)| *b = -v);
+            }
+            at += k;
+        }
+        params[at] = u[at].exp();
+        params
+    }
+
+    /// Inverse of [`Spec::constrain`]; blocks outside the admissible region
+    /// restart from zero.
+    fn unconstrain(&self, params: &[f64]) -> Vec<f64> {
+        let mut u = params.to_vec();
+        let (lo, _) = self.arma_range();
+        let mut at = lo;
+        for (k, ar) in [
+            (self.p, true),
+            (self.q, false),
+            (self.sp, true),
+            (self.sq, false),
+        ] {
+            let block = &mut u[at..at + k];
+            if ar && self.enforce_stationarity {
+                let v = unconstrain_stationary(block).unwrap_or_else(|| vec![0.0; k]);
+                block.copy_from_slice(&v);
+            } else if !ar && self.enforce_invertibility {
+                let neg: Vec<f64> = block.iter().map(|v| -v).collect();
+                let v = unconstrain_stationary(&neg).unwrap_or_else(|| vec![0.0; k]);
+                block.copy_from_slice(&v);
+            }
+            at += k;
+        }
+        u[at] = params[at].max(1e-12).ln();
+        u
+    }
+
+    /// Starting values: least squares for the regression part on the
+    /// differenced data, Hannan-Rissanen on the interpolated errors.
+    fn start_params(&self, y: &Array1<f64>, exog: Option<&Array2<f64>>) -> Vec<f64> {
+        let diff = |v: &Array1<f64>| {
+            let mut z = difference(v, self.d);
+            if self.sd > 0 {
+                z = seasonal_difference(&z, self.sd, self.s);
+            }
+            z
+        };
+        let mut coefs = vec![0.0; self.mean as usize + self.k_exog];
+        let yd = diff(y);
+        let mut cols: Vec<Array1<f64>> = Vec::new();
+        if self.mean {
+            cols.push(Array1::ones(yd.len()));
+        }
+        if let Some(x) = exog {
+            cols.extend(x.columns().into_iter().map(|c| diff(&c.to_owned())));
+        }
+        if !cols.is_empty() {
+            let rows: Vec<usize> = (0..yd.len()).filter(|&i| yd[i].is_finite()).collect();
+            let xm = Array2::from_shape_fn((rows.len(), cols.len()), |(i, j)| cols[j][rows[i]]);
+            let ym = Array1::from_iter(rows.iter().map(|&i| yd[i]));
+            if let Ok(inv) = xm.t().dot(&xm).inv() {
+                let b = inv.dot(&xm.t().dot(&ym));
+                if b.iter().all(|v| v.is_finite()) {
+                    coefs = b.to_vec();
+                }
+            }
+        }
+
+        let mut params = coefs.clone();
+        params.extend(std::iter::repeat_n(
+            0.0,
+            self.p + self.q + self.sp + self.sq,
+        ));
+        params.push(1.0);
+        let w = y - &self.regression_mean(&params, exog, y.len());
+        let w = interpolate(&w);
+        let (lo, hi) = self.arma_range();
+        let seasonal = (self.sp, self.sd, self.sq, self.s);
+        match ARIMA::fit_sarimax(&w, (self.p, self.d, self.q), seasonal, None) {
+            Ok(hr) => {
+                let arma: Vec<f64> = hr
+                    .ar_params
+                    .iter()
+                    .chain(hr.ma_params.iter())
+                    .chain(hr.seasonal_ar_params.iter())
+                    .chain(hr.seasonal_ma_params.iter())
+                    .copied()
+                    .collect();
+                if arma.len() == hi - lo && arma.iter().all(|v| v.is_finite()) {
+                    params[lo..hi].copy_from_slice(&arma);
+                }
+                params[hi] = hr.sigma2;
+            }
+            Err(_) => {
+                let wd = diff(&w);
+                params[hi] = wd.var(1.0);
+            }
+        }
+        if params[hi].is_nan() || params[hi] <= 0.0 {
+            params[hi] = 1.0;
+        }
+        // Round trip through the transforms to land in the admissible region
+        self.constrain(&self.unconstrain(&params))
+    }
+
+    /// Inverse of the numerical Hessian of the negative log-likelihood.
+    fn observed_information_inverse(
+        &self,
+        y: &Array1<f64>,
+        exog: Option<&Array2<f64>>,
+        params: &[f64],
+    ) -> Array2<f64> {
+        inverse_hessian(&|x: &[f64]| self.neg_loglik(y, exog, x), params)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_result(
+        &self,
+        y: &Array1<f64>,
+        exog: Option<&Array2<f64>>,
+        params: Vec<f64>,
+        cov_params: Array2<f64>,
+        kf: KalmanResult,
+        ssm: StateSpaceModel,
+        log_likelihood: f64,
+        n_obs: usize,
+        n_iter: u64,
+        converged: bool,
+    ) -> Result<SarimaxResult, GreenersError> {
+        let n = y.len();
+        let xb = self.regression_mean(&params, exog, n);
+        let fitted_values =
+            Array1::from_shape_fn(n, |t| xb[t] + ssm.h.row(0).dot(&kf.predicted_states[t]));
+        let prediction_variance = Array1::from_shape_fn(n, |t| kf.innovation_cov[t][[0, 0]]);
+        let residuals = y - &fitted_values;
+
+        let k = params.len();
+        let est = Array1::from(params.clone());
+        let (std_errors, z_values, p_values, conf_lower, conf_upper) =
+            wald_inference(&est, &cov_params);
+
+        let kf_f = k as f64;
+        let nf = n_obs as f64;
+        let aic = -2.0 * log_likelihood + 2.0 * kf_f;
+        let aicc = if nf > kf_f + 1.0 {
+            aic + 2.0 * kf_f * (kf_f + 1.0) / (nf - kf_f - 1.0)
+        } else {
+            f64::INFINITY
+        };
+        let bic = -2.0 * log_likelihood + kf_f * nf.ln();
+
+        let b = self.split(&params);
+        let last_state = kf.filtered_states.last().cloned().unwrap_or(ssm.s0.clone());
+        let last_cov = kf.filtered_cov.last().cloned().unwrap_or(ssm.p0.clone());
+        Ok(SarimaxResult {
+            intercept: b.mean,
+            exog_params: (self.k_exog > 0).then(|| Array1::from(b.beta.to_vec())),
+            ar_params: Array1::from(b.ar.to_vec()),
+            ma_params: Array1::from(b.ma.to_vec()),
+            seasonal_ar_params: Array1::from(b.sar.to_vec()),
+            seasonal_ma_params: Array1::from(b.sma.to_vec()),
+            sigma2: b.sigma2,
+            params: est,
+            std_errors,
+            z_values,
+            p_values,
+            conf_lower,
+            conf_upper,
+            cov_params,
+            param_names: self.param_names(),
+            order: ArimaOrder {
+                p: self.p,
+                d: self.d,
+                q: self.q,
+            },
+            seasonal_order: (self.s > 1).then_some(SeasonalOrder {
+                p: self.sp,
+                d: self.sd,
+                q: self.sq,
+                s: self.s,
+            }),
+            log_likelihood,
+            aic,
+            aicc,
+            bic,
+            fitted_values,
+            prediction_variance,
+            residuals,
+            n_obs,
+            n_missing: y.iter().filter(|v| v.is_nan()).count(),
+            n_diffuse: self.burn(),
+            initialization: self.initialization,
+            n_iter,
+            converged,
+            ssm,
+            last_state,
+            last_cov,
+        })
+    }
+}
+
+struct Blocks<'a> {
+    mean: f64,
+    beta: &'a [f64],
+    ar: &'a [f64],
+    ma: &'a [f64],
+    sar: &'a [f64],
+    sma: &'a [f64],
+    sigma2: f64,
+}
+
+#[derive(Clone)]
+struct SarimaxProblem {
+    spec: Spec,
+    y: Array1<f64>,
+    exog: Option<Array2<f64>>,
+}
+
+impl CostFunction for SarimaxProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, ArgminError> {
+        let params = self.spec.constrain(param);
+        let v = self.spec.neg_loglik(&self.y, self.exog.as_ref(), &params);
+        Ok(if v.is_finite() { v } else { 1e12 })
+    }
+}
+
+/// Lag polynomial `1 + sign * (c_1 L^step + c_2 L^{2 step} + ...)`.
+fn lag_poly(coefs: &[f64], sign: f64, step: usize) -> Vec<f64> {
+    let mut poly = vec![0.0; coefs.len() * step + 1];
+    poly[0] = 1.0;
+    for (i, &c) in coefs.iter().enumerate() {
+        poly[(i + 1) * step] = sign * c;
+    }
+    poly
+}
+
+fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+/// Linear interpolation of missing values; the ends are held constant.
+fn interpolate(y: &Array1<f64>) -> Array1<f64> {
+    let known: Vec<usize> = (0..y.len()).filter(|&i| y[i].is_finite()).collect();
+    let mut out = y.clone();
+    if known.is_empty() {
+        return out;
+    }
+    for i in 0..y.len() {
+        if y[i].is_finite() {
+            continue;
+        }
+        let next = known.partition_point(|&k| k < i);
+        out[i] = match (next.checked_sub(1).map(|j| known[j]), known.get(next)) {
+            (Some(a), Some(&b)) => y[a] + (y[b] - y[a]) * (i - a) as f64 / (b - a) as f64,
+            (Some(a), None) => y[a],
+            (None, Some(&b)) => y[b],
+            (None, None) => 0.0,
+        };
+    }
+    out
+}
+
+fn build_simplex(center: &[f64], scale: f64) -> Vec<Vec<f64>> {
+    let n = center.len();
+    let mut vertices = Vec::with_capacity(n + 1);
+    vertices.push(center.to_vec());
+    for i in 0..n {
+        let mut v = center.to_vec();
+        v[i] += scale * center[i].abs().max(1.0);
+        vertices.push(v);
+    }
+    vertices
+}
@@ -15,12 +15,13 @@
 //! This implementation uses a simplified eigenvalue computation
 //! via the power iteration / QR approach on small matrices.
 
-use greeners_core::GreenersError;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of spectral clustering.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SpectralResult {
     /// Cluster assignments (n), values 0..k-1
     pub labels: Vec<usize>,
@@ -89,12 +90,15 @@ impl SpectralClustering {
     /// * `n_clusters` - Number of clusters k
     /// * `sigma` - Gaussian kernel width (default: median pairwise distance)
     /// * `max_iter` - Max k-means iterations (default 100)
+    /// * `seed` - Random seed (`None` uses the default seed)
     pub fn fit(
         x: &Array2<f64>,
         n_clusters: usize,
         sigma: Option<f64>,
         max_iter: Option<usize>,
+        seed: Option<u64>,
     ) -> Result<SpectralResult, GreenersError> {
+        let mut rng = GreenersRng::new(seed);
         let n = x.nrows();
         let d = x.ncols();
         if n < n_clusters + 1 {
@@ -177,7 +181,8 @@ impl SpectralClustering {
 
         // 5. K-means on normalized eigenvectors
         let iterations = max_iter.unwrap_or(100);
-        let (labels, centroids, inertia) = Self::kmeans(&u_norm, n, n_clusters, iterations)?;
+        let (labels, centroids, inertia) =
+            Self::kmeans(&u_norm, n, n_clusters, iterations, &mut rng)?;
 
         Ok(SpectralResult {
             labels,
@@ -294,12 +299,13 @@ impl SpectralClustering {
         n: usize,
         k: usize,
         max_iter: usize,
+        rng: &mut GreenersRng,
     ) -> Result<(Vec<usize>, Array2<f64>, f64), GreenersError> {
         let d = x.ncols();
 
         // Initialize centroids via k-means++
         let mut centroids = Array2::zeros((k, d));
-        let first = Self::rand_int(n);
+        let first = rng.index(n);
         for j in 0..d {
             centroids[(0, j)] = x[(first, j)];
         }
@@ -322,13 +328,13 @@ impl SpectralClustering {
             let total: f64 = dists.iter().sum();
             if total < 1e-15 {
                 // All points are at centroids, pick random
-                let idx = Self::rand_int(n);
+                let idx = rng.index(n);
                 for j in 0..d {
                     centroids[(c, j)] = x[(idx, j)];
                 }
                 continue;
             }
-            let r = Self::rand_uniform() * total;
+            let r = rng.uniform() * total;
             let mut cumsum = 0.0;
             let mut chosen = 0;
             for (i, &di) in dists.iter().enumerate().take(n) {
@@ -397,26 +403,4 @@ impl SpectralClustering {
 
         Ok((labels, centroids, inertia))
     }
-
-    fn rand_int(n: usize) -> usize {
-        if n == 0 {
-            return 0;
-        }
-        (Self::rand_uniform() * n as f64) as usize
-    }
-
-    fn rand_uniform() -> f64 {
-        use std::cell::Cell;
-        thread_local! {
-            static STATE: Cell<u64> = const { Cell::new(1357902468) };
-        }
-        STATE.with(|s| {
-            let mut state = s.get();
-            state = state
-                .wrapping_mul(6364136223846793005)
-                .wrapping_add(1442695040888963407);
-            s.set(state);
-            ((state >> 11) as f64) / (1u64 << 53) as f64
-        })
-    }
 }
@@ -4,13 +4,14 @@ use argmin::{
 };
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, Axis};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// State space model specification:
 /// y_t = H * s_t + e_t,  e_t ~ N(0, R_obs)
 /// s_t = F * s_{t-1} + R * u_t,  u_t ~ N(0, Q)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateSpaceModel {
     /// Observation matrix (n_obs x n_states)
     pub h: Array2<f64>,
@@ -29,7 +30,7 @@ pub struct StateSpaceModel {
 }
 
 /// Kalman filter result.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KalmanResult {
     pub filtered_states: Vec<Array1<f64>>,
     pub filtered_cov: Vec<Array2<f64>>,
@@ -38,12 +39,15 @@ pub struct KalmanResult {
     pub innovations: Vec<Array1<f64>>,
     pub innovation_cov: Vec<Array2<f64>>,
     pub log_likelihood: f64,
+    /// Log-likelihood contribution of each period (zero when nothing is observed)
+    #[serde(default)]
+    pub loglik_contributions: Vec<f64>,
     pub n_obs: usize,
     pub n_states: usize,
 }
 
 /// Smoothed state result (Rauch-Tung-Striebel).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SmoothedResult {
     pub smoothed_states: Vec<Array1<f64>>,
     pub smoothed_cov: Vec<Array2<f64>>,
@@ -52,7 +56,7 @@ pub struct SmoothedResult {
 }
 
 /// Full state space estimation result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StateSpaceResult {
     pub filtered_states: Vec<Array1<f64>>,
     pub smoothed_states: Vec<Array1<f64>>,
@@ -102,6 +106,11 @@ impl KalmanFilter {
     ///
     /// * `model` — state space model specification
     /// * `y` — observations, each element is a vector (can handle multivariate)
+    ///
+    /// Non-finite elements of `y` are treated as missing: the update uses the
+    /// observed elements only, and a fully missing period skips the update so
+    /// the filtered state equals the prediction. Missing elements get a NaN
+    /// innovation and add nothing to the log-likelihood.
     pub fn filter(
         model: &StateSpaceModel,
         y: &[Array1<f64>],
@@ -115,6 +124,7 @@ impl KalmanFilter {
         let mut predicted_cov = Vec::with_capacity(t);
         let mut innovations = Vec::with_capacity(t);
         let mut innovation_cov = Vec::with_capacity(t);
+        let mut loglik_contributions = Vec::with_capacity(t);
         let mut log_lik = 0.0;
 
         let rqr = model.r.dot(&model.q).dot(&model.r.t());
@@ -137,25 +147,48 @@ impl KalmanFilter {
             innovations.push(v.clone());
             innovation_cov.push(f_cov.clone());
 
+            let observed: Vec<usize> = (0..obs.len()).filter(|&i| obs[i].is_finite()).collect();
+            if observed.is_empty() {
+                filtered_states.push(s_pred.clone());
+                filtered_cov.push(p_pred.clone());
+                loglik_contributions.push(0.0);
+                s_pred = model.f.dot(&s_pred);
+                p_pred = model.f.dot(&p_pred).dot(&model.f.t()) + &rqr;
+                continue;
+            }
+            // Keep the rows of the observed elements
+            let (h, v, f_cov) = if observed.len() == obs.len() {
+                (model.h.clone(), v, f_cov)
+            } else {
+                (
+                    model.h.select(Axis(0), &observed),
+                    v.select(Axis(0), &observed),
+                    f_cov.select(Axis(0), &observed).select(Axis(1), &observed),
+                )
+            };
+
             // Kalman gain: K = P_pred * H' * F^{-1}
             let f_inv = f_cov.inv().map_err(|_| GreenersError::SingularMatrix)?;
-            let k_gain = p_pred.dot(&model.h.t()).dot(&f_inv);
+            let k_gain = p_pred.dot(&h.t()).dot(&f_inv);
 
             // Update
             let s_filt = &s_pred + &k_gain.dot(&v);
             let eye = Array2::<f64>::eye(m);
-            let p_filt = (&eye - &k_gain.dot(&model.h)).dot(&p_pred);
+            let p_filt = (&eye - &k_gain.dot(&h)).dot(&p_pred);
 
             filtered_states.push(s_filt.clone());
             filtered_cov.push(p_filt.clone());
 
             // Log-likelihood contribution
-            let n_y = obs.len() as f64;
+            let n_y = observed.len() as f64;
             let det = det_positive(&f_cov);
-            if det > 1e-30 {
-                log_lik += -0.5
-                    * (n_y * (2.0 * std::f64::consts::PI).ln() + det.ln() + v.dot(&f_inv.dot(&v)));
-            }
+            let contribution = if det > 1e-30 {
+                -0.5 * (n_y * (2.0 * std::f64::consts::PI).ln() + det.ln() + v.dot(&f_inv.dot(&v)))
+            } else {
+                0.0
+            };
+            log_lik += contribution;
+            loglik_contributions.push(contribution);
 
             // Predict next
             s_pred = model.f.dot(&s_filt);
@@ -170,6 +203,7 @@ impl KalmanFilter {
             innovations,
             innovation_cov,
             log_likelihood: log_lik,
+            loglik_contributions,
             n_obs: t,
             n_states: m,
         })
@@ -266,7 +300,7 @@ fn det_positive(m: &Array2<f64>) -> f64 {
 // ============================================================================
 
 /// Result of a local-level Kalman filter with estimated variances.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalLevelResult {
     pub sigma_obs: f64,
     pub sigma_state: f64,
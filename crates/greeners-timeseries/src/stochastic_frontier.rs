@@ -12,12 +12,13 @@
 use greeners_core::error::GreenersError;
 use greeners_core::linalg::LinalgInverse as _;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 use std::f64::consts;
 use std::fmt;
 
 /// Result of stochastic frontier estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SfaResult {
     /// Model type: "production" or "cost"
     pub model_type: String,
@@ -23,6 +23,7 @@ use argmin::{
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::ContinuousCDF;
 use std::fmt;
 
@@ -30,7 +31,7 @@ const LOG_CHI2_MEAN: f64 = -1.2703628454614762; // E[ln(χ²(1))] = -γ - ln 2
 const LOG_CHI2_VAR: f64 = 4.934802200544679; // Var[ln(χ²(1))] = π²/2
 
 /// Result of Stochastic Volatility estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SvResult {
     /// Long-run mean of log-volatility (mu)
     pub mu: f64,
@@ -1,12 +1,14 @@
 use crate::var::VarResult;
 use crate::var::VAR;
 use greeners_core::linalg::{LinalgCholesky as _, LinalgInverse as _, UPLO};
+use greeners_core::var_dynamics::VarDynamics;
 use greeners_core::GreenersError;
-use ndarray::{s, Array2, Array3};
+use ndarray::{s, Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Identification scheme for SVAR.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SVarIdentification {
     /// Cholesky decomposition (recursive ordering).
     Cholesky,
@@ -19,12 +21,15 @@ pub enum SVarIdentification {
 }
 
 /// SVAR estimation result.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SVarResult {
     pub var_result: VarResult,
     pub a_matrix: Array2<f64>,
     pub b_matrix: Array2<f64>,
     pub identification: String,
+    /// Scheme used in estimation, re-applied when the model is refitted
+    #[serde(default)]
+    pub identification_scheme: Option<SVarIdentification>,
 }
 
 impl SVarResult {
@@ -107,6 +112,50 @@ impl SVarResult {
     }
 }
 
+/// Forecasts and bootstrap bands for the structural shocks `A⁻¹B ε_t`.
+impl VarDynamics for SVarResult {
+    fn var_names(&self) -> Vec<String> {
+        self.var_result.var_names()
+    }
+
+    fn intercept(&self) -> Array1<f64> {
+        self.var_result.intercept()
+    }
+
+    fn lag_matrices(&self) -> Vec<Array2<f64>> {
+        self.var_result.lag_matrices()
+    }
+
+    fn innovation_cov(&self) -> Array2<f64> {
+        self.var_result.sigma_u.clone()
+    }
+
+    fn sample(&self) -> &Array2<f64> {
+        &self.var_result.data
+    }
+
+    fn innovations(&self) -> &Array2<f64> {
+        &self.var_result.residuals
+    }
+
+    fn impact(&self) -> Result<Array2<f64>, GreenersError> {
+        let a_inv = self
+            .a_matrix
+            .inv()
+            .map_err(|_| GreenersError::SingularMatrix)?;
+        Ok(a_inv.dot(&self.b_matrix))
+    }
+
+    fn refit(&self, data: &Array2<f64>) -> Result<Self, GreenersError> {
+        let scheme = self.identification_scheme.clone().ok_or_else(|| {
+            GreenersError::InvalidOperation(
+                "The identification scheme is not stored in this result".into(),
+            )
+        })?;
+        SVAR::fit(data, self.var_result.lags, scheme)
+    }
+}
+
 impl fmt::Display for SVarResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
@@ -157,6 +206,7 @@ impl SVAR {
         // First, estimate reduced-form VAR
         let var_result = VAR::fit(data, lags, None)?;
 
+        let scheme = identification.clone();
         let (a_matrix, b_matrix, id_name) = match identification {
             SVarIdentification::Cholesky => {
                 let p_chol = var_result
@@ -212,6 +262,7 @@ impl SVAR {
             a_matrix,
             b_matrix,
             identification: id_name,
+            identification_scheme: Some(scheme),
         })
     }
 }
@@ -1,14 +1,14 @@
-#[allow(unused_imports)]
 use greeners_core::linalg::{LinalgEigh as _, LinalgInverse as _, UPLO};
 use greeners_core::{CovarianceType, GreenersError};
 use greeners_ols::ols::OLS;
 use ndarray::{s, Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ChiSquared, ContinuousCDF, FisherSnedecor};
 
 // ─── Result structs ──────────────────────────────────────────────────────────
 
 /// Results of the Augmented Dickey-Fuller Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AdfResult {
     pub test_statistic: f64,
     pub p_value: Option<f64>,
@@ -19,7 +19,7 @@ pub struct AdfResult {
 }
 
 /// Results of the KPSS Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KpssResult {
     pub test_statistic: f64,
     pub critical_values: (f64, f64, f64, f64), // 10%, 5%, 2.5%, 1%
@@ -31,7 +31,7 @@ pub struct KpssResult {
 }
 
 /// Results of the Ljung-Box Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LjungBoxResult {
     pub test_statistic: f64,
     pub p_value: f64,
@@ -40,7 +40,7 @@ pub struct LjungBoxResult {
 }
 
 /// Results of the ARCH Test (Engle's LM test)
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ArchTestResult {
     pub test_statistic: f64,
     pub p_value: f64,
@@ -49,7 +49,7 @@ pub struct ArchTestResult {
 }
 
 /// Results of the Granger Causality Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct GrangerResult {
     pub f_statistic: f64,
     pub p_value: f64,
@@ -59,7 +59,7 @@ pub struct GrangerResult {
 }
 
 /// Results of the Engle-Granger Cointegration Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EngleGrangerResult {
     pub adf_statistic: f64,
     pub critical_values: (f64, f64, f64), // 1%, 5%, 10%
@@ -69,7 +69,7 @@ pub struct EngleGrangerResult {
 }
 
 /// Results of the Johansen Cointegration Test
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct JohansenResult {
     pub trace_stats: Array1<f64>,
     pub trace_critical_values: Array2<f64>, // n_vars x 3 (10%, 5%, 1%)
@@ -1115,7 +1115,7 @@ impl TimeSeries {
 }
 
 /// Results of the Phillips-Perron test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PhillipsPerronResult {
     pub z_alpha: f64,
     pub z_t: f64,
@@ -1126,7 +1126,7 @@ pub struct PhillipsPerronResult {
 }
 
 /// Results of the Zivot-Andrews test.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ZivotAndrewsResult {
     pub statistic: f64,
     pub break_point: usize,
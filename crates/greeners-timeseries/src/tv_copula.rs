@@ -15,11 +15,12 @@
 
 use greeners_core::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
 /// Copula types for time-varying estimation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum TvCopulaType {
     Gaussian,
     Clayton,
@@ -27,7 +28,7 @@ pub enum TvCopulaType {
 }
 
 /// Result of time-varying copula estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TvCopulaResult {
     /// Copula type
     pub copula_type: TvCopulaType,
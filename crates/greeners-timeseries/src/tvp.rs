@@ -11,10 +11,11 @@
 
 use greeners_core::error::GreenersError;
 use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of TVP regression.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TvpResult {
     /// Time-varying beta estimates (smoothed), shape (T, k)
     pub beta_smoothed: Array2<f64>,
@@ -16,10 +16,11 @@ use greeners_core::linalg::LinalgDeterminant as _;
 use greeners_core::linalg::LinalgInverse as _;
 use greeners_core::GreenersError;
 use ndarray::{Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of TVP-VAR estimation.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TvpVarResult {
     /// Smoothed time-varying coefficients, shape (T, n_regressors, k)
     pub beta_smoothed: Array3<f64>,
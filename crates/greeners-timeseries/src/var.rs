@@ -1,11 +1,13 @@
 use greeners_core::linalg::{
     LinalgCholesky as _, LinalgDeterminant as _, LinalgInverse as _, UPLO,
 };
+use greeners_core::var_dynamics::VarDynamics;
 use greeners_core::GreenersError; // Removidos OLS, CovarianceType
-use ndarray::{s, Array2, Array3}; // Removidos Array1, Axis
+use ndarray::{s, Array1, Array2, Array3};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VarResult {
     pub params: Array2<f64>,     // Matriz (1 + k*p) x k
     pub std_errors: Array2<f64>, // Matriz (1 + k*p) x k
@@ -16,6 +18,12 @@ pub struct VarResult {
     pub n_vars: usize,
     pub n_obs: usize,
     pub var_names: Vec<String>,
+    /// Estimation sample (T x k), used by forecasts and the bootstrap
+    #[serde(default)]
+    pub data: Array2<f64>,
+    /// Residuals for observations `lags..T` (n_obs x k)
+    #[serde(default)]
+    pub residuals: Array2<f64>,
 }
 
 impl VarResult {
@@ -122,6 +130,45 @@ impl VarResult {
     }
 }
 
+/// Forecasts, bootstrap IRF bands and historical decompositions.
+impl VarDynamics for VarResult {
+    fn var_names(&self) -> Vec<String> {
+        self.var_names.clone()
+    }
+
+    fn intercept(&self) -> Array1<f64> {
+        self.params.row(0).to_owned()
+    }
+
+    fn lag_matrices(&self) -> Vec<Array2<f64>> {
+        let k = self.n_vars;
+        (0..self.lags)
+            .map(|l| {
+                self.params
+                    .slice(s![1 + l * k..1 + (l + 1) * k, ..])
+                    .t()
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    fn innovation_cov(&self) -> Array2<f64> {
+        self.sigma_u.clone()
+    }
+
+    fn sample(&self) -> &Array2<f64> {
+        &self.data
+    }
+
+    fn innovations(&self) -> &Array2<f64> {
+        &self.residuals
+    }
+
+    fn refit(&self, data: &Array2<f64>) -> Result<Self, GreenersError> {
+        VAR::fit(data, self.lags, Some(self.var_names.clone()))
+    }
+}
+
 impl fmt::Display for VarResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
@@ -280,6 +327,8 @@ impl VAR {
             n_vars: k,
             n_obs,
             var_names: names,
+            data: data.to_owned(),
+            residuals,
         })
     }
 }
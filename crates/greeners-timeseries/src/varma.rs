@@ -1,9 +1,10 @@
 use greeners_core::linalg::{LinalgDeterminant as _, LinalgInverse as _};
 use greeners_core::GreenersError;
 use ndarray::{s, Array2};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VarmaResult {
     pub ar_params: Array2<f64>,           // Matriz A (AR)
     pub ma_params: Array2<f64>,           // Matriz M (MA)
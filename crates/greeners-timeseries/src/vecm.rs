@@ -1,13 +1,13 @@
 use greeners_core::linalg::{LinalgCholesky as _, LinalgEig as _, LinalgInverse as _, UPLO};
-use greeners_core::GreenersError;
+use greeners_core::var_dynamics::VarDynamics;
+use greeners_core::{GreenersError, GreenersRng};
 use ndarray::{s, Array1, Array2, Axis};
 use num_complex::Complex64;
-use rand::distributions::{Distribution, Uniform};
-use rand::thread_rng;
+use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VecmResult {
     pub alpha: Array2<f64>,
     pub beta: Array2<f64>,
@@ -125,7 +125,12 @@ impl fmt::Display for VecmResult {
 impl VecmResult {
     /// Run a parametric residual bootstrap and return a new `VecmResult` with
     /// `std_errors_alpha`, `std_errors_beta` and `std_errors_gamma` populated.
-    pub fn bootstrap_standard_errors(&self, n_boot: usize) -> Result<VecmResult, GreenersError> {
+    /// `seed: None` uses the default seed.
+    pub fn bootstrap_standard_errors(
+        &self,
+        n_boot: usize,
+        seed: Option<u64>,
+    ) -> Result<VecmResult, GreenersError> {
         if n_boot == 0 {
             return Err(GreenersError::InvalidOperation(
                 "n_boot must be positive".into(),
@@ -146,8 +151,7 @@ impl VecmResult {
         let mut gamma_sum = Array2::<f64>::zeros((k, n_gamma_short_cols));
         let mut gamma_sum_sq = Array2::<f64>::zeros((k, n_gamma_short_cols));
 
-        let mut rng = thread_rng();
-        let idx_dist = Uniform::new(0, n_eff);
+        let mut rng = GreenersRng::new(seed);
 
         let mut successes = 0usize;
         let max_failures = n_boot / 2 + 1;
@@ -178,7 +182,7 @@ impl VecmResult {
                 dy_t += &self.gamma.column(0).insert_axis(Axis(1));
 
                 // Bootstrap residual
-                let idx = idx_dist.sample(&mut rng);
+                let idx = rng.index(n_eff);
                 dy_t += &self.residuals.row(idx).insert_axis(Axis(1));
 
                 let y_t = &y_boot.row(t - 1).insert_axis(Axis(1)) + &dy_t;
@@ -261,8 +265,63 @@ impl VecmResult {
     }
 
     /// Convenience alias for `bootstrap_standard_errors`.
-    pub fn with_inference(&self, n_boot: usize) -> Result<VecmResult, GreenersError> {
-        self.bootstrap_standard_errors(n_boot)
+    pub fn with_inference(
+        &self,
+        n_boot: usize,
+        seed: Option<u64>,
+    ) -> Result<VecmResult, GreenersError> {
+        self.bootstrap_standard_errors(n_boot, seed)
+    }
+}
+
+/// Forecasts and bootstrap bands through the VAR in levels implied by the
+/// VECM: `A_1 = I + αβ' + Γ_1`, `A_l = Γ_l - Γ_{l-1}`, `A_p = -Γ_{p-1}`.
+impl VarDynamics for VecmResult {
+    fn var_names(&self) -> Vec<String> {
+        self.variable_names.clone()
+    }
+
+    fn intercept(&self) -> Array1<f64> {
+        self.gamma.column(0).to_owned()
+    }
+
+    fn lag_matrices(&self) -> Vec<Array2<f64>> {
+        let k = self.n_vars;
+        let p = self.lags;
+        let gamma_l = |l: usize| -> Array2<f64> {
+            if l == 0 || l >= p {
+                Array2::zeros((k, k))
+            } else {
+                self.gamma
+                    .slice(s![.., 1 + (l - 1) * k..1 + l * k])
+                    .to_owned()
+            }
+        };
+        (1..=p)
+            .map(|l| {
+                let mut a = &gamma_l(l) - &gamma_l(l - 1);
+                if l == 1 {
+                    a += &(Array2::<f64>::eye(k) + self.alpha.dot(&self.beta.t()));
+                }
+                a
+            })
+            .collect()
+    }
+
+    fn innovation_cov(&self) -> Array2<f64> {
+        self.residuals.t().dot(&self.residuals) / self.n_obs.max(1) as f64
+    }
+
+    fn sample(&self) -> &Array2<f64> {
+        &self.data
+    }
+
+    fn innovations(&self) -> &Array2<f64> {
+        &self.residuals
+    }
+
+    fn refit(&self, data: &Array2<f64>) -> Result<Self, GreenersError> {
+        VECM::fit(data, self.lags, self.rank)
     }
 }
 
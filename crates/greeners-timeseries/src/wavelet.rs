@@ -17,10 +17,11 @@
 
 use greeners_core::GreenersError;
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Result of MODWT decomposition.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ModwtResult {
     /// Wavelet coefficients at each scale, shape (n_scales, T)
     pub wavelet_coeffs: Vec<Array1<f64>>,
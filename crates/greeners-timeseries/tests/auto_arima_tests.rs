@@ -0,0 +1,194 @@
+use greeners_core::GreenersRng;
+use greeners_timeseries::auto_arima::{
+    ArimaMethod, AutoArima, AutoArimaConfig, InformationCriterion, SeasonalTest, UnitRootTest,
+};
+use ndarray::Array1;
+
+fn white_noise(n: usize, seed: u64) -> Array1<f64> {
+    let mut rng = GreenersRng::new(Some(seed));
+    Array1::from_shape_fn(n, |_| rng.normal())
+}
+
+fn cumsum(x: &Array1<f64>) -> Array1<f64> {
+    let mut acc = 0.0;
+    x.mapv(|v| {
+        acc += v;
+        acc
+    })
+}
+
+/// y_t = 0.6 y_{t-1} - 0.3 y_{t-2} + e_t
+fn ar2(n: usize, seed: u64) -> Array1<f64> {
+    let e = white_noise(n, seed);
+    let mut y = Array1::<f64>::zeros(n);
+    for t in 2..n {
+        y[t] = 0.6 * y[t - 1] - 0.3 * y[t - 2] + e[t];
+    }
+    y
+}
+
+/// Seasonal random walk of period 12 around a fixed seasonal pattern.
+fn seasonal_walk(n: usize, seed: u64) -> Array1<f64> {
+    let e = white_noise(n, seed);
+    let mut y = Array1::<f64>::zeros(n);
+    for t in 0..n {
+        let pattern = 5.0 * (2.0 * std::f64::consts::PI * t as f64 / 12.0).sin();
+        y[t] = if t >= 12 {
+            y[t - 12] + 0.3 * e[t]
+        } else {
+            pattern
+        };
+    }
+    y
+}
+
+#[test]
+fn test_ndiffs_with_kpss_and_adf() {
+    let noise = white_noise(300, 1);
+    let walk = cumsum(&noise);
+    let i2 = cumsum(&walk);
+    for test in [UnitRootTest::Kpss, UnitRootTest::Adf] {
+        assert_eq!(AutoArima::ndiffs(&noise, test, 2).unwrap(), 0);
+        assert_eq!(AutoArima::ndiffs(&walk, test, 2).unwrap(), 1);
+        assert_eq!(AutoArima::ndiffs(&i2, test, 2).unwrap(), 2);
+    }
+    // Capped at max_d
+    assert_eq!(AutoArima::ndiffs(&i2, UnitRootTest::Kpss, 1).unwrap(), 1);
+}
+
+#[test]
+fn test_nsdiffs_detects_seasonal_unit_root() {
+    let y = seasonal_walk(240, 2);
+    assert!(AutoArima::seasonal_strength(&y, 12).unwrap() > 0.64);
+    let (stat, crit) = AutoArima::ocsb(&y, 12).unwrap();
+    assert!(stat > crit, "OCSB {} vs {}", stat, crit);
+    for test in [SeasonalTest::SeasonalStrength, SeasonalTest::Ocsb] {
+        assert_eq!(AutoArima::nsdiffs(&y, 12, test, 1).unwrap(), 1);
+    }
+
+    let noise = white_noise(240, 3);
+    assert!(AutoArima::seasonal_strength(&noise, 12).unwrap() < 0.64);
+    let (stat, crit) = AutoArima::ocsb(&noise, 12).unwrap();
+    assert!(stat < crit);
+    for test in [SeasonalTest::SeasonalStrength, SeasonalTest::Ocsb] {
+        assert_eq!(AutoArima::nsdiffs(&noise, 12, test, 1).unwrap(), 0);
+    }
+    assert_eq!(AutoArima::nsdiffs(&y, 1, SeasonalTest::Ocsb, 1).unwrap(), 0);
+}
+
+#[test]
+fn test_stepwise_search_on_ar2() {
+    let y = ar2(400, 4);
+    let config = AutoArimaConfig::default();
+    let fit = AutoArima::fit(&y, &config).unwrap();
+    assert_eq!(fit.order.1, 0);
+    assert!(fit.model.is_stationary() && fit.model.is_invertible());
+    assert_eq!(fit.model.order.p, fit.order.0);
+    assert_eq!(fit.model.order.q, fit.order.2);
+    assert!(fit.candidates.len() + fit.n_failed <= config.max_models);
+
+    // Ranked table: admissible candidates first, by increasing AICc
+    let best = &fit.candidates[0];
+    assert_eq!(best.order, fit.order);
+    let admissible: Vec<_> = fit
+        .candidates
+        .iter()
+        .take_while(|c| c.admissible(InformationCriterion::Aicc))
+        .collect();
+    assert!(admissible.windows(2).all(|w| w[0].aicc <= w[1].aicc));
+    assert!(fit.candidates[admissible.len()..]
+        .iter()
+        .all(|c| !c.admissible(InformationCriterion::Aicc)));
+
+    // AICc = AIC + 2k(k+1)/(n-k-1) on the differenced sample
+    let k = best.n_params as f64;
+    let n = 400.0;
+    assert!((best.aicc - best.aic - 2.0 * k * (k + 1.0) / (n - k - 1.0)).abs() < 1e-9);
+
+    // The AR structure is found: white noise is far worse
+    let wn = fit
+        .candidates
+        .iter()
+        .find(|c| c.order == (0, 0, 0))
+        .unwrap();
+    assert!(wn.aicc - best.aicc > 50.0);
+    assert!(best.order.0 >= 1);
+    assert!(format!("{}", fit).contains("Selected model"));
+}
+
+#[test]
+fn test_full_grid_is_at_least_as_good_as_stepwise() {
+    let y = cumsum(&ar2(300, 5));
+    let small = AutoArimaConfig {
+        max_p: 3,
+        max_q: 2,
+        max_order: 4,
+        ..AutoArimaConfig::default()
+    };
+    let stepwise = AutoArima::fit(&y, &small).unwrap();
+    assert_eq!(stepwise.order.1, 1);
+
+    let grid = AutoArima::fit(
+        &y,
+        &AutoArimaConfig {
+            stepwise: false,
+            ..small.clone()
+        },
+    )
+    .unwrap();
+    // p <= 3, q <= 2, p + q <= 4
+    assert_eq!(grid.candidates.len() + grid.n_failed, 11);
+    assert!(grid.candidates[0].aicc <= stepwise.candidates[0].aicc + 1e-9);
+
+    // Fixed d and other criteria
+    let bic = AutoArima::fit(
+        &y,
+        &AutoArimaConfig {
+            d: Some(1),
+            criterion: InformationCriterion::Bic,
+            ..small
+        },
+    )
+    .unwrap();
+    let ranked = &bic.candidates;
+    assert!(ranked[0].bic <= ranked[1].bic || !ranked[1].admissible(InformationCriterion::Bic));
+}
+
+#[test]
+fn test_seasonal_search_and_mle() {
+    let y = seasonal_walk(240, 6);
+    let config = AutoArimaConfig {
+        seasonal_period: 12,
+        max_p: 2,
+        max_q: 2,
+        max_seasonal_p: 1,
+        max_seasonal_q: 1,
+        ..AutoArimaConfig::default()
+    };
+    let fit = AutoArima::fit(&y, &config).unwrap();
+    assert_eq!(fit.seasonal_order.1, 1);
+    assert_eq!(fit.seasonal_order.3, 12);
+    assert_eq!(fit.seasonal_test, Some(SeasonalTest::SeasonalStrength));
+    assert!(fit.model.is_stationary() && fit.model.is_invertible());
+
+    let mle = AutoArima::fit(
+        &ar2(300, 7),
+        &AutoArimaConfig {
+            method: ArimaMethod::Mle,
+            max_order: 4,
+            ..AutoArimaConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(mle.model.estimation_method, "mle");
+    assert!(mle.candidates[0].log_likelihood == mle.model.log_likelihood);
+
+    let bad = AutoArimaConfig {
+        method: ArimaMethod::Mle,
+        ..config
+    };
+    assert!(AutoArima::fit(&y, &bad).is_err());
+    let mut with_nan = y.clone();
+    with_nan[3] = f64::NAN;
+    assert!(AutoArima::fit(&with_nan, &AutoArimaConfig::default()).is_err());
+}
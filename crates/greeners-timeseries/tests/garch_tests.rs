@@ -1,6 +1,9 @@
+use greeners_core::GreenersRng;
+use greeners_timeseries::garch::ConstantVariance;
 use greeners_timeseries::garch::EGARCH;
 use greeners_timeseries::garch::GARCH;
 use greeners_timeseries::garch::GJRGARCH;
+use greeners_timeseries::garch::{GarchModelType, APARCH, FIGARCH, HARCH};
 use ndarray::Array1;
 
 /// Generate synthetic GARCH(1,1) data for testing
@@ -191,3 +194,184 @@ fn test_error_handling() {
     let y = generate_garch_data(100);
     assert!(GARCH::fit(&y, 1, 0).is_err()); // q=0 invalid
 }
+
+/// APARCH(1,1) with Normal shocks, after a burn-in
+fn generate_aparch_data(n: usize, params: [f64; 5], seed: u64) -> Array1<f64> {
+    let [omega, alpha, gamma, beta, delta] = params;
+    let mut rng = GreenersRng::new(Some(seed));
+    let mut e: f64 = 0.0;
+    let mut power: f64 = omega / (1.0 - beta);
+    let mut y = Vec::with_capacity(n);
+    for t in 0..n + 200 {
+        power = omega + alpha * (e.abs() - gamma * e).powf(delta) + beta * power;
+        e = power.powf(1.0 / delta) * rng.normal();
+        if t >= 200 {
+            y.push(e);
+        }
+    }
+    Array1::from_vec(y)
+}
+
+/// HARCH with Normal shocks, after a burn-in
+fn generate_harch_data(n: usize, omega: f64, alphas: &[f64], lags: &[usize]) -> Array1<f64> {
+    let mut rng = GreenersRng::new(Some(17));
+    let mut e = vec![0.0; n + 200];
+    for t in 0..n + 200 {
+        let mut h = omega;
+        for (a, &l) in alphas.iter().zip(lags) {
+            let mean = e[t.saturating_sub(l)..t].iter().sum::<f64>() / l as f64;
+            h += a * mean * mean;
+        }
+        e[t] = h.sqrt() * rng.normal();
+    }
+    Array1::from_vec(e[200..].to_vec())
+}
+
+#[test]
+fn test_aparch_fit_and_simulated_forecasts() {
+    let y = generate_aparch_data(1500, [0.05, 0.08, 0.4, 0.85, 1.3], 7);
+    let result = APARCH::fit(&y, 1, 1).unwrap();
+    assert_eq!(result.model_type, GarchModelType::APARCH);
+    assert_eq!(
+        result.variable_names,
+        vec!["mu", "omega", "alpha[1]", "gamma[1]", "beta[1]", "delta"]
+    );
+    let (omega, alpha, gamma, beta, delta) = (
+        result.params[1],
+        result.params[2],
+        result.params[3],
+        result.params[4],
+        result.params[5],
+    );
+    assert!(gamma > 0.0 && gamma < 1.0, "gamma {}", gamma);
+    assert!(beta > 0.6 && (0.1..=4.0).contains(&delta));
+    assert!(result.log_likelihood.is_finite());
+
+    // One step ahead is the recursion at the end of the sample
+    let n = y.len();
+    let e = result.residuals[n - 1];
+    let h = result.conditional_variance[n - 1];
+    let expected = (omega + alpha * (e.abs() - gamma * e).powf(delta) + beta * h.powf(delta / 2.0))
+        .powf(2.0 / delta);
+    let fc = result.forecast(10);
+    assert!((fc[0] / expected - 1.0).abs() < 1e-10);
+
+    // Further steps are simulated, reproducibly
+    assert_eq!(fc, result.forecast_simulation(10, 10_000, None));
+    assert!(fc.iter().all(|v| v.is_finite() && *v > 0.0));
+    let vol = result.forecast_volatility(10);
+    assert!((vol[9] - fc[9].sqrt()).abs() < 1e-12);
+
+    // With delta = 2 the closed form agrees with the simulation
+    let mut quadratic = result.clone();
+    quadratic.params[5] = 2.0;
+    let analytic = quadratic.forecast(10);
+    let simulated = quadratic.forecast_simulation(10, 20_000, Some(3));
+    assert!((analytic[0] - simulated[0]).abs() < 1e-12);
+    for s in 1..10 {
+        assert!(
+            (analytic[s] / simulated[s] - 1.0).abs() < 0.05,
+            "step {}",
+            s
+        );
+    }
+
+    let result_t = APARCH::fit_t(&y, 1, 1).unwrap();
+    assert_eq!(result_t.params.len(), 7);
+    assert!(result_t.params[6] > result_t.params[5]);
+    assert!(APARCH::fit(&y, 1, 0).is_err());
+}
+
+#[test]
+fn test_figarch_long_memory_forecasts() {
+    let y = generate_garch_data(1000);
+    let result = FIGARCH::fit(&y, 1, 1).unwrap();
+    assert_eq!(result.model_type, GarchModelType::FIGARCH);
+    assert_eq!(
+        result.variable_names,
+        vec!["mu", "omega", "phi", "d", "beta"]
+    );
+    let (phi, d, beta) = (result.params[2], result.params[3], result.params[4]);
+    assert!((0.0..=1.0).contains(&d));
+    assert!(phi >= 0.0 && phi <= (1.0 - d) / 2.0 + 1e-12);
+    assert!(beta >= 0.0 && beta <= d + phi + 1e-12);
+    assert!(result.conditional_variance.iter().all(|h| *h > 0.0));
+    assert!(format!("{}", result).contains("FIGARCH(1,d,1)"));
+
+    // Analytic forecasts match the simulated mean
+    let fc = result.forecast(20);
+    let sim = result.forecast_simulation(20, 4000, Some(1));
+    assert!((fc[0] - sim[0]).abs() < 1e-12);
+    for s in [4, 19] {
+        assert!((fc[s] / sim[s] - 1.0).abs() < 0.05, "step {}", s);
+    }
+    assert!(result
+        .forecast_volatility(250)
+        .iter()
+        .all(|v| v.is_finite() && *v > 0.0));
+
+    let restricted = FIGARCH::fit_t(&y, 0, 0).unwrap();
+    assert_eq!(restricted.variable_names, vec!["mu", "omega", "d", "nu"]);
+    assert!(FIGARCH::fit(&y, 2, 1).is_err());
+}
+
+#[test]
+fn test_harch_fit_and_forecasts() {
+    let lags = [1, 5];
+    let y = generate_harch_data(2000, 0.1, &[0.3, 0.4], &lags);
+    let result = HARCH::fit(&y, &lags).unwrap();
+    assert_eq!(result.model_type, GarchModelType::HARCH);
+    assert_eq!(result.lags, vec![1, 5]);
+    assert_eq!((result.p, result.q), (0, 2));
+    assert_eq!(
+        result.variable_names,
+        vec!["mu", "omega", "alpha[1]", "alpha[5]"]
+    );
+    assert!(
+        (result.params[2] - 0.3).abs() < 0.15,
+        "{}",
+        result.params[2]
+    );
+    assert!(result.params[3] >= 0.0);
+    assert!(format!("{}", result).contains("HARCH(1,5)"));
+
+    // One step: omega + a1 e_n^2 + a5 (mean of the last five shocks)^2
+    let n = y.len();
+    let e = &result.residuals;
+    let mean5 = e.slice(ndarray::s![n - 5..]).sum() / 5.0;
+    let expected = result.params[1]
+        + result.params[2] * e[n - 1] * e[n - 1]
+        + result.params[3] * mean5 * mean5;
+    let fc = result.forecast(10);
+    assert!((fc[0] - expected).abs() < 1e-12);
+    let sim = result.forecast_simulation(10, 20_000, Some(2));
+    for s in 1..10 {
+        assert!((fc[s] / sim[s] - 1.0).abs() < 0.05, "step {}", s);
+    }
+
+    assert!(HARCH::fit(&y, &[]).is_err());
+    assert!(HARCH::fit(&y, &[5, 1]).is_err());
+    assert!(HARCH::fit(&y, &[0, 5]).is_err());
+}
+
+#[test]
+fn test_constant_variance() {
+    let y = generate_garch_data(500);
+    let result = ConstantVariance::fit(&y).unwrap();
+    let n = y.len() as f64;
+    let mean = y.sum() / n;
+    let var = y.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    assert!((result.params[0] - mean).abs() < 1e-8);
+    assert!((result.params[1] / var - 1.0).abs() < 1e-6);
+    assert_eq!(result.variable_names, vec!["mu", "sigma2"]);
+    assert!(result.forecast(4).iter().all(|v| *v == result.params[1]));
+    assert!(format!("{}", result).contains("Constant Variance"));
+
+    // Volatility clustering: every GARCH-type model beats the benchmark
+    let garch = GARCH::fit(&y, 1, 1).unwrap();
+    assert!(garch.log_likelihood > result.log_likelihood);
+
+    let result_t = ConstantVariance::fit_t(&y).unwrap();
+    assert_eq!(result_t.params.len(), 3);
+    assert!(result_t.params[2] > 2.0);
+}
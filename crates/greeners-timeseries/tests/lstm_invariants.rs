@@ -10,7 +10,16 @@ fn test_lstm_invariants() {
         .collect();
     let n_fc = 4;
 
-    let r = LSTM::fit(&y, Some(8), Some(5), Some(0.01), Some(50), Some(n_fc)).unwrap();
+    let r = LSTM::fit(
+        &y,
+        Some(8),
+        Some(5),
+        Some(0.01),
+        Some(50),
+        Some(n_fc),
+        Some(1),
+    )
+    .unwrap();
 
     assert_eq!(r.n_obs, n);
     assert_eq!(r.fitted.len(), n);
@@ -25,6 +34,20 @@ fn test_lstm_invariants() {
     assert_eq!(r.seq_len, 5);
     assert!(r.learning_rate > 0.0);
     assert!(r.n_epochs > 0);
+
+    // The weight initialisation is reproducible from the seed
+    let again = LSTM::fit(
+        &y,
+        Some(8),
+        Some(5),
+        Some(0.01),
+        Some(50),
+        Some(n_fc),
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(r.fitted, again.fitted);
+    assert_eq!(r.forecast, again.forecast);
 }
 
 /// Forecast length and n_hidden follow the defaults and requested values.
@@ -33,7 +56,7 @@ fn test_lstm_defaults_invariants() {
     let n = 25;
     let y: Array1<f64> = (0..n).map(|i| (i as f64) + 1.0).collect();
 
-    let r = LSTM::fit(&y, None, None, None, None, Some(3)).unwrap();
+    let r = LSTM::fit(&y, None, None, None, None, Some(3), None).unwrap();
 
     assert_eq!(r.n_obs, n);
     assert_eq!(r.forecast.len(), 3);
@@ -45,8 +68,8 @@ fn test_lstm_defaults_invariants() {
 #[test]
 fn test_lstm_input_validation() {
     let short = Array1::from_vec(vec![1.0; 10]);
-    assert!(LSTM::fit(&short, None, None, None, None, None).is_err());
+    assert!(LSTM::fit(&short, None, None, None, None, None, None).is_err());
 
     let zero_var = Array1::from_vec(vec![5.0; 30]);
-    assert!(LSTM::fit(&zero_var, None, None, None, None, None).is_err());
+    assert!(LSTM::fit(&zero_var, None, None, None, None, None, None).is_err());
 }
@@ -0,0 +1,319 @@
+use greeners_core::{GreenersError, GreenersRng};
+use greeners_timeseries::mlemodel::{
+    constrain_params, unconstrain_params, DiffuseKalmanFilter, MleConfig, MleModel, ParamTransform,
+    SimulationSmoother, StateInit, StateSpaceSystem, SystemMatrices,
+};
+use greeners_timeseries::statespace::{KalmanFilter, KalmanSmoother, StateSpaceModel};
+use ndarray::{array, Array1, Array2};
+use std::f64::consts::PI;
+
+fn normal_logpdf(x: f64, var: f64) -> f64 {
+    -0.5 * ((2.0 * PI * var).ln() + x * x / var)
+}
+
+fn local_level(obs_var: f64, level_var: f64, init: StateInit) -> StateSpaceSystem {
+    StateSpaceSystem::new(
+        SystemMatrices::new(
+            array![[1.0]],
+            array![[obs_var]],
+            array![[1.0]],
+            array![[1.0]],
+            array![[level_var]],
+        ),
+        init,
+    )
+}
+
+fn simulate_local_level(n: usize, obs_sd: f64, level_sd: f64, seed: u64) -> Vec<Array1<f64>> {
+    let mut rng = GreenersRng::new(Some(seed));
+    let mut level = 3.0;
+    (0..n)
+        .map(|_| {
+            let y = level + obs_sd * rng.normal();
+            level += level_sd * rng.normal();
+            Array1::from_elem(1, y)
+        })
+        .collect()
+}
+
+#[test]
+fn test_univariate_treatment_matches_multivariate_filter() {
+    // Two series loading on an AR(2) state, correlated measurement errors,
+    // one element missing in period 7 and both in period 12
+    let model = StateSpaceModel {
+        h: array![[1.0, 0.0], [0.5, 1.0]],
+        f: array![[0.6, 0.2], [1.0, 0.0]],
+        r: array![[1.0], [0.0]],
+        q: array![[0.8]],
+        r_obs: array![[0.5, 0.2], [0.2, 0.4]],
+        s0: array![0.3, -0.1],
+        p0: array![[2.0, 0.5], [0.5, 1.5]],
+    };
+    let mut rng = GreenersRng::new(Some(11));
+    let mut y: Vec<Array1<f64>> = (0..30)
+        .map(|_| array![rng.normal(), rng.normal()])
+        .collect();
+    y[7][1] = f64::NAN;
+    y[12].fill(f64::NAN);
+
+    let system = StateSpaceSystem::new(
+        SystemMatrices::from(&model),
+        StateInit::Known {
+            mean: model.s0.clone(),
+            cov: model.p0.clone(),
+        },
+    );
+    let uni = DiffuseKalmanFilter::filter(&system, &y).unwrap();
+    let multi = KalmanFilter::filter(&model, &y).unwrap();
+    assert_eq!(uni.n_diffuse, 0);
+    assert!((uni.log_likelihood - multi.log_likelihood).abs() < 1e-9);
+    for t in 0..30 {
+        assert!((uni.loglik_contributions[t] - multi.loglik_contributions[t]).abs() < 1e-9);
+        for i in 0..2 {
+            assert!((uni.filtered_states[t][i] - multi.filtered_states[t][i]).abs() < 1e-9);
+            for j in 0..2 {
+                assert!((uni.filtered_cov[t][[i, j]] - multi.filtered_cov[t][[i, j]]).abs() < 1e-9);
+            }
+        }
+    }
+    assert_eq!(uni.loglik_contributions[12], 0.0);
+
+    // The disturbance smoother recursions reproduce the RTS smoother
+    let smoothed = DiffuseKalmanFilter::smooth(&system, &uni).unwrap();
+    let rts = KalmanSmoother::smooth(&model, &multi).unwrap();
+    for t in 0..30 {
+        for i in 0..2 {
+            assert!((smoothed.smoothed_states[t][i] - rts.smoothed_states[t][i]).abs() < 1e-8);
+            for j in 0..2 {
+                assert!(
+                    (smoothed.smoothed_cov[t][[i, j]] - rts.smoothed_cov[t][[i, j]]).abs() < 1e-8
+                );
+            }
+        }
+    }
+
+    // A bad dimension is rejected
+    let short: Vec<Array1<f64>> = vec![array![1.0]; 3];
+    assert!(DiffuseKalmanFilter::filter(&system, &short).is_err());
+    // ...in any period, not just the first
+    let mut ragged: Vec<Array1<f64>> = vec![array![1.0, 2.0]; 5];
+    ragged[3] = array![1.0];
+    assert!(DiffuseKalmanFilter::filter(&system, &ragged).is_err());
+}
+
+#[test]
+fn test_exact_diffuse_initialization() {
+    // Pure random walk: the first observation only identifies the level
+    let y: Vec<Array1<f64>> = [1.0, 1.5, 0.7, 2.0, 2.4, 1.9]
+        .iter()
+        .map(|v| Array1::from_elem(1, *v))
+        .collect();
+    let rw = local_level(0.0, 0.5, StateInit::Diffuse);
+    let res = DiffuseKalmanFilter::filter(&rw, &y).unwrap();
+    let expected: f64 = -0.5 * (2.0 * PI).ln()
+        + (1..6)
+            .map(|t| normal_logpdf(y[t][0] - y[t - 1][0], 0.5))
+            .sum::<f64>();
+    assert_eq!(res.n_diffuse, 1);
+    assert!((res.log_likelihood - expected).abs() < 1e-10);
+
+    // Local level: the exact diffuse likelihood is the limit of a large prior
+    // variance once its log determinant is removed
+    let mut y = simulate_local_level(80, 0.7, 0.4, 3);
+    y[20][0] = f64::NAN;
+    let kappa = 1e7;
+    let exact = local_level(0.49, 0.16, StateInit::Diffuse);
+    let approx = StateSpaceModel {
+        h: array![[1.0]],
+        f: array![[1.0]],
+        r: array![[1.0]],
+        q: array![[0.16]],
+        r_obs: array![[0.49]],
+        s0: array![0.0],
+        p0: array![[kappa]],
+    };
+    let res = DiffuseKalmanFilter::filter(&exact, &y).unwrap();
+    let kf = KalmanFilter::filter(&approx, &y).unwrap();
+    assert!((res.log_likelihood - (kf.log_likelihood + 0.5 * kappa.ln())).abs() < 1e-5);
+
+    let smoothed = DiffuseKalmanFilter::smooth(&exact, &res).unwrap();
+    let rts = KalmanSmoother::smooth(&approx, &kf).unwrap();
+    for t in 0..80 {
+        assert!((smoothed.smoothed_states[t][0] - rts.smoothed_states[t][0]).abs() < 1e-5);
+        assert!((smoothed.smoothed_cov[t][[0, 0]] - rts.smoothed_cov[t][[0, 0]]).abs() < 1e-5);
+    }
+
+    // Mixed: diffuse level, known stationary cycle
+    let mixed = StateSpaceSystem::new(
+        SystemMatrices::new(
+            array![[1.0, 1.0]],
+            array![[0.2]],
+            array![[1.0, 0.0], [0.0, 0.5]],
+            Array2::eye(2),
+            array![[0.1, 0.0], [0.0, 0.3]],
+        ),
+        StateInit::Mixed {
+            mean: array![0.0, 0.0],
+            cov: array![[0.0, 0.0], [0.0, 0.4]],
+            diffuse: vec![true, false],
+        },
+    );
+    let res = DiffuseKalmanFilter::filter(&mixed, &y).unwrap();
+    assert_eq!(res.n_diffuse, 1);
+    assert!(res.log_likelihood.is_finite());
+}
+
+/// AR(1) signal observed with noise whose variance scales with known weights.
+struct WeightedAr1 {
+    y: Vec<Array1<f64>>,
+    weights: Vec<f64>,
+}
+
+impl MleModel for WeightedAr1 {
+    fn observations(&self) -> &[Array1<f64>] {
+        &self.y
+    }
+
+    fn n_params(&self) -> usize {
+        3
+    }
+
+    fn start_params(&self) -> Array1<f64> {
+        array![0.0, 1.0, 1.0]
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        vec!["ar.L1".into(), "sigma2.state".into(), "sigma2.obs".into()]
+    }
+
+    fn transforms(&self) -> Vec<ParamTransform> {
+        vec![
+            ParamTransform::Stationary(1),
+            ParamTransform::Positive,
+            ParamTransform::Positive,
+        ]
+    }
+
+    fn update(&self, params: &Array1<f64>) -> Result<StateSpaceSystem, GreenersError> {
+        let matrices = self
+            .weights
+            .iter()
+            .map(|w| {
+                SystemMatrices::new(
+                    array![[1.0]],
+                    array![[params[2] * w]],
+                    array![[params[0]]],
+                    array![[1.0]],
+                    array![[params[1]]],
+                )
+            })
+            .collect();
+        Ok(StateSpaceSystem {
+            matrices,
+            init: StateInit::Stationary,
+        })
+    }
+}
+
+#[test]
+fn test_fit_user_model_with_time_varying_matrices() {
+    let (phi, q, h): (f64, f64, f64) = (0.8, 0.5, 0.3);
+    let n = 500;
+    let mut rng = GreenersRng::new(Some(21));
+    let weights: Vec<f64> = (0..n).map(|t| 1.0 + (t % 4) as f64).collect();
+    let mut state = (q / (1.0 - phi * phi)).sqrt() * rng.normal();
+    let y = (0..n)
+        .map(|t| {
+            let obs = state + (h * weights[t]).sqrt() * rng.normal();
+            state = phi * state + q.sqrt() * rng.normal();
+            Array1::from_elem(1, obs)
+        })
+        .collect();
+    let model = WeightedAr1 { y, weights };
+
+    let fit = model.fit(&MleConfig::default()).unwrap();
+    assert!(fit.converged);
+    assert!((fit.params[0] - phi).abs() < 0.1, "phi {}", fit.params[0]);
+    assert!((fit.params[1] - q).abs() < 0.2, "q {}", fit.params[1]);
+    assert!((fit.params[2] - h).abs() < 0.15, "h {}", fit.params[2]);
+    assert!(fit.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+    assert!(fit.conf_lower[0] < fit.params[0] && fit.params[0] < fit.conf_upper[0]);
+    assert_eq!(fit.n_obs, n);
+    assert_eq!(fit.n_diffuse, 0);
+
+    // The optimum beats the truth and the starting values
+    let ll_true = model.loglike(&array![phi, q, h]).unwrap();
+    assert!(fit.log_likelihood >= ll_true - 1e-6);
+    assert!(fit.log_likelihood > model.loglike(&model.start_params()).unwrap());
+    assert!(format!("{}", fit).contains("sigma2.obs"));
+}
+
+#[test]
+fn test_simulation_smoother_moments() {
+    let mut y = simulate_local_level(40, 0.5, 0.3, 5);
+    for obs in &mut y[15..18] {
+        obs[0] = f64::NAN;
+    }
+    let system = local_level(0.25, 0.09, StateInit::Diffuse);
+    let filtered = DiffuseKalmanFilter::filter(&system, &y).unwrap();
+    let smoothed = DiffuseKalmanFilter::smooth(&system, &filtered).unwrap();
+
+    let n_draws = 2000;
+    let draws = SimulationSmoother::draw(&system, &y, n_draws, Some(9)).unwrap();
+    assert_eq!(draws.len(), n_draws);
+    assert_eq!(draws[0].dim(), (40, 1));
+    for t in [0, 16, 39] {
+        let mean = draws.iter().map(|d| d[[t, 0]]).sum::<f64>() / n_draws as f64;
+        let var = draws
+            .iter()
+            .map(|d| (d[[t, 0]] - mean).powi(2))
+            .sum::<f64>()
+            / (n_draws - 1) as f64;
+        let (m, v) = (
+            smoothed.smoothed_states[t][0],
+            smoothed.smoothed_cov[t][[0, 0]],
+        );
+        assert!(
+            (mean - m).abs() < 4.0 * (v / n_draws as f64).sqrt(),
+            "t={}",
+            t
+        );
+        assert!((var / v - 1.0).abs() < 0.15, "t={} var {} vs {}", t, var, v);
+    }
+
+    // Same seed, same draws
+    let again = SimulationSmoother::draw(&system, &y, 3, Some(9)).unwrap();
+    assert_eq!(again[2], draws[2]);
+}
+
+#[test]
+fn test_parameter_transforms_round_trip() {
+    let transforms = [
+        ParamTransform::Stationary(3),
+        ParamTransform::Invertible(2),
+        ParamTransform::Positive,
+        ParamTransform::Bounded(-2.0, 5.0),
+        ParamTransform::Identity,
+    ];
+    let u = [0.4, -1.3, 0.7, 2.0, -0.5, -3.0, 1.1, 0.25];
+    let params = constrain_params(&transforms, &u).unwrap();
+
+    // AR block: roots of 1 - phi_1 z - phi_2 z^2 - phi_3 z^3 outside the unit
+    // circle, checked through the companion matrix spectral radius bound
+    let phi = &params[..3];
+    let companion = array![[phi[0], phi[1], phi[2]], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    let mut power = Array2::<f64>::eye(3);
+    for _ in 0..200 {
+        power = power.dot(&companion);
+    }
+    assert!(power.iter().all(|v| v.abs() < 1e-3));
+    assert!(params[5] > 0.0);
+    assert!(params[6] > -2.0 && params[6] < 5.0);
+    assert_eq!(params[7], 0.25);
+
+    let back = unconstrain_params(&transforms, &params).unwrap();
+    for (a, b) in u.iter().zip(back.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+    assert!(constrain_params(&transforms, &u[..7]).is_err());
+}
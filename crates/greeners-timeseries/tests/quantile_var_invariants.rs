@@ -37,7 +37,7 @@ fn test_quantile_var_runs_and_produces_finite_output() {
 
     // n_boot = 0 keeps the test deterministic; standard errors are NaN but
     // the point estimates are still finite and useful to inspect.
-    let result = QuantileVAR::fit(&data, 1, 0.5, 0, None).unwrap();
+    let result = QuantileVAR::fit(&data, 1, 0.5, 0, None, None).unwrap();
 
     assert_eq!(result.n_vars, 2);
     assert_eq!(result.lags, 1);
@@ -50,6 +50,12 @@ fn test_quantile_var_runs_and_produces_finite_output() {
         .all(|v| v.is_finite() && *v >= 0.0 && *v <= 1.0));
     assert!(result.n_obs > 0);
     assert_eq!(result.var_names, vec!["y0", "y1"]);
+
+    // With a bootstrap the standard errors are reproducible from the seed
+    let boot = QuantileVAR::fit(&data, 1, 0.5, 20, None, Some(3)).unwrap();
+    let again = QuantileVAR::fit(&data, 1, 0.5, 20, None, Some(3)).unwrap();
+    assert!(boot.std_errors.iter().all(|s| s.is_finite()));
+    assert_eq!(boot.std_errors, again.std_errors);
 }
 
 #[test]
@@ -59,7 +65,8 @@ fn test_quantile_var_irf_shape_and_median_recovery() {
     let y0 = Array1::from(vec![0.0, 0.0]);
     let data = generate_var1(&c, &a, &y0, 120, 0.4, 8002);
 
-    let result = QuantileVAR::fit(&data, 1, 0.5, 0, Some(vec!["a".into(), "b".into()])).unwrap();
+    let result =
+        QuantileVAR::fit(&data, 1, 0.5, 0, Some(vec!["a".into(), "b".into()]), None).unwrap();
 
     let irf = QuantileVAR::irf(&result, 5);
     assert_eq!(irf.shape(), &[5, 2, 2]);
@@ -78,13 +85,13 @@ fn test_quantile_var_input_validation() {
     let data = Array2::from_shape_vec((8, 2), vec![1.0; 16]).unwrap();
 
     // Invalid tau values.
-    assert!(QuantileVAR::fit(&data, 1, 0.0, 0, None).is_err());
-    assert!(QuantileVAR::fit(&data, 1, 1.0, 0, None).is_err());
+    assert!(QuantileVAR::fit(&data, 1, 0.0, 0, None, None).is_err());
+    assert!(QuantileVAR::fit(&data, 1, 1.0, 0, None, None).is_err());
 
     // Zero lags.
-    assert!(QuantileVAR::fit(&data, 0, 0.5, 0, None).is_err());
+    assert!(QuantileVAR::fit(&data, 0, 0.5, 0, None, None).is_err());
 
     // Too few observations.
     let short = Array2::from_shape_vec((4, 2), vec![1.0; 8]).unwrap();
-    assert!(QuantileVAR::fit(&short, 1, 0.5, 0, None).is_err());
+    assert!(QuantileVAR::fit(&short, 1, 0.5, 0, None, None).is_err());
 }
@@ -0,0 +1,245 @@
+use greeners_core::GreenersRng;
+use greeners_timeseries::arima::ARIMA;
+use greeners_timeseries::sarimax::{SarimaxConfig, SarimaxInitialization, SARIMAX};
+use greeners_timeseries::statespace::KalmanFilter;
+use ndarray::{Array1, Array2};
+use std::f64::consts::PI;
+
+/// AR(1) around `mu`, started from its stationary distribution.
+fn simulate_ar1(n: usize, mu: f64, phi: f64, sigma: f64, seed: u64) -> Array1<f64> {
+    let mut rng = GreenersRng::new(Some(seed));
+    let mut y = Array1::zeros(n);
+    let mut w = sigma / (1.0 - phi * phi).sqrt() * rng.normal();
+    for t in 0..n {
+        if t > 0 {
+            w = phi * w + sigma * rng.normal();
+        }
+        y[t] = mu + w;
+    }
+    y
+}
+
+fn normal_logpdf(x: f64, var: f64) -> f64 {
+    -0.5 * ((2.0 * PI * var).ln() + x * x / var)
+}
+
+#[test]
+fn test_exact_likelihood_with_missing_values() {
+    let (mu, phi, sigma2): (f64, f64, f64) = (2.0, 0.6, 1.5);
+    let mut y = simulate_ar1(40, mu, phi, sigma2.sqrt(), 1);
+    y[10] = f64::NAN;
+    let params = Array1::from(vec![mu, phi, sigma2]);
+    let config = SarimaxConfig::default();
+    let ll = SARIMAX::log_likelihood(&y, (1, 0, 0), (0, 0, 0, 0), None, &params, &config).unwrap();
+
+    // Stationary start, and across the gap y_11 | y_9 ~ N(mu + phi^2 (y_9 - mu), sigma2 (1 + phi^2))
+    let mut expected = normal_logpdf(y[0] - mu, sigma2 / (1.0 - phi * phi));
+    for t in 1..40 {
+        if t == 10 {
+            continue;
+        }
+        if t == 11 {
+            let e = y[11] - mu - phi * phi * (y[9] - mu);
+            expected += normal_logpdf(e, sigma2 * (1.0 + phi * phi));
+        } else {
+            expected += normal_logpdf(y[t] - mu - phi * (y[t - 1] - mu), sigma2);
+        }
+    }
+    assert!((ll - expected).abs() < 1e-9, "{} vs {}", ll, expected);
+
+    // The filter skips the missing period: no update, no contribution
+    let model = SARIMAX::state_space((1, 0, 0), (0, 0, 0, 0), 0, &params, &config).unwrap();
+    let obs: Vec<Array1<f64>> = y.iter().map(|v| Array1::from_elem(1, v - mu)).collect();
+    let kf = KalmanFilter::filter(&model, &obs).unwrap();
+    assert_eq!(kf.filtered_states[10], kf.predicted_states[10]);
+    assert_eq!(kf.loglik_contributions[10], 0.0);
+    assert!(kf.innovations[10][0].is_nan());
+    assert!((kf.log_likelihood - expected).abs() < 1e-9);
+
+    assert!(SARIMAX::log_likelihood(
+        &y,
+        (1, 0, 0),
+        (0, 0, 0, 0),
+        None,
+        &params.slice(ndarray::s![..2]).to_owned(),
+        &config
+    )
+    .is_err());
+}
+
+#[test]
+fn test_differencing_in_the_state_and_diffuse_initialization() {
+    let mut rng = GreenersRng::new(Some(2));
+    let mut y = Array1::zeros(60);
+    for t in 1..60 {
+        y[t] = y[t - 1] + 0.8 * rng.normal();
+    }
+    y[0] += 5.0;
+    let params = Array1::from(vec![0.7]);
+    let stationary = SarimaxConfig::default();
+    let diffuse = SarimaxConfig {
+        initialization: SarimaxInitialization::ApproximateDiffuse,
+        ..SarimaxConfig::default()
+    };
+
+    // Random walk: the likelihood of the differences after the diffuse periods
+    let ll_s =
+        SARIMAX::log_likelihood(&y, (0, 1, 0), (0, 0, 0, 0), None, &params, &stationary).unwrap();
+    let ll_d =
+        SARIMAX::log_likelihood(&y, (0, 1, 0), (0, 0, 0, 0), None, &params, &diffuse).unwrap();
+    let exact = |from: usize| -> f64 {
+        (from..60)
+            .map(|t| normal_logpdf(y[t] - y[t - 1], 0.7))
+            .sum()
+    };
+    assert!((ll_s - exact(1)).abs() < 1e-6, "{} vs {}", ll_s, exact(1));
+    assert!((ll_d - exact(2)).abs() < 1e-6, "{} vs {}", ll_d, exact(2));
+
+    // Differencing polynomial (1 - L)(1 - L^4) sits in the observation row
+    let model = SARIMAX::state_space(
+        (0, 1, 0),
+        (0, 1, 0, 4),
+        0,
+        &Array1::from(vec![1.0]),
+        &stationary,
+    )
+    .unwrap();
+    assert_eq!(model.f.nrows(), 6);
+    let h: Vec<f64> = model.h.row(0).to_vec();
+    assert_eq!(h, vec![1.0, 1.0, 0.0, 0.0, 1.0, -1.0]);
+
+    // Explosive AR coefficients have no stationary prior
+    let explosive = Array1::from(vec![1.2, 1.0]);
+    assert!(SARIMAX::state_space(
+        (1, 0, 0),
+        (0, 0, 0, 0),
+        0,
+        &explosive,
+        &SarimaxConfig {
+            include_mean: false,
+            ..SarimaxConfig::default()
+        }
+    )
+    .is_err());
+}
+
+#[test]
+fn test_fit_ar1_with_observed_information_and_forecasts() {
+    let n = 400;
+    let y = simulate_ar1(n, 1.0, 0.6, 1.0, 3);
+    let fit = SARIMAX::fit(&y, (1, 0, 0), (0, 0, 0, 0), None, &SarimaxConfig::default()).unwrap();
+    assert_eq!(fit.param_names, vec!["intercept", "ar.L1", "sigma2"]);
+    assert!((fit.ar_params[0] - 0.6).abs() < 0.1);
+    assert!((fit.intercept - 1.0).abs() < 0.3);
+    assert!((fit.sigma2 - 1.0).abs() < 0.2);
+    assert!(fit.converged && fit.is_stationary());
+    assert_eq!(fit.n_obs, n);
+
+    // Observed information matches the asymptotic variances of an AR(1)
+    let phi = fit.ar_params[0];
+    let se_phi = ((1.0 - phi * phi) / n as f64).sqrt();
+    let se_sigma2 = fit.sigma2 * (2.0 / n as f64).sqrt();
+    assert!((fit.std_errors[1] / se_phi - 1.0).abs() < 0.2);
+    assert!((fit.std_errors[2] / se_sigma2 - 1.0).abs() < 0.2);
+
+    // The exact MLE is at least as good as the mean-profiled ARIMA MLE
+    let mle = ARIMA::fit_mle(&y, (1, 0, 0)).unwrap();
+    assert!(fit.log_likelihood >= mle.log_likelihood - 1e-4);
+    assert!((fit.ar_params[0] - mle.ar_params[0]).abs() < 0.02);
+
+    // One-step predictions from the filter
+    for t in 1..n {
+        let pred = fit.intercept + phi * (y[t - 1] - fit.intercept);
+        assert!((fit.fitted_values[t] - pred).abs() < 1e-8);
+    }
+    assert!((fit.prediction_variance[5] - fit.sigma2).abs() < 1e-8);
+
+    // Forecasts decay to the mean with growing variance
+    let fc = fit.forecast(5, None, 0.05).unwrap();
+    let mut var = 0.0;
+    for h in 0..5 {
+        let mean = fit.intercept + phi.powi(h as i32 + 1) * (y[n - 1] - fit.intercept);
+        var += fit.sigma2 * phi.powi(2 * h as i32);
+        assert!((fc.mean[h] - mean).abs() < 1e-8);
+        assert!((fc.std_errors[h] - var.sqrt()).abs() < 1e-8);
+        assert!(fc.lower[h] < fc.mean[h] && fc.mean[h] < fc.upper[h]);
+    }
+    assert!(fit.forecast(5, None, 0.0).is_err());
+    assert!(format!("{}", fit).contains("via exact MLE"));
+}
+
+#[test]
+fn test_fit_arma_with_gaps() {
+    // ARMA(1,1) with every seventh observation missing
+    let mut rng = GreenersRng::new(Some(4));
+    let n = 300;
+    let mut y = Array1::zeros(n);
+    let mut e_prev = 0.0;
+    let mut w = 0.0;
+    for t in 0..n + 50 {
+        let e = rng.normal();
+        w = 0.5 * w + e + 0.4 * e_prev;
+        e_prev = e;
+        if t >= 50 {
+            y[t - 50] = w;
+        }
+    }
+    for t in (3..n).step_by(7) {
+        y[t] = f64::NAN;
+    }
+    let fit = SARIMAX::fit(&y, (1, 0, 1), (0, 0, 0, 0), None, &SarimaxConfig::default()).unwrap();
+    assert_eq!(fit.n_missing, 43);
+    assert_eq!(fit.n_obs, n - 43);
+    assert!((fit.ar_params[0] - 0.5).abs() < 0.15);
+    assert!((fit.ma_params[0] - 0.4).abs() < 0.15);
+    assert!(fit.is_invertible());
+    assert!(fit.std_errors.iter().all(|s| s.is_finite() && *s > 0.0));
+
+    // Predictions exist at the gaps; residuals do not
+    assert!(fit.fitted_values.iter().all(|v| v.is_finite()));
+    assert!(fit.residuals[3].is_nan() && fit.residuals[4].is_finite());
+    assert!(fit.prediction_variance[4] > fit.prediction_variance[5]);
+
+    // Hannan-Rissanen still rejects the gaps
+    assert!(ARIMA::fit_sarimax(&y, (1, 0, 1), (0, 0, 0, 1), None).is_err());
+}
+
+#[test]
+fn test_seasonal_regression_errors() {
+    // y = 0.5 x + w, (1 - L)(1 - L^4) w_t = (1 - 0.4 L)(1 - 0.5 L^4) e_t
+    let mut rng = GreenersRng::new(Some(5));
+    let n = 120;
+    let e: Vec<f64> = (0..n).map(|_| 0.5 * rng.normal()).collect();
+    let x = Array2::from_shape_fn((n, 1), |(t, _)| (t as f64 / 5.0).sin() * 3.0);
+    let mut dw = vec![0.0; n];
+    let mut w = vec![0.0; n];
+    for t in 0..n {
+        let lag = |k: usize| if t >= k { e[t - k] } else { 0.0 };
+        dw[t] = e[t] - 0.4 * lag(1) - 0.5 * lag(4) + 0.2 * lag(5);
+        w[t] = dw[t] + if t >= 1 { w[t - 1] } else { 0.0 } + if t >= 4 { w[t - 4] } else { 0.0 }
+            - if t >= 5 { w[t - 5] } else { 0.0 };
+    }
+    let y = Array1::from_shape_fn(n, |t| 10.0 + 0.5 * x[[t, 0]] + w[t]);
+
+    for initialization in [
+        SarimaxInitialization::Stationary,
+        SarimaxInitialization::ApproximateDiffuse,
+    ] {
+        let config = SarimaxConfig {
+            initialization,
+            ..SarimaxConfig::default()
+        };
+        let fit = SARIMAX::fit(&y, (0, 1, 1), (0, 1, 1, 4), Some(&x), &config).unwrap();
+        // The mean is differenced away
+        assert_eq!(fit.param_names, vec!["x1", "ma.L1", "ma.S.L4", "sigma2"]);
+        let beta = fit.exog_params.as_ref().unwrap()[0];
+        assert!((beta - 0.5).abs() < 0.1, "beta {}", beta);
+        assert!(fit.ma_params[0] < 0.0 && fit.seasonal_ma_params[0] < 0.0);
+        assert_eq!(fit.n_obs, n - fit.n_diffuse);
+
+        let future = Array2::from_shape_fn((8, 1), |(h, _)| ((n + h) as f64 / 5.0).sin() * 3.0);
+        let fc = fit.forecast(8, Some(&future), 0.1).unwrap();
+        assert!(fc.std_errors[7] > fc.std_errors[0]);
+        assert!(fit.forecast(8, None, 0.1).is_err());
+    }
+}
@@ -46,7 +46,7 @@ fn assert_spectral_result_finite(result: &SpectralResult, n: usize, k: usize) {
 fn test_spectral_cluster_recovery() {
     let x = make_spectral_data(9433);
     let n = x.nrows();
-    let result = SpectralClustering::fit(&x, 2, None, Some(100)).unwrap();
+    let result = SpectralClustering::fit(&x, 2, None, Some(100), None).unwrap();
     assert_spectral_result_finite(&result, n, 2);
     let mut sizes = vec![0; 2];
     for &l in &result.labels {
@@ -61,7 +61,7 @@ fn test_spectral_cluster_recovery() {
 fn test_spectral_n_components() {
     let x = make_spectral_data(9434);
     let n = x.nrows();
-    let result = SpectralClustering::fit(&x, 2, Some(1.0), Some(50)).unwrap();
+    let result = SpectralClustering::fit(&x, 2, Some(1.0), Some(50), None).unwrap();
     assert_spectral_result_finite(&result, n, 2);
 }
 
@@ -69,9 +69,9 @@ fn test_spectral_n_components() {
 #[test]
 fn test_spectral_input_validation() {
     let x = make_spectral_data(9435);
-    assert!(SpectralClustering::fit(&x, 1, None, None).is_err());
-    assert!(SpectralClustering::fit(&x, 1000, None, None).is_err());
+    assert!(SpectralClustering::fit(&x, 1, None, None, None).is_err());
+    assert!(SpectralClustering::fit(&x, 1000, None, None, None).is_err());
 
     let x_short = Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap();
-    assert!(SpectralClustering::fit(&x_short, 2, None, None).is_err());
+    assert!(SpectralClustering::fit(&x_short, 2, None, None, None).is_err());
 }
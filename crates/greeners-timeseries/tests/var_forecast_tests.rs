@@ -0,0 +1,292 @@
+use greeners_core::var_dynamics::{IrfKind, VarBootstrapConfig, VarBootstrapScheme, VarDynamics};
+use greeners_core::GreenersRng;
+use greeners_timeseries::svar::{SVarIdentification, SVAR};
+use greeners_timeseries::var::VAR;
+use greeners_timeseries::vecm::VECM;
+use ndarray::{s, Array1, Array2};
+
+/// VAR(1) with correlated Gaussian noise: y_t = c + A y_{t-1} + L e_t.
+fn simulate_var1(t: usize, seed: u64) -> Array2<f64> {
+    let c = Array1::from(vec![1.0, -0.5]);
+    let a = Array2::from_shape_vec((2, 2), vec![0.5, 0.1, 0.2, 0.3]).unwrap();
+    let chol = Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.6, 0.8]).unwrap();
+    let mut rng = GreenersRng::new(Some(seed));
+    let mut data = Array2::<f64>::zeros((t, 2));
+    for i in 1..t {
+        let e = Array1::from(vec![rng.normal(), rng.normal()]);
+        let y = &c + &a.dot(&data.row(i - 1)) + chol.dot(&e);
+        data.row_mut(i).assign(&y);
+    }
+    data
+}
+
+/// Two I(1) series sharing a stochastic trend, so y1 - y2 is stationary.
+fn simulate_cointegrated(t: usize, seed: u64) -> Array2<f64> {
+    let mut rng = GreenersRng::new(Some(seed));
+    let mut data = Array2::<f64>::zeros((t, 2));
+    let mut trend = 0.0;
+    let mut gap = 0.0;
+    for i in 0..t {
+        trend += 0.1 + rng.normal();
+        gap = 0.5 * gap + 0.5 * rng.normal();
+        data[[i, 0]] = trend + gap;
+        data[[i, 1]] = trend - gap + 0.3 * rng.normal();
+    }
+    data
+}
+
+#[test]
+fn test_forecast_matches_recursion_and_mse() {
+    let data = simulate_var1(300, 1);
+    let var = VAR::fit(&data, 2, Some(vec!["a".into(), "b".into()])).unwrap();
+    let fc = var.forecast(3, 0.05).unwrap();
+    assert_eq!(fc.mean.dim(), (3, 2));
+
+    // Point forecasts by direct recursion on the estimated coefficients
+    let c = var.params.row(0).to_owned();
+    let a1 = var.params.slice(s![1..3, ..]).t().to_owned();
+    let a2 = var.params.slice(s![3..5, ..]).t().to_owned();
+    let mut prev2 = data.row(298).to_owned();
+    let mut prev1 = data.row(299).to_owned();
+    for h in 0..3 {
+        let y = &c + &a1.dot(&prev1) + &a2.dot(&prev2);
+        for i in 0..2 {
+            assert!((fc.mean[[h, i]] - y[i]).abs() < 1e-10);
+        }
+        prev2 = prev1;
+        prev1 = y;
+    }
+
+    // One step ahead: MSE = Sigma_u; two steps: Sigma_u + A1 Sigma_u A1'
+    let sigma = &var.sigma_u;
+    let mse2 = sigma + &a1.dot(sigma).dot(&a1.t());
+    for i in 0..2 {
+        assert!((fc.std_errors[[0, i]] - sigma[[i, i]].sqrt()).abs() < 1e-10);
+        assert!((fc.std_errors[[1, i]] - mse2[[i, i]].sqrt()).abs() < 1e-10);
+        let half = 1.959964 * fc.std_errors[[0, i]];
+        assert!((fc.upper[[0, i]] - fc.mean[[0, i]] - half).abs() < 1e-5);
+    }
+    // Uncertainty grows with the horizon
+    assert!(fc.std_errors[[2, 0]] > fc.std_errors[[0, 0]]);
+    assert!(format!("{}", fc).contains("VAR Forecast"));
+
+    assert!(var.forecast(3, 1.5).is_err());
+}
+
+#[test]
+fn test_bootstrap_forecast_fan_chart() {
+    let data = simulate_var1(200, 2);
+    let var = VAR::fit(&data, 1, None).unwrap();
+    let config = VarBootstrapConfig {
+        n_bootstrap: 199,
+        seed: Some(11),
+        ..VarBootstrapConfig::default()
+    };
+    let fc = var.bootstrap_forecast(4, &config).unwrap();
+    let paths = fc.paths.as_ref().unwrap();
+    assert_eq!(paths.dim(), (199, 4, 2));
+    assert_eq!(fc.bootstrap, Some(VarBootstrapScheme::Residual));
+
+    let analytic = var.forecast(4, 0.05).unwrap();
+    assert_eq!(fc.mean, analytic.mean);
+    for h in 0..4 {
+        for i in 0..2 {
+            assert!(fc.lower[[h, i]] < fc.mean[[h, i]] && fc.mean[[h, i]] < fc.upper[[h, i]]);
+            // Bootstrap and MSE standard errors agree roughly in a long sample
+            let ratio = fc.std_errors[[h, i]] / analytic.std_errors[[h, i]];
+            assert!(ratio > 0.8 && ratio < 1.25, "ratio {}", ratio);
+        }
+    }
+
+    // Nested bands of a fan chart
+    let (lo50, hi50) = fc.interval(0.5).unwrap();
+    let (lo90, hi90) = fc.interval(0.1).unwrap();
+    assert!(lo90.iter().zip(lo50.iter()).all(|(a, b)| a <= b));
+    assert!(hi90.iter().zip(hi50.iter()).all(|(a, b)| a >= b));
+
+    // Same seed, same draws
+    let again = var.bootstrap_forecast(4, &config).unwrap();
+    assert_eq!(again.lower, fc.lower);
+
+    let wild = VarBootstrapConfig {
+        scheme: VarBootstrapScheme::Wild,
+        ..config
+    };
+    let fw = var.bootstrap_forecast(4, &wild).unwrap();
+    assert!(fw.upper.iter().zip(fw.lower.iter()).all(|(u, l)| u > l));
+}
+
+#[test]
+fn test_conditional_forecast_is_gaussian_conditional_mean() {
+    let data = simulate_var1(250, 3);
+    let var = VAR::fit(&data, 1, None).unwrap();
+    let uncond = var.forecast(3, 0.05).unwrap();
+    let sigma = &var.sigma_u;
+
+    // Fix variable 0 one step ahead: variable 1 moves by the regression slope
+    let mut cond = Array2::from_elem((3, 2), f64::NAN);
+    cond[[0, 0]] = uncond.mean[[0, 0]] + 1.0;
+    let fc = var.conditional_forecast(&cond, 0.05).unwrap();
+    assert!((fc.mean[[0, 0]] - cond[[0, 0]]).abs() < 1e-10);
+    assert_eq!(fc.std_errors[[0, 0]], 0.0);
+    let slope = sigma[[1, 0]] / sigma[[0, 0]];
+    assert!((fc.mean[[0, 1]] - uncond.mean[[0, 1]] - slope).abs() < 1e-10);
+    let cond_var = sigma[[1, 1]] - sigma[[1, 0]].powi(2) / sigma[[0, 0]];
+    assert!((fc.std_errors[[0, 1]] - cond_var.sqrt()).abs() < 1e-10);
+    // Later horizons are free and inherit the shock
+    assert!(fc.std_errors[[1, 0]] > 0.0);
+    assert!((fc.mean[[1, 0]] - uncond.mean[[1, 0]]).abs() > 1e-3);
+
+    // The conditional mean does not depend on the identification
+    let svar = SVAR::fit(
+        &data,
+        1,
+        SVarIdentification::LongRun(Array2::from_elem((2, 2), f64::NAN)),
+    )
+    .unwrap();
+    let fs = svar.conditional_forecast(&cond, 0.05).unwrap();
+    for (a, b) in fs.mean.iter().zip(fc.mean.iter()) {
+        assert!((a - b).abs() < 1e-8);
+    }
+
+    // Conditioning on the unconditional path changes nothing
+    let mut on_path = Array2::from_elem((3, 2), f64::NAN);
+    on_path.column_mut(1).assign(&uncond.mean.column(1));
+    let same = var.conditional_forecast(&on_path, 0.05).unwrap();
+    for (a, b) in same.mean.iter().zip(uncond.mean.iter()) {
+        assert!((a - b).abs() < 1e-10);
+    }
+    assert!(same.shocks.unwrap().iter().all(|e| e.abs() < 1e-10));
+    assert!(format!("{}", fc).contains("imposed path"));
+
+    assert!(var
+        .conditional_forecast(&Array2::zeros((3, 3)), 0.05)
+        .is_err());
+}
+
+#[test]
+fn test_irf_bands_cover_point_estimates() {
+    let data = simulate_var1(200, 4);
+    let var = VAR::fit(&data, 1, None).unwrap();
+
+    // Orthogonalized responses are the Cholesky IRFs; the first generalized
+    // shock coincides with the first orthogonalized one
+    let orth = var.orthogonalized_irf(6).unwrap();
+    let legacy = var.irf(6).unwrap();
+    let gen = var.generalized_irf(6).unwrap();
+    for h in 0..6 {
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((orth[[h, i, j]] - legacy[[h, i, j]]).abs() < 1e-12);
+            }
+            assert!((gen[[h, i, 0]] - orth[[h, i, 0]]).abs() < 1e-10);
+        }
+    }
+    // Impact of a generalized shock to variable 1 on itself is its std dev
+    assert!((gen[[0, 1, 1]] - var.sigma_u[[1, 1]].sqrt()).abs() < 1e-12);
+
+    for scheme in [VarBootstrapScheme::Residual, VarBootstrapScheme::Wild] {
+        for kind in [IrfKind::Orthogonalized, IrfKind::Generalized] {
+            let config = VarBootstrapConfig {
+                scheme,
+                n_bootstrap: 199,
+                seed: Some(5),
+                ..VarBootstrapConfig::default()
+            };
+            let bands = var.irf_bands(6, kind, &config).unwrap();
+            assert_eq!(bands.n_bootstrap, 199);
+            assert_eq!(bands.lower.dim(), (6, 2, 2));
+            let mut inside = 0;
+            for ((l, u), p) in bands
+                .lower
+                .iter()
+                .zip(bands.upper.iter())
+                .zip(bands.irf.iter())
+            {
+                assert!(l <= u);
+                if l <= p && p <= u {
+                    inside += 1;
+                }
+            }
+            assert!(inside >= 22, "{} of 24 inside", inside);
+            // Responses die out, and so does their uncertainty
+            assert!(bands.std_errors[[5, 0, 0]] < bands.std_errors[[1, 0, 0]] + 0.05);
+        }
+    }
+}
+
+#[test]
+fn test_historical_decomposition_adds_up() {
+    let data = simulate_var1(120, 6);
+    let var = VAR::fit(&data, 2, None).unwrap();
+    let hd = var.historical_decomposition().unwrap();
+    assert_eq!(hd.contributions.dim(), (118, 2, 2));
+    let total = hd.total();
+    for t in 0..118 {
+        for i in 0..2 {
+            assert!((total[[t, i]] - data[[t + 2, i]]).abs() < 1e-8);
+        }
+    }
+    // Recursive identification: the second shock has no impact on variable 0
+    let first = hd.contributions.slice(s![0, 0, 1]).into_scalar();
+    assert!(first.abs() < 1e-12);
+    // Shocks are standardized
+    let sd = hd.shocks.std_axis(ndarray::Axis(0), 0.0);
+    assert!(sd.iter().all(|s| (s - 1.0).abs() < 0.1));
+
+    // Same identity for a structural model with its own impact matrix
+    let a_mask = Array2::from_shape_vec((2, 2), vec![1.0, 0.0, f64::NAN, 1.0]).unwrap();
+    let b_mask = Array2::from_shape_vec((2, 2), vec![f64::NAN, 0.0, 0.0, f64::NAN]).unwrap();
+    let svar = SVAR::fit(&data, 2, SVarIdentification::ShortRun(a_mask, b_mask)).unwrap();
+    let total = svar.historical_decomposition().unwrap().total();
+    for t in 0..118 {
+        assert!((total[[t, 1]] - data[[t + 2, 1]]).abs() < 1e-8);
+    }
+
+    // Results without a stored sample cannot be decomposed
+    let mut bare = VAR::fit(&data, 2, None).unwrap();
+    bare.data = Array2::zeros((0, 0));
+    assert!(bare.historical_decomposition().is_err());
+    assert!(bare.forecast(2, 0.05).is_err());
+}
+
+#[test]
+fn test_vecm_dynamics_through_levels_var() {
+    let data = simulate_cointegrated(250, 7);
+    let vecm = VECM::fit(&data, 2, 1).unwrap();
+    let fc = vecm.forecast(5, 0.05).unwrap();
+
+    // One step ahead from the VECM equation itself
+    let t = data.nrows();
+    let y_last = data.row(t - 1).to_owned();
+    let dy_last = &data.row(t - 1) - &data.row(t - 2);
+    let pi = vecm.alpha.dot(&vecm.beta.t());
+    let gamma1 = vecm.gamma.slice(s![.., 1..3]).to_owned();
+    let dy = &vecm.gamma.column(0) + &pi.dot(&y_last) + gamma1.dot(&dy_last);
+    let y_next = &y_last + &dy;
+    for i in 0..2 {
+        assert!((fc.mean[[0, i]] - y_next[i]).abs() < 1e-8);
+    }
+    // Integrated series: forecast uncertainty keeps growing
+    assert!(fc.std_errors[[4, 0]] > 1.5 * fc.std_errors[[0, 0]]);
+
+    let total = vecm.historical_decomposition().unwrap().total();
+    for r in 0..total.nrows() {
+        for i in 0..2 {
+            assert!((total[[r, i]] - data[[r + 2, i]]).abs() < 1e-6);
+        }
+    }
+
+    let config = VarBootstrapConfig {
+        n_bootstrap: 99,
+        seed: Some(3),
+        ..VarBootstrapConfig::default()
+    };
+    let bands = vecm.irf_bands(8, IrfKind::Orthogonalized, &config).unwrap();
+    assert!(bands.n_bootstrap > 50);
+    assert!(bands
+        .lower
+        .iter()
+        .zip(bands.upper.iter())
+        .all(|(l, u)| l <= u));
+}
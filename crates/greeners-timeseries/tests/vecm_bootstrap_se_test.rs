@@ -23,7 +23,7 @@ fn test_vecm_bootstrap_standard_errors() {
 
     // VECM(1) in differences => 2 lags in levels
     let model = VECM::fit(&data, 2, 1).unwrap();
-    let inferred = model.with_inference(100).unwrap();
+    let inferred = model.with_inference(100, None).unwrap();
 
     println!("{}", inferred);
 
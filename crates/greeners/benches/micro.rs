@@ -182,7 +182,8 @@ fn bench_quantile(c: &mut Criterion) {
         let formula = Formula::parse("y ~ x1 + x2 + x3").unwrap();
         group.bench_with_input(BenchmarkId::new("fit_boot0", n), &n, |b, _| {
             b.iter(|| {
-                QuantileReg::from_formula(black_box(&formula), black_box(&df), 0.5, 0).unwrap()
+                QuantileReg::from_formula(black_box(&formula), black_box(&df), 0.5, 0, None)
+                    .unwrap()
             })
         });
     }
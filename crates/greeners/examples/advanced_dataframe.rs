@@ -98,7 +98,7 @@ fn main() {
     // 8. SAMPLE - Random sampling
     println!("=== 8. SAMPLE - Random sampling ===");
     println!("Random sample of 5 rows:");
-    let sample = df.sample(5).unwrap();
+    let sample = df.sample(5, Some(42)).unwrap();
     println!("{}\n", sample);
 
     // Combining operations
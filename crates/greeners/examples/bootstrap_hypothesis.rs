@@ -79,7 +79,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (y, x) = df.to_design_matrix(&formula)?;
 
     println!("\n⏳ Running 1000 bootstrap replications...");
-    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 1000)?;
+    let boot_coefs = Bootstrap::pairs_bootstrap(&y, &x, 1000, None)?;
 
     let boot_se = Bootstrap::bootstrap_se(&boot_coefs);
 
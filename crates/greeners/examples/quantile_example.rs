@@ -37,17 +37,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 1. Quantil 0.10 (Os "Poupadores" / Limite Inferior)
     // A inclinação deve ser MENOR que 2.0, pois o erro negativo cresce com X
-    let q10 = QuantileReg::fit(&y, &x, 0.10, 100)?; // 100 bootstraps para rapidez
+    let q10 = QuantileReg::fit(&y, &x, 0.10, 100, None)?; // 100 bootstraps para rapidez
     println!("{}", q10);
 
     // 2. Quantil 0.50 (Mediana)
     // A inclinação deve ser próxima de 2.0 (igual ao OLS)
-    let q50 = QuantileReg::fit(&y, &x, 0.50, 100)?;
+    let q50 = QuantileReg::fit(&y, &x, 0.50, 100, None)?;
     println!("{}", q50);
 
     // 3. Quantil 0.90 (Os "Gastadores" / Limite Superior)
     // A inclinação deve ser MAIOR que 2.0, pois o erro positivo cresce com X
-    let q90 = QuantileReg::fit(&y, &x, 0.90, 100)?;
+    let q90 = QuantileReg::fit(&y, &x, 0.90, 100, None)?;
     println!("{}", q90);
 
     println!("Análise:");
@@ -25,6 +25,7 @@ pub use greeners_causal::rd;
 pub use greeners_causal::synth;
 pub use greeners_causal::synth_did;
 pub use greeners_causal::tmle;
+pub use greeners_causal::twfe_diagnostics;
 pub use greeners_core::biplot;
 pub use greeners_core::bootstrap;
 pub use greeners_core::bspline;
@@ -33,24 +34,31 @@ pub use greeners_core::copula;
 pub use greeners_core::dataframe;
 pub use greeners_core::datasets;
 pub use greeners_core::descrstatsw;
+pub use greeners_core::design;
 pub use greeners_core::distributions;
 pub use greeners_core::error;
 pub use greeners_core::formula;
 pub use greeners_core::functional_coef;
 pub use greeners_core::gmm_clustering;
+pub use greeners_core::groupby;
 pub use greeners_core::isotonic;
 pub use greeners_core::linalg;
 pub use greeners_core::margins;
+pub use greeners_core::model;
 pub use greeners_core::moment_helpers;
 pub use greeners_core::multipletests;
 pub use greeners_core::multivariate;
 pub use greeners_core::nonparametric;
+pub use greeners_core::persist;
 pub use greeners_core::predicate;
 pub use greeners_core::proportion;
+pub use greeners_core::rng;
+pub use greeners_core::sandwich;
 pub use greeners_core::stats;
 pub use greeners_core::summary_col;
 pub use greeners_core::transforms;
 pub use greeners_core::types;
+pub use greeners_core::var_dynamics;
 pub use greeners_diagnostics::binary_diagnostics;
 pub use greeners_diagnostics::diagnostics;
 pub use greeners_diagnostics::fama_macbeth;
@@ -71,6 +79,7 @@ pub use greeners_glm::zero_inflated;
 pub use greeners_imputation::imputation;
 pub use greeners_imputation::mice;
 pub use greeners_ml::bart;
+pub use greeners_ml::classification;
 pub use greeners_ml::dbscan;
 pub use greeners_ml::gp;
 pub use greeners_ml::gradient_boosting;
@@ -93,6 +102,7 @@ pub use greeners_ols::glsar;
 pub use greeners_ols::gmm;
 pub use greeners_ols::heckman;
 pub use greeners_ols::iv;
+pub use greeners_ols::lasso_inference;
 pub use greeners_ols::nls;
 pub use greeners_ols::ols;
 pub use greeners_ols::quantile;
@@ -106,12 +116,14 @@ pub use greeners_ols::wls;
 pub use greeners_panel::dynamic_panel;
 pub use greeners_panel::fa_panel;
 pub use greeners_panel::hausman;
+pub use greeners_panel::hdfe;
 pub use greeners_panel::panel;
 pub use greeners_panel::panel_heckman;
 pub use greeners_panel::panel_quantile;
 pub use greeners_panel::panel_robust;
 pub use greeners_panel::panel_tobit;
 pub use greeners_panel::panel_var;
+pub use greeners_panel::ppml_hdfe;
 pub use greeners_panel::pstr;
 pub use greeners_panel::threshold;
 pub use greeners_spatial::spatial;
@@ -120,6 +132,7 @@ pub use greeners_spatial::spatial_durbin_error;
 pub use greeners_spatial::spatial_panel;
 pub use greeners_survival::survival;
 pub use greeners_timeseries::arima;
+pub use greeners_timeseries::auto_arima;
 pub use greeners_timeseries::autoreg;
 pub use greeners_timeseries::dcc_garch;
 pub use greeners_timeseries::decomposition;
@@ -133,10 +146,12 @@ pub use greeners_timeseries::lstm;
 pub use greeners_timeseries::markov;
 pub use greeners_timeseries::markov_autoreg;
 pub use greeners_timeseries::midas;
+pub use greeners_timeseries::mlemodel;
 pub use greeners_timeseries::ms_var;
 pub use greeners_timeseries::mstl;
 pub use greeners_timeseries::nardl;
 pub use greeners_timeseries::quantile_var;
+pub use greeners_timeseries::sarimax;
 pub use greeners_timeseries::setar;
 pub use greeners_timeseries::spectral;
 pub use greeners_timeseries::statespace;
@@ -166,13 +181,18 @@ pub use greeners_core::CategoricalColumn;
 pub use greeners_core::Column;
 pub use greeners_core::ColumnType;
 pub use greeners_core::CovarianceType;
+pub use greeners_core::CrossValidation;
 pub use greeners_core::DataFrame;
 pub use greeners_core::DataType;
 pub use greeners_core::Datasets;
 pub use greeners_core::DescrStatsW;
+pub use greeners_core::DesignInfo;
 pub use greeners_core::FactorAnalysis;
+pub use greeners_core::FittedModel;
 pub use greeners_core::Formula;
 pub use greeners_core::GreenersError;
+pub use greeners_core::GreenersRng;
+pub use greeners_core::GroupBy;
 pub use greeners_core::HypothesisTest;
 pub use greeners_core::InferenceType;
 pub use greeners_core::KDEMultivariate;
@@ -180,8 +200,12 @@ pub use greeners_core::KDEUnivariate;
 pub use greeners_core::Kernel;
 pub use greeners_core::KernelReg;
 pub use greeners_core::Lowess;
+pub use greeners_core::MissingPolicy;
+pub use greeners_core::Model;
 pub use greeners_core::ModelSummary;
+pub use greeners_core::Persist;
 pub use greeners_core::Rotation;
+pub use greeners_core::RowMap;
 pub use greeners_core::Stats;
 pub use greeners_core::SummaryCol;
 pub use greeners_core::TypeInferenceConfig;
@@ -221,6 +245,8 @@ pub use greeners_glm::ZINB;
 pub use greeners_glm::ZIP;
 pub use greeners_imputation::BayesGaussMI;
 pub use greeners_imputation::MICE;
+pub use greeners_ml::GradientBoostingClassifier;
+pub use greeners_ml::RandomForestClassifier;
 pub use greeners_ols::Equation;
 pub use greeners_ols::OlsResult;
 pub use greeners_ols::QuantileReg;
@@ -260,6 +286,7 @@ pub use greeners_timeseries::StateSpaceModel;
 pub use greeners_timeseries::TimeSeries;
 pub use greeners_timeseries::ARDL;
 pub use greeners_timeseries::ARIMA;
+pub use greeners_timeseries::SARIMAX;
 pub use greeners_timeseries::SVAR;
 pub use greeners_timeseries::VAR;
 pub use greeners_timeseries::VARMA;
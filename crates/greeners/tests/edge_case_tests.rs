@@ -329,7 +329,7 @@ fn test_quantile_n_less_than_k() {
     let y = Array1::from(vec![1.0, 2.0]);
     let x = Array2::from_shape_vec((2, 3), vec![1.0, 1.0, 1.0, 1.0, 2.0, 3.0]).unwrap();
 
-    let result = catch_panic(|| QuantileReg::fit(&y, &x, 0.5, 100));
+    let result = catch_panic(|| QuantileReg::fit(&y, &x, 0.5, 100, None));
     match result {
         Ok(Err(_)) => {}
         Ok(Ok(_)) => panic!("Expected error for n < k in quantile"),
@@ -346,7 +346,7 @@ fn test_quantile_nboot_zero_allowed() {
     )
     .unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.5, 0).expect("n_boot=0 should be allowed");
+    let result = QuantileReg::fit(&y, &x, 0.5, 0, None).expect("n_boot=0 should be allowed");
     assert!(result.std_errors.iter().all(|v| v.is_nan()));
     assert!(result.t_values.iter().all(|v| v.is_nan()));
     assert!(result.p_values.iter().all(|v| v.is_nan()));
@@ -356,21 +356,21 @@ fn test_quantile_nboot_zero_allowed() {
 fn test_quantile_invalid_tau_zero() {
     let y = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
     let x = Array2::from_shape_vec((5, 2), vec![1.0; 10]).unwrap();
-    assert!(QuantileReg::fit(&y, &x, 0.0, 100).is_err());
+    assert!(QuantileReg::fit(&y, &x, 0.0, 100, None).is_err());
 }
 
 #[test]
 fn test_quantile_invalid_tau_one() {
     let y = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
     let x = Array2::from_shape_vec((5, 2), vec![1.0; 10]).unwrap();
-    assert!(QuantileReg::fit(&y, &x, 1.0, 100).is_err());
+    assert!(QuantileReg::fit(&y, &x, 1.0, 100, None).is_err());
 }
 
 #[test]
 fn test_quantile_invalid_tau_negative() {
     let y = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
     let x = Array2::from_shape_vec((5, 2), vec![1.0; 10]).unwrap();
-    assert!(QuantileReg::fit(&y, &x, -0.5, 100).is_err());
+    assert!(QuantileReg::fit(&y, &x, -0.5, 100, None).is_err());
 }
 
 // ============================================================================
@@ -319,7 +319,7 @@ fn test_mice() {
     data.insert("x1".to_string(), x1);
     data.insert("x2".to_string(), x2);
 
-    let result = MICE::impute(&data, 3, 5).unwrap();
+    let result = MICE::impute(&data, 3, 5, None).unwrap();
     assert_eq!(result.n_imputations, 3);
     assert_eq!(result.datasets.len(), 3);
     // Check no NaN in imputed datasets
@@ -345,7 +345,7 @@ fn test_bayes_gauss_mi() {
     data.insert("x1".to_string(), x1);
     data.insert("x2".to_string(), x2);
 
-    let result = BayesGaussMI::impute(&data, 3).unwrap();
+    let result = BayesGaussMI::impute(&data, 3, None).unwrap();
     assert_eq!(result.n_imputations, 3);
     for ds in &result.datasets {
         let x2_imp = &ds["x2"];
@@ -429,3 +429,36 @@ fn test_compare_means_equal() {
     assert!(result.p_value > 0.99);
     assert!((result.cohens_d).abs() < 1e-10);
 }
+
+// ─── Tree ensemble classifiers ──────────────────────────────────────────────
+
+#[test]
+fn test_tree_classifier_probabilities_in_binary_diagnostics() {
+    // Treatment assignment depends on x1 through a logistic propensity score.
+    let mut rng = GreenersRng::new(Some(2024));
+    let n = 300;
+    let mut x = Array2::zeros((n, 2));
+    let mut d = Array1::zeros(n);
+    for i in 0..n {
+        x[[i, 0]] = rng.normal();
+        x[[i, 1]] = rng.normal();
+        let p = 1.0 / (1.0 + (-2.0 * x[[i, 0]]).exp());
+        d[i] = if rng.uniform() < p { 1.0 } else { 0.0 };
+    }
+    let d_vec = d.to_vec();
+
+    let rf = RandomForestClassifier::fit(&d, &x, 50, 4, None, None, None, None).unwrap();
+    let gb =
+        GradientBoostingClassifier::fit(&d, &x, 50, Some(0.1), Some(2), None, None, None, None)
+            .unwrap();
+    for probs in [
+        rf.oob_proba.column(1).to_vec(),
+        gb.fitted_proba.column(1).to_vec(),
+    ] {
+        let roc = BinaryDiagnostics::roc(&d_vec, &probs).unwrap();
+        assert!(roc.auc > 0.75 && roc.auc <= 1.0, "auc = {}", roc.auc);
+        let hl = BinaryDiagnostics::hosmer_lemeshow(&d_vec, &probs, 10).unwrap();
+        assert!(hl.hl_stat.is_finite());
+        assert!((0.0..=1.0).contains(&hl.p_value));
+    }
+}
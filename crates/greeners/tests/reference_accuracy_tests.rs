@@ -464,7 +464,7 @@ fn test_quantile_median_vs_statsmodels() {
     let x = Array2::from_shape_vec((n, 3), x_flat).unwrap();
 
     // Median regression (tau=0.50) — point estimates should match closely
-    let result = QuantileReg::fit(&y, &x, 0.50, 200).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.50, 200, None).unwrap();
 
     let names = ["const", "x1", "x2"];
     for (i, name) in names.iter().enumerate() {
@@ -489,7 +489,7 @@ fn test_quantile_025_vs_statsmodels() {
     }
     let x = Array2::from_shape_vec((n, 3), x_flat).unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.25, 200).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.25, 200, None).unwrap();
 
     let names = ["const", "x1", "x2"];
     for (i, name) in names.iter().enumerate() {
@@ -514,7 +514,7 @@ fn test_quantile_075_vs_statsmodels() {
     }
     let x = Array2::from_shape_vec((n, 3), x_flat).unwrap();
 
-    let result = QuantileReg::fit(&y, &x, 0.75, 200).unwrap();
+    let result = QuantileReg::fit(&y, &x, 0.75, 200, None).unwrap();
 
     let names = ["const", "x1", "x2"];
     for (i, name) in names.iter().enumerate() {
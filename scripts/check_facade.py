@@ -25,19 +25,20 @@ BASE = {
     "BayesMixedGLM", "BetaLink", "BetaModel", "BetweenEstimator",
     "BinaryDiagnostics", "Bootstrap", "CUSUMTest", "CanCorr", "CategoricalColumn",
     "Column", "ColumnType", "ConditionalLogit", "ConditionalMNLogit",
-    "ConditionalPoisson", "CorrStructure", "CovarianceType", "CoxPH", "DataFrame",
-    "DataType", "Datasets", "Decomposition", "DescrStatsW", "Diagnostics",
+    "ConditionalPoisson", "CorrStructure", "CovarianceType", "CoxPH", "CrossValidation", "DataFrame",
+    "DataType", "Datasets", "Decomposition", "DescrStatsW", "DesignInfo", "Diagnostics",
     "DiffInDiff", "Equation", "ExponentialSmoothing", "FGLS", "FactorAnalysis",
-    "Family", "FixedEffects", "Formula", "GEE", "GLM", "GLMGam", "GLSAR", "GMM",
-    "GarchDist", "GarchModelType", "GenPoisson", "GreenersError", "HausmanTest",
+    "Family", "FittedModel", "FixedEffects", "Formula", "GEE", "GLM", "GLMGam", "GLSAR", "GMM",
+    "GarchDist", "GarchModelType", "GenPoisson", "GreenersError", "GreenersRng",
+    "HausmanTest",
     "HypothesisTest", "IV", "InferenceType", "Influence", "KDEMultivariate",
     "KDEUnivariate", "KalmanFilter", "KalmanSmoother", "KaplanMeier", "Kernel",
     "KernelReg", "Link", "Logit", "Lowess", "MANOVA", "MICE", "MNLogit",
-    "MarkovSwitching", "MixedLM", "ModelSelection", "ModelSummary", "NegBin",
+    "MarkovSwitching", "MissingPolicy", "MixedLM", "Model", "ModelSelection", "ModelSummary", "NegBin",
     "NegBinP", "NominalGEE", "OLS", "OlsResult", "OrderedLogit", "OrderedProbit",
-    "OrdinalGEE", "PCA", "PanelDiagnostics", "PanelThreshold", "Poisson",
+    "OrdinalGEE", "PCA", "PanelDiagnostics", "PanelThreshold", "Persist", "Poisson",
     "Probit", "QuantileReg", "RLM", "RandomEffects", "RecursiveLS", "RobustNorm",
-    "RollingOLS", "RollingWLS", "Rotation", "SUR", "SVAR", "SVarIdentification",
+    "RollingOLS", "RollingWLS", "Rotation", "RowMap", "SUR", "SVAR", "SVarIdentification",
     "SpecificationTests", "StateSpaceModel", "Stats", "SummaryCol", "SummaryStats",
     "SurEquation", "ThreeSLS", "TimeSeries", "TypeInferenceConfig", "VAR",
     "VARMA", "VECM", "WLS", "ZINB", "ZIP", "state_space_estimate",